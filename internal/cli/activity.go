@@ -0,0 +1,201 @@
+package cli
+
+import (
+	"bytes"
+	"errors"
+	"fmt"
+	"io"
+	"regexp"
+	"strings"
+	"sync"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// errBudgetExhausted is the cancellation cause of an attempt stopped for
+// exceeding its turn, tool-call, or token limit.
+var errBudgetExhausted = errors.New("agent budget exhausted")
+
+// errAgentDone is the cancellation cause of an attempt stopped because the
+// agent printed a line matching its done_pattern.
+var errAgentDone = errors.New("agent reported completion")
+
+// maxActivityLine bounds how much of an unterminated output line is buffered
+// before it is counted anyway.
+const maxActivityLine = 64 << 10
+
+// activityLimits caps what an agent may do in a single attempt. Zero means
+// unlimited.
+type activityLimits struct {
+	MaxTurns     int
+	MaxToolCalls int
+	MaxTokens    int64
+}
+
+func (l activityLimits) enabled() bool {
+	return l.MaxTurns > 0 || l.MaxToolCalls > 0 || l.MaxTokens > 0
+}
+
+func (l activityLimits) String() string {
+	var parts []string
+	if l.MaxTurns > 0 {
+		parts = append(parts, fmt.Sprintf("%d turns", l.MaxTurns))
+	}
+	if l.MaxToolCalls > 0 {
+		parts = append(parts, fmt.Sprintf("%d tool calls", l.MaxToolCalls))
+	}
+	if l.MaxTokens > 0 {
+		parts = append(parts, formatCount(l.MaxTokens)+" tokens")
+	}
+	return strings.Join(parts, ", ")
+}
+
+// resolveActivityLimits returns the eval flag limits, falling back to
+// [harness] max_turns, max_tool_calls, and max_tokens_per_attempt.
+func resolveActivityLimits() activityLimits {
+	l := activityLimits{MaxTurns: evalMaxTurns, MaxToolCalls: evalMaxToolCalls, MaxTokens: evalMaxTokens}
+	if cfg != nil {
+		if l.MaxTurns == 0 {
+			l.MaxTurns = cfg.Harness.MaxTurns
+		}
+		if l.MaxToolCalls == 0 {
+			l.MaxToolCalls = cfg.Harness.MaxToolCalls
+		}
+		if l.MaxTokens == 0 {
+			l.MaxTokens = cfg.Harness.MaxTokensPerAttempt
+		}
+	}
+	return l
+}
+
+// activityCounter counts agent turns and tool calls in streamed agent output
+// while passing it through to out. Turns are only observable for agents with
+// a turn_pattern. Tool calls use the agent's tool_call_pattern, falling back
+// to the shell command lines that behavior telemetry already recognizes.
+// Tokens are the input and output tokens usage reads from each line, for
+// agents whose adapter reports usage as it goes. A line matching the agent's
+// done_pattern calls onDone once.
+type activityCounter struct {
+	out       io.Writer
+	limits    activityLimits
+	turnRe    *regexp.Regexp
+	toolRe    *regexp.Regexp
+	doneRe    *regexp.Regexp
+	usage     func(agentLog []byte) *TokenUsage
+	onExceed  func(reason string)
+	onDone    func()
+	mu        sync.Mutex
+	pending   []byte
+	turns     int
+	toolCalls int
+	tokens    int64
+	exceeded  string
+	done      bool
+}
+
+// newActivityCounter returns a counter for agentCfg's output. onExceed is
+// called once, from a Write, when a limit is first crossed.
+func newActivityCounter(out io.Writer, limits activityLimits, agentCfg *config.AgentConfig, onExceed func(reason string)) *activityCounter {
+	c := &activityCounter{out: out, limits: limits, onExceed: onExceed}
+	// Patterns were validated when the config was loaded.
+	if agentCfg.TurnPattern != "" {
+		c.turnRe = regexp.MustCompile(agentCfg.TurnPattern)
+	}
+	if agentCfg.ToolCallPattern != "" {
+		c.toolRe = regexp.MustCompile(agentCfg.ToolCallPattern)
+	}
+	if agentCfg.DonePattern != "" {
+		c.doneRe = regexp.MustCompile(agentCfg.DonePattern)
+	}
+	return c
+}
+
+func (c *activityCounter) Write(p []byte) (int, error) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	n, err := c.out.Write(p)
+	c.pending = append(c.pending, p[:n]...)
+	for {
+		i := bytes.IndexByte(c.pending, '\n')
+		if i < 0 {
+			break
+		}
+		c.countLine(string(c.pending[:i]))
+		c.pending = c.pending[i+1:]
+	}
+	if len(c.pending) > maxActivityLine {
+		c.countLine(string(c.pending))
+		c.pending = c.pending[:0]
+	}
+	return n, err
+}
+
+// Flush counts a final unterminated line.
+func (c *activityCounter) Flush() {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	if len(c.pending) > 0 {
+		c.countLine(string(c.pending))
+		c.pending = c.pending[:0]
+	}
+}
+
+func (c *activityCounter) countLine(line string) {
+	line = strings.TrimSpace(ansiEscapePattern.ReplaceAllString(line, ""))
+	if line == "" {
+		return
+	}
+	if c.turnRe != nil && c.turnRe.MatchString(line) {
+		c.turns++
+	}
+	if c.toolRe != nil {
+		if c.toolRe.MatchString(line) {
+			c.toolCalls++
+		}
+	} else if len(extractCommandLines([]string{line})) > 0 {
+		c.toolCalls++
+	}
+	if c.usage != nil {
+		if u := c.usage([]byte(line)); u != nil {
+			c.tokens += u.InputTokens + u.OutputTokens
+		}
+	}
+	if !c.done && c.doneRe != nil && c.doneRe.MatchString(line) {
+		c.done = true
+		if c.onDone != nil {
+			c.onDone()
+		}
+	}
+
+	if c.exceeded != "" {
+		return
+	}
+	switch {
+	case c.limits.MaxTurns > 0 && c.turns > c.limits.MaxTurns:
+		c.exceeded = fmt.Sprintf("max_turns=%d", c.limits.MaxTurns)
+	case c.limits.MaxToolCalls > 0 && c.toolCalls > c.limits.MaxToolCalls:
+		c.exceeded = fmt.Sprintf("max_tool_calls=%d", c.limits.MaxToolCalls)
+	case c.limits.MaxTokens > 0 && c.tokens > c.limits.MaxTokens:
+		c.exceeded = fmt.Sprintf("max_tokens_per_attempt=%d", c.limits.MaxTokens)
+	default:
+		return
+	}
+	if c.onExceed != nil {
+		c.onExceed(c.exceeded)
+	}
+}
+
+// Counts returns the turns and tool calls seen so far and the limit that was
+// exceeded, if any.
+func (c *activityCounter) Counts() (turns, toolCalls int, exceeded string) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.turns, c.toolCalls, c.exceeded
+}
+
+// Tokens returns the input and output tokens reported so far.
+func (c *activityCounter) Tokens() int64 {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+	return c.tokens
+}
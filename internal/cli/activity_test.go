@@ -0,0 +1,113 @@
+package cli
+
+import (
+	"bytes"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestActivityCounterPatterns(t *testing.T) {
+	t.Parallel()
+
+	var out bytes.Buffer
+	var reasons []string
+	agentCfg := &config.AgentConfig{TurnPattern: `^> assistant`, ToolCallPattern: `^\[tool\] `}
+	c := newActivityCounter(&out, activityLimits{MaxToolCalls: 2}, agentCfg, func(reason string) {
+		reasons = append(reasons, reason)
+	})
+
+	input := "> assistant\n[tool] read main.go\n\x1b[32m[tool] edit main.go\x1b[0m\n> assistant\n[tool] go te"
+	for _, chunk := range []string{input[:7], input[7:40], input[40:]} {
+		if _, err := c.Write([]byte(chunk)); err != nil {
+			t.Fatal(err)
+		}
+	}
+	if out.String() != input {
+		t.Fatalf("output not passed through: %q", out.String())
+	}
+	if turns, calls, exceeded := c.Counts(); turns != 2 || calls != 2 || exceeded != "" {
+		t.Fatalf("Counts() = %d, %d, %q before flush", turns, calls, exceeded)
+	}
+
+	c.Flush()
+	turns, calls, exceeded := c.Counts()
+	if turns != 2 || calls != 3 || exceeded != "max_tool_calls=2" {
+		t.Fatalf("Counts() = %d, %d, %q after flush", turns, calls, exceeded)
+	}
+	if len(reasons) != 1 {
+		t.Fatalf("onExceed called %d times, want 1", len(reasons))
+	}
+}
+
+func TestActivityCounterCommandFallback(t *testing.T) {
+	t.Parallel()
+
+	var reasons []string
+	c := newActivityCounter(&bytes.Buffer{}, activityLimits{MaxTurns: 1, MaxToolCalls: 5}, &config.AgentConfig{}, func(reason string) {
+		reasons = append(reasons, reason)
+	})
+	_, _ = c.Write([]byte("thinking...\n$ go test ./...\n/usr/bin/bash -lc 'ls -la'\nplain text\n"))
+
+	turns, calls, exceeded := c.Counts()
+	if turns != 0 || calls != 2 || exceeded != "" || len(reasons) != 0 {
+		t.Fatalf("Counts() = %d, %d, %q (reasons %v)", turns, calls, exceeded, reasons)
+	}
+}
+
+func TestActivityCounterDonePattern(t *testing.T) {
+	t.Parallel()
+
+	done := 0
+	c := newActivityCounter(&bytes.Buffer{}, activityLimits{}, &config.AgentConfig{DonePattern: `^=== DONE ===$`}, nil)
+	c.onDone = func() { done++ }
+	_, _ = c.Write([]byte("working\n\x1b[1m=== DONE ===\x1b[0m\n=== DONE ===\n"))
+
+	if done != 1 {
+		t.Fatalf("onDone called %d times, want 1", done)
+	}
+}
+
+func TestActivityCounterTokenLimit(t *testing.T) {
+	t.Parallel()
+
+	var reasons []string
+	c := newActivityCounter(&bytes.Buffer{}, activityLimits{MaxTokens: 1500}, &config.AgentConfig{}, func(reason string) {
+		reasons = append(reasons, reason)
+	})
+	c.usage = parseCodexUsage
+	turn := `{"type":"turn.completed","usage":{"input_tokens":600,"cached_input_tokens":0,"output_tokens":150}}` + "\n"
+	_, _ = c.Write([]byte(turn + `{"type":"item.completed","item":{"type":"agent_message","text":"hi"}}` + "\n" + turn))
+	if _, _, exceeded := c.Counts(); exceeded != "" || c.Tokens() != 1500 {
+		t.Fatalf("at the limit: exceeded = %q, tokens = %d", exceeded, c.Tokens())
+	}
+
+	_, _ = c.Write([]byte(turn))
+	if _, _, exceeded := c.Counts(); exceeded != "max_tokens_per_attempt=1500" || len(reasons) != 1 {
+		t.Fatalf("over the limit: exceeded = %q (reasons %v)", exceeded, reasons)
+	}
+}
+
+func TestClassifyAttemptBudgetExhausted(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	logPath := filepath.Join(dir, "agent.log")
+	if err := os.WriteFile(logPath, []byte("$ go test ./...\nFAIL\n$ go test ./...\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	var quota, infra, timeouts int
+	var result agentExecutionResult
+	decision := classifyAttempt(agentAttemptResult{budgetExhausted: true}, logPath, dir, time.Now(),
+		&quota, &infra, &timeouts, &result)
+	if !decision.done {
+		t.Fatal("budget exhaustion should not be retried")
+	}
+	if !result.budgetExhausted || result.failureClass != FailureClassBudgetExhausted || result.infraFailure {
+		t.Fatalf("unexpected result: %+v", result)
+	}
+}
@@ -0,0 +1,193 @@
+package cli
+
+import (
+	"io/fs"
+	"maps"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"slices"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// agentAdapter is what a built-in agent needs beyond its [agents] config.
+// Adapters are keyed by agent name, so an [agents.<name>] override keeps the
+// built-in adapter.
+type agentAdapter struct {
+	// args returns arguments appended to the configured ones for an attempt
+	// in workspaceDir.
+	args func(workspaceDir string) ([]string, error)
+	// model splits a --model value into the model passed with model_flag and
+	// arguments appended after the configured ones, such as a provider.
+	model func(model string) (string, []string)
+	// artifacts are workspace globs of agent state that are removed after
+	// each attempt, so they are neither audited nor validated.
+	artifacts []string
+	// env is set on every attempt, even when [agents.<name>] replaces the
+	// built-in config.
+	env map[string]string
+	// sandboxEnv is set only when the agent runs in the bwrap sandbox.
+	sandboxEnv map[string]string
+	// usage reads the token usage the agent reported from its log.
+	usage func(agentLog []byte) *TokenUsage
+	// finalMessage reads the agent's last message from its log.
+	finalMessage func(agentLog []byte) string
+	// mcp returns the arguments and environment that give the agent the
+	// --mcp-servers servers. Agents without it cannot be given servers.
+	mcp func(servers []mcpServer) ([]string, map[string]string)
+	// samplingEnv maps the sampling parameters the agent reads from its
+	// environment to their variables.
+	samplingEnv map[string]string
+}
+
+// agentAdapters holds the adapters of built-in agents.
+var agentAdapters = map[string]agentAdapter{
+	"aider": {args: aiderFileArgs, artifacts: []string{".aider*"}},
+	// Claude Code refuses --dangerously-skip-permissions as root unless told
+	// it is sandboxed.
+	"claude": {sandboxEnv: map[string]string{"IS_SANDBOX": "1"}, usage: parseClaudeUsage, finalMessage: claudeFinalMessage, mcp: claudeMCPArgs},
+	"codex":  {usage: parseCodexUsage, finalMessage: codexFinalMessage, mcp: codexMCPArgs},
+	// Gemini CLI's own sandbox would run it in a container that cannot see
+	// the workspace from inside bwrap; the harness sandbox replaces it. The
+	// variable overrides the sandbox setting in its settings.json.
+	"gemini": {env: map[string]string{"GEMINI_SANDBOX": "false"}},
+	"goose":    {model: gooseModel, samplingEnv: map[string]string{config.SamplingTemperature: "GOOSE_TEMPERATURE"}},
+	"omo":      {mcp: openCodeMCPEnv},
+	"opencode": {mcp: openCodeMCPEnv},
+}
+
+// gooseProviders are the goose providers a --model value may be prefixed
+// with, as in "openrouter/qwen/qwen3-coder".
+var gooseProviders = []string{
+	"anthropic", "aws_bedrock", "azure_openai", "databricks", "gcp_vertex_ai", "github_copilot",
+	"google", "groq", "litellm", "ollama", "openai", "openrouter", "snowflake", "venice", "xai",
+}
+
+// pluginAdapter reads what plugin agents report through their events.
+var pluginAdapter = agentAdapter{usage: parsePluginUsage, finalMessage: pluginFinalMessage}
+
+// adapterFor returns the adapter for agent: the plugin adapter for a plugin
+// agent, otherwise its built-in adapter, if any.
+func adapterFor(agent string, agentCfg *config.AgentConfig) agentAdapter {
+	if agentCfg != nil && agentCfg.Protocol == config.AgentProtocolPlugin {
+		return pluginAdapter
+	}
+	return agentAdapters[agent]
+}
+
+// extraArgs returns the adapter's arguments for an attempt in workspaceDir.
+func (a agentAdapter) extraArgs(workspaceDir string) ([]string, error) {
+	if a.args == nil {
+		return nil, nil
+	}
+	return a.args(workspaceDir)
+}
+
+// splitModel returns the model to pass with model_flag and any arguments the
+// adapter derives from it.
+func (a agentAdapter) splitModel(model string) (string, []string) {
+	if a.model == nil || model == "" {
+		return model, nil
+	}
+	return a.model(model)
+}
+
+// removeArtifacts deletes the adapter's artifacts from workspaceDir.
+func (a agentAdapter) removeArtifacts(workspaceDir string) {
+	for _, pattern := range a.artifacts {
+		matches, _ := filepath.Glob(filepath.Join(workspaceDir, pattern))
+		for _, path := range matches {
+			_ = os.RemoveAll(path)
+		}
+	}
+}
+
+// environ adds the adapter's variables to env, which is nil when the agent
+// inherits the harness environment. They come last, so they win over
+// [agents] env and the harness environment.
+func (a agentAdapter) environ(env []string, sandboxed bool) []string {
+	env = appendEnv(env, a.env)
+	if sandboxed {
+		env = appendEnv(env, a.sandboxEnv)
+	}
+	return env
+}
+
+// provisionMCP adds the arguments and environment that give the agent
+// servers, with {workspace} expanded to workspaceDir.
+func (a agentAdapter) provisionMCP(cmd *exec.Cmd, servers []mcpServer, workspaceDir string) {
+	if a.mcp == nil || len(servers) == 0 {
+		return
+	}
+	args, env := a.mcp(mcpServersFor(servers, workspaceDir))
+	cmd.Args = append(cmd.Args, args...)
+	cmd.Env = appendEnv(cmd.Env, env)
+}
+
+// provisionSampling adds the arguments and environment that pass p to the
+// agent.
+func (a agentAdapter) provisionSampling(cmd *exec.Cmd, agentCfg *config.AgentConfig, p samplingParams) {
+	args, env := samplingArgs(agentCfg, a, p)
+	cmd.Args = append(cmd.Args, args...)
+	cmd.Env = appendEnv(cmd.Env, env)
+}
+
+// appendEnv appends vars to env in sorted order. A nil env stands for the
+// harness environment, which is copied first when there is anything to add.
+func appendEnv(env []string, vars map[string]string) []string {
+	if len(vars) == 0 {
+		return env
+	}
+	if env == nil {
+		env = os.Environ()
+	}
+	for _, k := range slices.Sorted(maps.Keys(vars)) {
+		env = append(env, k+"="+vars[k])
+	}
+	return env
+}
+
+// aiderFileArgs lists the workspace files for aider to add to the chat.
+// Without a git repo aider only edits files it is given, so every file
+// outside hidden directories is passed, relative to the workspace.
+func aiderFileArgs(workspaceDir string) ([]string, error) {
+	var files []string
+	err := filepath.WalkDir(workspaceDir, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if path == workspaceDir {
+			return nil
+		}
+		if strings.HasPrefix(d.Name(), ".") {
+			if d.IsDir() {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		rel, err := filepath.Rel(workspaceDir, path)
+		if err != nil {
+			return err
+		}
+		files = append(files, rel)
+		return nil
+	})
+	return files, err
+}
+
+// gooseModel turns "<provider>/<model>" into --provider, so a run can switch
+// providers without editing goose's config.yaml. Other values, including
+// model names that contain a slash, are passed through unchanged and goose
+// uses its configured provider (or GOOSE_PROVIDER).
+func gooseModel(model string) (string, []string) {
+	provider, name, ok := strings.Cut(model, "/")
+	if !ok || name == "" || !slices.Contains(gooseProviders, provider) {
+		return model, nil
+	}
+	return name, []string{"--provider", provider}
+}
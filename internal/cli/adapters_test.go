@@ -0,0 +1,90 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+)
+
+func TestAiderAdapter(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	for _, name := range []string{"main.go", "pkg/util.go", ".agents/skills/x.md", ".hidden", ".aider.tags.cache.v4/cache.db", ".aider.chat.history.md"} {
+		path := filepath.Join(dir, name)
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte("x"), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	adapter := agentAdapters["aider"]
+	args, err := adapter.extraArgs(dir)
+	if err != nil {
+		t.Fatalf("extraArgs() error = %v", err)
+	}
+	if want := []string{"main.go", filepath.Join("pkg", "util.go")}; !slices.Equal(args, want) {
+		t.Errorf("extraArgs() = %q, want %q", args, want)
+	}
+
+	adapter.removeArtifacts(dir)
+	for _, name := range []string{".aider.tags.cache.v4", ".aider.chat.history.md"} {
+		if _, err := os.Stat(filepath.Join(dir, name)); !os.IsNotExist(err) {
+			t.Errorf("%s not removed (stat error %v)", name, err)
+		}
+	}
+	if _, err := os.Stat(filepath.Join(dir, ".hidden")); err != nil {
+		t.Errorf(".hidden was removed: %v", err)
+	}
+}
+
+func TestAgentAdapterZeroValue(t *testing.T) {
+	t.Parallel()
+
+	var adapter agentAdapter
+	if args, err := adapter.extraArgs(t.TempDir()); args != nil || err != nil {
+		t.Errorf("extraArgs() = %v, %v, want nothing", args, err)
+	}
+}
+
+func TestAgentAdapterEnviron(t *testing.T) {
+	t.Parallel()
+
+	adapter := agentAdapter{env: map[string]string{"B": "2", "A": "1"}, sandboxEnv: map[string]string{"S": "1"}}
+	base := []string{"PATH=/bin", "A=0"}
+	if got, want := adapter.environ(base, false), []string{"PATH=/bin", "A=0", "A=1", "B=2"}; !slices.Equal(got, want) {
+		t.Errorf("environ(unsandboxed) = %q, want %q", got, want)
+	}
+	if got := adapter.environ([]string{"PATH=/bin"}, true); !slices.Contains(got, "S=1") {
+		t.Errorf("environ(sandboxed) = %q, want S=1", got)
+	}
+	if got := adapter.environ(nil, false); !slices.Contains(got, "A=1") {
+		t.Errorf("environ(nil) = %q, want the harness environment plus A=1", got)
+	}
+	if got := (agentAdapter{}).environ(nil, true); got != nil {
+		t.Errorf("zero adapter environ(nil) = %q, want nil to inherit", got)
+	}
+}
+
+func TestGooseModel(t *testing.T) {
+	t.Parallel()
+
+	for _, tc := range []struct {
+		in, model string
+		args      []string
+	}{
+		{"openrouter/qwen/qwen3-coder", "qwen/qwen3-coder", []string{"--provider", "openrouter"}},
+		{"ollama/qwen3:8b", "qwen3:8b", []string{"--provider", "ollama"}},
+		{"qwen/qwen3-coder", "qwen/qwen3-coder", nil},
+		{"gpt-5", "gpt-5", nil},
+		{"openai/", "openai/", nil},
+	} {
+		model, args := agentAdapters["goose"].splitModel(tc.in)
+		if model != tc.model || !slices.Equal(args, tc.args) {
+			t.Errorf("splitModel(%q) = %q, %q, want %q, %q", tc.in, model, args, tc.model, tc.args)
+		}
+	}
+}
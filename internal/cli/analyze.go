@@ -0,0 +1,1076 @@
+package cli
+
+import (
+	"encoding/csv"
+	"encoding/json"
+	"fmt"
+	"io"
+	"math"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/spf13/cobra"
+)
+
+var (
+	analyzeJSON        bool
+	analyzeBy          string
+	analyzeCSV         bool
+	analyzeCosts       []string
+	analyzeCostPerHour float64
+)
+
+// Model size buckets used when grouping results by parameter count.
+const (
+	sizeBucketSmall   = "<8B"
+	sizeBucketMedium  = "8–48B"
+	sizeBucketLarge   = "48–120B"
+	sizeBucketXLarge  = ">120B"
+	sizeBucketUnknown = "unknown"
+)
+
+var sizeBucketOrder = []string{sizeBucketSmall, sizeBucketMedium, sizeBucketLarge, sizeBucketXLarge, sizeBucketUnknown}
+
+var (
+	modelParamsPattern = regexp.MustCompile(`(?i)^(?:(\d+)x)?(\d+(?:\.\d+)?)([bmt])$`)
+	modelNameParams    = regexp.MustCompile(`(?i)(?:^|[^a-z0-9.])((?:\d+x)?\d+(?:\.\d+)?[bmt])(?:$|[^a-z0-9])`)
+	modelFamilyTrim    = regexp.MustCompile(`[0-9.]+$`)
+	modelNameQuant     = regexp.MustCompile(`(?i)(?:^|[^a-z0-9])(i?q\d(?:_[a-z0-9]+)*|b?f16|fp(?:8|16|32)|f32|int[48]|nf4|awq|gptq|\d+-?bit)(?:$|[^a-z0-9])`)
+	leadingDigits      = regexp.MustCompile(`\d+`)
+)
+
+// GroupStats aggregates eval runs that share a grouping key.
+type GroupStats struct {
+	Group                string   `json:"group"`
+	Runs                 int      `json:"runs"`
+	Models               []string `json:"models"`
+	MeanPassRate         float64  `json:"mean_pass_rate"`
+	MeanWeightedPassRate float64  `json:"mean_weighted_pass_rate"`
+	BestWeightedPassRate float64  `json:"best_weighted_pass_rate"`
+	BestModel            string   `json:"best_model"`
+	MeanDuration         float64  `json:"mean_duration_seconds"`
+}
+
+var analyzeCmd = &cobra.Command{
+	Use:   "analyze",
+	Short: "Analyze results across multiple eval runs",
+	Long: `Aggregates summary.json files from one or more eval result directories.
+
+Directories are searched recursively, so multi-run umbrella directories and
+the whole eval-results/ tree can be passed directly.`,
+}
+
+var analyzeScaleCmd = &cobra.Command{
+	Use:   "scale <dir> [dir...]",
+	Short: "Aggregate scores by model size bucket or model family",
+	Long: `Groups eval runs by model parameter count (<8B, 8–48B, 48–120B, >120B) or by
+model family, and reports mean and best scores per group.
+
+Model metadata comes from --model-family/--model-params at eval time. For runs
+without it, family and size are inferred from the model name where possible
+(e.g. qwen3-coder-30b → qwen, 30B).`,
+	Example: `  sanity analyze scale eval-results/
+  sanity analyze scale eval-results/ --by family
+  sanity analyze scale eval-results/*-opencode --json`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		summaries, _, err := loadRunSummaries(args)
+		if err != nil {
+			return err
+		}
+
+		var groups []GroupStats
+		switch analyzeBy {
+		case "size":
+			groups = aggregateBySize(summaries)
+		case "family":
+			groups = aggregateByFamily(summaries)
+		default:
+			return fmt.Errorf("invalid --by %q (valid: size, family)", analyzeBy)
+		}
+
+		if analyzeJSON {
+			data, _ := json.MarshalIndent(groups, "", "  ")
+			fmt.Println(string(data))
+			return nil
+		}
+		fmt.Print(buildGroupReport(analyzeBy, groups))
+		return nil
+	},
+}
+
+func init() {
+	analyzeCmd.PersistentFlags().BoolVar(&analyzeJSON, "json", false, "output as JSON")
+	analyzeScaleCmd.Flags().StringVar(&analyzeBy, "by", "size", "grouping: size or family")
+	analyzeCmd.AddCommand(analyzeScaleCmd)
+	analyzeParetoCmd.Flags().BoolVar(&analyzeCSV, "csv", false, "output as CSV")
+	analyzeParetoCmd.Flags().StringArrayVar(&analyzeCosts, "cost", nil, "total cost in USD for a run ID or model, e.g. gpt-5=4.20 (repeatable)")
+	analyzeParetoCmd.Flags().Float64Var(&analyzeCostPerHour, "cost-per-hour", 0, "estimate cost from agent time at this USD/hour rate for runs without --cost")
+	analyzeCmd.AddCommand(analyzeLanguagesCmd)
+	analyzeCmd.AddCommand(analyzeParetoCmd)
+	analyzeScalingCmd.Flags().BoolVar(&analyzeCSV, "csv", false, "output the dataset as CSV")
+	analyzeCmd.AddCommand(analyzeScalingCmd)
+	analyzeSimilarityCmd.Flags().Float64Var(&analyzeSimilarityThreshold, "threshold", 0.6, "similarity (0-1] at or above which two solutions are linked")
+	analyzeCmd.AddCommand(analyzeSimilarityCmd)
+}
+
+// loadRunSummaries loads every summary.json found under the given directories.
+// The returned run directories are parallel to the summaries.
+func loadRunSummaries(dirs []string) ([]EvalSummary, []string, error) {
+	var summaries []EvalSummary
+	var runDirs []string
+	for _, dir := range dirs {
+		found, err := findRunDirs(dir)
+		if err != nil {
+			return nil, nil, err
+		}
+		for _, runDir := range found {
+			s, err := loadSummaryFromDir(runDir)
+			if err != nil {
+				return nil, nil, fmt.Errorf("loading summary from %s: %w", runDir, err)
+			}
+			summaries = append(summaries, *s)
+			runDirs = append(runDirs, runDir)
+		}
+	}
+	if len(summaries) == 0 {
+		return nil, nil, fmt.Errorf("no summary.json found under %v", dirs)
+	}
+	return summaries, runDirs, nil
+}
+
+// parseModelParams parses a parameter count such as "7B", "1.5b", "500M",
+// "1T", or "8x7B" into billions. An empty string yields 0.
+func parseModelParams(s string) (float64, error) {
+	s = strings.TrimSpace(s)
+	if s == "" {
+		return 0, nil
+	}
+	m := modelParamsPattern.FindStringSubmatch(s)
+	if m == nil {
+		return 0, fmt.Errorf("invalid --model-params %q (expected e.g. 7B, 500M, 8x7B)", s)
+	}
+	n, err := strconv.ParseFloat(m[2], 64)
+	if err != nil {
+		return 0, fmt.Errorf("invalid --model-params %q: %w", s, err)
+	}
+	if m[1] != "" {
+		experts, _ := strconv.Atoi(m[1])
+		n *= float64(experts)
+	}
+	switch strings.ToLower(m[3]) {
+	case "m":
+		n /= 1000
+	case "t":
+		n *= 1000
+	}
+	return n, nil
+}
+
+// inferModelParams extracts a parameter count from a model name such as
+// "qwen3-coder-30b-a3b" or "llama3.1:8b". Returns 0 if none is found.
+func inferModelParams(model string) float64 {
+	m := modelNameParams.FindStringSubmatch(model)
+	if m == nil {
+		return 0
+	}
+	n, err := parseModelParams(m[1])
+	if err != nil {
+		return 0
+	}
+	return n
+}
+
+// inferModelFamily derives a family name from a model name by taking the first
+// name component without provider prefix or version digits,
+// e.g. "openrouter/qwen3-coder-30b" → "qwen".
+func inferModelFamily(model string) string {
+	name := strings.ToLower(model)
+	if i := strings.LastIndex(name, "/"); i >= 0 {
+		name = name[i+1:]
+	}
+	if name == "" || name == "unknown" {
+		return ""
+	}
+	if i := strings.IndexAny(name, "-_:"); i >= 0 {
+		name = name[:i]
+	}
+	return modelFamilyTrim.ReplaceAllString(name, "")
+}
+
+// resolveModelFamily returns the explicit family if set, otherwise the inferred one.
+func resolveModelFamily(explicit, model string) string {
+	if explicit != "" {
+		return strings.ToLower(explicit)
+	}
+	return inferModelFamily(model)
+}
+
+// resolveModelParams returns the explicit parameter count if set and valid,
+// otherwise the count inferred from the model name.
+func resolveModelParams(explicit, model string) float64 {
+	if n, err := parseModelParams(explicit); err == nil && n > 0 {
+		return n
+	}
+	return inferModelParams(model)
+}
+
+// inferModelQuant extracts a quantization tag such as Q4_K_M, FP8, or AWQ
+// from a model name. Returns "" if none is found.
+func inferModelQuant(model string) string {
+	m := modelNameQuant.FindStringSubmatch(model)
+	if m == nil {
+		return ""
+	}
+	return strings.ToUpper(m[1])
+}
+
+// resolveModelQuant returns the explicit quantization if set, otherwise the inferred one.
+func resolveModelQuant(explicit, model string) string {
+	if q := strings.TrimSpace(explicit); q != "" {
+		return q
+	}
+	return inferModelQuant(model)
+}
+
+// quantBits estimates the weight bit width of a quantization tag, e.g.
+// Q4_K_M → 4, FP8 → 8, BF16 → 16. Returns 0 if unknown.
+func quantBits(quant string) int {
+	q := strings.ToLower(quant)
+	if d := leadingDigits.FindString(q); d != "" {
+		n, _ := strconv.Atoi(d)
+		return n
+	}
+	if q == "awq" || q == "gptq" {
+		return 4
+	}
+	return 0
+}
+
+// sizeBucket maps a parameter count in billions to its size bucket.
+func sizeBucket(paramsB float64) string {
+	switch {
+	case paramsB <= 0:
+		return sizeBucketUnknown
+	case paramsB < 8:
+		return sizeBucketSmall
+	case paramsB < 48:
+		return sizeBucketMedium
+	case paramsB <= 120:
+		return sizeBucketLarge
+	default:
+		return sizeBucketXLarge
+	}
+}
+
+// summaryModelParams returns the recorded parameter count, falling back to the model name.
+func summaryModelParams(s EvalSummary) float64 {
+	if s.ModelParamsB > 0 {
+		return s.ModelParamsB
+	}
+	return inferModelParams(s.Model)
+}
+
+// summaryModelFamily returns the recorded model family, falling back to the model name.
+func summaryModelFamily(s EvalSummary) string {
+	if s.ModelFamily != "" {
+		return s.ModelFamily
+	}
+	if f := inferModelFamily(s.Model); f != "" {
+		return f
+	}
+	return "unknown"
+}
+
+// aggregateBySize groups summaries into parameter-count buckets in ascending size order.
+func aggregateBySize(summaries []EvalSummary) []GroupStats {
+	groups := aggregateSummaries(summaries, func(s EvalSummary) string {
+		return sizeBucket(summaryModelParams(s))
+	})
+	rank := make(map[string]int, len(sizeBucketOrder))
+	for i, b := range sizeBucketOrder {
+		rank[b] = i
+	}
+	sort.Slice(groups, func(i, j int) bool { return rank[groups[i].Group] < rank[groups[j].Group] })
+	return groups
+}
+
+// aggregateByFamily groups summaries by model family, best family first.
+func aggregateByFamily(summaries []EvalSummary) []GroupStats {
+	groups := aggregateSummaries(summaries, summaryModelFamily)
+	sort.Slice(groups, func(i, j int) bool {
+		return groups[i].MeanWeightedPassRate > groups[j].MeanWeightedPassRate
+	})
+	return groups
+}
+
+// aggregateSummaries computes GroupStats for each key returned by keyFn.
+func aggregateSummaries(summaries []EvalSummary, keyFn func(EvalSummary) string) []GroupStats {
+	type acc struct {
+		passRates, weighted, durations []float64
+		models                         map[string]bool
+		best                           float64
+		bestModel                      string
+	}
+	byKey := make(map[string]*acc)
+	for _, s := range summaries {
+		key := keyFn(s)
+		a := byKey[key]
+		if a == nil {
+			a = &acc{models: make(map[string]bool), best: -1}
+			byKey[key] = a
+		}
+		a.passRates = append(a.passRates, s.PassRate)
+		a.weighted = append(a.weighted, s.WeightedPassRate)
+		a.durations = append(a.durations, s.Duration)
+		a.models[s.Model] = true
+		if s.WeightedPassRate > a.best {
+			a.best = s.WeightedPassRate
+			a.bestModel = s.Model
+		}
+	}
+
+	groups := make([]GroupStats, 0, len(byKey))
+	for key, a := range byKey {
+		models := make([]string, 0, len(a.models))
+		for m := range a.models {
+			models = append(models, m)
+		}
+		sort.Strings(models)
+		groups = append(groups, GroupStats{
+			Group:                key,
+			Runs:                 len(a.passRates),
+			Models:               models,
+			MeanPassRate:         mean(a.passRates),
+			MeanWeightedPassRate: mean(a.weighted),
+			BestWeightedPassRate: a.best,
+			BestModel:            a.bestModel,
+			MeanDuration:         mean(a.durations),
+		})
+	}
+	return groups
+}
+
+// buildGroupReport renders grouped stats as a Markdown table.
+func buildGroupReport(by string, groups []GroupStats) string {
+	var sb strings.Builder
+	title := "Model Size"
+	if by == "family" {
+		title = "Model Family"
+	}
+	fmt.Fprintf(&sb, "### Scores by %s\n\n", title)
+	fmt.Fprintf(&sb, "| %s | Runs | Models | Mean Pass Rate | Mean Weighted | Best Weighted | Best Model | Mean Duration |\n", title)
+	fmt.Fprintf(&sb, "|------|------|--------|----------------|---------------|---------------|------------|---------------|\n")
+	for _, g := range groups {
+		fmt.Fprintf(&sb, "| %s | %d | %d | %.1f%% | %.1f%% | %.1f%% | %s | %s |\n",
+			g.Group, g.Runs, len(g.Models), g.MeanPassRate, g.MeanWeightedPassRate,
+			g.BestWeightedPassRate, g.BestModel, formatDuration(g.MeanDuration))
+	}
+	sb.WriteString("\n")
+	return sb.String()
+}
+
+// LanguageConsistency compares one agent/model configuration's performance
+// across the languages it ran.
+type LanguageConsistency struct {
+	Config                  string          `json:"config"`
+	Agent                   string          `json:"agent"`
+	Model                   string          `json:"model"`
+	Runs                    int             `json:"runs"`
+	OverallPassRate         float64         `json:"overall_pass_rate"`
+	OverallWeightedPassRate float64         `json:"overall_weighted_pass_rate"`
+	Languages               []LanguageScore `json:"languages"`
+	Spread                  float64         `json:"spread"`
+	StdDev                  float64         `json:"stddev"`
+	Strongest               string          `json:"strongest"`
+	Weakest                 string          `json:"weakest"`
+}
+
+// LanguageScore is a configuration's score in a single language. Delta is the
+// difference from the configuration's overall weighted pass rate.
+type LanguageScore struct {
+	Language         string                   `json:"language"`
+	Attempts         int                      `json:"attempts"`
+	Passed           int                      `json:"passed"`
+	PassRate         float64                  `json:"pass_rate"`
+	WeightedPassRate float64                  `json:"weighted_pass_rate"`
+	Delta            float64                  `json:"delta"`
+	ByCategory       map[string]CategoryDelta `json:"by_category"`
+}
+
+// CategoryDelta is a language's pass rate within one tier or difficulty,
+// compared with the configuration's pass rate in that category across all
+// languages. Keys are "tier:<name>" or "difficulty:<name>".
+type CategoryDelta struct {
+	Attempts int     `json:"attempts"`
+	Passed   int     `json:"passed"`
+	PassRate float64 `json:"pass_rate"`
+	Delta    float64 `json:"delta"`
+}
+
+var analyzeLanguagesCmd = &cobra.Command{
+	Use:   "languages <dir> [dir...]",
+	Short: "Compare each model's performance across languages",
+	Long: `Reports per-language weighted pass rates for each agent/model configuration,
+with deltas from the configuration's overall score and per-tier and
+per-difficulty deltas, to surface language-specific weaknesses that the
+combined total hides.
+
+Repeated runs of the same configuration are pooled.`,
+	Example: `  sanity analyze languages eval-results/
+  sanity analyze languages eval-results/multi-2026-02-21T024300 --json`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		summaries, _, err := loadRunSummaries(args)
+		if err != nil {
+			return err
+		}
+		reports := computeLanguageConsistency(summaries)
+		if analyzeJSON {
+			data, _ := json.MarshalIndent(reports, "", "  ")
+			fmt.Println(string(data))
+			return nil
+		}
+		fmt.Print(buildLanguageReport(reports))
+		return nil
+	},
+}
+
+// summaryConfigLabel identifies an agent/model/reasoning configuration.
+func summaryConfigLabel(s EvalSummary) string {
+	label := s.Agent + "/" + s.Model
+	if s.Reasoning != "" {
+		label += " (" + s.Reasoning + ")"
+	}
+	return label
+}
+
+// scoreTally accumulates pass counts and weighted scores.
+type scoreTally struct {
+	attempts, passed  int
+	weighted, maxPoss float64
+}
+
+func (t *scoreTally) add(r EvalResult) {
+	t.attempts++
+	if r.Passed {
+		t.passed++
+	}
+	t.weighted += r.WeightedScore
+	t.maxPoss += r.Weight
+}
+
+func (t *scoreTally) passRate() float64 {
+	if t.attempts == 0 {
+		return 0
+	}
+	return float64(t.passed) / float64(t.attempts) * 100
+}
+
+func (t *scoreTally) weightedPassRate() float64 {
+	if t.maxPoss == 0 {
+		return 0
+	}
+	return t.weighted / t.maxPoss * 100
+}
+
+// resultCategories returns the category keys a result belongs to.
+func resultCategories(r EvalResult) []string {
+	var cats []string
+	if r.Tier != "" {
+		cats = append(cats, "tier:"+r.Tier)
+	}
+	if r.Difficulty != "" {
+		cats = append(cats, "difficulty:"+r.Difficulty)
+	}
+	return cats
+}
+
+// computeLanguageConsistency builds a LanguageConsistency per configuration,
+// ordered by label.
+func computeLanguageConsistency(summaries []EvalSummary) []LanguageConsistency {
+	type configAcc struct {
+		agent, model string
+		runs         int
+		overall      scoreTally
+		byCategory   map[string]*scoreTally
+		byLanguage   map[string]*scoreTally
+		byLangCat    map[string]map[string]*scoreTally
+	}
+	tally := func(m map[string]*scoreTally, key string) *scoreTally {
+		if m[key] == nil {
+			m[key] = &scoreTally{}
+		}
+		return m[key]
+	}
+
+	configs := make(map[string]*configAcc)
+	for _, s := range summaries {
+		label := summaryConfigLabel(s)
+		c := configs[label]
+		if c == nil {
+			c = &configAcc{
+				agent: s.Agent, model: s.Model,
+				byCategory: make(map[string]*scoreTally),
+				byLanguage: make(map[string]*scoreTally),
+				byLangCat:  make(map[string]map[string]*scoreTally),
+			}
+			configs[label] = c
+		}
+		c.runs++
+		for _, r := range s.Results {
+			c.overall.add(r)
+			tally(c.byLanguage, r.Language).add(r)
+			if c.byLangCat[r.Language] == nil {
+				c.byLangCat[r.Language] = make(map[string]*scoreTally)
+			}
+			for _, cat := range resultCategories(r) {
+				tally(c.byCategory, cat).add(r)
+				tally(c.byLangCat[r.Language], cat).add(r)
+			}
+		}
+	}
+
+	labels := make([]string, 0, len(configs))
+	for label := range configs {
+		labels = append(labels, label)
+	}
+	sort.Strings(labels)
+
+	reports := make([]LanguageConsistency, 0, len(labels))
+	for _, label := range labels {
+		c := configs[label]
+		lc := LanguageConsistency{
+			Config:                  label,
+			Agent:                   c.agent,
+			Model:                   c.model,
+			Runs:                    c.runs,
+			OverallPassRate:         c.overall.passRate(),
+			OverallWeightedPassRate: c.overall.weightedPassRate(),
+		}
+
+		rates := make([]float64, 0, len(c.byLanguage))
+		for lang, lt := range c.byLanguage {
+			ls := LanguageScore{
+				Language:         lang,
+				Attempts:         lt.attempts,
+				Passed:           lt.passed,
+				PassRate:         lt.passRate(),
+				WeightedPassRate: lt.weightedPassRate(),
+				Delta:            lt.weightedPassRate() - lc.OverallWeightedPassRate,
+				ByCategory:       make(map[string]CategoryDelta),
+			}
+			for cat, ct := range c.byLangCat[lang] {
+				ls.ByCategory[cat] = CategoryDelta{
+					Attempts: ct.attempts,
+					Passed:   ct.passed,
+					PassRate: ct.passRate(),
+					Delta:    ct.passRate() - c.byCategory[cat].passRate(),
+				}
+			}
+			lc.Languages = append(lc.Languages, ls)
+			rates = append(rates, ls.WeightedPassRate)
+		}
+		sort.Slice(lc.Languages, func(i, j int) bool {
+			return lc.Languages[i].WeightedPassRate > lc.Languages[j].WeightedPassRate
+		})
+		if len(lc.Languages) > 0 {
+			lc.Strongest = lc.Languages[0].Language
+			lc.Weakest = lc.Languages[len(lc.Languages)-1].Language
+			lc.Spread = maxVal(rates) - minVal(rates)
+			lc.StdDev = stddev(rates)
+		}
+		reports = append(reports, lc)
+	}
+	return reports
+}
+
+// buildLanguageReport renders language consistency reports as Markdown.
+func buildLanguageReport(reports []LanguageConsistency) string {
+	var sb strings.Builder
+	sb.WriteString("# Cross-Language Consistency\n\n")
+	for _, lc := range reports {
+		fmt.Fprintf(&sb, "## %s\n\n", lc.Config)
+		fmt.Fprintf(&sb, "Runs: %d | Overall: %.1f%% weighted (%.1f%% pass rate) | Spread: %.1f pts | Std Dev: %.1f\n\n",
+			lc.Runs, lc.OverallWeightedPassRate, lc.OverallPassRate, lc.Spread, lc.StdDev)
+		if len(lc.Languages) > 1 {
+			fmt.Fprintf(&sb, "Strongest: **%s** | Weakest: **%s**\n\n", lc.Strongest, lc.Weakest)
+		}
+
+		sb.WriteString("| Language | Passed | Pass Rate | Weighted | Δ Overall | Category Deltas |\n")
+		sb.WriteString("|----------|--------|-----------|----------|-----------|-----------------|\n")
+		for _, ls := range lc.Languages {
+			cats := make([]string, 0, len(ls.ByCategory))
+			for cat := range ls.ByCategory {
+				cats = append(cats, cat)
+			}
+			sort.Strings(cats)
+			parts := make([]string, 0, len(cats))
+			for _, cat := range cats {
+				parts = append(parts, fmt.Sprintf("%s %+.0f", cat, ls.ByCategory[cat].Delta))
+			}
+			fmt.Fprintf(&sb, "| %s | %d/%d | %.1f%% | %.1f%% | %+.1f | %s |\n",
+				ls.Language, ls.Passed, ls.Attempts, ls.PassRate, ls.WeightedPassRate, ls.Delta, strings.Join(parts, ", "))
+		}
+		sb.WriteString("\n")
+	}
+	return sb.String()
+}
+
+// ParetoPoint is one eval run plotted by score against time and cost.
+type ParetoPoint struct {
+	RunID            string  `json:"run_id"`
+	Agent            string  `json:"agent"`
+	Model            string  `json:"model"`
+	WeightedPassRate float64 `json:"weighted_pass_rate"`
+	PassRate         float64 `json:"pass_rate"`
+	Duration         float64 `json:"duration_seconds"`
+	CostUSD          float64 `json:"cost_usd,omitempty"`
+	CostSource       string  `json:"cost_source,omitempty"`
+	TimeFrontier     bool    `json:"time_frontier"`
+	CostFrontier     bool    `json:"cost_frontier"`
+}
+
+// Cost sources recorded on a ParetoPoint.
+const (
+	costSourceExplicit  = "explicit"
+	costSourceEstimated = "estimated"
+)
+
+var analyzeParetoCmd = &cobra.Command{
+	Use:   "pareto <dir> [dir...]",
+	Short: "Report score against cost and time with the Pareto frontier",
+	Long: `Plots each eval run's weighted pass rate against total task time and, when
+known, total cost, and marks the runs on the Pareto frontier: no other run
+scores higher for the same or lower time (or cost).
+
+Cost is not recorded by the harness. Supply it per run ID or model with
+--cost, or estimate it from agent time with --cost-per-hour (useful for
+local models, e.g. GPU rental or power cost).`,
+	Example: `  sanity analyze pareto eval-results/
+  sanity analyze pareto eval-results/ --cost gpt-5=4.20 --cost claude-sonnet-4-5=6.10
+  sanity analyze pareto eval-results/ --cost-per-hour 0.80 --csv > pareto.csv`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		summaries, runDirs, err := loadRunSummaries(args)
+		if err != nil {
+			return err
+		}
+		costs, err := parseCostOverrides(analyzeCosts)
+		if err != nil {
+			return err
+		}
+		points := buildParetoPoints(summaries, runDirs, costs, analyzeCostPerHour)
+
+		switch {
+		case analyzeJSON:
+			data, _ := json.MarshalIndent(points, "", "  ")
+			fmt.Println(string(data))
+			return nil
+		case analyzeCSV:
+			return writeParetoCSV(os.Stdout, points)
+		}
+		fmt.Print(buildParetoReport(points))
+		return nil
+	},
+}
+
+// parseCostOverrides parses "key=USD" pairs into a map.
+func parseCostOverrides(pairs []string) (map[string]float64, error) {
+	costs := make(map[string]float64, len(pairs))
+	for _, pair := range pairs {
+		key, value, ok := strings.Cut(pair, "=")
+		if !ok || strings.TrimSpace(key) == "" {
+			return nil, fmt.Errorf("invalid --cost %q (expected <run-id|model>=<usd>)", pair)
+		}
+		usd, err := strconv.ParseFloat(strings.TrimPrefix(strings.TrimSpace(value), "$"), 64)
+		if err != nil || usd < 0 {
+			return nil, fmt.Errorf("invalid --cost %q (expected <run-id|model>=<usd>)", pair)
+		}
+		costs[strings.TrimSpace(key)] = usd
+	}
+	return costs, nil
+}
+
+// buildParetoPoints creates one point per run, resolves costs, and marks
+// both frontiers. Points are ordered by weighted pass rate, best first.
+func buildParetoPoints(summaries []EvalSummary, runDirs []string, costs map[string]float64, costPerHour float64) []ParetoPoint {
+	points := make([]ParetoPoint, 0, len(summaries))
+	for i, s := range summaries {
+		p := ParetoPoint{
+			RunID:            filepath.Base(runDirs[i]),
+			Agent:            s.Agent,
+			Model:            s.Model,
+			WeightedPassRate: s.WeightedPassRate,
+			PassRate:         s.PassRate,
+			Duration:         s.Duration,
+		}
+		runCost, hasRunCost := costs[p.RunID]
+		modelCost, hasModelCost := costs[s.Model]
+		switch {
+		case hasRunCost:
+			p.CostUSD, p.CostSource = runCost, costSourceExplicit
+		case hasModelCost:
+			p.CostUSD, p.CostSource = modelCost, costSourceExplicit
+		case costPerHour > 0:
+			p.CostUSD, p.CostSource = s.AgentTime/3600*costPerHour, costSourceEstimated
+		}
+		points = append(points, p)
+	}
+
+	timeFrontier := paretoFrontier(points, func(p ParetoPoint) (float64, bool) { return p.Duration, true })
+	costFrontier := paretoFrontier(points, func(p ParetoPoint) (float64, bool) { return p.CostUSD, p.CostSource != "" })
+	for i := range points {
+		points[i].TimeFrontier = timeFrontier[i]
+		points[i].CostFrontier = costFrontier[i]
+	}
+
+	sort.SliceStable(points, func(i, j int) bool { return points[i].WeightedPassRate > points[j].WeightedPassRate })
+	return points
+}
+
+// paretoFrontier reports which points are not dominated, where a point is
+// dominated if another scores at least as high at a lower or equal x. Points
+// for which x reports false are excluded.
+func paretoFrontier(points []ParetoPoint, x func(ParetoPoint) (float64, bool)) []bool {
+	onFrontier := make([]bool, len(points))
+	idx := make([]int, 0, len(points))
+	for i, p := range points {
+		if _, ok := x(p); ok {
+			idx = append(idx, i)
+		}
+	}
+	sort.SliceStable(idx, func(a, b int) bool {
+		xa, _ := x(points[idx[a]])
+		xb, _ := x(points[idx[b]])
+		if xa != xb {
+			return xa < xb
+		}
+		return points[idx[a]].WeightedPassRate > points[idx[b]].WeightedPassRate
+	})
+
+	best := -1.0
+	for _, i := range idx {
+		if points[i].WeightedPassRate > best {
+			onFrontier[i] = true
+			best = points[i].WeightedPassRate
+		}
+	}
+	return onFrontier
+}
+
+// writeParetoCSV writes points as CSV with a header row.
+func writeParetoCSV(out io.Writer, points []ParetoPoint) error {
+	w := csv.NewWriter(out)
+	_ = w.Write([]string{
+		"run_id", "agent", "model", "weighted_pass_rate", "pass_rate", "duration_seconds",
+		"cost_usd", "cost_source", "time_frontier", "cost_frontier",
+	})
+	for _, p := range points {
+		cost := ""
+		if p.CostSource != "" {
+			cost = strconv.FormatFloat(p.CostUSD, 'f', 4, 64)
+		}
+		_ = w.Write([]string{
+			p.RunID, p.Agent, p.Model,
+			strconv.FormatFloat(p.WeightedPassRate, 'f', 2, 64),
+			strconv.FormatFloat(p.PassRate, 'f', 2, 64),
+			strconv.FormatFloat(p.Duration, 'f', 1, 64),
+			cost, p.CostSource,
+			strconv.FormatBool(p.TimeFrontier),
+			strconv.FormatBool(p.CostFrontier),
+		})
+	}
+	w.Flush()
+	if err := w.Error(); err != nil {
+		return fmt.Errorf("writing CSV: %w", err)
+	}
+	return nil
+}
+
+// buildParetoReport renders points as a Markdown table with frontier markers.
+func buildParetoReport(points []ParetoPoint) string {
+	var sb strings.Builder
+	sb.WriteString("# Score vs Cost and Time\n\n")
+	sb.WriteString("| Run | Agent | Model | Weighted | Pass Rate | Time | Cost | Time Frontier | Cost Frontier |\n")
+	sb.WriteString("|-----|-------|-------|----------|-----------|------|------|---------------|---------------|\n")
+	for _, p := range points {
+		cost := "-"
+		switch p.CostSource {
+		case costSourceExplicit:
+			cost = fmt.Sprintf("$%.2f", p.CostUSD)
+		case costSourceEstimated:
+			cost = fmt.Sprintf("~$%.2f", p.CostUSD)
+		}
+		fmt.Fprintf(&sb, "| %s | %s | %s | %.1f%% | %.1f%% | %s | %s | %s | %s |\n",
+			p.RunID, p.Agent, p.Model, p.WeightedPassRate, p.PassRate, formatDuration(p.Duration),
+			cost, frontierMark(p.TimeFrontier), frontierMark(p.CostFrontier))
+	}
+
+	sb.WriteString("\nRuns marked ★ are on the Pareto frontier: no other run scores higher for the same or lower time (or cost).\n")
+	sb.WriteString("Time is total task time; ~ marks costs estimated from agent time.\n")
+	return sb.String()
+}
+
+// frontierMark returns the table marker for frontier membership.
+func frontierMark(on bool) string {
+	if on {
+		return "★"
+	}
+	return ""
+}
+
+// ScalingRow is one eval run in the tidy scaling-study dataset.
+type ScalingRow struct {
+	RunID            string  `json:"run_id"`
+	Timestamp        string  `json:"timestamp"`
+	Agent            string  `json:"agent"`
+	Model            string  `json:"model"`
+	Family           string  `json:"family"`
+	ParamsB          float64 `json:"params_b,omitempty"`
+	Log10ParamsB     float64 `json:"log10_params_b,omitempty"`
+	SizeBucket       string  `json:"size_bucket"`
+	Quant            string  `json:"quant,omitempty"`
+	QuantBits        int     `json:"quant_bits,omitempty"`
+	PassRate         float64 `json:"pass_rate"`
+	WeightedPassRate float64 `json:"weighted_pass_rate"`
+	Duration         float64 `json:"duration_seconds"`
+}
+
+// TrendFit is a least-squares fit of weighted pass rate against
+// log10(parameters in billions): score = Intercept + Slope*log10(params).
+// Slope is therefore the score gain in points per 10x parameters.
+type TrendFit struct {
+	Group     string  `json:"group"`
+	N         int     `json:"n"`
+	Slope     float64 `json:"slope"`
+	Intercept float64 `json:"intercept"`
+	R2        float64 `json:"r2"`
+}
+
+// ScalingStudy bundles the dataset with its fitted trends.
+type ScalingStudy struct {
+	Rows []ScalingRow `json:"rows"`
+	Fits []TrendFit   `json:"fits"`
+}
+
+var analyzeScalingCmd = &cobra.Command{
+	Use:   "scaling <dir> [dir...]",
+	Short: "Export a scaling-study dataset with fitted size trends",
+	Long: `Joins each eval run's scores with its model family, parameter count, and
+quantization metadata and exports a tidy dataset (one row per run), plus a
+log-linear fit of weighted pass rate against parameter count overall and per
+family.
+
+Metadata comes from --model-family, --model-params, and --model-quant at eval
+time, falling back to what can be inferred from the model name. Runs without
+a known parameter count are exported but excluded from the fits.`,
+	Example: `  sanity analyze scaling eval-results/
+  sanity analyze scaling eval-results/ --csv > scaling.csv
+  sanity analyze scaling eval-results/ --json`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		summaries, runDirs, err := loadRunSummaries(args)
+		if err != nil {
+			return err
+		}
+		study := buildScalingStudy(summaries, runDirs)
+
+		switch {
+		case analyzeJSON:
+			data, _ := json.MarshalIndent(study, "", "  ")
+			fmt.Println(string(data))
+			return nil
+		case analyzeCSV:
+			return writeScalingCSV(os.Stdout, study.Rows)
+		}
+		fmt.Print(buildScalingReport(study))
+		return nil
+	},
+}
+
+// buildScalingStudy builds the dataset rows and fits trends overall and per family.
+func buildScalingStudy(summaries []EvalSummary, runDirs []string) ScalingStudy {
+	rows := make([]ScalingRow, 0, len(summaries))
+	for i, s := range summaries {
+		quant := s.ModelQuant
+		if quant == "" {
+			quant = inferModelQuant(s.Model)
+		}
+		row := ScalingRow{
+			RunID:            filepath.Base(runDirs[i]),
+			Timestamp:        s.Timestamp,
+			Agent:            s.Agent,
+			Model:            s.Model,
+			Family:           summaryModelFamily(s),
+			ParamsB:          summaryModelParams(s),
+			Quant:            quant,
+			QuantBits:        quantBits(quant),
+			PassRate:         s.PassRate,
+			WeightedPassRate: s.WeightedPassRate,
+			Duration:         s.Duration,
+		}
+		row.SizeBucket = sizeBucket(row.ParamsB)
+		if row.ParamsB > 0 {
+			row.Log10ParamsB = math.Log10(row.ParamsB)
+		}
+		rows = append(rows, row)
+	}
+	sort.SliceStable(rows, func(i, j int) bool { return rows[i].ParamsB < rows[j].ParamsB })
+
+	byGroup := map[string][]ScalingRow{}
+	for _, r := range rows {
+		if r.ParamsB <= 0 {
+			continue
+		}
+		byGroup["all"] = append(byGroup["all"], r)
+		byGroup[r.Family] = append(byGroup[r.Family], r)
+	}
+	groups := make([]string, 0, len(byGroup))
+	for g := range byGroup {
+		groups = append(groups, g)
+	}
+	sort.Slice(groups, func(i, j int) bool {
+		if (groups[i] == "all") != (groups[j] == "all") {
+			return groups[i] == "all"
+		}
+		return groups[i] < groups[j]
+	})
+
+	fits := make([]TrendFit, 0, len(groups))
+	for _, g := range groups {
+		xs := make([]float64, 0, len(byGroup[g]))
+		ys := make([]float64, 0, len(byGroup[g]))
+		for _, r := range byGroup[g] {
+			xs = append(xs, r.Log10ParamsB)
+			ys = append(ys, r.WeightedPassRate)
+		}
+		if fit, ok := linearFit(xs, ys); ok {
+			fit.Group = g
+			fits = append(fits, fit)
+		}
+	}
+	return ScalingStudy{Rows: rows, Fits: fits}
+}
+
+// linearFit computes an ordinary least-squares fit of ys against xs. It
+// returns false when there are fewer than two distinct x values.
+func linearFit(xs, ys []float64) (TrendFit, bool) {
+	n := len(xs)
+	if n < 2 {
+		return TrendFit{}, false
+	}
+	mx, my := mean(xs), mean(ys)
+	var sxx, sxy, syy float64
+	for i := range xs {
+		dx, dy := xs[i]-mx, ys[i]-my
+		sxx += dx * dx
+		sxy += dx * dy
+		syy += dy * dy
+	}
+	if sxx == 0 {
+		return TrendFit{}, false
+	}
+	slope := sxy / sxx
+	fit := TrendFit{N: n, Slope: slope, Intercept: my - slope*mx}
+	if syy > 0 {
+		fit.R2 = sxy * sxy / (sxx * syy)
+	}
+	return fit, true
+}
+
+// writeScalingCSV writes scaling rows as CSV with a header row.
+func writeScalingCSV(out io.Writer, rows []ScalingRow) error {
+	w := csv.NewWriter(out)
+	_ = w.Write([]string{
+		"run_id", "timestamp", "agent", "model", "family", "params_b", "log10_params_b", "size_bucket",
+		"quant", "quant_bits", "pass_rate", "weighted_pass_rate", "duration_seconds",
+	})
+	for _, r := range rows {
+		params, logParams, bits := "", "", ""
+		if r.ParamsB > 0 {
+			params = strconv.FormatFloat(r.ParamsB, 'f', -1, 64)
+			logParams = strconv.FormatFloat(r.Log10ParamsB, 'f', 4, 64)
+		}
+		if r.QuantBits > 0 {
+			bits = strconv.Itoa(r.QuantBits)
+		}
+		_ = w.Write([]string{
+			r.RunID, r.Timestamp, r.Agent, r.Model, r.Family, params, logParams, r.SizeBucket,
+			r.Quant, bits,
+			strconv.FormatFloat(r.PassRate, 'f', 2, 64),
+			strconv.FormatFloat(r.WeightedPassRate, 'f', 2, 64),
+			strconv.FormatFloat(r.Duration, 'f', 1, 64),
+		})
+	}
+	w.Flush()
+	if err := w.Error(); err != nil {
+		return fmt.Errorf("writing CSV: %w", err)
+	}
+	return nil
+}
+
+// buildScalingReport renders fitted trends and the dataset as Markdown.
+func buildScalingReport(study ScalingStudy) string {
+	var sb strings.Builder
+	sb.WriteString("# Scaling Study\n\n")
+
+	sb.WriteString("## Trend Fits\n\n")
+	if len(study.Fits) == 0 {
+		sb.WriteString("Not enough runs with distinct known parameter counts to fit a trend.\n\n")
+	} else {
+		sb.WriteString("Weighted pass rate ≈ intercept + slope × log10(params in B).\n\n")
+		sb.WriteString("| Group | Runs | Pts per 10x Params | Intercept (1B) | R² |\n")
+		sb.WriteString("|-------|------|--------------------|----------------|----|\n")
+		for _, f := range study.Fits {
+			fmt.Fprintf(&sb, "| %s | %d | %+.1f | %.1f%% | %.2f |\n", f.Group, f.N, f.Slope, f.Intercept, f.R2)
+		}
+		sb.WriteString("\n")
+	}
+
+	sb.WriteString("## Runs\n\n")
+	sb.WriteString("| Run | Model | Family | Params | Quant | Weighted | Pass Rate |\n")
+	sb.WriteString("|-----|-------|--------|--------|-------|----------|-----------|\n")
+	for _, r := range study.Rows {
+		params := "-"
+		if r.ParamsB > 0 {
+			params = fmt.Sprintf("%gB", r.ParamsB)
+		}
+		quant := r.Quant
+		if quant == "" {
+			quant = "-"
+		}
+		fmt.Fprintf(&sb, "| %s | %s | %s | %s | %s | %.1f%% | %.1f%% |\n",
+			r.RunID, r.Model, r.Family, params, quant, r.WeightedPassRate, r.PassRate)
+	}
+	sb.WriteString("\n")
+	return sb.String()
+}
@@ -0,0 +1,206 @@
+package cli
+
+import (
+	"math"
+	"testing"
+)
+
+func TestParseModelParams(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		in   string
+		want float64
+	}{
+		{"", 0},
+		{"7B", 7},
+		{"1.5b", 1.5},
+		{"500M", 0.5},
+		{"1T", 1000},
+		{"8x7B", 56},
+	}
+	for _, tt := range tests {
+		got, err := parseModelParams(tt.in)
+		if err != nil {
+			t.Fatalf("parseModelParams(%q) error = %v", tt.in, err)
+		}
+		if got != tt.want {
+			t.Fatalf("parseModelParams(%q) = %v, want %v", tt.in, got, tt.want)
+		}
+	}
+
+	for _, bad := range []string{"7", "seven", "7GB"} {
+		if _, err := parseModelParams(bad); err == nil {
+			t.Fatalf("parseModelParams(%q) expected error", bad)
+		}
+	}
+}
+
+func TestInferModelMetadata(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		model  string
+		family string
+		params float64
+	}{
+		{"openrouter/qwen/qwen3-coder-30b-a3b-instruct", "qwen", 30},
+		{"llama3.1:8b", "llama", 8},
+		{"mistralai/mixtral-8x7b", "mixtral", 56},
+		{"gpt-5", "gpt", 0},
+		{"claude-sonnet-4-5", "claude", 0},
+		{"unknown", "", 0},
+	}
+	for _, tt := range tests {
+		if got := inferModelFamily(tt.model); got != tt.family {
+			t.Fatalf("inferModelFamily(%q) = %q, want %q", tt.model, got, tt.family)
+		}
+		if got := inferModelParams(tt.model); got != tt.params {
+			t.Fatalf("inferModelParams(%q) = %v, want %v", tt.model, got, tt.params)
+		}
+	}
+
+	if got := resolveModelParams("235B", "qwen3-30b"); got != 235 {
+		t.Fatalf("explicit params should win, got %v", got)
+	}
+	if got := resolveModelFamily("DeepSeek", "ds-v3"); got != "deepseek" {
+		t.Fatalf("explicit family should win, got %q", got)
+	}
+}
+
+func TestAggregateBySize(t *testing.T) {
+	t.Parallel()
+
+	summaries := []EvalSummary{
+		{Model: "qwen3-4b", PassRate: 20, WeightedPassRate: 15},
+		{Model: "qwen3-32b", PassRate: 50, WeightedPassRate: 40},
+		{Model: "custom", ModelParamsB: 30, PassRate: 70, WeightedPassRate: 60},
+		{Model: "gpt-5", PassRate: 90, WeightedPassRate: 85},
+	}
+
+	groups := aggregateBySize(summaries)
+	if len(groups) != 3 {
+		t.Fatalf("groups len = %d, want 3: %+v", len(groups), groups)
+	}
+	if groups[0].Group != sizeBucketSmall || groups[1].Group != sizeBucketMedium || groups[2].Group != sizeBucketUnknown {
+		t.Fatalf("unexpected group order: %+v", groups)
+	}
+	medium := groups[1]
+	if medium.Runs != 2 || medium.MeanWeightedPassRate != 50 || medium.BestModel != "custom" {
+		t.Fatalf("unexpected medium bucket: %+v", medium)
+	}
+
+	if sizeBucket(120) != sizeBucketLarge || sizeBucket(121) != sizeBucketXLarge || sizeBucket(8) != sizeBucketMedium {
+		t.Fatal("unexpected bucket boundaries")
+	}
+}
+
+func TestComputeLanguageConsistency(t *testing.T) {
+	t.Parallel()
+
+	run := EvalSummary{
+		Agent: "opencode",
+		Model: "qwen3-32b",
+		Results: []EvalResult{
+			{Task: "go/a", Language: "go", Difficulty: "hard", Passed: true, Weight: 1, WeightedScore: 1},
+			{Task: "go/b", Language: "go", Difficulty: "easy", Passed: true, Weight: 1, WeightedScore: 1},
+			{Task: "rust/a", Language: "rust", Difficulty: "hard", Passed: false, Weight: 1},
+			{Task: "rust/b", Language: "rust", Difficulty: "easy", Passed: true, Weight: 1, WeightedScore: 1},
+		},
+	}
+
+	reports := computeLanguageConsistency([]EvalSummary{run, run})
+	if len(reports) != 1 {
+		t.Fatalf("reports len = %d, want 1", len(reports))
+	}
+	lc := reports[0]
+	if lc.Runs != 2 || lc.OverallWeightedPassRate != 75 {
+		t.Fatalf("unexpected overall: %+v", lc)
+	}
+	if lc.Strongest != "go" || lc.Weakest != "rust" || lc.Spread != 50 {
+		t.Fatalf("unexpected strongest/weakest/spread: %+v", lc)
+	}
+
+	rust := lc.Languages[1]
+	if rust.Attempts != 4 || rust.Delta != -25 {
+		t.Fatalf("unexpected rust score: %+v", rust)
+	}
+	// Overall hard pass rate is 50%; rust passes no hard tasks.
+	if got := rust.ByCategory["difficulty:hard"].Delta; got != -50 {
+		t.Fatalf("rust difficulty:hard delta = %v, want -50", got)
+	}
+	if got := rust.ByCategory["difficulty:easy"].Delta; got != 0 {
+		t.Fatalf("rust difficulty:easy delta = %v, want 0", got)
+	}
+}
+
+func TestBuildParetoPoints(t *testing.T) {
+	t.Parallel()
+
+	summaries := []EvalSummary{
+		{Agent: "a", Model: "big", WeightedPassRate: 80, Duration: 600, AgentTime: 3600},
+		{Agent: "a", Model: "small", WeightedPassRate: 50, Duration: 200, AgentTime: 1800},
+		{Agent: "a", Model: "slow", WeightedPassRate: 45, Duration: 900, AgentTime: 7200},
+	}
+	runDirs := []string{"/r/run-big", "/r/run-small", "/r/run-slow"}
+	costs, err := parseCostOverrides([]string{"big=10", "run-slow=$1"})
+	if err != nil {
+		t.Fatalf("parseCostOverrides() error = %v", err)
+	}
+
+	points := buildParetoPoints(summaries, runDirs, costs, 2)
+	if points[0].Model != "big" || points[2].Model != "slow" {
+		t.Fatalf("unexpected order: %+v", points)
+	}
+	if !points[0].TimeFrontier || !points[1].TimeFrontier || points[2].TimeFrontier {
+		t.Fatalf("unexpected time frontier: %+v", points)
+	}
+	// small costs ~$1 (estimated), slow $1 (explicit): small dominates slow.
+	if points[1].CostSource != costSourceEstimated || points[1].CostUSD != 1 {
+		t.Fatalf("unexpected estimated cost: %+v", points[1])
+	}
+	if !points[0].CostFrontier || !points[1].CostFrontier || points[2].CostFrontier {
+		t.Fatalf("unexpected cost frontier: %+v", points)
+	}
+
+	if _, err := parseCostOverrides([]string{"gpt-5"}); err == nil {
+		t.Fatal("expected error for missing value")
+	}
+}
+
+func TestBuildScalingStudy(t *testing.T) {
+	t.Parallel()
+
+	summaries := []EvalSummary{
+		{Agent: "a", Model: "qwen3-100b", WeightedPassRate: 60},
+		{Agent: "a", Model: "qwen3-1b-q4_k_m", WeightedPassRate: 20},
+		{Agent: "a", Model: "qwen3-10b", ModelQuant: "FP8", WeightedPassRate: 40},
+		{Agent: "a", Model: "gpt-5", WeightedPassRate: 90},
+	}
+	runDirs := []string{"r1", "r2", "r3", "r4"}
+
+	study := buildScalingStudy(summaries, runDirs)
+	if len(study.Rows) != 4 {
+		t.Fatalf("rows len = %d, want 4", len(study.Rows))
+	}
+	small := study.Rows[1]
+	if small.ParamsB != 1 || small.Quant != "Q4_K_M" || small.QuantBits != 4 {
+		t.Fatalf("unexpected small row: %+v", small)
+	}
+	if study.Rows[2].QuantBits != 8 {
+		t.Fatalf("unexpected explicit quant row: %+v", study.Rows[2])
+	}
+
+	// all and qwen cover the same three runs; gpt-5 has no known size.
+	if len(study.Fits) != 2 || study.Fits[0].Group != "all" || study.Fits[1].Group != "qwen" {
+		t.Fatalf("unexpected fits: %+v", study.Fits)
+	}
+	fit := study.Fits[0]
+	if fit.N != 3 || math.Abs(fit.Slope-20) > 1e-9 || math.Abs(fit.Intercept-20) > 1e-9 || math.Abs(fit.R2-1) > 1e-9 {
+		t.Fatalf("unexpected fit: %+v", fit)
+	}
+
+	if _, ok := linearFit([]float64{1, 1}, []float64{2, 3}); ok {
+		t.Fatal("expected no fit for identical x values")
+	}
+}
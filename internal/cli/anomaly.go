@@ -0,0 +1,187 @@
+package cli
+
+import (
+	"fmt"
+	"slices"
+	"sort"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// Duration anomalies recorded in EvalResult.DurationAnomaly.
+const (
+	durationAnomalyFast = "too_fast"
+	durationAnomalySlow = "too_slow"
+)
+
+const (
+	// durationStatsWindow is how many of a task's most recent agent times
+	// its expected duration is calibrated from.
+	durationStatsWindow = 20
+	// durationStatsMinSamples is how many past runs a task needs before its
+	// duration is compared against them.
+	durationStatsMinSamples = 5
+	// durationFastFloor is the agent time, in seconds, below which an attempt
+	// is always suspicious: no agent reads a task and edits code that fast,
+	// so it most likely failed to start.
+	durationFastFloor = 1.0
+	// durationFastRatio and durationSlowRatio bound agent time relative to a
+	// task's median.
+	durationFastRatio = 0.1
+	durationSlowRatio = 4.0
+)
+
+// taskDurationStats are a task's rolling agent-time statistics.
+type taskDurationStats struct {
+	Samples int
+	Median  float64
+}
+
+// evalDurationStats holds the rolling per-task statistics of past runs that
+// results are checked against. Like the other eval globals it is set by
+// evalRunSingle before tasks start.
+var evalDurationStats map[string]taskDurationStats
+
+// agentSeconds is the time an attempt spent in the agent, or its whole
+// duration for results recorded before agent time was.
+func agentSeconds(r EvalResult) float64 {
+	if r.AgentTime > 0 {
+		return r.AgentTime
+	}
+	return r.Duration
+}
+
+// calibratesDuration reports whether r is a normal sample of its task's
+// agent time. Timeouts, infrastructure failures, and earlier anomalies are
+// left out so they don't skew the baseline.
+func calibratesDuration(r EvalResult) bool {
+	return r.DurationAnomaly == "" && !r.AgentTimedOut && !r.InfraFailure &&
+		r.Status != task.StatusInfraFailure && r.Status != task.StatusError && agentSeconds(r) > 0
+}
+
+// loadDurationStats computes rolling per-task agent-time statistics from the
+// most recent eval runs under root, across all agents. Unreadable runs are
+// ignored.
+func loadDurationStats(root string) map[string]taskDurationStats {
+	dirs, err := findRunDirs(root)
+	if err != nil {
+		return nil
+	}
+	var summaries []*EvalSummary
+	for _, dir := range dirs {
+		if summary, err := loadSummaryFromDir(dir); err == nil {
+			summaries = append(summaries, summary)
+		}
+	}
+	// Newest first, so each task keeps its most recent samples.
+	sort.SliceStable(summaries, func(i, j int) bool { return summaries[i].Timestamp > summaries[j].Timestamp })
+
+	samples := make(map[string][]float64)
+	for _, summary := range summaries {
+		for _, r := range summary.Results {
+			if calibratesDuration(r) && len(samples[r.Task]) < durationStatsWindow {
+				samples[r.Task] = append(samples[r.Task], agentSeconds(r))
+			}
+		}
+	}
+	stats := make(map[string]taskDurationStats, len(samples))
+	for id, s := range samples {
+		stats[id] = taskDurationStats{Samples: len(s), Median: median(s)}
+	}
+	return stats
+}
+
+func median(values []float64) float64 {
+	if len(values) == 0 {
+		return 0
+	}
+	sorted := slices.Clone(values)
+	slices.Sort(sorted)
+	mid := len(sorted) / 2
+	if len(sorted)%2 == 0 {
+		return (sorted[mid-1] + sorted[mid]) / 2
+	}
+	return sorted[mid]
+}
+
+// annotateDurationAnomaly flags r when its agent time is far outside what
+// past runs of the task took: suspiciously fast attempts usually mean the
+// agent never ran, anomalously long ones a hung tool or a stuck loop.
+func annotateDurationAnomaly(r *EvalResult, stats map[string]taskDurationStats) {
+	if r.InfraFailure || isResumableExternalFailure(*r) {
+		return
+	}
+	s := stats[r.Task]
+	calibrated := s.Samples >= durationStatsMinSamples
+	secs := agentSeconds(*r)
+	switch {
+	case secs < durationFastFloor || (calibrated && secs < s.Median*durationFastRatio):
+		r.DurationAnomaly = durationAnomalyFast
+	case calibrated && !r.AgentTimedOut && secs > s.Median*durationSlowRatio:
+		r.DurationAnomaly = durationAnomalySlow
+	default:
+		return
+	}
+	if calibrated {
+		r.ExpectedDuration = s.Median
+	}
+}
+
+func countDurationAnomalies(results []EvalResult) int {
+	var n int
+	for _, r := range results {
+		if r.DurationAnomaly != "" {
+			n++
+		}
+	}
+	return n
+}
+
+// durationAnomalyNote describes r's duration anomaly for live output, or
+// returns "" if it has none.
+func durationAnomalyNote(r EvalResult) string {
+	var sb strings.Builder
+	switch r.DurationAnomaly {
+	case durationAnomalyFast:
+		fmt.Fprintf(&sb, "Suspiciously fast: %.1fs agent time", agentSeconds(r))
+	case durationAnomalySlow:
+		fmt.Fprintf(&sb, "Anomalously long: %s agent time", formatDuration(agentSeconds(r)))
+	default:
+		return ""
+	}
+	if r.ExpectedDuration > 0 {
+		fmt.Fprintf(&sb, " (typically %s)", formatDuration(r.ExpectedDuration))
+	}
+	if r.DurationAnomaly == durationAnomalyFast {
+		sb.WriteString(" — check the agent started and the environment is healthy")
+	}
+	return sb.String()
+}
+
+func writeReportDurationAnomalies(sb *strings.Builder, summary EvalSummary) {
+	if summary.DurationAnomalies == 0 {
+		return
+	}
+	sb.WriteString("## Duration Anomalies\n\n")
+	sb.WriteString("Suspiciously fast attempts usually mean the agent never ran; anomalously long ones a hung tool or a stuck loop. ")
+	fmt.Fprintf(sb, "Typical times are the median of each task's last %d runs.\n\n", durationStatsWindow)
+	sb.WriteString("| Task | Anomaly | Agent Time | Typical | Status |\n")
+	sb.WriteString("|------|---------|------------|---------|--------|\n")
+	for _, r := range summary.Results {
+		if r.DurationAnomaly == "" {
+			continue
+		}
+		anomaly := "⚠️ Too fast"
+		if r.DurationAnomaly == durationAnomalySlow {
+			anomaly = "⚠️ Too long"
+		}
+		typical := "-"
+		if r.ExpectedDuration > 0 {
+			typical = fmt.Sprintf("%.1fs", r.ExpectedDuration)
+		}
+		_, status := getResultStatusDisplay(r)
+		fmt.Fprintf(sb, "| %s | %s | %.1fs | %s | %s |\n", r.Task, anomaly, agentSeconds(r), typical, status)
+	}
+	sb.WriteString("\n")
+}
@@ -0,0 +1,97 @@
+package cli
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestLoadDurationStats(t *testing.T) {
+	t.Parallel()
+
+	root := t.TempDir()
+	writeRun := func(name, timestamp string, results ...EvalResult) {
+		t.Helper()
+		dir := filepath.Join(root, name)
+		if err := os.MkdirAll(dir, 0o755); err != nil {
+			t.Fatal(err)
+		}
+		data, _ := json.Marshal(EvalSummary{Agent: "gemini", Timestamp: timestamp, Results: results})
+		if err := os.WriteFile(filepath.Join(dir, "summary.json"), data, 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	// The oldest runs fall out of the window.
+	for i := range durationStatsWindow + 3 {
+		agentTime := 100.0
+		if i < 3 {
+			agentTime = 1000
+		}
+		writeRun(fmt.Sprintf("run-%02d", i), fmt.Sprintf("2026-01-%02dT120000", i+1),
+			EvalResult{Task: "go/bank-account", AgentTime: agentTime},
+			EvalResult{Task: "go/react", AgentTime: 0.1, DurationAnomaly: durationAnomalyFast},
+			EvalResult{Task: "go/grep", AgentTime: 600, AgentTimedOut: true},
+		)
+	}
+
+	stats := loadDurationStats(root)
+	if got := stats["go/bank-account"]; got.Samples != durationStatsWindow || got.Median != 100 {
+		t.Errorf("go/bank-account stats = %+v, want %d samples with median 100", got, durationStatsWindow)
+	}
+	for _, id := range []string{"go/react", "go/grep"} {
+		if _, ok := stats[id]; ok {
+			t.Errorf("%s has stats from anomalous or timed-out runs", id)
+		}
+	}
+}
+
+func TestAnnotateDurationAnomaly(t *testing.T) {
+	t.Parallel()
+
+	stats := map[string]taskDurationStats{
+		"go/calibrated": {Samples: 8, Median: 120},
+		"go/new":        {Samples: 2, Median: 120},
+	}
+	tests := []struct {
+		name string
+		r    EvalResult
+		want string
+	}{
+		{"instant exit without history", EvalResult{Task: "go/unknown", AgentTime: 0}, durationAnomalyFast},
+		{"fast relative to median", EvalResult{Task: "go/calibrated", AgentTime: 8}, durationAnomalyFast},
+		{"normal", EvalResult{Task: "go/calibrated", AgentTime: 150}, ""},
+		{"long", EvalResult{Task: "go/calibrated", AgentTime: 600}, durationAnomalySlow},
+		{"long but timed out", EvalResult{Task: "go/calibrated", AgentTime: 600, AgentTimedOut: true}, ""},
+		{"too few samples", EvalResult{Task: "go/new", AgentTime: 600}, ""},
+		{"infra failure", EvalResult{Task: "go/calibrated", InfraFailure: true}, ""},
+	}
+	for _, tt := range tests {
+		r := tt.r
+		annotateDurationAnomaly(&r, stats)
+		if r.DurationAnomaly != tt.want {
+			t.Errorf("%s: DurationAnomaly = %q, want %q", tt.name, r.DurationAnomaly, tt.want)
+		}
+	}
+
+	r := EvalResult{Task: "go/calibrated", AgentTime: 0.2}
+	annotateDurationAnomaly(&r, stats)
+	if r.ExpectedDuration != 120 {
+		t.Errorf("ExpectedDuration = %v, want 120", r.ExpectedDuration)
+	}
+	var out bytes.Buffer
+	p := newEvalProgress(&out, []string{"go/calibrated"}, 2, false, nil)
+	p.Finish(r)
+	p.Stop()
+	if !strings.Contains(out.String(), "Suspiciously fast: 0.2s agent time (typically 2m 00s)") {
+		t.Errorf("live output missing anomaly note:\n%s", out.String())
+	}
+	var report strings.Builder
+	writeReportDurationAnomalies(&report, EvalSummary{Results: []EvalResult{r}, DurationAnomalies: 1})
+	if !strings.Contains(report.String(), "| go/calibrated | ⚠️ Too fast | 0.2s | 120.0s | FAIL |") {
+		t.Errorf("report missing anomaly row:\n%s", report.String())
+	}
+}
@@ -0,0 +1,104 @@
+package cli
+
+import (
+	"os"
+	"os/user"
+	"regexp"
+	"sort"
+	"strings"
+)
+
+// Placeholders substituted by the anonymizer.
+const (
+	anonWorkspace = "<workspace>"
+	anonHome      = "~"
+	anonHost      = "<host>"
+	anonUser      = "<user>"
+	anonRedacted  = "<redacted>"
+)
+
+var (
+	// anonWorkspacePattern matches the harness's temporary agent and diff
+	// workspaces, wherever the host's temp directory is.
+	anonWorkspacePattern = regexp.MustCompile(`(?:/[^/\s"'` + "`" + `]+)*/sanity-(?:eval|solution-diff)-[\w.-]+`)
+
+	// anonHomePatterns match home directories on Linux, macOS, and Windows,
+	// including JSON-escaped Windows paths.
+	anonHomePatterns = []*regexp.Regexp{
+		regexp.MustCompile(`(?:/home|/Users)/[^/\s"'` + "`" + `:]+`),
+		regexp.MustCompile(`/root\b`),
+		regexp.MustCompile(`(?i)\b[A-Z]:\\{1,2}Users\\{1,2}[^\\\s"']+`),
+	}
+)
+
+// anonymizer strips environment details from results and transcripts so they
+// can be published: temporary workspace paths, home directories, the host
+// name, the user name, and configured identifiers.
+type anonymizer struct {
+	words       []*regexp.Regexp
+	wordValues  []string
+	identifiers []string
+}
+
+// newAnonymizer builds an anonymizer for the current machine and user plus
+// identifiers. User names shorter than three characters and "root" are left
+// alone because they would mangle ordinary words.
+func newAnonymizer(identifiers []string) *anonymizer {
+	a := &anonymizer{identifiers: sortedByLength(identifiers)}
+	if host, err := os.Hostname(); err == nil && host != "" && host != "localhost" {
+		a.addWord(host, anonHost)
+		if short, _, ok := strings.Cut(host, "."); ok {
+			a.addWord(short, anonHost)
+		}
+	}
+	names := []string{os.Getenv("USER"), os.Getenv("USERNAME")}
+	if u, err := user.Current(); err == nil {
+		names = append(names, u.Username)
+	}
+	seen := make(map[string]bool)
+	for _, name := range names {
+		if len(name) < 3 || name == "root" || seen[name] {
+			continue
+		}
+		seen[name] = true
+		a.addWord(name, anonUser)
+	}
+	return a
+}
+
+func (a *anonymizer) addWord(word, replacement string) {
+	a.words = append(a.words, regexp.MustCompile(`\b`+regexp.QuoteMeta(word)+`\b`))
+	a.wordValues = append(a.wordValues, replacement)
+}
+
+// apply returns data with environment details replaced by placeholders. A
+// nil anonymizer returns data unchanged.
+func (a *anonymizer) apply(data []byte) []byte {
+	if a == nil {
+		return data
+	}
+	for _, id := range a.identifiers {
+		data = []byte(strings.ReplaceAll(string(data), id, anonRedacted))
+	}
+	data = anonWorkspacePattern.ReplaceAll(data, []byte(anonWorkspace))
+	for _, re := range anonHomePatterns {
+		data = re.ReplaceAll(data, []byte(anonHome))
+	}
+	for i, re := range a.words {
+		data = re.ReplaceAll(data, []byte(a.wordValues[i]))
+	}
+	return data
+}
+
+// sortedByLength returns the non-empty values longest first, so an
+// identifier containing another is replaced whole.
+func sortedByLength(values []string) []string {
+	out := make([]string, 0, len(values))
+	for _, v := range values {
+		if v != "" {
+			out = append(out, v)
+		}
+	}
+	sort.SliceStable(out, func(i, j int) bool { return len(out[i]) > len(out[j]) })
+	return out
+}
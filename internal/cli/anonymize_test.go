@@ -0,0 +1,36 @@
+package cli
+
+import "testing"
+
+func TestAnonymizerApply(t *testing.T) {
+	t.Parallel()
+
+	a := &anonymizer{identifiers: sortedByLength([]string{"acme", "acme-labs", ""})}
+	a.addWord("bench-01", anonHost)
+	a.addWord("alice", anonUser)
+
+	tests := []struct {
+		in   string
+		want string
+	}{
+		{"cd /tmp/sanity-eval-go-bank-account-123 && go test", "cd <workspace> && go test"},
+		{"/private/var/folders/x1/T/sanity-solution-diff-9/a.go", "<workspace>/a.go"},
+		{"reading /home/alice/.config/opencode.json", "reading ~/.config/opencode.json"},
+		{`"cwd":"/Users/bob/src/sanity"`, `"cwd":"~/src/sanity"`},
+		{`C:\\Users\\bob\\AppData`, `~\\AppData`},
+		{"/root/.cache/go-build", "~/.cache/go-build"},
+		{"ssh alice@bench-01.lan", "ssh <user>@<host>.lan"},
+		{"malice and bench-011 stay", "malice and bench-011 stay"},
+		{"run for acme-labs by acme", "run for <redacted> by <redacted>"},
+	}
+	for _, tt := range tests {
+		if got := string(a.apply([]byte(tt.in))); got != tt.want {
+			t.Errorf("apply(%q) = %q, want %q", tt.in, got, tt.want)
+		}
+	}
+
+	var none *anonymizer
+	if got := string(none.apply([]byte("/home/alice"))); got != "/home/alice" {
+		t.Errorf("nil anonymizer changed input to %q", got)
+	}
+}
@@ -0,0 +1,354 @@
+package cli
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path"
+	"path/filepath"
+	"strings"
+	"time"
+	"unicode/utf8"
+
+	"github.com/spf13/cobra"
+)
+
+// archiveManifestName records where and when a run was archived. It is kept
+// in the run directory on import so the run's origin survives further hops.
+const archiveManifestName = "archive.json"
+
+// ArchiveManifest describes the machine and harness that archived a run.
+type ArchiveManifest struct {
+	CreatedAt      string `json:"created_at"`
+	Host           string `json:"host,omitempty"`
+	HarnessVersion string `json:"harness_version"`
+}
+
+var (
+	archiveOutput     string
+	archiveWorkspaces bool
+	archiveAnonymize  bool
+)
+
+// archiveOptions selects what writeRunArchive includes.
+type archiveOptions struct {
+	Workspaces bool
+	Anonymizer *anonymizer // Applied to every text file when set
+}
+
+var archiveCmd = &cobra.Command{
+	Use:   "archive <eval-dir>",
+	Short: "Pack an eval run into a single compressed archive",
+	Long: `Packs an eval results directory into one gzip-compressed tar (.tar.gz) so
+results are cheap to store and share. gzip is used rather than zstd so the
+format needs nothing beyond Go's standard library and any tar can unpack it,
+like submit-bundle bundles. -o must end in .tar.gz or .tgz.
+
+The archive holds the run's summary, report, attestation, and every task's
+transcript, validation log, solution diff, and other harness artifacts.
+Workspace source files are left out unless --workspaces is given.
+
+With --anonymize, text files have temporary workspace paths, home
+directories, the host name, the user name, and the [anonymize] identifiers
+from sanity.toml replaced with placeholders, so the archive can be published.
+attestation.json is kept as is, but the hashes it pins no longer match
+anonymized files.
+
+compare, analyze, export, verify, and inspect read archives directly:
+pass the .tar.gz in place of the directory.`,
+	Example: `  sanity archive eval-results/2026-01-07T120000-gemini
+  sanity archive eval-results/2026-01-07T120000-gemini --workspaces -o gemini-full.tar.gz
+  sanity compare gemini.tar.gz eval-results/2026-01-08T090000-codex`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		evalDir := filepath.Clean(args[0])
+		if _, err := loadSummaryFromDir(evalDir); err != nil {
+			return err
+		}
+		out := archiveOutput
+		if out == "" {
+			out = evalDir + ".tar.gz"
+		}
+		// Other commands only recognize archives by these extensions.
+		if !isRunArchive(out) {
+			return fmt.Errorf("archive path %q must end in .tar.gz or .tgz", out)
+		}
+		opts := archiveOptions{Workspaces: archiveWorkspaces}
+		if archiveAnonymize {
+			opts.Anonymizer = newAnonymizer(cfg.Anonymize.Identifiers)
+		}
+		if err := writeRunArchive(evalDir, out, opts); err != nil {
+			return err
+		}
+		fmt.Printf(" Archive written to: %s\n", out)
+		return nil
+	},
+}
+
+func init() {
+	archiveCmd.Flags().StringVarP(&archiveOutput, "output", "o", "", "archive path (default: <eval-dir>.tar.gz)")
+	archiveCmd.Flags().BoolVar(&archiveWorkspaces, "workspaces", false, "include workspace source files kept with --keep-workspaces")
+	archiveCmd.Flags().BoolVar(&archiveAnonymize, "anonymize", false, "strip user names, host names, paths, and configured identifiers")
+}
+
+// isRunArchive reports whether p names a run archive rather than a directory.
+func isRunArchive(p string) bool {
+	return strings.HasSuffix(p, ".tar.gz") || strings.HasSuffix(p, ".tgz")
+}
+
+// writeRunArchive writes evalDir to out as a gzip-compressed tar rooted at the
+// directory's base name. Task workspace sources are skipped unless
+// opts.Workspaces is set. Runs archived for the first time gain an
+// archive.json naming this machine, unless they are anonymized.
+func writeRunArchive(evalDir, out string, opts archiveOptions) error {
+	f, err := os.Create(out)
+	if err != nil {
+		return fmt.Errorf("creating archive: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+
+	gz := gzip.NewWriter(f)
+	tw := tar.NewWriter(gz)
+	root := filepath.Base(evalDir)
+	if _, err := os.Stat(filepath.Join(evalDir, archiveManifestName)); err != nil && opts.Anonymizer == nil {
+		if err := writeArchiveManifest(tw, path.Join(root, archiveManifestName)); err != nil {
+			return err
+		}
+	}
+	taskDirs := make(map[string]bool)
+	err = filepath.WalkDir(evalDir, func(p string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if p == evalDir {
+			return nil
+		}
+		if opts.skips(d.Name(), taskDirs[filepath.Dir(p)]) {
+			if d.IsDir() {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if d.IsDir() {
+			if isTaskOutputDirName(d.Name()) {
+				taskDirs[p] = true
+			}
+			return nil
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		rel, err := filepath.Rel(evalDir, p)
+		if err != nil {
+			return err
+		}
+		name := path.Join(root, filepath.ToSlash(rel))
+		if opts.Anonymizer != nil && d.Name() != "attestation.json" {
+			return addAnonymizedFile(tw, p, name, opts.Anonymizer)
+		}
+		return addArchiveFile(tw, p, name)
+	})
+	if err != nil {
+		return fmt.Errorf("archiving %s: %w", evalDir, err)
+	}
+
+	if err := tw.Close(); err != nil {
+		return fmt.Errorf("closing tar: %w", err)
+	}
+	if err := gz.Close(); err != nil {
+		return fmt.Errorf("closing gzip: %w", err)
+	}
+	return f.Close()
+}
+
+// skips reports whether an entry named name is left out of the archive.
+func (o archiveOptions) skips(name string, inTaskDir bool) bool {
+	if inTaskDir {
+		return !o.Workspaces && artifactKind(name) == "workspaces"
+	}
+	// Provenance records name machines, which anonymized archives must not.
+	return o.Anonymizer != nil && (name == archiveManifestName || name == importRecordName)
+}
+
+func writeArchiveManifest(tw *tar.Writer, name string) error {
+	host, _ := os.Hostname()
+	now := time.Now()
+	data, err := json.MarshalIndent(ArchiveManifest{
+		CreatedAt:      now.UTC().Format(time.RFC3339),
+		Host:           host,
+		HarnessVersion: Version,
+	}, "", "  ")
+	if err != nil {
+		return fmt.Errorf("marshal archive manifest: %w", err)
+	}
+	hdr := &tar.Header{Name: name, Mode: 0o644, Size: int64(len(data)), ModTime: now, Typeflag: tar.TypeReg}
+	if err := tw.WriteHeader(hdr); err != nil {
+		return fmt.Errorf("writing %s header: %w", name, err)
+	}
+	if _, err := tw.Write(data); err != nil {
+		return fmt.Errorf("writing %s: %w", name, err)
+	}
+	return nil
+}
+
+func addArchiveFile(tw *tar.Writer, src, name string) error {
+	info, err := os.Stat(src)
+	if err != nil {
+		return err
+	}
+	hdr, err := tar.FileInfoHeader(info, "")
+	if err != nil {
+		return err
+	}
+	hdr.Name = name
+	if err := tw.WriteHeader(hdr); err != nil {
+		return fmt.Errorf("writing %s header: %w", name, err)
+	}
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer func() { _ = in.Close() }()
+	if _, err := io.Copy(tw, in); err != nil {
+		return fmt.Errorf("writing %s: %w", name, err)
+	}
+	return nil
+}
+
+// addAnonymizedFile archives src with anon applied when it is text. Binary
+// files are archived unchanged.
+func addAnonymizedFile(tw *tar.Writer, src, name string, anon *anonymizer) error {
+	data, err := os.ReadFile(src)
+	if err != nil {
+		return err
+	}
+	if !utf8.Valid(data) {
+		return addArchiveFile(tw, src, name)
+	}
+	info, err := os.Stat(src)
+	if err != nil {
+		return err
+	}
+	data = anon.apply(data)
+	hdr := &tar.Header{Name: name, Mode: int64(info.Mode().Perm()), Size: int64(len(data)), ModTime: info.ModTime(), Typeflag: tar.TypeReg}
+	if err := tw.WriteHeader(hdr); err != nil {
+		return fmt.Errorf("writing %s header: %w", name, err)
+	}
+	if _, err := tw.Write(data); err != nil {
+		return fmt.Errorf("writing %s: %w", name, err)
+	}
+	return nil
+}
+
+// extractRunArchive unpacks a run archive into a new temporary directory dest
+// below tmpParent (the system default when empty) and returns the run
+// directory inside it. Entries that would land outside dest are rejected.
+func extractRunArchive(archivePath, tmpParent string) (runDir, dest string, err error) {
+	f, err := os.Open(archivePath)
+	if err != nil {
+		return "", "", fmt.Errorf("opening archive: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+	gz, err := gzip.NewReader(f)
+	if err != nil {
+		return "", "", fmt.Errorf("reading gzip: %w", err)
+	}
+
+	dest, err = os.MkdirTemp(tmpParent, ".sanity-archive-*")
+	if err != nil {
+		return "", "", fmt.Errorf("creating extraction directory: %w", err)
+	}
+	if err := extractTar(tar.NewReader(gz), dest); err != nil {
+		_ = os.RemoveAll(dest)
+		return "", "", fmt.Errorf("extracting %s: %w", archivePath, err)
+	}
+
+	// Archives written by `sanity archive` hold a single top-level run
+	// directory; anything else is read from the extraction root.
+	entries, err := os.ReadDir(dest)
+	if err == nil && len(entries) == 1 && entries[0].IsDir() {
+		return filepath.Join(dest, entries[0].Name()), dest, nil
+	}
+	return dest, dest, nil
+}
+
+func extractTar(tr *tar.Reader, dest string) error {
+	for {
+		hdr, err := tr.Next()
+		if errors.Is(err, io.EOF) {
+			return nil
+		}
+		if err != nil {
+			return fmt.Errorf("reading tar: %w", err)
+		}
+		name := path.Clean(hdr.Name)
+		if path.IsAbs(name) || name == ".." || strings.HasPrefix(name, "../") {
+			return fmt.Errorf("archive entry %q escapes the archive root", hdr.Name)
+		}
+		target := filepath.Join(dest, filepath.FromSlash(name))
+		switch hdr.Typeflag {
+		case tar.TypeDir:
+			if err := os.MkdirAll(target, 0o755); err != nil {
+				return err
+			}
+		case tar.TypeReg:
+			if err := writeTarEntry(tr, target, hdr); err != nil {
+				return err
+			}
+		}
+	}
+}
+
+func writeTarEntry(r io.Reader, target string, hdr *tar.Header) error {
+	if err := os.MkdirAll(filepath.Dir(target), 0o755); err != nil {
+		return err
+	}
+	out, err := os.OpenFile(target, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, fs.FileMode(hdr.Mode).Perm()|0o600)
+	if err != nil {
+		return err
+	}
+	if _, err := io.Copy(out, r); err != nil {
+		_ = out.Close()
+		return fmt.Errorf("writing %s: %w", hdr.Name, err)
+	}
+	if err := out.Close(); err != nil {
+		return err
+	}
+	return os.Chtimes(target, hdr.ModTime, hdr.ModTime)
+}
+
+// resolveRunArgs replaces every run archive in args with the directory it
+// extracts to, so commands that read eval results accept either. The returned
+// cleanup removes the extracted copies and must always be called.
+func resolveRunArgs(args []string) ([]string, func(), error) {
+	var extracted []string
+	cleanup := func() {
+		for _, dir := range extracted {
+			_ = os.RemoveAll(dir)
+		}
+	}
+	resolved := make([]string, len(args))
+	for i, arg := range args {
+		if !isRunArchive(arg) {
+			resolved[i] = arg
+			continue
+		}
+		if info, err := os.Stat(arg); err == nil && info.IsDir() {
+			resolved[i] = arg
+			continue
+		}
+		dir, dest, err := extractRunArchive(arg, "")
+		if err != nil {
+			cleanup()
+			return nil, func() {}, err
+		}
+		extracted = append(extracted, dest)
+		resolved[i] = dir
+	}
+	return resolved, cleanup, nil
+}
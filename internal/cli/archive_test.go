@@ -0,0 +1,95 @@
+package cli
+
+import (
+	"archive/tar"
+	"compress/gzip"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestRunArchiveRoundTrip(t *testing.T) {
+	t.Parallel()
+
+	evalDir := filepath.Join(t.TempDir(), "2026-01-07T120000-gemini")
+	files := map[string]string{
+		"summary.json":                    `{"agent":"gemini"}`,
+		"report.md":                       "# Report\n",
+		"go-bank-account/agent.log":       "transcript",
+		"go-bank-account/solution.diff":   "diff",
+		"go-bank-account/bank_account.go": "package bank",
+		"go-bank-account/snapshots/a.go":  "package bank",
+	}
+	for name, content := range files {
+		p := filepath.Join(evalDir, filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(p), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(p, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	for _, includeWorkspaces := range []bool{false, true} {
+		out := filepath.Join(t.TempDir(), "run.tar.gz")
+		if err := writeRunArchive(evalDir, out, archiveOptions{Workspaces: includeWorkspaces}); err != nil {
+			t.Fatalf("writeRunArchive(%v) error = %v", includeWorkspaces, err)
+		}
+		resolved, cleanup, err := resolveRunArgs([]string{out})
+		if err != nil {
+			t.Fatalf("resolveRunArgs() error = %v", err)
+		}
+		runDir := resolved[0]
+		if filepath.Base(runDir) != filepath.Base(evalDir) {
+			t.Errorf("run dir = %s, want base %s", runDir, filepath.Base(evalDir))
+		}
+		for name, content := range files {
+			data, err := os.ReadFile(filepath.Join(runDir, filepath.FromSlash(name)))
+			wantPresent := includeWorkspaces || name != "go-bank-account/bank_account.go"
+			if !wantPresent {
+				if err == nil {
+					t.Errorf("workspaces=%v: %s archived, want skipped", includeWorkspaces, name)
+				}
+				continue
+			}
+			if err != nil || string(data) != content {
+				t.Errorf("workspaces=%v: %s = %q, %v; want %q", includeWorkspaces, name, data, err, content)
+			}
+		}
+		cleanup()
+		if _, err := os.Stat(runDir); !os.IsNotExist(err) {
+			t.Errorf("cleanup left %s behind", runDir)
+		}
+	}
+
+	if got, _, _ := resolveRunArgs([]string{evalDir}); got[0] != evalDir {
+		t.Errorf("resolveRunArgs(dir) = %s, want it unchanged", got[0])
+	}
+}
+
+func TestExtractRunArchiveRejectsEscapingEntries(t *testing.T) {
+	t.Parallel()
+
+	out := filepath.Join(t.TempDir(), "evil.tar.gz")
+	f, err := os.Create(out)
+	if err != nil {
+		t.Fatal(err)
+	}
+	gz := gzip.NewWriter(f)
+	tw := tar.NewWriter(gz)
+	if err := tw.WriteHeader(&tar.Header{Name: "../escape.txt", Mode: 0o644, Size: 1, Typeflag: tar.TypeReg}); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := tw.Write([]byte("x")); err != nil {
+		t.Fatal(err)
+	}
+	_ = tw.Close()
+	_ = gz.Close()
+	_ = f.Close()
+
+	_, _, err = extractRunArchive(out, "")
+	if err == nil || !strings.Contains(err.Error(), "escapes") {
+		t.Fatalf("extractRunArchive() error = %v, want escape rejection", err)
+	}
+}
@@ -0,0 +1,14 @@
+//go:build !linux && !darwin
+
+package cli
+
+import (
+	"os"
+	"time"
+)
+
+// fileAccessTime is not implemented on this platform, so the filesystem
+// audit for hidden test access is skipped.
+func fileAccessTime(_ os.FileInfo) (time.Time, bool) {
+	return time.Time{}, false
+}
@@ -0,0 +1,391 @@
+package cli
+
+import (
+	"bufio"
+	"bytes"
+	"cmp"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+const (
+	// baselineDefaultBaseURL is used when neither --base-url nor
+	// OPENAI_BASE_URL is set.
+	baselineDefaultBaseURL = "https://api.openai.com/v1"
+
+	// baselineMaxToolOutput caps the tool output sent back to the model.
+	baselineMaxToolOutput = 16000
+
+	// baselineShellTimeout bounds a single shell tool call.
+	baselineShellTimeout = 2 * time.Minute
+
+	// baselineMaxRetries is how often a rate-limited or failed completion
+	// request is retried.
+	baselineMaxRetries = 3
+)
+
+var (
+	baselineBaseURL   string
+	baselineAPIKeyEnv string
+	baselineMaxSteps  int
+)
+
+// baselineSystemPrompt is deliberately plain, so the baseline measures the
+// model rather than prompt engineering.
+const baselineSystemPrompt = `You are a coding agent working in the directory %s.
+Use the tools to inspect and edit files and to run commands there. Paths are relative to that directory.
+When the task is complete, reply with a short summary and no tool calls.`
+
+// baselineTools are the tools offered to the model, in OpenAI function
+// calling format.
+var baselineTools = []map[string]any{
+	baselineTool("read_file", "Read a file.", [2]string{"path", "File path"}),
+	baselineTool("write_file", "Create or overwrite a file with the given content.", [2]string{"path", "File path"}, [2]string{"content", "Full file content"}),
+	baselineTool("shell", "Run a shell command and return its combined output and exit status.", [2]string{"command", "Command line for sh -c"}),
+}
+
+// baselineTool describes a function tool whose parameters, given as name and
+// description pairs, are all required strings.
+func baselineTool(name, description string, params ...[2]string) map[string]any {
+	properties := make(map[string]any, len(params))
+	required := make([]string, 0, len(params))
+	for _, p := range params {
+		properties[p[0]] = map[string]string{"type": "string", "description": p[1]}
+		required = append(required, p[0])
+	}
+	return map[string]any{
+		"type": "function",
+		"function": map[string]any{
+			"name":        name,
+			"description": description,
+			"parameters":  map[string]any{"type": "object", "properties": properties, "required": required},
+		},
+	}
+}
+
+var baselineAgentCmd = &cobra.Command{
+	Use:   "baseline-agent",
+	Short: "Run the built-in baseline agent",
+	Long: `Run the built-in baseline agent, which eval starts for --agent baseline.
+
+It reads a plugin task request from stdin (see docs/PLUGINS.md) and works on
+the task with a bare tool loop against an OpenAI-compatible chat completions
+endpoint: the model can only read files, write files, and run shell commands.
+The endpoint comes from --base-url or OPENAI_BASE_URL, and the API key from
+the variable named by --api-key-env.`,
+	Hidden:       true,
+	Args:         cobra.NoArgs,
+	SilenceUsage: true,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		line, err := bufio.NewReader(os.Stdin).ReadBytes('\n')
+		if err != nil && !errors.Is(err, io.EOF) {
+			return fmt.Errorf("reading task request: %w", err)
+		}
+		var req pluginRequest
+		if err = json.Unmarshal(line, &req); err != nil {
+			return fmt.Errorf("parsing task request: %w", err)
+		}
+		agent := &baselineAgent{
+			baseURL:  strings.TrimRight(cmp.Or(baselineBaseURL, os.Getenv("OPENAI_BASE_URL"), baselineDefaultBaseURL), "/"),
+			apiKey:   os.Getenv(baselineAPIKeyEnv),
+			maxSteps: baselineMaxSteps,
+			client:   http.DefaultClient,
+			out:      os.Stdout,
+		}
+		return agent.run(cmd.Context(), req)
+	},
+}
+
+func init() {
+	baselineAgentCmd.Flags().StringVar(&baselineBaseURL, "base-url", "", "OpenAI-compatible API base URL (default: OPENAI_BASE_URL, else "+baselineDefaultBaseURL+")")
+	baselineAgentCmd.Flags().StringVar(&baselineAPIKeyEnv, "api-key-env", "OPENAI_API_KEY", "environment variable holding the API key")
+	baselineAgentCmd.Flags().IntVar(&baselineMaxSteps, "max-steps", 100, "maximum model requests before giving up")
+
+	// The baseline agent runs this binary, which need not be on PATH.
+	if exe, err := os.Executable(); err == nil {
+		baseline := config.DefaultAgents["baseline"]
+		baseline.Command = exe
+		config.DefaultAgents["baseline"] = baseline
+	}
+}
+
+// baselineMessage is a chat completions message.
+type baselineMessage struct {
+	Role       string             `json:"role"`
+	Content    string             `json:"content"`
+	ToolCalls  []baselineToolCall `json:"tool_calls,omitempty"`
+	ToolCallID string             `json:"tool_call_id,omitempty"`
+}
+
+type baselineToolCall struct {
+	ID       string `json:"id"`
+	Type     string `json:"type"`
+	Function struct {
+		Name      string `json:"name"`
+		Arguments string `json:"arguments"`
+	} `json:"function"`
+}
+
+// baselineCompletion is the part of a chat completions response the agent
+// reads.
+type baselineCompletion struct {
+	Choices []struct {
+		Message baselineMessage `json:"message"`
+	} `json:"choices"`
+	Usage *struct {
+		PromptTokens        int64 `json:"prompt_tokens"`
+		CompletionTokens    int64 `json:"completion_tokens"`
+		PromptTokensDetails struct {
+			CachedTokens int64 `json:"cached_tokens"`
+		} `json:"prompt_tokens_details"`
+	} `json:"usage"`
+}
+
+// baselineAgent runs one task as a plugin agent.
+type baselineAgent struct {
+	baseURL  string
+	apiKey   string
+	maxSteps int
+	client   *http.Client
+	out      io.Writer
+}
+
+// emit writes one plugin event.
+func (a *baselineAgent) emit(event pluginEvent) {
+	data, _ := json.Marshal(event)
+	_, _ = a.out.Write(append(data, '\n'))
+}
+
+// run loops over model requests and tool calls until the model replies
+// without tool calls, a request fails, or maxSteps is reached.
+func (a *baselineAgent) run(ctx context.Context, req pluginRequest) error {
+	if req.Model == "" {
+		err := errors.New("the baseline agent needs --model")
+		a.emit(pluginEvent{Type: "done", Status: "failed", Message: err.Error()})
+		return err
+	}
+	messages := []baselineMessage{
+		{Role: "system", Content: fmt.Sprintf(baselineSystemPrompt, req.Workspace)},
+		{Role: "user", Content: req.Prompt},
+	}
+	for step := range a.maxSteps {
+		a.emit(pluginEvent{Type: "turn", Message: strconv.Itoa(step + 1)})
+		reply, usage, err := a.complete(ctx, req, messages)
+		if usage != nil {
+			a.emit(pluginEvent{Type: "usage", TokenUsage: usage})
+		}
+		if err != nil {
+			a.emit(pluginEvent{Type: "error", Message: err.Error()})
+			a.emit(pluginEvent{Type: "done", Status: "failed", Message: err.Error()})
+			return err
+		}
+		messages = append(messages, reply)
+		if len(reply.ToolCalls) == 0 {
+			a.emit(pluginEvent{Type: "done", Status: "completed", Message: reply.Content})
+			return nil
+		}
+		for _, call := range reply.ToolCalls {
+			var args baselineToolArgs
+			output := ""
+			if err = json.Unmarshal([]byte(call.Function.Arguments), &args); err != nil {
+				output = fmt.Sprintf("error: arguments are not valid JSON: %v", err)
+			}
+			a.emit(pluginEvent{Type: "tool_call", Name: call.Function.Name, Input: cmp.Or(args.Command, args.Path, call.Function.Arguments)})
+			if output == "" {
+				output = runBaselineTool(ctx, req.Workspace, call.Function.Name, args)
+			}
+			messages = append(messages, baselineMessage{Role: "tool", ToolCallID: call.ID, Content: output})
+		}
+	}
+	a.emit(pluginEvent{Type: "done", Status: "failed", Message: fmt.Sprintf("stopped after %d steps", a.maxSteps)})
+	return nil
+}
+
+// complete sends one chat completions request, retrying rate limits and
+// server errors with backoff.
+func (a *baselineAgent) complete(ctx context.Context, req pluginRequest, messages []baselineMessage) (baselineMessage, *TokenUsage, error) {
+	payload := map[string]any{
+		"model":    req.Model,
+		"messages": messages,
+		"tools":    baselineTools,
+	}
+	if req.Reasoning != "" {
+		payload["reasoning_effort"] = req.Reasoning
+	}
+	if req.Temperature != nil {
+		payload["temperature"] = *req.Temperature
+	}
+	if req.TopP != nil {
+		payload["top_p"] = *req.TopP
+	}
+	// min_p is not part of the OpenAI API, but vLLM, llama.cpp, and other
+	// OpenAI-compatible servers accept it.
+	if req.MinP != nil {
+		payload["min_p"] = *req.MinP
+	}
+	body, err := json.Marshal(payload)
+	if err != nil {
+		return baselineMessage{}, nil, fmt.Errorf("encoding request: %w", err)
+	}
+	data, err := a.postWithRetry(ctx, body)
+	if err != nil {
+		return baselineMessage{}, nil, err
+	}
+
+	var completion baselineCompletion
+	if err = json.Unmarshal(data, &completion); err != nil {
+		return baselineMessage{}, nil, fmt.Errorf("parsing response: %w", err)
+	}
+	var usage *TokenUsage
+	if u := completion.Usage; u != nil {
+		usage = &TokenUsage{
+			InputTokens:     u.PromptTokens,
+			OutputTokens:    u.CompletionTokens,
+			CacheReadTokens: u.PromptTokensDetails.CachedTokens,
+		}
+	}
+	if len(completion.Choices) == 0 {
+		return baselineMessage{}, usage, errors.New("response has no choices")
+	}
+	return completion.Choices[0].Message, usage, nil
+}
+
+// postWithRetry posts body, retrying connection errors, rate limits, and
+// server errors with exponential backoff.
+func (a *baselineAgent) postWithRetry(ctx context.Context, body []byte) ([]byte, error) {
+	for retry := 0; ; retry++ {
+		data, status, err := a.post(ctx, body)
+		if err == nil && status >= 200 && status <= 299 {
+			return data, nil
+		}
+		if err == nil {
+			err = fmt.Errorf("endpoint returned %d: %s", status, truncateEvidence(string(data)))
+			if status != http.StatusTooManyRequests && status < 500 {
+				return nil, err
+			}
+		}
+		if retry == baselineMaxRetries || ctx.Err() != nil {
+			return nil, err
+		}
+		wait := time.Duration(2<<retry) * time.Second
+		a.emit(pluginEvent{Type: "progress", Message: fmt.Sprintf("%v; retrying in %s", err, wait)})
+		select {
+		case <-ctx.Done():
+			return nil, ctx.Err()
+		case <-time.After(wait):
+		}
+	}
+}
+
+// post sends body to the chat completions endpoint.
+func (a *baselineAgent) post(ctx context.Context, body []byte) ([]byte, int, error) {
+	httpReq, err := http.NewRequestWithContext(ctx, http.MethodPost, a.baseURL+"/chat/completions", bytes.NewReader(body))
+	if err != nil {
+		return nil, 0, err
+	}
+	httpReq.Header.Set("Content-Type", "application/json")
+	httpReq.Header.Set("User-Agent", "sanity/"+Version)
+	if a.apiKey != "" {
+		httpReq.Header.Set("Authorization", "Bearer "+a.apiKey)
+	}
+	resp, err := a.client.Do(httpReq)
+	if err != nil {
+		return nil, 0, fmt.Errorf("calling endpoint: %w", err)
+	}
+	defer func() { _ = resp.Body.Close() }()
+	data, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return nil, resp.StatusCode, fmt.Errorf("reading response: %w", err)
+	}
+	return data, resp.StatusCode, nil
+}
+
+// baselineToolArgs holds the arguments of every baseline tool.
+type baselineToolArgs struct {
+	Path    string `json:"path"`
+	Content string `json:"content"`
+	Command string `json:"command"`
+}
+
+// runBaselineTool runs one tool call in workspace and returns the output for
+// the model. Failures are reported to the model, not returned.
+func runBaselineTool(ctx context.Context, workspace, name string, args baselineToolArgs) string {
+	switch name {
+	case "read_file":
+		path, err := baselinePath(workspace, args.Path)
+		if err != nil {
+			return "error: " + err.Error()
+		}
+		data, err := os.ReadFile(path)
+		if err != nil {
+			return "error: " + err.Error()
+		}
+		return truncateToolOutput(string(data))
+	case "write_file":
+		path, err := baselinePath(workspace, args.Path)
+		if err != nil {
+			return "error: " + err.Error()
+		}
+		if err = os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			return "error: " + err.Error()
+		}
+		if err = os.WriteFile(path, []byte(args.Content), 0o644); err != nil {
+			return "error: " + err.Error()
+		}
+		return fmt.Sprintf("wrote %d bytes to %s", len(args.Content), args.Path)
+	case "shell":
+		ctx, cancel := context.WithTimeout(ctx, baselineShellTimeout)
+		defer cancel()
+		cmd := exec.CommandContext(ctx, "sh", "-c", args.Command)
+		cmd.Dir = workspace
+		cmd.WaitDelay = time.Second
+		out, err := cmd.CombinedOutput()
+		status := "exit status 0"
+		if err != nil {
+			status = err.Error()
+			if errors.Is(ctx.Err(), context.DeadlineExceeded) {
+				status = fmt.Sprintf("timed out after %s", baselineShellTimeout)
+			}
+		}
+		return truncateToolOutput(string(out)) + "\n[" + status + "]"
+	}
+	return fmt.Sprintf("error: unknown tool %q", name)
+}
+
+// baselinePath resolves a tool path against workspace and rejects paths
+// outside it.
+func baselinePath(workspace, path string) (string, error) {
+	if path == "" {
+		return "", errors.New("path is required")
+	}
+	if !filepath.IsAbs(path) {
+		path = filepath.Join(workspace, path)
+	}
+	rel, err := filepath.Rel(workspace, filepath.Clean(path))
+	if err != nil || rel == ".." || strings.HasPrefix(rel, ".."+string(filepath.Separator)) {
+		return "", fmt.Errorf("%s is outside the workspace", path)
+	}
+	return filepath.Join(workspace, rel), nil
+}
+
+// truncateToolOutput keeps tool output within baselineMaxToolOutput.
+func truncateToolOutput(s string) string {
+	if len(s) <= baselineMaxToolOutput {
+		return s
+	}
+	return s[:baselineMaxToolOutput] + fmt.Sprintf("\n[output truncated, %d bytes omitted]", len(s)-baselineMaxToolOutput)
+}
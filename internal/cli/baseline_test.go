@@ -0,0 +1,118 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestBaselineAgentRun(t *testing.T) {
+	t.Parallel()
+
+	type request struct {
+		Model           string            `json:"model"`
+		Messages        []baselineMessage `json:"messages"`
+		ReasoningEffort string            `json:"reasoning_effort"`
+		Temperature     *float64          `json:"temperature"`
+		MinP            *float64          `json:"min_p"`
+	}
+	var requests []request
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/v1/chat/completions" {
+			t.Errorf("path = %q", r.URL.Path)
+		}
+		if auth := r.Header.Get("Authorization"); auth != "Bearer secret" {
+			t.Errorf("Authorization = %q", auth)
+		}
+		var req request
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			t.Errorf("decoding request: %v", err)
+		}
+		requests = append(requests, req)
+		message := map[string]any{"role": "assistant", "content": "Implemented Add."}
+		if len(requests) == 1 {
+			message = map[string]any{"role": "assistant", "tool_calls": []map[string]any{
+				{"id": "call_1", "type": "function", "function": map[string]string{"name": "write_file", "arguments": `{"path":"add.go","content":"package add\n"}`}},
+				{"id": "call_2", "type": "function", "function": map[string]string{"name": "read_file", "arguments": `{"path":"../secret"}`}},
+			}}
+		}
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{{"message": message}},
+			"usage":   map[string]any{"prompt_tokens": 100, "completion_tokens": 10, "prompt_tokens_details": map[string]int{"cached_tokens": 40}},
+		})
+	}))
+	defer srv.Close()
+
+	workspace := t.TempDir()
+	var out bytes.Buffer
+	agent := &baselineAgent{baseURL: srv.URL + "/v1", apiKey: "secret", maxSteps: 5, client: srv.Client(), out: &out}
+	temperature := 0.2
+	req := pluginRequest{Type: "task", Prompt: "Implement Add.", Workspace: workspace, Model: "qwen3-coder", Reasoning: "high"}
+	req.Temperature = &temperature
+	if err := agent.run(context.Background(), req); err != nil {
+		t.Fatal(err)
+	}
+
+	if data, err := os.ReadFile(filepath.Join(workspace, "add.go")); err != nil || string(data) != "package add\n" {
+		t.Errorf("add.go = %q, %v", data, err)
+	}
+	if len(requests) != 2 {
+		t.Fatalf("got %d requests, want 2", len(requests))
+	}
+	if r := requests[0]; r.Model != "qwen3-coder" || r.ReasoningEffort != "high" || r.Temperature == nil || *r.Temperature != 0.2 || r.MinP != nil {
+		t.Errorf("first request = %+v", requests[0])
+	}
+	msgs := requests[1].Messages
+	if len(msgs) != 5 || msgs[3].ToolCallID != "call_1" || !strings.Contains(msgs[4].Content, "outside the workspace") {
+		t.Errorf("second request messages = %+v", msgs)
+	}
+
+	var log bytes.Buffer
+	d := newPluginDecoder(&log, nil)
+	_, _ = d.Write(out.Bytes())
+	want := "[turn] 1\n" +
+		`[usage] {"input_tokens":100,"output_tokens":10,"cache_read_tokens":40}` + "\n" +
+		"[tool] write_file: add.go\n" +
+		"[tool] read_file: ../secret\n" +
+		"[turn] 2\n" +
+		`[usage] {"input_tokens":100,"output_tokens":10,"cache_read_tokens":40}` + "\n" +
+		`[done] {"status":"completed","message":"Implemented Add."}` + "\n"
+	if log.String() != want {
+		t.Errorf("events:\n%s\nwant:\n%s", log.String(), want)
+	}
+}
+
+func TestBaselinePath(t *testing.T) {
+	t.Parallel()
+
+	workspace := filepath.Join(t.TempDir(), "ws")
+	tests := []struct {
+		path string
+		want string
+	}{
+		{"main.go", filepath.Join(workspace, "main.go")},
+		{"pkg/../main.go", filepath.Join(workspace, "main.go")},
+		{filepath.Join(workspace, "a", "b.go"), filepath.Join(workspace, "a", "b.go")},
+		{"../escape.go", ""},
+		{"/etc/passwd", ""},
+		{"", ""},
+	}
+	for _, tc := range tests {
+		got, err := baselinePath(workspace, tc.path)
+		if tc.want == "" {
+			if err == nil {
+				t.Errorf("baselinePath(%q) = %q, want error", tc.path, got)
+			}
+			continue
+		}
+		if err != nil || got != tc.want {
+			t.Errorf("baselinePath(%q) = %q, %v; want %q", tc.path, got, err, tc.want)
+		}
+	}
+}
@@ -10,6 +10,9 @@ import (
 
 	"github.com/BurntSushi/toml"
 	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
 )
 
 // BatchConfig is the top-level structure of a batch TOML file.
@@ -20,19 +23,25 @@ type BatchConfig struct {
 
 // BatchDefaults holds default settings applied to all runs unless overridden.
 type BatchDefaults struct {
-	Tier           string `toml:"tier"`
-	Difficulty     string `toml:"difficulty"`
-	Lang           string `toml:"lang"`
-	Tasks          string `toml:"tasks"`
-	Timeout        int    `toml:"timeout"`
-	Parallel       int    `toml:"parallel"`
-	KeepWorkspaces bool   `toml:"keep_workspaces"`
-	UseMCPTools    bool   `toml:"use_mcp_tools"`
-	UseSkills      bool   `toml:"use_skills"`
-	DisableMCP     bool   `toml:"disable_mcp"`
-	NoSandbox      bool   `toml:"no_sandbox"`
-	Legacy         bool   `toml:"legacy"`
-	Repeat         int    `toml:"repeat"`
+	Tier           string   `toml:"tier"`
+	Difficulty     string   `toml:"difficulty"`
+	Lang           string   `toml:"lang"`
+	Tasks          string   `toml:"tasks"`
+	Filter         string   `toml:"filter"`
+	Exclude        string   `toml:"exclude"`
+	Tags           string   `toml:"tags"`
+	Timeout        int      `toml:"timeout"`
+	Parallel       int      `toml:"parallel"`
+	KeepWorkspaces bool     `toml:"keep_workspaces"`
+	UseMCPTools    bool     `toml:"use_mcp_tools"`
+	UseSkills      bool     `toml:"use_skills"`
+	SkillPacks     []string `toml:"skill_packs"`
+	DisableMCP     bool     `toml:"disable_mcp"`
+	MCPServers     string   `toml:"mcp_servers"`
+	NoSandbox      bool     `toml:"no_sandbox"`
+	Legacy         bool     `toml:"legacy"`
+	Repeat         int      `toml:"repeat"`
+	samplingParams
 }
 
 // BatchRun defines a single run entry in the batch config.
@@ -86,15 +95,26 @@ The TOML file supports defaults that apply to all runs, with per-run overrides.`
 			Difficulty:     defaults.Difficulty,
 			Lang:           defaults.Lang,
 			Tasks:          defaults.Tasks,
+			Filter:         defaults.Filter,
+			Exclude:        defaults.Exclude,
+			Tags:           defaults.Tags,
 			Timeout:        defaults.Timeout,
 			Parallel:       defaults.Parallel,
 			KeepWorkspaces: defaults.KeepWorkspaces,
 			UseMCPTools:    defaults.UseMCPTools,
 			UseSkills:      defaults.UseSkills,
+			SkillPacks:     defaults.SkillPacks,
 			DisableMCP:     defaults.DisableMCP,
+			MCPServers:     defaults.MCPServers,
+			Temperature:    defaults.flag(config.SamplingTemperature),
+			TopP:           defaults.flag(config.SamplingTopP),
+			MinP:           defaults.flag(config.SamplingMinP),
 			NoSandbox:      defaults.NoSandbox,
 			Legacy:         defaults.Legacy,
 		}
+		if cfg != nil {
+			shared.Upload = cfg.Upload.URL
+		}
 		if shared.Timeout == 0 {
 			if cfg != nil && cfg.Harness.DefaultTimeout > 0 {
 				shared.Timeout = cfg.Harness.DefaultTimeout
@@ -170,6 +190,10 @@ The TOML file supports defaults that apply to all runs, with per-run overrides.`
 			return nil
 		}
 
+		if _, err := task.ParseFilter(shared.Filter, shared.Exclude, shared.Tags); err != nil {
+			return err
+		}
+
 		// Create runner.
 		r, err := newRunnerFromConfig()
 		if err != nil {
@@ -0,0 +1,259 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+const (
+	// benchmarkLogName is the benchmark command's output in the task output
+	// directory.
+	benchmarkLogName = "benchmark.log"
+
+	// criterionHomeDir is where criterion writes its estimates, relative to
+	// the workspace. Pointing CRITERION_HOME into the workspace keeps each
+	// task's measurements apart from the shared cargo target cache.
+	criterionHomeDir = ".sanity-criterion"
+)
+
+// BenchmarkResult is one criterion benchmark's estimates, in nanoseconds per
+// iteration. MaxMeanNs is the task's threshold for it, if any.
+type BenchmarkResult struct {
+	ID          string  `json:"id"`
+	MeanNs      float64 `json:"mean_ns"`
+	MeanLowerNs float64 `json:"mean_lower_ns"`
+	MeanUpperNs float64 `json:"mean_upper_ns"`
+	MedianNs    float64 `json:"median_ns"`
+	StdDevNs    float64 `json:"std_dev_ns"`
+	MaxMeanNs   float64 `json:"max_mean_ns,omitempty"`
+	Exceeded    bool    `json:"exceeded,omitempty"`
+}
+
+// BenchmarkRun is the outcome of running a performance task's benchmarks.
+// Missing lists thresholded benchmarks that produced no estimates.
+type BenchmarkRun struct {
+	Results  []BenchmarkResult `json:"results,omitempty"`
+	Missing  []string          `json:"missing,omitempty"`
+	Duration float64           `json:"duration_seconds"`
+	Error    string            `json:"error,omitempty"`
+}
+
+// WithinThresholds reports whether every benchmark ran and met its threshold.
+func (b *BenchmarkRun) WithinThresholds() bool {
+	if b.Error != "" || len(b.Missing) > 0 {
+		return false
+	}
+	for _, res := range b.Results {
+		if res.Exceeded {
+			return false
+		}
+	}
+	return true
+}
+
+// criterionEstimate is one statistic in criterion's estimates.json.
+type criterionEstimate struct {
+	PointEstimate      float64 `json:"point_estimate"`
+	ConfidenceInterval struct {
+		LowerBound float64 `json:"lower_bound"`
+		UpperBound float64 `json:"upper_bound"`
+	} `json:"confidence_interval"`
+}
+
+type criterionEstimates struct {
+	Mean   criterionEstimate `json:"mean"`
+	Median criterionEstimate `json:"median"`
+	StdDev criterionEstimate `json:"std_dev"`
+}
+
+// runTaskBenchmarks runs the task's [benchmark] command against a solution
+// that passed validation and records criterion's estimates. Benchmark results
+// are reported alongside, and never change, the functional result.
+func runTaskBenchmarks(ctx context.Context, result *EvalResult, r *runner.Runner, t *task.Task, workspaceDir, taskOutputDir string, validationTimeout int) {
+	if t.Benchmark == nil || !result.Passed {
+		return
+	}
+	timeout := t.Benchmark.Timeout
+	if timeout == 0 {
+		timeout = validationTimeout
+	}
+	home := filepath.Join(workspaceDir, criterionHomeDir)
+	_ = os.RemoveAll(home)
+	cmd := append([]string{"env", "CRITERION_HOME=/workspace/" + criterionHomeDir}, t.BenchmarkCommand()...)
+
+	session, duration, err := runValidationSession(ctx, r, t, workspaceDir, timeout, cmd)
+	run := &BenchmarkRun{Duration: duration}
+	result.Benchmark = run
+	rawOutput, exitCode, _, _ := lastSessionAttempt(session)
+	writeBenchmarkLog(filepath.Join(taskOutputDir, benchmarkLogName), rawOutput, cmd, exitCode, duration, err)
+	switch {
+	case err != nil:
+		run.Error = err.Error()
+	case exitCode != 0:
+		run.Error = fmt.Sprintf("benchmark command exited with code %d", exitCode)
+	default:
+		run.Results, err = readCriterionEstimates(home)
+		if err != nil {
+			run.Error = err.Error()
+			break
+		}
+		applyBenchmarkThresholds(run, t.Benchmark.Thresholds)
+	}
+	if !run.WithinThresholds() {
+		logger.Warn("benchmarks did not meet thresholds", "task", t.ID(), "error", run.Error, "missing", run.Missing)
+	}
+}
+
+// readCriterionEstimates collects the latest estimates of every benchmark
+// criterion wrote below home. Benchmarks are identified by the full_id in
+// criterion's benchmark.json, or by their directory when it is absent.
+func readCriterionEstimates(home string) ([]BenchmarkResult, error) {
+	var results []BenchmarkResult
+	err := filepath.WalkDir(home, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if d.IsDir() || d.Name() != "estimates.json" || filepath.Base(filepath.Dir(path)) != "new" {
+			return nil
+		}
+		data, err := os.ReadFile(path)
+		if err != nil {
+			return err
+		}
+		var est criterionEstimates
+		if err := json.Unmarshal(data, &est); err != nil {
+			return fmt.Errorf("parsing %s: %w", path, err)
+		}
+		results = append(results, BenchmarkResult{
+			ID:          criterionBenchmarkID(home, filepath.Dir(path)),
+			MeanNs:      est.Mean.PointEstimate,
+			MeanLowerNs: est.Mean.ConfidenceInterval.LowerBound,
+			MeanUpperNs: est.Mean.ConfidenceInterval.UpperBound,
+			MedianNs:    est.Median.PointEstimate,
+			StdDevNs:    est.StdDev.PointEstimate,
+		})
+		return nil
+	})
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil, errors.New("benchmark produced no criterion estimates")
+	}
+	if err != nil {
+		return nil, fmt.Errorf("reading criterion estimates: %w", err)
+	}
+	sort.Slice(results, func(i, j int) bool { return results[i].ID < results[j].ID })
+	return results, nil
+}
+
+// criterionBenchmarkID names the benchmark whose latest measurements are in
+// newDir.
+func criterionBenchmarkID(home, newDir string) string {
+	var meta struct {
+		FullID string `json:"full_id"`
+	}
+	if data, err := os.ReadFile(filepath.Join(newDir, "benchmark.json")); err == nil {
+		if json.Unmarshal(data, &meta) == nil && meta.FullID != "" {
+			return meta.FullID
+		}
+	}
+	rel, err := filepath.Rel(home, filepath.Dir(newDir))
+	if err != nil {
+		return filepath.Base(filepath.Dir(newDir))
+	}
+	return filepath.ToSlash(rel)
+}
+
+// applyBenchmarkThresholds compares each benchmark's mean with the task's
+// threshold for it and records thresholded benchmarks that did not run.
+func applyBenchmarkThresholds(run *BenchmarkRun, thresholds map[string]float64) {
+	seen := make(map[string]bool, len(run.Results))
+	for i := range run.Results {
+		res := &run.Results[i]
+		seen[res.ID] = true
+		if maxNs, ok := thresholds[res.ID]; ok {
+			res.MaxMeanNs = maxNs
+			res.Exceeded = res.MeanNs > maxNs
+		}
+	}
+	for id := range thresholds {
+		if !seen[id] {
+			run.Missing = append(run.Missing, id)
+		}
+	}
+	sort.Strings(run.Missing)
+}
+
+func writeBenchmarkLog(path, rawOutput string, command []string, exitCode int, durationSeconds float64, runErr error) {
+	var sb strings.Builder
+	if rawOutput != "" {
+		sb.WriteString(strings.TrimRight(rawOutput, "\n"))
+		sb.WriteString("\n\n")
+	}
+	fmt.Fprintf(&sb, "HARNESS: benchmark command=%q exit_code=%d duration_seconds=%.3f\n",
+		strings.Join(command, " "), exitCode, durationSeconds)
+	if runErr != nil {
+		fmt.Fprintf(&sb, "HARNESS: benchmark error=%q\n", runErr.Error())
+	}
+	_ = os.WriteFile(path, []byte(sb.String()), 0o644)
+}
+
+// formatBenchmarkNs renders a duration in nanoseconds with a readable unit.
+func formatBenchmarkNs(ns float64) string {
+	switch {
+	case ns >= 1e9:
+		return fmt.Sprintf("%.2f s", ns/1e9)
+	case ns >= 1e6:
+		return fmt.Sprintf("%.2f ms", ns/1e6)
+	case ns >= 1e3:
+		return fmt.Sprintf("%.2f µs", ns/1e3)
+	default:
+		return fmt.Sprintf("%.2f ns", ns)
+	}
+}
+
+// writeReportBenchmarks lists the criterion estimates of performance tasks.
+func writeReportBenchmarks(sb *strings.Builder, summary EvalSummary) {
+	if summary.BenchmarkedTasks == 0 {
+		return
+	}
+	sb.WriteString("## Benchmarks\n\n")
+	fmt.Fprintf(sb, "Criterion estimates for passing performance tasks. %d of %d benchmarked tasks met every threshold. "+
+		"Benchmarks do not affect pass rate or weighted score; raw output is kept in `%s`.\n\n",
+		summary.BenchmarkedTasks-summary.BenchmarksOverThreshold, summary.BenchmarkedTasks, benchmarkLogName)
+	sb.WriteString("| Task | Benchmark | Mean | 95% CI | Median | Threshold | Status |\n")
+	sb.WriteString("|------|-----------|------|--------|--------|-----------|--------|\n")
+	for _, r := range summary.Results {
+		if r.Benchmark == nil {
+			continue
+		}
+		if r.Benchmark.Error != "" {
+			fmt.Fprintf(sb, "| %s | - | - | - | - | - | ❌ %s |\n", r.Task, strings.ReplaceAll(r.Benchmark.Error, "|", `\|`))
+			continue
+		}
+		for _, b := range r.Benchmark.Results {
+			threshold, status := "-", "✅"
+			if b.MaxMeanNs > 0 {
+				threshold = "≤ " + formatBenchmarkNs(b.MaxMeanNs)
+			}
+			if b.Exceeded {
+				status = "❌ too slow"
+			}
+			fmt.Fprintf(sb, "| %s | %s | %s | %s – %s | %s | %s | %s |\n",
+				r.Task, b.ID, formatBenchmarkNs(b.MeanNs), formatBenchmarkNs(b.MeanLowerNs), formatBenchmarkNs(b.MeanUpperNs),
+				formatBenchmarkNs(b.MedianNs), threshold, status)
+		}
+		for _, id := range r.Benchmark.Missing {
+			fmt.Fprintf(sb, "| %s | %s | - | - | - | - | ❌ not run |\n", r.Task, id)
+		}
+	}
+	sb.WriteString("\n")
+}
@@ -0,0 +1,107 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+const criterionEstimatesJSON = `{
+  "mean": {"confidence_interval": {"confidence_level": 0.95, "lower_bound": 1180.5, "upper_bound": 1260.1}, "point_estimate": 1215.3, "standard_error": 20.2},
+  "median": {"confidence_interval": {"confidence_level": 0.95, "lower_bound": 1170.0, "upper_bound": 1230.0}, "point_estimate": 1201.7, "standard_error": 15.1},
+  "median_abs_dev": {"confidence_interval": {"confidence_level": 0.95, "lower_bound": 10.0, "upper_bound": 30.0}, "point_estimate": 18.4, "standard_error": 5.0},
+  "slope": null,
+  "std_dev": {"confidence_interval": {"confidence_level": 0.95, "lower_bound": 40.0, "upper_bound": 90.0}, "point_estimate": 61.9, "standard_error": 12.3}
+}`
+
+func writeCriterionBenchmark(t *testing.T, home, dir, fullID, estimates string) {
+	t.Helper()
+	for _, sub := range []string{"new", "base"} {
+		newDir := filepath.Join(home, filepath.FromSlash(dir), sub)
+		if err := os.MkdirAll(newDir, 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(filepath.Join(newDir, "estimates.json"), []byte(estimates), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		if fullID != "" {
+			meta := `{"group_id": "x", "full_id": "` + fullID + `"}`
+			if err := os.WriteFile(filepath.Join(newDir, "benchmark.json"), []byte(meta), 0o644); err != nil {
+				t.Fatal(err)
+			}
+		}
+	}
+}
+
+func TestReadCriterionEstimates(t *testing.T) {
+	t.Parallel()
+
+	home := t.TempDir()
+	writeCriterionBenchmark(t, home, "buffer/push_1000", "buffer/push 1000", criterionEstimatesJSON)
+	writeCriterionBenchmark(t, home, "pop", "", strings.ReplaceAll(criterionEstimatesJSON, "1215.3", "5000.0"))
+	if err := os.MkdirAll(filepath.Join(home, "report"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+
+	results, err := readCriterionEstimates(home)
+	if err != nil {
+		t.Fatalf("readCriterionEstimates() error = %v", err)
+	}
+	if len(results) != 2 {
+		t.Fatalf("got %d results, want 2: %+v", len(results), results)
+	}
+	push := results[0]
+	if push.ID != "buffer/push 1000" || push.MeanNs != 1215.3 || push.MeanLowerNs != 1180.5 ||
+		push.MeanUpperNs != 1260.1 || push.MedianNs != 1201.7 || push.StdDevNs != 61.9 {
+		t.Fatalf("push = %+v", push)
+	}
+	if results[1].ID != "pop" || results[1].MeanNs != 5000 {
+		t.Fatalf("pop = %+v", results[1])
+	}
+
+	run := &BenchmarkRun{Results: results}
+	applyBenchmarkThresholds(run, map[string]float64{"buffer/push 1000": 2000, "pop": 4000, "peek": 100})
+	if run.Results[0].Exceeded || run.Results[0].MaxMeanNs != 2000 || !run.Results[1].Exceeded {
+		t.Fatalf("thresholds not applied: %+v", run.Results)
+	}
+	if len(run.Missing) != 1 || run.Missing[0] != "peek" || run.WithinThresholds() {
+		t.Fatalf("run = %+v", run)
+	}
+
+	if _, err := readCriterionEstimates(filepath.Join(home, "missing")); err == nil {
+		t.Fatal("expected error when criterion wrote nothing")
+	}
+}
+
+func TestWriteReportBenchmarks(t *testing.T) {
+	t.Parallel()
+
+	summary := EvalSummary{
+		BenchmarkedTasks:        2,
+		BenchmarksOverThreshold: 1,
+		Results: []EvalResult{
+			{Task: "rust/circular-buffer", Passed: true, Benchmark: &BenchmarkRun{Results: []BenchmarkResult{
+				{ID: "push", MeanNs: 1215.3, MeanLowerNs: 1180.5, MeanUpperNs: 1260.1, MedianNs: 1201.7, MaxMeanNs: 2000},
+			}}},
+			{Task: "rust/regex-lite", Passed: true, Benchmark: &BenchmarkRun{Error: "benchmark command exited with code 101"}},
+			{Task: "rust/macros"},
+		},
+	}
+	var sb strings.Builder
+	writeReportBenchmarks(&sb, summary)
+	out := sb.String()
+	for _, want := range []string{
+		"## Benchmarks",
+		"1 of 2 benchmarked tasks met every threshold",
+		"| rust/circular-buffer | push | 1.22 µs | 1.18 µs – 1.26 µs | 1.20 µs | ≤ 2.00 µs | ✅ |",
+		"| rust/regex-lite | - | - | - | - | - | ❌ benchmark command exited with code 101 |",
+	} {
+		if !strings.Contains(out, want) {
+			t.Errorf("report missing %q:\n%s", want, out)
+		}
+	}
+	if strings.Contains(out, "rust/macros") {
+		t.Errorf("unbenchmarked task in report:\n%s", out)
+	}
+}
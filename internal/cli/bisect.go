@@ -0,0 +1,300 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// bisectVersionPlaceholder is replaced by the version under test in --agent,
+// --model, and --setup.
+const bisectVersionPlaceholder = "{version}"
+
+var (
+	bisectTask      string
+	bisectAgent     string
+	bisectModel     string
+	bisectReasoning string
+	bisectVersions  string
+	bisectSetup     string
+	bisectRuns      int
+	bisectTimeout   int
+	bisectOutput    string
+)
+
+// BisectProbe is the outcome of running the task at one version.
+type BisectProbe struct {
+	Version string `json:"version"`
+	Runs    int    `json:"runs"`
+	Passes  int    `json:"passes"`
+	Passed  bool   `json:"passed"`
+	Dir     string `json:"dir"`
+}
+
+// BisectReport is written to bisect.json. LastBefore and FirstChanged are the
+// adjacent versions between which the task's outcome flipped.
+type BisectReport struct {
+	Task         string        `json:"task"`
+	Agent        string        `json:"agent"`
+	Model        string        `json:"model,omitempty"`
+	Versions     []string      `json:"versions"`
+	Setup        string        `json:"setup,omitempty"`
+	Probes       []BisectProbe `json:"probes"`
+	LastBefore   string        `json:"last_before,omitempty"`
+	FirstChanged string        `json:"first_changed,omitempty"`
+	CreatedAt    string        `json:"created_at"`
+}
+
+var bisectCmd = &cobra.Command{
+	Use:   "bisect",
+	Short: "Find the agent version where a task's outcome changed",
+	Long: `Binary-searches an ordered list of versions for the one where a task started
+passing or failing.
+
+--versions lists the candidates oldest first. The first and last are run
+first and must disagree; the search then probes the midpoint until two
+adjacent versions disagree. A version passes when a majority of its --runs
+runs pass, which damps flaky agents.
+
+A version is selected by substituting it for {version} in --agent, --model,
+and --setup. --setup is a shell command run before each version's runs, e.g.
+to install that agent release or check out a task or harness revision; with
+no placeholder in --agent or --model, it is the only thing that changes.
+
+The search assumes the outcome changed once in the range. Every run's results
+are kept under --output, with bisect.json listing each probe.`,
+	Example: `  sanity bisect --task go/react --agent gemini --versions 0.8.0,0.9.0,0.9.1,0.10.0 \
+    --setup 'npm install -g @google/gemini-cli@{version}' --runs 3
+  sanity bisect --task rust/regex-lite --agent 'claude-{version}' --versions 1,2,3,4,5`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		versions := splitBisectVersions(bisectVersions)
+		if len(versions) < 2 {
+			return errors.New("--versions needs at least two versions")
+		}
+		if bisectRuns < 1 {
+			return errors.New("--runs must be at least 1")
+		}
+		if bisectSetup == "" && !strings.Contains(bisectAgent+bisectModel, bisectVersionPlaceholder) {
+			return fmt.Errorf("nothing varies between versions; use %s in --agent, --model, or --setup", bisectVersionPlaceholder)
+		}
+		timeout := bisectTimeout
+		if timeout == 0 {
+			timeout = cfg.Harness.DefaultTimeout
+		}
+
+		r, err := newRunnerFromConfig()
+		if err != nil {
+			return err
+		}
+		defer func() { _ = r.Close() }()
+		evalSandboxActive = initSandbox()
+		if restoreFn, err := protectTasksDir(); err != nil {
+			logger.Warn("failed to protect tasks directory", "error", err)
+		} else if restoreFn != nil {
+			defer restoreFn()
+		}
+		interruptCtx, interruptCancel := setupInterruptHandler()
+		defer interruptCancel()
+
+		timestamp := time.Now().Format("2006-01-02T150405")
+		outDir := bisectOutput
+		if outDir == "" {
+			outDir = filepath.Join(defaultEvalResultsDir, "bisect-"+timestamp)
+		}
+		if err := os.MkdirAll(outDir, 0o755); err != nil {
+			return fmt.Errorf("creating output directory: %w", err)
+		}
+
+		report := BisectReport{
+			Task:      bisectTask,
+			Agent:     bisectAgent,
+			Model:     bisectModel,
+			Versions:  versions,
+			Setup:     bisectSetup,
+			CreatedAt: time.Now().Format(time.RFC3339),
+		}
+		probe := func(version string) (bool, error) {
+			if checkInterrupted(interruptCtx) {
+				return false, errors.New("interrupted")
+			}
+			p, err := runBisectProbe(interruptCtx, version, timeout, filepath.Join(outDir, sanitizeModel(version)), timestamp, r)
+			if err != nil {
+				return false, fmt.Errorf("version %s: %w", version, err)
+			}
+			report.Probes = append(report.Probes, p)
+			writeBisectReport(outDir, report)
+			fmt.Printf("\n ▶ %s: %s (%d/%d runs passed)\n\n", version, passLabel(p.Passed), p.Passes, p.Runs)
+			return p.Passed, nil
+		}
+
+		before, changed, err := bisectSearch(versions, probe)
+		if err != nil {
+			return err
+		}
+		report.LastBefore, report.FirstChanged = versions[before], versions[changed]
+		writeBisectReport(outDir, report)
+
+		fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+		fmt.Printf(" %s %s at %s and %s at %s (%d probes)\n", bisectTask,
+			outcomeVerb(probedPassed(report.Probes, report.LastBefore)), report.LastBefore,
+			outcomeVerb(probedPassed(report.Probes, report.FirstChanged)), report.FirstChanged, len(report.Probes))
+		fmt.Printf(" Results saved to: %s\n", outDir)
+		return nil
+	},
+}
+
+func init() {
+	bisectCmd.Flags().StringVar(&bisectTask, "task", "", "task that regressed, e.g. go/react (required)")
+	bisectCmd.Flags().StringVar(&bisectAgent, "agent", "", "agent to run; may contain {version} (required)")
+	bisectCmd.Flags().StringVar(&bisectModel, "model", "", "model to use; may contain {version}")
+	bisectCmd.Flags().StringVar(&bisectReasoning, "reasoning", "", "reasoning effort passed to the agent")
+	bisectCmd.Flags().StringVar(&bisectVersions, "versions", "", "comma-separated versions to search, oldest first (required)")
+	bisectCmd.Flags().StringVar(&bisectSetup, "setup", "", "shell command run before each version's runs; may contain {version}")
+	bisectCmd.Flags().IntVar(&bisectRuns, "runs", 1, "runs per version; a version passes when most of its runs pass")
+	bisectCmd.Flags().IntVar(&bisectTimeout, "timeout", 0, "agent timeout in seconds (default: from config)")
+	bisectCmd.Flags().StringVarP(&bisectOutput, "output", "o", "", "output directory (default: eval-results/bisect-<timestamp>)")
+	_ = bisectCmd.MarkFlagRequired("task")
+	_ = bisectCmd.MarkFlagRequired("agent")
+	_ = bisectCmd.MarkFlagRequired("versions")
+}
+
+func splitBisectVersions(s string) []string {
+	var versions []string
+	for _, v := range strings.Split(s, ",") {
+		if v = strings.TrimSpace(v); v != "" {
+			versions = append(versions, v)
+		}
+	}
+	return versions
+}
+
+// bisectSearch probes the ends of versions, then binary-searches for adjacent
+// indexes whose outcomes differ. Each version is probed at most once.
+func bisectSearch(versions []string, probe func(string) (bool, error)) (before, changed int, err error) {
+	seen := make(map[int]bool)
+	outcome := func(i int) (bool, error) {
+		if passed, ok := seen[i]; ok {
+			return passed, nil
+		}
+		passed, err := probe(versions[i])
+		if err != nil {
+			return false, err
+		}
+		seen[i] = passed
+		return passed, nil
+	}
+
+	lo, hi := 0, len(versions)-1
+	first, err := outcome(lo)
+	if err != nil {
+		return 0, 0, err
+	}
+	last, err := outcome(hi)
+	if err != nil {
+		return 0, 0, err
+	}
+	if first == last {
+		return 0, 0, fmt.Errorf("the task %s at both %s and %s; nothing to bisect", outcomeVerb(first), versions[lo], versions[hi])
+	}
+	for hi-lo > 1 {
+		mid := (lo + hi) / 2
+		passed, err := outcome(mid)
+		if err != nil {
+			return 0, 0, err
+		}
+		if passed == first {
+			lo = mid
+		} else {
+			hi = mid
+		}
+	}
+	return lo, hi, nil
+}
+
+// runBisectProbe runs the setup command for version, then the task --runs
+// times with version substituted into the agent and model.
+func runBisectProbe(ctx context.Context, version string, timeout int, dir, timestamp string, r *runner.Runner) (BisectProbe, error) {
+	probe := BisectProbe{Version: version, Runs: bisectRuns, Dir: dir}
+	expand := func(s string) string { return strings.ReplaceAll(s, bisectVersionPlaceholder, version) }
+
+	if bisectSetup != "" {
+		fmt.Printf(" $ %s\n", expand(bisectSetup))
+		setup := exec.CommandContext(ctx, "sh", "-c", expand(bisectSetup))
+		setup.Stdout, setup.Stderr = os.Stdout, os.Stderr
+		if err := setup.Run(); err != nil {
+			return probe, fmt.Errorf("setup: %w", err)
+		}
+	}
+
+	spec := RunSpec{Agent: expand(bisectAgent), Model: expand(bisectModel), Reasoning: bisectReasoning}
+	agentCfg := cfg.GetAgent(spec.Agent)
+	if agentCfg == nil {
+		return probe, fmt.Errorf("unknown agent: %s", spec.Agent)
+	}
+	if _, err := exec.LookPath(agentCfg.Command); err != nil {
+		return probe, fmt.Errorf("agent %q binary %q not found in PATH", spec.Agent, agentCfg.Command)
+	}
+	// Tasks are reloaded each time, since setup may check out another revision.
+	allTasks, err := r.ListTasks()
+	if err != nil {
+		return probe, fmt.Errorf("listing tasks: %w", err)
+	}
+	t, err := task.ResolveRef(allTasks, bisectTask)
+	if err != nil {
+		return probe, err
+	}
+
+	shared := SharedConfig{Tasks: t.ID(), Timeout: timeout, Parallel: 1}
+	for run := 1; run <= bisectRuns; run++ {
+		runDir := filepath.Join(dir, fmt.Sprintf("run-%d", run))
+		summary, _, err := evalRunSingle(ctx, spec, shared, []*task.Task{t}, []*task.Task{t}, runDir, timestamp, r, false, nil, nil, nil, nil, nil)
+		if err != nil {
+			return probe, err
+		}
+		if summary == nil || len(summary.Results) == 0 {
+			return probe, fmt.Errorf("run %d produced no result (external failure?); see %s", run, runDir)
+		}
+		if summary.Results[0].Passed {
+			probe.Passes++
+		}
+	}
+	probe.Passed = probe.Passes*2 > probe.Runs
+	return probe, nil
+}
+
+func outcomeVerb(passed bool) string {
+	if passed {
+		return "passes"
+	}
+	return "fails"
+}
+
+func probedPassed(probes []BisectProbe, version string) bool {
+	for _, p := range probes {
+		if p.Version == version {
+			return p.Passed
+		}
+	}
+	return false
+}
+
+func writeBisectReport(dir string, report BisectReport) {
+	data, err := json.MarshalIndent(report, "", "  ")
+	if err != nil {
+		return
+	}
+	if err := os.WriteFile(filepath.Join(dir, "bisect.json"), data, 0o644); err != nil {
+		logger.Warn("failed to write bisect.json", "error", err)
+	}
+}
@@ -0,0 +1,51 @@
+package cli
+
+import (
+	"errors"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestBisectSearch(t *testing.T) {
+	t.Parallel()
+
+	versions := []string{"1.0", "1.1", "1.2", "1.3", "1.4", "1.5", "1.6"}
+	var probed []string
+	probe := func(v string) (bool, error) {
+		probed = append(probed, v)
+		return v < "1.4", nil // Regressed in 1.4.
+	}
+	before, changed, err := bisectSearch(versions, probe)
+	if err != nil {
+		t.Fatalf("bisectSearch() error = %v", err)
+	}
+	if versions[before] != "1.3" || versions[changed] != "1.4" {
+		t.Fatalf("bisectSearch() = %s..%s, want 1.3..1.4", versions[before], versions[changed])
+	}
+	if want := []string{"1.0", "1.6", "1.3", "1.4"}; !reflect.DeepEqual(probed, want) {
+		t.Errorf("probed %v, want %v", probed, want)
+	}
+
+	// A fix is found the same way.
+	_, changed, err = bisectSearch(versions, func(v string) (bool, error) { return v >= "1.1", nil })
+	if err != nil || versions[changed] != "1.1" {
+		t.Errorf("fix bisect = %d, %v; want 1.1", changed, err)
+	}
+
+	if _, _, err := bisectSearch(versions, func(string) (bool, error) { return true, nil }); err == nil || !strings.Contains(err.Error(), "nothing to bisect") {
+		t.Errorf("expected nothing-to-bisect error, got %v", err)
+	}
+	boom := errors.New("setup failed")
+	if _, _, err := bisectSearch(versions, func(string) (bool, error) { return false, boom }); !errors.Is(err, boom) {
+		t.Errorf("expected probe error, got %v", err)
+	}
+}
+
+func TestSplitBisectVersions(t *testing.T) {
+	t.Parallel()
+
+	if got := splitBisectVersions(" 0.8.0, 0.9.0,,0.10.0 "); !reflect.DeepEqual(got, []string{"0.8.0", "0.9.0", "0.10.0"}) {
+		t.Errorf("splitBisectVersions() = %v", got)
+	}
+}
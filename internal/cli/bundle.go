@@ -0,0 +1,580 @@
+package cli
+
+import (
+	"archive/tar"
+	"bytes"
+	"compress/gzip"
+	"context"
+	"crypto/ed25519"
+	"crypto/x509"
+	"encoding/base64"
+	"encoding/json"
+	"encoding/pem"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path"
+	"path/filepath"
+	"runtime"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/runner"
+)
+
+const (
+	bundleManifestName  = "bundle.json"
+	bundleSignatureName = "bundle.sig"
+	bundleRunPrefix     = "run/"
+	bundleFormatVersion = "1"
+)
+
+var (
+	bundleOutput  string
+	bundleSignKey string
+	bundlePubKey  string
+)
+
+// BundleManifest describes the contents of a submission bundle. It is the
+// signed payload: every other file in the archive is pinned by its hash here.
+type BundleManifest struct {
+	Version     string                 `json:"version"`
+	CreatedAt   string                 `json:"created_at"`
+	Harness     BundleHarness          `json:"harness"`
+	Environment BundleEnvironment      `json:"environment"`
+	Images      map[string]BundleImage `json:"images,omitempty"`
+	Agent       string                 `json:"agent"`
+	Model       string                 `json:"model,omitempty"`
+	Timestamp   string                 `json:"timestamp"`
+	Files       []BundleFile           `json:"files"`
+}
+
+// BundleHarness identifies the harness build that produced the run.
+type BundleHarness struct {
+	Version   string `json:"version"`
+	Commit    string `json:"commit"`
+	BuildDate string `json:"build_date"`
+}
+
+// BundleEnvironment records the host the bundle was created on.
+type BundleEnvironment struct {
+	OS        string `json:"os"`
+	Arch      string `json:"arch"`
+	GoVersion string `json:"go_version"`
+	Hostname  string `json:"hostname,omitempty"`
+}
+
+// BundleImage records the resolved container image for a language.
+type BundleImage struct {
+	Ref         string   `json:"ref"`
+	ID          string   `json:"id,omitempty"`
+	RepoDigests []string `json:"repo_digests,omitempty"`
+}
+
+// BundleFile pins one file of the eval run by size and BLAKE3 hash.
+type BundleFile struct {
+	Path string `json:"path"`
+	Size int64  `json:"size"`
+	Hash string `json:"hash"`
+}
+
+// BundleSignature is stored as bundle.sig and signs the exact bytes of bundle.json.
+type BundleSignature struct {
+	Algorithm string `json:"algorithm"`
+	PublicKey string `json:"public_key"`
+	Signature string `json:"signature"`
+}
+
+var submitBundleCmd = &cobra.Command{
+	Use:   "submit-bundle <eval-dir>",
+	Short: "Package an eval run into a verifiable submission bundle",
+	Long: `Packages an eval result directory into a .tar.gz bundle for leaderboard
+submission. The bundle contains every file of the run plus bundle.json, which
+records the harness build, host environment, container image digests, and a
+BLAKE3 hash of each file.
+
+With --sign-key, bundle.json is signed with an Ed25519 private key (PKCS#8 PEM)
+and the signature is stored as bundle.sig. A key can be created with:
+
+  openssl genpkey -algorithm ed25519 -out sanity-key.pem
+  openssl pkey -in sanity-key.pem -pubout -out sanity-key.pub.pem`,
+	Example: `  sanity submit-bundle eval-results/2026-01-07T120000-gemini
+  sanity submit-bundle eval-results/2026-01-07T120000-gemini --sign-key sanity-key.pem -o gemini.tar.gz`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		evalDir := args[0]
+		summary, err := loadSummaryFromDir(evalDir)
+		if err != nil {
+			return err
+		}
+		if _, err := os.Stat(filepath.Join(evalDir, "attestation.json")); err != nil {
+			return fmt.Errorf("eval directory has no attestation.json: %w", err)
+		}
+
+		var signer ed25519.PrivateKey
+		if bundleSignKey != "" {
+			signer, err = loadEd25519PrivateKey(bundleSignKey)
+			if err != nil {
+				return err
+			}
+		}
+
+		manifest := BundleManifest{
+			Version:   bundleFormatVersion,
+			CreatedAt: time.Now().UTC().Format(time.RFC3339),
+			Harness:   BundleHarness{Version: Version, Commit: Commit, BuildDate: BuildDate},
+			Environment: BundleEnvironment{
+				OS:        runtime.GOOS,
+				Arch:      runtime.GOARCH,
+				GoVersion: runtime.Version(),
+			},
+			Images:    collectBundleImages(summary),
+			Agent:     summary.Agent,
+			Model:     summary.Model,
+			Timestamp: summary.Timestamp,
+		}
+		if host, err := os.Hostname(); err == nil {
+			manifest.Environment.Hostname = host
+		}
+
+		out := bundleOutput
+		if out == "" {
+			out = filepath.Clean(evalDir) + ".bundle.tar.gz"
+		}
+		if err := writeSubmissionBundle(evalDir, out, manifest, signer); err != nil {
+			return err
+		}
+
+		data, err := os.ReadFile(out)
+		if err != nil {
+			return fmt.Errorf("reading bundle: %w", err)
+		}
+		fmt.Printf(" Bundle saved to: %s\n", out)
+		fmt.Printf(" Checksum:        %s\n", hashBytes(data))
+		if signer != nil {
+			fmt.Println(" Signed:          ed25519")
+		}
+		return nil
+	},
+}
+
+var verifyBundleCmd = &cobra.Command{
+	Use:   "verify-bundle <bundle.tar.gz>",
+	Short: "Verify a submission bundle created by submit-bundle",
+	Long: `Verifies a submission bundle: every file in the archive must match the hash
+recorded in bundle.json, no unlisted files may be present, the run's
+attestation must match its summary.json, and (when present) the Ed25519
+signature over bundle.json must be valid.
+
+With --pubkey, the bundle must be signed by that key.`,
+	Example: `  sanity verify-bundle gemini.tar.gz
+  sanity verify-bundle gemini.tar.gz --pubkey sanity-key.pub.pem`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		var trusted ed25519.PublicKey
+		if bundlePubKey != "" {
+			var err error
+			trusted, err = loadEd25519PublicKey(bundlePubKey)
+			if err != nil {
+				return err
+			}
+		}
+
+		manifest, problems, err := verifySubmissionBundle(args[0], trusted)
+		if err != nil {
+			return err
+		}
+
+		fmt.Println()
+		fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+		fmt.Println(" SANITY HARNESS - Bundle Verification")
+		fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+		fmt.Println()
+		fmt.Printf(" Agent:     %s\n", manifest.Agent)
+		if manifest.Model != "" {
+			fmt.Printf(" Model:     %s\n", manifest.Model)
+		}
+		fmt.Printf(" Timestamp: %s\n", manifest.Timestamp)
+		fmt.Printf(" Harness:   %s (%s, built %s)\n", manifest.Harness.Version, manifest.Harness.Commit, manifest.Harness.BuildDate)
+		fmt.Printf(" Host:      %s/%s (%s)\n", manifest.Environment.OS, manifest.Environment.Arch, manifest.Environment.GoVersion)
+		fmt.Printf(" Files:     %d\n", len(manifest.Files))
+		langs := make([]string, 0, len(manifest.Images))
+		for lang := range manifest.Images {
+			langs = append(langs, lang)
+		}
+		sort.Strings(langs)
+		for _, lang := range langs {
+			img := manifest.Images[lang]
+			digest := img.ID
+			if len(img.RepoDigests) > 0 {
+				digest = img.RepoDigests[0]
+			}
+			fmt.Printf(" Image:     %-10s %s\n", lang, digest)
+		}
+		fmt.Println()
+
+		if len(problems) > 0 {
+			for _, p := range problems {
+				fmt.Printf(" ✗ %s\n", p)
+			}
+			fmt.Println()
+			return &exitError{code: 1}
+		}
+		fmt.Println(" ✓ Bundle is intact and all checks passed")
+		fmt.Println()
+		return nil
+	},
+}
+
+func init() {
+	submitBundleCmd.Flags().StringVarP(&bundleOutput, "output", "o", "", "bundle path (default: <eval-dir>.bundle.tar.gz)")
+	submitBundleCmd.Flags().StringVar(&bundleSignKey, "sign-key", "", "Ed25519 private key (PKCS#8 PEM) used to sign the bundle")
+	verifyBundleCmd.Flags().StringVar(&bundlePubKey, "pubkey", "", "require a signature from this Ed25519 public key (PKIX PEM)")
+}
+
+// collectBundleImages resolves the configured image for each language in the
+// run. Image digests are best-effort: without Docker only the ref is recorded.
+func collectBundleImages(summary *EvalSummary) map[string]BundleImage {
+	if cfg == nil || len(summary.ByLanguage) == 0 {
+		return nil
+	}
+
+	docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+	if err != nil {
+		logger.Warn("docker unavailable; image digests will not be recorded", "error", err)
+	}
+	if docker != nil {
+		defer func() { _ = docker.Close() }()
+	}
+
+	images := make(map[string]BundleImage, len(summary.ByLanguage))
+	for lang := range summary.ByLanguage {
+		img := BundleImage{Ref: cfg.ImageForLanguage(lang)}
+		if img.Ref == "" {
+			continue
+		}
+		if docker != nil {
+			ctx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+			id, digests, err := docker.ImageDigest(ctx, img.Ref)
+			cancel()
+			if err != nil {
+				logger.Warn("failed to inspect image", "image", img.Ref, "error", err)
+			} else {
+				img.ID = id
+				img.RepoDigests = digests
+			}
+		}
+		images[lang] = img
+	}
+	return images
+}
+
+// writeSubmissionBundle writes evalDir, the manifest, and an optional signature
+// into a gzip-compressed tar archive at out.
+func writeSubmissionBundle(evalDir, out string, manifest BundleManifest, signer ed25519.PrivateKey) error {
+	type entry struct {
+		rel  string
+		data []byte
+	}
+	var entries []entry
+	err := filepath.WalkDir(evalDir, func(p string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		rel, err := filepath.Rel(evalDir, p)
+		if err != nil {
+			return err
+		}
+		data, err := os.ReadFile(p)
+		if err != nil {
+			return fmt.Errorf("reading %s: %w", rel, err)
+		}
+		entries = append(entries, entry{rel: filepath.ToSlash(rel), data: data})
+		return nil
+	})
+	if err != nil {
+		return fmt.Errorf("collecting run files: %w", err)
+	}
+
+	manifest.Files = make([]BundleFile, 0, len(entries))
+	for _, e := range entries {
+		manifest.Files = append(manifest.Files, BundleFile{
+			Path: e.rel,
+			Size: int64(len(e.data)),
+			Hash: hashBytes(e.data),
+		})
+	}
+
+	manifestData, err := json.MarshalIndent(manifest, "", "  ")
+	if err != nil {
+		return fmt.Errorf("marshal bundle manifest: %w", err)
+	}
+
+	f, err := os.Create(out)
+	if err != nil {
+		return fmt.Errorf("creating bundle: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+
+	gz := gzip.NewWriter(f)
+	tw := tar.NewWriter(gz)
+	modTime := time.Now()
+	writeEntry := func(name string, data []byte) error {
+		hdr := &tar.Header{Name: name, Mode: 0o644, Size: int64(len(data)), ModTime: modTime}
+		if err := tw.WriteHeader(hdr); err != nil {
+			return fmt.Errorf("writing %s header: %w", name, err)
+		}
+		if _, err := tw.Write(data); err != nil {
+			return fmt.Errorf("writing %s: %w", name, err)
+		}
+		return nil
+	}
+
+	if err := writeEntry(bundleManifestName, manifestData); err != nil {
+		return err
+	}
+	if signer != nil {
+		sigData, err := signBundleManifest(manifestData, signer)
+		if err != nil {
+			return err
+		}
+		if err := writeEntry(bundleSignatureName, sigData); err != nil {
+			return err
+		}
+	}
+	for _, e := range entries {
+		if err := writeEntry(bundleRunPrefix+e.rel, e.data); err != nil {
+			return err
+		}
+	}
+
+	if err := tw.Close(); err != nil {
+		return fmt.Errorf("closing tar: %w", err)
+	}
+	if err := gz.Close(); err != nil {
+		return fmt.Errorf("closing gzip: %w", err)
+	}
+	return f.Close()
+}
+
+// signBundleManifest returns the JSON-encoded bundle.sig for manifestData.
+func signBundleManifest(manifestData []byte, key ed25519.PrivateKey) ([]byte, error) {
+	pubDER, err := x509.MarshalPKIXPublicKey(key.Public())
+	if err != nil {
+		return nil, fmt.Errorf("encoding public key: %w", err)
+	}
+	sig := BundleSignature{
+		Algorithm: "ed25519",
+		PublicKey: base64.StdEncoding.EncodeToString(pubDER),
+		Signature: base64.StdEncoding.EncodeToString(ed25519.Sign(key, manifestData)),
+	}
+	data, err := json.MarshalIndent(sig, "", "  ")
+	if err != nil {
+		return nil, fmt.Errorf("marshal signature: %w", err)
+	}
+	return data, nil
+}
+
+// verifySubmissionBundle checks a bundle and returns its manifest along with a
+// list of human-readable problems. An error is returned only when the bundle
+// cannot be read at all.
+//
+//nolint:gocognit,gocyclo // Sequential checks over archive entries, manifest, signature, and attestation.
+func verifySubmissionBundle(bundlePath string, trusted ed25519.PublicKey) (*BundleManifest, []string, error) {
+	f, err := os.Open(bundlePath)
+	if err != nil {
+		return nil, nil, fmt.Errorf("opening bundle: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+
+	gz, err := gzip.NewReader(f)
+	if err != nil {
+		return nil, nil, fmt.Errorf("reading gzip: %w", err)
+	}
+	tr := tar.NewReader(gz)
+
+	var manifestData, sigData []byte
+	runFiles := make(map[string][]byte)
+	for {
+		hdr, err := tr.Next()
+		if errors.Is(err, io.EOF) {
+			break
+		}
+		if err != nil {
+			return nil, nil, fmt.Errorf("reading tar: %w", err)
+		}
+		if hdr.Typeflag != tar.TypeReg {
+			continue
+		}
+		data, err := io.ReadAll(tr)
+		if err != nil {
+			return nil, nil, fmt.Errorf("reading %s: %w", hdr.Name, err)
+		}
+		name := path.Clean(hdr.Name)
+		switch {
+		case name == bundleManifestName:
+			manifestData = data
+		case name == bundleSignatureName:
+			sigData = data
+		case strings.HasPrefix(name, bundleRunPrefix):
+			runFiles[strings.TrimPrefix(name, bundleRunPrefix)] = data
+		default:
+			runFiles[name] = data
+		}
+	}
+
+	if manifestData == nil {
+		return nil, nil, fmt.Errorf("bundle has no %s", bundleManifestName)
+	}
+	var manifest BundleManifest
+	if err := json.Unmarshal(manifestData, &manifest); err != nil {
+		return nil, nil, fmt.Errorf("parsing %s: %w", bundleManifestName, err)
+	}
+
+	var problems []string
+
+	// Signature.
+	switch {
+	case sigData == nil && trusted != nil:
+		problems = append(problems, "bundle is unsigned but --pubkey was given")
+	case sigData != nil:
+		if err := verifyBundleSignature(manifestData, sigData, trusted); err != nil {
+			problems = append(problems, err.Error())
+		}
+	}
+
+	// File hashes.
+	listed := make(map[string]bool, len(manifest.Files))
+	for _, bf := range manifest.Files {
+		listed[bf.Path] = true
+		data, ok := runFiles[bf.Path]
+		if !ok {
+			problems = append(problems, fmt.Sprintf("missing file: %s", bf.Path))
+			continue
+		}
+		if hashBytes(data) != bf.Hash || int64(len(data)) != bf.Size {
+			problems = append(problems, fmt.Sprintf("hash mismatch: %s", bf.Path))
+		}
+	}
+	var extra []string
+	for name := range runFiles {
+		if !listed[name] {
+			extra = append(extra, name)
+		}
+	}
+	sort.Strings(extra)
+	for _, name := range extra {
+		problems = append(problems, fmt.Sprintf("unlisted file: %s", name))
+	}
+
+	// Attestation results hash, as checked by `sanity verify`.
+	var summary EvalSummary
+	var attestation EvalAttestation
+	switch {
+	case json.Unmarshal(runFiles["summary.json"], &summary) != nil:
+		problems = append(problems, "summary.json missing or unreadable")
+	case json.Unmarshal(runFiles["attestation.json"], &attestation) != nil:
+		problems = append(problems, "attestation.json missing or unreadable")
+	default:
+		resultsJSON, _ := json.Marshal(summary.Results)
+		if hashBytes(resultsJSON) != attestation.Integrity.ResultsHash {
+			problems = append(problems, "attestation results hash does not match summary.json")
+		}
+	}
+
+	return &manifest, problems, nil
+}
+
+// verifyBundleSignature checks sigData against manifestData. If trusted is
+// non-nil, the embedded public key must also equal it.
+func verifyBundleSignature(manifestData, sigData []byte, trusted ed25519.PublicKey) error {
+	var sig BundleSignature
+	if err := json.Unmarshal(sigData, &sig); err != nil {
+		return fmt.Errorf("parsing %s: %w", bundleSignatureName, err)
+	}
+	if sig.Algorithm != "ed25519" {
+		return fmt.Errorf("unsupported signature algorithm %q", sig.Algorithm)
+	}
+	pubDER, err := base64.StdEncoding.DecodeString(sig.PublicKey)
+	if err != nil {
+		return fmt.Errorf("decoding signature public key: %w", err)
+	}
+	parsed, err := x509.ParsePKIXPublicKey(pubDER)
+	if err != nil {
+		return fmt.Errorf("parsing signature public key: %w", err)
+	}
+	pub, ok := parsed.(ed25519.PublicKey)
+	if !ok {
+		return fmt.Errorf("signature public key is not ed25519")
+	}
+	if trusted != nil && !bytes.Equal(pub, trusted) {
+		return fmt.Errorf("bundle was signed by a different key than --pubkey")
+	}
+	rawSig, err := base64.StdEncoding.DecodeString(sig.Signature)
+	if err != nil {
+		return fmt.Errorf("decoding signature: %w", err)
+	}
+	if !ed25519.Verify(pub, manifestData, rawSig) {
+		return fmt.Errorf("signature is invalid")
+	}
+	return nil
+}
+
+// loadEd25519PrivateKey reads a PKCS#8 PEM-encoded Ed25519 private key.
+func loadEd25519PrivateKey(keyPath string) (ed25519.PrivateKey, error) {
+	data, err := os.ReadFile(keyPath)
+	if err != nil {
+		return nil, fmt.Errorf("reading signing key: %w", err)
+	}
+	block, _ := pem.Decode(data)
+	if block == nil {
+		return nil, fmt.Errorf("signing key %s is not PEM encoded", keyPath)
+	}
+	parsed, err := x509.ParsePKCS8PrivateKey(block.Bytes)
+	if err != nil {
+		return nil, fmt.Errorf("parsing signing key: %w", err)
+	}
+	key, ok := parsed.(ed25519.PrivateKey)
+	if !ok {
+		return nil, fmt.Errorf("signing key %s is not an ed25519 key", keyPath)
+	}
+	return key, nil
+}
+
+// loadEd25519PublicKey reads a PKIX PEM-encoded Ed25519 public key.
+func loadEd25519PublicKey(keyPath string) (ed25519.PublicKey, error) {
+	data, err := os.ReadFile(keyPath)
+	if err != nil {
+		return nil, fmt.Errorf("reading public key: %w", err)
+	}
+	key, err := parseEd25519PublicKey(data)
+	if err != nil {
+		return nil, fmt.Errorf("public key %s: %w", keyPath, err)
+	}
+	return key, nil
+}
+
+// parseEd25519PublicKey parses a PKIX PEM-encoded Ed25519 public key.
+func parseEd25519PublicKey(data []byte) (ed25519.PublicKey, error) {
+	block, _ := pem.Decode(data)
+	if block == nil {
+		return nil, fmt.Errorf("not PEM encoded")
+	}
+	parsed, err := x509.ParsePKIXPublicKey(block.Bytes)
+	if err != nil {
+		return nil, fmt.Errorf("parsing public key: %w", err)
+	}
+	key, ok := parsed.(ed25519.PublicKey)
+	if !ok {
+		return nil, fmt.Errorf("not an ed25519 key")
+	}
+	return key, nil
+}
@@ -0,0 +1,101 @@
+package cli
+
+import (
+	"crypto/ed25519"
+	"crypto/rand"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func writeBundleTestRun(t *testing.T) string {
+	t.Helper()
+
+	evalDir := filepath.Join(t.TempDir(), "2026-02-22T010203-codex")
+	if err := os.MkdirAll(filepath.Join(evalDir, "go-bank-account"), 0o755); err != nil {
+		t.Fatalf("mkdir: %v", err)
+	}
+	summary := EvalSummary{
+		Agent:     "codex",
+		Timestamp: "2026-02-22T010203",
+		Results:   []EvalResult{{Task: "go/bank-account", Language: "go", Passed: true}},
+	}
+	resultsJSON, _ := json.Marshal(summary.Results)
+	attestation := EvalAttestation{Integrity: AttestationIntegrity{ResultsHash: hashBytes(resultsJSON)}}
+
+	summaryData, _ := json.Marshal(summary)
+	attestationData, _ := json.Marshal(attestation)
+	files := map[string][]byte{
+		"summary.json":              summaryData,
+		"attestation.json":          attestationData,
+		"go-bank-account/agent.log": []byte("transcript"),
+	}
+	for name, data := range files {
+		if err := os.WriteFile(filepath.Join(evalDir, name), data, 0o644); err != nil {
+			t.Fatalf("write %s: %v", name, err)
+		}
+	}
+	return evalDir
+}
+
+func TestSubmissionBundleRoundTrip(t *testing.T) {
+	t.Parallel()
+
+	evalDir := writeBundleTestRun(t)
+	pub, priv, err := ed25519.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatalf("generate key: %v", err)
+	}
+
+	bundlePath := filepath.Join(t.TempDir(), "run.tar.gz")
+	manifest := BundleManifest{Version: bundleFormatVersion, Agent: "codex"}
+	if err := writeSubmissionBundle(evalDir, bundlePath, manifest, priv); err != nil {
+		t.Fatalf("writeSubmissionBundle() error = %v", err)
+	}
+
+	got, problems, err := verifySubmissionBundle(bundlePath, pub)
+	if err != nil {
+		t.Fatalf("verifySubmissionBundle() error = %v", err)
+	}
+	if len(problems) != 0 {
+		t.Fatalf("expected no problems, got %v", problems)
+	}
+	if len(got.Files) != 3 {
+		t.Fatalf("manifest files = %d, want 3", len(got.Files))
+	}
+
+	otherPub, _, _ := ed25519.GenerateKey(rand.Reader)
+	_, problems, err = verifySubmissionBundle(bundlePath, otherPub)
+	if err != nil {
+		t.Fatalf("verifySubmissionBundle() error = %v", err)
+	}
+	if len(problems) != 1 || !strings.Contains(problems[0], "different key") {
+		t.Fatalf("expected key mismatch problem, got %v", problems)
+	}
+}
+
+func TestVerifySubmissionBundleRequiresSignatureWithPubkey(t *testing.T) {
+	t.Parallel()
+
+	evalDir := writeBundleTestRun(t)
+	bundlePath := filepath.Join(t.TempDir(), "run.tar.gz")
+	if err := writeSubmissionBundle(evalDir, bundlePath, BundleManifest{Version: bundleFormatVersion}, nil); err != nil {
+		t.Fatalf("writeSubmissionBundle() error = %v", err)
+	}
+
+	_, problems, err := verifySubmissionBundle(bundlePath, nil)
+	if err != nil {
+		t.Fatalf("verifySubmissionBundle() error = %v", err)
+	}
+	if len(problems) != 0 {
+		t.Fatalf("unsigned bundle without --pubkey should pass, got %v", problems)
+	}
+
+	pub, _, _ := ed25519.GenerateKey(rand.Reader)
+	_, problems, _ = verifySubmissionBundle(bundlePath, pub)
+	if len(problems) != 1 || !strings.Contains(problems[0], "unsigned") {
+		t.Fatalf("expected unsigned problem, got %v", problems)
+	}
+}
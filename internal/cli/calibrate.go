@@ -0,0 +1,333 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"math"
+	"os"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+var (
+	calibrateOutput     string
+	calibrateMinConfigs int
+	calibrateJSON       bool
+)
+
+// TaskCalibration is one task's empirical difficulty and proposed weight.
+type TaskCalibration struct {
+	Task       string  `json:"task"`
+	Configs    int     `json:"configs"`
+	Attempts   int     `json:"attempts"`
+	SolveRate  float64 `json:"solve_rate"` // Mean of each configuration's pass rate, 0-1
+	Current    float64 `json:"current_weight"`
+	Proposed   float64 `json:"proposed_weight"`
+	Shift      float64 `json:"shift"`
+	Calibrated bool    `json:"calibrated"` // False when too few configurations attempted the task; Proposed keeps Current
+}
+
+// ConfigRescore is a configuration's weighted pass rate under the current and
+// proposed weights.
+type ConfigRescore struct {
+	Config   string  `json:"config"`
+	Current  float64 `json:"current_weighted_pass_rate"`
+	Proposed float64 `json:"proposed_weighted_pass_rate"`
+}
+
+// WeightCalibration is the proposed weights file written by sanity calibrate.
+type WeightCalibration struct {
+	WeightVersion   string             `json:"weight_version"` // Scoring version the current weights come from
+	CreatedAt       string             `json:"created_at"`
+	Runs            int                `json:"runs"`
+	Configs         int                `json:"configs"`
+	MinConfigs      int                `json:"min_configs"`
+	Weights         map[string]float64 `json:"weights"`
+	Tasks           []TaskCalibration  `json:"tasks"`
+	ConfigScores    []ConfigRescore    `json:"config_scores"`
+	MeanAbsShift    float64            `json:"mean_abs_shift"`
+	RankCorrelation float64            `json:"rank_correlation"`
+	CurrentSpread   float64            `json:"current_score_stddev"`
+	ProposedSpread  float64            `json:"proposed_score_stddev"`
+}
+
+var calibrateCmd = &cobra.Command{
+	Use:   "calibrate <dir> [dir...]",
+	Short: "Propose task weights from historical pass rates",
+	Long: `Recomputes task weights from how often past eval runs solved each task, so
+rarely-solved tasks weigh more and scoring stays discriminative as models
+improve.
+
+Runs are pooled per agent/model configuration, and a task's solve rate is the
+mean of its configurations' pass rates, so a model with many repeats doesn't
+dominate. The proposed weight scales linearly from 1.0 for a task every
+configuration solves to the 1.5 cap for one none solves. Tasks attempted by
+fewer than --min-configs configurations keep their current weight.
+
+The proposed weights are written to --output with the evidence behind them.
+The report lists each task's shift from its current weight, the rank
+correlation between current and proposed weights, and how far apart the
+configurations' weighted scores are under each. Weights are not applied;
+review the file and update the task difficulty factors by hand.`,
+	Example: `  sanity calibrate eval-results/
+  sanity calibrate eval-results/ --min-configs 5 --output weights-2026q3.json`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if calibrateMinConfigs < 1 {
+			return fmt.Errorf("--min-configs must be at least 1")
+		}
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		summaries, _, err := loadRunSummaries(args)
+		if err != nil {
+			return err
+		}
+		c := calibrateWeights(summaries, calibrateMinConfigs)
+		c.CreatedAt = time.Now().Format(time.RFC3339)
+
+		data, _ := json.MarshalIndent(c, "", "  ")
+		if err := os.WriteFile(calibrateOutput, data, 0o644); err != nil {
+			return fmt.Errorf("writing proposed weights: %w", err)
+		}
+		if calibrateJSON {
+			fmt.Println(string(data))
+			return nil
+		}
+		fmt.Print(buildCalibrationReport(c))
+		fmt.Printf("\nProposed weights written to %s\n", calibrateOutput)
+		return nil
+	},
+}
+
+func init() {
+	calibrateCmd.Flags().StringVarP(&calibrateOutput, "output", "o", "weights-proposed.json", "proposed weights file")
+	calibrateCmd.Flags().IntVar(&calibrateMinConfigs, "min-configs", 3, "configurations that must have attempted a task before it is recalibrated")
+	calibrateCmd.Flags().BoolVar(&calibrateJSON, "json", false, "print the proposed weights file instead of the report")
+}
+
+// calibratesWeight reports whether r says anything about the task's
+// difficulty. Infrastructure and provider failures don't.
+func calibratesWeight(r EvalResult) bool {
+	return !r.InfraFailure && r.Status != task.StatusInfraFailure && !isResumableExternalFailure(r)
+}
+
+// proposedWeight maps a solve rate to a weight between 1.0 and the cap.
+func proposedWeight(solveRate float64) float64 {
+	w := 1.0 + (task.MaxWeight-1.0)*(1.0-solveRate)
+	return math.Round(w*100) / 100
+}
+
+// calibrateWeights proposes task weights from the pooled results of every
+// configuration in summaries.
+func calibrateWeights(summaries []EvalSummary, minConfigs int) WeightCalibration {
+	byConfig := tallyConfigTasks(summaries)
+	type taskAcc struct {
+		rates    []float64
+		attempts int
+	}
+	byTask := make(map[string]*taskAcc)
+	for _, tallies := range byConfig {
+		for id, t := range tallies {
+			if byTask[id] == nil {
+				byTask[id] = &taskAcc{}
+			}
+			byTask[id].rates = append(byTask[id].rates, t.passRate()/100)
+			byTask[id].attempts += t.attempts
+		}
+	}
+
+	c := WeightCalibration{
+		WeightVersion: task.WeightVersion,
+		Runs:          len(summaries),
+		Configs:       len(byConfig),
+		MinConfigs:    minConfigs,
+		Weights:       make(map[string]float64, len(byTask)),
+	}
+	current := make([]float64, 0, len(byTask))
+	proposed := make([]float64, 0, len(byTask))
+	var totalShift float64
+	for id, acc := range byTask {
+		tc := TaskCalibration{
+			Task:      id,
+			Configs:   len(acc.rates),
+			Attempts:  acc.attempts,
+			SolveRate: mean(acc.rates),
+			Current:   task.ComputeWeightForID(id).Base,
+		}
+		tc.Proposed = tc.Current
+		if tc.Configs >= minConfigs {
+			tc.Calibrated = true
+			tc.Proposed = proposedWeight(tc.SolveRate)
+			current = append(current, tc.Current)
+			proposed = append(proposed, tc.Proposed)
+		}
+		tc.Shift = math.Round((tc.Proposed-tc.Current)*100) / 100
+		totalShift += math.Abs(tc.Shift)
+		c.Weights[id] = tc.Proposed
+		c.Tasks = append(c.Tasks, tc)
+	}
+	sort.Slice(c.Tasks, func(i, j int) bool {
+		if a, b := math.Abs(c.Tasks[i].Shift), math.Abs(c.Tasks[j].Shift); a != b {
+			return a > b
+		}
+		return c.Tasks[i].Task < c.Tasks[j].Task
+	})
+	if len(c.Tasks) > 0 {
+		c.MeanAbsShift = totalShift / float64(len(c.Tasks))
+	}
+	c.RankCorrelation = spearman(current, proposed)
+
+	c.ConfigScores = rescoreConfigs(byConfig, c.Tasks)
+	currentScores := make([]float64, 0, len(c.ConfigScores))
+	proposedScores := make([]float64, 0, len(c.ConfigScores))
+	for _, cs := range c.ConfigScores {
+		currentScores = append(currentScores, cs.Current)
+		proposedScores = append(proposedScores, cs.Proposed)
+	}
+	c.CurrentSpread = stddev(currentScores)
+	c.ProposedSpread = stddev(proposedScores)
+	return c
+}
+
+// tallyConfigTasks pools each configuration's results per task.
+func tallyConfigTasks(summaries []EvalSummary) map[string]map[string]*scoreTally {
+	byConfig := make(map[string]map[string]*scoreTally)
+	for _, s := range summaries {
+		label := summaryConfigLabel(s)
+		if byConfig[label] == nil {
+			byConfig[label] = make(map[string]*scoreTally)
+		}
+		for _, r := range s.Results {
+			if !calibratesWeight(r) {
+				continue
+			}
+			if byConfig[label][r.Task] == nil {
+				byConfig[label][r.Task] = &scoreTally{}
+			}
+			byConfig[label][r.Task].add(r)
+		}
+	}
+	return byConfig
+}
+
+// rescoreConfigs computes each configuration's weighted pass rate under the
+// current and proposed weights, best proposed score first.
+func rescoreConfigs(byConfig map[string]map[string]*scoreTally, tasks []TaskCalibration) []ConfigRescore {
+	weights := make(map[string]TaskCalibration, len(tasks))
+	for _, tc := range tasks {
+		weights[tc.Task] = tc
+	}
+	scores := make([]ConfigRescore, 0, len(byConfig))
+	for label, byTask := range byConfig {
+		var curEarned, curMax, propEarned, propMax float64
+		for id, t := range byTask {
+			w := weights[id]
+			rate := t.passRate() / 100
+			curEarned += rate * w.Current
+			curMax += w.Current
+			propEarned += rate * w.Proposed
+			propMax += w.Proposed
+		}
+		cs := ConfigRescore{Config: label}
+		if curMax > 0 {
+			cs.Current = curEarned / curMax * 100
+			cs.Proposed = propEarned / propMax * 100
+		}
+		scores = append(scores, cs)
+	}
+	sort.Slice(scores, func(i, j int) bool {
+		if scores[i].Proposed != scores[j].Proposed {
+			return scores[i].Proposed > scores[j].Proposed
+		}
+		return scores[i].Config < scores[j].Config
+	})
+	return scores
+}
+
+// spearman returns the Spearman rank correlation of xs and ys, or 0 if
+// either has no variance.
+func spearman(xs, ys []float64) float64 {
+	if len(xs) < 2 || len(xs) != len(ys) {
+		return 0
+	}
+	rx, ry := ranks(xs), ranks(ys)
+	mx, my := mean(rx), mean(ry)
+	var cov, vx, vy float64
+	for i := range rx {
+		dx, dy := rx[i]-mx, ry[i]-my
+		cov += dx * dy
+		vx += dx * dx
+		vy += dy * dy
+	}
+	if vx == 0 || vy == 0 {
+		return 0
+	}
+	return cov / math.Sqrt(vx*vy)
+}
+
+// ranks returns the 1-based rank of each value, averaging ties.
+func ranks(vals []float64) []float64 {
+	idx := make([]int, len(vals))
+	for i := range idx {
+		idx[i] = i
+	}
+	sort.SliceStable(idx, func(a, b int) bool { return vals[idx[a]] < vals[idx[b]] })
+	out := make([]float64, len(vals))
+	for i := 0; i < len(idx); {
+		j := i
+		for j+1 < len(idx) && vals[idx[j+1]] == vals[idx[i]] {
+			j++
+		}
+		rank := float64(i+j)/2 + 1
+		for k := i; k <= j; k++ {
+			out[idx[k]] = rank
+		}
+		i = j + 1
+	}
+	return out
+}
+
+// buildCalibrationReport builds a human-readable weight calibration report.
+func buildCalibrationReport(c WeightCalibration) string {
+	var sb strings.Builder
+	sb.WriteString("# Task Weight Calibration\n\n")
+	var calibrated int
+	for _, tc := range c.Tasks {
+		if tc.Calibrated {
+			calibrated++
+		}
+	}
+	fmt.Fprintf(&sb, "Runs: %d | Configurations: %d | Tasks: %d (%d calibrated, min %d configurations)\n\n",
+		c.Runs, c.Configs, len(c.Tasks), calibrated, c.MinConfigs)
+	fmt.Fprintf(&sb, "Mean |shift|: %.2f | Rank correlation with current weights: %.2f\n", c.MeanAbsShift, c.RankCorrelation)
+	fmt.Fprintf(&sb, "Score spread across configurations: %.1f pts (current) → %.1f pts (proposed)\n\n", c.CurrentSpread, c.ProposedSpread)
+
+	sb.WriteString("| Task | Configs | Solve Rate | Current | Proposed | Shift |\n")
+	sb.WriteString("|------|---------|------------|---------|----------|-------|\n")
+	for _, tc := range c.Tasks {
+		shift := fmt.Sprintf("%+.2f", tc.Shift)
+		if !tc.Calibrated {
+			shift = "too few configs"
+		}
+		fmt.Fprintf(&sb, "| %s | %d | %.0f%% | %.2f | %.2f | %s |\n",
+			tc.Task, tc.Configs, tc.SolveRate*100, tc.Current, tc.Proposed, shift)
+	}
+
+	if len(c.ConfigScores) > 0 {
+		sb.WriteString("\n| Configuration | Current Weighted | Proposed Weighted | Δ |\n")
+		sb.WriteString("|---------------|------------------|-------------------|---|\n")
+		for _, cs := range c.ConfigScores {
+			fmt.Fprintf(&sb, "| %s | %.1f%% | %.1f%% | %+.1f |\n", cs.Config, cs.Current, cs.Proposed, cs.Proposed-cs.Current)
+		}
+	}
+	return sb.String()
+}
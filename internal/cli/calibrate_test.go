@@ -0,0 +1,88 @@
+package cli
+
+import (
+	"math"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestCalibrateWeights(t *testing.T) {
+	t.Parallel()
+
+	summary := func(model string, results ...EvalResult) EvalSummary {
+		return EvalSummary{Agent: "opencode", Model: model, Results: results}
+	}
+	results := func(hardPassed bool) []EvalResult {
+		return []EvalResult{
+			{Task: "go/bank-account", Passed: true},
+			{Task: "zig/comptime-json", Passed: hardPassed},
+			{Task: "go/react", Passed: true},
+		}
+	}
+	summaries := []EvalSummary{
+		summary("a", results(false)...),
+		// Repeats of one model are pooled, so they don't outvote the others.
+		summary("b", results(true)...),
+		summary("b", results(true)...),
+		summary("b", results(true)...),
+		summary("c", append(results(false), EvalResult{Task: "go/singleflight", Passed: false})...),
+		// Infrastructure failures say nothing about difficulty.
+		summary("c", EvalResult{Task: "go/react", InfraFailure: true}),
+	}
+
+	c := calibrateWeights(summaries, 3)
+	if c.Configs != 3 || c.Runs != 6 {
+		t.Fatalf("Configs, Runs = %d, %d; want 3, 6", c.Configs, c.Runs)
+	}
+	byTask := make(map[string]TaskCalibration)
+	for _, tc := range c.Tasks {
+		byTask[tc.Task] = tc
+	}
+
+	hard := byTask["zig/comptime-json"]
+	if math.Abs(hard.SolveRate-1.0/3) > 1e-9 || hard.Proposed != 1.33 || !hard.Calibrated {
+		t.Errorf("zig/comptime-json = %+v, want solve rate 1/3 and weight 1.33", hard)
+	}
+	if easy := byTask["go/bank-account"]; easy.Proposed != 1.0 || easy.Shift != math.Round((1.0-easy.Current)*100)/100 {
+		t.Errorf("go/bank-account = %+v, want weight 1.0", easy)
+	}
+	if react := byTask["go/react"]; react.Attempts != 5 {
+		t.Errorf("go/react attempts = %d, want 5 (infra failure excluded)", react.Attempts)
+	}
+	rare := byTask["go/singleflight"]
+	if rare.Calibrated || rare.Proposed != task.ComputeWeightForID("go/singleflight").Base || rare.Shift != 0 {
+		t.Errorf("go/singleflight = %+v, want current weight kept", rare)
+	}
+	if c.Weights["zig/comptime-json"] != 1.33 {
+		t.Errorf("Weights = %v", c.Weights)
+	}
+	if len(c.ConfigScores) != 3 || c.ConfigScores[0].Config != "opencode/b" {
+		t.Errorf("ConfigScores = %+v, want opencode/b first", c.ConfigScores)
+	}
+
+	report := buildCalibrationReport(c)
+	for _, want := range []string{"| zig/comptime-json | 3 | 33% | 1.50 | 1.33 | -0.17 |", "too few configs"} {
+		if !strings.Contains(report, want) {
+			t.Errorf("report missing %q:\n%s", want, report)
+		}
+	}
+}
+
+func TestSpearman(t *testing.T) {
+	t.Parallel()
+
+	if got := spearman([]float64{1, 2, 3, 4}, []float64{10, 20, 30, 40}); math.Abs(got-1) > 1e-9 {
+		t.Errorf("spearman(increasing) = %v, want 1", got)
+	}
+	if got := spearman([]float64{1, 2, 3}, []float64{3, 2, 1}); math.Abs(got+1) > 1e-9 {
+		t.Errorf("spearman(reversed) = %v, want -1", got)
+	}
+	if got := spearman([]float64{1, 1, 1}, []float64{1, 2, 3}); got != 0 {
+		t.Errorf("spearman(constant) = %v, want 0", got)
+	}
+	if got := ranks([]float64{5, 1, 5, 3}); got[0] != 3.5 || got[1] != 1 || got[2] != 3.5 || got[3] != 2 {
+		t.Errorf("ranks() = %v, want [3.5 1 3.5 2]", got)
+	}
+}
@@ -0,0 +1,212 @@
+package cli
+
+import (
+	"bufio"
+	"context"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+const (
+	// chaosDirName holds the command shims inside the agent workspace. It is
+	// skipped by workspace audits and removed before validation.
+	chaosDirName = ".sanity-chaos"
+	// chaosLogName records one line per injected failure.
+	chaosLogName = "injections.log"
+)
+
+// ChaosSettings records the chaos profile a run used, so results are only
+// compared with runs under the same adversity.
+type ChaosSettings struct {
+	Profile  string   `json:"profile"`
+	FailRate float64  `json:"fail_rate"`
+	DelayMS  int      `json:"delay_ms"`
+	CPUs     int      `json:"cpus,omitempty"`
+	Commands []string `json:"commands,omitempty"`
+}
+
+// evalChaos holds the chaos settings of the current run, or nil. Like the
+// other eval globals it is set by evalRunSingle before tasks start.
+var evalChaos *ChaosSettings
+
+// chaosToolchainCommands are wrapped by default for each language.
+var chaosToolchainCommands = map[task.Language][]string{
+	task.Go:         {"go", "gofmt"},
+	task.Rust:       {"cargo", "rustc"},
+	task.TypeScript: {"node", "npm", "npx", "bun", "tsx", "tsc"},
+	task.Kotlin:     {"gradle", "kotlinc", "java"},
+	task.Dart:       {"dart"},
+	task.Zig:        {"zig"},
+}
+
+// chaosFileCommands are wrapped by default for every language, so reading
+// and searching the workspace feels like a slow filesystem.
+var chaosFileCommands = []string{"cat", "ls", "find", "grep", "rg", "sed", "head", "tail"}
+
+// resolveChaos looks up a chaos profile by name. An empty name disables
+// chaos. A CPU limit needs taskset and no more cores than the machine has.
+func resolveChaos(name string) (*ChaosSettings, error) {
+	if name == "" {
+		return nil, nil
+	}
+	p, ok := cfg.ChaosProfile(name)
+	if !ok {
+		return nil, fmt.Errorf("unknown chaos profile %q (available: %s)", name, strings.Join(cfg.ChaosProfileNames(), ", "))
+	}
+	if p.CPUs > 0 {
+		if _, err := exec.LookPath("taskset"); err != nil {
+			return nil, fmt.Errorf("chaos profile %q limits CPUs, which needs taskset: %w", name, err)
+		}
+		if p.CPUs > runtime.NumCPU() {
+			return nil, fmt.Errorf("chaos profile %q asks for %d CPUs, but this machine has %d", name, p.CPUs, runtime.NumCPU())
+		}
+	}
+	return &ChaosSettings{
+		Profile:  name,
+		FailRate: p.FailRate,
+		DelayMS:  p.DelayMS,
+		CPUs:     p.CPUs,
+		Commands: p.Commands,
+	}, nil
+}
+
+// commandsFor lists the commands wrapped for lang.
+func (c *ChaosSettings) commandsFor(lang task.Language) []string {
+	if len(c.Commands) > 0 {
+		return c.Commands
+	}
+	return append(append([]string{}, chaosToolchainCommands[lang]...), chaosFileCommands...)
+}
+
+// install writes a shim for every wrapped command found on PATH into the
+// workspace. A nil receiver, or a profile that neither delays nor fails,
+// installs nothing.
+func (c *ChaosSettings) install(workspace string, lang task.Language) error {
+	if c == nil || (c.FailRate == 0 && c.DelayMS == 0) {
+		return nil
+	}
+	dir := filepath.Join(workspace, chaosDirName)
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		return fmt.Errorf("creating %s: %w", chaosDirName, err)
+	}
+	hostPath := os.Getenv("PATH")
+	logPath := filepath.Join(dir, chaosLogName)
+	for _, name := range c.commandsFor(lang) {
+		target, err := exec.LookPath(name)
+		if err != nil {
+			continue // Not installed, so there is nothing to slow down.
+		}
+		shim := chaosShim(name, target, hostPath, logPath, c.FailRate, c.DelayMS)
+		if err := os.WriteFile(filepath.Join(dir, name), []byte(shim), 0o755); err != nil {
+			return fmt.Errorf("writing %s shim: %w", name, err)
+		}
+	}
+	return nil
+}
+
+// chaosShim returns a POSIX shell script that sleeps for delayMS, fails with
+// probability failRate, and otherwise runs target. Helpers run with the host
+// PATH so they are never shims themselves.
+func chaosShim(name, target, hostPath, logPath string, failRate float64, delayMS int) string {
+	var sb strings.Builder
+	sb.WriteString("#!/bin/sh\n")
+	fmt.Fprintf(&sb, "# Installed by sanity eval --chaos; wraps %s.\n", target)
+	if delayMS > 0 {
+		fmt.Fprintf(&sb, "PATH=%s sleep %d.%03d\n", shellQuote(hostPath), delayMS/1000, delayMS%1000)
+	}
+	if threshold := int(failRate * 65536); threshold > 0 {
+		fmt.Fprintf(&sb, "n=$(PATH=%s od -An -N2 -tu2 /dev/urandom)\n", shellQuote(hostPath))
+		fmt.Fprintf(&sb, "if [ $n -lt %d ]; then\n", threshold)
+		fmt.Fprintf(&sb, "\techo '%s: resource temporarily unavailable' >&2\n", name)
+		fmt.Fprintf(&sb, "\techo %s >>%s\n", name, shellQuote(logPath))
+		sb.WriteString("\texit 1\nfi\n")
+	}
+	fmt.Fprintf(&sb, "exec %s \"$@\"\n", shellQuote(target))
+	return sb.String()
+}
+
+// shellQuote single-quotes s for a POSIX shell.
+func shellQuote(s string) string {
+	return "'" + strings.ReplaceAll(s, "'", `'\''`) + "'"
+}
+
+// chaosEnv puts the workspace's shims first on the agent's PATH. A nil env
+// means the harness environment, as for exec.Cmd.
+func chaosEnv(env []string, workspace string) []string {
+	if env == nil {
+		env = os.Environ()
+	}
+	shimDir := filepath.Join(workspace, chaosDirName)
+	out := make([]string, 0, len(env)+1)
+	found := false
+	for _, kv := range env {
+		if v, ok := strings.CutPrefix(kv, "PATH="); ok {
+			kv = "PATH=" + shimDir + string(os.PathListSeparator) + v
+			found = true
+		}
+		out = append(out, kv)
+	}
+	if !found {
+		out = append(out, "PATH="+shimDir)
+	}
+	return out
+}
+
+// wrapCommandWithCPULimit pins cmd and everything it starts to the first
+// cpus cores.
+func wrapCommandWithCPULimit(ctx context.Context, cmd *exec.Cmd, cpus int) *exec.Cmd {
+	args := []string{"-c", fmt.Sprintf("0-%d", cpus-1), cmd.Path}
+	args = append(args, cmd.Args[1:]...)
+
+	wrapped := exec.CommandContext(ctx, "taskset", args...)
+	wrapped.Dir = cmd.Dir
+	wrapped.Env = cmd.Env
+	wrapped.Stdin = cmd.Stdin
+	wrapped.Stdout = cmd.Stdout
+	wrapped.Stderr = cmd.Stderr
+	return wrapped
+}
+
+// removeChaosShims deletes the workspace's shims and returns how many
+// failures they injected.
+func removeChaosShims(workspace string) (int, error) {
+	dir := filepath.Join(workspace, chaosDirName)
+	injections := 0
+	f, err := os.Open(filepath.Join(dir, chaosLogName))
+	switch {
+	case err == nil:
+		scanner := bufio.NewScanner(f)
+		for scanner.Scan() {
+			if strings.TrimSpace(scanner.Text()) != "" {
+				injections++
+			}
+		}
+		_ = f.Close()
+	case !errors.Is(err, fs.ErrNotExist):
+		return 0, fmt.Errorf("reading %s: %w", chaosLogName, err)
+	}
+	if err := os.RemoveAll(dir); err != nil {
+		return injections, fmt.Errorf("removing %s: %w", chaosDirName, err)
+	}
+	return injections, nil
+}
+
+// String describes the settings for headers and reports.
+func (c *ChaosSettings) String() string {
+	parts := []string{
+		fmt.Sprintf("%.0f%% command failures", c.FailRate*100),
+		fmt.Sprintf("%dms latency", c.DelayMS),
+	}
+	if c.CPUs > 0 {
+		parts = append(parts, fmt.Sprintf("%d CPUs", c.CPUs))
+	}
+	return fmt.Sprintf("%s (%s)", c.Profile, strings.Join(parts, ", "))
+}
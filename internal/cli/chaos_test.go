@@ -0,0 +1,80 @@
+package cli
+
+import (
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestChaosShimsInjectFailures(t *testing.T) {
+	t.Parallel()
+
+	if _, err := exec.LookPath("ls"); err != nil {
+		t.Skip("ls not available")
+	}
+	workspace := t.TempDir()
+	chaos := &ChaosSettings{Profile: "always", FailRate: 1, Commands: []string{"ls", "no-such-command"}}
+	if err := chaos.install(workspace, task.Go); err != nil {
+		t.Fatalf("install() error = %v", err)
+	}
+	shim := filepath.Join(workspace, chaosDirName, "ls")
+	if _, err := os.Stat(filepath.Join(workspace, chaosDirName, "no-such-command")); err == nil {
+		t.Fatal("shim written for a command that is not installed")
+	}
+
+	for range 2 {
+		out, err := exec.Command(shim).CombinedOutput()
+		if err == nil || !strings.Contains(string(out), "resource temporarily unavailable") {
+			t.Fatalf("shim output = %q, err = %v; want an injected failure", out, err)
+		}
+	}
+	injections, err := removeChaosShims(workspace)
+	if err != nil || injections != 2 {
+		t.Fatalf("removeChaosShims() = %d, %v; want 2", injections, err)
+	}
+	if _, err := os.Stat(filepath.Join(workspace, chaosDirName)); err == nil {
+		t.Fatal("chaos directory not removed")
+	}
+}
+
+func TestChaosShimPassesThrough(t *testing.T) {
+	t.Parallel()
+
+	got := chaosShim("cat", "/bin/cat", "/usr/bin:/bin", "/w/log", 0, 1500)
+	if !strings.Contains(got, "sleep 1.500\n") || strings.Contains(got, "exit 1") {
+		t.Fatalf("shim = %q; want a delay and no failure branch", got)
+	}
+	if !strings.HasSuffix(got, "exec '/bin/cat' \"$@\"\n") {
+		t.Fatalf("shim = %q; want it to exec the real command", got)
+	}
+}
+
+func TestChaosEnv(t *testing.T) {
+	t.Parallel()
+
+	env := chaosEnv([]string{"HOME=/home/x", "PATH=/usr/bin"}, "/w")
+	want := "PATH=" + filepath.Join("/w", chaosDirName) + string(os.PathListSeparator) + "/usr/bin"
+	if len(env) != 2 || env[1] != want {
+		t.Fatalf("chaosEnv() = %v, want PATH %q", env, want)
+	}
+	if env := chaosEnv([]string{"HOME=/home/x"}, "/w"); env[len(env)-1] != "PATH="+filepath.Join("/w", chaosDirName) {
+		t.Fatalf("chaosEnv() without PATH = %v", env)
+	}
+}
+
+func TestChaosCommandsFor(t *testing.T) {
+	t.Parallel()
+
+	got := (&ChaosSettings{}).commandsFor(task.Rust)
+	if got[0] != "cargo" || got[len(got)-1] != chaosFileCommands[len(chaosFileCommands)-1] {
+		t.Fatalf("commandsFor(rust) = %v", got)
+	}
+	custom := &ChaosSettings{Commands: []string{"make"}}
+	if got := custom.commandsFor(task.Rust); len(got) != 1 || got[0] != "make" {
+		t.Fatalf("commandsFor with custom commands = %v", got)
+	}
+}
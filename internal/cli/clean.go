@@ -5,6 +5,7 @@ import (
 	"fmt"
 	"os"
 	"strings"
+	"time"
 
 	"github.com/spf13/cobra"
 
@@ -18,6 +19,7 @@ var (
 	cleanSessions   bool
 	cleanEval       bool
 	cleanAll        bool
+	cleanRetention  bool
 )
 
 var cleanCmd = &cobra.Command{
@@ -29,14 +31,22 @@ session directories, and eval results.
 By default, shows what would be deleted and asks for confirmation.
 Use --force to skip confirmation.
 
+With --retention, removes only the eval artifacts and sessions that are past
+the ages set in the config's [retention] section instead.
+
 Examples:
   sanity clean                    # Interactive cleanup of workspaces
   sanity clean --workspaces       # Clean only workspace directories
   sanity clean --sessions         # Clean only session directories  
   sanity clean --eval             # Clean only eval-results
   sanity clean --all              # Clean everything
+  sanity clean --retention        # Apply [retention] policies
   sanity clean --force            # Skip confirmation prompts`,
 	RunE: func(cmd *cobra.Command, args []string) error {
+		if cleanRetention {
+			return runCleanRetention()
+		}
+
 		// Default to workspaces if no specific flag is set
 		if !cleanWorkspaces && !cleanSessions && !cleanEval && !cleanAll {
 			cleanWorkspaces = true
@@ -87,18 +97,8 @@ Examples:
 		fmt.Println()
 
 		// Confirm unless --force
-		if !cleanForce {
-			fmt.Print("Delete these directories? [y/N] ")
-			reader := bufio.NewReader(os.Stdin)
-			response, err := reader.ReadString('\n')
-			if err != nil {
-				return fmt.Errorf("reading response: %w", err)
-			}
-			response = strings.TrimSpace(strings.ToLower(response))
-			if response != "y" && response != "yes" {
-				fmt.Println("Cancelled.")
-				return nil
-			}
+		if ok, err := confirmClean("Delete these directories?"); !ok {
+			return err
 		}
 
 		// Delete directories
@@ -117,6 +117,63 @@ Examples:
 	},
 }
 
+// confirmClean asks the user to confirm a deletion unless --force is set.
+func confirmClean(question string) (bool, error) {
+	if cleanForce {
+		return true, nil
+	}
+	fmt.Printf("%s [y/N] ", question)
+	reader := bufio.NewReader(os.Stdin)
+	response, err := reader.ReadString('\n')
+	if err != nil {
+		return false, fmt.Errorf("reading response: %w", err)
+	}
+	response = strings.TrimSpace(strings.ToLower(response))
+	if response != "y" && response != "yes" {
+		fmt.Println("Cancelled.")
+		return false, nil
+	}
+	return true, nil
+}
+
+// runCleanRetention removes the artifacts in eval-results and the session
+// directory that are past their configured retention.
+func runCleanRetention() error {
+	if !cfg.Retention.Enabled() {
+		fmt.Println("No [retention] policies configured; nothing to clean.")
+		return nil
+	}
+	expired, err := findExpiredArtifacts(cfg.Retention, "eval-results", cfg.Harness.SessionDir, time.Now())
+	if err != nil {
+		return err
+	}
+	if len(expired) == 0 {
+		fmt.Println("Nothing past retention.")
+		return nil
+	}
+
+	fmt.Println("The following artifacts are past retention and will be deleted:")
+	fmt.Println()
+	for _, a := range expired {
+		fmt.Printf("  %-14s %s\n", a.Kind, a.Path)
+	}
+	fmt.Println()
+	if ok, err := confirmClean("Delete these artifacts?"); !ok {
+		return err
+	}
+
+	deleted := 0
+	for _, a := range expired {
+		if err := os.RemoveAll(a.Path); err != nil {
+			fmt.Printf("  Failed to delete %s: %v\n", a.Path, err)
+			continue
+		}
+		deleted++
+	}
+	fmt.Printf("Deleted %d artifacts.\n", deleted)
+	return nil
+}
+
 // findWorkspaceDirectories finds workspace directories in the current directory
 // by matching against known task slugs.
 func findWorkspaceDirectories() ([]string, error) {
@@ -201,4 +258,5 @@ func init() {
 	cleanCmd.Flags().BoolVar(&cleanSessions, "sessions", false, "clean sessions directory")
 	cleanCmd.Flags().BoolVar(&cleanEval, "eval", false, "clean eval-results directory")
 	cleanCmd.Flags().BoolVar(&cleanAll, "all", false, "clean everything")
+	cleanCmd.Flags().BoolVar(&cleanRetention, "retention", false, "delete only eval artifacts and sessions past their [retention] policy")
 }
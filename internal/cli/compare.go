@@ -1,7 +1,6 @@
 package cli
 
 import (
-	"encoding/json"
 	"fmt"
 	"os"
 	"path/filepath"
@@ -23,6 +22,12 @@ Supports glob patterns for convenient selection of multiple directories.`,
   sanity compare eval-results/multi-2026-02-21T024300/codex-gpt-5.2 eval-results/multi-2026-02-21T024300/opencode-kimi-k2.5`,
 	Args: cobra.MinimumNArgs(2),
 	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
 		var summaries []EvalSummary
 		for _, dir := range args {
 			s, err := loadSummaryFromDir(dir)
@@ -56,9 +61,9 @@ func loadSummaryFromDir(dir string) (*EvalSummary, error) {
 	if err != nil {
 		return nil, fmt.Errorf("reading summary.json: %w", err)
 	}
-	var s EvalSummary
-	if err := json.Unmarshal(data, &s); err != nil {
+	s, err := parseEvalSummary(data)
+	if err != nil {
 		return nil, fmt.Errorf("parsing summary.json: %w", err)
 	}
-	return &s, nil
+	return s, nil
 }
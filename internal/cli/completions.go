@@ -0,0 +1,301 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"sort"
+	"strings"
+	"sync"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// defaultEvalResultsDir is where eval writes results unless --output is set.
+const defaultEvalResultsDir = "eval-results"
+
+var completionsCmd = &cobra.Command{
+	Use:   "completions <bash|zsh|fish|powershell>",
+	Short: "Generate shell completion scripts",
+	Long: `Generates a shell completion script for sanity.
+
+Completions include task IDs (e.g. go/dining-philosophers) from the embedded
+tasks or --tasks-dir, language globs and tags for --filter, --exclude, and
+--tag, agent names from the built-in list and sanity.toml, config keys for
+--set, session directories for show, inspect, debug, and merge, and eval
+result directories for --resume, verify, compare, export, and analyze.
+
+Bash:
+  source <(sanity completions bash)
+  # or persist it:
+  sanity completions bash > /etc/bash_completion.d/sanity
+
+Zsh:
+  sanity completions zsh > "${fpath[1]}/_sanity"
+
+Fish:
+  sanity completions fish > ~/.config/fish/completions/sanity.fish
+
+PowerShell:
+  sanity completions powershell | Out-String | Invoke-Expression`,
+	ValidArgs: []string{"bash", "zsh", "fish", "powershell"},
+	Args:      cobra.MatchAll(cobra.ExactArgs(1), cobra.OnlyValidArgs),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		out := cmd.OutOrStdout()
+		switch args[0] {
+		case "bash":
+			return rootCmd.GenBashCompletionV2(out, true)
+		case "zsh":
+			return rootCmd.GenZshCompletion(out)
+		case "fish":
+			return rootCmd.GenFishCompletion(out, true)
+		case "powershell":
+			return rootCmd.GenPowerShellCompletionWithDesc(out)
+		}
+		return fmt.Errorf("unsupported shell %q", args[0])
+	},
+}
+
+var registerCompletionsOnce sync.Once
+
+// registerCompletions wires dynamic completions into commands and flags. It
+// must run after every command has defined its flags, so Execute calls it
+// rather than an init function.
+func registerCompletions() {
+	registerCompletionsOnce.Do(func() {
+		rootCmd.CompletionOptions.HiddenDefaultCmd = true
+
+		runCmd.ValidArgsFunction = completeTaskIDs
+		initCmd.ValidArgsFunction = completeSingleArg(completeTaskIDs)
+		shellCmd.ValidArgsFunction = completeSingleArg(completeTaskIDs)
+		tasksShowCmd.ValidArgsFunction = completeSingleArg(completeTaskIDs)
+		showCmd.ValidArgsFunction = completeSingleArg(completeSessionDirs)
+		inspectCmd.ValidArgsFunction = completeInspectArgs
+		debugCmd.ValidArgsFunction = completeInspectArgs
+		mergeCmd.ValidArgsFunction = completeSessionDirs
+		imagesExportCmd.ValidArgsFunction = completeLanguages
+		verifyCmd.ValidArgsFunction = completeSingleArg(completeEvalDirs)
+		submitBundleCmd.ValidArgsFunction = completeSingleArg(completeEvalDirs)
+		replayCmd.ValidArgsFunction = completeSingleArg(completeEvalDirs)
+		archiveCmd.ValidArgsFunction = completeSingleArg(completeEvalDirs)
+		for _, cmd := range []*cobra.Command{compareCmd, exportCmd, calibrateCmd, analyzeScaleCmd, analyzeLanguagesCmd, analyzeParetoCmd, analyzeScalingCmd, analyzeSimilarityCmd} {
+			cmd.ValidArgsFunction = completeEvalDirs
+		}
+
+		registerFlagCompletion(evalCmd, "agent", completeCommaList(agentNames))
+		registerFlagCompletion(evalCmd, "tasks", completeCommaList(taskIDs))
+		registerFlagCompletion(evalCmd, "lang", completeCommaList(languageNames))
+		registerFlagCompletion(evalCmd, "tier", cobra.FixedCompletions([]string{"core", "extended", "all"}, cobra.ShellCompDirectiveNoFileComp))
+		registerFlagCompletion(evalCmd, "difficulty", completeCommaList(func() []string { return task.ValidDifficulties }))
+		registerFlagCompletion(evalCmd, "resume", completeEvalDirs)
+		for _, cmd := range []*cobra.Command{evalCmd, tasksListCmd} {
+			registerFlagCompletion(cmd, "filter", completeCommaList(taskGlobs))
+			registerFlagCompletion(cmd, "exclude", completeCommaList(taskGlobs))
+			registerFlagCompletion(cmd, "tag", completeCommaList(taskTags))
+		}
+		registerFlagCompletion(rootCmd, "set", completeConfigKeys)
+		registerFlagCompletion(replayCmd, "tasks", completeCommaList(taskIDs))
+		registerFlagCompletion(runCmd, "agent", completeCommaList(agentNames))
+		registerFlagCompletion(runCmd, "lang", cobra.FixedCompletions(languageNames(), cobra.ShellCompDirectiveNoFileComp))
+		registerFlagCompletion(runCmd, "tier", cobra.FixedCompletions([]string{"core", "extended", "all"}, cobra.ShellCompDirectiveNoFileComp))
+		registerFlagCompletion(tasksListCmd, "tasks", completeCommaList(taskIDs))
+		registerFlagCompletion(tasksListCmd, "lang", cobra.FixedCompletions(languageNames(), cobra.ShellCompDirectiveNoFileComp))
+		registerFlagCompletion(tasksListCmd, "tier", cobra.FixedCompletions([]string{"core", "extended", "all"}, cobra.ShellCompDirectiveNoFileComp))
+		registerFlagCompletion(tasksListCmd, "difficulty", completeCommaList(func() []string { return task.ValidDifficulties }))
+		registerFlagCompletion(listCmd, "language", cobra.FixedCompletions(languageNames(), cobra.ShellCompDirectiveNoFileComp))
+		registerFlagCompletion(listCmd, "tier", cobra.FixedCompletions(task.ValidTiers, cobra.ShellCompDirectiveNoFileComp))
+		registerFlagCompletion(listCmd, "difficulty", cobra.FixedCompletions(task.ValidDifficulties, cobra.ShellCompDirectiveNoFileComp))
+	})
+}
+
+// registerFlagCompletion registers fn for a flag of cmd. Naming a flag the
+// command does not define is a programming error, so it panics.
+func registerFlagCompletion(cmd *cobra.Command, flag string, fn completionFunc) {
+	if err := cmd.RegisterFlagCompletionFunc(flag, fn); err != nil {
+		panic(fmt.Sprintf("registering completion for %s --%s: %v", cmd.CommandPath(), flag, err))
+	}
+}
+
+type completionFunc = func(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective)
+
+// completeSingleArg offers fn's completions only for the first positional argument.
+func completeSingleArg(fn completionFunc) completionFunc {
+	return func(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+		if len(args) > 0 {
+			return nil, cobra.ShellCompDirectiveNoFileComp
+		}
+		return fn(cmd, args, toComplete)
+	}
+}
+
+// completeCommaList completes the last element of a comma-separated flag value.
+func completeCommaList(values func() []string) completionFunc {
+	return func(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+		prefix := ""
+		if i := strings.LastIndex(toComplete, ","); i >= 0 {
+			prefix = toComplete[:i+1]
+		}
+		all := values()
+		out := make([]string, 0, len(all))
+		for _, v := range all {
+			if strings.HasPrefix(prefix+v, toComplete) {
+				out = append(out, prefix+v)
+			}
+		}
+		return out, cobra.ShellCompDirectiveNoFileComp | cobra.ShellCompDirectiveNoSpace
+	}
+}
+
+func completeTaskIDs(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	return taskIDs(), cobra.ShellCompDirectiveNoFileComp
+}
+
+// completeLanguages offers each language not already given as an argument.
+func completeLanguages(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	langs := languageNames()
+	out := make([]string, 0, len(langs))
+	for _, lang := range langs {
+		if !slices.Contains(args, lang) {
+			out = append(out, lang)
+		}
+	}
+	return out, cobra.ShellCompDirectiveNoFileComp
+}
+
+// completeConfigKeys offers every config key as "key=" for --set.
+func completeConfigKeys(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	c := completionConfig()
+	if c == nil {
+		return nil, cobra.ShellCompDirectiveNoFileComp
+	}
+	settings, err := c.Settings(nil)
+	if err != nil {
+		return nil, cobra.ShellCompDirectiveNoFileComp
+	}
+	keys := make([]string, 0, len(settings))
+	for _, s := range settings {
+		keys = append(keys, s.Key+"=")
+	}
+	return keys, cobra.ShellCompDirectiveNoFileComp | cobra.ShellCompDirectiveNoSpace
+}
+
+func completeSessionDirs(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	sessionDir := config.Default.Harness.SessionDir
+	if c := completionConfig(); c != nil {
+		sessionDir = c.Harness.SessionDir
+	}
+	return dirCompletions(listSubdirs(sessionDir))
+}
+
+// completeInspectArgs offers session and eval directories, then task IDs.
+func completeInspectArgs(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	switch len(args) {
+	case 0:
+		sessions, _ := completeSessionDirs(cmd, args, toComplete)
+		return dirCompletions(append(sessions, listSubdirs(defaultEvalResultsDir)...))
+	case 1:
+		return completeTaskIDs(cmd, args, toComplete)
+	}
+	return nil, cobra.ShellCompDirectiveNoFileComp
+}
+
+func completeEvalDirs(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	return dirCompletions(listSubdirs(defaultEvalResultsDir))
+}
+
+// dirCompletions offers dirs when there are any and falls back to the shell's
+// directory completion otherwise, e.g. for results stored elsewhere.
+func dirCompletions(dirs []string) ([]string, cobra.ShellCompDirective) {
+	if len(dirs) == 0 {
+		return nil, cobra.ShellCompDirectiveFilterDirs
+	}
+	return dirs, cobra.ShellCompDirectiveNoFileComp
+}
+
+// taskIDs returns every task ID (e.g. "go/bank-account"), sorted.
+func taskIDs() []string {
+	all, err := task.NewLoader(tasks.FS, tasksDir).LoadAll()
+	if err != nil {
+		return nil
+	}
+	ids := make([]string, 0, len(all))
+	for _, t := range all {
+		ids = append(ids, t.ID())
+	}
+	sort.Strings(ids)
+	return ids
+}
+
+// taskGlobs returns a "<language>/*" glob per language followed by every
+// task ID, for --filter and --exclude.
+func taskGlobs() []string {
+	globs := make([]string, 0, len(task.AllLanguages))
+	for _, lang := range languageNames() {
+		globs = append(globs, lang+"/*")
+	}
+	return append(globs, taskIDs()...)
+}
+
+// taskTags returns every tag used by a task, sorted and deduplicated.
+func taskTags() []string {
+	all, err := task.NewLoader(tasks.FS, tasksDir).LoadAll()
+	if err != nil {
+		return nil
+	}
+	tags := make([]string, 0, len(all))
+	for _, t := range all {
+		tags = append(tags, t.Tags...)
+	}
+	sort.Strings(tags)
+	return slices.Compact(tags)
+}
+
+// agentNames returns built-in and configured agent names.
+func agentNames() []string {
+	if c := completionConfig(); c != nil {
+		return c.ListAgents()
+	}
+	return nil
+}
+
+// languageNames returns the supported language names.
+func languageNames() []string {
+	names := make([]string, 0, len(task.AllLanguages))
+	for _, lang := range task.AllLanguages {
+		names = append(names, string(lang))
+	}
+	return names
+}
+
+// completionConfig returns the loaded config, loading it if completion runs
+// before PersistentPreRunE has.
+func completionConfig() *config.Config {
+	if cfg != nil {
+		return cfg
+	}
+	c, _, err := config.LoadLayered(config.LoadOptions{File: cfgFile, Overrides: cfgOverrides})
+	if err != nil {
+		return nil
+	}
+	return c
+}
+
+// listSubdirs returns the immediate subdirectories of dir as paths joined with dir.
+func listSubdirs(dir string) []string {
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return nil
+	}
+	dirs := make([]string, 0, len(entries))
+	for _, e := range entries {
+		if e.IsDir() {
+			dirs = append(dirs, filepath.Join(dir, e.Name()))
+		}
+	}
+	return dirs
+}
@@ -0,0 +1,129 @@
+package cli
+
+import (
+	"bytes"
+	"io"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/spf13/cobra"
+)
+
+func TestCompleteCommaList(t *testing.T) {
+	t.Parallel()
+
+	complete := completeCommaList(func() []string { return []string{"go", "rust", "zig"} })
+
+	got, directive := complete(nil, nil, "go,ru")
+	if !slices.Equal(got, []string{"go,rust"}) {
+		t.Fatalf("completions = %v, want [go,rust]", got)
+	}
+	if directive&cobra.ShellCompDirectiveNoSpace == 0 {
+		t.Fatal("expected NoSpace directive for comma lists")
+	}
+
+	got, _ = complete(nil, nil, "")
+	if len(got) != 3 {
+		t.Fatalf("completions = %v, want all values", got)
+	}
+}
+
+func TestTaskIDsIncludeLanguagePrefix(t *testing.T) {
+	t.Parallel()
+
+	ids := taskIDs()
+	if !slices.Contains(ids, "go/bank-account") {
+		t.Fatalf("expected go/bank-account in task IDs, got %d IDs", len(ids))
+	}
+}
+
+func TestTaskGlobsAndTags(t *testing.T) {
+	t.Parallel()
+
+	globs := taskGlobs()
+	if !slices.Contains(globs, "rust/*") || !slices.Contains(globs, "go/bank-account") {
+		t.Fatalf("taskGlobs() = %v, want language globs and task IDs", globs)
+	}
+
+	tags := taskTags()
+	if !slices.Contains(tags, "concurrency") {
+		t.Fatalf("taskTags() = %v, want concurrency", tags)
+	}
+	if !slices.IsSorted(tags) || len(slices.Compact(slices.Clone(tags))) != len(tags) {
+		t.Fatalf("taskTags() = %v, want sorted and unique", tags)
+	}
+}
+
+func TestCompleteLanguagesSkipsGivenArgs(t *testing.T) {
+	t.Parallel()
+
+	got, _ := completeLanguages(nil, []string{"go"}, "")
+	if slices.Contains(got, "go") || !slices.Contains(got, "rust") {
+		t.Fatalf("completeLanguages() = %v, want every language but go", got)
+	}
+}
+
+// completeCommand runs the hidden command the shell scripts call, as
+// "sanity __complete <args>", and returns the candidates it prints.
+func completeCommand(t *testing.T, args ...string) []string {
+	t.Helper()
+
+	registerCompletions()
+	prevCfg, prevOrigins, prevLogger := cfg, cfgOrigins, logger
+	t.Cleanup(func() {
+		cfg, cfgOrigins, logger = prevCfg, prevOrigins, prevLogger
+		rootCmd.SetArgs(nil)
+		rootCmd.SetOut(nil)
+		rootCmd.SetErr(nil)
+	})
+	var out bytes.Buffer
+	rootCmd.SetOut(&out)
+	rootCmd.SetErr(io.Discard)
+	rootCmd.SetArgs(append([]string{cobra.ShellCompRequestCmd}, args...))
+	if err := rootCmd.Execute(); err != nil {
+		t.Fatalf("__complete %q error = %v", args, err)
+	}
+	var candidates []string
+	for _, line := range strings.Split(strings.TrimSpace(out.String()), "\n") {
+		// The last line is the ":<directive>" for the shell.
+		if line != "" && !strings.HasPrefix(line, ":") {
+			name, _, _ := strings.Cut(line, "\t")
+			candidates = append(candidates, name)
+		}
+	}
+	return candidates
+}
+
+func TestCompleteRunAgent(t *testing.T) {
+	got := completeCommand(t, "run", "--agent", "")
+	if !slices.Contains(got, "codex") || !slices.Contains(got, "gemini") {
+		t.Fatalf("__complete run --agent = %v, want the built-in agents", got)
+	}
+}
+
+func TestCompleteTasksListTier(t *testing.T) {
+	got := completeCommand(t, "tasks", "list", "--tier", "")
+	if !slices.Equal(got, []string{"core", "extended", "all"}) {
+		t.Fatalf("__complete tasks list --tier = %v, want core, extended, all", got)
+	}
+}
+
+func TestCompleteSelectorFlags(t *testing.T) {
+	for _, command := range [][]string{{"eval"}, {"tasks", "list"}} {
+		for _, flag := range []string{"--filter", "--exclude"} {
+			got := completeCommand(t, append(slices.Clone(command), flag, "rust/m")...)
+			if !slices.Contains(got, "rust/macros") || slices.ContainsFunc(got, func(c string) bool { return !strings.HasPrefix(c, "rust/m") }) {
+				t.Errorf("__complete %v %s rust/m = %v, want rust/ tasks starting with m", command, flag, got)
+			}
+			got = completeCommand(t, append(slices.Clone(command), flag, "go/*,")...)
+			if !slices.Contains(got, "go/*,rust/*") {
+				t.Errorf("__complete %v %s go/*, = %v, want the next glob after the comma", command, flag, got)
+			}
+		}
+		got := completeCommand(t, append(slices.Clone(command), "--tag", "")...)
+		if !slices.Contains(got, "concurrency") {
+			t.Errorf("__complete %v --tag = %v, want concurrency", command, got)
+		}
+	}
+}
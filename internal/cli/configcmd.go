@@ -0,0 +1,127 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"text/tabwriter"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+var configShowOrigin bool
+
+var configCmd = &cobra.Command{
+	Use:   "config",
+	Short: "Inspect and validate the configuration",
+	Long: `Inspects and validates the configuration after all layers are merged.
+
+Layers apply in order, each overriding the ones before it: built-in defaults,
+/etc/sanity/config.toml, ~/.config/sanity/config.toml and ~/.sanity.toml, the
+sanity.toml in the working directory or its nearest parent up to the
+repository root, the --config file, and --set overrides.`,
+}
+
+var configShowCmd = &cobra.Command{
+	Use:   "show",
+	Short: "Print every effective config value",
+	// Stdout is the data itself, so the output theme must not touch it.
+	Annotations: map[string]string{annotationRawStdout: "true"},
+	Example: `  sanity config show
+  sanity config show --origin
+  sanity config show --origin --set harness.max_attempts=3`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		settings, err := cfg.Settings(cfgOrigins)
+		if err != nil {
+			return err
+		}
+		return writeConfigSettings(os.Stdout, settings, cfgOrigins, configShowOrigin)
+	},
+}
+
+var configValidateCmd = &cobra.Command{
+	Use:   "validate [file]",
+	Short: "Check the config files for unknown keys and invalid values",
+	Long: `Checks every config layer, or only the given file, and reports each
+problem with its file, line, and column: TOML syntax errors, keys that match
+no setting (such as a misspelled max_attemps), and invalid values. Exits
+with status 1 if there are any. eval --strict-config runs the same checks
+before a run.`,
+	Example: `  sanity config validate
+  sanity config validate ./sanity.toml`,
+	Args: cobra.MaximumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		var files []config.LayerFile
+		if len(args) == 1 {
+			if err := config.ValidateFile(args[0]); err != nil {
+				return reportConfigProblems(os.Stdout, err)
+			}
+			files = []config.LayerFile{{Layer: config.LayerCLI, Path: args[0]}}
+		} else {
+			_, origins, err := config.LoadLayered(config.LoadOptions{File: cfgFile, Overrides: cfgOverrides, Strict: true})
+			if err != nil {
+				return reportConfigProblems(os.Stdout, err)
+			}
+			files = origins.Files
+		}
+
+		if len(files) == 0 {
+			fmt.Println(" ✓ No config files found; using the built-in defaults")
+			return nil
+		}
+		for _, f := range files {
+			fmt.Printf(" ✓ %-8s %s\n", f.Layer, f.Path)
+		}
+		return nil
+	},
+}
+
+func init() {
+	configShowCmd.Flags().BoolVar(&configShowOrigin, "origin", false, "show the layer and file each value came from")
+	configCmd.AddCommand(configShowCmd)
+	configCmd.AddCommand(configValidateCmd)
+}
+
+// reportConfigProblems prints each problem of a strict load and returns an
+// exit error, or returns err unchanged if it is not a validation error.
+func reportConfigProblems(w io.Writer, err error) error {
+	var verr *config.ValidationError
+	if !errors.As(err, &verr) {
+		return err
+	}
+	for _, p := range verr.Problems {
+		_, _ = fmt.Fprintf(w, " ✗ %s\n", p)
+	}
+	return &exitError{code: 1}
+}
+
+// writeConfigSettings prints settings as TOML key = value lines. With
+// showOrigin, it first lists the loaded files and annotates each value with
+// the layer that set it.
+func writeConfigSettings(w io.Writer, settings []config.Setting, origins *config.Origins, showOrigin bool) error {
+	if !showOrigin {
+		for _, s := range settings {
+			if _, err := fmt.Fprintf(w, "%s = %s\n", s.Key, s.Value); err != nil {
+				return err
+			}
+		}
+		return nil
+	}
+
+	tw := tabwriter.NewWriter(w, 0, 0, 2, ' ', 0)
+	_, _ = fmt.Fprintf(tw, "# %s\tbuilt in\n", config.LayerDefault)
+	if origins != nil {
+		for _, f := range origins.Files {
+			_, _ = fmt.Fprintf(tw, "# %s\t%s\n", f.Layer, f.Path)
+		}
+	}
+	_, _ = fmt.Fprintln(tw)
+	for _, s := range settings {
+		_, _ = fmt.Fprintf(tw, "%s = %s\t# %s\n", s.Key, s.Value, s.Origin)
+	}
+	return tw.Flush()
+}
@@ -0,0 +1,43 @@
+package cli
+
+import (
+	"bytes"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestWriteConfigSettings(t *testing.T) {
+	t.Parallel()
+
+	project := config.LayerFile{Layer: config.LayerProject, Path: "/repo/sanity.toml"}
+	origins := &config.Origins{Files: []config.LayerFile{project}}
+	settings := []config.Setting{
+		{Key: "docker.go_image", Value: `"custom-go:latest"`, Origin: project},
+		{Key: "harness.max_attempts", Value: "5", Origin: config.LayerFile{Layer: config.LayerDefault}},
+	}
+
+	var plain bytes.Buffer
+	if err := writeConfigSettings(&plain, settings, origins, false); err != nil {
+		t.Fatalf("writeConfigSettings() error = %v", err)
+	}
+	if want := "docker.go_image = \"custom-go:latest\"\nharness.max_attempts = 5\n"; plain.String() != want {
+		t.Errorf("output = %q, want %q", plain.String(), want)
+	}
+
+	var withOrigin bytes.Buffer
+	if err := writeConfigSettings(&withOrigin, settings, origins, true); err != nil {
+		t.Fatalf("writeConfigSettings() error = %v", err)
+	}
+	out := withOrigin.String()
+	for _, want := range []string{
+		"# project  /repo/sanity.toml\n",
+		`docker.go_image = "custom-go:latest"  # project (/repo/sanity.toml)`,
+		"harness.max_attempts = 5              # default\n",
+	} {
+		if !strings.Contains(out, want) {
+			t.Errorf("output missing %q:\n%s", want, out)
+		}
+	}
+}
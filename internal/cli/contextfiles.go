@@ -0,0 +1,75 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"unicode/utf8"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// ContextFile records a configured context file placed in a task workspace.
+type ContextFile struct {
+	Path   string `json:"path"`
+	Source string `json:"source"`
+	Hash   string `json:"hash"`
+	Chars  int    `json:"chars"`
+}
+
+// placeContextFiles copies the context files that apply to lang into
+// workspace and returns what was placed. Sources are read at placement time,
+// so every task records the exact bytes its agent saw. Context files may add
+// files but never replace task files.
+func placeContextFiles(files []config.ContextFile, lang, workspace string) ([]ContextFile, error) {
+	var placed []ContextFile
+	for _, f := range files {
+		if !f.AppliesTo(lang) {
+			continue
+		}
+		data, err := os.ReadFile(f.Source)
+		if err != nil {
+			return placed, fmt.Errorf("reading context file: %w", err)
+		}
+		dest := filepath.Join(workspace, filepath.FromSlash(f.Dest))
+		if _, err := os.Lstat(dest); err == nil {
+			return placed, fmt.Errorf("context file %s would overwrite an existing workspace file", f.Dest)
+		} else if !errors.Is(err, fs.ErrNotExist) {
+			return placed, fmt.Errorf("checking %s: %w", f.Dest, err)
+		}
+		if err := os.MkdirAll(filepath.Dir(dest), 0o755); err != nil {
+			return placed, fmt.Errorf("creating directory for %s: %w", f.Dest, err)
+		}
+		if err := os.WriteFile(dest, data, 0o644); err != nil {
+			return placed, fmt.Errorf("writing context file %s: %w", f.Dest, err)
+		}
+		placed = append(placed, ContextFile{
+			Path:   f.Dest,
+			Source: f.Source,
+			Hash:   hashBytes(data),
+			Chars:  utf8.RuneCount(data),
+		})
+	}
+	return placed, nil
+}
+
+// removeContextFiles deletes placed context files from workspace.
+func removeContextFiles(placed []ContextFile, workspace string) error {
+	for _, f := range placed {
+		if err := os.Remove(filepath.Join(workspace, filepath.FromSlash(f.Path))); err != nil && !errors.Is(err, fs.ErrNotExist) {
+			return fmt.Errorf("removing context file %s: %w", f.Path, err)
+		}
+	}
+	return nil
+}
+
+// contextChars returns the total size of placed context files.
+func contextChars(placed []ContextFile) int {
+	n := 0
+	for _, f := range placed {
+		n += f.Chars
+	}
+	return n
+}
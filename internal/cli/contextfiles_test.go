@@ -0,0 +1,55 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestPlaceContextFiles(t *testing.T) {
+	t.Parallel()
+
+	src := t.TempDir()
+	agents := filepath.Join(src, "AGENTS.md")
+	style := filepath.Join(src, "style.md")
+	if err := os.WriteFile(agents, []byte("be careful"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(style, []byte("gofmt"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	files := []config.ContextFile{
+		{Source: agents, Dest: "AGENTS.md"},
+		{Source: style, Dest: "docs/STYLE.md", Languages: []string{"go"}},
+	}
+
+	workspace := t.TempDir()
+	placed, err := placeContextFiles(files, "rust", workspace)
+	if err != nil {
+		t.Fatalf("placeContextFiles() error = %v", err)
+	}
+	if len(placed) != 1 || placed[0].Path != "AGENTS.md" || placed[0].Hash != hashBytes([]byte("be careful")) {
+		t.Fatalf("placed = %+v", placed)
+	}
+	if _, err := os.Stat(filepath.Join(workspace, "docs", "STYLE.md")); err == nil {
+		t.Fatal("go-only context file placed for rust")
+	}
+
+	goWorkspace := t.TempDir()
+	placed, err = placeContextFiles(files, "go", goWorkspace)
+	if err != nil || len(placed) != 2 {
+		t.Fatalf("placeContextFiles(go) = %+v, %v", placed, err)
+	}
+	data, err := os.ReadFile(filepath.Join(goWorkspace, "docs", "STYLE.md"))
+	if err != nil || string(data) != "gofmt" {
+		t.Fatalf("STYLE.md = %q, %v", data, err)
+	}
+
+	// Task files always win over context files.
+	if _, err := placeContextFiles(files[:1], "go", goWorkspace); err == nil || !strings.Contains(err.Error(), "overwrite") {
+		t.Fatalf("expected overwrite error, got %v", err)
+	}
+}
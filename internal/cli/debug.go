@@ -0,0 +1,449 @@
+package cli
+
+import (
+	"bufio"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// debugPageLines is how many agent output lines `output` shows at a time.
+const debugPageLines = 40
+
+var debugOutput string
+
+var debugCmd = &cobra.Command{
+	Use:   "debug <session> <task>",
+	Short: "Step through a recorded task attempt interactively",
+	Long: `Opens an interactive prompt for one task of a past eval run, to work out why
+it passed or failed without re-running the agent.
+
+Commands at the prompt:
+  info              the recorded result
+  prompt            the prompt the agent was given
+  output [N|all]    the next N (default 40) lines of agent output, or all of it
+  attempt <n>       move the output cursor to the start of attempt n
+  snapshots         the workspace snapshots that can be verified
+  diff <n>          the changes attempt n made over attempt n-1
+  verify [n|final]  re-run validation on a snapshot (default: final)
+  score             re-score the task from the last verification
+  help, quit
+
+Verification copies the snapshot into a temporary workspace, adds the current
+hidden tests, and validates it in the task's container image, pinned by digest
+when the run recorded one. Validation logs go to --output (default: a new
+temporary directory); the run directory is never modified. Archives from
+'sanity archive' are accepted in place of <session>.`,
+	Example: `  sanity debug eval-results/2026-01-07T120000-gemini go/bank-account
+  sanity debug gemini.tar.gz rust/regex-lite -o /tmp/regex-debug`,
+	Args: cobra.ExactArgs(2),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		sessionArgs, cleanup, err := resolveRunArgs(args[:1])
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		d, err := newDebugSession(sessionArgs[0], args[1])
+		if err != nil {
+			return err
+		}
+		defer d.close()
+		d.outDir = debugOutput
+		return d.loop(cmd.Context(), cmd.InOrStdin())
+	},
+}
+
+func init() {
+	debugCmd.Flags().StringVarP(&debugOutput, "output", "o", "", "directory for verification logs (default: a new temporary directory)")
+}
+
+// debugSession is the state of one `sanity debug` prompt.
+type debugSession struct {
+	dir       string
+	taskDir   string
+	task      *task.Task
+	loader    *task.Loader
+	result    EvalResult
+	record    *ReplayRecord
+	logLines  []string
+	logPos    int
+	snapshots []int
+	outDir    string
+	runner    *replayRunner
+	verified  *debugVerification
+	out       io.Writer
+}
+
+// debugVerification is the outcome of the last `verify`.
+type debugVerification struct {
+	Target  string
+	Outcome ReplayOutcome
+}
+
+// newDebugSession loads the recorded result, replay record, agent output, and
+// snapshot list of taskRef in the eval run at dir.
+func newDebugSession(dir, taskRef string) (*debugSession, error) {
+	summary, err := loadSummaryFromDir(dir)
+	if err != nil {
+		return nil, err
+	}
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	allTasks, err := loader.LoadAll()
+	if err != nil {
+		return nil, fmt.Errorf("loading tasks: %w", err)
+	}
+	t, err := task.ResolveRef(allTasks, taskRef)
+	if err != nil {
+		return nil, err
+	}
+
+	d := &debugSession{dir: dir, task: t, loader: loader, out: os.Stdout}
+	found := false
+	for _, r := range summary.Results {
+		if r.Task == t.ID() {
+			d.result, found = r, true
+			break
+		}
+	}
+	if !found {
+		return nil, fmt.Errorf("%s has no result in %s", t.ID(), dir)
+	}
+	_, d.taskDir = evalWorkspacePaths(dir, t)
+
+	if data, err := os.ReadFile(filepath.Join(d.taskDir, replayRecordName)); err == nil {
+		var rec ReplayRecord
+		if err := json.Unmarshal(data, &rec); err != nil {
+			return nil, fmt.Errorf("parsing %s: %w", replayRecordName, err)
+		}
+		d.record = &rec
+	}
+	if data, err := readArtifact(filepath.Join(d.taskDir, "agent.log")); err == nil {
+		d.logLines = strings.Split(strings.TrimRight(string(data), "\n"), "\n")
+	}
+	d.snapshots, err = listAttemptSnapshots(d.taskDir)
+	if err != nil {
+		return nil, err
+	}
+	return d, nil
+}
+
+// listAttemptSnapshots returns the attempt numbers with a snapshot, in order.
+func listAttemptSnapshots(taskDir string) ([]int, error) {
+	entries, err := os.ReadDir(filepath.Join(taskDir, attemptSnapshotsDir))
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("reading snapshots: %w", err)
+	}
+	var attempts []int
+	for _, e := range entries {
+		n, ok := strings.CutPrefix(e.Name(), "attempt-")
+		if !e.IsDir() || !ok {
+			continue
+		}
+		if i, err := strconv.Atoi(n); err == nil {
+			attempts = append(attempts, i)
+		}
+	}
+	sort.Ints(attempts)
+	return attempts, nil
+}
+
+func (d *debugSession) close() {
+	if d.runner != nil {
+		_ = d.runner.Close()
+	}
+}
+
+// loop reads commands from in until quit or end of input.
+func (d *debugSession) loop(ctx context.Context, in io.Reader) error {
+	fmt.Fprintf(d.out, " Debugging %s in %s. Type 'help' for commands.\n", d.task.ID(), d.dir)
+	scanner := bufio.NewScanner(in)
+	for {
+		fmt.Fprint(d.out, "debug> ")
+		if !scanner.Scan() {
+			fmt.Fprintln(d.out)
+			return scanner.Err()
+		}
+		quit, err := d.exec(ctx, scanner.Text())
+		if err != nil {
+			fmt.Fprintf(d.out, " ✗ %v\n", err)
+		}
+		if quit || ctx.Err() != nil {
+			return nil
+		}
+	}
+}
+
+// exec runs one prompt command. Errors are reported at the prompt and never
+// end the session.
+func (d *debugSession) exec(ctx context.Context, line string) (quit bool, err error) {
+	fields := strings.Fields(line)
+	if len(fields) == 0 {
+		return false, nil
+	}
+	arg := ""
+	if len(fields) > 1 {
+		arg = fields[1]
+	}
+	switch fields[0] {
+	case "help", "?":
+		d.printHelp()
+	case "info":
+		d.printInfo()
+	case "prompt":
+		return false, d.printPrompt()
+	case "output", "o":
+		return false, d.printOutput(arg)
+	case "attempt":
+		return false, d.seekAttempt(arg)
+	case "snapshots":
+		d.printSnapshots()
+	case "diff":
+		return false, d.printDiff(arg)
+	case "verify":
+		return false, d.verify(ctx, arg)
+	case "score":
+		return false, d.score()
+	case "quit", "exit", "q":
+		return true, nil
+	default:
+		return false, fmt.Errorf("unknown command %q (try 'help')", fields[0])
+	}
+	return false, nil
+}
+
+func (d *debugSession) printHelp() {
+	fmt.Fprint(d.out, `  info              the recorded result
+  prompt            the prompt the agent was given
+  output [N|all]    the next N lines of agent output, or all of it
+  attempt <n>       move the output cursor to the start of attempt n
+  snapshots         the workspace snapshots that can be verified
+  diff <n>          the changes attempt n made over attempt n-1
+  verify [n|final]  re-run validation on a snapshot
+  score             re-score the task from the last verification
+  quit
+`)
+}
+
+func (d *debugSession) printInfo() {
+	r := d.result
+	fmt.Fprintf(d.out, " Task:      %s\n", r.Task)
+	fmt.Fprintf(d.out, " Status:    %s (score %.2f of %.2f)\n", r.Status, r.WeightedScore, r.Weight)
+	fmt.Fprintf(d.out, " Attempts:  %d\n", r.Attempts)
+	fmt.Fprintf(d.out, " Agent:     %.1fs", r.AgentTime)
+	if r.AgentTimedOut {
+		fmt.Fprint(d.out, " (timed out)")
+	}
+	fmt.Fprintln(d.out)
+	if r.FailureClass != "" && r.FailureClass != FailureClassNone {
+		fmt.Fprintf(d.out, " Failure:   %s\n", r.FailureClass)
+	}
+	if r.Error != "" {
+		fmt.Fprintf(d.out, " Error:     %s\n", r.Error)
+	}
+	fmt.Fprintf(d.out, " Output:    %d lines\n", len(d.logLines))
+}
+
+func (d *debugSession) printPrompt() error {
+	if d.record == nil {
+		return fmt.Errorf("no %s for %s; the prompt was not recorded", replayRecordName, d.task.ID())
+	}
+	fmt.Fprintln(d.out, d.record.Prompt)
+	return nil
+}
+
+// printOutput shows the agent output from the cursor on and advances it.
+func (d *debugSession) printOutput(arg string) error {
+	if len(d.logLines) == 0 {
+		return errors.New("no agent output was recorded")
+	}
+	n := debugPageLines
+	switch {
+	case arg == "all":
+		n = len(d.logLines)
+	case arg != "":
+		v, err := strconv.Atoi(arg)
+		if err != nil || v <= 0 {
+			return fmt.Errorf("invalid line count %q", arg)
+		}
+		n = v
+	}
+	if d.logPos >= len(d.logLines) {
+		fmt.Fprintln(d.out, " (end of output; 'attempt 1' starts over)")
+		return nil
+	}
+	end := min(d.logPos+n, len(d.logLines))
+	for i := d.logPos; i < end; i++ {
+		fmt.Fprintf(d.out, "%5d │ %s\n", i+1, d.logLines[i])
+	}
+	d.logPos = end
+	if end < len(d.logLines) {
+		fmt.Fprintf(d.out, " (%d more lines)\n", len(d.logLines)-end)
+	}
+	return nil
+}
+
+// seekAttempt moves the output cursor to the start of attempt arg. Attempts
+// after the first begin at the harness's RETRY separator.
+func (d *debugSession) seekAttempt(arg string) error {
+	n, err := strconv.Atoi(arg)
+	if err != nil || n < 1 {
+		return fmt.Errorf("usage: attempt <n>, with n from 1")
+	}
+	if n == 1 {
+		d.logPos = 0
+		return nil
+	}
+	marker := fmt.Sprintf("=== RETRY %d ", n-1)
+	for i, line := range d.logLines {
+		if strings.HasPrefix(line, marker) {
+			d.logPos = i
+			return nil
+		}
+	}
+	return fmt.Errorf("attempt %d is not in the agent output", n)
+}
+
+func (d *debugSession) printSnapshots() {
+	for _, n := range d.snapshots {
+		fmt.Fprintf(d.out, " attempt %d  %s\n", n, filepath.Join(d.taskDir, attemptSnapshotPath(n)))
+	}
+	if d.record != nil {
+		fmt.Fprintf(d.out, " final      %s (%d files)\n", filepath.Join(d.taskDir, replayRecordName), len(d.record.Files))
+	}
+	if len(d.snapshots) == 0 && d.record == nil {
+		fmt.Fprintln(d.out, " No snapshots were recorded for this task.")
+	}
+}
+
+func (d *debugSession) printDiff(arg string) error {
+	n, err := strconv.Atoi(arg)
+	if err != nil || n < 2 {
+		return errors.New("usage: diff <n>, with n from 2")
+	}
+	data, err := os.ReadFile(filepath.Join(d.taskDir, attemptSnapshotPath(n)+".diff"))
+	if err != nil {
+		return fmt.Errorf("no diff for attempt %d: %w", n, err)
+	}
+	if len(data) == 0 {
+		fmt.Fprintf(d.out, " Attempt %d changed nothing.\n", n)
+		return nil
+	}
+	_, err = d.out.Write(data)
+	return err
+}
+
+// verifyRecord builds the replay record for target: the recorded final
+// workspace, or the snapshot of an attempt. Runs without replay.json fall
+// back to the task's current validation command.
+func (d *debugSession) verifyRecord(target string) (*ReplayRecord, error) {
+	rec := ReplayRecord{Task: d.task.ID()}
+	if d.record != nil {
+		rec = *d.record
+	} else {
+		_, rec.ValidationCommand = buildValidationCommands(d.task)
+		rec.ValidationTimeout = resolveValidationTimeout(d.task.Timeout)
+		image, err := runner.TaskImage(cfg, d.loader, d.task)
+		if err != nil {
+			return nil, err
+		}
+		rec.Image = image
+	}
+	if target == "final" {
+		if d.record == nil {
+			return nil, fmt.Errorf("no %s for %s; verify an attempt snapshot instead", replayRecordName, d.task.ID())
+		}
+		return &rec, nil
+	}
+	n, err := strconv.Atoi(target)
+	if err != nil || n < 1 {
+		return nil, fmt.Errorf("usage: verify [n|final]")
+	}
+	dir := filepath.Join(d.taskDir, attemptSnapshotPath(n))
+	if _, err := os.Stat(dir); err != nil {
+		return nil, fmt.Errorf("no snapshot for attempt %d", n)
+	}
+	files, err := collectReplayFiles(dir)
+	if err != nil {
+		return nil, err
+	}
+	rec.Files = files
+	return &rec, nil
+}
+
+// verify re-runs validation on a snapshot in a temporary workspace.
+func (d *debugSession) verify(ctx context.Context, target string) error {
+	if target == "" {
+		target = "final"
+	}
+	rec, err := d.verifyRecord(target)
+	if err != nil {
+		return err
+	}
+	if d.outDir == "" {
+		if d.outDir, err = os.MkdirTemp("", "sanity-debug-*"); err != nil {
+			return fmt.Errorf("creating output directory: %w", err)
+		}
+	}
+	if d.runner == nil {
+		if d.runner, err = newReplayRunner(); err != nil {
+			return err
+		}
+	}
+	d.runner.pin(d.task, rec)
+
+	name := "final"
+	if target != "final" {
+		name = "attempt-" + target
+	}
+	fmt.Fprintf(d.out, " ▶ Validating %s snapshot...\n", name)
+	outDir := filepath.Join(d.outDir, name)
+	outcome := replayTask(ctx, d.runner.Runner, d.loader, d.task, rec, outDir)
+	d.verified = &debugVerification{Target: name, Outcome: outcome}
+
+	var notes []string
+	if outcome.TestsChanged {
+		notes = append(notes, "hidden tests changed since the run")
+	}
+	if outcome.ImageDrift {
+		notes = append(notes, "image changed since the run")
+	}
+	if outcome.Error != "" {
+		notes = append(notes, outcome.Error)
+	}
+	fmt.Fprintf(d.out, " %s in %.1fs  %s\n", passLabel(outcome.Passed), outcome.Duration, strings.Join(notes, "; "))
+	fmt.Fprintf(d.out, " Log: %s\n", filepath.Join(outDir, fmt.Sprintf("%s-%s", d.task.Language, d.task.Slug), "validation.log"))
+	return nil
+}
+
+// score re-scores the task as if the last verification had been the run's
+// validation. The recorded agent timeout still applies.
+func (d *debugSession) score() error {
+	if d.verified == nil {
+		return errors.New("nothing verified yet; run 'verify' first")
+	}
+	o := d.verified.Outcome
+	weight := taskWeight(d.task)
+	status := task.DetermineStatus(o.Passed, d.result.AgentTimedOut, o.Error)
+	score := task.ScoreResult(o.Passed, d.result.AgentTimedOut, o.Error, weight)
+	fmt.Fprintf(d.out, " Recorded:  %-18s %.2f\n", d.result.Status, d.result.WeightedScore)
+	fmt.Fprintf(d.out, " Rescored:  %-18s %.2f  (%s snapshot)\n", status, score, d.verified.Target)
+	return nil
+}
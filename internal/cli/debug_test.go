@@ -0,0 +1,90 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestDebugSessionCommands(t *testing.T) {
+	t.Parallel()
+
+	evalDir := t.TempDir()
+	summary := EvalSummary{
+		SchemaVersion: summarySchemaVersion,
+		Results: []EvalResult{{
+			Task: "go/bank-account", Status: "fail", Attempts: 2, Error: "tests failed",
+		}},
+	}
+	data, err := json.Marshal(summary)
+	if err != nil {
+		t.Fatal(err)
+	}
+	rec, err := json.Marshal(ReplayRecord{Task: "go/bank-account", Prompt: "Implement the bank account."})
+	if err != nil {
+		t.Fatal(err)
+	}
+	writePackFiles(t, evalDir, map[string]string{
+		"summary.json":                               string(data),
+		"go-bank-account/agent.log":                  "reading files\nediting\n\n\n=== RETRY 1 (after 5s delay) ===\n\nsecond try\n",
+		"go-bank-account/replay.json":                string(rec),
+		"go-bank-account/snapshots/attempt-1/a.go":   "package bank\n",
+		"go-bank-account/snapshots/attempt-2/a.go":   "package bank // v2\n",
+		"go-bank-account/snapshots/attempt-2.diff":   "-package bank\n+package bank // v2\n",
+		"go-bank-account/snapshots/attempt-2/README": "notes\n",
+	})
+
+	d, err := newDebugSession(evalDir, "go/bank-account")
+	if err != nil {
+		t.Fatalf("newDebugSession() error = %v", err)
+	}
+	if len(d.snapshots) != 2 || d.snapshots[1] != 2 {
+		t.Fatalf("snapshots = %v, want [1 2]", d.snapshots)
+	}
+
+	var out bytes.Buffer
+	d.out = &out
+	input := strings.Join([]string{"info", "prompt", "output 2", "attempt 2", "output", "diff 2", "score", "bogus", "quit", "info"}, "\n")
+	if err := d.loop(context.Background(), strings.NewReader(input)); err != nil {
+		t.Fatalf("loop() error = %v", err)
+	}
+	got := out.String()
+	for _, want := range []string{
+		"Status:    fail",
+		"Implement the bank account.",
+		"    1 │ reading files\n    2 │ editing\n (5 more lines)",
+		"    5 │ === RETRY 1 (after 5s delay) ===",
+		"+package bank // v2",
+		"nothing verified yet",
+		`unknown command "bogus"`,
+	} {
+		if !strings.Contains(got, want) {
+			t.Errorf("output missing %q:\n%s", want, got)
+		}
+	}
+	if strings.Count(got, "Status:") != 1 {
+		t.Error("commands after quit were run")
+	}
+
+	if _, err := d.verifyRecord("3"); err == nil {
+		t.Error("verifyRecord(3) should fail without a snapshot")
+	}
+	snap, err := d.verifyRecord("2")
+	if err != nil {
+		t.Fatalf("verifyRecord(2) error = %v", err)
+	}
+	if string(snap.Files["a.go"]) != "package bank // v2\n" || snap.Prompt != "Implement the bank account." {
+		t.Errorf("verifyRecord(2) = %+v", snap)
+	}
+
+	if _, err := newDebugSession(evalDir, "rust/regex-lite"); err == nil {
+		t.Error("expected an error for a task without a result")
+	}
+	if _, err := os.Stat(filepath.Join(evalDir, "go-bank-account", "snapshots", "attempt-2", "a.go")); err != nil {
+		t.Errorf("snapshot was modified: %v", err)
+	}
+}
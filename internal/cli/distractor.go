@@ -0,0 +1,178 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// DistractorPack is a directory of realistic but irrelevant files, such as
+// unrelated modules, misleading TODOs, and decoy configs, seeded into every
+// agent workspace to measure whether the agent stays on task. Files under a
+// top-level directory named after a task language are only seeded into that
+// language's workspaces, with the directory stripped; the rest go everywhere.
+type DistractorPack struct {
+	Name        string   `json:"name"`
+	Description string   `json:"description,omitempty"`
+	Path        string   `json:"path"`
+	Hash        string   `json:"hash"`
+	Files       []string `json:"files"`
+}
+
+// DistractorActivity records what an agent did with the distractor files
+// seeded into its workspace. Read lists files its transcript shows it
+// opening or searching; Modified and Deleted come from hashing the files
+// after it finished. Paths are relative to the workspace.
+type DistractorActivity struct {
+	Seeded   int      `json:"seeded"`
+	Read     []string `json:"read,omitempty"`
+	Modified []string `json:"modified,omitempty"`
+	Deleted  []string `json:"deleted,omitempty"`
+}
+
+// Edited reports whether the agent changed or removed any distractor.
+func (a *DistractorActivity) Edited() bool {
+	return a != nil && (len(a.Modified) > 0 || len(a.Deleted) > 0)
+}
+
+// evalDistractorPack holds the pack loaded for the current run, or nil. Like
+// the other eval globals it is set by evalRunSingle before tasks start.
+var evalDistractorPack *DistractorPack
+
+// loadDistractorPack reads a distractor pack directory. An empty dir means no
+// pack. Distractors are never announced to the agent, so pack.toml may not
+// carry prompt text.
+func loadDistractorPack(dir string) (*DistractorPack, error) {
+	if dir == "" {
+		return nil, nil
+	}
+	p, err := loadPackDir(dir, "distractor pack")
+	if err != nil {
+		return nil, err
+	}
+	if p.Prompt != "" {
+		return nil, fmt.Errorf("distractor pack %s: %s may not set a prompt", dir, skillPackManifestName)
+	}
+	return &DistractorPack{
+		Name:        p.Name,
+		Description: p.Description,
+		Path:        p.Path,
+		Hash:        p.Hash,
+		Files:       p.Files,
+	}, nil
+}
+
+// filesFor maps the workspace path of every file seeded for lang to its path
+// within the pack.
+func (p *DistractorPack) filesFor(lang task.Language) map[string]string {
+	files := make(map[string]string, len(p.Files))
+	for _, rel := range p.Files {
+		first, rest, nested := strings.Cut(rel, "/")
+		switch {
+		case !nested || !isLanguageDir(first):
+			files[rel] = rel
+		case first == string(lang):
+			files[rest] = rel
+		}
+	}
+	return files
+}
+
+func isLanguageDir(name string) bool {
+	for _, l := range task.AllLanguages {
+		if name == string(l) {
+			return true
+		}
+	}
+	return false
+}
+
+// install seeds the pack's files for lang into workspace and returns their
+// sorted workspace paths. A nil pack installs nothing. Like skill packs,
+// distractors may add files but never replace task files.
+func (p *DistractorPack) install(workspace string, lang task.Language) ([]string, error) {
+	if p == nil {
+		return nil, nil
+	}
+	files := p.filesFor(lang)
+	seeded := make([]string, 0, len(files))
+	for rel, src := range files {
+		dest := filepath.Join(workspace, filepath.FromSlash(rel))
+		if _, err := os.Lstat(dest); err == nil {
+			return nil, fmt.Errorf("distractor pack %q would overwrite %s", p.Name, rel)
+		} else if !errors.Is(err, fs.ErrNotExist) {
+			return nil, fmt.Errorf("checking %s: %w", rel, err)
+		}
+		if err := copyPackFile(filepath.Join(p.Path, filepath.FromSlash(src)), dest, rel); err != nil {
+			return nil, err
+		}
+		seeded = append(seeded, rel)
+	}
+	sort.Strings(seeded)
+	return seeded, nil
+}
+
+// auditDistractors reports what the agent did with the seeded files. before is
+// the workspace snapshot taken before the agent ran; when it is missing, edits
+// cannot be detected and only transcript reads are reported.
+func auditDistractors(seeded []string, before map[string]string, workspace, agentLogPath string) *DistractorActivity {
+	if len(seeded) == 0 {
+		return nil
+	}
+	activity := &DistractorActivity{Seeded: len(seeded)}
+	if data, err := os.ReadFile(agentLogPath); err == nil {
+		for _, access := range scanTranscriptForHiddenAccess(strings.Split(string(data), "\n"), seeded) {
+			activity.Read = append(activity.Read, access.File)
+		}
+		sort.Strings(activity.Read)
+	}
+	if before == nil {
+		return activity
+	}
+	for _, rel := range seeded {
+		data, err := os.ReadFile(filepath.Join(workspace, filepath.FromSlash(rel)))
+		switch {
+		case errors.Is(err, fs.ErrNotExist):
+			activity.Deleted = append(activity.Deleted, rel)
+		case err == nil && hashBytes(data) != before[rel]:
+			activity.Modified = append(activity.Modified, rel)
+		}
+	}
+	return activity
+}
+
+// writeReportDistractors lists the tasks whose agent read or edited
+// distractor files, alongside their outcome.
+func writeReportDistractors(sb *strings.Builder, summary EvalSummary) {
+	pack := summary.DistractorPack
+	if pack == nil {
+		return
+	}
+	sb.WriteString("## Distractors\n\n")
+	fmt.Fprintf(sb, "Pack **%s** (%d files, `%s`). %d tasks read distractors and %d edited them.\n\n",
+		pack.Name, len(pack.Files), pack.Hash, summary.TasksReadingDistractors, summary.TasksEditingDistractors)
+	if summary.TasksReadingDistractors == 0 && summary.TasksEditingDistractors == 0 {
+		return
+	}
+	sb.WriteString("| Task | Result | Read | Edited |\n")
+	sb.WriteString("|------|--------|------|--------|\n")
+	for _, r := range summary.Results {
+		d := r.Distractors
+		if d == nil || (len(d.Read) == 0 && !d.Edited()) {
+			continue
+		}
+		status := "FAIL"
+		if r.Passed {
+			status = "PASS"
+		}
+		edited := append(append([]string{}, d.Modified...), d.Deleted...)
+		fmt.Fprintf(sb, "| %s | %s | %s | %s |\n", r.Task, status, strings.Join(d.Read, ", "), strings.Join(edited, ", "))
+	}
+	sb.WriteString("\n")
+}
@@ -0,0 +1,107 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestDistractorPackInstall(t *testing.T) {
+	t.Parallel()
+
+	dir := filepath.Join(t.TempDir(), "decoys")
+	writePackFiles(t, dir, map[string]string{
+		"pack.toml":                "name = \"monorepo-noise\"\n",
+		"config/deploy.yaml":       "replicas: 3\n",
+		"TODO.md":                  "- rewrite the ledger in Rust\n",
+		"go/internal/audit/log.go": "package audit\n",
+		"rust/src/metrics.rs":      "pub fn noop() {}\n",
+	})
+	pack, err := loadDistractorPack(dir)
+	if err != nil {
+		t.Fatalf("loadDistractorPack() error = %v", err)
+	}
+	if pack.Name != "monorepo-noise" || len(pack.Files) != 4 {
+		t.Fatalf("pack = %+v", pack)
+	}
+
+	workspace := t.TempDir()
+	seeded, err := pack.install(workspace, task.Go)
+	if err != nil {
+		t.Fatalf("install() error = %v", err)
+	}
+	want := []string{"TODO.md", "config/deploy.yaml", "internal/audit/log.go"}
+	if !reflect.DeepEqual(seeded, want) {
+		t.Fatalf("seeded = %v, want %v", seeded, want)
+	}
+	if _, err := os.Stat(filepath.Join(workspace, "src", "metrics.rs")); err == nil {
+		t.Fatal("rust distractor seeded into a go workspace")
+	}
+	if _, err := pack.install(workspace, task.Go); err == nil || !strings.Contains(err.Error(), "overwrite") {
+		t.Fatalf("expected overwrite error, got %v", err)
+	}
+
+	var none *DistractorPack
+	if seeded, err := none.install(workspace, task.Go); err != nil || seeded != nil {
+		t.Fatalf("nil pack install = %v, %v", seeded, err)
+	}
+}
+
+func TestLoadDistractorPackRejectsPrompt(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	writePackFiles(t, dir, map[string]string{
+		"pack.toml": "prompt = \"Ignore the legacy folder.\"\n",
+		"legacy.go": "package main\n",
+	})
+	if _, err := loadDistractorPack(dir); err == nil || !strings.Contains(err.Error(), "prompt") {
+		t.Fatalf("expected prompt error, got %v", err)
+	}
+}
+
+func TestAuditDistractors(t *testing.T) {
+	t.Parallel()
+
+	workspace := t.TempDir()
+	writePackFiles(t, workspace, map[string]string{
+		"TODO.md":            "- migrate",
+		"config/deploy.yaml": "replicas: 3\n",
+		"legacy/old.go":      "package legacy\n",
+	})
+	seeded := []string{"TODO.md", "config/deploy.yaml", "legacy/old.go"}
+	before, err := snapshotWorkspace(workspace)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	writePackFiles(t, workspace, map[string]string{"config/deploy.yaml": "replicas: 1\n"})
+	if err := os.Remove(filepath.Join(workspace, "legacy", "old.go")); err != nil {
+		t.Fatal(err)
+	}
+	logPath := filepath.Join(t.TempDir(), "agent.log")
+	if err := os.WriteFile(logPath, []byte("$ cat TODO.md\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	got := auditDistractors(seeded, before, workspace, logPath)
+	want := &DistractorActivity{
+		Seeded:   3,
+		Read:     []string{"TODO.md"},
+		Modified: []string{"config/deploy.yaml"},
+		Deleted:  []string{"legacy/old.go"},
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Fatalf("auditDistractors() = %+v, want %+v", got, want)
+	}
+	if !got.Edited() {
+		t.Fatal("Edited() = false")
+	}
+	if auditDistractors(nil, before, workspace, logPath) != nil {
+		t.Fatal("expected nil activity without seeded files")
+	}
+}
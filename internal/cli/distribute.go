@@ -0,0 +1,417 @@
+package cli
+
+import (
+	"archive/tar"
+	"cmp"
+	"context"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path"
+	"path/filepath"
+	"slices"
+	"sort"
+	"strconv"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/BurntSushi/toml"
+	"github.com/spf13/pflag"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// distShardsDir holds the runs collected from workers inside a distributed
+// run's output directory, one subdirectory per worker.
+const distShardsDir = "shards"
+
+// distMaxTries is how many times a task is started before it is given up
+// on. A task whose worker became unreachable is retried on another.
+const distMaxTries = 2
+
+// distWorkdir is the remote scratch directory when a worker sets none. A
+// relative path is under the SSH user's home directory.
+const distWorkdir = "sanity-distributed"
+
+// sshUnreachable is the exit status ssh uses for its own errors, such as a
+// refused connection, as opposed to the remote command's.
+const sshUnreachable = 255
+
+// distLocalFlags are flags the coordinator resolves itself instead of passing
+// to workers: task selection becomes one --tasks per remote run, output goes
+// to the worker's scratch directory, and local paths mean nothing remotely.
+var distLocalFlags = []string{
+	"distribute", "output", "resume", "pick", "dry-run", "parallel", "repeat",
+	"tasks", "lang", "tier", "difficulty", "filter", "exclude", "tag",
+	"timeout", "no-progress", "config", "tasks-dir", "theme", "no-color",
+}
+
+// distWorker is one [[workers]] entry of a --distribute file.
+type distWorker struct {
+	Name    string   `toml:"name"`
+	Host    string   `toml:"host"`     // ssh destination, e.g. bench@10.0.0.5
+	SSHArgs []string `toml:"ssh_args"` // Extra ssh options, e.g. ["-p", "2222"]
+	Sanity  string   `toml:"sanity"`   // Remote sanity command
+	Workdir string   `toml:"workdir"`  // Remote scratch directory
+	Slots   int      `toml:"slots"`    // Tasks the worker runs at once
+}
+
+// distWorkersFile is the --distribute file.
+type distWorkersFile struct {
+	Workers []distWorker `toml:"workers"`
+}
+
+// loadDistWorkers reads and checks a --distribute file, filling in defaults.
+func loadDistWorkers(path string) ([]distWorker, error) {
+	var f distWorkersFile
+	if _, err := toml.DecodeFile(path, &f); err != nil {
+		return nil, fmt.Errorf("reading workers file: %w", err)
+	}
+	if len(f.Workers) == 0 {
+		return nil, fmt.Errorf("workers file %s defines no workers", path)
+	}
+	seen := make(map[string]bool, len(f.Workers))
+	for i := range f.Workers {
+		w := &f.Workers[i]
+		if w.Host == "" {
+			return nil, fmt.Errorf("workers file %s: workers[%d] has no host", path, i)
+		}
+		if w.Name == "" {
+			w.Name = w.Host
+		}
+		if strings.ContainsAny(w.Name, `/\`) || w.Name == "." || w.Name == ".." {
+			return nil, fmt.Errorf("workers file %s: worker name %q is not a valid directory name", path, w.Name)
+		}
+		if seen[w.Name] {
+			return nil, fmt.Errorf("workers file %s: duplicate worker %q", path, w.Name)
+		}
+		seen[w.Name] = true
+		if w.Slots < 0 {
+			return nil, fmt.Errorf("workers file %s: worker %q has negative slots", path, w.Name)
+		}
+		if w.Slots == 0 {
+			w.Slots = 1
+		}
+		if w.Sanity == "" {
+			w.Sanity = "sanity"
+		}
+		if w.Workdir == "" {
+			w.Workdir = distWorkdir
+		}
+	}
+	return f.Workers, nil
+}
+
+// checkDistributable rejects eval settings a distributed run cannot honor.
+// Each worker runs one task at a time as its own eval, so settings that span
+// tasks would be split across runs.
+func checkDistributable(isMultiRun bool, shared SharedConfig) error {
+	switch {
+	case evalResume != "":
+		return errors.New("--distribute cannot be combined with --resume")
+	case isMultiRun:
+		return errors.New("--distribute runs one agent and model at a time; it cannot be combined with --repeat, --toolchains, or several agents")
+	case shared.PromptVariants != "":
+		return errors.New("--distribute cannot be combined with --prompt-variants, which assigns variants across a run's tasks")
+	case shared.Upload != "":
+		return errors.New("--distribute cannot be combined with --upload; upload the merged run afterwards")
+	}
+	return nil
+}
+
+// forwardedEvalArgs returns the eval flags the user set, other than those in
+// distLocalFlags, for workers to run with. timeout is always passed so every
+// shard is graded under the coordinator's setting.
+func forwardedEvalArgs(flags *pflag.FlagSet, timeout int) []string {
+	args := []string{"--timeout", strconv.Itoa(timeout), "--no-progress"}
+	flags.Visit(func(f *pflag.Flag) {
+		if slices.Contains(distLocalFlags, f.Name) {
+			return
+		}
+		if sv, ok := f.Value.(pflag.SliceValue); ok {
+			for _, v := range sv.GetSlice() {
+				args = append(args, "--"+f.Name+"="+v)
+			}
+			return
+		}
+		args = append(args, "--"+f.Name+"="+f.Value.String())
+	})
+	return args
+}
+
+// remoteEvalCommand returns the shell command that runs one task on w.
+func remoteEvalCommand(w distWorker, args []string, taskID, remoteDir string) string {
+	argv := []string{w.Sanity, "--theme", themePlain, "eval"}
+	argv = append(argv, args...)
+	argv = append(argv, "--tasks", taskID, "--output", remoteDir)
+	quoted := make([]string, len(argv))
+	for i, a := range argv {
+		quoted[i] = shellQuote(a)
+	}
+	return strings.Join(quoted, " ")
+}
+
+// sshCommand runs command on w through ssh.
+func sshCommand(ctx context.Context, w distWorker, command string) *exec.Cmd {
+	args := append([]string{"-o", "BatchMode=yes"}, w.SSHArgs...)
+	args = append(args, w.Host, command)
+	return exec.CommandContext(ctx, "ssh", args...)
+}
+
+// distJob is a task waiting for a worker.
+type distJob struct {
+	t     *task.Task
+	tries int
+}
+
+// distributor hands tasks to worker slots and collects their runs.
+type distributor struct {
+	ctx       context.Context
+	workers   []distWorker
+	args      []string
+	runID     string
+	outputDir string
+
+	mu       sync.Mutex
+	cond     *sync.Cond
+	pending  []distJob
+	inFlight int
+	down     map[string]bool
+	shards   []string
+	failed   map[string]string
+	perHost  map[string]int
+}
+
+// runDistributed runs every task on the workers, at most Slots at a time on
+// each, and merges the collected runs into outputDir. A first interrupt stops
+// handing out tasks and lets running ones finish; a second stops them.
+func runDistributed(ctx context.Context, workers []distWorker, args []string, allTasks []*task.Task, outputDir, timestamp string) error {
+	if err := os.MkdirAll(filepath.Join(outputDir, distShardsDir), 0o755); err != nil {
+		return fmt.Errorf("creating output directory: %w", err)
+	}
+	d := &distributor{
+		ctx:       ctx,
+		workers:   workers,
+		args:      args,
+		runID:     timestamp,
+		outputDir: outputDir,
+		down:      make(map[string]bool),
+		failed:    make(map[string]string),
+		perHost:   make(map[string]int),
+	}
+	d.cond = sync.NewCond(&d.mu)
+	for _, t := range allTasks {
+		d.pending = append(d.pending, distJob{t: t})
+	}
+	stop := context.AfterFunc(ctx, func() {
+		d.mu.Lock()
+		d.cond.Broadcast()
+		d.mu.Unlock()
+	})
+	defer stop()
+
+	slots := 0
+	for _, w := range workers {
+		slots += w.Slots
+	}
+	fmt.Printf(" Distributing %d task(s) over %d worker(s), %d slot(s)\n", len(allTasks), len(workers), slots)
+
+	var wg sync.WaitGroup
+	for _, w := range workers {
+		for range w.Slots {
+			wg.Go(func() { d.work(w) })
+		}
+	}
+	wg.Wait()
+
+	for _, j := range d.pending {
+		if ctx.Err() != nil {
+			d.failed[j.t.ID()] = "not started before the interrupt"
+		} else {
+			d.failed[j.t.ID()] = "no reachable worker left"
+		}
+	}
+	for _, w := range workers {
+		fmt.Printf(" %s: %d task(s)\n", w.Name, d.perHost[w.Name])
+	}
+	if len(d.shards) == 0 {
+		return errors.New("no task finished on any worker; see the logs in " + filepath.Join(outputDir, distShardsDir))
+	}
+	if err := mergeDistributed(outputDir, d.shards); err != nil {
+		return err
+	}
+	if len(d.failed) > 0 {
+		ids := make([]string, 0, len(d.failed))
+		for id := range d.failed {
+			ids = append(ids, id)
+		}
+		sort.Strings(ids)
+		for _, id := range ids {
+			fmt.Printf(" ✗ %s: %s\n", id, d.failed[id])
+		}
+		return fmt.Errorf("%d task(s) did not finish on any worker and are missing from the results", len(ids))
+	}
+	return nil
+}
+
+// work runs tasks on one slot of w until none are left for it.
+func (d *distributor) work(w distWorker) {
+	for {
+		job, ok := d.next(w.Name)
+		if !ok {
+			return
+		}
+		shard, unreachable, err := d.runTask(w, job.t)
+		d.finish(w.Name, job, shard, unreachable, err)
+	}
+}
+
+// next blocks until a task is ready for worker name, and reports false once
+// the worker is down, the run was interrupted, or nothing is left to do.
+func (d *distributor) next(name string) (distJob, bool) {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	for {
+		if d.down[name] || d.ctx.Err() != nil {
+			return distJob{}, false
+		}
+		if len(d.pending) > 0 {
+			job := d.pending[0]
+			d.pending = d.pending[1:]
+			d.inFlight++
+			return job, true
+		}
+		if d.inFlight == 0 {
+			return distJob{}, false
+		}
+		d.cond.Wait() // A running task may be handed back.
+	}
+}
+
+// finish records a task's outcome. A failed task is handed back while it
+// has tries left and some worker can still take it.
+func (d *distributor) finish(name string, job distJob, shard string, unreachable bool, err error) {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	defer d.cond.Broadcast()
+	d.inFlight--
+	id := job.t.ID()
+	if err == nil {
+		d.shards = append(d.shards, shard)
+		d.perHost[name]++
+		fmt.Printf(" ✓ %s (%s)\n", id, name)
+		return
+	}
+	if unreachable && !d.down[name] {
+		d.down[name] = true
+		logger.Warn("worker unreachable; no more tasks will be sent to it", "worker", name, "error", err)
+	}
+	job.tries++
+	if job.tries < distMaxTries && d.ctx.Err() == nil && len(d.down) < len(d.workers) {
+		logger.Warn("task failed on worker; retrying", "task", id, "worker", name, "error", err)
+		d.pending = append(d.pending, job)
+		return
+	}
+	d.failed[id] = fmt.Sprintf("%v (last worker %s)", err, name)
+}
+
+// runTask runs t on w, copies the run back, and removes it from the worker.
+// It returns the local copy and whether ssh could not reach w.
+func (d *distributor) runTask(w distWorker, t *task.Task) (string, bool, error) {
+	// A task already started is finished on a first interrupt, like in a
+	// local run.
+	ctx, cancel := gradingContext(d.ctx)
+	defer cancel()
+
+	name := strings.Replace(t.ID(), "/", "-", 1)
+	remoteDir := path.Join(w.Workdir, d.runID, name)
+	workerDir := filepath.Join(d.outputDir, distShardsDir, w.Name)
+	if err := os.MkdirAll(workerDir, 0o755); err != nil {
+		return "", false, err
+	}
+	logPath := filepath.Join(workerDir, name+".log")
+	logFile, err := os.Create(logPath)
+	if err != nil {
+		return "", false, err
+	}
+	defer func() { _ = logFile.Close() }()
+
+	start := time.Now()
+	cmd := sshCommand(ctx, w, remoteEvalCommand(w, d.args, t.ID(), remoteDir))
+	cmd.Stdout = logFile
+	cmd.Stderr = logFile
+	runErr := cmd.Run()
+	var exitErr *exec.ExitError
+	if errors.As(runErr, &exitErr) && exitErr.ExitCode() == sshUnreachable {
+		return "", true, fmt.Errorf("ssh %s: %w", w.Host, runErr)
+	}
+	defer func() {
+		// Best effort; the scratch directory is per run, so leftovers never
+		// collide with later runs.
+		_ = sshCommand(context.WithoutCancel(ctx), w, "rm -rf "+shellQuote(remoteDir)).Run()
+	}()
+
+	local := filepath.Join(workerDir, name)
+	if err := fetchRemoteDir(ctx, w, remoteDir, local); err != nil {
+		return "", false, errors.Join(runErr, err)
+	}
+	if _, err := os.Stat(filepath.Join(local, "summary.json")); err != nil {
+		return "", false, fmt.Errorf("no results after %s (see %s): %w",
+			time.Since(start).Round(time.Second), logPath, cmp.Or(runErr, err))
+	}
+	return local, false, nil
+}
+
+// fetchRemoteDir copies remoteDir on w to local by streaming a tar archive
+// over ssh.
+func fetchRemoteDir(ctx context.Context, w distWorker, remoteDir, local string) error {
+	cmd := sshCommand(ctx, w, "tar -cf - -C "+shellQuote(remoteDir)+" .")
+	out, err := cmd.StdoutPipe()
+	if err != nil {
+		return err
+	}
+	var stderr strings.Builder
+	cmd.Stderr = &stderr
+	if err := cmd.Start(); err != nil {
+		return fmt.Errorf("starting ssh: %w", err)
+	}
+	extractErr := extractTar(tar.NewReader(out), local)
+	if err := cmd.Wait(); err != nil {
+		return fmt.Errorf("copying %s from %s: %w: %s", remoteDir, w.Name, err, strings.TrimSpace(stderr.String()))
+	}
+	return extractErr
+}
+
+// mergeDistributed merges the collected runs into outputDir, as sanity merge
+// would.
+func mergeDistributed(outputDir string, shards []string) error {
+	inputs, err := loadMergeInputs(shards)
+	if err != nil {
+		return err
+	}
+	if problems := checkMergeCompatible(inputs); len(problems) > 0 {
+		for _, p := range problems {
+			fmt.Printf(" ✗ %s\n", p)
+		}
+		return errors.New("workers produced incompatible runs (check that they run the same sanity version and config); the runs are kept in " +
+			filepath.Join(outputDir, distShardsDir))
+	}
+	var taskOrder map[string]int
+	if allTasks, err := task.NewLoader(tasks.FS, tasksDir).LoadAll(); err == nil {
+		taskOrder = make(map[string]int, len(allTasks))
+		for i, t := range allTasks {
+			taskOrder[t.ID()] = i
+		}
+	}
+	summary, attestation, record := mergeSessions(inputs, taskOrder, time.Now())
+	if err := writeMergedSession(outputDir, inputs, summary, attestation, record); err != nil {
+		return err
+	}
+	fmt.Printf(" Merged %d task(s): %d passed (%.1f%%), weighted %.1f%%\n",
+		summary.Total, summary.Passed, summary.PassRate, summary.WeightedPassRate)
+	fmt.Printf(" Results saved to: %s\n", outputDir)
+	return nil
+}
@@ -0,0 +1,101 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/spf13/pflag"
+)
+
+func TestLoadDistWorkers(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	write := func(name, content string) string {
+		p := filepath.Join(dir, name)
+		if err := os.WriteFile(p, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		return p
+	}
+
+	workers, err := loadDistWorkers(write("ok.toml", `
+[[workers]]
+host = "bench@10.0.0.5"
+
+[[workers]]
+name = "gpu"
+host = "gpu.internal"
+ssh_args = ["-p", "2222"]
+sanity = "/opt/sanity/bin/sanity"
+workdir = "/scratch/sanity"
+slots = 4
+`))
+	if err != nil {
+		t.Fatalf("loadDistWorkers() error = %v", err)
+	}
+	if len(workers) != 2 {
+		t.Fatalf("got %d workers, want 2", len(workers))
+	}
+	if w := workers[0]; w.Name != "bench@10.0.0.5" || w.Slots != 1 || w.Sanity != "sanity" || w.Workdir != distWorkdir {
+		t.Errorf("defaults not applied: %+v", w)
+	}
+	if w := workers[1]; w.Slots != 4 || !slices.Equal(w.SSHArgs, []string{"-p", "2222"}) {
+		t.Errorf("worker not read: %+v", w)
+	}
+
+	for name, content := range map[string]string{
+		"none.toml":      ``,
+		"nohost.toml":    "[[workers]]\nname = \"a\"\n",
+		"dup.toml":       "[[workers]]\nhost = \"a\"\n[[workers]]\nhost = \"a\"\n",
+		"slots.toml":     "[[workers]]\nhost = \"a\"\nslots = -1\n",
+		"badname.toml":   "[[workers]]\nname = \"a/b\"\nhost = \"a\"\n",
+		"malformed.toml": "[[workers]\n",
+	} {
+		if _, err := loadDistWorkers(write(name, content)); err == nil {
+			t.Errorf("loadDistWorkers(%s) succeeded, want an error", name)
+		}
+	}
+}
+
+func TestForwardedEvalArgs(t *testing.T) {
+	t.Parallel()
+
+	flags := pflag.NewFlagSet("eval", pflag.ContinueOnError)
+	flags.String("agent", "", "")
+	flags.String("model", "", "")
+	flags.String("lang", "", "")
+	flags.String("output", "", "")
+	flags.String("config", "", "")
+	flags.Bool("use-skills", false, "")
+	flags.StringArray("skill-pack", nil, "")
+	flags.Int("timeout", 0, "")
+	if err := flags.Parse([]string{
+		"--agent", "claude", "--model", "opus", "--lang", "go", "--output", "out",
+		"--config", "local.toml", "--use-skills", "--skill-pack", "a", "--skill-pack", "b c", "--timeout", "60",
+	}); err != nil {
+		t.Fatal(err)
+	}
+
+	got := forwardedEvalArgs(flags, 120)
+	want := []string{
+		"--timeout", "120", "--no-progress",
+		"--agent=claude", "--model=opus", "--skill-pack=a", "--skill-pack=b c", "--use-skills=true",
+	}
+	if !slices.Equal(got, want) {
+		t.Errorf("forwardedEvalArgs() = %q, want %q", got, want)
+	}
+}
+
+func TestRemoteEvalCommand(t *testing.T) {
+	t.Parallel()
+
+	w := distWorker{Sanity: "/opt/sanity"}
+	got := remoteEvalCommand(w, []string{"--agent=claude", "--model=it's"}, "go/bank-account", "sanity-distributed/run/go-bank-account")
+	want := `'/opt/sanity' '--theme' 'plain' 'eval' '--agent=claude' '--model=it'\''s' '--tasks' 'go/bank-account' '--output' 'sanity-distributed/run/go-bank-account'`
+	if got != want {
+		t.Errorf("remoteEvalCommand() =\n%s\nwant\n%s", got, want)
+	}
+}
@@ -0,0 +1,455 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"os/exec"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// doctorCheckTimeout bounds each network and subprocess check.
+const doctorCheckTimeout = 10 * time.Second
+
+// endpointEnvVars are the variables agents read a model endpoint from. Agent
+// [agents.<name>.env] values take precedence over the environment.
+var endpointEnvVars = []string{
+	"OPENAI_BASE_URL",
+	"OPENAI_API_BASE",
+	"ANTHROPIC_BASE_URL",
+	"GOOGLE_GEMINI_BASE_URL",
+	"OLLAMA_HOST",
+}
+
+var (
+	doctorAgents    string
+	doctorEndpoints []string
+	doctorLang      string
+)
+
+// doctorStatus is the outcome of one check.
+type doctorStatus int
+
+const (
+	doctorOK doctorStatus = iota
+	doctorWarn
+	doctorFail
+	doctorSkip
+)
+
+// doctorCheck is one diagnostic and, when it did not pass, how to fix it.
+type doctorCheck struct {
+	Name   string
+	Status doctorStatus
+	Detail string
+	Fix    string
+}
+
+var doctorCmd = &cobra.Command{
+	Use:   "doctor",
+	Short: "Diagnose the environment before an eval",
+	Long: `Checks everything an eval depends on and prints a fix for each failure:
+
+  - Docker or Podman is installed, its daemon answers, and its version
+  - the config layers load without unknown keys or invalid values
+  - each agent binary is on PATH and answers --version
+  - each model endpoint is reachable
+  - each task image is present locally or can be pulled from its registry
+
+With container_backend = "local", the sandbox and each language's toolchain
+on PATH are checked instead of the engine and images.
+
+Without --agent, every known agent is checked and those not installed are
+skipped. Model endpoints come from --endpoint, the agents' env settings and
+the environment (OPENAI_BASE_URL, ANTHROPIC_BASE_URL, and similar), and
+[judge] endpoint. Exits with status 1 if any check fails.`,
+	Example: `  sanity doctor
+  sanity doctor --agent opencode --endpoint http://localhost:11434
+  sanity doctor --lang go,rust`,
+	Args: cobra.NoArgs,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx := cmd.Context()
+		var checks []doctorCheck
+		report := func(check doctorCheck) {
+			checks = append(checks, check)
+			writeDoctorCheck(os.Stdout, check)
+		}
+
+		c := doctorConfig(report)
+		local := c.Docker.ContainerBackend == config.ContainerBackendLocal
+		var docker *runner.DockerClient
+		if local {
+			report(checkLocalSandbox(ctx, c.Docker.LocalSandbox, allowUnsandboxed))
+		} else if docker = doctorRuntime(ctx, c.Docker.ContainerBackend, report); docker != nil {
+			defer func() { _ = docker.Close() }()
+		}
+
+		agents := splitCommaList(doctorAgents)
+		for _, name := range agentsToCheck(c, agents) {
+			report(checkAgent(ctx, c, name, len(agents) > 0))
+		}
+
+		endpoints := modelEndpoints(c, agents, doctorEndpoints, os.Getenv)
+		if len(endpoints) == 0 {
+			report(doctorCheck{
+				Name:   "model endpoint",
+				Status: doctorSkip,
+				Detail: "none configured",
+				Fix:    "pass --endpoint <url> to check the endpoint your agent talks to",
+			})
+		}
+		for _, url := range endpoints {
+			report(checkEndpoint(ctx, http.DefaultClient, url))
+		}
+
+		langs, err := doctorLanguages(doctorLang)
+		if err != nil {
+			return err
+		}
+		for _, lang := range langs {
+			if local {
+				report(checkToolchain(lang))
+				continue
+			}
+			report(checkImage(ctx, docker, lang, c.ImageForLanguage(lang)))
+		}
+
+		if slices.ContainsFunc(checks, func(check doctorCheck) bool { return check.Status == doctorFail }) {
+			return &exitError{code: 1}
+		}
+		fmt.Println("\nNo problems found.")
+		return nil
+	},
+}
+
+func init() {
+	doctorCmd.Flags().StringVar(&doctorAgents, "agent", "", "comma-separated agents to check (default: every installed agent)")
+	doctorCmd.Flags().StringArrayVar(&doctorEndpoints, "endpoint", nil, "model endpoint URL to check (repeatable)")
+	doctorCmd.Flags().StringVar(&doctorLang, "lang", "", "comma-separated languages whose images to check (default: all)")
+}
+
+// writeDoctorCheck prints one check result, with its fix when it did not pass.
+func writeDoctorCheck(w io.Writer, c doctorCheck) {
+	mark := map[doctorStatus]string{doctorOK: "✓", doctorWarn: "!", doctorFail: "✗", doctorSkip: "-"}[c.Status]
+	_, _ = fmt.Fprintf(w, " %s %s: %s\n", mark, c.Name, c.Detail)
+	if c.Fix != "" && c.Status != doctorOK {
+		_, _ = fmt.Fprintf(w, "     fix: %s\n", c.Fix)
+	}
+}
+
+// doctorConfig strictly loads the config layers, reporting any problem, and
+// returns the config to run the remaining checks with: the layered config if
+// it loads at all, otherwise the built-in defaults.
+func doctorConfig(report func(doctorCheck)) *config.Config {
+	opts := config.LoadOptions{File: cfgFile, Overrides: cfgOverrides, Strict: true}
+	c, origins, err := config.LoadLayered(opts)
+	if err == nil {
+		detail := "built-in defaults only"
+		if len(origins.Files) > 0 {
+			names := make([]string, 0, len(origins.Files))
+			for _, f := range origins.Files {
+				names = append(names, f.Path)
+			}
+			detail = strings.Join(names, ", ")
+		}
+		report(doctorCheck{Name: "config", Status: doctorOK, Detail: detail})
+		return c
+	}
+
+	var verr *config.ValidationError
+	if errors.As(err, &verr) {
+		for _, p := range verr.Problems {
+			report(doctorCheck{Name: "config", Status: doctorFail, Detail: p.String(), Fix: "correct or remove the setting; see docs/CONFIGURATION.md"})
+		}
+	} else {
+		report(doctorCheck{Name: "config", Status: doctorFail, Detail: err.Error(), Fix: "run 'sanity config validate' for details"})
+	}
+	opts.Strict = false
+	if c, _, err = config.LoadLayered(opts); err == nil {
+		return c
+	}
+	fallback := config.Default
+	return &fallback
+}
+
+// doctorRuntime checks the configured container engine and returns a client
+// for it, or nil if it is not usable.
+func doctorRuntime(ctx context.Context, backend string, report func(doctorCheck)) *runner.DockerClient {
+	fix := "start Docker, or set container_backend = \"podman\" under [docker] to use Podman"
+	if backend == config.ContainerBackendPodman {
+		fix = "run 'systemctl --user start podman.socket' (or 'podman machine start'), or set CONTAINER_HOST to the Podman API socket"
+	}
+	_, dockerErr := exec.LookPath("docker")
+	_, podmanErr := exec.LookPath("podman")
+	if dockerErr != nil && podmanErr != nil && os.Getenv("DOCKER_HOST") == "" && os.Getenv("CONTAINER_HOST") == "" {
+		report(doctorCheck{
+			Name:   "container runtime",
+			Status: doctorFail,
+			Detail: "neither docker nor podman is on PATH",
+			Fix:    "install Docker (https://docs.docker.com/get-docker/) or Podman",
+		})
+		return nil
+	}
+
+	docker, err := runner.NewDockerClient(backend)
+	if err != nil {
+		report(doctorCheck{Name: "container runtime", Status: doctorFail, Detail: err.Error(), Fix: fix})
+		return nil
+	}
+	ctx, cancel := context.WithTimeout(ctx, doctorCheckTimeout)
+	defer cancel()
+	name, version, err := docker.ServerVersion(ctx)
+	if err != nil {
+		report(doctorCheck{Name: "container runtime", Status: doctorWarn, Detail: err.Error(), Fix: "upgrade the engine if runs fail"})
+		return docker
+	}
+	if name == "" {
+		name = "Docker"
+	}
+	report(doctorCheck{Name: "container runtime", Status: doctorOK, Detail: fmt.Sprintf("%s %s", name, version)})
+	return docker
+}
+
+// localToolchains is the command each language's tasks validate with, which
+// the local backend runs from the host's PATH.
+var localToolchains = map[string]string{
+	"go":         "go",
+	"rust":       "cargo",
+	"typescript": "npx",
+	"kotlin":     "gradle",
+	"dart":       "dart",
+	"zig":        "zig",
+}
+
+// checkLocalSandbox verifies the local backend's sandbox can start. bwrap is
+// tried for real, since unprivileged user namespaces may be disabled.
+func checkLocalSandbox(ctx context.Context, sandbox string, allowUnsandboxed bool) doctorCheck {
+	check := doctorCheck{Name: "local sandbox"}
+	resolved, err := runner.ResolveLocalSandbox(sandbox, allowUnsandboxed)
+	if err != nil {
+		check.Status = doctorFail
+		check.Detail = err.Error()
+		check.Fix = "install bubblewrap (bwrap) or firejail, or use container_backend = \"docker\" or \"podman\""
+		return check
+	}
+	if resolved == config.LocalSandboxNone {
+		check.Status = doctorWarn
+		check.Detail = "none: task commands run directly on the host"
+		check.Fix = "install bubblewrap (bwrap) or firejail"
+		return check
+	}
+	check.Status = doctorOK
+	check.Detail = resolved
+	if resolved != config.LocalSandboxBwrap {
+		return check
+	}
+	ctx, cancel := context.WithTimeout(ctx, doctorCheckTimeout)
+	defer cancel()
+	if out, err := exec.CommandContext(ctx, "bwrap", "--ro-bind", "/", "/", "--dev", "/dev", "--unshare-all", "true").CombinedOutput(); err != nil {
+		check.Status = doctorFail
+		check.Detail = fmt.Sprintf("bwrap cannot create a sandbox: %v", err)
+		if line := firstLine(out); line != "" {
+			check.Detail += ": " + line
+		}
+		check.Fix = "enable unprivileged user namespaces (sysctl kernel.unprivileged_userns_clone=1), or set [docker] local_sandbox = \"firejail\""
+	}
+	return check
+}
+
+// checkToolchain verifies the command lang's tasks validate with is on PATH,
+// for the local backend.
+func checkToolchain(lang string) doctorCheck {
+	check := doctorCheck{Name: lang + " toolchain"}
+	command, ok := localToolchains[lang]
+	if !ok {
+		check.Status = doctorSkip
+		check.Detail = "unknown language"
+		return check
+	}
+	path, err := exec.LookPath(command)
+	if err != nil {
+		check.Status = doctorFail
+		check.Detail = command + " not found on PATH"
+		check.Fix = fmt.Sprintf("install the %s toolchain, or skip the language with --lang", lang)
+		return check
+	}
+	check.Status = doctorOK
+	check.Detail = path
+	return check
+}
+
+// agentsToCheck returns the requested agents, or every known agent.
+func agentsToCheck(c *config.Config, requested []string) []string {
+	if len(requested) > 0 {
+		return requested
+	}
+	return c.ListAgents()
+}
+
+// checkAgent verifies an agent's binary is on PATH and answers --version. A
+// missing binary fails only when the agent was requested explicitly.
+func checkAgent(ctx context.Context, c *config.Config, name string, requested bool) doctorCheck {
+	check := doctorCheck{Name: "agent " + name}
+	agentCfg := c.GetAgent(name)
+	if agentCfg == nil {
+		check.Status = doctorFail
+		check.Detail = "unknown agent"
+		check.Fix = fmt.Sprintf("use one of %s, or add an [agents.%s] section to sanity.toml", strings.Join(c.ListAgents(), ", "), name)
+		return check
+	}
+	path, err := exec.LookPath(agentCfg.Command)
+	if err != nil {
+		check.Status = doctorSkip
+		if requested {
+			check.Status = doctorFail
+		}
+		check.Detail = fmt.Sprintf("%s not found on PATH", agentCfg.Command)
+		check.Fix = fmt.Sprintf("install %s, or set [agents.%s] command to its full path", agentCfg.Command, name)
+		return check
+	}
+
+	ctx, cancel := context.WithTimeout(ctx, doctorCheckTimeout)
+	defer cancel()
+	out, err := exec.CommandContext(ctx, path, "--version").CombinedOutput()
+	version := firstLine(out)
+	if err != nil {
+		check.Status = doctorWarn
+		check.Detail = fmt.Sprintf("%s --version failed: %v", path, err)
+		if version != "" {
+			check.Detail += ": " + version
+		}
+		check.Fix = "run it by hand to check it is installed and logged in"
+		return check
+	}
+	check.Status = doctorOK
+	check.Detail = path
+	if version != "" {
+		check.Detail += " (" + version + ")"
+	}
+	return check
+}
+
+func firstLine(out []byte) string {
+	line, _, _ := bytes.Cut(bytes.TrimSpace(out), []byte("\n"))
+	return strings.TrimSpace(string(line))
+}
+
+// modelEndpoints collects the endpoints to check, deduplicated and in order:
+// explicit ones, then those set in the checked agents' env or the
+// environment, then the judge endpoint.
+func modelEndpoints(c *config.Config, agents, explicit []string, getenv func(string) string) []string {
+	var endpoints []string
+	add := func(url string) {
+		if url = strings.TrimSpace(url); url != "" && !slices.Contains(endpoints, url) {
+			endpoints = append(endpoints, url)
+		}
+	}
+	for _, url := range explicit {
+		add(url)
+	}
+	for _, name := range agents {
+		if agentCfg := c.GetAgent(name); agentCfg != nil {
+			for _, key := range endpointEnvVars {
+				add(agentCfg.Env[key])
+			}
+		}
+	}
+	for _, key := range endpointEnvVars {
+		add(getenv(key))
+	}
+	add(c.Judge.Endpoint)
+	return endpoints
+}
+
+// checkEndpoint reports whether url answers HTTP. Any response counts, since
+// an unauthenticated request to an API is commonly rejected.
+func checkEndpoint(ctx context.Context, client *http.Client, url string) doctorCheck {
+	check := doctorCheck{Name: "endpoint " + url}
+	if !strings.Contains(url, "://") {
+		url = "http://" + url // OLLAMA_HOST is often host:port
+	}
+	ctx, cancel := context.WithTimeout(ctx, doctorCheckTimeout)
+	defer cancel()
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		check.Status = doctorFail
+		check.Detail = err.Error()
+		check.Fix = "check the URL"
+		return check
+	}
+	resp, err := client.Do(req)
+	if err != nil {
+		check.Status = doctorFail
+		check.Detail = err.Error()
+		check.Fix = "check the URL, any proxy settings, and that the server is running"
+		return check
+	}
+	_ = resp.Body.Close()
+	check.Status = doctorOK
+	check.Detail = "reachable (HTTP " + resp.Status + ")"
+	return check
+}
+
+// splitCommaList splits a comma-separated flag value, dropping empty items.
+func splitCommaList(s string) []string {
+	var items []string
+	for _, item := range strings.Split(s, ",") {
+		if item = strings.TrimSpace(item); item != "" {
+			items = append(items, item)
+		}
+	}
+	return items
+}
+
+// doctorLanguages parses --lang, defaulting to every language.
+func doctorLanguages(list string) ([]string, error) {
+	if list == "" {
+		return languageNames(), nil
+	}
+	langs := splitCommaList(list)
+	for _, lang := range langs {
+		if _, err := task.ParseLanguage(lang); err != nil {
+			return nil, err
+		}
+	}
+	return langs, nil
+}
+
+// checkImage reports whether a task image is present locally or can be
+// pulled from its registry.
+func checkImage(ctx context.Context, docker *runner.DockerClient, lang, ref string) doctorCheck {
+	check := doctorCheck{Name: lang + " image"}
+	if docker == nil {
+		check.Status = doctorSkip
+		check.Detail = ref + " (no container runtime)"
+		return check
+	}
+	ctx, cancel := context.WithTimeout(ctx, doctorCheckTimeout)
+	defer cancel()
+	if ok, err := docker.ImageExists(ctx, ref); err == nil && ok {
+		check.Status = doctorOK
+		check.Detail = ref + " (present)"
+		return check
+	}
+	if err := docker.CheckPullable(ctx, ref); err != nil {
+		check.Status = doctorFail
+		check.Detail = err.Error()
+		check.Fix = fmt.Sprintf("check registry access (docker login, [docker] mirrors), set [docker] %s_image, or load a tarball with 'sanity images import'", lang)
+		return check
+	}
+	check.Status = doctorOK
+	check.Detail = ref + " (pullable)"
+	return check
+}
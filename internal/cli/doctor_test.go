@@ -0,0 +1,81 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"net/http"
+	"net/http/httptest"
+	"slices"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestModelEndpoints(t *testing.T) {
+	t.Parallel()
+
+	c := config.Default
+	c.Agents = map[string]config.AgentConfig{
+		"local": {Command: "local-agent", Env: map[string]string{"OPENAI_BASE_URL": "http://localhost:8080/v1"}},
+	}
+	c.Judge.Endpoint = "https://judge.example/v1/chat/completions"
+	env := map[string]string{"ANTHROPIC_BASE_URL": "https://proxy.example", "OPENAI_BASE_URL": "http://localhost:8080/v1"}
+
+	got := modelEndpoints(&c, []string{"local"}, []string{"http://explicit:1234"}, func(k string) string { return env[k] })
+	want := []string{
+		"http://explicit:1234",
+		"http://localhost:8080/v1",
+		"https://proxy.example",
+		"https://judge.example/v1/chat/completions",
+	}
+	if !slices.Equal(got, want) {
+		t.Fatalf("modelEndpoints() = %v, want %v", got, want)
+	}
+}
+
+func TestCheckEndpoint(t *testing.T) {
+	t.Parallel()
+
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		w.WriteHeader(http.StatusUnauthorized)
+	}))
+	if check := checkEndpoint(context.Background(), srv.Client(), srv.URL); check.Status != doctorOK {
+		t.Errorf("reachable endpoint: status = %v, detail = %q", check.Status, check.Detail)
+	}
+
+	url := srv.URL
+	srv.Close()
+	check := checkEndpoint(context.Background(), http.DefaultClient, url)
+	if check.Status != doctorFail || check.Fix == "" {
+		t.Errorf("closed endpoint: got %+v, want a failure with a fix", check)
+	}
+}
+
+func TestCheckAgent(t *testing.T) {
+	t.Parallel()
+
+	c := config.Default
+	c.Agents = map[string]config.AgentConfig{"ghost": {Command: "sanity-no-such-agent-binary"}}
+
+	if check := checkAgent(context.Background(), &c, "nope", true); check.Status != doctorFail {
+		t.Errorf("unknown agent: status = %v, want fail", check.Status)
+	}
+	if check := checkAgent(context.Background(), &c, "ghost", false); check.Status != doctorSkip {
+		t.Errorf("missing binary, not requested: status = %v, want skip", check.Status)
+	}
+	if check := checkAgent(context.Background(), &c, "ghost", true); check.Status != doctorFail || check.Fix == "" {
+		t.Errorf("missing binary, requested: got %+v, want a failure with a fix", check)
+	}
+}
+
+func TestWriteDoctorCheck(t *testing.T) {
+	t.Parallel()
+
+	var buf bytes.Buffer
+	writeDoctorCheck(&buf, doctorCheck{Name: "go image", Status: doctorFail, Detail: "not found", Fix: "docker login"})
+	writeDoctorCheck(&buf, doctorCheck{Name: "config", Status: doctorOK, Detail: "sanity.toml", Fix: "ignored"})
+	want := " ✗ go image: not found\n     fix: docker login\n ✓ config: sanity.toml\n"
+	if buf.String() != want {
+		t.Errorf("output = %q, want %q", buf.String(), want)
+	}
+}
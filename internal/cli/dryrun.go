@@ -0,0 +1,235 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"maps"
+	"path/filepath"
+	"slices"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// dryRunPlan is everything eval --dry-run reports: what would run, how, and
+// where its results would go.
+type dryRunPlan struct {
+	Specs     []RunSpec
+	Shared    SharedConfig
+	Tasks     []*task.Task
+	Repeat    int
+	OutputDir string
+}
+
+// defaultEvalOutputDir names the results directory of a new run.
+func defaultEvalOutputDir(specs []RunSpec, timestamp string, multiRun bool) string {
+	if multiRun && len(specs) > 1 {
+		return filepath.Join(defaultEvalResultsDir, "multi-"+timestamp)
+	}
+	return filepath.Join(defaultEvalResultsDir, fmt.Sprintf("%s-%s", timestamp, specs[0].Agent))
+}
+
+// writeDryRunPlan prints the resolved plan without touching Docker or any
+// agent.
+func writeDryRunPlan(w io.Writer, plan dryRunPlan) {
+	shared := plan.Shared
+	p := func(format string, args ...any) { _, _ = fmt.Fprintf(w, format, args...) }
+
+	p("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n")
+	p(" SANITY HARNESS - Dry Run\n")
+	p("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n\n")
+	if shared.Tier != "" {
+		p(" Tier:       %s\n", shared.Tier)
+	}
+	if shared.Difficulty != "" {
+		p(" Difficulty: %s\n", shared.Difficulty)
+	}
+	if shared.Filter != "" {
+		p(" Filter:     %s\n", shared.Filter)
+	}
+	if shared.Tags != "" {
+		p(" Tags:       %s\n", shared.Tags)
+	}
+	if shared.Exclude != "" {
+		p(" Exclude:    %s\n", shared.Exclude)
+	}
+	if shared.PromptLang != "" {
+		p(" Prompt:     %s\n", shared.PromptLang)
+	}
+	if shared.MCPServers != "" {
+		p(" MCP:        %s\n", shared.MCPServers)
+	}
+	if shared.Chaos != "" {
+		p(" Chaos:      %s\n", shared.Chaos)
+	}
+	if shared.Seed != 0 {
+		p(" Seed:       %d\n", shared.Seed)
+	}
+	if shared.PromptVariants != "" {
+		p(" Variants:   %s\n", shared.PromptVariants)
+	}
+	if shared.AgentMemory != "" && shared.AgentMemory != config.AgentMemoryHost {
+		p(" Memory:     %s\n", agentMemoryLabel(shared.AgentMemory))
+	}
+	if shared.TestVisibility != config.TestVisibilityVisibleOnly {
+		p(" Tests:      %s\n", testVisibilityLabel(shared.TestVisibility))
+	}
+	if shared.RepairAttempts > 0 {
+		p(" Repairs:    up to %d, %s feedback\n", shared.RepairAttempts, shared.AttemptFeedback)
+	}
+	if shared.Upload != "" {
+		p(" Upload:     %s\n", shared.Upload)
+	}
+	if plan.Repeat > 1 {
+		p(" Repeat:     %d\n", plan.Repeat)
+	}
+	p(" Parallel:   %d\n", max(shared.Parallel, 1))
+	p(" Tasks:      %d\n", len(plan.Tasks))
+	p(" Output:     %s\n", plan.OutputDir)
+
+	for _, spec := range plan.Specs {
+		p("\n")
+		writeDryRunSpec(w, spec, shared, plan.Tasks)
+	}
+
+	p("\n Tasks that would be executed:\n")
+	p("─────────────────────────────────────────────────────────────\n")
+	for i, t := range plan.Tasks {
+		timeout := resolveAgentTimeout(taskTimeoutSeconds(t, shared.Timeout), 0, t.AgentTimeout)
+		p(" %3d. %-35s [%s, %s, %ds, weight %.2f]\n",
+			i+1, t.ID(), t.Tier, t.Difficulty, int(timeout/time.Second), taskWeight(t).Base)
+	}
+	p("─────────────────────────────────────────────────────────────\n\n")
+	p(" No containers or agents were started.\n\n")
+}
+
+// writeDryRunSpec prints one run's agent command line and the images its
+// tasks would be validated in.
+func writeDryRunSpec(w io.Writer, spec RunSpec, shared SharedConfig, taskList []*task.Task) {
+	p := func(format string, args ...any) { _, _ = fmt.Fprintf(w, format, args...) }
+
+	if spec.Agent != "" {
+		p(" Agent:      %s\n", spec.Agent)
+	}
+	if spec.Model != "" {
+		p(" Model:      %s\n", spec.Model)
+	}
+	if spec.Reasoning != "" {
+		p(" Reasoning:  %s\n", spec.Reasoning)
+	}
+	if spec.Toolchain != "" {
+		p(" Toolchain:  %s\n", toolchainLabel(spec.Toolchain))
+	}
+	if spec.Agent != "" {
+		agentCfg := cfg.GetAgent(spec.Agent)
+		if agentCfg == nil {
+			p(" Command:    unknown agent (available: %s)\n", strings.Join(cfg.ListAgents(), ", "))
+		} else {
+			adapter := agentAdapters[spec.Agent]
+			model, modelArgs := adapter.splitModel(spec.Model)
+			cmd := buildAgentCommand(context.Background(), agentCfg, "{prompt}", model, spec.Reasoning, "{workspace}", shared.DisableMCP, shared.UseMCPTools, spec.Agent)
+			command := shellJoin(append(cmd.Args, modelArgs...))
+			if adapter.args != nil {
+				command += " <workspace files>"
+			}
+			if shared.MCPServers != "" {
+				servers, err := resolveMCPServers(shared.MCPServers, spec.Agent, agentCfg, shared.DisableMCP)
+				if err != nil {
+					p(" MCP:        %v\n", err)
+				} else if mcpArgs, _ := adapter.mcp(mcpServersFor(servers, "{workspace}")); len(mcpArgs) > 0 {
+					command += " " + shellJoin(mcpArgs)
+				}
+			}
+			sampling, samplingErr := resolveSampling(shared, spec.Agent, agentCfg)
+			if samplingFlags, _ := samplingArgs(agentCfg, adapter, sampling); len(samplingFlags) > 0 {
+				command += " " + shellJoin(samplingFlags)
+			}
+			p(" Command:    %s\n", command)
+			switch {
+			case samplingErr != nil:
+				p(" Sampling:   %v\n", samplingErr)
+			case sampling.label() != "":
+				p(" Sampling:   %s\n", sampling.label())
+			}
+			switch {
+			case agentCfg.Protocol == config.AgentProtocolPlugin:
+				p(" Stdin:      plugin task request (protocol v%d)\n", pluginProtocolVersion)
+			case agentCfg.PromptMode == config.PromptModeStdin:
+				p(" Stdin:      {prompt}\n")
+			}
+			if len(agentCfg.Env) > 0 {
+				p(" Env:        %s\n", strings.Join(slices.Sorted(maps.Keys(agentCfg.Env)), ", "))
+			}
+			if agentCfg.DefaultTimeout > 0 {
+				p(" Timeout:    at least %ds (default_timeout)\n", agentCfg.DefaultTimeout)
+			}
+		}
+	}
+
+	images := dryRunImages(spec.Toolchain, taskList)
+	refs := slices.Sorted(maps.Keys(images))
+	for i, ref := range refs {
+		label := ""
+		if i == 0 {
+			label = "Images:"
+		}
+		p(" %-11s %s (%d task(s))\n", label, ref, images[ref])
+	}
+}
+
+// dryRunImages counts the tasks validated in each image under a toolchain
+// variant. A per-task image override, or an image or Dockerfile in the task's
+// manifest, wins over the variant.
+func dryRunImages(toolchain string, taskList []*task.Task) map[string]int {
+	var variant map[string]string
+	if tc, ok := cfg.Toolchain(toolchain); ok {
+		variant = tc.Images
+	}
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	images := make(map[string]int)
+	for _, t := range taskList {
+		lang := string(t.Language)
+		ref, err := runner.TaskImage(cfg, loader, t)
+		if err != nil {
+			ref = fmt.Sprintf("%s (%v)", t.Dockerfile, err)
+		}
+		if cfg.TaskOverride(t.ID()).Image == "" && !t.OwnImage() && variant[lang] != "" {
+			ref = variant[lang]
+		}
+		images[ref]++
+	}
+	return images
+}
+
+// taskTimeoutSeconds returns the agent timeout for t before any per-agent
+// minimum: its [task] override if set, otherwise timeout.
+func taskTimeoutSeconds(t *task.Task, timeout int) int {
+	if cfg != nil {
+		if o := cfg.TaskOverride(t.ID()); o.Timeout > 0 {
+			return o.Timeout
+		}
+	}
+	return timeout
+}
+
+// shellJoin joins args into a command line, quoting those a POSIX shell
+// would otherwise split or expand.
+func shellJoin(args []string) string {
+	quoted := make([]string, 0, len(args))
+	for _, arg := range args {
+		if arg == "" || strings.ContainsFunc(arg, func(r rune) bool { return !shellSafe(r) }) {
+			arg = shellQuote(arg)
+		}
+		quoted = append(quoted, arg)
+	}
+	return strings.Join(quoted, " ")
+}
+
+func shellSafe(r rune) bool {
+	return r >= 'a' && r <= 'z' || r >= 'A' && r <= 'Z' || r >= '0' && r <= '9' || strings.ContainsRune("-_./:=,@%+{}", r)
+}
@@ -0,0 +1,83 @@
+package cli
+
+import (
+	"bytes"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestShellJoin(t *testing.T) {
+	t.Parallel()
+
+	got := shellJoin([]string{"opencode", "run", "-m", "qwen/qwen3-coder", "{prompt}", "it's", ""})
+	want := `opencode run -m qwen/qwen3-coder {prompt} 'it'\''s' ''`
+	if got != want {
+		t.Errorf("shellJoin() = %q, want %q", got, want)
+	}
+}
+
+func TestDefaultEvalOutputDir(t *testing.T) {
+	t.Parallel()
+
+	one := []RunSpec{{Agent: "gemini"}}
+	two := []RunSpec{{Agent: "gemini"}, {Agent: "codex"}}
+	for _, tc := range []struct {
+		specs []RunSpec
+		multi bool
+		want  string
+	}{
+		{one, false, "2026-01-02T150405-gemini"},
+		{one, true, "2026-01-02T150405-gemini"},
+		{two, true, "multi-2026-01-02T150405"},
+	} {
+		want := filepath.Join(defaultEvalResultsDir, tc.want)
+		if got := defaultEvalOutputDir(tc.specs, "2026-01-02T150405", tc.multi); got != want {
+			t.Errorf("defaultEvalOutputDir(%d specs, %v) = %q, want %q", len(tc.specs), tc.multi, got, want)
+		}
+	}
+}
+
+func TestWriteDryRunPlan(t *testing.T) {
+	c := config.Default
+	c.Agents = map[string]config.AgentConfig{
+		"local": {Command: "local-agent", Args: []string{"run", "{prompt}"}, ModelFlag: "-m", DefaultTimeout: 900, Env: map[string]string{"API_KEY": "secret"}},
+	}
+	c.Tasks = map[string]config.TaskOverride{"go/bank-account": {Timeout: 1200, Weight: 2, Image: "custom-go:1"}}
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+
+	tasks := []*task.Task{
+		{Slug: "bank-account", Language: task.Go, Tier: "core", Difficulty: "hard"},
+		{Slug: "react", Language: task.Go, Tier: "core", Difficulty: "hard", AgentTimeout: 700},
+	}
+	var buf bytes.Buffer
+	writeDryRunPlan(&buf, dryRunPlan{
+		Specs:     []RunSpec{{Agent: "local", Model: "qwen3-coder"}},
+		Shared:    SharedConfig{Timeout: 600, TestVisibility: config.TestVisibilityVisibleOnly},
+		Tasks:     tasks,
+		OutputDir: "eval-results/run",
+	})
+	out := buf.String()
+	for _, want := range []string{
+		" Output:     eval-results/run\n",
+		" Command:    local-agent -m qwen3-coder run {prompt}\n",
+		" Env:        API_KEY\n",
+		" Timeout:    at least 900s (default_timeout)\n",
+		" Images:     custom-go:1 (1 task(s))\n",
+		c.ImageForLanguage("go") + " (1 task(s))\n",
+		"go/bank-account                     [core, hard, 1200s, weight 2.00]",
+		"go/react                            [core, hard, 700s, weight",
+	} {
+		if !strings.Contains(out, want) {
+			t.Errorf("plan missing %q:\n%s", want, out)
+		}
+	}
+	if strings.Contains(out, "secret") {
+		t.Errorf("plan leaks an env value:\n%s", out)
+	}
+}
@@ -0,0 +1,258 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"crypto/tls"
+	"encoding/base64"
+	"fmt"
+	"html/template"
+	"io"
+	"mime"
+	"mime/multipart"
+	"net"
+	"net/mail"
+	"net/smtp"
+	"net/textproto"
+	"os"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// smtpTimeout bounds a whole SMTP delivery, from dialing to QUIT.
+const smtpTimeout = 2 * time.Minute
+
+// emailNotifier sends run notifications over SMTP: the summary table in the
+// body and the HTML report attached.
+type emailNotifier struct {
+	cfg config.EmailConfig
+}
+
+func (e emailNotifier) channel() string {
+	return "email"
+}
+
+func (e emailNotifier) notify(ctx context.Context, n runNotification) error {
+	msg, err := buildNotificationEmail(e.cfg, n, time.Now())
+	if err != nil {
+		return err
+	}
+	return sendSMTP(ctx, e.cfg, msg)
+}
+
+// htmlReportTemplate renders the report attached to notification emails.
+var htmlReportTemplate = template.Must(template.New("report").Funcs(template.FuncMap{
+	"status": func(r EvalResult) string {
+		_, status := getResultStatusDisplay(r)
+		return status
+	},
+	"duration": formatDuration,
+}).Parse(`<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{{.Subject}}</title>
+<style>
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2em; color: #1f2328; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #d0d7de; padding: 4px 10px; text-align: left; }
+th { background: #f6f8fa; }
+.pass { color: #1a7f37; font-weight: 600; }
+.fail { color: #cf222e; font-weight: 600; }
+.skip { color: #9a6700; }
+</style>
+</head>
+<body>
+<h1>Evaluation Report</h1>
+{{with .Summary}}
+<h2>Summary</h2>
+<table>
+<tr><th>Agent</th><td>{{.Agent}}</td></tr>
+{{if .Model}}<tr><th>Model</th><td>{{.Model}}</td></tr>{{end}}
+{{if .Reasoning}}<tr><th>Reasoning Effort</th><td>{{.Reasoning}}</td></tr>{{end}}
+<tr><th>Timestamp</th><td>{{.Timestamp}}</td></tr>
+<tr><th>Passed</th><td>{{.Passed}}/{{.Total}}</td></tr>
+<tr><th>Pass Rate</th><td>{{printf "%.1f" .PassRate}}%</td></tr>
+{{if .MaxPossibleScore}}<tr><th>Weighted Score</th><td>{{printf "%.2f" .WeightedScore}}/{{printf "%.2f" .MaxPossibleScore}} ({{printf "%.1f" .WeightedPassRate}}%)</td></tr>{{end}}
+{{if .SkippedExternalTasks}}<tr><th>Skipped (external)</th><td>{{.SkippedExternalTasks}}</td></tr>{{end}}
+{{if .Duration}}<tr><th>Duration</th><td>{{duration .Duration}}</td></tr>{{end}}
+{{if $.Interrupted}}<tr><th>Interrupted</th><td class="fail">yes</td></tr>{{end}}
+</table>
+{{if .ByLanguage}}
+<h2>By Language</h2>
+<table>
+<tr><th>Language</th><th>Passed</th><th>Total</th><th>Pass Rate</th></tr>
+{{range $lang, $agg := .ByLanguage}}<tr><td>{{$lang}}</td><td>{{$agg.Passed}}</td><td>{{$agg.Total}}</td><td>{{printf "%.1f" $agg.PassRate}}%</td></tr>
+{{end}}</table>
+{{end}}
+<h2>Task Results</h2>
+<table>
+<tr><th>Task</th><th>Status</th><th>Attempts</th><th>Weight</th><th>Score</th><th>Duration</th><th>Error</th></tr>
+{{range .Results}}<tr><td>{{.Task}}</td><td class="{{if .Passed}}pass{{else}}fail{{end}}">{{status .}}</td><td>{{.Attempts}}</td><td>{{printf "%.2f" .Weight}}</td><td>{{printf "%.2f" .WeightedScore}}</td><td>{{printf "%.1f" .Duration}}s</td><td>{{.Error}}</td></tr>
+{{end}}{{range .ExternalFailures}}<tr><td>{{.Task}}</td><td class="skip">SKIPPED ({{.FailureClass}})</td><td>-</td><td>-</td><td>-</td><td>-</td><td>{{.Error}}</td></tr>
+{{end}}</table>
+<p><em>Generated by SanityHarness on {{.Timestamp}}</em></p>
+{{end}}
+</body>
+</html>
+`))
+
+// renderHTMLReport renders a run as a standalone HTML page.
+func renderHTMLReport(n runNotification) ([]byte, error) {
+	var buf bytes.Buffer
+	err := htmlReportTemplate.Execute(&buf, struct {
+		runNotification
+		Subject string
+	}{n, notificationSubject(n)})
+	if err != nil {
+		return nil, fmt.Errorf("rendering HTML report: %w", err)
+	}
+	return buf.Bytes(), nil
+}
+
+// buildNotificationEmail assembles the MIME message for a run: a plain-text
+// body with the summary table and the HTML report as an attachment.
+func buildNotificationEmail(ec config.EmailConfig, n runNotification, now time.Time) ([]byte, error) {
+	report, err := renderHTMLReport(n)
+	if err != nil {
+		return nil, err
+	}
+
+	var body bytes.Buffer
+	mw := multipart.NewWriter(&body)
+
+	text, err := mw.CreatePart(textproto.MIMEHeader{
+		"Content-Type":              {"text/plain; charset=utf-8"},
+		"Content-Transfer-Encoding": {"8bit"},
+	})
+	if err != nil {
+		return nil, err
+	}
+	fmt.Fprintf(text, "%s\n\n%s", notificationSubject(n), notificationTable(n))
+
+	name := "sanity-report.html"
+	if n.Summary.Timestamp != "" {
+		name = "sanity-report-" + n.Summary.Timestamp + ".html"
+	}
+	attachment, err := mw.CreatePart(textproto.MIMEHeader{
+		"Content-Type":              {mime.FormatMediaType("text/html", map[string]string{"charset": "utf-8", "name": name})},
+		"Content-Disposition":       {mime.FormatMediaType("attachment", map[string]string{"filename": name})},
+		"Content-Transfer-Encoding": {"base64"},
+	})
+	if err != nil {
+		return nil, err
+	}
+	writeBase64Lines(attachment, report)
+	if err := mw.Close(); err != nil {
+		return nil, err
+	}
+
+	var msg bytes.Buffer
+	fmt.Fprintf(&msg, "From: %s\r\n", ec.From)
+	fmt.Fprintf(&msg, "To: %s\r\n", strings.Join(ec.To, ", "))
+	fmt.Fprintf(&msg, "Subject: %s\r\n", mime.QEncoding.Encode("utf-8", notificationSubject(n)))
+	fmt.Fprintf(&msg, "Date: %s\r\n", now.Format(time.RFC1123Z))
+	msg.WriteString("MIME-Version: 1.0\r\n")
+	fmt.Fprintf(&msg, "Content-Type: %s\r\n\r\n", mime.FormatMediaType("multipart/mixed", map[string]string{"boundary": mw.Boundary()}))
+	msg.Write(body.Bytes())
+	return msg.Bytes(), nil
+}
+
+// writeBase64Lines writes data base64-encoded in 76-character lines, as MIME
+// requires.
+func writeBase64Lines(w io.Writer, data []byte) {
+	encoded := base64.StdEncoding.EncodeToString(data)
+	for len(encoded) > 76 {
+		_, _ = w.Write([]byte(encoded[:76] + "\r\n"))
+		encoded = encoded[76:]
+	}
+	_, _ = w.Write([]byte(encoded + "\r\n"))
+}
+
+// sendSMTP delivers msg to the configured recipients.
+func sendSMTP(ctx context.Context, ec config.EmailConfig, msg []byte) error {
+	from, to, err := envelopeAddresses(ec)
+	if err != nil {
+		return err
+	}
+	addr := net.JoinHostPort(ec.SMTPHost, strconv.Itoa(ec.SMTPPort))
+	ctx, cancel := context.WithTimeout(ctx, smtpTimeout)
+	defer cancel()
+
+	var dialer net.Dialer
+	conn, err := dialer.DialContext(ctx, "tcp", addr)
+	if err != nil {
+		return fmt.Errorf("connecting to %s: %w", addr, err)
+	}
+	if deadline, ok := ctx.Deadline(); ok {
+		_ = conn.SetDeadline(deadline)
+	}
+	tlsConfig := &tls.Config{ServerName: ec.SMTPHost, MinVersion: tls.VersionTLS12}
+	if ec.TLS == config.SMTPTLS {
+		conn = tls.Client(conn, tlsConfig)
+	}
+	c, err := smtp.NewClient(conn, ec.SMTPHost)
+	if err != nil {
+		_ = conn.Close()
+		return fmt.Errorf("connecting to %s: %w", addr, err)
+	}
+	defer func() { _ = c.Close() }()
+
+	if ec.TLS == config.SMTPStartTLS {
+		if ok, _ := c.Extension("STARTTLS"); !ok {
+			return fmt.Errorf("%s does not support STARTTLS; set notify.email.tls = \"none\" to send unencrypted", addr)
+		}
+		if err := c.StartTLS(tlsConfig); err != nil {
+			return fmt.Errorf("starting TLS with %s: %w", addr, err)
+		}
+	}
+	if user := os.Getenv(ec.UsernameEnv); user != "" {
+		if err := c.Auth(smtp.PlainAuth("", user, os.Getenv(ec.PasswordEnv), ec.SMTPHost)); err != nil {
+			return fmt.Errorf("authenticating with %s: %w", addr, err)
+		}
+	}
+	return deliverSMTP(c, from, to, msg)
+}
+
+// envelopeAddresses returns the bare sender and recipient addresses of the
+// configured, possibly named, addresses.
+func envelopeAddresses(ec config.EmailConfig) (from string, to []string, err error) {
+	sender, err := mail.ParseAddress(ec.From)
+	if err != nil {
+		return "", nil, fmt.Errorf("invalid sender %q: %w", ec.From, err)
+	}
+	for _, raw := range ec.To {
+		rcpt, parseErr := mail.ParseAddress(raw)
+		if parseErr != nil {
+			return "", nil, fmt.Errorf("invalid recipient %q: %w", raw, parseErr)
+		}
+		to = append(to, rcpt.Address)
+	}
+	return sender.Address, to, nil
+}
+
+// deliverSMTP sends msg over an established session and ends it.
+func deliverSMTP(c *smtp.Client, from string, to []string, msg []byte) error {
+	if err := c.Mail(from); err != nil {
+		return fmt.Errorf("sending MAIL FROM: %w", err)
+	}
+	for _, rcpt := range to {
+		if err := c.Rcpt(rcpt); err != nil {
+			return fmt.Errorf("sending RCPT TO %s: %w", rcpt, err)
+		}
+	}
+	w, err := c.Data()
+	if err != nil {
+		return fmt.Errorf("sending DATA: %w", err)
+	}
+	if _, err := w.Write(msg); err != nil {
+		return fmt.Errorf("sending message: %w", err)
+	}
+	if err := w.Close(); err != nil {
+		return fmt.Errorf("sending message: %w", err)
+	}
+	return c.Quit()
+}
@@ -0,0 +1,125 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// encryptedSuffix is appended to artifacts encrypted at rest.
+const encryptedSuffix = ".age"
+
+// encryptedArtifactNames lists the task output files encrypted at rest:
+// agent transcripts and provider logs, in the task directory and in its
+// follow-up directories.
+var encryptedArtifactNames = map[string]bool{
+	"agent.log":    true,
+	judgeAuditName: true,
+}
+
+// checkEncryption reports whether [encryption] can run on this host.
+func checkEncryption() error {
+	if _, err := exec.LookPath("age"); err != nil {
+		return errors.New("[encryption] requires age on PATH (https://age-encryption.org)")
+	}
+	return nil
+}
+
+// encryptTaskArtifacts replaces the transcripts and provider logs below a
+// finished task's output directory with age-encrypted copies and returns how
+// many it encrypted. A file that fails to encrypt is left as is.
+func encryptTaskArtifacts(ctx context.Context, ec config.EncryptionConfig, dir string) (int, error) {
+	var encrypted int
+	var errs []error
+	err := filepath.WalkDir(dir, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			if errors.Is(err, fs.ErrNotExist) {
+				return nil
+			}
+			return err
+		}
+		if d.IsDir() || !encryptedArtifactNames[d.Name()] {
+			return nil
+		}
+		if err := encryptFile(ctx, ec, path); err != nil {
+			errs = append(errs, err)
+			return nil
+		}
+		encrypted++
+		return nil
+	})
+	if err != nil {
+		errs = append(errs, err)
+	}
+	return encrypted, errors.Join(errs...)
+}
+
+// encryptFile encrypts path to path.age for the configured recipients and
+// removes the plaintext.
+func encryptFile(ctx context.Context, ec config.EncryptionConfig, path string) error {
+	out := path + encryptedSuffix
+	args := []string{"--encrypt", "--output", out}
+	for _, r := range ec.Recipients {
+		args = append(args, "--recipient", strings.TrimSpace(r))
+	}
+	if ec.RecipientsFile != "" {
+		args = append(args, "--recipients-file", ec.RecipientsFile)
+	}
+	args = append(args, path)
+	if output, err := exec.CommandContext(ctx, "age", args...).CombinedOutput(); err != nil {
+		_ = os.Remove(out)
+		return fmt.Errorf("encrypting %s: %w: %s", path, err, strings.TrimSpace(string(output)))
+	}
+	return os.Remove(path)
+}
+
+// readArtifact reads a task output file. If only its encrypted copy exists,
+// the copy is decrypted with [encryption] identity.
+func readArtifact(path string) ([]byte, error) {
+	var identity string
+	if cfg != nil {
+		identity = cfg.Encryption.Identity
+	}
+	return readArtifactWith(path, identity)
+}
+
+// readArtifactWith is readArtifact with an explicit identity file.
+func readArtifactWith(path, identity string) ([]byte, error) {
+	data, err := os.ReadFile(path)
+	if !errors.Is(err, fs.ErrNotExist) {
+		return data, err
+	}
+	if _, statErr := os.Stat(path + encryptedSuffix); statErr != nil {
+		return nil, err
+	}
+	if identity == "" {
+		return nil, fmt.Errorf("%s is encrypted; set [encryption] identity to read it", filepath.Base(path))
+	}
+	var stderr strings.Builder
+	cmd := exec.CommandContext(context.Background(), "age", "--decrypt", "--identity", identity, path+encryptedSuffix)
+	cmd.Stderr = &stderr
+	data, err = cmd.Output()
+	if err != nil {
+		return nil, fmt.Errorf("decrypting %s: %w: %s", path+encryptedSuffix, err, strings.TrimSpace(stderr.String()))
+	}
+	return data, nil
+}
+
+// encryptResultArtifacts encrypts a finished task's transcripts and provider
+// logs when [encryption] is configured. It runs even after an interrupt, so
+// no plaintext is left behind.
+func encryptResultArtifacts(ctx context.Context, result *EvalResult) {
+	if cfg == nil || !cfg.Encryption.Enabled() || result.WorkspaceDir == "" {
+		return
+	}
+	if _, err := encryptTaskArtifacts(context.WithoutCancel(ctx), cfg.Encryption, result.WorkspaceDir); err != nil {
+		logger.Warn("failed to encrypt task artifacts", "task", result.Task, "error", err)
+	}
+}
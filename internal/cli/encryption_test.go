@@ -0,0 +1,77 @@
+package cli
+
+import (
+	"context"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestEncryptTaskArtifacts(t *testing.T) {
+	t.Parallel()
+
+	for _, bin := range []string{"age", "age-keygen"} {
+		if _, err := exec.LookPath(bin); err != nil {
+			t.Skipf("%s not on PATH", bin)
+		}
+	}
+	dir := t.TempDir()
+	identity := filepath.Join(dir, "key.txt")
+	out, err := exec.CommandContext(context.Background(), "age-keygen", "-o", identity).CombinedOutput()
+	if err != nil {
+		t.Fatalf("age-keygen: %v: %s", err, out)
+	}
+	recipient, err := exec.CommandContext(context.Background(), "age-keygen", "-y", identity).Output()
+	if err != nil {
+		t.Fatalf("age-keygen -y: %v", err)
+	}
+
+	taskDir := filepath.Join(dir, "go-bank-account")
+	followUpDir := filepath.Join(taskDir, followUpDirName, "1")
+	if err := os.MkdirAll(followUpDir, 0o755); err != nil {
+		t.Fatal(err)
+	}
+	files := map[string]string{
+		filepath.Join(taskDir, "agent.log"):      "proprietary prompt",
+		filepath.Join(taskDir, judgeAuditName):   `{"reply":"5"}`,
+		filepath.Join(taskDir, "validation.log"): "PASS",
+		filepath.Join(followUpDir, "agent.log"):  "follow-up transcript",
+	}
+	for path, content := range files {
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	ec := config.EncryptionConfig{Recipients: []string{strings.TrimSpace(string(recipient))}}
+	n, err := encryptTaskArtifacts(context.Background(), ec, taskDir)
+	if err != nil {
+		t.Fatalf("encryptTaskArtifacts() error = %v", err)
+	}
+	if n != 3 {
+		t.Errorf("encrypted %d files, want 3", n)
+	}
+	if _, err := os.Stat(filepath.Join(taskDir, "agent.log")); !os.IsNotExist(err) {
+		t.Errorf("plaintext agent.log left behind: %v", err)
+	}
+	if _, err := os.Stat(filepath.Join(taskDir, "validation.log")); err != nil {
+		t.Errorf("validation.log should stay in plaintext: %v", err)
+	}
+
+	if _, err := readArtifactWith(filepath.Join(taskDir, "agent.log"), ""); err == nil || !strings.Contains(err.Error(), "is encrypted") {
+		t.Errorf("readArtifactWith() without identity error = %v", err)
+	}
+	for _, path := range []string{filepath.Join(taskDir, "agent.log"), filepath.Join(followUpDir, "agent.log")} {
+		data, err := readArtifactWith(path, identity)
+		if err != nil {
+			t.Fatalf("readArtifactWith(%s) error = %v", path, err)
+		}
+		if string(data) != files[path] {
+			t.Errorf("readArtifactWith(%s) = %q, want %q", path, data, files[path])
+		}
+	}
+}
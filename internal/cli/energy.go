@@ -0,0 +1,71 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+)
+
+// raplRoot is where Linux exposes RAPL energy counters (Intel, and AMD since
+// Zen).
+const raplRoot = "/sys/class/powercap"
+
+// raplSample is a reading of each RAPL package's energy counter, in
+// microjoules.
+type raplSample map[string]int64
+
+// readRAPL returns the package energy counters under root, or nil when RAPL
+// is unavailable or unreadable; recent kernels restrict energy_uj to root.
+// Subzones (core, uncore, dram) are skipped because their package already
+// counts them.
+func readRAPL(root string) raplSample {
+	matches, _ := filepath.Glob(filepath.Join(root, "intel-rapl:*", "energy_uj"))
+	var sample raplSample
+	for _, path := range matches {
+		zone := filepath.Base(filepath.Dir(path))
+		if strings.Count(zone, ":") != 1 {
+			continue
+		}
+		uj, err := readCounter(path)
+		if err != nil {
+			continue
+		}
+		if sample == nil {
+			sample = make(raplSample)
+		}
+		sample[zone] = uj
+	}
+	return sample
+}
+
+// joulesSince returns the energy used since prev, allowing for counters that
+// wrapped around. RAPL measures the whole machine, so concurrent work is
+// included.
+func (s raplSample) joulesSince(root string, prev raplSample) float64 {
+	var uj int64
+	for zone, before := range prev {
+		after, ok := s[zone]
+		if !ok {
+			continue
+		}
+		delta := after - before
+		if delta < 0 {
+			maxRange, err := readCounter(filepath.Join(root, zone, "max_energy_range_uj"))
+			if err != nil {
+				continue
+			}
+			delta += maxRange
+		}
+		uj += delta
+	}
+	return float64(uj) / 1e6
+}
+
+func readCounter(path string) (int64, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return 0, err
+	}
+	return strconv.ParseInt(strings.TrimSpace(string(data)), 10, 64)
+}
@@ -0,0 +1,45 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func writeRAPLZone(t *testing.T, root, zone, energy, maxRange string) {
+	t.Helper()
+	dir := filepath.Join(root, zone)
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "energy_uj"), []byte(energy+"\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "max_energy_range_uj"), []byte(maxRange+"\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+}
+
+func TestRAPLEnergy(t *testing.T) {
+	t.Parallel()
+
+	if sample := readRAPL(t.TempDir()); sample != nil {
+		t.Fatalf("readRAPL() without RAPL = %v, want nil", sample)
+	}
+
+	root := t.TempDir()
+	writeRAPLZone(t, root, "intel-rapl:0", "1000000", "10000000")
+	writeRAPLZone(t, root, "intel-rapl:1", "9500000", "10000000")
+	writeRAPLZone(t, root, "intel-rapl:0:0", "500000", "10000000")
+	before := readRAPL(root)
+	if len(before) != 2 {
+		t.Fatalf("readRAPL() = %v, want two package zones", before)
+	}
+
+	// Package 0 uses 2.5 J; package 1 wraps around after using 1 J.
+	writeRAPLZone(t, root, "intel-rapl:0", "3500000", "10000000")
+	writeRAPLZone(t, root, "intel-rapl:1", "500000", "10000000")
+	if got := readRAPL(root).joulesSince(root, before); got != 3.5 {
+		t.Fatalf("joulesSince() = %v, want 3.5", got)
+	}
+}
@@ -2,6 +2,7 @@ package cli
 
 import (
 	"bytes"
+	"cmp"
 	"context"
 	"encoding/hex"
 	"encoding/json"
@@ -18,7 +19,6 @@ import (
 	"sync"
 	"syscall"
 	"time"
-	"unicode/utf8"
 
 	"github.com/spf13/cobra"
 	"github.com/zeebo/blake3"
@@ -41,14 +41,24 @@ var (
 	evalLang            string
 	evalTier            string
 	evalDifficulty      string
+	evalFilter          string
+	evalExclude         string
+	evalTags            string
 	evalTimeout         int
 	evalOutputDir       string
 	evalKeepWorkspaces  bool
 	evalParallel        int
 	evalDryRun          bool
+	evalNoProgress      bool
+	evalTUIMode         bool
 	evalUseMCPTools     bool
 	evalUseSkills       bool
+	evalSkillPacks      []string
 	evalDisableMCP      bool
+	evalMCPServers      string
+	evalTemperature     string
+	evalTopP            string
+	evalMinP            string
 	evalNoSandbox       bool
 	evalLegacy          bool
 	evalSandboxActive   bool
@@ -57,17 +67,35 @@ var (
 	evalSandboxSharedRO []string
 	evalResume          string
 	evalRepeat          int
+	evalModelFamily     string
+	evalModelParams     string
+	evalModelQuant      string
+	evalPick            bool
+	evalMaxTurns        int
+	evalMaxToolCalls    int
+	evalMaxTokens       int64
+	evalNetworkAudit    bool
+	evalJudge           bool
+	evalPromptLang      string
+	evalDistractors     string
+	evalChaosProfile    string
+	evalPromptVariants  string
+	evalVariantSeed     uint64
+	evalSeed            uint64
+	evalAgentMemoryMode string
+	evalTestVisibility  string
+	evalRepairAttempts  int
+	evalAttemptFeedback string
+	evalUpload          string
+	evalToolchains      string
+	evalToolchain       string
+	evalDistribute      string
+	evalStrictConfig    bool
 )
 
-// Quota retry configuration.
+// Retry limits and backoff for each failure type come from [retry]; see
+// retryPolicy.
 const (
-	quotaMaxRetries  = 5
-	quotaRetryDelay1 = 30 * time.Second
-	quotaRetryDelay2 = 60 * time.Second
-	quotaRetryDelay3 = 120 * time.Second
-	quotaRetryDelay4 = 240 * time.Second
-	quotaRetryDelay5 = 480 * time.Second
-
 	// Threshold for considering an agent log as an infra failure (empty or near-empty).
 	infraFailureLogThreshold = 10 // bytes
 
@@ -77,28 +105,6 @@ const (
 	quotaExhaustedStopThreshold = 5
 )
 
-// Infra failure retry configuration (separate from quota retries).
-const (
-	infraMaxRetries  = 5
-	infraRetryDelay1 = 15 * time.Second
-	infraRetryDelay2 = 30 * time.Second
-	infraRetryDelay3 = 60 * time.Second
-	infraRetryDelay4 = 120 * time.Second
-	infraRetryDelay5 = 240 * time.Second
-)
-
-// Agent-timeout retry configuration. Plain agent timeouts (the agent produced
-// *some* output but then stalled for the whole wall-clock budget) are not
-// infra failures — isInfraFailure sees meaningful content and returns false —
-// but they are also not the agent "meaningfully failing", since we have no
-// signal either way about what happened. This often looks like a long-tail
-// SSE hang on the model/provider side. One cheap retry rescues the common
-// case without burning much time.
-const (
-	agentTimeoutMaxRetries  = 1
-	agentTimeoutRetryDelay1 = 15 * time.Second
-)
-
 // Patterns indicating recoverable rate limit errors (worth retrying).
 // These use contextual phrases to avoid false positives from bare numbers
 // appearing in durations (e.g. "0.503s"), UUIDs, git hashes, line numbers, etc.
@@ -238,6 +244,7 @@ type agentBehaviorMetrics struct {
 	ToolchainSearchAttempts      int
 	SkillsUsed                   bool
 	SkillsUsageSignals           int
+	ToolCalls                    map[string]int
 }
 
 // FailureClass categorizes the root cause of non-successful or degraded runs.
@@ -252,40 +259,80 @@ const (
 	FailureClassIntegrity         FailureClass = "integrity"
 	FailureClassValidationError   FailureClass = "validation_error"
 	FailureClassValidationTimeout FailureClass = "validation_timeout"
+	FailureClassBudgetExhausted   FailureClass = "budget_exhausted"
+	FailureClassInterrupted       FailureClass = "interrupted" // Cut short by Ctrl-C; never scored, left for --resume
 )
 
 // EvalResult holds the result of evaluating a single task.
 type EvalResult struct {
-	Task                         string            `json:"task"`
-	Language                     string            `json:"language"`
-	Tier                         string            `json:"tier,omitempty"`
-	Difficulty                   string            `json:"difficulty,omitempty"`
-	Passed                       bool              `json:"passed"`
-	AgentTimedOut                bool              `json:"agent_timed_out"`
-	Status                       task.ResultStatus `json:"status"`
-	Attempts                     int               `json:"attempts"`
-	Duration                     float64           `json:"duration_seconds"`
-	AgentTime                    float64           `json:"agent_duration_seconds,omitempty"`
-	ValidateTime                 float64           `json:"validation_duration_seconds,omitempty"`
-	PromptChars                  int               `json:"prompt_chars,omitempty"`
-	Error                        string            `json:"error,omitempty"`
-	FailureClass                 FailureClass      `json:"failure_class"`
-	Weight                       float64           `json:"weight,omitempty"`
-	WeightedScore                float64           `json:"weighted_score,omitempty"`
-	QuotaRetries                 int               `json:"quota_retries"`
-	InfraRetries                 int               `json:"infra_retries"`
-	AgentTimeoutRetries          int               `json:"agent_timeout_retries,omitempty"`
-	QuotaExhausted               bool              `json:"quota_exhausted"`
-	InfraFailure                 bool              `json:"infra_failure"`
-	SelfTestCommands             int               `json:"self_test_commands"`
-	SelfTestCommandsConfident    bool              `json:"self_test_commands_confident"`
-	ToolchainInstallAttempts     int               `json:"toolchain_install_attempts"`
-	OutOfWorkspaceReadAttempts   int               `json:"out_of_workspace_read_attempts"`
-	OutOfWorkspaceReadsConfident bool              `json:"out_of_workspace_read_attempts_confident"`
-	ToolchainSearchAttempts      int               `json:"toolchain_search_attempts"`
-	SkillsUsed                   bool              `json:"skills_used"`
-	SkillsUsageSignals           int               `json:"skills_usage_signals"`
-	WorkspaceDir                 string            `json:"-"` // Not serialized, used for cleanup
+	Task                         string              `json:"task"`
+	Language                     string              `json:"language"`
+	Tier                         string              `json:"tier,omitempty"`
+	Difficulty                   string              `json:"difficulty,omitempty"`
+	PromptLang                   string              `json:"prompt_lang,omitempty"`
+	PromptVariant                string              `json:"prompt_variant,omitempty"`
+	Passed                       bool                `json:"passed"`
+	AgentTimedOut                bool                `json:"agent_timed_out"`
+	AgentTermination             string              `json:"agent_termination,omitempty"`
+	ValidationTermination        string              `json:"validation_termination,omitempty"`
+	Status                       task.ResultStatus   `json:"status"`
+	Attempts                     int                 `json:"attempts"`
+	Duration                     float64             `json:"duration_seconds"`
+	AgentTime                    float64             `json:"agent_duration_seconds,omitempty"`
+	ValidateTime                 float64             `json:"validation_duration_seconds,omitempty"`
+	AgentCPUTime                 float64             `json:"agent_cpu_seconds,omitempty"`
+	ValidateCPUTime              float64             `json:"validation_cpu_seconds,omitempty"`
+	AgentEnergy                  float64             `json:"agent_energy_joules,omitempty"`
+	ValidateEnergy               float64             `json:"validation_energy_joules,omitempty"`
+	PromptChars                  int                 `json:"prompt_chars,omitempty"`
+	ContextChars                 int                 `json:"context_chars,omitempty"`
+	PromptBudget                 *PromptBudget       `json:"prompt_budget,omitempty"`
+	Error                        string              `json:"error,omitempty"`
+	FailureClass                 FailureClass        `json:"failure_class"`
+	Weight                       float64             `json:"weight,omitempty"`
+	WeightedScore                float64             `json:"weighted_score,omitempty"`
+	QuotaRetries                 int                 `json:"quota_retries"`
+	InfraRetries                 int                 `json:"infra_retries"`
+	AgentTimeoutRetries          int                 `json:"agent_timeout_retries,omitempty"`
+	ValidationRetries            int                 `json:"validation_retries,omitempty"`
+	FlakyReruns                  int                 `json:"flaky_reruns,omitempty"`
+	QuotaExhausted               bool                `json:"quota_exhausted"`
+	InfraFailure                 bool                `json:"infra_failure"`
+	BudgetExhausted              bool                `json:"budget_exhausted,omitempty"`
+	AgentTurns                   int                 `json:"agent_turns,omitempty"`
+	AgentToolCalls               int                 `json:"agent_tool_calls,omitempty"`
+	SelfTestCommands             int                 `json:"self_test_commands"`
+	SelfTestCommandsConfident    bool                `json:"self_test_commands_confident"`
+	ToolchainInstallAttempts     int                 `json:"toolchain_install_attempts"`
+	OutOfWorkspaceReadAttempts   int                 `json:"out_of_workspace_read_attempts"`
+	OutOfWorkspaceReadsConfident bool                `json:"out_of_workspace_read_attempts_confident"`
+	ToolchainSearchAttempts      int                 `json:"toolchain_search_attempts"`
+	SkillsUsed                   bool                `json:"skills_used"`
+	SkillsUsageSignals           int                 `json:"skills_usage_signals"`
+	ToolCalls                    map[string]int      `json:"tool_calls,omitempty"`
+	ContextFiles                 []ContextFile       `json:"context_files,omitempty"`
+	FileActivity                 *FileActivity       `json:"file_activity,omitempty"`
+	Network                      *NetworkActivity    `json:"network,omitempty"`
+	AttemptSnapshots             []string            `json:"attempt_snapshots,omitempty"`
+	AttemptChanges               []AttemptChange     `json:"attempt_changes,omitempty"`
+	PeakMemoryBytes              int64               `json:"peak_memory_bytes,omitempty"`
+	BinarySizeBytes              int64               `json:"binary_size_bytes,omitempty"`
+	Judge                        *JudgeScore         `json:"judge,omitempty"`
+	Plagiarism                   *PlagiarismMatch    `json:"plagiarism,omitempty"`
+	Benchmark                    *BenchmarkRun       `json:"benchmark,omitempty"`
+	HiddenTestAccess             []HiddenAccess      `json:"hidden_test_access,omitempty"`
+	Distractors                  *DistractorActivity `json:"distractors,omitempty"`
+	ChaosInjections              int                 `json:"chaos_injections,omitempty"`
+	Tainted                      bool                `json:"tainted,omitempty"`
+	DurationAnomaly              string              `json:"duration_anomaly,omitempty"`
+	ExpectedDuration             float64             `json:"expected_agent_duration_seconds,omitempty"`
+	FollowUpCount                int                 `json:"follow_up_count,omitempty"`
+	FollowUps                    []FollowUpResult    `json:"follow_ups,omitempty"`
+	FollowUpScore                float64             `json:"follow_up_score,omitempty"`
+	PassedOnAttempt              int                 `json:"passed_on_attempt,omitempty"`
+	Repairs                      []RepairAttempt     `json:"repairs,omitempty"`
+	Usage                        *TokenUsage         `json:"token_usage,omitempty"`
+	WorkspaceDir                 string              `json:"-"` // Not serialized, used for cleanup
 }
 
 // EvalAggregate summarizes results for a group (language, tier, difficulty).
@@ -311,12 +358,25 @@ type ExternalFailure struct {
 
 // EvalSummary holds the overall evaluation summary.
 type EvalSummary struct {
+	SchemaVersion                   int                      `json:"schema_version"`
 	Agent                           string                   `json:"agent"`
 	Model                           string                   `json:"model,omitempty"`
+	ModelFamily                     string                   `json:"model_family,omitempty"`
+	ModelParamsB                    float64                  `json:"model_params_b,omitempty"`
+	ModelQuant                      string                   `json:"model_quant,omitempty"`
 	Reasoning                       string                   `json:"reasoning,omitempty"`
 	Timestamp                       string                   `json:"timestamp"`
 	Tier                            string                   `json:"tier,omitempty"`
 	Difficulty                      string                   `json:"difficulty,omitempty"`
+	Seed                            uint64                   `json:"seed,omitempty"`
+	PromptLang                      string                   `json:"prompt_lang,omitempty"`
+	PromptExperiment                *PromptExperiment        `json:"prompt_experiment,omitempty"`
+	AgentMemory                     string                   `json:"agent_memory,omitempty"`
+	TestVisibility                  string                   `json:"test_visibility,omitempty"`
+	UploadURL                       string                   `json:"upload_url,omitempty"`
+	Toolchain                       string                   `json:"toolchain,omitempty"`
+	ToolchainImages                 map[string]string        `json:"toolchain_images,omitempty"`
+	EncryptedArtifacts              bool                     `json:"encrypted_artifacts,omitempty"`
 	Timeout                         int                      `json:"timeout"`
 	Parallel                        int                      `json:"parallel"`
 	Results                         []EvalResult             `json:"results"`
@@ -324,6 +384,8 @@ type EvalSummary struct {
 	Failed                          int                      `json:"failed"`
 	Total                           int                      `json:"total"`
 	SkippedExternalTasks            int                      `json:"skipped_external_tasks"`
+	Interrupted                     bool                     `json:"interrupted,omitempty"`
+	RemainingTasks                  int                      `json:"remaining_tasks,omitempty"`
 	PassRate                        float64                  `json:"pass_rate"`
 	WeightedScore                   float64                  `json:"weighted_score,omitempty"`
 	MaxPossibleScore                float64                  `json:"max_possible_score,omitempty"`
@@ -332,14 +394,48 @@ type EvalSummary struct {
 	Duration                        float64                  `json:"duration_seconds,omitempty"`
 	AgentTime                       float64                  `json:"agent_duration_seconds,omitempty"`
 	ValidateTime                    float64                  `json:"validation_duration_seconds,omitempty"`
+	AgentCPUTime                    float64                  `json:"agent_cpu_seconds,omitempty"`
+	ValidateCPUTime                 float64                  `json:"validation_cpu_seconds,omitempty"`
+	AgentEnergy                     float64                  `json:"agent_energy_joules,omitempty"`
+	ValidateEnergy                  float64                  `json:"validation_energy_joules,omitempty"`
+	Usage                           *TokenUsage              `json:"token_usage,omitempty"`
 	PromptChars                     int                      `json:"prompt_chars,omitempty"`
+	OverBudgetTasks                 int                      `json:"over_budget_tasks,omitempty"`
+	BudgetExhaustedTasks            int                      `json:"budget_exhausted_tasks,omitempty"`
+	TaintedTasks                    int                      `json:"tainted_tasks,omitempty"`
+	NoOpTasks                       int                      `json:"no_op_tasks,omitempty"`
+	DurationAnomalies               int                      `json:"duration_anomalies,omitempty"`
+	LikelyMemorizedTasks            int                      `json:"likely_memorized_tasks,omitempty"`
+	DistractorPack                  *DistractorPack          `json:"distractor_pack,omitempty"`
+	TasksReadingDistractors         int                      `json:"tasks_reading_distractors,omitempty"`
+	TasksEditingDistractors         int                      `json:"tasks_editing_distractors,omitempty"`
+	Chaos                           *ChaosSettings           `json:"chaos,omitempty"`
+	ChaosInjections                 int                      `json:"chaos_injections,omitempty"`
+	NetworkAudit                    bool                     `json:"network_audit,omitempty"`
+	TasksWithNetwork                int                      `json:"tasks_with_network,omitempty"`
+	JudgeModel                      string                   `json:"judge_model,omitempty"`
+	JudgedTasks                     int                      `json:"judged_tasks,omitempty"`
+	JudgeScore                      float64                  `json:"judge_score,omitempty"`
+	BenchmarkedTasks                int                      `json:"benchmarked_tasks,omitempty"`
+	BenchmarksOverThreshold         int                      `json:"benchmarks_over_threshold,omitempty"`
+	FollowUpTasks                   int                      `json:"follow_up_tasks,omitempty"`
+	FollowUpsPassed                 int                      `json:"follow_ups_passed,omitempty"`
+	FollowUpsTotal                  int                      `json:"follow_ups_total,omitempty"`
+	FollowUpPassRate                float64                  `json:"follow_up_pass_rate,omitempty"`
+	RepairAttempts                  int                      `json:"repair_attempts,omitempty"`
+	AttemptFeedback                 string                   `json:"attempt_feedback,omitempty"`
+	RepairedTasks                   int                      `json:"repaired_tasks,omitempty"`
+	UnrepairedTasks                 int                      `json:"unrepaired_tasks,omitempty"`
 	ByLanguage                      map[string]EvalAggregate `json:"by_language,omitempty"`
 	ByTier                          map[string]EvalAggregate `json:"by_tier,omitempty"`
 	ByDifficulty                    map[string]EvalAggregate `json:"by_difficulty,omitempty"`
 	ExternalFailures                []ExternalFailure        `json:"external_failures,omitempty"`
 	UseMCPTools                     bool                     `json:"use_mcp_tools"`
 	UseSkills                       bool                     `json:"use_skills"`
+	SkillPacks                      []SkillPack              `json:"skill_packs,omitempty"`
 	DisableMCP                      bool                     `json:"disable_mcp"`
+	MCPServers                      []string                 `json:"mcp_servers,omitempty"`
+	Sampling                        *samplingParams          `json:"sampling,omitempty"`
 	Sandbox                         bool                     `json:"sandbox"`
 	Legacy                          bool                     `json:"legacy"`
 	QuotaAffectedTasks              int                      `json:"quota_affected_tasks"`
@@ -348,6 +444,8 @@ type EvalSummary struct {
 	TotalQuotaRetries               int                      `json:"total_quota_retries"`
 	TotalInfraRetries               int                      `json:"total_infra_retries"`
 	TotalAgentTimeoutRetries        int                      `json:"total_agent_timeout_retries"`
+	TotalValidationRetries          int                      `json:"total_validation_retries,omitempty"`
+	FlakyPasses                     int                      `json:"flaky_passes,omitempty"`
 	AgentTimeoutTasks               int                      `json:"agent_timeout_tasks"`
 	AgentTimeoutRetriedTasks        int                      `json:"agent_timeout_retried_tasks"`
 	TotalSelfTestCommands           int                      `json:"total_self_test_commands"`
@@ -355,6 +453,7 @@ type EvalSummary struct {
 	TotalOutOfWorkspaceReadAttempts int                      `json:"total_out_of_workspace_read_attempts"`
 	SkillsUsageRate                 float64                  `json:"skills_usage_rate"`
 	TotalSkillsUsageSignals         int                      `json:"total_skills_usage_signals"`
+	ToolCalls                       map[string]int           `json:"tool_calls,omitempty"`
 	TasksWithSelfTesting            int                      `json:"tasks_with_self_testing"`
 	TasksWithToolchainInstall       int                      `json:"tasks_with_toolchain_install"`
 	TasksWithOutOfWorkspaceReads    int                      `json:"tasks_with_out_of_workspace_reads"`
@@ -365,47 +464,103 @@ type EvalSummary struct {
 
 // RunSpec defines a single eval run's configuration.
 type RunSpec struct {
-	Agent     string `json:"agent"`
-	Model     string `json:"model,omitempty"`
-	Reasoning string `json:"reasoning,omitempty"`
+	Agent       string `json:"agent"`
+	Model       string `json:"model,omitempty"`
+	Reasoning   string `json:"reasoning,omitempty"`
+	ModelFamily string `json:"model_family,omitempty"`
+	ModelParams string `json:"model_params,omitempty"`
+	ModelQuant  string `json:"model_quant,omitempty"`
+	Toolchain   string `json:"toolchain,omitempty"`
 }
 
 // SharedConfig holds settings common to all runs.
 type SharedConfig struct {
-	Tier           string
-	Difficulty     string
-	Lang           string
-	Tasks          string
-	Timeout        int
-	Parallel       int
-	KeepWorkspaces bool
-	UseMCPTools    bool
-	UseSkills      bool
-	DisableMCP     bool
-	NoSandbox      bool
-	Legacy         bool
-	DryRun         bool
+	Tier            string
+	Difficulty      string
+	Lang            string
+	Tasks           string
+	Filter          string
+	Exclude         string
+	Tags            string
+	Timeout         int
+	Parallel        int
+	KeepWorkspaces  bool
+	UseMCPTools     bool
+	UseSkills       bool
+	SkillPacks      []string
+	DisableMCP      bool
+	MCPServers      string
+	Temperature     string
+	TopP            string
+	MinP            string
+	NoSandbox       bool
+	Legacy          bool
+	DryRun          bool
+	MaxTurns        int
+	MaxToolCalls    int
+	MaxTokens       int64
+	NetworkAudit    bool
+	Judge           bool
+	PromptLang      string
+	DistractorPack  string
+	Chaos           string
+	PromptVariants  string
+	VariantSeed     uint64
+	Seed            uint64
+	AgentMemory     string
+	TestVisibility  string
+	RepairAttempts  int
+	AttemptFeedback string
+	Upload          string
 }
 
 // RunConfig stores the original eval configuration for resume capability.
 type RunConfig struct {
-	Agent          string   `json:"agent"`
-	Model          string   `json:"model,omitempty"`
-	Reasoning      string   `json:"reasoning,omitempty"`
-	Tier           string   `json:"tier,omitempty"`
-	Difficulty     string   `json:"difficulty,omitempty"`
-	Lang           string   `json:"lang,omitempty"`
-	Tasks          string   `json:"tasks,omitempty"`
-	Timeout        int      `json:"timeout"`
-	Parallel       int      `json:"parallel"`
-	UseMCPTools    bool     `json:"use_mcp_tools"`
-	UseSkills      bool     `json:"use_skills"`
-	DisableMCP     bool     `json:"disable_mcp"`
-	NoSandbox      bool     `json:"no_sandbox"`
-	Legacy         bool     `json:"legacy"`
-	KeepWorkspaces bool     `json:"keep_workspaces"`
-	TaskList       []string `json:"task_list"`
-	CreatedAt      string   `json:"created_at"`
+	Agent           string   `json:"agent"`
+	Model           string   `json:"model,omitempty"`
+	Reasoning       string   `json:"reasoning,omitempty"`
+	ModelFamily     string   `json:"model_family,omitempty"`
+	ModelParams     string   `json:"model_params,omitempty"`
+	ModelQuant      string   `json:"model_quant,omitempty"`
+	Tier            string   `json:"tier,omitempty"`
+	Difficulty      string   `json:"difficulty,omitempty"`
+	Lang            string   `json:"lang,omitempty"`
+	Tasks           string   `json:"tasks,omitempty"`
+	Filter          string   `json:"filter,omitempty"`
+	Exclude         string   `json:"exclude,omitempty"`
+	Tags            string   `json:"tags,omitempty"`
+	Timeout         int      `json:"timeout"`
+	Parallel        int      `json:"parallel"`
+	UseMCPTools     bool     `json:"use_mcp_tools"`
+	UseSkills       bool     `json:"use_skills"`
+	SkillPacks      []string `json:"skill_packs,omitempty"`
+	DisableMCP      bool     `json:"disable_mcp"`
+	MCPServers      string   `json:"mcp_servers,omitempty"`
+	Temperature     string   `json:"temperature,omitempty"`
+	TopP            string   `json:"top_p,omitempty"`
+	MinP            string   `json:"min_p,omitempty"`
+	NoSandbox       bool     `json:"no_sandbox"`
+	Legacy          bool     `json:"legacy"`
+	KeepWorkspaces  bool     `json:"keep_workspaces"`
+	MaxTurns        int      `json:"max_turns,omitempty"`
+	MaxToolCalls    int      `json:"max_tool_calls,omitempty"`
+	MaxTokens       int64    `json:"max_tokens_per_attempt,omitempty"`
+	NetworkAudit    bool     `json:"network_audit,omitempty"`
+	Judge           bool     `json:"judge,omitempty"`
+	PromptLang      string   `json:"prompt_lang,omitempty"`
+	DistractorPack  string   `json:"distractor_pack,omitempty"`
+	Chaos           string   `json:"chaos,omitempty"`
+	PromptVariants  string   `json:"prompt_variants,omitempty"`
+	VariantSeed     uint64   `json:"variant_seed,omitempty"`
+	Seed            uint64   `json:"seed,omitempty"`
+	AgentMemory     string   `json:"agent_memory,omitempty"`
+	TestVisibility  string   `json:"test_visibility,omitempty"`
+	RepairAttempts  int      `json:"repair_attempts,omitempty"`
+	AttemptFeedback string   `json:"attempt_feedback,omitempty"`
+	Upload          string   `json:"upload,omitempty"`
+	Toolchain       string   `json:"toolchain,omitempty"`
+	TaskList        []string `json:"task_list"`
+	CreatedAt       string   `json:"created_at"`
 }
 
 var evalCmd = &cobra.Command{
@@ -433,6 +588,7 @@ Built-in agents:
   ccs       - Claude Code Switch
   cline     - Cline CLI
   pi        - Pi CLI
+  aider     - Aider
 
 Custom agents can be configured in sanity.toml under [agents.<name>].
 
@@ -443,7 +599,15 @@ Examples:
   sanity eval --agent opencode --model google/gemini-2.5-flash
   sanity eval --agent claude --lang go
   sanity eval --agent my-custom-agent --tasks bank-account,react
+  sanity eval --agent claude --filter "rust/*" --exclude rust/macros
+  sanity eval --agent claude --tag concurrency
   sanity eval --agent gemini --dry-run
+  sanity eval --agent gemini --tier all --pick
+  sanity eval --agent gemini --prompt-lang ja
+  sanity eval --agent gemini --chaos mild
+  sanity eval --agent gemini --prompt-variants control,terse --repeat 3
+  sanity eval --agent gemini --lang rust --toolchains default,rust-beta
+  sanity eval --agent gemini --tier all --distribute workers.toml
   sanity eval --resume ./eval-results/2026-01-19T192910-gemini`,
 	RunE: func(cmd *cobra.Command, args []string) error {
 		// Apply config defaults for flags not explicitly set.
@@ -458,13 +622,77 @@ Examples:
 		if evalRepeat < 1 {
 			evalRepeat = 1
 		}
+		evalPromptLang = strings.ToLower(evalPromptLang)
+		if task.IsSourcePromptLang(evalPromptLang) {
+			evalPromptLang = ""
+		}
+		if evalPromptVariants != "" && evalVariantSeed == 0 {
+			if evalSeed != 0 {
+				evalVariantSeed = evalSeed
+			} else {
+				evalVariantSeed = newVariantSeed()
+			}
+		}
+		if evalAgentMemoryMode == "" && cfg != nil {
+			evalAgentMemoryMode = cfg.Harness.AgentMemory
+		}
+		if evalTestVisibility == "" && !evalLegacy && cfg != nil {
+			evalTestVisibility = cfg.Harness.TestVisibility
+		}
+		if err := resolveTestVisibilityFlags(); err != nil {
+			return err
+		}
+		if evalRepairAttempts == 0 && cfg != nil {
+			evalRepairAttempts = cfg.Harness.RepairAttempts
+		}
+		if evalAttemptFeedback == "" && cfg != nil {
+			evalAttemptFeedback = cfg.Harness.AttemptFeedback
+		}
+		if err := resolveRepairFlags(); err != nil {
+			return err
+		}
+		if evalUpload == "" && cfg != nil {
+			evalUpload = cfg.Upload.URL
+		}
+		if evalUpload != "" {
+			if _, err := config.ParseUploadURL(evalUpload); err != nil {
+				return err
+			}
+		}
+		if _, err := task.ParseFilter(evalFilter, evalExclude, evalTags); err != nil {
+			return err
+		}
+
+		if evalPick {
+			if evalResume != "" {
+				return fmt.Errorf("--pick cannot be combined with --resume")
+			}
+			candidates, err := selectTasks(evalTasks, evalLang, evalTier, evalDifficulty)
+			if err != nil {
+				return err
+			}
+			taskFilter, _ := task.ParseFilter(evalFilter, evalExclude, evalTags) // Validated above
+			picked, err := pickTasks(taskFilter.Apply(candidates))
+			if err != nil {
+				return err
+			}
+			evalTasks = strings.Join(picked, ",")
+			fmt.Printf(" Picked %d task(s): %s\n", len(picked), evalTasks)
+		}
 
 		shared := SharedConfig{
 			Tier: evalTier, Difficulty: evalDifficulty, Lang: evalLang,
 			Tasks: evalTasks, Timeout: evalTimeout, Parallel: evalParallel,
+			Filter: evalFilter, Exclude: evalExclude, Tags: evalTags,
 			KeepWorkspaces: evalKeepWorkspaces, UseMCPTools: evalUseMCPTools,
-			UseSkills: evalUseSkills, DisableMCP: evalDisableMCP, NoSandbox: evalNoSandbox,
-			Legacy: evalLegacy, DryRun: evalDryRun,
+			UseSkills: evalUseSkills, SkillPacks: evalSkillPacks, DisableMCP: evalDisableMCP, MCPServers: evalMCPServers,
+			Temperature: evalTemperature, TopP: evalTopP, MinP: evalMinP,
+			NoSandbox: evalNoSandbox, Legacy: evalLegacy, DryRun: evalDryRun,
+			MaxTurns: evalMaxTurns, MaxToolCalls: evalMaxToolCalls, MaxTokens: evalMaxTokens, NetworkAudit: evalNetworkAudit, Judge: evalJudge,
+			PromptLang: evalPromptLang, DistractorPack: evalDistractors, Chaos: evalChaosProfile,
+			PromptVariants: evalPromptVariants, VariantSeed: evalVariantSeed, Seed: evalSeed, AgentMemory: evalAgentMemoryMode,
+			TestVisibility: evalTestVisibility, Upload: evalUpload,
+			RepairAttempts: evalRepairAttempts, AttemptFeedback: evalAttemptFeedback,
 		}
 
 		// Track if we're resuming a previous run.
@@ -489,6 +717,9 @@ Examples:
 				return fmt.Errorf("loading resume config: %w", err)
 			}
 			applyRunConfig(runCfg)
+			if err := resolveTestVisibilityFlags(); err != nil {
+				return err
+			}
 			evalOutputDir = evalResume
 			isResuming = true
 
@@ -496,9 +727,16 @@ Examples:
 			shared = SharedConfig{
 				Tier: evalTier, Difficulty: evalDifficulty, Lang: evalLang,
 				Tasks: evalTasks, Timeout: evalTimeout, Parallel: evalParallel,
+				Filter: evalFilter, Exclude: evalExclude, Tags: evalTags,
 				KeepWorkspaces: evalKeepWorkspaces, UseMCPTools: evalUseMCPTools,
-				UseSkills: evalUseSkills, DisableMCP: evalDisableMCP, NoSandbox: evalNoSandbox,
-				Legacy: evalLegacy, DryRun: evalDryRun,
+				UseSkills: evalUseSkills, SkillPacks: evalSkillPacks, DisableMCP: evalDisableMCP, MCPServers: evalMCPServers,
+				Temperature: evalTemperature, TopP: evalTopP, MinP: evalMinP,
+				NoSandbox: evalNoSandbox, Legacy: evalLegacy, DryRun: evalDryRun,
+				MaxTurns: evalMaxTurns, MaxToolCalls: evalMaxToolCalls, MaxTokens: evalMaxTokens, NetworkAudit: evalNetworkAudit, Judge: evalJudge,
+				PromptLang: evalPromptLang, DistractorPack: evalDistractors, Chaos: evalChaosProfile,
+				PromptVariants: evalPromptVariants, VariantSeed: evalVariantSeed, Seed: evalSeed, AgentMemory: evalAgentMemoryMode,
+				TestVisibility: evalTestVisibility, Upload: evalUpload,
+				RepairAttempts: evalRepairAttempts, AttemptFeedback: evalAttemptFeedback,
 			}
 
 			completedTasks, err = findCompletedTasks(evalOutputDir)
@@ -515,6 +753,7 @@ Examples:
 				previousExternalFailures = prevSummary.ExternalFailures
 				timestamp = prevSummary.Timestamp
 			}
+			previousResults = withTaskResults(previousResults, evalOutputDir)
 
 			// Load previous attestation to preserve hashes of tasks whose workspaces are gone.
 			prevAttestation, err = loadPreviousAttestation(evalOutputDir)
@@ -527,6 +766,11 @@ Examples:
 			timestamp = time.Now().Format("2006-01-02T150405")
 		}
 
+		// [sampling] reasoning_effort is the default for every agent.
+		if evalReasoning == "" {
+			evalReasoning = cfg.Sampling.ReasoningEffort
+		}
+
 		// Parse comma-separated agent/model/reasoning for multi-agent support.
 		agents := strings.Split(evalAgent, ",")
 		for i := range agents {
@@ -540,13 +784,35 @@ Examples:
 		if err != nil {
 			return err
 		}
+		families, err := broadcastOrSplit(evalModelFamily, len(agents), "model-family")
+		if err != nil {
+			return err
+		}
+		params, err := broadcastOrSplit(evalModelParams, len(agents), "model-params")
+		if err != nil {
+			return err
+		}
+		for _, p := range params {
+			if _, err := parseModelParams(p); err != nil {
+				return err
+			}
+		}
+		quants, err := broadcastOrSplit(evalModelQuant, len(agents), "model-quant")
+		if err != nil {
+			return err
+		}
 
 		var specs []RunSpec
 		for i := range agents {
 			specs = append(specs, RunSpec{
 				Agent: agents[i], Model: models[i], Reasoning: reasonings[i],
+				ModelFamily: families[i], ModelParams: params[i], ModelQuant: quants[i],
 			})
 		}
+		specs, err = expandToolchainMatrix(specs, evalToolchains)
+		if err != nil {
+			return err
+		}
 		isMultiRun := len(specs) > 1 || evalRepeat > 1
 
 		// Dry-run mode doesn't require agent to be installed.
@@ -560,26 +826,18 @@ Examples:
 					available := strings.Join(cfg.ListAgents(), ", ")
 					return fmt.Errorf("unknown agent: %s (available: %s)", spec.Agent, available)
 				}
+				if evalDistribute != "" {
+					continue // The agent runs on the workers.
+				}
 				if _, err := exec.LookPath(agentCfg.Command); err != nil {
 					return fmt.Errorf("agent %q binary %q not found in PATH", spec.Agent, agentCfg.Command)
 				}
 			}
 		}
 
-		r, err := runner.NewRunner(cfg, tasks.FS, tasksDir, logger)
-		if err != nil {
-			return err
-		}
-		defer func() { _ = r.Close() }()
-
-		if shared.Legacy {
-			r.LegacyHiddenTests = true
-			logger.Info("legacy mode enabled: hidden tests exposed to agent (pre-v1.6.0 behavior)")
-		}
-
 		// If the user specified another selector, default tier should not hide tasks.
 		tierChanged := cmd.Flags().Changed("tier")
-		if !tierChanged && (shared.Lang != "" || shared.Tasks != "" || shared.Difficulty != "") {
+		if !tierChanged && (shared.Lang != "" || shared.Tasks != "" || shared.Difficulty != "" || shared.Filter != "" || shared.Tags != "") {
 			shared.Tier = "all"
 			evalTier = "all"
 		}
@@ -591,8 +849,9 @@ Examples:
 			return fmt.Errorf("invalid --tier %q (valid: core, extended, all)", shared.Tier)
 		}
 
-		// Get tasks to run
-		allTasks, err := r.ListTasks()
+		// Get tasks to run. Listing them does not need Docker, so a dry run
+		// works without it.
+		allTasks, err := task.NewLoader(tasks.FS, tasksDir).LoadAll()
 		if err != nil {
 			return fmt.Errorf("listing tasks: %w", err)
 		}
@@ -664,52 +923,62 @@ Examples:
 			allTasks = filtered
 		}
 
+		// Filter by ID glob and tag if specified
+		taskFilter, err := task.ParseFilter(evalFilter, evalExclude, evalTags)
+		if err != nil {
+			return err
+		}
+		allTasks = taskFilter.Apply(allTasks)
+
+		// Translated prompts only cover tasks that ship the translation.
+		var untranslated []string
+		allTasks, untranslated = filterTranslatedTasks(allTasks, shared.PromptLang)
+		if len(untranslated) > 0 {
+			fmt.Printf(" Skipping %d task(s) without a %q translation: %s\n", len(untranslated), shared.PromptLang, strings.Join(untranslated, ", "))
+		}
+
 		if len(allTasks) == 0 {
 			return fmt.Errorf("no tasks match the specified filters")
 		}
 
 		// Dry-run mode: print what would be executed and exit
 		if shared.DryRun {
-			fmt.Println()
-			fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
-			fmt.Println(" SANITY HARNESS - Dry Run")
-			fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
-			fmt.Println()
-			for _, spec := range specs {
-				if spec.Agent != "" {
-					fmt.Printf(" Agent:      %s\n", spec.Agent)
-				}
-				if spec.Model != "" {
-					fmt.Printf(" Model:      %s\n", spec.Model)
-				}
-				if spec.Reasoning != "" {
-					fmt.Printf(" Reasoning:  %s\n", spec.Reasoning)
-				}
+			outputDir := evalOutputDir
+			if outputDir == "" {
+				outputDir = defaultEvalOutputDir(specs, timestamp, isMultiRun)
 			}
-			if shared.Tier != "" {
-				fmt.Printf(" Tier:       %s\n", shared.Tier)
-			}
-			if shared.Difficulty != "" {
-				fmt.Printf(" Difficulty: %s\n", shared.Difficulty)
+			writeDryRunPlan(os.Stdout, dryRunPlan{
+				Specs: specs, Shared: shared, Tasks: allTasks, Repeat: evalRepeat, OutputDir: outputDir,
+			})
+			return nil
+		}
+
+		// Distributed mode: workers run the tasks, so nothing runs here.
+		if evalDistribute != "" {
+			if err := checkDistributable(isMultiRun, shared); err != nil {
+				return err
 			}
-			if evalRepeat > 1 {
-				fmt.Printf(" Repeat:     %d\n", evalRepeat)
+			workers, err := loadDistWorkers(evalDistribute)
+			if err != nil {
+				return err
 			}
-			fmt.Printf(" Tasks:      %d\n", len(allTasks))
-			fmt.Println()
-			fmt.Println(" Tasks that would be executed:")
-			fmt.Println("─────────────────────────────────────────────────────────────")
-			for i, t := range allTasks {
-				timeout := shared.Timeout
-				if t.AgentTimeout > 0 {
-					timeout = t.AgentTimeout
-				}
-				fmt.Printf(" %3d. %-35s [%s, %s, %ds]\n",
-					i+1, t.ID(), t.Tier, t.Difficulty, timeout)
+			if evalOutputDir == "" {
+				evalOutputDir = defaultEvalOutputDir(specs, timestamp, false)
 			}
-			fmt.Println("─────────────────────────────────────────────────────────────")
-			fmt.Println()
-			return nil
+			interruptCtx, interruptCancel := setupInterruptHandler()
+			defer interruptCancel()
+			return runDistributed(interruptCtx, workers, forwardedEvalArgs(cmd.Flags(), shared.Timeout), allTasks, evalOutputDir, timestamp)
+		}
+
+		r, err := runner.NewRunner(cfg, tasks.FS, tasksDir, logger)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = r.Close() }()
+
+		if shared.Legacy {
+			r.LegacyHiddenTests = true
+			logger.Info("legacy mode enabled: hidden tests exposed to agent (pre-v1.6.0 behavior)")
 		}
 
 		// Detect sandbox availability.
@@ -731,14 +1000,9 @@ Examples:
 
 		if isMultiRun {
 			// Multi-run mode: create umbrella directory and orchestrate runs.
-			var umbrellaDir string
-			if evalOutputDir != "" {
-				umbrellaDir = evalOutputDir
-			} else if len(specs) == 1 {
-				// Single-agent repeat: use normal naming.
-				umbrellaDir = filepath.Join("eval-results", fmt.Sprintf("%s-%s", timestamp, specs[0].Agent))
-			} else {
-				umbrellaDir = filepath.Join("eval-results", fmt.Sprintf("multi-%s", timestamp))
+			umbrellaDir := evalOutputDir
+			if umbrellaDir == "" {
+				umbrellaDir = defaultEvalOutputDir(specs, timestamp, true)
 			}
 			if err := os.MkdirAll(umbrellaDir, 0o755); err != nil {
 				return fmt.Errorf("creating umbrella directory: %w", err)
@@ -770,25 +1034,8 @@ Examples:
 				}
 			}
 
-			// Generate comparison if multiple specs.
-			if len(specs) > 1 {
-				var summaries []EvalSummary
-				for _, rr := range allSummaries {
-					if rr.summary != nil {
-						summaries = append(summaries, *rr.summary)
-					}
-				}
-				if len(summaries) > 1 {
-					comparison := generateComparison(summaries)
-					writeComparisonJSON(umbrellaDir, comparison)
-					writeComparisonMarkdown(umbrellaDir, comparison)
-				}
-			}
-
-			// Generate repeat stats if repeating.
-			if evalRepeat > 1 {
-				writeRepeatStats(umbrellaDir, specs, allSummaries, evalRepeat)
-			}
+			// Generate comparison, repeat, and toolchain stats.
+			writeMultiRunOutputs(umbrellaDir, MultiRunConfig{Specs: specs, Repeat: evalRepeat}, allSummaries)
 
 			fmt.Printf("\n Multi-run results saved to: %s\n\n", umbrellaDir)
 			return nil
@@ -799,7 +1046,7 @@ Examples:
 
 		// Create output directory.
 		if evalOutputDir == "" {
-			evalOutputDir = filepath.Join("eval-results", fmt.Sprintf("%s-%s", timestamp, spec.Agent))
+			evalOutputDir = defaultEvalOutputDir(specs, timestamp, false)
 		}
 
 		_, _, err = evalRunSingle(
@@ -833,11 +1080,92 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 	evalAgent = spec.Agent
 	evalModel = spec.Model
 	evalReasoning = spec.Reasoning
+	evalModelFamily = spec.ModelFamily
+	evalModelParams = spec.ModelParams
+	evalModelQuant = spec.ModelQuant
+	evalToolchain = spec.Toolchain
+	toolchainImages, restoreImages, err := applyToolchain(spec.Toolchain)
+	if err != nil {
+		return nil, nil, err
+	}
+	defer restoreImages()
 	evalUseMCPTools = shared.UseMCPTools
 	evalUseSkills = shared.UseSkills
 	evalDisableMCP = shared.DisableMCP
 	evalLegacy = shared.Legacy
+	evalTestVisibility = shared.TestVisibility
+	if err := resolveTestVisibilityFlags(); err != nil {
+		return nil, nil, err
+	}
+	evalRepairAttempts = shared.RepairAttempts
+	evalAttemptFeedback = shared.AttemptFeedback
+	if err := resolveRepairFlags(); err != nil {
+		return nil, nil, err
+	}
 	evalKeepWorkspaces = shared.KeepWorkspaces
+	evalMaxTurns = shared.MaxTurns
+	evalMaxToolCalls = shared.MaxToolCalls
+	evalMaxTokens = shared.MaxTokens
+	evalNetworkAudit = shared.NetworkAudit
+	if evalNetworkAudit {
+		if err := checkNetworkAudit(); err != nil {
+			return nil, nil, err
+		}
+	}
+	if cfg != nil && cfg.Encryption.Enabled() {
+		if err := checkEncryption(); err != nil {
+			return nil, nil, err
+		}
+	}
+	evalPromptLang = shared.PromptLang
+	evalJudge = shared.Judge
+	if evalJudge {
+		if err := cfg.Judge.Ready(); err != nil {
+			return nil, nil, fmt.Errorf("--judge: %w", err)
+		}
+	}
+	packs, err := loadSkillPacks(shared.SkillPacks)
+	if err != nil {
+		return nil, nil, err
+	}
+	evalSkillPackSet = packs
+	mcpServers, err := resolveMCPServers(shared.MCPServers, spec.Agent, cfg.GetAgent(spec.Agent), shared.DisableMCP)
+	if err != nil {
+		return nil, nil, err
+	}
+	evalMCPServerSet = mcpServers
+	sampling, err := resolveSampling(shared, spec.Agent, cfg.GetAgent(spec.Agent))
+	if err != nil {
+		return nil, nil, err
+	}
+	evalSamplingSet = sampling
+	distractors, err := loadDistractorPack(shared.DistractorPack)
+	if err != nil {
+		return nil, nil, err
+	}
+	evalDistractorPack = distractors
+	chaos, err := resolveChaos(shared.Chaos)
+	if err != nil {
+		return nil, nil, err
+	}
+	evalChaos = chaos
+	evalPromptVariants = shared.PromptVariants
+	evalVariantSeed = shared.VariantSeed
+	evalSeed = shared.Seed
+	evalAgentMemoryMode = shared.AgentMemory
+	memory, err := newAgentMemory(shared.AgentMemory, spec.Agent, cfg.GetAgent(spec.Agent))
+	if err != nil {
+		return nil, nil, err
+	}
+	defer memory.close()
+	evalAgentMemory = memory
+	evalHookRun = HookEvent{
+		Agent:     spec.Agent,
+		Model:     spec.Model,
+		Reasoning: spec.Reasoning,
+		Timestamp: timestamp,
+		OutputDir: outputDir,
+	}
 
 	// Create output directory.
 	if err := os.MkdirAll(outputDir, 0755); err != nil {
@@ -863,6 +1191,33 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		}
 	}
 
+	// Artifacts upload in the background as tasks finish; a resumed run
+	// first catches up on anything that is not in the bucket yet.
+	evalUpload = shared.Upload
+	uploader, err := newRunUploader(shared.Upload, outputDir)
+	if err != nil {
+		return nil, nil, err
+	}
+	uploader.Sync(outputDir)
+
+	// Assign prompt variants over the full task list, so a resumed run gives
+	// the remaining tasks the variants they would have had.
+	runKey := variantRunKey(outputDir)
+	if shared.Seed != 0 {
+		runKey = seededRunKey(outputDir)
+	}
+	variants, err := resolvePromptVariants(shared.PromptVariants, shared.VariantSeed, runKey, allTasks)
+	if err != nil {
+		return nil, nil, err
+	}
+	evalVariantPlan = variants
+
+	// A seeded run shuffles the tasks it runs; a resumed run runs what is
+	// left in the order it would have.
+	if shared.Seed != 0 {
+		tasksToRun = shuffleTasks(tasksToRun, shared.Seed, runKey)
+	}
+
 	var wasInterrupted bool
 
 	// Print header
@@ -879,26 +1234,88 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 	if spec.Model != "" {
 		fmt.Printf(" Model:   %s\n", spec.Model)
 	}
+	if spec.Toolchain != "" {
+		fmt.Printf(" Toolchain: %s\n", toolchainLabel(spec.Toolchain))
+	}
 	if shared.Tier != "" {
 		fmt.Printf(" Tier:    %s\n", shared.Tier)
 	}
 	if shared.Difficulty != "" {
 		fmt.Printf(" Difficulty: %s\n", shared.Difficulty)
 	}
+	if shared.Filter != "" {
+		fmt.Printf(" Filter:  %s\n", shared.Filter)
+	}
+	if shared.Tags != "" {
+		fmt.Printf(" Tags:    %s\n", shared.Tags)
+	}
+	if shared.Exclude != "" {
+		fmt.Printf(" Exclude: %s\n", shared.Exclude)
+	}
 	if shared.Parallel > 1 {
 		fmt.Printf(" Parallel: %d\n", shared.Parallel)
 	}
 	if evalSandboxActive {
 		fmt.Println(" Sandbox: enabled (bwrap)")
 	}
+	if len(packs) > 0 {
+		fmt.Printf(" Skill packs: %s\n", strings.Join(skillPackNames(packs), ", "))
+	}
+	if distractors != nil {
+		fmt.Printf(" Distractors: %s (%d files)\n", distractors.Name, len(distractors.Files))
+	}
+	if chaos != nil {
+		fmt.Printf(" Chaos: %s\n", chaos)
+	}
+	if evalNetworkAudit {
+		fmt.Println(" Network audit: enabled (strace)")
+	}
+	if evalJudge {
+		fmt.Printf(" Judge:   %s\n", cfg.Judge.Model)
+	}
+	if evalPromptLang != "" {
+		fmt.Printf(" Prompt:  %s\n", evalPromptLang)
+	}
+	if shared.Seed != 0 {
+		fmt.Printf(" Seed:    %d\n", shared.Seed)
+	}
+	if variants != nil {
+		fmt.Printf(" Variants: %s (seed %d)\n", strings.Join(variants.names, ", "), variants.seed)
+	}
+	if memory != nil {
+		fmt.Printf(" Memory:  %s\n", agentMemoryLabel(memory.scope))
+	}
+	if evalTestVisibility != config.TestVisibilityVisibleOnly {
+		fmt.Printf(" Tests:   %s\n", testVisibilityLabel(evalTestVisibility))
+	}
+	if evalRepairAttempts > 0 {
+		fmt.Printf(" Repairs: up to %d, %s feedback\n", evalRepairAttempts, evalAttemptFeedback)
+	}
+	if limits := resolveActivityLimits(); limits.enabled() {
+		fmt.Printf(" Limits:  %s per attempt\n", limits)
+		agentCfg := cfg.GetAgent(spec.Agent)
+		if limits.MaxTurns > 0 && agentCfg != nil && agentCfg.TurnPattern == "" {
+			logger.Warn("max turns set but agent has no turn_pattern; turns are not limited", "agent", spec.Agent)
+		}
+		if limits.MaxTokens > 0 && adapterFor(spec.Agent, agentCfg).usage == nil {
+			logger.Warn("max tokens set but agent reports no token usage; tokens are not limited", "agent", spec.Agent)
+		}
+	}
 	if isResuming {
 		fmt.Printf(" Tasks:   %d remaining of %d total\n", len(tasksToRun), totalTaskCount)
 	} else {
 		fmt.Printf(" Tasks:   %d\n", len(tasksToRun))
 	}
 	fmt.Printf(" Output:  %s\n", outputDir)
+	if uploader != nil {
+		fmt.Printf(" Upload:  %s\n", uploader.dest)
+	}
 	fmt.Println()
 
+	if err := runHooks(interruptCtx, HookEvent{Event: hookPreRun, Tasks: taskIDsOf(tasksToRun)}); err != nil {
+		return nil, nil, err
+	}
+
 	// Run tasks
 	results := make([]EvalResult, 0, len(tasksToRun))
 	passed, failed := 0, 0
@@ -937,53 +1354,74 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		parallel = 1
 	}
 
+	// Non-verbose terminal runs get a single live status line instead of banners.
+	liveProgress := !verbose && !evalNoProgress && isTerminal(rawStdout)
+	var durationHistory map[string]float64
+	if liveProgress {
+		durationHistory = loadTaskDurationHistory(defaultEvalResultsDir, spec.Agent)
+	}
+	evalDurationStats = loadDurationStats(defaultEvalResultsDir)
+	var progress evalReporter
+	if evalTUIMode && isTerminal(rawStdout) {
+		tui := newEvalTUI(os.Stdout, toolchainConfigKey(spec), outputDir, tasksToRun)
+		evalAttemptHook = tui.Attempt
+		defer func() { evalAttemptHook = nil }()
+		progress = tui
+	} else {
+		progress = newEvalProgress(os.Stdout, taskIDsOf(tasksToRun), parallel, liveProgress, durationHistory)
+	}
+	defer progress.Stop()
+
 	if parallel == 1 { //nolint:nestif // Sequential execution loop with deeply interleaved interrupt/quota/progress handling.
 		consecutiveQuotaExhausted := 0
-		for i, t := range tasksToRun {
+		for _, t := range tasksToRun {
 			// Check for interrupt before starting next task.
 			if checkInterrupted(interruptCtx) {
 				wasInterrupted = true
-				fmt.Println("\n\033[33m⚠ Interrupt received. Saving partial results...\033[0m")
+				progress.Stop()
+				fmt.Println("\n\033[33m⚠ Saving partial results...\033[0m")
 				break
 			}
 
-			fmt.Println("─────────────────────────────────────────────────────────────")
-			fmt.Printf(" [%d/%d] %s\n", i+1, len(tasksToRun), t.ID())
-			fmt.Println("─────────────────────────────────────────────────────────────")
-
+			progress.Start(t.ID())
 			result := runTaskWithAgent(interruptCtx, r, t, spec.Agent, spec.Model, outputDir, shared.Timeout)
+			annotateDurationAnomaly(&result, evalDurationStats)
+
+			// Tasks cut short by an interrupt are left for --resume; the
+			// check at the top of the loop stops the run.
+			if result.FailureClass == FailureClassInterrupted {
+				removeTaskArtifactsForResume(outputDir, result)
+				continue
+			}
 
 			// External failures are excluded from results so they can be resumed later.
 			if isResumableExternalFailure(result) {
 				recordExternalFailure(result)
-				fmt.Printf(" ⚠ %s — will be skipped (resumable)\n", externalFailureLabel(result.FailureClass))
+				progress.Skip(result)
 				resumableFailedTasks = append(resumableFailedTasks, fmt.Sprintf("%s [%s]", t.ID(), result.FailureClass))
 				removeTaskArtifactsForResume(outputDir, result)
 				if result.FailureClass == FailureClassQuotaExhausted {
 					consecutiveQuotaExhausted++
 					if consecutiveQuotaExhausted >= quotaExhaustedStopThreshold {
 						wasInterrupted = true
+						progress.Stop()
 						fmt.Printf("\n\033[33m⚠ Quota exhausted for %d consecutive tasks. Stopping early to allow resume.\033[0m\n", consecutiveQuotaExhausted)
 						break
 					}
 				} else {
 					consecutiveQuotaExhausted = 0
 				}
-				fmt.Println()
 				continue
 			}
 
 			results = append(results, result)
+			saveTaskResult(outputDir, result)
+			progress.Finish(result)
 
 			if result.Passed {
-				fmt.Printf(" ✓ PASSED (%.2fs)\n", result.Duration)
 				passed++
 				consecutiveQuotaExhausted = 0 // Reset counter on success
 			} else {
-				fmt.Printf(" ✗ FAILED (%.2fs)\n", result.Duration)
-				if result.Error != "" {
-					fmt.Printf("   Error: %s\n", result.Error)
-				}
 				failed++
 
 				// Track consecutive quota exhaustion
@@ -991,6 +1429,7 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 					consecutiveQuotaExhausted++
 					if consecutiveQuotaExhausted >= quotaExhaustedStopThreshold {
 						wasInterrupted = true
+						progress.Stop()
 						fmt.Printf("\n\033[33m⚠ Quota exhausted for %d consecutive tasks. Stopping early to allow resume.\033[0m\n", consecutiveQuotaExhausted)
 						break
 					}
@@ -1005,8 +1444,7 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 			if !shared.KeepWorkspaces && result.WorkspaceDir != "" {
 				cleanupWorkspaceFiles(result.WorkspaceDir)
 			}
-
-			fmt.Println()
+			uploader.Sync(result.WorkspaceDir)
 		}
 	} else {
 		type job struct {
@@ -1028,7 +1466,9 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 			go func() {
 				defer wg.Done()
 				for j := range jobs {
+					progress.Start(j.t.ID())
 					res := runTaskWithAgent(interruptCtx, r, j.t, spec.Agent, spec.Model, outputDir, shared.Timeout)
+					annotateDurationAnomaly(&res, evalDurationStats)
 					jobResults <- jobResult{idx: j.idx, r: res}
 				}
 			}()
@@ -1053,16 +1493,17 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		}()
 
 		collected := make([]EvalResult, len(tasksToRun))
-		seen := 0
 		consecutiveQuotaExhausted := 0
 	collectLoop:
 		for jr := range jobResults {
-			seen++
-
-			// External failures are excluded from results so they can be resumed later.
-			if isResumableExternalFailure(jr.r) {
+			// Tasks cut short by an interrupt are left for --resume, and
+			// external failures are excluded from results so they can be
+			// resumed later.
+			if jr.r.FailureClass == FailureClassInterrupted {
+				removeTaskArtifactsForResume(outputDir, jr.r)
+			} else if isResumableExternalFailure(jr.r) {
 				recordExternalFailure(jr.r)
-				fmt.Printf(" [%d/%d] %s ⚠ %s — will be skipped (resumable)\n", seen, len(tasksToRun), jr.r.Task, externalFailureLabel(jr.r.FailureClass))
+				progress.Skip(jr.r)
 				resumableFailedTasks = append(resumableFailedTasks, fmt.Sprintf("%s [%s]", jr.r.Task, jr.r.FailureClass))
 				removeTaskArtifactsForResume(outputDir, jr.r)
 				if jr.r.FailureClass == FailureClassQuotaExhausted {
@@ -1072,15 +1513,8 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 				}
 			} else {
 				collected[jr.idx] = jr.r
-
-				status := "FAILED"
-				if jr.r.Passed {
-					status = "PASSED"
-				}
-				fmt.Printf(" [%d/%d] %s %s (%.2fs)\n", seen, len(tasksToRun), jr.r.Task, status, jr.r.Duration)
-				if !jr.r.Passed && jr.r.Error != "" {
-					fmt.Printf("   Error: %s\n", jr.r.Error)
-				}
+				saveTaskResult(outputDir, jr.r)
+				progress.Finish(jr.r)
 
 				if jr.r.Passed {
 					passed++
@@ -1098,6 +1532,7 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 				if !shared.KeepWorkspaces && jr.r.WorkspaceDir != "" {
 					cleanupWorkspaceFiles(jr.r.WorkspaceDir)
 				}
+				uploader.Sync(jr.r.WorkspaceDir)
 			}
 
 			// Check for interrupt after each result.
@@ -1112,23 +1547,27 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 
 			if shouldStop {
 				wasInterrupted = true
+				progress.Stop()
 				fmt.Printf("\n\033[33m⚠ %s. Waiting for in-flight tasks...\033[0m\n", stopReason)
 				close(stopSending)
 				// Drain remaining results from in-flight tasks.
 				for jr := range jobResults {
-					if isResumableExternalFailure(jr.r) {
+					if jr.r.FailureClass == FailureClassInterrupted {
+						removeTaskArtifactsForResume(outputDir, jr.r)
+					} else if isResumableExternalFailure(jr.r) {
 						recordExternalFailure(jr.r)
 						resumableFailedTasks = append(resumableFailedTasks, fmt.Sprintf("%s [%s]", jr.r.Task, jr.r.FailureClass))
 						removeTaskArtifactsForResume(outputDir, jr.r)
 					} else {
 						collected[jr.idx] = jr.r
+						saveTaskResult(outputDir, jr.r)
 						if jr.r.Passed {
 							passed++
 						} else {
 							failed++
 						}
 						if !shared.KeepWorkspaces && jr.r.WorkspaceDir != "" {
-							_ = os.RemoveAll(jr.r.WorkspaceDir)
+							cleanupWorkspaceFiles(jr.r.WorkspaceDir)
 						}
 					}
 				}
@@ -1143,6 +1582,8 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		}
 	}
 
+	progress.Stop()
+
 	// If resuming, merge with previous results.
 	if isResuming && len(previousResults) > 0 {
 		// Build a set of task IDs from new results.
@@ -1193,7 +1634,7 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 	// these fields (they were never set due to a defer/named-return bug).
 	taskWeights := make(map[string]task.Weight)
 	for _, t := range allTasks {
-		taskWeights[t.ID()] = task.ComputeWeight(t)
+		taskWeights[t.ID()] = taskWeight(t)
 	}
 	for i := range results {
 		r := &results[i]
@@ -1202,19 +1643,7 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 			r.Weight = w.Base
 		}
 		if r.FailureClass == "" {
-			r.FailureClass = FailureClassNone
-			switch {
-			case strings.Contains(r.Error, "modified task files"):
-				r.FailureClass = FailureClassIntegrity
-			case strings.Contains(r.Error, "infra failure"):
-				r.FailureClass = FailureClassInfra
-			case strings.Contains(strings.ToLower(r.Error), "timed out"):
-				r.FailureClass = FailureClassValidationTimeout
-			case r.Error != "":
-				r.FailureClass = FailureClassValidationError
-			case r.QuotaExhausted:
-				r.FailureClass = FailureClassQuotaExhausted
-			}
+			r.FailureClass = inferFailureClass(r.Error, r.QuotaExhausted)
 		}
 		if !r.SelfTestCommandsConfident && r.SelfTestCommands == 0 {
 			r.SelfTestCommandsConfident = true
@@ -1222,7 +1651,7 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		if !r.OutOfWorkspaceReadsConfident && r.OutOfWorkspaceReadAttempts == 0 {
 			r.OutOfWorkspaceReadsConfident = true
 		}
-		r.Status = task.DetermineStatus(r.Passed, r.AgentTimedOut, r.Error)
+		r.Status = evalResultStatus(r)
 		r.WeightedScore = task.ScoreResult(r.Passed, r.AgentTimedOut, r.Error, w)
 	}
 
@@ -1249,9 +1678,195 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		fmt.Printf(" Skipped:   %d (external auth/quota/infra)\n", len(externalFailures))
 	}
 	fmt.Printf(" Pass Rate: %.1f%%\n", passRate)
+	if n := countDurationAnomalies(results); n > 0 {
+		fmt.Printf(" \033[33m⚠ %d task(s) took far more or less time than past runs; see Duration Anomalies in report.md\033[0m\n", n)
+	}
 	fmt.Println()
 
+	var judgeModel string
+	if shared.Judge && cfg != nil {
+		judgeModel = cfg.Judge.Model
+	}
+
+	// Default model to "unknown" if not specified
+	model := spec.Model
+	if model == "" {
+		model = "unknown"
+	}
+
 	// Save summary
+	summary := EvalSummary{
+		SchemaVersion:    summarySchemaVersion,
+		Agent:            spec.Agent,
+		Model:            model,
+		ModelFamily:      resolveModelFamily(spec.ModelFamily, spec.Model),
+		ModelParamsB:     resolveModelParams(spec.ModelParams, spec.Model),
+		ModelQuant:       resolveModelQuant(spec.ModelQuant, spec.Model),
+		Reasoning:        spec.Reasoning,
+		Timestamp:        timestamp,
+		Tier:             shared.Tier,
+		Difficulty:       shared.Difficulty,
+		Seed:             shared.Seed,
+		Timeout:          shared.Timeout,
+		Parallel:         parallel,
+		NetworkAudit:     shared.NetworkAudit,
+		PromptLang:       shared.PromptLang,
+		PromptExperiment: variants.experiment(),
+		AgentMemory:      cmp.Or(shared.AgentMemory, config.AgentMemoryHost),
+		TestVisibility:   evalTestVisibility,
+		JudgeModel:       judgeModel,
+		UseMCPTools:      shared.UseMCPTools,
+		UseSkills:        shared.UseSkills,
+		SkillPacks:       packs,
+		DistractorPack:   distractors,
+		Chaos:            chaos,
+		DisableMCP:       shared.DisableMCP,
+		MCPServers:       mcpServerNames(mcpServers),
+		Sampling:         sampling.orNil(),
+		Sandbox:          evalSandboxActive,
+		Legacy:           shared.Legacy,
+	}
+	if uploader != nil {
+		summary.UploadURL = uploader.dest
+	}
+	summary.Toolchain = spec.Toolchain
+	summary.ToolchainImages = toolchainImages
+	if evalRepairAttempts > 0 {
+		summary.RepairAttempts = evalRepairAttempts
+		summary.AttemptFeedback = evalAttemptFeedback
+	}
+	summary.EncryptedArtifacts = cfg != nil && cfg.Encryption.Enabled()
+	summarizeResults(&summary, results, externalFailures)
+	if wasInterrupted {
+		summary.Interrupted = true
+		summary.RemainingTasks = max(len(allTasks)-len(results)-len(externalFailures), 0)
+	}
+
+	summaryPath := filepath.Join(outputDir, "summary.json")
+	summaryData, _ := json.MarshalIndent(summary, "", "  ")
+	if err := os.WriteFile(summaryPath, summaryData, 0644); err != nil {
+		logger.Warn("failed to save summary", "error", err)
+	} else {
+		fmt.Printf(" Results saved to: %s\n", summaryPath)
+	}
+
+	// Generate attestation for verification
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	var prevTasks map[string]AttestationTask
+	if prevAttestation != nil {
+		prevTasks = prevAttestation.Tasks
+	}
+	// Build set of tasks that were newly run in this session
+	newlyRunTasks := make(map[string]bool)
+	for _, t := range tasksToRun {
+		newlyRunTasks[t.ID()] = true
+	}
+	attestation, err := generateAttestation(
+		spec.Agent, spec.Model, timestamp, summary.Duration,
+		results, outputDir, loader, allTasks, newlyRunTasks, prevTasks,
+	)
+	if err != nil {
+		logger.Warn("failed to generate attestation", "error", err)
+	} else {
+		attestationPath := filepath.Join(outputDir, "attestation.json")
+		attestationData, _ := json.MarshalIndent(attestation, "", "  ")
+		if err := os.WriteFile(attestationPath, attestationData, 0644); err != nil {
+			logger.Warn("failed to save attestation", "error", err)
+		} else {
+			fmt.Printf(" Attestation saved to: %s\n", attestationPath)
+		}
+	}
+
+	// Generate human-readable report.md
+	reportMd := generateEvalReport(summary, attestation)
+	reportPath := filepath.Join(outputDir, "report.md")
+	if err := os.WriteFile(reportPath, []byte(reportMd), 0644); err != nil {
+		logger.Warn("failed to save report", "error", err)
+	} else {
+		fmt.Printf(" Report saved to: %s\n", reportPath)
+	}
+
+	// Generate leaderboard submission file
+	submission := generateLeaderboardSubmission(summary, attestation)
+	submissionData, _ := json.MarshalIndent(submission, "", "  ")
+	submissionPath := filepath.Join(outputDir, "submission.json")
+	if err := os.WriteFile(submissionPath, submissionData, 0644); err != nil {
+		logger.Warn("failed to save submission", "error", err)
+	} else {
+		fmt.Printf(" Submission saved to: %s\n", submissionPath)
+	}
+
+	// The last upload runs before post_run hooks and retention, which may
+	// move or delete artifacts.
+	if uploader != nil {
+		uploaded, uploadFailures := uploader.Close()
+		if uploadFailures > 0 {
+			fmt.Printf(" \033[33m⚠ %d artifact(s) failed to upload to %s\033[0m\n", uploadFailures, uploader.dest)
+		} else {
+			fmt.Printf(" Artifacts uploaded to: %s (%d files)\n", uploader.dest, uploaded)
+		}
+	}
+	sendNotifications(context.WithoutCancel(interruptCtx), runNotification{
+		Summary:     &summary,
+		OutputDir:   outputDir,
+		Interrupted: wasInterrupted,
+	})
+
+	fmt.Println()
+
+	// Hooks still run after an interrupt so partial results can be shipped.
+	warnOnHookError(runHooks(context.WithoutCancel(interruptCtx), HookEvent{
+		Event:       hookPostRun,
+		Summary:     &summary,
+		Interrupted: wasInterrupted,
+	}))
+	// Retention runs after post_run hooks so they can ship artifacts first.
+	applyRetention(outputDir)
+
+	// Report resumable external failures and provide resume command.
+	if len(resumableFailedTasks) > 0 {
+		fmt.Println("\033[33m━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\033[0m")
+		fmt.Printf("\033[33m ⚠ %d task(s) skipped due to external failures (auth/quota/infra):\033[0m\n", len(resumableFailedTasks))
+		for _, t := range resumableFailedTasks {
+			fmt.Printf("   • %s\n", t)
+		}
+		fmt.Println()
+		fmt.Println(" These tasks were not counted in the results above.")
+		fmt.Println(" To retry them, run:")
+		fmt.Printf("   ./sanity eval --resume %s\n", outputDir)
+		fmt.Println("\033[33m━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\033[0m")
+		fmt.Println()
+	}
+
+	// If interrupted, print resume command.
+	if wasInterrupted {
+		printResumeCommand(outputDir)
+	}
+
+	return &summary, attestation, nil
+
+}
+
+// summarizeResults fills summary's results, counts, and aggregates from
+// results and externalFailures, leaving run metadata untouched. Results must
+// already carry their final status and weighted score.
+//
+//nolint:gocognit,gocyclo,maintidx // Flat accumulation over every per-result metric.
+func summarizeResults(summary *EvalSummary, results []EvalResult, externalFailures []ExternalFailure) {
+	passed, failed := 0, 0
+	for _, r := range results {
+		if r.Passed {
+			passed++
+		} else {
+			failed++
+		}
+	}
+	total := passed + failed
+	passRate := 0.0
+	if total > 0 {
+		passRate = float64(passed) / float64(total) * 100
+	}
+
 	// Aggregate stats
 	byLanguage := make(map[string]EvalAggregate)
 	byTier := make(map[string]EvalAggregate)
@@ -1260,7 +1875,26 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 	var totalDuration float64
 	var totalAgentTime float64
 	var totalValidateTime float64
+	var totalAgentCPUTime, totalValidateCPUTime float64
+	var totalAgentEnergy, totalValidateEnergy float64
+	var totalUsage *TokenUsage
 	var totalPromptChars int
+	var overBudgetTasks int
+	var budgetExhaustedTasks int
+	var taintedTasks int
+	var durationAnomalies int
+	var noOpTasks int
+	var likelyMemorizedTasks int
+	var tasksReadingDistractors, tasksEditingDistractors int
+	var chaosInjections int
+	var tasksWithNetwork int
+	var judgedTasks int
+	var totalJudgeScore float64
+	var benchmarkedTasks int
+	var benchmarksOverThreshold int
+	var followUpTasks, followUpsPassed, followUpsTotal int
+	var repairedTasks, unrepairedTasks int
+	var toolCalls map[string]int
 	var totalWeightedScore float64
 	var maxPossibleScore float64
 	var integrityViolations int
@@ -1271,6 +1905,8 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 	var totalSelfTestCommands int
 	var totalInfraRetries int
 	var totalAgentTimeoutRetries int
+	var totalValidationRetries int
+	var flakyPasses int
 	var agentTimeoutTasks int        // tasks that ultimately ended as a timeout
 	var agentTimeoutRetriedTasks int // subset of above that got at least one retry
 	var totalToolchainInstallAttempts int
@@ -1314,7 +1950,61 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		totalDuration += r.Duration
 		totalAgentTime += r.AgentTime
 		totalValidateTime += r.ValidateTime
+		totalAgentCPUTime += r.AgentCPUTime
+		totalValidateCPUTime += r.ValidateCPUTime
+		totalAgentEnergy += r.AgentEnergy
+		totalValidateEnergy += r.ValidateEnergy
+		totalUsage = addUsage(totalUsage, r.Usage)
 		totalPromptChars += r.PromptChars
+		if r.PromptBudget != nil && r.PromptBudget.OverBudget {
+			overBudgetTasks++
+		}
+		if r.BudgetExhausted {
+			budgetExhaustedTasks++
+		}
+		if r.Tainted {
+			taintedTasks++
+		}
+		if r.DurationAnomaly != "" {
+			durationAnomalies++
+		}
+		if r.FileActivity != nil && r.FileActivity.NoOp() {
+			noOpTasks++
+		}
+		if r.Plagiarism != nil && r.Plagiarism.Flagged {
+			likelyMemorizedTasks++
+		}
+		if r.Distractors != nil && len(r.Distractors.Read) > 0 {
+			tasksReadingDistractors++
+		}
+		if r.Distractors.Edited() {
+			tasksEditingDistractors++
+		}
+		chaosInjections += r.ChaosInjections
+		if r.Network != nil {
+			tasksWithNetwork++
+		}
+		if r.Benchmark != nil {
+			benchmarkedTasks++
+			if !r.Benchmark.WithinThresholds() {
+				benchmarksOverThreshold++
+			}
+		}
+		if r.FollowUpCount > 0 {
+			followUpTasks++
+			followUpsTotal += r.FollowUpCount
+			followUpsPassed += followUpsPassedIn(r)
+		}
+		switch {
+		case r.PassedOnAttempt > 1:
+			repairedTasks++
+		case len(r.Repairs) > 0:
+			unrepairedTasks++
+		}
+		if r.Judge != nil && r.Judge.Error == "" {
+			judgedTasks++
+			totalJudgeScore += r.Judge.Normalized()
+		}
 		totalWeightedScore += r.WeightedScore
 		maxPossibleScore += r.Weight
 		totalSelfTestCommands += r.SelfTestCommands
@@ -1322,6 +2012,7 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		totalOutOfWorkspaceReadAttempts += r.OutOfWorkspaceReadAttempts
 		totalToolchainSearchAttempts += r.ToolchainSearchAttempts
 		totalSkillsUsageSignals += r.SkillsUsageSignals
+		toolCalls = sumToolCalls(toolCalls, r.ToolCalls)
 		if r.SelfTestCommands > 0 {
 			tasksWithSelfTesting++
 		}
@@ -1349,6 +2040,10 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 			}
 		}
 		totalAgentTimeoutRetries += r.AgentTimeoutRetries
+		totalValidationRetries += r.ValidationRetries
+		if r.Status == task.StatusFlakyPass {
+			flakyPasses++
+		}
 		accumulateFailureStats(r.FailureClass, r.QuotaRetries, r.InfraRetries)
 
 		addAgg(byLanguage, r.Language, r)
@@ -1373,6 +2068,10 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 	if total > 0 {
 		skillsUsageRate = float64(tasksWithSkillsUsage) / float64(total) * 100
 	}
+	judgeScore := 0.0
+	if judgedTasks > 0 {
+		judgeScore = totalJudgeScore / float64(judgedTasks)
+	}
 
 	finalize := func(m map[string]EvalAggregate) map[string]EvalAggregate {
 		for k, v := range m {
@@ -1384,154 +2083,92 @@ func evalRunSingle( //nolint:gocognit,gocyclo,maintidx
 		return m
 	}
 
-	// Default model to "unknown" if not specified
-	model := spec.Model
-	if model == "" {
-		model = "unknown"
-	}
-
-	summary := EvalSummary{
-		Agent:                           spec.Agent,
-		Model:                           model,
-		Reasoning:                       spec.Reasoning,
-		Timestamp:                       timestamp,
-		Tier:                            shared.Tier,
-		Difficulty:                      shared.Difficulty,
-		Timeout:                         shared.Timeout,
-		Parallel:                        parallel,
-		Results:                         results,
-		Passed:                          passed,
-		Failed:                          failed,
-		Total:                           total,
-		SkippedExternalTasks:            len(externalFailures),
-		PassRate:                        passRate,
-		WeightedScore:                   totalWeightedScore,
-		MaxPossibleScore:                maxPossibleScore,
-		WeightedPassRate:                weightedPassRate,
-		IntegrityViolations:             integrityViolations,
-		Duration:                        totalDuration,
-		AgentTime:                       totalAgentTime,
-		ValidateTime:                    totalValidateTime,
-		PromptChars:                     totalPromptChars,
-		ByLanguage:                      finalize(byLanguage),
-		ByTier:                          finalize(byTier),
-		ByDifficulty:                    finalize(byDifficulty),
-		ExternalFailures:                externalFailures,
-		UseMCPTools:                     shared.UseMCPTools,
-		UseSkills:                       shared.UseSkills,
-		DisableMCP:                      shared.DisableMCP,
-		Sandbox:                         evalSandboxActive,
-		Legacy:                          shared.Legacy,
-		QuotaAffectedTasks:              quotaAffectedTasks,
-		AuthAffectedTasks:               authAffectedTasks,
-		InfraAffectedTasks:              infraAffectedTasks,
-		TotalQuotaRetries:               totalQuotaRetries,
-		TotalInfraRetries:               totalInfraRetries,
-		TotalAgentTimeoutRetries:        totalAgentTimeoutRetries,
-		AgentTimeoutTasks:               agentTimeoutTasks,
-		AgentTimeoutRetriedTasks:        agentTimeoutRetriedTasks,
-		TotalSelfTestCommands:           totalSelfTestCommands,
-		TotalToolchainInstallAttempts:   totalToolchainInstallAttempts,
-		TotalOutOfWorkspaceReadAttempts: totalOutOfWorkspaceReadAttempts,
-		SkillsUsageRate:                 skillsUsageRate,
-		TotalSkillsUsageSignals:         totalSkillsUsageSignals,
-		TasksWithSelfTesting:            tasksWithSelfTesting,
-		TasksWithToolchainInstall:       tasksWithToolchainInstall,
-		TasksWithOutOfWorkspaceReads:    tasksWithOutOfWorkspaceReads,
-		TotalToolchainSearchAttempts:    totalToolchainSearchAttempts,
-		TasksWithToolchainSearch:        tasksWithToolchainSearch,
-		TasksWithSkillsUsage:            tasksWithSkillsUsage,
-	}
-
-	summaryPath := filepath.Join(outputDir, "summary.json")
-	summaryData, _ := json.MarshalIndent(summary, "", "  ")
-	if err := os.WriteFile(summaryPath, summaryData, 0644); err != nil {
-		logger.Warn("failed to save summary", "error", err)
-	} else {
-		fmt.Printf(" Results saved to: %s\n", summaryPath)
-	}
-
-	// Generate attestation for verification
-	loader := task.NewLoader(tasks.FS, tasksDir)
-	var prevTasks map[string]AttestationTask
-	if prevAttestation != nil {
-		prevTasks = prevAttestation.Tasks
-	}
-	// Build set of tasks that were newly run in this session
-	newlyRunTasks := make(map[string]bool)
-	for _, t := range tasksToRun {
-		newlyRunTasks[t.ID()] = true
-	}
-	attestation, err := generateAttestation(
-		spec.Agent, spec.Model, timestamp, totalDuration,
-		results, outputDir, loader, allTasks, newlyRunTasks, prevTasks,
-	)
-	if err != nil {
-		logger.Warn("failed to generate attestation", "error", err)
-	} else {
-		attestationPath := filepath.Join(outputDir, "attestation.json")
-		attestationData, _ := json.MarshalIndent(attestation, "", "  ")
-		if err := os.WriteFile(attestationPath, attestationData, 0644); err != nil {
-			logger.Warn("failed to save attestation", "error", err)
-		} else {
-			fmt.Printf(" Attestation saved to: %s\n", attestationPath)
-		}
-	}
-
-	// Generate human-readable report.md
-	reportMd := generateEvalReport(summary, attestation)
-	reportPath := filepath.Join(outputDir, "report.md")
-	if err := os.WriteFile(reportPath, []byte(reportMd), 0644); err != nil {
-		logger.Warn("failed to save report", "error", err)
-	} else {
-		fmt.Printf(" Report saved to: %s\n", reportPath)
-	}
-
-	// Generate leaderboard submission file
-	submission := generateLeaderboardSubmission(summary, attestation)
-	submissionData, _ := json.MarshalIndent(submission, "", "  ")
-	submissionPath := filepath.Join(outputDir, "submission.json")
-	if err := os.WriteFile(submissionPath, submissionData, 0644); err != nil {
-		logger.Warn("failed to save submission", "error", err)
-	} else {
-		fmt.Printf(" Submission saved to: %s\n", submissionPath)
-	}
-
-	fmt.Println()
-
-	// Report resumable external failures and provide resume command.
-	if len(resumableFailedTasks) > 0 {
-		fmt.Println("\033[33m━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\033[0m")
-		fmt.Printf("\033[33m ⚠ %d task(s) skipped due to external failures (auth/quota/infra):\033[0m\n", len(resumableFailedTasks))
-		for _, t := range resumableFailedTasks {
-			fmt.Printf("   • %s\n", t)
-		}
-		fmt.Println()
-		fmt.Println(" These tasks were not counted in the results above.")
-		fmt.Println(" To retry them, run:")
-		fmt.Printf("   ./sanity eval --resume %s\n", outputDir)
-		fmt.Println("\033[33m━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\033[0m")
-		fmt.Println()
-	}
-
-	// If interrupted, print resume command.
-	if wasInterrupted {
-		printResumeCommand(outputDir)
-	}
-
-	return &summary, attestation, nil
-
+	summary.Results = results
+	summary.ExternalFailures = externalFailures
+	summary.Passed = passed
+	summary.Failed = failed
+	summary.Total = total
+	summary.SkippedExternalTasks = len(externalFailures)
+	summary.PassRate = passRate
+	summary.WeightedScore = totalWeightedScore
+	summary.MaxPossibleScore = maxPossibleScore
+	summary.WeightedPassRate = weightedPassRate
+	summary.IntegrityViolations = integrityViolations
+	summary.Duration = totalDuration
+	summary.AgentTime = totalAgentTime
+	summary.ValidateTime = totalValidateTime
+	summary.AgentCPUTime = totalAgentCPUTime
+	summary.ValidateCPUTime = totalValidateCPUTime
+	summary.AgentEnergy = totalAgentEnergy
+	summary.ValidateEnergy = totalValidateEnergy
+	summary.Usage = totalUsage
+	summary.PromptChars = totalPromptChars
+	summary.OverBudgetTasks = overBudgetTasks
+	summary.BudgetExhaustedTasks = budgetExhaustedTasks
+	summary.TaintedTasks = taintedTasks
+	summary.NoOpTasks = noOpTasks
+	summary.DurationAnomalies = durationAnomalies
+	summary.LikelyMemorizedTasks = likelyMemorizedTasks
+	summary.TasksReadingDistractors = tasksReadingDistractors
+	summary.TasksEditingDistractors = tasksEditingDistractors
+	summary.ChaosInjections = chaosInjections
+	summary.TasksWithNetwork = tasksWithNetwork
+	summary.JudgedTasks = judgedTasks
+	summary.JudgeScore = judgeScore
+	summary.BenchmarkedTasks = benchmarkedTasks
+	summary.BenchmarksOverThreshold = benchmarksOverThreshold
+	summary.FollowUpTasks = followUpTasks
+	summary.FollowUpsPassed = followUpsPassed
+	summary.FollowUpsTotal = followUpsTotal
+	if followUpsTotal > 0 {
+		summary.FollowUpPassRate = float64(followUpsPassed) / float64(followUpsTotal) * 100
+	}
+	summary.RepairedTasks = repairedTasks
+	summary.UnrepairedTasks = unrepairedTasks
+	summary.ByLanguage = finalize(byLanguage)
+	summary.ByTier = finalize(byTier)
+	summary.ByDifficulty = finalize(byDifficulty)
+	if summary.PromptExperiment != nil {
+		exp := *summary.PromptExperiment
+		exp.Arms = compareVariants(results, exp.Variants)
+		summary.PromptExperiment = &exp
+	}
+	summary.QuotaAffectedTasks = quotaAffectedTasks
+	summary.AuthAffectedTasks = authAffectedTasks
+	summary.InfraAffectedTasks = infraAffectedTasks
+	summary.TotalQuotaRetries = totalQuotaRetries
+	summary.TotalInfraRetries = totalInfraRetries
+	summary.TotalAgentTimeoutRetries = totalAgentTimeoutRetries
+	summary.TotalValidationRetries = totalValidationRetries
+	summary.FlakyPasses = flakyPasses
+	summary.AgentTimeoutTasks = agentTimeoutTasks
+	summary.AgentTimeoutRetriedTasks = agentTimeoutRetriedTasks
+	summary.TotalSelfTestCommands = totalSelfTestCommands
+	summary.TotalToolchainInstallAttempts = totalToolchainInstallAttempts
+	summary.TotalOutOfWorkspaceReadAttempts = totalOutOfWorkspaceReadAttempts
+	summary.SkillsUsageRate = skillsUsageRate
+	summary.TotalSkillsUsageSignals = totalSkillsUsageSignals
+	summary.ToolCalls = toolCalls
+	summary.TasksWithSelfTesting = tasksWithSelfTesting
+	summary.TasksWithToolchainInstall = tasksWithToolchainInstall
+	summary.TasksWithOutOfWorkspaceReads = tasksWithOutOfWorkspaceReads
+	summary.TotalToolchainSearchAttempts = totalToolchainSearchAttempts
+	summary.TasksWithToolchainSearch = tasksWithToolchainSearch
+	summary.TasksWithSkillsUsage = tasksWithSkillsUsage
 }
 
 func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent, model, outputDir string, timeout int) (result EvalResult) {
 	start := time.Now()
-	weight := task.ComputeWeight(t)
+	timeout = taskTimeoutSeconds(t, timeout)
+	weight := taskWeight(t)
 	result = newEvalResult(t, weight)
 	defer finalizeEvalResult(&result, start, weight)
 
 	loader := task.NewLoader(tasks.FS, tasksDir)
 	workspaceName, workspaceDir := evalWorkspacePaths(outputDir, t)
 	result.WorkspaceDir = workspaceDir
+	// Runs after the judge and follow-ups, the last readers of the transcripts.
+	defer encryptResultArtifacts(ctx, &result)
 
 	// Create an isolated temp workspace for the agent so it cannot read
 	// other eval results or sibling task directories. After the agent
@@ -1547,6 +2184,10 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 		result.Error = fmt.Sprintf("init failed: %v", err)
 		return result
 	}
+	if err := hideVisibleTests(t, agentWorkDir); err != nil {
+		result.Error = err.Error()
+		return result
+	}
 
 	if evalUseSkills {
 		if homeDir, err := os.UserHomeDir(); err == nil {
@@ -1559,6 +2200,42 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 		}
 	}
 
+	placed, err := placeContextFiles(cfg.ContextFiles, string(t.Language), agentWorkDir)
+	result.ContextFiles = placed
+	if err != nil {
+		result.Error = fmt.Sprintf("placing context files: %v", err)
+		return result
+	}
+
+	if err := installSkillPacks(evalSkillPackSet, agentWorkDir); err != nil {
+		result.Error = fmt.Sprintf("installing skill packs: %v", err)
+		return result
+	}
+	distractors, err := evalDistractorPack.install(agentWorkDir, t.Language)
+	if err != nil {
+		result.Error = fmt.Sprintf("installing distractor pack: %v", err)
+		return result
+	}
+	if err := evalChaos.install(agentWorkDir, t.Language); err != nil {
+		result.Error = fmt.Sprintf("installing chaos shims: %v", err)
+		return result
+	}
+	cleanupMemory, err := evalAgentMemory.prepare(agentWorkDir)
+	if err != nil {
+		result.Error = err.Error()
+		return result
+	}
+	defer cleanupMemory()
+
+	// A failing pre_task hook means the environment isn't ready, so the task
+	// is skipped as a resumable infra failure rather than scored.
+	if err := runHooks(ctx, HookEvent{Event: hookPreTask, Task: t.ID(), Workspace: agentWorkDir}); err != nil {
+		result.Error = fmt.Sprintf("infra failure: %v", err)
+		result.InfraFailure = true
+		result.FailureClass = FailureClassInfra
+		return result
+	}
+
 	// Get agent configuration
 	agentCfg := cfg.GetAgent(agent)
 	if agentCfg == nil {
@@ -1567,8 +2244,27 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 	}
 
 	// Build agent command
-	prompt := buildAgentPrompt(t, evalUseMCPTools, evalUseSkills, agentCfg.MCPPrompt)
-	result.PromptChars = utf8.RuneCountInString(prompt)
+	promptTask, _ := t.Localized(evalPromptLang)
+	if promptTask != t {
+		result.PromptLang = evalPromptLang
+	}
+	if testsHidden() {
+		promptTask = withoutVisibleTests(promptTask)
+	}
+	basePrompt := buildAgentPrompt(promptTask, evalUseMCPTools, evalUseSkills, agentCfg.MCPPrompt)
+	if evalVariantPlan != nil {
+		result.PromptVariant = evalVariantPlan.assignment[t.ID()]
+		basePrompt = evalVariantPlan.render(result.PromptVariant, basePrompt, promptTask)
+	}
+	prompt, err := applyPromptBudget(&result, promptParts{
+		Base:         basePrompt,
+		SkillPacks:   skillPackPrompt(evalSkillPackSet),
+		ContextChars: contextChars(result.ContextFiles),
+	}, model, agentWorkDir)
+	if err != nil {
+		result.Error = fmt.Sprintf("applying prompt budget: %v", err)
+		return result
+	}
 	agentTimeout := resolveAgentTimeout(timeout, agentCfg.DefaultTimeout, t.AgentTimeout)
 
 	// Place agent.log in the task output directory (eval-results/<run>/<lang>-<slug>/).
@@ -1579,10 +2275,44 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 		return result
 	}
 
+	// Hash the workspace as the agent will find it so its changes can be
+	// audited afterwards.
+	workspaceBefore, err := snapshotWorkspace(agentWorkDir)
+	if err != nil {
+		logger.Debug("failed to snapshot workspace", "task", t.ID(), "error", err)
+	}
+
 	// Execute agent in the isolated temp workspace
 	workspaceReadyAt := time.Now()
 	agentResult := executeAgentWithRetries(ctx, t, agentCfg, prompt, model, agentWorkDir, agentLogPath, agentTimeout, agent, workspaceReadyAt)
+	if evalChaos != nil {
+		injections, err := removeChaosShims(agentWorkDir)
+		if err != nil {
+			logger.Warn("failed to remove chaos shims", "task", t.ID(), "error", err)
+		}
+		result.ChaosInjections = injections
+	}
 	applyAgentExecutionResult(&result, agentResult, agentLogPath, agentWorkDir)
+	recordAgentOutput(&result, adapterFor(agent, agentCfg), agentLogPath)
+
+	// In legacy mode hidden tests sit in the workspace, so check whether the
+	// agent looked at them before anything else touches the files.
+	if r.LegacyHiddenTests {
+		result.HiddenTestAccess = detectHiddenTestAccess(t, agentLogPath, agentWorkDir, workspaceReadyAt)
+		if len(result.HiddenTestAccess) > 0 {
+			result.Tainted = true
+			logger.Warn("agent accessed hidden tests", "task", t.ID(), "files", len(result.HiddenTestAccess))
+		}
+	}
+	auditWorkspaceChanges(&result, workspaceBefore, agentWorkDir, taskOutputDir)
+	result.Distractors = auditDistractors(distractors, workspaceBefore, agentWorkDir, agentLogPath)
+
+	// An agent stopped by an interrupt left nothing to grade. The task is
+	// excluded from results so it can be resumed later.
+	if result.FailureClass == FailureClassInterrupted {
+		result.Error = "interrupted before the agent finished"
+		return result
+	}
 
 	// If agent execution failed due auth/quota/infra, skip validation entirely.
 	// The task will be excluded from results so it can be resumed later.
@@ -1590,6 +2320,11 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 		return result
 	}
 
+	// The agent finished, so its work is graded even if the eval is
+	// interrupted from here on.
+	gradeCtx, stopGrading := gradingContext(ctx)
+	defer stopGrading()
+
 	// Ensure the agent didn't modify task-owned files.
 	integrityViolated, err := detectAndRecordIntegrityViolation(
 		loader,
@@ -1612,16 +2347,35 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 		result.Error = fmt.Sprintf("copying agent workspace: %v", err)
 		return result
 	}
-
-	if err := writeHiddenTestsIfNeeded(loader, t, workspaceDir); err != nil {
-		result.Error = fmt.Sprintf("writing hidden tests: %v", err)
+	if err := restoreVisibleTests(loader, t, workspaceDir); err != nil {
+		result.Error = err.Error()
 		return result
 	}
 
+	// Record the agent's changes relative to the stubs before hidden tests are
+	// overlaid, so the diff survives workspace cleanup.
+	if diff, err := buildSolutionDiff(loader, t, workspaceDir); err != nil {
+		logger.Debug("failed to build solution diff", "task", t.ID(), "error", err)
+	} else if err := os.WriteFile(filepath.Join(taskOutputDir, "solution.diff"), []byte(diff), 0o644); err != nil {
+		logger.Debug("failed to write solution diff", "task", t.ID(), "error", err)
+	}
+	checkKnownSolution(&result, loader, t, workspaceDir)
+
 	validationCmd, effectiveValidationCmd := buildValidationCommands(t)
 	validationTimeout := resolveValidationTimeout(timeout)
-	session, validateDuration, err := runValidationSession(
-		ctx,
+
+	// Record the validation inputs so `sanity replay` can re-run this phase.
+	if err := writeReplayRecord(gradeCtx, loader, t, prompt, workspaceDir, taskOutputDir, effectiveValidationCmd, validationTimeout); err != nil {
+		logger.Debug("failed to write replay record", "task", t.ID(), "error", err)
+	}
+
+	if err := writeHiddenTestsIfNeeded(loader, t, workspaceDir); err != nil {
+		result.Error = fmt.Sprintf("writing hidden tests: %v", err)
+		return result
+	}
+	energyBefore := readRAPL(raplRoot)
+	session, validateDuration, validationRetries, err := runValidationWithRetries(
+		gradeCtx,
 		r,
 		t,
 		workspaceDir,
@@ -1629,6 +2383,16 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 		validationCmd,
 	)
 	result.ValidateTime = validateDuration
+	result.ValidationRetries = sumRetries(validationRetries)
+	result.FlakyReruns = validationRetries[config.RetryFlaky]
+	if energyBefore != nil {
+		result.ValidateEnergy = readRAPL(raplRoot).joulesSince(raplRoot, energyBefore)
+	}
+	if err != nil && gradeCtx.Err() != nil {
+		result.FailureClass = FailureClassInterrupted
+		result.Error = "interrupted during validation"
+		return result
+	}
 	if err != nil {
 		handleValidationRunError(&result, session, err, validationLogPath, effectiveValidationCmd)
 		return result
@@ -1636,16 +2400,33 @@ func runTaskWithAgent(ctx context.Context, r *runner.Runner, t *task.Task, agent
 
 	applyValidationSessionResult(&result, session)
 	writeValidationSessionLog(validationLogPath, effectiveValidationCmd, session)
+	turns := followUpSession{
+		agentCfg:          agentCfg,
+		agent:             agent,
+		model:             model,
+		agentWorkDir:      agentWorkDir,
+		workspaceDir:      workspaceDir,
+		agentTimeout:      agentTimeout,
+		validationTimeout: validationTimeout,
+	}
+	runRepairAttempts(ctx, &result, r, loader, t, turns, session)
+	if result.FailureClass == FailureClassInterrupted {
+		return result
+	}
+	runTaskBenchmarks(gradeCtx, &result, r, t, workspaceDir, taskOutputDir, validationTimeout)
+	judgeTask(gradeCtx, &result, t, prompt, taskOutputDir)
+	runFollowUps(ctx, &result, r, loader, t, turns)
 	return result
 }
 
 func newEvalResult(t *task.Task, weight task.Weight) EvalResult {
 	return EvalResult{
-		Task:       t.ID(),
-		Language:   string(t.Language),
-		Tier:       t.Tier,
-		Difficulty: t.Difficulty,
-		Weight:     weight.Base,
+		Task:          t.ID(),
+		Language:      string(t.Language),
+		Tier:          t.Tier,
+		Difficulty:    t.Difficulty,
+		Weight:        weight.Base,
+		FollowUpCount: len(t.FollowUps),
 	}
 }
 
@@ -1654,6 +2435,18 @@ func evalWorkspacePaths(outputDir string, t *task.Task) (workspaceName, workspac
 	return workspaceName, filepath.Join(outputDir, workspaceName)
 }
 
+// taskWeight returns t's difficulty weight, with the base replaced by its
+// [task."<id>"] weight override if one is set.
+func taskWeight(t *task.Task) task.Weight {
+	w := task.ComputeWeight(t)
+	if cfg != nil {
+		if o := cfg.TaskOverride(t.ID()); o.Weight > 0 {
+			w.Base = o.Weight
+		}
+	}
+	return w
+}
+
 func resolveAgentTimeout(timeoutSeconds, defaultSeconds, taskSeconds int) time.Duration {
 	timeout := time.Duration(timeoutSeconds) * time.Second
 	if timeout <= 0 {
@@ -1679,10 +2472,21 @@ func resolveAgentTimeout(timeoutSeconds, defaultSeconds, taskSeconds int) time.D
 // source files after validation.
 var evalOutputFiles = map[string]bool{
 	"agent.log":       true,
+	"agent.log.age":   true,
 	"validation.log":  true,
 	"integrity.json":  true,
 	"integrity-files": true,
 	"integrity-diff":  true,
+	"solution.diff":   true,
+	"replay.json":     true,
+	"fs-audit.json":   true,
+	"snapshots":       true,
+	"judge.json":      true,
+	"judge.json.age":  true,
+	"benchmark.log":   true,
+	taskResultFile:    true,
+	followUpDirName:   true,
+	repairDirName:     true,
 }
 
 // cleanupWorkspaceFiles removes workspace source files from the task output
@@ -1714,12 +2518,21 @@ func ensureEvalTaskOutputPaths(outputDir, workspaceName string) (taskOutputDir,
 
 func applyAgentExecutionResult(result *EvalResult, agentResult agentExecutionResult, agentLogPath, workspaceDir string) {
 	result.AgentTime = agentResult.totalTime
+	result.AgentCPUTime = agentResult.cpuTime
+	result.AgentEnergy = agentResult.energy
 	result.AgentTimedOut = agentResult.timedOut
+	result.AgentTermination = agentResult.termination
 	result.QuotaRetries = agentResult.quotaRetries
 	result.InfraRetries = agentResult.infraRetries
 	result.AgentTimeoutRetries = agentResult.agentTimeoutRetries
 	result.QuotaExhausted = agentResult.quotaExhausted
 	result.InfraFailure = agentResult.infraFailure
+	result.BudgetExhausted = agentResult.budgetExhausted
+	result.AgentTurns = agentResult.turns
+	result.AgentToolCalls = agentResult.toolCalls
+	result.Network = agentResult.network
+	result.AttemptSnapshots = agentResult.snapshots
+	result.AttemptChanges = agentResult.attemptChanges
 	result.FailureClass = agentResult.failureClass
 
 	metrics := parseAgentBehaviorMetrics(agentLogPath, workspaceDir)
@@ -1731,6 +2544,7 @@ func applyAgentExecutionResult(result *EvalResult, agentResult agentExecutionRes
 	result.ToolchainSearchAttempts = metrics.ToolchainSearchAttempts
 	result.SkillsUsed = metrics.SkillsUsed
 	result.SkillsUsageSignals = metrics.SkillsUsageSignals
+	result.ToolCalls = metrics.ToolCalls
 }
 
 func shouldSkipValidationForExternalFailure(result *EvalResult) bool {
@@ -1841,10 +2655,7 @@ func resolveValidationTimeout(timeout int) int {
 
 func buildValidationCommands(t *task.Task) (validationCmd, effectiveValidationCmd []string) {
 	if t.Language == task.TypeScript && len(t.HiddenTestFiles()) > 0 {
-		validationCmd = append([]string{}, t.ValidationCommand()...)
-		for _, filename := range t.HiddenTestFiles() {
-			validationCmd = append(validationCmd, task.StripTxtExtension(filename))
-		}
+		validationCmd = t.HiddenValidationCommand()
 	}
 
 	effectiveValidationCmd = t.ValidationCommand()
@@ -1873,6 +2684,70 @@ func runValidationSession(
 	return session, time.Since(start).Seconds(), err
 }
 
+// runValidationWithRetries runs validation, rerunning it on the same code
+// after container failures, test failures, and failed timing tests as
+// [retry] allows. It returns the last session, the total validation time,
+// and the number of reruns by failure type.
+func runValidationWithRetries(
+	ctx context.Context,
+	r *runner.Runner,
+	t *task.Task,
+	workspaceDir string,
+	validationTimeout int,
+	validationCmd []string,
+) (*resultpkg.Session, float64, map[string]int, error) {
+	var total float64
+	retries := make(map[string]int)
+	for {
+		session, duration, err := runValidationSession(ctx, r, t, workspaceDir, validationTimeout, validationCmd)
+		total += duration
+
+		var kind string
+		switch {
+		case err != nil && isValidationInfraError(err):
+			kind = config.RetryContainer
+		case err == nil && session != nil && !session.Passed():
+			kind = validationFailureKind(t, session, retries)
+		default:
+			return session, total, retries, err
+		}
+		policy := retryPolicy(kind)
+		if retries[kind] >= policy.MaxRetries {
+			return session, total, retries, err
+		}
+		delay := policy.Wait(retries[kind] + 1)
+		logger.Info("retrying validation",
+			"task", t.ID(),
+			"type", kind,
+			"retry", retries[kind]+1,
+			"delay", delay)
+		sleepCtx(ctx, delay)
+		if ctx.Err() != nil {
+			return session, total, retries, err
+		}
+		retries[kind]++
+	}
+}
+
+// validationFailureKind returns the retry type of a failed validation run:
+// flaky while one of the task's timing tests failed and flaky reruns remain,
+// otherwise test_failure.
+func validationFailureKind(t *task.Task, session *resultpkg.Session, retries map[string]int) string {
+	last := session.LastAttempt()
+	if last != nil && retries[config.RetryFlaky] < retryPolicy(config.RetryFlaky).MaxRetries && t.TimingFailure(last.RawOutput) {
+		return config.RetryFlaky
+	}
+	return config.RetryTestFailure
+}
+
+func sumRetries(retries map[string]int) int {
+	var n int
+	for _, c := range retries {
+		n += c
+	}
+	return n
+}
+
 func handleValidationRunError(
 	result *EvalResult,
 	session *resultpkg.Session,
@@ -1926,6 +2801,12 @@ func applyValidationSessionResult(result *EvalResult, session *resultpkg.Session
 	}
 	result.Passed = session.Passed()
 	result.Attempts = len(session.Attempts)
+	if last := session.LastAttempt(); last != nil {
+		result.PeakMemoryBytes = last.PeakMemoryBytes
+		result.BinarySizeBytes = last.BinarySizeBytes
+		result.ValidateCPUTime = last.CPUSeconds
+		result.ValidationTermination = last.Termination
+	}
 }
 
 func validationErrorEvidence(session *resultpkg.Session, validateSeconds float64) (rawOutput string, exitCode int, duration time.Duration) {
@@ -1979,19 +2860,38 @@ func finalizeEvalResult(result *EvalResult, start time.Time, weight task.Weight)
 			result.FailureClass = FailureClassValidationError
 		}
 	}
-	result.Status = task.DetermineStatus(result.Passed, result.AgentTimedOut, result.Error)
+	result.Status = evalResultStatus(result)
 	result.WeightedScore = task.ScoreResult(result.Passed, result.AgentTimedOut, result.Error, weight)
 }
 
+// evalResultStatus returns the status of a graded result. A clean pass that
+// needed a flaky timing-test rerun is a flaky pass; it scores like a pass.
+func evalResultStatus(r *EvalResult) task.ResultStatus {
+	status := task.DetermineStatus(r.Passed, r.AgentTimedOut, r.Error)
+	if status == task.StatusPass && r.FlakyReruns > 0 {
+		return task.StatusFlakyPass
+	}
+	return status
+}
+
 // agentExecutionResult holds the outcome of agent execution with retries.
 type agentExecutionResult struct {
 	totalTime           float64
+	cpuTime             float64
+	energy              float64
 	timedOut            bool
+	termination         string // how the last attempt was stopped, if it was
 	quotaRetries        int
 	quotaExhausted      bool
 	infraRetries        int
 	infraFailure        bool // true when agent produced no output after all retries
 	agentTimeoutRetries int  // retries triggered purely by wall-clock agent timeout
+	budgetExhausted     bool // true when the last attempt hit a turn or tool-call limit
+	turns               int
+	toolCalls           int
+	network             *NetworkActivity
+	snapshots           []string // per-attempt workspace snapshots, relative to the task output dir
+	attemptChanges      []AttemptChange
 	failureClass        FailureClass
 }
 
@@ -2012,22 +2912,57 @@ func executeAgentWithRetries(
 ) agentExecutionResult {
 	var result agentExecutionResult
 	var quotaAttempts, infraAttempts, agentTimeoutAttempts int
-	var localAttempts int    // retries within this run (controls delay/logging)
-	var lastRetryType string // "quota", "infra", or "agent_timeout"
+	var localAttempts int         // retries within this run (controls logging)
+	var lastRetry attemptDecision // failure type and count of the last retry (controls delay)
 
-	for waitBeforeRetry(ctx, t.ID(), localAttempts, lastRetryType) {
+	for waitBeforeRetry(ctx, t.ID(), localAttempts, lastRetry) {
 		// Run single attempt.
+		if evalAttemptHook != nil {
+			evalAttemptHook(t.ID(), localAttempts)
+		}
 		attemptResult := runAgentAttempt(ctx, agentCfg, prompt, model, workspaceDir, agentLogPath, agentTimeout, agent, localAttempts)
 		result.totalTime += attemptResult.duration
+		result.cpuTime += attemptResult.cpuTime
+		result.energy += attemptResult.energy
 		result.timedOut = attemptResult.timedOut
-
+		result.termination = attemptResult.termination
+		result.turns += attemptResult.turns
+		result.toolCalls += attemptResult.toolCalls
+		if attemptResult.network != nil {
+			if result.network == nil {
+				result.network = &NetworkActivity{}
+			}
+			result.network.merge(attemptResult.network)
+		}
+		change := recordAttemptSnapshot(&result, t.ID(), workspaceDir, filepath.Dir(agentLogPath), localAttempts+1)
+		warnOnHookError(runHooks(ctx, HookEvent{
+			Event:     hookPostAttempt,
+			Task:      t.ID(),
+			Workspace: workspaceDir,
+			Attempt: &HookAttempt{
+				Number:          localAttempts + 1,
+				DurationSeconds: attemptResult.duration,
+				TimedOut:        attemptResult.timedOut,
+				AgentLog:        agentLogPath,
+			},
+		}))
+
+		if attemptResult.interrupted {
+			result.failureClass = FailureClassInterrupted
+			break
+		}
 		decision := classifyAttempt(attemptResult, agentLogPath, workspaceDir, workspaceReadyAt,
 			&quotaAttempts, &infraAttempts, &agentTimeoutAttempts, &result)
+		// Written after classification so the note can't make an empty
+		// attempt look like agent output.
+		if change != nil {
+			writeAgentAttemptChangeFooter(agentLogPath, *change)
+		}
 		if decision.done {
 			break
 		}
 		localAttempts++
-		lastRetryType = decision.retryType
+		lastRetry = decision
 	}
 
 	return result
@@ -2036,34 +2971,40 @@ func executeAgentWithRetries(
 // waitBeforeRetry sleeps (interruptibly) before a retry attempt and returns
 // false if the context has been cancelled. Returns true if the caller should
 // proceed with the attempt.
-func waitBeforeRetry(ctx context.Context, taskID string, localAttempts int, lastRetryType string) bool {
+func waitBeforeRetry(ctx context.Context, taskID string, localAttempts int, last attemptDecision) bool {
 	if localAttempts > 0 {
-		var delay time.Duration
-		switch lastRetryType {
-		case "infra":
-			delay = getInfraRetryDelay(localAttempts)
-		case "agent_timeout":
-			delay = agentTimeoutRetryDelay1
-		default:
-			delay = getRetryDelay(localAttempts)
-		}
+		delay := retryPolicy(last.retryType).Wait(last.retry)
 		logger.Info("retrying agent execution",
 			"task", taskID,
 			"attempt", localAttempts,
-			"type", lastRetryType,
+			"type", last.retryType,
 			"delay", delay)
-		select {
-		case <-time.After(delay):
-		case <-ctx.Done():
-		}
+		sleepCtx(ctx, delay)
 	}
 	return ctx.Err() == nil
 }
 
+// sleepCtx sleeps for d or until ctx is cancelled.
+func sleepCtx(ctx context.Context, d time.Duration) {
+	select {
+	case <-time.After(d):
+	case <-ctx.Done():
+	}
+}
+
+// retryPolicy returns the [retry] policy for the named failure type.
+func retryPolicy(kind string) config.RetryPolicy {
+	if cfg == nil {
+		return config.Default.Retry.Policy(kind)
+	}
+	return cfg.Retry.Policy(kind)
+}
+
 // attemptDecision describes what the retry loop should do after an attempt.
 type attemptDecision struct {
 	done      bool   // true if loop should exit
-	retryType string // "quota", "infra", "agent_timeout" — only when !done
+	retryType string // [retry] failure type, e.g. config.RetryProvider — only when !done
+	retry     int    // which retry of that type this is (1-indexed) — only when !done
 }
 
 // classifyAttempt inspects a single agent attempt and updates retry counters
@@ -2087,6 +3028,14 @@ func classifyAttempt(
 		return classifyQuota(isRecoverable, quotaAttempts, result)
 	}
 
+	// A runaway agent stopped by its turn or tool-call limit is final: its
+	// work so far is validated and retrying would only repeat the loop.
+	if attempt.budgetExhausted {
+		result.budgetExhausted = true
+		result.failureClass = FailureClassBudgetExhausted
+		return attemptDecision{done: true}
+	}
+
 	// Infra failures (empty/near-empty agent log).
 	if isInfraFailure(agentLogPath, workspaceDir, workspaceReadyAt) {
 		return classifyInfra(infraAttempts, result)
@@ -2095,13 +3044,15 @@ func classifyAttempt(
 	// Wall-clock agent timeout with meaningful output — treated as an
 	// infra-class failure so it feeds the existing resumable-external-failure
 	// path (isResumableExternalFailure → skipped from scoring → surfaced in
-	// the printed resume command). A single cheap retry first in case the
-	// stall was a one-shot SSE hiccup.
+	// the printed resume command). Plain timeouts are not the agent
+	// meaningfully failing: they often look like a long-tail SSE hang on the
+	// provider side, so by default one cheap retry comes first in case the
+	// stall was a one-shot hiccup.
 	if attempt.timedOut {
-		if *agentTimeoutAttempts < agentTimeoutMaxRetries {
+		if *agentTimeoutAttempts < retryPolicy(config.RetryAgentTimeout).MaxRetries {
 			*agentTimeoutAttempts++
 			result.agentTimeoutRetries = *agentTimeoutAttempts
-			return attemptDecision{retryType: "agent_timeout"}
+			return attemptDecision{retryType: config.RetryAgentTimeout, retry: *agentTimeoutAttempts}
 		}
 		result.infraFailure = true
 		result.failureClass = FailureClassInfra
@@ -2124,29 +3075,37 @@ func classifyQuota(isRecoverable bool, quotaAttempts *int, result *agentExecutio
 	*quotaAttempts++
 	result.quotaRetries = *quotaAttempts
 	result.failureClass = FailureClassQuotaRecoverable
-	if *quotaAttempts >= quotaMaxRetries {
+	if *quotaAttempts > retryPolicy(config.RetryProvider).MaxRetries {
 		result.quotaExhausted = true
 		result.failureClass = FailureClassQuotaExhausted
 		return attemptDecision{done: true}
 	}
-	return attemptDecision{retryType: "quota"}
+	return attemptDecision{retryType: config.RetryProvider, retry: *quotaAttempts}
 }
 
 func classifyInfra(infraAttempts *int, result *agentExecutionResult) attemptDecision {
 	*infraAttempts++
 	result.infraRetries = *infraAttempts
-	if *infraAttempts >= infraMaxRetries {
+	if *infraAttempts > retryPolicy(config.RetryAgentCrash).MaxRetries {
 		result.infraFailure = true
 		result.failureClass = FailureClassInfra
 		return attemptDecision{done: true}
 	}
-	return attemptDecision{retryType: "infra"}
+	return attemptDecision{retryType: config.RetryAgentCrash, retry: *infraAttempts}
 }
 
 // agentAttemptResult holds the outcome of a single agent attempt.
 type agentAttemptResult struct {
-	duration float64
-	timedOut bool
+	duration        float64
+	cpuTime         float64 // user+system seconds of the agent process and the children it waited for
+	energy          float64 // machine-wide RAPL joules while the agent ran
+	timedOut        bool
+	budgetExhausted bool
+	interrupted     bool   // Stopped by an interrupt of the eval
+	termination     string // How a timed-out or stopped attempt ended; see setupProcessGroup
+	turns           int
+	toolCalls       int
+	network         *NetworkActivity
 }
 
 // runAgentAttempt executes a single agent command attempt.
@@ -2160,33 +3119,69 @@ func runAgentAttempt(
 ) agentAttemptResult {
 	var result agentAttemptResult
 
-	agentCtx, cancel := context.WithTimeout(ctx, agentTimeout)
+	timeoutCtx, cancel := context.WithTimeout(ctx, agentTimeout)
 	defer cancel()
+	// Turn and tool-call limits cancel the attempt with errBudgetExhausted.
+	agentCtx, cancelAgent := context.WithCancelCause(timeoutCtx)
+	defer cancelAgent(nil)
 
-	cmd := buildAgentCommand(agentCtx, agentCfg, prompt, model, evalReasoning, evalDisableMCP, evalUseMCPTools, agent)
+	adapter := adapterFor(agent, agentCfg)
+	plugin := agentCfg.Protocol == config.AgentProtocolPlugin
+	if plugin {
+		agentCfg = pluginAgentConfig(agentCfg)
+	}
+	model, modelArgs := adapter.splitModel(model)
+	cmd := buildAgentCommand(agentCtx, agentCfg, prompt, model, evalReasoning, workspaceDir, evalDisableMCP, evalUseMCPTools, agent)
 	cmd.Dir = workspaceDir
+	cmd.Args = append(cmd.Args, modelArgs...)
 
-	// Use /dev/null for stdin to prevent TTY issues with agents that use Ink/React
-	devNull, err := os.Open(os.DevNull)
-	if err == nil {
-		cmd.Stdin = devNull
-		defer func() { _ = devNull.Close() }()
+	if extraArgs, err := adapter.extraArgs(workspaceDir); err != nil {
+		logger.Debug("agent adapter failed to build args", "agent", agent, "error", err)
+	} else {
+		cmd.Args = append(cmd.Args, extraArgs...)
 	}
+	cmd.Env = adapter.environ(cmd.Env, evalSandboxActive)
+	adapter.provisionMCP(cmd, evalMCPServerSet, workspaceDir)
+	adapter.provisionSampling(cmd, agentCfg, evalSamplingSet)
+	defer adapter.removeArtifacts(workspaceDir)
+
+	cleanupPrompt := deliverAgentPrompt(cmd, agentCfg, prompt, model, workspaceDir, agentTimeout, attempt)
+	defer cleanupPrompt()
 
 	cmd.Stdout = nil // Suppress output
 	cmd.Stderr = nil
 
 	// Open log file: create on first attempt, append on retry
 	logFile := openAgentLogFile(agentLogPath, attempt)
+	var counter *activityCounter
+	var decoder *pluginDecoder
 	if logFile != nil {
 		cmd.Stdout = logFile
 		cmd.Stderr = logFile
+		if limits := resolveActivityLimits(); limits.enabled() || agentCfg.DonePattern != "" {
+			counter = newActivityCounter(logFile, limits, agentCfg, func(reason string) {
+				cancelAgent(fmt.Errorf("%w: %s", errBudgetExhausted, reason))
+			})
+			counter.onDone = func() { cancelAgent(errAgentDone) }
+			counter.usage = adapter.usage
+			cmd.Stdout = counter
+			cmd.Stderr = counter
+		}
+		if plugin {
+			decoder = newPluginDecoder(cmd.Stdout, func() { cancelAgent(errAgentDone) })
+			cmd.Stdout = decoder
+		}
 		defer func() {
 			_ = logFile.Sync()
 			_ = logFile.Close()
 		}()
 	}
 
+	// Chaos shims go first on PATH; the sandbox passes the environment on.
+	if evalChaos != nil {
+		cmd.Env = chaosEnv(cmd.Env, workspaceDir)
+	}
+
 	// Wrap in bubblewrap sandbox if enabled.
 	if evalSandboxActive {
 		var extraDirs []string
@@ -2200,31 +3195,117 @@ func runAgentAttempt(
 			evalSandboxSharedRW,
 			evalSandboxSharedRO,
 			evalSandboxDenylist,
+			evalAgentMemory.sandboxArgs(workspaceDir)...,
 		)
 	}
 
-	// Run agent in its own process group so we can kill the entire tree on
+	// Trace outside the sandbox so every process the agent starts is seen.
+	// The raw trace sits next to agent.log, out of the agent's reach.
+	var tracePath string
+	if evalNetworkAudit {
+		tracePath = filepath.Join(filepath.Dir(agentLogPath), networkTraceName)
+		cmd = wrapCommandWithNetworkTrace(agentCtx, cmd, tracePath)
+	}
+
+	if evalChaos != nil && evalChaos.CPUs > 0 {
+		cmd = wrapCommandWithCPULimit(agentCtx, cmd, evalChaos.CPUs)
+	}
+
+	// Run agent in its own process group so we can stop the entire tree on
 	// timeout or interrupt, preventing orphaned child processes.
-	setupProcessGroup(cmd)
+	termination := setupProcessGroup(cmd)
 
 	// Run agent
 	agentStart := time.Now()
+	energyBefore := readRAPL(raplRoot)
 	agentErr := cmd.Run()
 	result.duration = time.Since(agentStart).Seconds()
+	result.termination = termination.finish()
+	result.interrupted = ctx.Err() != nil
+	if cmd.ProcessState != nil {
+		result.cpuTime = (cmd.ProcessState.UserTime() + cmd.ProcessState.SystemTime()).Seconds()
+	}
+	if energyBefore != nil {
+		result.energy = readRAPL(raplRoot).joulesSince(raplRoot, energyBefore)
+	}
+
+	if decoder != nil {
+		decoder.Flush()
+	}
+	if counter != nil {
+		counter.Flush()
+		var exceeded string
+		result.turns, result.toolCalls, exceeded = counter.Counts()
+		if errors.Is(context.Cause(agentCtx), errBudgetExhausted) {
+			result.budgetExhausted = true
+			logger.Debug("agent budget exhausted", "limit", exceeded)
+			writeAgentBudgetFooter(logFile, attempt, exceeded, result.turns, result.toolCalls, counter.Tokens())
+		}
+		if errors.Is(context.Cause(agentCtx), errAgentDone) {
+			logger.Debug("agent reported completion", "pattern", agentCfg.DonePattern)
+		}
+	}
 
 	// Check for timeout
-	if errors.Is(agentCtx.Err(), context.DeadlineExceeded) {
+	if !result.budgetExhausted && errors.Is(agentCtx.Err(), context.DeadlineExceeded) {
 		result.timedOut = true
 		logger.Debug("agent timed out", "timeout", agentTimeout)
-		writeAgentTimeoutFooter(logFile, attempt, agentTimeout, time.Since(agentStart))
+		writeAgentTimeoutFooter(logFile, attempt, agentTimeout, time.Since(agentStart), result.termination)
 	}
 	if agentErr != nil {
 		logger.Debug("agent returned error", "error", agentErr)
 	}
+	if tracePath != "" {
+		network, err := parseNetworkTrace(tracePath)
+		if err != nil {
+			logger.Debug("failed to parse network trace", "error", err)
+		}
+		result.network = network
+		_ = os.Remove(tracePath)
+	}
 
 	return result
 }
 
+// deliverAgentPrompt sets up how the agent receives its prompt: in its
+// args (done by buildAgentCommand), on stdin, in a workspace file, or in a
+// plugin task request. The returned cleanup runs when the attempt ends.
+func deliverAgentPrompt(
+	cmd *exec.Cmd,
+	agentCfg *config.AgentConfig,
+	prompt, model, workspaceDir string,
+	agentTimeout time.Duration,
+	attempt int,
+) (cleanup func()) {
+	text := agentPromptText(agentCfg, prompt)
+	switch {
+	case agentCfg.Protocol == config.AgentProtocolPlugin:
+		cmd.Stdin = bytes.NewReader(newPluginRequest(text, workspaceDir, model, evalReasoning, evalSamplingSet, agentTimeout, attempt))
+		return func() {}
+	case agentCfg.PromptMode == config.PromptModeStdin:
+		cmd.Stdin = strings.NewReader(text)
+		return func() {}
+	case agentCfg.PromptMode == config.PromptModeFile:
+		promptPath := filepath.Join(workspaceDir, config.AgentPromptFile)
+		if err := os.WriteFile(promptPath, []byte(text), 0644); err != nil {
+			logger.Debug("failed to write prompt file", "error", err)
+		}
+		cleanup = func() { _ = os.Remove(promptPath) }
+	default:
+		cleanup = func() {}
+	}
+	// Use /dev/null for stdin to prevent TTY issues with agents that use Ink/React
+	devNull, err := os.Open(os.DevNull)
+	if err != nil {
+		return cleanup
+	}
+	cmd.Stdin = devNull
+	return func() {
+		cleanup()
+		_ = devNull.Close()
+	}
+}
+
 // openAgentLogFile opens the agent log file for writing.
 func openAgentLogFile(agentLogPath string, attempt int) *os.File {
 	var logFile *os.File
@@ -2235,7 +3316,7 @@ func openAgentLogFile(agentLogPath string, attempt int) *os.File {
 	} else {
 		logFile, err = os.OpenFile(agentLogPath, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
 		if err == nil {
-			separator := fmt.Sprintf("\n\n=== RETRY %d (after %v delay) ===\n\n", attempt, getRetryDelay(attempt))
+			separator := fmt.Sprintf("\n\n=== RETRY %d (after %v delay) ===\n\n", attempt, retryPolicy(config.RetryProvider).Wait(attempt))
 			_, _ = logFile.WriteString(separator)
 		}
 	}
@@ -2246,17 +3327,37 @@ func openAgentLogFile(agentLogPath string, attempt int) *os.File {
 	return logFile
 }
 
-// writeAgentTimeoutFooter appends deterministic timeout evidence to the agent log.
-func writeAgentTimeoutFooter(logFile *os.File, attempt int, timeout, runDuration time.Duration) {
+// writeAgentTimeoutFooter appends deterministic timeout evidence to the agent
+// log, including how the agent was stopped.
+func writeAgentTimeoutFooter(logFile *os.File, attempt int, timeout, runDuration time.Duration, termination string) {
 	if logFile == nil {
 		return
 	}
 	_, _ = fmt.Fprintf(
 		logFile,
-		"\n\nHARNESS: agent timed out (attempt=%d timeout_seconds=%.3f duration_seconds=%.3f)\n",
+		"\n\nHARNESS: agent timed out (attempt=%d timeout_seconds=%.3f duration_seconds=%.3f termination=%s)\n",
 		attempt+1,
 		timeout.Seconds(),
 		runDuration.Seconds(),
+		cmp.Or(termination, "exited"),
+	)
+	_ = logFile.Sync()
+}
+
+// writeAgentBudgetFooter appends evidence of a turn, tool-call, or token limit stop to
+// the agent log.
+func writeAgentBudgetFooter(logFile *os.File, attempt int, limit string, turns, toolCalls int, tokens int64) {
+	if logFile == nil {
+		return
+	}
+	_, _ = fmt.Fprintf(
+		logFile,
+		"\n\nHARNESS: agent budget exhausted (attempt=%d limit=%s turns=%d tool_calls=%d tokens=%d)\n",
+		attempt+1,
+		limit,
+		turns,
+		toolCalls,
+		tokens,
 	)
 	_ = logFile.Sync()
 }
@@ -2338,6 +3439,22 @@ func toolchainInfo(lang task.Language) string {
 	}
 }
 
+// filterTranslatedTasks keeps the tasks that have a translation into lang and
+// returns the IDs of the rest. Every task is kept for the source language.
+func filterTranslatedTasks(list []*task.Task, lang string) (kept []*task.Task, missing []string) {
+	if task.IsSourcePromptLang(lang) {
+		return list, nil
+	}
+	for _, t := range list {
+		if _, ok := t.Localized(lang); ok {
+			kept = append(kept, t)
+		} else {
+			missing = append(missing, t.ID())
+		}
+	}
+	return kept, missing
+}
+
 func buildAgentPrompt(t *task.Task, useMCPTools, useSkills bool, mcpPrompt string) string {
 	stubFiles := make([]string, 0, len(t.Files.Stub))
 	for _, f := range t.Files.Stub {
@@ -2386,6 +3503,18 @@ func buildAgentPrompt(t *task.Task, useMCPTools, useSkills bool, mcpPrompt strin
 6. Ensure thread-safety if the tests use concurrent operations.`
 	}
 
+	testFileList := strings.Join(testFiles, ", ")
+	if len(testFiles) == 0 {
+		// The agent works from the spec alone (test visibility "none").
+		testFileList = "none provided; work from the description and the stub file(s)"
+		taskInstructions = strings.NewReplacer(
+			"Read the visible test file(s) to understand expected behavior and edge cases.",
+			"Infer the expected behavior and edge cases from the description and the stub file(s).",
+			"Read the stub file(s) and test file(s) to understand", "Read the stub file(s) to understand",
+			"if the tests use concurrent operations", "if the API may be used concurrently",
+		).Replace(taskInstructions)
+	}
+
 	prompt := fmt.Sprintf(`You are solving a coding task called "%s".
 
 TASK INFO:
@@ -2417,15 +3546,24 @@ RULES:
 - Evaluation fails if you modify protected files.
 - Do NOT navigate to parent directories or read files outside the workspace.%s%s`,
 		t.Name, t.Language, t.Tier, t.Difficulty, t.Description,
-		strings.Join(stubFiles, ", "), strings.Join(testFiles, ", "),
+		strings.Join(stubFiles, ", "), testFileList,
 		toolchainInfo(t.Language), mcpEnvironmentLine, skillsEnvironmentLine, taskInstructions, mcpImportantLine, skillsImportantLine, mcpRuleLine, skillsRuleLine)
 
 	return prompt
 }
 
+// protectedTaskFiles returns the task files the agent must leave unchanged:
+// its visible tests, unless they were hidden from it, and its support files.
+func protectedTaskFiles(t *task.Task) []string {
+	if testsHidden() {
+		return append([]string{}, t.Files.Support...)
+	}
+	return append(append([]string{}, t.Files.Test...), t.Files.Support...)
+}
+
 func detectModifiedTaskFiles(loader *task.Loader, t *task.Task, workspaceDir string) ([]string, error) {
 	var modified []string
-	for _, filename := range append(append([]string{}, t.Files.Test...), t.Files.Support...) {
+	for _, filename := range protectedTaskFiles(t) {
 		want, err := loader.ReadTaskFile(t, filename)
 		if err != nil {
 			return nil, fmt.Errorf("reading canonical %s: %w", filename, err)
@@ -2486,7 +3624,7 @@ func writeIntegrityViolationArtifacts(
 	}
 
 	canonicalByWorkspace := make(map[string]string)
-	for _, filename := range append(append([]string{}, t.Files.Test...), t.Files.Support...) {
+	for _, filename := range protectedTaskFiles(t) {
 		canonicalByWorkspace[task.StripTxtExtension(filename)] = filename
 	}
 
@@ -2603,6 +3741,60 @@ func buildIntegrityDiffContent(expectedPath, actualPath string, expectedExists,
 	}
 }
 
+// buildSolutionDiff returns a unified diff of the task's stub files against the
+// agent's final versions in workspaceDir. Paths are labelled a/<file> and b/<file>
+// so the output applies cleanly with `git apply` against a fresh workspace.
+func buildSolutionDiff(loader *task.Loader, t *task.Task, workspaceDir string) (string, error) {
+	tmpDir, err := os.MkdirTemp("", "sanity-solution-diff-*")
+	if err != nil {
+		return "", fmt.Errorf("creating temp dir: %w", err)
+	}
+	defer func() { _ = os.RemoveAll(tmpDir) }()
+
+	var sb strings.Builder
+	for _, filename := range t.Files.Stub {
+		name := task.StripTxtExtension(filename)
+		stub, err := loader.ReadTaskFile(t, filename)
+		if err != nil {
+			return "", fmt.Errorf("reading canonical %s: %w", filename, err)
+		}
+		stubPath := filepath.Join(tmpDir, filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(stubPath), 0o755); err != nil {
+			return "", fmt.Errorf("creating stub dir: %w", err)
+		}
+		if err := os.WriteFile(stubPath, stub, 0o644); err != nil {
+			return "", fmt.Errorf("writing stub copy: %w", err)
+		}
+
+		out, err := unifiedDiff(stubPath, filepath.Join(workspaceDir, filepath.FromSlash(name)), filepath.ToSlash(name))
+		if err != nil {
+			return "", err
+		}
+		sb.WriteString(out)
+	}
+	return sb.String(), nil
+}
+
+// unifiedDiff returns `diff -u` output for two files labelled a/<label> and
+// b/<label>. A missing file is diffed as empty.
+func unifiedDiff(oldPath, newPath, label string) (string, error) {
+	if _, err := os.Stat(oldPath); err != nil {
+		oldPath = os.DevNull
+	}
+	if _, err := os.Stat(newPath); err != nil {
+		newPath = os.DevNull
+	}
+	cmd := exec.CommandContext(context.Background(), "diff", "-u",
+		"--label", "a/"+label, "--label", "b/"+label, oldPath, newPath)
+	out, err := cmd.Output()
+	// diff exits 1 when files differ; only exit codes >1 are real failures.
+	var exitErr *exec.ExitError
+	if err != nil && (!errors.As(err, &exitErr) || exitErr.ExitCode() > 1) {
+		return "", fmt.Errorf("diffing %s: %w", label, err)
+	}
+	return string(out), nil
+}
+
 func writeTaskFilesToWorkspace(loader *task.Loader, t *task.Task, workspaceDir string, files []string) error {
 	for _, filename := range files {
 		content, err := loader.ReadTaskFile(t, filename)
@@ -2667,7 +3859,7 @@ func copyDirContents(src, dst string) error {
 func buildAgentCommand(
 	ctx context.Context,
 	agentCfg *config.AgentConfig,
-	prompt, model, reasoning string,
+	prompt, model, reasoning, workspace string,
 	disableMCP, useMCPTools bool,
 	agentName string,
 ) *exec.Cmd {
@@ -2709,18 +3901,21 @@ func buildAgentCommand(
 		}
 	}
 
-	// Apply prompt prefix if configured (e.g., "ulw" for OMO ultrawork mode).
-	if agentCfg.PromptPrefix != "" {
-		prompt = agentCfg.PromptPrefix + " " + prompt
-	}
-
-	// Process args, replacing {prompt} placeholder
+	// Process args, expanding placeholders. An arg that is only a placeholder
+	// with no value (e.g. {model} without --model) is dropped.
+	placeholders := strings.NewReplacer(
+		"{prompt}", agentPromptText(agentCfg, prompt),
+		"{prompt_file}", filepath.Join(workspace, config.AgentPromptFile),
+		"{model}", model,
+		"{reasoning}", reasoning,
+		"{workspace}", workspace,
+	)
 	for _, arg := range agentCfg.Args {
-		if arg == "{prompt}" {
-			args = append(args, prompt)
-		} else {
-			args = append(args, arg)
+		expanded := placeholders.Replace(arg)
+		if expanded == "" && arg != "" && arg != "{prompt}" {
+			continue
 		}
+		args = append(args, expanded)
 	}
 
 	// Add model flag if specified (after position)
@@ -2753,14 +3948,25 @@ func buildAgentCommand(
 	return cmd
 }
 
+// agentPromptText returns the prompt as the agent receives it, with any
+// configured prefix (e.g., "ulw" for OMO ultrawork mode).
+func agentPromptText(agentCfg *config.AgentConfig, prompt string) string {
+	if agentCfg.PromptPrefix != "" {
+		return agentCfg.PromptPrefix + " " + prompt
+	}
+	return prompt
+}
+
 // wrapCommandWithSandbox wraps an exec.Cmd in a bubblewrap sandbox.
 // The sandbox restricts filesystem access so the agent can only write to the
 // workspace directory and /tmp. The rest of the filesystem (including $HOME)
 // is mounted read-only. Network access is preserved for LLM API calls.
+// extraArgs are added after the sandbox's own mounts.
 func wrapCommandWithSandbox(
 	ctx context.Context,
 	cmd *exec.Cmd,
 	extraWritableDirs, sharedReadWriteDirs, sharedReadOnlyDirs, readableDenylist []string,
+	extraArgs ...string,
 ) *exec.Cmd {
 	bwrapArgs := buildSandboxArgs(
 		cmd.Dir,
@@ -2770,6 +3976,7 @@ func wrapCommandWithSandbox(
 		sharedReadOnlyDirs,
 		readableDenylist,
 	)
+	bwrapArgs = append(bwrapArgs, extraArgs...)
 	bwrapArgs = append(bwrapArgs, "--", cmd.Path)
 	bwrapArgs = append(bwrapArgs, cmd.Args[1:]...)
 
@@ -3134,14 +4341,27 @@ const openCodeGlobalMCPTimeoutMS = 180000
 // buildOpenCodeMCPDisableConfig creates the OPENCODE_CONFIG_CONTENT value
 // by merging the user's existing config with the MCP disable settings.
 func buildOpenCodeMCPDisableConfig() string {
-	return buildOpenCodeMCPConfig(true, false)
+	return buildOpenCodeMCPConfig(true, false, nil)
 }
 
 // buildOpenCodeMCPConfig creates the OPENCODE_CONFIG_CONTENT value with optional MCP settings.
 // It preserves the user's existing OpenCode config and applies runtime overrides.
-func buildOpenCodeMCPConfig(disableMCP, useMCPTools bool) string {
+// Servers are added as local MCP servers.
+func buildOpenCodeMCPConfig(disableMCP, useMCPTools bool, servers []mcpServer) string {
 	overrides := map[string]any{}
 
+	if len(servers) > 0 {
+		mcp := make(map[string]any, len(servers))
+		for _, s := range servers {
+			entry := map[string]any{"type": "local", "command": append([]string{s.Command}, s.Args...), "enabled": true}
+			if len(s.Env) > 0 {
+				entry["environment"] = s.Env
+			}
+			mcp[s.Name] = entry
+		}
+		overrides["mcp"] = mcp
+	}
+
 	// MCP tools are registered as "servername_toolname", so "*_*" matches all.
 	if disableMCP {
 		overrides["tools"] = map[string]any{
@@ -3197,7 +4417,7 @@ func buildAgentEnv(agentEnv map[string]string, disableMCP, useMCPTools bool, age
 
 	// Inject OpenCode config overrides for MCP behavior.
 	if needsOpenCodeConfig {
-		configContent := buildOpenCodeMCPConfig(disableMCP, useMCPTools)
+		configContent := buildOpenCodeMCPConfig(disableMCP, useMCPTools, nil)
 		env = append(env, "OPENCODE_CONFIG_CONTENT="+configContent)
 	}
 
@@ -3368,6 +4588,11 @@ type LeaderboardSubmission struct {
 	Reasoning string `json:"reasoning,omitempty"`
 	Timestamp string `json:"timestamp"`
 
+	// Model metadata
+	ModelFamily  string  `json:"model_family,omitempty"`
+	ModelParamsB float64 `json:"model_params_b,omitempty"`
+	ModelQuant   string  `json:"model_quant,omitempty"`
+
 	// Core metrics
 	PassRate             float64 `json:"pass_rate"`
 	WeightedPassRate     float64 `json:"weighted_pass_rate"`
@@ -3437,6 +4662,9 @@ func generateLeaderboardSubmission(summary EvalSummary, attestation *EvalAttesta
 		Model:                           summary.Model,
 		Reasoning:                       summary.Reasoning,
 		Timestamp:                       summary.Timestamp,
+		ModelFamily:                     summary.ModelFamily,
+		ModelParamsB:                    summary.ModelParamsB,
+		ModelQuant:                      summary.ModelQuant,
 		PassRate:                        summary.PassRate,
 		WeightedPassRate:                summary.WeightedPassRate,
 		Passed:                          summary.Passed,
@@ -3502,10 +4730,20 @@ func generateEvalReport(summary EvalSummary, attestation *EvalAttestation) strin
 	sb.WriteString("# Evaluation Report\n\n")
 	writeReportSummary(&sb, summary)
 	writeReportQuality(&sb, summary)
+	writeReportExperiment(&sb, summary)
 	writeReportBehaviorTelemetry(&sb, summary)
+	writeReportToolUsage(&sb, summary)
+	writeReportNetwork(&sb, summary)
+	writeReportDistractors(&sb, summary)
 	writeReportByLanguage(&sb, summary)
 	writeReportByTier(&sb, summary)
 	writeReportTaskResults(&sb, summary)
+	writeReportDurationAnomalies(&sb, summary)
+	writeReportBenchmarks(&sb, summary)
+	writeReportFollowUps(&sb, summary)
+	writeReportRepairs(&sb, summary)
+	writeReportJudge(&sb, summary)
+	writeReportAttemptChanges(&sb, summary)
 	writeReportExternalFailures(&sb, summary)
 	writeReportErrors(&sb, summary)
 	writeReportVerification(&sb, attestation)
@@ -3526,6 +4764,54 @@ func writeReportSummary(sb *strings.Builder, summary EvalSummary) {
 	if summary.Reasoning != "" {
 		fmt.Fprintf(sb, "| Reasoning Effort | %s |\n", summary.Reasoning)
 	}
+	if summary.ModelFamily != "" {
+		fmt.Fprintf(sb, "| Model Family | %s |\n", summary.ModelFamily)
+	}
+	if summary.ModelParamsB > 0 {
+		fmt.Fprintf(sb, "| Model Size | %gB (%s) |\n", summary.ModelParamsB, sizeBucket(summary.ModelParamsB))
+	}
+	if summary.ModelQuant != "" {
+		fmt.Fprintf(sb, "| Quantization | %s |\n", summary.ModelQuant)
+	}
+	if summary.PromptLang != "" {
+		fmt.Fprintf(sb, "| Prompt Language | %s |\n", summary.PromptLang)
+	}
+	if summary.Seed != 0 {
+		fmt.Fprintf(sb, "| Seed | %d |\n", summary.Seed)
+	}
+	if len(summary.MCPServers) > 0 {
+		fmt.Fprintf(sb, "| MCP Servers | %s |\n", strings.Join(summary.MCPServers, ", "))
+	}
+	if summary.Sampling != nil {
+		fmt.Fprintf(sb, "| Sampling | %s |\n", summary.Sampling.label())
+	}
+	if summary.RepairAttempts > 0 {
+		fmt.Fprintf(sb, "| Repair Attempts | Up to %d, %s feedback |\n", summary.RepairAttempts, summary.AttemptFeedback)
+	}
+	if summary.FlakyPasses > 0 {
+		fmt.Fprintf(sb, "| Flaky Passes | %d (passed after rerunning timing tests) |\n", summary.FlakyPasses)
+	}
+	if summary.Interrupted {
+		fmt.Fprintf(sb, "| Interrupted | Partial results, %d task(s) not graded |\n", summary.RemainingTasks)
+	}
+	if summary.Chaos != nil {
+		fmt.Fprintf(sb, "| Chaos | %s, %d injected failures |\n", summary.Chaos, summary.ChaosInjections)
+	}
+	if summary.AgentMemory != "" && summary.AgentMemory != config.AgentMemoryHost {
+		fmt.Fprintf(sb, "| Agent Memory | %s |\n", agentMemoryLabel(summary.AgentMemory))
+	}
+	if visibility := summaryTestVisibility(summary); visibility != config.TestVisibilityVisibleOnly {
+		fmt.Fprintf(sb, "| Test Visibility | %s |\n", testVisibilityLabel(visibility))
+	}
+	if summary.UploadURL != "" {
+		fmt.Fprintf(sb, "| Uploaded To | %s |\n", summary.UploadURL)
+	}
+	if summary.Toolchain != "" {
+		fmt.Fprintf(sb, "| Toolchain | %s |\n", summary.Toolchain)
+	}
+	if summary.EncryptedArtifacts {
+		sb.WriteString("| Encrypted Artifacts | agent.log, judge.json (age) |\n")
+	}
 	if summary.UseMCPTools {
 		sb.WriteString("| MCP Tools Mode | Yes |\n")
 	}
@@ -3547,6 +4833,15 @@ func writeReportSummary(sb *strings.Builder, summary EvalSummary) {
 	fmt.Fprintf(sb, "| Weighted Pass Rate | **%.1f%%** |\n", summary.WeightedPassRate)
 	fmt.Fprintf(sb, "| Weighted Score | %.2f / %.2f |\n", summary.WeightedScore, summary.MaxPossibleScore)
 	fmt.Fprintf(sb, "| Duration | %.1fs |\n", summary.Duration)
+	if summary.AgentCPUTime > 0 || summary.ValidateCPUTime > 0 {
+		fmt.Fprintf(sb, "| CPU Time | %.1fs agent, %.1fs validation |\n", summary.AgentCPUTime, summary.ValidateCPUTime)
+	}
+	if summary.AgentEnergy > 0 || summary.ValidateEnergy > 0 {
+		fmt.Fprintf(sb, "| Energy (RAPL) | %.0f J agent, %.0f J validation |\n", summary.AgentEnergy, summary.ValidateEnergy)
+	}
+	if summary.Usage != nil {
+		fmt.Fprintf(sb, "| Tokens | %s |\n", summary.Usage)
+	}
 	sb.WriteString("\n")
 }
 
@@ -3554,6 +4849,18 @@ func writeReportQuality(sb *strings.Builder, summary EvalSummary) {
 	sb.WriteString("## Quality Breakdown\n\n")
 	fmt.Fprintf(sb, "- **Integrity Violations** (modified test files): %d\n", summary.IntegrityViolations)
 	fmt.Fprintf(sb, "- **Failures**: %d\n", summary.Failed-summary.IntegrityViolations)
+	if summary.TaintedTasks > 0 {
+		fmt.Fprintf(sb, "- **Tainted tasks** (agent accessed hidden tests): %d\n", summary.TaintedTasks)
+	}
+	if summary.NoOpTasks > 0 {
+		fmt.Fprintf(sb, "- **No-op tasks** (agent changed no files): %d\n", summary.NoOpTasks)
+	}
+	if summary.DurationAnomalies > 0 {
+		fmt.Fprintf(sb, "- **Duration anomalies** (far faster or slower than past runs): %d\n", summary.DurationAnomalies)
+	}
+	if summary.LikelyMemorizedTasks > 0 {
+		fmt.Fprintf(sb, "- **Likely memorized** (near-verbatim match with a known public solution): %d\n", summary.LikelyMemorizedTasks)
+	}
 	if summary.SkippedExternalTasks > 0 {
 		fmt.Fprintf(sb, "- **Skipped external tasks** (not scored): %d\n", summary.SkippedExternalTasks)
 	}
@@ -3672,12 +4979,26 @@ func writeReportByTier(sb *strings.Builder, summary EvalSummary) {
 
 func writeReportTaskResults(sb *strings.Builder, summary EvalSummary) {
 	sb.WriteString("## Task Results\n\n")
-	sb.WriteString("| Task | Status | Weight | Score | Duration |\n")
-	sb.WriteString("|------|--------|--------|-------|----------|\n")
+	sb.WriteString("| Task | Status | Weight | Score | Duration | Peak Memory | Binary Size |\n")
+	sb.WriteString("|------|--------|--------|-------|----------|-------------|-------------|\n")
 	for _, r := range summary.Results {
 		statusIcon, status := getResultStatusDisplay(r)
-		fmt.Fprintf(sb, "| %s | %s %s | %.2f | %.2f | %.1fs |\n",
-			r.Task, statusIcon, status, r.Weight, r.WeightedScore, r.Duration)
+		if r.Tainted {
+			status += " ⚠️ TAINTED"
+		}
+		if r.Plagiarism != nil && r.Plagiarism.Flagged {
+			status += fmt.Sprintf(" ⚠️ MEMORIZED (%.0f%% of %s)", r.Plagiarism.Similarity*100, r.Plagiarism.Source)
+		}
+		peakMemory := "-"
+		if r.PeakMemoryBytes > 0 {
+			peakMemory = resultpkg.FormatMemory(r.PeakMemoryBytes)
+		}
+		binarySize := "-"
+		if r.BinarySizeBytes > 0 {
+			binarySize = resultpkg.FormatSize(r.BinarySizeBytes)
+		}
+		fmt.Fprintf(sb, "| %s | %s %s | %.2f | %.2f | %.1fs | %s | %s |\n",
+			r.Task, statusIcon, status, r.Weight, r.WeightedScore, r.Duration, peakMemory, binarySize)
 	}
 	sb.WriteString("\n")
 }
@@ -3686,6 +5007,8 @@ func getResultStatusDisplay(r EvalResult) (icon, text string) {
 	switch {
 	case r.Status == task.StatusIntegrityViolation:
 		return "🚫", "VIOLATION"
+	case r.Status == task.StatusFlakyPass:
+		return "✅", "FLAKY PASS"
 	case r.Passed:
 		return "✅", "PASS"
 	default:
@@ -3780,6 +5103,7 @@ func parseAgentBehaviorMetrics(logPath, workspaceDir string) agentBehaviorMetric
 		ToolchainSearchAttempts:      toolchainSearches,
 		SkillsUsed:                   skillsSignals > 0,
 		SkillsUsageSignals:           skillsSignals,
+		ToolCalls:                    countToolCalls(lines),
 	}
 }
 
@@ -4121,38 +5445,6 @@ func detectQuotaError(logPath string) (bool, bool) {
 	return false, false
 }
 
-// getRetryDelay returns the delay for the given quota retry attempt (1-indexed).
-func getRetryDelay(attempt int) time.Duration {
-	switch attempt {
-	case 1:
-		return quotaRetryDelay1
-	case 2:
-		return quotaRetryDelay2
-	case 3:
-		return quotaRetryDelay3
-	case 4:
-		return quotaRetryDelay4
-	default:
-		return quotaRetryDelay5
-	}
-}
-
-// getInfraRetryDelay returns the delay for the given infra retry attempt (1-indexed).
-func getInfraRetryDelay(attempt int) time.Duration {
-	switch attempt {
-	case 1:
-		return infraRetryDelay1
-	case 2:
-		return infraRetryDelay2
-	case 3:
-		return infraRetryDelay3
-	case 4:
-		return infraRetryDelay4
-	default:
-		return infraRetryDelay5
-	}
-}
-
 // isInfraFailure checks if the agent log indicates an infrastructure failure
 // (empty or near-empty output suggesting the provider never responded).
 // It strips retry separator lines and whitespace to avoid false negatives
@@ -4235,23 +5527,51 @@ func saveRunConfig(outputDir string, allTasks []*task.Task) error {
 	}
 
 	runCfg := RunConfig{
-		Agent:          evalAgent,
-		Model:          evalModel,
-		Reasoning:      evalReasoning,
-		Tier:           evalTier,
-		Difficulty:     evalDifficulty,
-		Lang:           evalLang,
-		Tasks:          evalTasks,
-		Timeout:        evalTimeout,
-		Parallel:       evalParallel,
-		UseMCPTools:    evalUseMCPTools,
-		UseSkills:      evalUseSkills,
-		DisableMCP:     evalDisableMCP,
-		NoSandbox:      evalNoSandbox,
-		Legacy:         evalLegacy,
-		KeepWorkspaces: evalKeepWorkspaces,
-		TaskList:       taskList,
-		CreatedAt:      time.Now().Format(time.RFC3339),
+		Agent:           evalAgent,
+		Model:           evalModel,
+		Reasoning:       evalReasoning,
+		ModelFamily:     evalModelFamily,
+		ModelParams:     evalModelParams,
+		ModelQuant:      evalModelQuant,
+		Tier:            evalTier,
+		Difficulty:      evalDifficulty,
+		Lang:            evalLang,
+		Tasks:           evalTasks,
+		Filter:          evalFilter,
+		Exclude:         evalExclude,
+		Tags:            evalTags,
+		Timeout:         evalTimeout,
+		Parallel:        evalParallel,
+		UseMCPTools:     evalUseMCPTools,
+		UseSkills:       evalUseSkills,
+		SkillPacks:      evalSkillPacks,
+		MaxTurns:        evalMaxTurns,
+		MaxToolCalls:    evalMaxToolCalls,
+		MaxTokens:       evalMaxTokens,
+		NetworkAudit:    evalNetworkAudit,
+		Judge:           evalJudge,
+		PromptLang:      evalPromptLang,
+		DistractorPack:  evalDistractors,
+		Chaos:           evalChaosProfile,
+		PromptVariants:  evalPromptVariants,
+		VariantSeed:     evalVariantSeed,
+		Seed:            evalSeed,
+		AgentMemory:     evalAgentMemoryMode,
+		TestVisibility:  evalTestVisibility,
+		RepairAttempts:  evalRepairAttempts,
+		AttemptFeedback: evalAttemptFeedback,
+		Upload:          evalUpload,
+		Toolchain:       evalToolchain,
+		DisableMCP:      evalDisableMCP,
+		MCPServers:      evalMCPServers,
+		Temperature:     evalTemperature,
+		TopP:            evalTopP,
+		MinP:            evalMinP,
+		NoSandbox:       evalNoSandbox,
+		Legacy:          evalLegacy,
+		KeepWorkspaces:  evalKeepWorkspaces,
+		TaskList:        taskList,
+		CreatedAt:       time.Now().Format(time.RFC3339),
 	}
 
 	data, err := json.MarshalIndent(runCfg, "", "  ")
@@ -4282,23 +5602,55 @@ func applyRunConfig(runCfg *RunConfig) {
 	evalAgent = runCfg.Agent
 	evalModel = runCfg.Model
 	evalReasoning = runCfg.Reasoning
+	evalModelFamily = runCfg.ModelFamily
+	evalModelParams = runCfg.ModelParams
+	evalModelQuant = runCfg.ModelQuant
 	evalTier = runCfg.Tier
 	evalDifficulty = runCfg.Difficulty
 	evalLang = runCfg.Lang
 	evalTasks = runCfg.Tasks
+	evalFilter = runCfg.Filter
+	evalExclude = runCfg.Exclude
+	evalTags = runCfg.Tags
 	evalTimeout = runCfg.Timeout
 	evalParallel = runCfg.Parallel
 	evalUseMCPTools = runCfg.UseMCPTools
 	evalUseSkills = runCfg.UseSkills
+	evalSkillPacks = runCfg.SkillPacks
 	evalDisableMCP = runCfg.DisableMCP
+	evalMCPServers = runCfg.MCPServers
+	evalTemperature = runCfg.Temperature
+	evalTopP = runCfg.TopP
+	evalMinP = runCfg.MinP
 	evalNoSandbox = runCfg.NoSandbox
 	evalLegacy = runCfg.Legacy
 	evalKeepWorkspaces = runCfg.KeepWorkspaces
-}
-
-// findCompletedTasks returns a set of task slugs that have validation.log files.
+	evalMaxTurns = runCfg.MaxTurns
+	evalMaxToolCalls = runCfg.MaxToolCalls
+	evalMaxTokens = runCfg.MaxTokens
+	evalNetworkAudit = runCfg.NetworkAudit
+	evalJudge = runCfg.Judge
+	evalPromptLang = runCfg.PromptLang
+	evalDistractors = runCfg.DistractorPack
+	evalChaosProfile = runCfg.Chaos
+	evalPromptVariants = runCfg.PromptVariants
+	evalVariantSeed = runCfg.VariantSeed
+	evalSeed = runCfg.Seed
+	evalAgentMemoryMode = runCfg.AgentMemory
+	evalTestVisibility = runCfg.TestVisibility
+	evalRepairAttempts = runCfg.RepairAttempts
+	evalAttemptFeedback = runCfg.AttemptFeedback
+	evalUpload = runCfg.Upload
+	evalToolchains = runCfg.Toolchain
+}
+
+// findCompletedTasks returns the set of task slugs that were graded in
+// outputDir. A task counts once its task-result.json is written. Runs from
+// before per-task results fall back to validation.log, but only when their
+// summary.json holds the results; otherwise every task is rerun.
 func findCompletedTasks(outputDir string) (map[string]bool, error) {
-	completed := make(map[string]bool)
+	withResult := make(map[string]bool)
+	withLog := make(map[string]bool)
 
 	entries, err := os.ReadDir(outputDir)
 	if err != nil {
@@ -4310,19 +5662,80 @@ func findCompletedTasks(outputDir string) (map[string]bool, error) {
 			continue
 		}
 
-		// Check if validation.log exists in this task directory.
-		validationLog := filepath.Join(outputDir, entry.Name(), "validation.log")
-		if _, err := os.Stat(validationLog); err == nil {
-			// Directory name format is "language-slug", convert to "language/slug".
-			name := entry.Name()
-			if idx := strings.Index(name, "-"); idx > 0 {
-				taskSlug := name[:idx] + "/" + name[idx+1:]
-				completed[taskSlug] = true
-			}
+		// Directory name format is "language-slug", convert to "language/slug".
+		name := entry.Name()
+		idx := strings.Index(name, "-")
+		if idx <= 0 {
+			continue
+		}
+		taskSlug := name[:idx] + "/" + name[idx+1:]
+		if _, err := os.Stat(filepath.Join(outputDir, name, taskResultFile)); err == nil {
+			withResult[taskSlug] = true
+		}
+		if _, err := os.Stat(filepath.Join(outputDir, name, "validation.log")); err == nil {
+			withLog[taskSlug] = true
+		}
+	}
+
+	if len(withResult) > 0 {
+		return withResult, nil
+	}
+	if _, err := os.Stat(filepath.Join(outputDir, "summary.json")); err == nil {
+		return withLog, nil
+	}
+	return withResult, nil
+}
+
+// taskResultFile is the per-task result written as soon as a task is graded,
+// so a run killed without writing summary.json can still be resumed.
+const taskResultFile = "task-result.json"
+
+// saveTaskResult writes result to its task directory. The write goes through
+// a temporary file so a crash never leaves a truncated result behind.
+func saveTaskResult(outputDir string, result EvalResult) {
+	lang, slug, ok := strings.Cut(result.Task, "/")
+	if !ok {
+		return
+	}
+	taskDir := filepath.Join(outputDir, lang+"-"+slug)
+	data, err := json.MarshalIndent(result, "", "  ")
+	if err == nil {
+		err = os.MkdirAll(taskDir, 0o755)
+	}
+	if err == nil {
+		tmp := filepath.Join(taskDir, taskResultFile+".tmp")
+		if err = os.WriteFile(tmp, data, 0o644); err == nil {
+			err = os.Rename(tmp, filepath.Join(taskDir, taskResultFile))
 		}
 	}
+	if err != nil {
+		logger.Warn("failed to save task result", "task", result.Task, "error", err)
+	}
+}
 
-	return completed, nil
+// withTaskResults adds the per-task results saved in outputDir to results,
+// for tasks that results does not already cover. Results are only missing
+// from summary.json when the run was killed before it could write one.
+func withTaskResults(results []EvalResult, outputDir string) []EvalResult {
+	known := make(map[string]bool, len(results))
+	for _, r := range results {
+		known[r.Task] = true
+	}
+	paths, _ := filepath.Glob(filepath.Join(outputDir, "*", taskResultFile))
+	sort.Strings(paths)
+	for _, path := range paths {
+		data, err := os.ReadFile(path)
+		if err != nil {
+			continue
+		}
+		var r EvalResult
+		if err := json.Unmarshal(data, &r); err != nil || r.Task == "" || known[r.Task] {
+			continue
+		}
+		known[r.Task] = true
+		results = append(results, r)
+	}
+	return results
 }
 
 // loadPreviousSummary loads results from a previous eval run for merging.
@@ -4336,12 +5749,12 @@ func loadPreviousSummary(outputDir string) (*EvalSummary, error) {
 		return nil, fmt.Errorf("reading summary: %w", err)
 	}
 
-	var summary EvalSummary
-	if err := json.Unmarshal(data, &summary); err != nil {
+	summary, err := parseEvalSummary(data)
+	if err != nil {
 		return nil, fmt.Errorf("parsing summary: %w", err)
 	}
 
-	return &summary, nil
+	return summary, nil
 }
 
 // loadPreviousAttestation loads attestation from a previous eval run.
@@ -4412,7 +5825,7 @@ func prepareResumedTasks(
 	return orderedTasks, tasksToRun, nil
 }
 
-// cleanIncompleteTaskDirs removes task directories that don't have validation.log.
+// cleanIncompleteTaskDirs removes the directories of tasks that were not graded.
 func cleanIncompleteTaskDirs(outputDir string, completed map[string]bool, allTasks []*task.Task) error {
 	for _, t := range allTasks {
 		taskSlug := string(t.Language) + "/" + t.Slug
@@ -4432,21 +5845,55 @@ func cleanIncompleteTaskDirs(outputDir string, completed map[string]bool, allTas
 	return nil
 }
 
+// forceStopCtx is cancelled by a second interrupt signal. The first one
+// stops agents but lets grading of finished work complete; the second stops
+// that too. Containers are still removed on the way out.
+var forceStopCtx = context.Background()
+
 // setupInterruptHandler creates a context that is cancelled on interrupt signals.
+// A second signal also cancels forceStopCtx, and a third kills the process.
 // The returned cancel function should be deferred to clean up signal handling.
 func setupInterruptHandler() (context.Context, context.CancelFunc) {
 	ctx, cancel := context.WithCancel(context.Background())
-	sigCh := make(chan os.Signal, 1)
+	force, cancelForce := context.WithCancel(context.Background())
+	forceStopCtx = force
+	done := make(chan struct{})
+	sigCh := make(chan os.Signal, 2)
 	signal.Notify(sigCh, os.Interrupt, syscall.SIGTERM)
 	go func() {
+		defer signal.Stop(sigCh)
 		select {
 		case <-sigCh:
+			fmt.Println("\n\033[33m⚠ Interrupt received. Finishing up; press Ctrl-C again to stop now.\033[0m")
 			cancel()
-		case <-ctx.Done():
+		case <-done:
+			return
+		}
+		select {
+		case <-sigCh:
+			fmt.Println("\n\033[33m⚠ Second interrupt. Stopping grading...\033[0m")
+			cancelForce()
+		case <-done:
 		}
-		signal.Stop(sigCh)
 	}()
-	return ctx, cancel
+	var once sync.Once
+	return ctx, func() {
+		once.Do(func() { close(done) })
+		cancel()
+		cancelForce()
+	}
+}
+
+// gradingContext returns a context for grading work an agent already
+// finished. It keeps ctx's values but outlives an interrupt of it, stopping
+// only at a second interrupt.
+func gradingContext(ctx context.Context) (context.Context, context.CancelFunc) {
+	gradeCtx, cancel := context.WithCancel(context.WithoutCancel(ctx))
+	stop := context.AfterFunc(forceStopCtx, cancel)
+	return gradeCtx, func() {
+		stop()
+		cancel()
+	}
 }
 
 // checkInterrupted checks if an interrupt signal has been received.
@@ -4536,20 +5983,54 @@ func init() {
 	evalCmd.Flags().StringVar(&evalAgent, "agent", "", "agent to evaluate (see --help for list)")
 	evalCmd.Flags().StringVar(&evalModel, "model", "", "model to use (e.g., gemini-2.5-pro or google/gemini-2.5-flash)")
 	evalCmd.Flags().StringVar(&evalReasoning, "reasoning", "", "reasoning effort level (e.g., off, none, low, medium, high)")
+	evalCmd.Flags().StringVar(&evalReasoning, "reasoning-effort", "", "same as --reasoning (default [sampling] reasoning_effort)")
+	evalCmd.Flags().StringVar(&evalTemperature, "temperature", "", "sampling temperature passed to the agent, 0 to 2 (default [sampling] temperature)")
+	evalCmd.Flags().StringVar(&evalTopP, "top-p", "", "nucleus sampling top_p passed to the agent, 0 to 1 (default [sampling] top_p)")
+	evalCmd.Flags().StringVar(&evalMinP, "min-p", "", "min_p sampling cutoff passed to the agent, 0 to 1 (default [sampling] min_p)")
 	evalCmd.Flags().StringVar(&evalTasks, "tasks", "", "comma-separated list of task slugs")
 	evalCmd.Flags().StringVar(&evalLang, "lang", "", "filter by language (go, rust, typescript)")
 	evalCmd.Flags().StringVar(&evalTier, "tier", "core", "filter by tier (core, extended, all)")
 	evalCmd.Flags().StringVar(&evalDifficulty, "difficulty", "", "filter by difficulty (comma-separated)")
+	evalCmd.Flags().StringVar(&evalFilter, "filter", "", "comma-separated task ID globs to run, e.g. \"rust/*\" or \"go/errgroup-*\"")
+	evalCmd.Flags().StringVar(&evalTags, "tag", "", "run only tasks with one of these comma-separated tags, e.g. concurrency")
+	evalCmd.Flags().StringVar(&evalExclude, "exclude", "", "comma-separated task ID globs to skip, e.g. \"rust/macros\"")
 	evalCmd.Flags().IntVar(&evalTimeout, "timeout", 0, "timeout per task in seconds (default from config)")
 	evalCmd.Flags().IntVar(&evalParallel, "parallel", 1, "run up to N tasks in parallel")
 	evalCmd.Flags().StringVar(&evalOutputDir, "output", "", "output directory for results")
 	evalCmd.Flags().BoolVar(&evalKeepWorkspaces, "keep-workspaces", false, "keep workspace directories after evaluation")
-	evalCmd.Flags().BoolVar(&evalDryRun, "dry-run", false, "show what tasks would be run without executing")
+	evalCmd.Flags().BoolVar(&evalDryRun, "dry-run", false, "print the execution plan (agent command, tasks, images, output dir) without running anything")
+	evalCmd.Flags().BoolVar(&evalNoProgress, "no-progress", false, "print per-task banners instead of the live progress line")
+	evalCmd.Flags().BoolVar(&evalTUIMode, "tui", false, "full-screen display with a task list, live agent output, attempt timers, and a running score")
 	evalCmd.Flags().BoolVar(&evalUseMCPTools, "use-mcp-tools", false, "inject MCP tool usage instructions into agent prompt")
 	evalCmd.Flags().BoolVar(&evalUseSkills, "use-skills", false, "inject Agent Skills usage instructions into agent prompt")
+	evalCmd.Flags().StringArrayVar(&evalSkillPacks, "skill-pack", nil, "overlay a skill pack directory onto every agent workspace (repeatable)")
+	evalCmd.Flags().IntVar(&evalMaxTurns, "max-turns", 0, "stop an agent attempt after this many turns (0 = [harness] max_turns)")
+	evalCmd.Flags().IntVar(&evalMaxToolCalls, "max-tool-calls", 0, "stop an agent attempt after this many tool calls (0 = [harness] max_tool_calls)")
+	evalCmd.Flags().Int64Var(&evalMaxTokens, "max-tokens-per-attempt", 0, "stop an agent attempt once it reports this many input and output tokens (0 = [harness] max_tokens_per_attempt)")
+	evalCmd.Flags().BoolVar(&evalNetworkAudit, "network-audit", false, "record DNS lookups and outbound connections made while agents run (Linux, requires strace)")
+	evalCmd.Flags().BoolVar(&evalJudge, "judge", false, "score each solution's code quality with the [judge] model and rubric (advisory, not part of the pass rate)")
+	evalCmd.Flags().StringVar(&evalChaosProfile, "chaos", "", "inject command failures, latency, and CPU limits from this chaos profile (mild, harsh, or a [chaos] profile)")
+	evalCmd.Flags().StringVar(&evalDistractors, "distractor-pack", "", "seed every agent workspace with the irrelevant files in this directory to test focus")
+	evalCmd.Flags().StringVar(&evalPromptVariants, "prompt-variants", "", "A/B test prompts: randomly assign these comma-separated variants (control or [prompt_variants] names) to tasks; the first is the baseline")
+	evalCmd.Flags().StringVar(&evalAgentMemoryMode, "agent-memory", "", "scope of the agent's session history and memory: host, run (fresh, shared across tasks), or task (fresh per task); needs the sandbox (default: [harness] agent_memory, else host)")
+	evalCmd.Flags().StringVar(&evalTestVisibility, "test-visibility", "", "tests the agent sees: all (visible and hidden, like --legacy), visible_only, or none (spec only) (default: [harness] test_visibility, else visible_only)")
+	evalCmd.Flags().IntVar(&evalRepairAttempts, "repair-attempts", 0, "give the agent up to this many more turns at a task whose solution failed, with feedback on the failure (default: [harness] repair_attempts)")
+	evalCmd.Flags().StringVar(&evalAttemptFeedback, "attempt-feedback", "", "feedback for repair attempts: none, failing_test_names, full_test_output, or diff_of_previous_attempt (default: [harness] attempt_feedback, else failing_test_names)")
+	evalCmd.Flags().StringVar(&evalDistribute, "distribute", "", "run the tasks on the remote workers listed in this TOML file over SSH and merge their results")
+	evalCmd.Flags().StringVar(&evalToolchains, "toolchains", "", "comma-separated toolchain variants to run every task on, comparing results across them (see [toolchains] in sanity.toml)")
+	evalCmd.Flags().BoolVar(&evalStrictConfig, "strict-config", false, "refuse to run if any config file has unknown keys or invalid values (see sanity config validate)")
+	evalCmd.Flags().StringVar(&evalUpload, "upload", "", "upload run artifacts as they are produced to s3://bucket/prefix or gs://bucket/prefix (default: [upload] url)")
+	evalCmd.Flags().Uint64Var(&evalVariantSeed, "variant-seed", 0, "seed for --prompt-variants assignment (default: --seed, else random, recorded in the summary)")
+	evalCmd.Flags().Uint64Var(&evalSeed, "seed", 0, "shuffle the task order with this seed, and use it for --prompt-variants, so runs are reproducible (default: catalog order)")
+	evalCmd.Flags().StringVar(&evalPromptLang, "prompt-lang", "", "give agents task names and descriptions in this language, e.g. ja, de, or pt (skips tasks without the translation)")
 	evalCmd.Flags().BoolVar(&evalDisableMCP, "disable-mcp", false, "disable MCP tools for agents that support it (currently: opencode)")
+	evalCmd.Flags().StringVar(&evalMCPServers, "mcp-servers", "", "comma-separated [mcp_servers] to add to the agent's configuration for every task, or all (claude, codex, opencode, omo)")
 	evalCmd.Flags().BoolVar(&evalNoSandbox, "no-sandbox", false, "disable bubblewrap sandbox for agent processes")
 	evalCmd.Flags().BoolVar(&evalLegacy, "legacy", false, "expose hidden tests to agent during workspace init (pre-v1.6.0 behavior)")
 	evalCmd.Flags().StringVar(&evalResume, "resume", "", "resume eval from existing output directory")
 	evalCmd.Flags().IntVar(&evalRepeat, "repeat", 1, "repeat each configuration N times for statistical analysis")
+	evalCmd.Flags().StringVar(&evalModelFamily, "model-family", "", "model family metadata, e.g. qwen or llama (default: inferred from --model)")
+	evalCmd.Flags().StringVar(&evalModelParams, "model-params", "", "model parameter count metadata, e.g. 7B or 235B (default: inferred from --model)")
+	evalCmd.Flags().BoolVar(&evalPick, "pick", false, "interactively pick tasks with a fuzzy multi-select (uses fzf if installed)")
+	evalCmd.Flags().StringVar(&evalModelQuant, "model-quant", "", "model quantization metadata, e.g. Q4_K_M, FP8, or BF16 (default: inferred from --model)")
 }
@@ -6,6 +6,7 @@ import (
 	"math"
 	"os"
 	"path/filepath"
+	"slices"
 	"sort"
 	"strings"
 	"time"
@@ -62,6 +63,10 @@ type RepeatStats struct {
 	MaxWeightedScore    float64            `json:"max_weighted_score"`
 	MeanDuration        float64            `json:"mean_duration_seconds"`
 	TaskConsistency     map[string]float64 `json:"task_consistency"`
+	TaskFlipRate        map[string]float64 `json:"task_flip_rate"`
+	MeanFlipRate        float64            `json:"mean_flip_rate"`
+	StabilityScore      float64            `json:"stability_score"`
+	PromptVariants      []VariantResult    `json:"prompt_variants,omitempty"`
 }
 
 // Comparison holds a side-by-side comparison of multiple eval runs.
@@ -125,6 +130,9 @@ func multiRunSubdir(umbrella string, spec RunSpec, specIdx, rep, totalRepeats in
 	if spec.Model != "" {
 		name += "-" + sanitizeModel(spec.Model)
 	}
+	if spec.Toolchain != "" {
+		name = filepath.Join(name, spec.Toolchain)
+	}
 	if totalRepeats > 1 {
 		return filepath.Join(umbrella, name, fmt.Sprintf("run-%d", rep))
 	}
@@ -154,18 +162,16 @@ func updateMultiRunState(umbrellaDir string, results []runResult, specs []RunSpe
 	// Build a set of completed runs.
 	completed := make(map[string]bool)
 	for _, rr := range results {
-		key := fmt.Sprintf("%s-%d", rr.spec.Agent, rr.repeat)
 		if rr.summary != nil || rr.err != nil {
-			completed[key] = true
+			completed[multiRunSubdir("", rr.spec, 0, rr.repeat, repeat)] = true
 		}
 	}
 
 	for specIdx, spec := range specs {
 		for rep := 1; rep <= repeat; rep++ {
 			dir := multiRunSubdir("", spec, specIdx, rep, repeat)
-			key := fmt.Sprintf("%s-%d", spec.Agent, rep)
 			status := "pending"
-			if completed[key] {
+			if completed[dir] {
 				status = "completed"
 			}
 			state.Runs = append(state.Runs, MultiRunItem{
@@ -197,7 +203,7 @@ func markInterruptedRun(runs []MultiRunItem, specs []RunSpec, results []runResul
 			break
 		}
 		for _, rr := range results {
-			if rr.spec.Agent == specs[runs[i-1].SpecIndex].Agent &&
+			if rr.spec == specs[runs[i-1].SpecIndex] &&
 				rr.repeat == runs[i-1].Repeat &&
 				rr.err != nil {
 				runs[i-1].Status = "interrupted"
@@ -331,8 +337,10 @@ type interruptedResumeState struct {
 }
 
 // prepareInterruptedResume loads resume state for an interrupted multi-run item.
+// A pending item whose run already started was killed before the state could
+// be updated, and is resumed the same way.
 func prepareInterruptedResume(item MultiRunItem, runDir string) interruptedResumeState {
-	if item.Status != "interrupted" {
+	if item.Status != "interrupted" && item.Status != "pending" {
 		return interruptedResumeState{}
 	}
 	runCfg, err := loadRunConfig(runDir)
@@ -346,6 +354,7 @@ func prepareInterruptedResume(item MultiRunItem, runDir string) interruptedResum
 		previousResults = prevSummary.Results
 		previousExternalFailures = prevSummary.ExternalFailures
 	}
+	previousResults = withTaskResults(previousResults, runDir)
 	prevAttestation, _ := loadPreviousAttestation(runDir)
 	return interruptedResumeState{
 		isResuming:               true,
@@ -375,6 +384,9 @@ func writeMultiRunOutputs(dir string, mrCfg MultiRunConfig, allSummaries []runRe
 	if mrCfg.Repeat > 1 {
 		writeRepeatStats(dir, mrCfg.Specs, allSummaries, mrCfg.Repeat)
 	}
+	if slices.ContainsFunc(mrCfg.Specs, func(s RunSpec) bool { return s.Toolchain != "" }) {
+		writeToolchainComparison(dir, allSummaries)
+	}
 }
 
 // restoreSharedConfigGlobals sets the global eval flags from a SharedConfig,
@@ -384,12 +396,36 @@ func restoreSharedConfigGlobals(shared SharedConfig) {
 	evalDifficulty = shared.Difficulty
 	evalLang = shared.Lang
 	evalTasks = shared.Tasks
+	evalFilter = shared.Filter
+	evalExclude = shared.Exclude
+	evalTags = shared.Tags
 	evalTimeout = shared.Timeout
 	evalParallel = shared.Parallel
 	evalKeepWorkspaces = shared.KeepWorkspaces
 	evalUseMCPTools = shared.UseMCPTools
 	evalUseSkills = shared.UseSkills
+	evalSkillPacks = shared.SkillPacks
+	evalMaxTurns = shared.MaxTurns
+	evalMaxToolCalls = shared.MaxToolCalls
+	evalMaxTokens = shared.MaxTokens
+	evalNetworkAudit = shared.NetworkAudit
+	evalJudge = shared.Judge
+	evalPromptLang = shared.PromptLang
+	evalDistractors = shared.DistractorPack
+	evalChaosProfile = shared.Chaos
+	evalPromptVariants = shared.PromptVariants
+	evalVariantSeed = shared.VariantSeed
+	evalSeed = shared.Seed
+	evalAgentMemoryMode = shared.AgentMemory
+	evalTestVisibility = shared.TestVisibility
+	evalRepairAttempts = shared.RepairAttempts
+	evalAttemptFeedback = shared.AttemptFeedback
+	evalUpload = shared.Upload
 	evalDisableMCP = shared.DisableMCP
+	evalMCPServers = shared.MCPServers
+	evalTemperature = shared.Temperature
+	evalTopP = shared.TopP
+	evalMinP = shared.MinP
 	evalNoSandbox = shared.NoSandbox
 	evalLegacy = shared.Legacy
 }
@@ -415,6 +451,9 @@ func generateComparison(summaries []EvalSummary) Comparison {
 		if s.Model != "" && s.Model != "unknown" {
 			id += "/" + s.Model
 		}
+		if s.Toolchain != "" {
+			id += " [" + s.Toolchain + "]"
+		}
 
 		run := ComparisonRun{
 			ID:                  id,
@@ -530,7 +569,7 @@ func writeRepeatStats(umbrellaDir string, specs []RunSpec, results []runResult,
 		var summaries []*EvalSummary
 		for _, rr := range results {
 			if rr.spec.Agent == spec.Agent && rr.spec.Model == spec.Model &&
-				rr.spec.Reasoning == spec.Reasoning && rr.summary != nil {
+				rr.spec.Reasoning == spec.Reasoning && rr.spec.Toolchain == spec.Toolchain && rr.summary != nil {
 				summaries = append(summaries, rr.summary)
 			}
 		}
@@ -558,6 +597,9 @@ func buildRepeatReport(allStats []RepeatStats) string {
 		if stats.Config.Model != "" {
 			label += " / " + stats.Config.Model
 		}
+		if stats.Config.Toolchain != "" {
+			label += " [" + stats.Config.Toolchain + "]"
+		}
 		fmt.Fprintf(&sb, "### Repeat Analysis — %s (%d runs)\n\n", label, stats.Runs)
 		fmt.Fprintf(&sb, "| Metric | Mean | Std Dev | Min | Max |\n")
 		fmt.Fprintf(&sb, "|--------|------|---------|-----|-----|\n")
@@ -567,12 +609,20 @@ func buildRepeatReport(allStats []RepeatStats) string {
 			stats.MeanWeightedScore, stats.StdDevWeightedScore, stats.MinWeightedScore, stats.MaxWeightedScore)
 		fmt.Fprintf(&sb, "| Duration | %s | — | — | — |\n", formatDuration(stats.MeanDuration))
 		sb.WriteString("\n")
+		if len(stats.TaskFlipRate) > 0 {
+			fmt.Fprintf(&sb, "**Stability:** %.1f%% of tasks had the same outcome in every run (mean flip rate %.1f%%)\n\n",
+				stats.StabilityScore, stats.MeanFlipRate)
+		}
+		if len(stats.PromptVariants) > 0 {
+			fmt.Fprintf(&sb, "### Prompt Experiment (pooled over %d runs)\n\n", stats.Runs)
+			writeVariantTable(&sb, stats.PromptVariants)
+		}
 
 		// Task consistency sorted by flakiness.
 		if len(stats.TaskConsistency) > 0 {
 			fmt.Fprintf(&sb, "### Task Consistency (sorted by flakiness)\n\n")
-			fmt.Fprintf(&sb, "| Task | Pass Rate | Status |\n")
-			fmt.Fprintf(&sb, "|------|-----------|--------|\n")
+			fmt.Fprintf(&sb, "| Task | Pass Rate | Flip Rate | Status |\n")
+			fmt.Fprintf(&sb, "|------|-----------|-----------|--------|\n")
 
 			type taskRate struct {
 				task string
@@ -583,7 +633,10 @@ func buildRepeatReport(allStats []RepeatStats) string {
 				sorted = append(sorted, taskRate{t, rate})
 			}
 			sort.Slice(sorted, func(i, j int) bool {
-				return sorted[i].rate < sorted[j].rate
+				if sorted[i].rate != sorted[j].rate {
+					return sorted[i].rate < sorted[j].rate
+				}
+				return stats.TaskFlipRate[sorted[i].task] > stats.TaskFlipRate[sorted[j].task]
 			})
 
 			for _, tr := range sorted {
@@ -593,7 +646,7 @@ func buildRepeatReport(allStats []RepeatStats) string {
 				} else if tr.rate < 100 {
 					status = "⚠️ Flaky"
 				}
-				fmt.Fprintf(&sb, "| %s | %.0f%% | %s |\n", tr.task, tr.rate, status)
+				fmt.Fprintf(&sb, "| %s | %.0f%% | %.0f%% | %s |\n", tr.task, tr.rate, stats.TaskFlipRate[tr.task], status)
 			}
 			sb.WriteString("\n")
 		}
@@ -609,6 +662,8 @@ func computeRepeatStats(spec RunSpec, summaries []*EvalSummary) RepeatStats {
 	durations := make([]float64, 0, len(summaries))
 	taskPassCounts := make(map[string]int)
 	taskTotal := make(map[string]int)
+	taskOutcomes := make(map[string][]bool)
+	var pooled []EvalResult
 
 	for _, s := range summaries {
 		passRates = append(passRates, s.PassRate)
@@ -619,13 +674,23 @@ func computeRepeatStats(spec RunSpec, summaries []*EvalSummary) RepeatStats {
 			if r.Passed {
 				taskPassCounts[r.Task]++
 			}
+			taskOutcomes[r.Task] = append(taskOutcomes[r.Task], r.Passed)
 		}
+		pooled = append(pooled, s.Results...)
 	}
 
 	taskConsistency := make(map[string]float64)
 	for tk, total := range taskTotal {
 		taskConsistency[tk] = float64(taskPassCounts[tk]) / float64(total) * 100.0
 	}
+	taskFlipRate, meanFlipRate, stabilityScore := computeStability(taskOutcomes)
+
+	// Each repeat assigns variants afresh, so pooling the runs gives every
+	// variant samples of every task.
+	var variants []VariantResult
+	if exp := summaries[0].PromptExperiment; exp != nil {
+		variants = compareVariants(pooled, exp.Variants)
+	}
 
 	return RepeatStats{
 		Config:              spec,
@@ -641,7 +706,43 @@ func computeRepeatStats(spec RunSpec, summaries []*EvalSummary) RepeatStats {
 		MaxWeightedScore:    maxVal(weightedScores),
 		MeanDuration:        mean(durations),
 		TaskConsistency:     taskConsistency,
+		TaskFlipRate:        taskFlipRate,
+		MeanFlipRate:        meanFlipRate,
+		StabilityScore:      stabilityScore,
+		PromptVariants:      variants,
+	}
+}
+
+// computeStability measures how consistently each task's outcome repeats across
+// runs, given per-task outcomes in run order. A task's flip rate is the
+// percentage of consecutive run pairs whose pass/fail outcome differs. The
+// stability score is the percentage of tasks with the same outcome in every run.
+// Tasks seen in fewer than two runs carry no signal and are skipped.
+func computeStability(taskOutcomes map[string][]bool) (flipRates map[string]float64, meanFlipRate, stabilityScore float64) {
+	flipRates = make(map[string]float64)
+	stable := 0
+	rates := make([]float64, 0, len(taskOutcomes))
+	for tk, outcomes := range taskOutcomes {
+		if len(outcomes) < 2 {
+			continue
+		}
+		flips := 0
+		for i := 1; i < len(outcomes); i++ {
+			if outcomes[i] != outcomes[i-1] {
+				flips++
+			}
+		}
+		rate := float64(flips) / float64(len(outcomes)-1) * 100.0
+		flipRates[tk] = rate
+		rates = append(rates, rate)
+		if flips == 0 {
+			stable++
+		}
 	}
+	if len(rates) == 0 {
+		return flipRates, 0, 0
+	}
+	return flipRates, mean(rates), float64(stable) / float64(len(rates)) * 100.0
 }
 
 // filterTasksForShared applies shared config filters to a task list.
@@ -660,6 +761,10 @@ func filterTasksForShared(allTasks []*task.Task, shared SharedConfig) []*task.Ta
 	if shared.Tier != "" && shared.Tier != "all" {
 		result = filterByTier(result, shared.Tier)
 	}
+	if f, err := task.ParseFilter(shared.Filter, shared.Exclude, shared.Tags); err == nil {
+		result = f.Apply(result)
+	}
+	result, _ = filterTranslatedTasks(result, shared.PromptLang)
 
 	return result
 }
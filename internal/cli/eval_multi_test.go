@@ -95,6 +95,11 @@ func TestMultiRunSubdir(t *testing.T) {
 			RunSpec{Agent: "opencode", Model: "google/gemini-2.5-pro"}, 0, 1, 1,
 			filepath.Join("/umbrella", "opencode-google-gemini-2.5-pro"),
 		},
+		{
+			"toolchain with repeat",
+			RunSpec{Agent: "gemini", Toolchain: "rust-beta"}, 0, 2, 3,
+			filepath.Join("/umbrella", "gemini", "rust-beta", "run-2"),
+		},
 	}
 	for _, tt := range tests {
 		t.Run(tt.name, func(t *testing.T) {
@@ -202,6 +207,40 @@ func TestComputeRepeatStats(t *testing.T) {
 	if stats.TaskConsistency["go/b"] != 50 {
 		t.Errorf("TaskConsistency[go/b] = %v, want 50", stats.TaskConsistency["go/b"])
 	}
+	if stats.TaskFlipRate["go/b"] != 100 {
+		t.Errorf("TaskFlipRate[go/b] = %v, want 100", stats.TaskFlipRate["go/b"])
+	}
+	if stats.StabilityScore != 50 {
+		t.Errorf("StabilityScore = %v, want 50", stats.StabilityScore)
+	}
+}
+
+func TestComputeStability(t *testing.T) {
+	t.Parallel()
+
+	flipRates, meanFlip, stability := computeStability(map[string][]bool{
+		"go/stable":  {true, true, true, true, true},
+		"go/flaky":   {true, false, true, false, true},
+		"go/once":    {false, false, true, true, true},
+		"go/single":  {true},
+		"go/failing": {false, false, false, false, false},
+	})
+
+	if _, ok := flipRates["go/single"]; ok {
+		t.Error("tasks with a single run should be skipped")
+	}
+	if flipRates["go/flaky"] != 100 {
+		t.Errorf("flipRates[go/flaky] = %v, want 100", flipRates["go/flaky"])
+	}
+	if flipRates["go/once"] != 25 {
+		t.Errorf("flipRates[go/once] = %v, want 25", flipRates["go/once"])
+	}
+	if meanFlip != 31.25 {
+		t.Errorf("meanFlip = %v, want 31.25", meanFlip)
+	}
+	if stability != 50 {
+		t.Errorf("stability = %v, want 50", stability)
+	}
 }
 
 func TestGenerateComparison(t *testing.T) {
@@ -220,7 +220,7 @@ func TestWriteAgentTimeoutFooter(t *testing.T) {
 	if err != nil {
 		t.Fatalf("open log file: %v", err)
 	}
-	writeAgentTimeoutFooter(logFile, 1, 120*time.Second, 121*time.Second)
+	writeAgentTimeoutFooter(logFile, 1, 120*time.Second, 121*time.Second, "graceful")
 	_ = logFile.Close()
 
 	data, err := os.ReadFile(path)
@@ -234,6 +234,9 @@ func TestWriteAgentTimeoutFooter(t *testing.T) {
 	if !strings.Contains(got, "attempt=2") {
 		t.Fatalf("expected attempt index in footer, got: %s", got)
 	}
+	if !strings.Contains(got, "termination=graceful") {
+		t.Fatalf("expected termination path in footer, got: %s", got)
+	}
 }
 
 func TestWriteValidationLog(t *testing.T) {
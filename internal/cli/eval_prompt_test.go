@@ -12,9 +12,42 @@ import (
 	"time"
 
 	"github.com/lemon07r/sanityharness/internal/config"
+	resultpkg "github.com/lemon07r/sanityharness/internal/result"
 	"github.com/lemon07r/sanityharness/internal/task"
 )
 
+func TestFilterTranslatedTasks(t *testing.T) {
+	t.Parallel()
+
+	translated := &task.Task{
+		Slug:         "demo",
+		Name:         "Demo Task",
+		Language:     task.Go,
+		Description:  "Implement the thing.",
+		Translations: map[string]task.Translation{"de": {Name: "Demo-Aufgabe", Description: "Implementiere das Ding."}},
+	}
+	plain := &task.Task{Slug: "plain", Language: task.Rust, Description: "Implement another thing."}
+
+	kept, missing := filterTranslatedTasks([]*task.Task{translated, plain}, "de")
+	if len(kept) != 1 || kept[0] != translated {
+		t.Fatalf("kept = %v, want only %s", kept, translated.ID())
+	}
+	if !reflect.DeepEqual(missing, []string{"rust/plain"}) {
+		t.Fatalf("missing = %v, want [rust/plain]", missing)
+	}
+	if kept, missing := filterTranslatedTasks([]*task.Task{translated, plain}, ""); len(kept) != 2 || len(missing) != 0 {
+		t.Fatalf("source language should keep every task, got kept=%d missing=%v", len(kept), missing)
+	}
+
+	localized, _ := translated.Localized("de")
+	prompt := buildAgentPrompt(localized, false, false, "")
+	for _, s := range []string{`coding task called "Demo-Aufgabe"`, "Description: Implementiere das Ding."} {
+		if !strings.Contains(prompt, s) {
+			t.Fatalf("prompt missing %q\n\nPrompt:\n%s", s, prompt)
+		}
+	}
+}
+
 func TestBuildAgentPromptIncludesKeyInfo(t *testing.T) {
 	t.Parallel()
 
@@ -278,6 +311,7 @@ func TestBuildAgentCommandDisableMCP(t *testing.T) {
 				"test prompt",
 				"",
 				"",
+				"",
 				tc.disableMCP,
 				tc.useMCPTools,
 				tc.agentName,
@@ -445,7 +479,7 @@ func TestBuildOpenCodeMCPDisableConfig(t *testing.T) {
 func TestBuildOpenCodeMCPConfigWithTimeout(t *testing.T) {
 	t.Parallel()
 
-	config := buildOpenCodeMCPConfig(false, true)
+	config := buildOpenCodeMCPConfig(false, true, nil)
 	if !strings.Contains(config, `"experimental"`) {
 		t.Fatal("config should contain experimental key")
 	}
@@ -528,6 +562,7 @@ type agentCommandTestCase struct {
 	prompt       string
 	model        string
 	reasoning    string
+	workspace    string
 	disableMCP   bool
 	useMCPTools  bool
 	agentName    string
@@ -547,6 +582,7 @@ func runAgentCommandTestCases(t *testing.T, tests []agentCommandTestCase) {
 				tc.prompt,
 				tc.model,
 				tc.reasoning,
+				tc.workspace,
 				tc.disableMCP,
 				tc.useMCPTools,
 				tc.agentName,
@@ -577,6 +613,45 @@ func TestBuildAgentCommand_NoFlags(t *testing.T) {
 	})
 }
 
+func TestBuildAgentCommand_Placeholders(t *testing.T) {
+	t.Parallel()
+
+	runAgentCommandTestCases(t, []agentCommandTestCase{
+		{
+			name: "placeholders_inside_args",
+			agentCfg: &config.AgentConfig{
+				Command: "agent",
+				Args:    []string{"--model={model}", "--cwd", "{workspace}", "--effort", "{reasoning}", "--message={prompt}"},
+			},
+			prompt:       "fix {model}",
+			model:        "qwen3-coder",
+			reasoning:    "high",
+			workspace:    "/tmp/ws",
+			expectedArgs: []string{"--model=qwen3-coder", "--cwd", "/tmp/ws", "--effort", "high", "--message=fix {model}"},
+		},
+		{
+			name: "empty_placeholder_dropped",
+			agentCfg: &config.AgentConfig{
+				Command: "agent",
+				Args:    []string{"run", "{model}", "{prompt}"},
+			},
+			prompt:       "",
+			expectedArgs: []string{"run", ""},
+		},
+		{
+			name: "prompt_file",
+			agentCfg: &config.AgentConfig{
+				Command:    "agent",
+				Args:       []string{"--task", "{prompt_file}"},
+				PromptMode: config.PromptModeFile,
+			},
+			prompt:       "do the thing",
+			workspace:    "/tmp/ws",
+			expectedArgs: []string{"--task", "/tmp/ws/" + config.AgentPromptFile},
+		},
+	})
+}
+
 func TestBuildAgentCommand_ModelFlag(t *testing.T) {
 	t.Parallel()
 
@@ -1215,6 +1290,77 @@ func TestIsInfraFailure(t *testing.T) {
 	}
 }
 
+func TestClassifyAttemptRetryPolicy(t *testing.T) {
+	c := config.Default
+	c.Retry.Provider = config.RetryPolicy{MaxRetries: 2, Delay: 1, Backoff: 3}
+	c.Retry.AgentCrash.MaxRetries = 0
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+
+	dir := t.TempDir()
+	quotaLog := filepath.Join(dir, "quota.log")
+	if err := os.WriteFile(quotaLog, []byte("HTTP 429 Too Many Requests\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	var quota, infra, timeouts int
+	var result agentExecutionResult
+	for want := 1; want <= 2; want++ {
+		decision := classifyAttempt(agentAttemptResult{}, quotaLog, dir, time.Now(), &quota, &infra, &timeouts, &result)
+		if decision.done || decision.retryType != config.RetryProvider || decision.retry != want {
+			t.Fatalf("provider error %d: decision = %+v", want, decision)
+		}
+	}
+	if got := retryPolicy(config.RetryProvider).Wait(2); got != 3*time.Second {
+		t.Errorf("provider retry 2 delay = %v, want 3s", got)
+	}
+	if decision := classifyAttempt(agentAttemptResult{}, quotaLog, dir, time.Now(), &quota, &infra, &timeouts, &result); !decision.done || !result.quotaExhausted {
+		t.Fatalf("after max_retries: decision = %+v, result = %+v", decision, result)
+	}
+
+	// With agent_crash.max_retries = 0 an empty log fails at once.
+	emptyLog := filepath.Join(dir, "empty.log")
+	if err := os.WriteFile(emptyLog, nil, 0o644); err != nil {
+		t.Fatal(err)
+	}
+	result = agentExecutionResult{}
+	if decision := classifyAttempt(agentAttemptResult{}, emptyLog, "", time.Now(), &quota, &infra, &timeouts, &result); !decision.done || result.failureClass != FailureClassInfra {
+		t.Errorf("agent crash: decision = %+v, result = %+v", decision, result)
+	}
+}
+
+func TestValidationFailureKindFlaky(t *testing.T) {
+	c := config.Default
+	c.Retry.Flaky.MaxRetries = 2
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+
+	tk := &task.Task{Validation: task.Validation{TimingTests: []string{"TestIsConcurrent"}}}
+	session := resultpkg.NewSession("speed", "go", resultpkg.SessionConfig{})
+	session.AddAttempt(1, time.Second, "--- FAIL: TestIsConcurrent (0.50s)\nFAIL\n", nil)
+
+	if kind := validationFailureKind(tk, session, map[string]int{}); kind != config.RetryFlaky {
+		t.Errorf("timing test failed: kind = %q, want flaky", kind)
+	}
+	if kind := validationFailureKind(tk, session, map[string]int{config.RetryFlaky: 2}); kind != config.RetryTestFailure {
+		t.Errorf("flaky reruns used up: kind = %q, want test_failure", kind)
+	}
+	session.AddAttempt(1, time.Second, "--- FAIL: TestCount (0.00s)\nFAIL\n", nil)
+	if kind := validationFailureKind(tk, session, map[string]int{}); kind != config.RetryTestFailure {
+		t.Errorf("other test failed: kind = %q, want test_failure", kind)
+	}
+
+	r := EvalResult{Passed: true, FlakyReruns: 1}
+	if got := evalResultStatus(&r); got != task.StatusFlakyPass {
+		t.Errorf("evalResultStatus() = %q, want flaky_pass", got)
+	}
+	r.FlakyReruns = 0
+	if got := evalResultStatus(&r); got != task.StatusPass {
+		t.Errorf("evalResultStatus() = %q, want pass", got)
+	}
+}
+
 func TestBuildSandboxArgs(t *testing.T) {
 	t.Parallel()
 
@@ -1280,7 +1426,7 @@ func TestWrapCommandWithSandbox(t *testing.T) {
 		Args:    []string{"{prompt}"},
 	}
 
-	cmd := buildAgentCommand(ctx, agentCfg, "test prompt", "", "", false, false, "test")
+	cmd := buildAgentCommand(ctx, agentCfg, "test prompt", "", "", "", false, false, "test")
 	cmd.Dir = workspaceDir
 
 	wrapped := wrapCommandWithSandbox(ctx, cmd, nil, nil, nil, nil)
@@ -6,6 +6,7 @@ import (
 	"testing"
 	"time"
 
+	"github.com/lemon07r/sanityharness/internal/config"
 	"github.com/lemon07r/sanityharness/internal/task"
 )
 
@@ -202,3 +203,20 @@ func TestResolveAgentTimeout(t *testing.T) {
 		})
 	}
 }
+
+func TestTaskWeightOverride(t *testing.T) {
+	c := config.Default
+	c.Tasks = map[string]config.TaskOverride{"go/bank-account": {Weight: 1.4}}
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+
+	overridden := &task.Task{Slug: "bank-account", Language: task.Go}
+	if got := taskWeight(overridden).Base; got != 1.4 {
+		t.Errorf("taskWeight(go/bank-account) = %v, want the 1.4 override", got)
+	}
+	other := &task.Task{Slug: "react", Language: task.Go}
+	if got, want := taskWeight(other).Base, task.ComputeWeight(other).Base; got != want {
+		t.Errorf("taskWeight(go/react) = %v, want the computed %v", got, want)
+	}
+}
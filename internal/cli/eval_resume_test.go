@@ -0,0 +1,105 @@
+package cli
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestFindCompletedTasksUsesTaskResults(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	saveTaskResult(dir, EvalResult{Task: "go/bank-account", Passed: true})
+	// Validated but killed before its result was saved: must be rerun.
+	if err := os.MkdirAll(filepath.Join(dir, "go-react"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "go-react", "validation.log"), nil, 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	completed, err := findCompletedTasks(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if want := map[string]bool{"go/bank-account": true}; !reflect.DeepEqual(completed, want) {
+		t.Errorf("findCompletedTasks() = %v, want %v", completed, want)
+	}
+
+	results := withTaskResults([]EvalResult{{Task: "go/bank-account", Passed: false}}, dir)
+	if len(results) != 1 || results[0].Passed {
+		t.Errorf("withTaskResults() = %+v, want the summary result kept", results)
+	}
+	results = withTaskResults(nil, dir)
+	if len(results) != 1 || results[0].Task != "go/bank-account" || !results[0].Passed {
+		t.Errorf("withTaskResults() = %+v, want the saved result", results)
+	}
+}
+
+func TestFindCompletedTasksLegacyRun(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	if err := os.MkdirAll(filepath.Join(dir, "rust-parser"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "rust-parser", "validation.log"), nil, 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	// Without summary.json the results are lost, so nothing counts as done.
+	completed, err := findCompletedTasks(dir)
+	if err != nil || len(completed) != 0 {
+		t.Fatalf("findCompletedTasks() = %v, %v, want none", completed, err)
+	}
+
+	if err := os.WriteFile(filepath.Join(dir, "summary.json"), []byte("{}"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	completed, err = findCompletedTasks(dir)
+	if err != nil || !completed["rust/parser"] || len(completed) != 1 {
+		t.Errorf("findCompletedTasks() = %v, %v, want rust/parser", completed, err)
+	}
+}
+
+func TestGradingContextOutlivesInterrupt(t *testing.T) {
+	force, forceStop := context.WithCancel(context.Background())
+	prev := forceStopCtx
+	forceStopCtx = force
+	t.Cleanup(func() { forceStopCtx = prev })
+
+	ctx, interrupt := context.WithCancel(context.Background())
+	gradeCtx, stop := gradingContext(ctx)
+	defer stop()
+
+	interrupt()
+	if gradeCtx.Err() != nil {
+		t.Fatal("grading stopped at the first interrupt")
+	}
+	forceStop()
+	select {
+	case <-gradeCtx.Done():
+	case <-time.After(time.Second):
+		t.Fatal("grading kept going after the second interrupt")
+	}
+}
+
+func TestReportSummaryInterrupted(t *testing.T) {
+	t.Parallel()
+
+	var sb strings.Builder
+	writeReportSummary(&sb, EvalSummary{Agent: "claude", Interrupted: true, RemainingTasks: 3})
+	if !strings.Contains(sb.String(), "| Interrupted | Partial results, 3 task(s) not graded |") {
+		t.Errorf("report summary = %q, want the interrupted row", sb.String())
+	}
+	sb.Reset()
+	writeReportSummary(&sb, EvalSummary{Agent: "claude"})
+	if strings.Contains(sb.String(), "Interrupted") {
+		t.Errorf("report summary = %q, want no interrupted row", sb.String())
+	}
+}
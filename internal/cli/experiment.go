@@ -0,0 +1,249 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"math"
+	"math/rand/v2"
+	"path/filepath"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// experimentAlpha is the p-value below which a variant's difference from the
+// baseline is reported as significant.
+const experimentAlpha = 0.05
+
+// PromptExperiment records an A/B prompt experiment: the variants compared,
+// the seed that assigned them to tasks, and each variant's results. The first
+// variant is the baseline the others are tested against.
+type PromptExperiment struct {
+	Variants []string        `json:"variants"`
+	Seed     uint64          `json:"seed"`
+	Arms     []VariantResult `json:"arms,omitempty"`
+}
+
+// VariantResult is one variant's share of an experiment. PValue is Fisher's
+// exact test of its pass rate against the baseline's and is unset for the
+// baseline itself.
+type VariantResult struct {
+	Variant       string  `json:"variant"`
+	Tasks         int     `json:"tasks"`
+	Passed        int     `json:"passed"`
+	PassRate      float64 `json:"pass_rate"`
+	WeightedScore float64 `json:"weighted_score"`
+	Delta         float64 `json:"delta_vs_baseline,omitempty"`
+	PValue        float64 `json:"p_value,omitempty"`
+	Significant   bool    `json:"significant,omitempty"`
+}
+
+// promptVariantPlan is the resolved experiment for one run: the variant
+// templates and which variant each task is given.
+type promptVariantPlan struct {
+	names      []string
+	seed       uint64
+	templates  map[string]config.PromptVariant
+	assignment map[string]string
+}
+
+// evalVariantPlan holds the experiment for the current run, or nil. Like the
+// other eval globals it is set by evalRunSingle before tasks start.
+var evalVariantPlan *promptVariantPlan
+
+// splitPromptVariants parses a --prompt-variants list.
+func splitPromptVariants(s string) []string {
+	var names []string
+	for _, name := range strings.Split(s, ",") {
+		if name = strings.TrimSpace(name); name != "" {
+			names = append(names, name)
+		}
+	}
+	return names
+}
+
+// resolvePromptVariants looks up the variants in list and assigns them to
+// tasks. runKey distinguishes runs sharing a seed, such as repeats, so each
+// gets its own assignment; the same seed and key always give the same one,
+// which keeps resumed runs consistent. An empty list means no experiment.
+func resolvePromptVariants(list string, seed uint64, runKey string, tasks []*task.Task) (*promptVariantPlan, error) {
+	names := splitPromptVariants(list)
+	if len(names) == 0 {
+		return nil, nil
+	}
+	if len(names) < 2 {
+		return nil, errors.New("--prompt-variants needs at least two variants")
+	}
+	plan := &promptVariantPlan{names: names, seed: seed, templates: make(map[string]config.PromptVariant, len(names))}
+	for _, name := range names {
+		if _, dup := plan.templates[name]; dup {
+			return nil, fmt.Errorf("--prompt-variants lists %q twice", name)
+		}
+		v, ok := cfg.PromptVariant(name)
+		if !ok {
+			return nil, fmt.Errorf("unknown prompt variant %q (available: %s)", name, strings.Join(cfg.PromptVariantNames(), ", "))
+		}
+		plan.templates[name] = v
+	}
+	plan.assignment = assignPromptVariants(tasks, names, seed, runKey)
+	return plan, nil
+}
+
+// assignPromptVariants shuffles tasks by a hash of the seed, runKey, and task
+// ID, then deals variants out in turn, so every variant gets an equal share
+// (to within one task) in random order.
+func assignPromptVariants(tasks []*task.Task, names []string, seed uint64, runKey string) map[string]string {
+	assignment := make(map[string]string, len(tasks))
+	for i, t := range shuffleTasks(tasks, seed, runKey) {
+		assignment[t.ID()] = names[i%len(names)]
+	}
+	return assignment
+}
+
+// variantRunKey returns the assignment key for a run's output directory.
+func variantRunKey(outputDir string) string {
+	return filepath.Base(filepath.Clean(outputDir))
+}
+
+// render returns t's prompt under variant, expanding the variant's template
+// around the standard prompt.
+func (p *promptVariantPlan) render(variant, standard string, t *task.Task) string {
+	stubFiles := make([]string, 0, len(t.Files.Stub))
+	for _, f := range t.Files.Stub {
+		stubFiles = append(stubFiles, task.StripTxtExtension(f))
+	}
+	testFiles := make([]string, 0, len(t.Files.Test))
+	for _, f := range t.Files.Test {
+		testFiles = append(testFiles, task.StripTxtExtension(f))
+	}
+	return strings.NewReplacer(
+		config.PromptPlaceholderPrompt, standard,
+		config.PromptPlaceholderTask, t.Name,
+		config.PromptPlaceholderLanguage, string(t.Language),
+		config.PromptPlaceholderDescription, t.Description,
+		config.PromptPlaceholderStubFiles, strings.Join(stubFiles, ", "),
+		config.PromptPlaceholderTestFiles, strings.Join(testFiles, ", "),
+	).Replace(p.templates[variant].Template)
+}
+
+// experiment returns the summary record for the plan, or nil without one.
+func (p *promptVariantPlan) experiment() *PromptExperiment {
+	if p == nil {
+		return nil
+	}
+	return &PromptExperiment{Variants: p.names, Seed: p.seed}
+}
+
+// compareVariants tallies results by prompt variant, in the order of names,
+// and tests each variant's pass rate against the first's.
+func compareVariants(results []EvalResult, names []string) []VariantResult {
+	arms := make([]VariantResult, len(names))
+	index := make(map[string]int, len(names))
+	for i, name := range names {
+		arms[i].Variant = name
+		index[name] = i
+	}
+	for _, r := range results {
+		i, ok := index[r.PromptVariant]
+		if !ok {
+			continue
+		}
+		arms[i].Tasks++
+		if r.Passed {
+			arms[i].Passed++
+		}
+		arms[i].WeightedScore += r.WeightedScore
+	}
+	for i := range arms {
+		if arms[i].Tasks > 0 {
+			arms[i].PassRate = float64(arms[i].Passed) / float64(arms[i].Tasks) * 100
+		}
+	}
+	if len(arms) == 0 {
+		return arms
+	}
+	base := arms[0]
+	for i := 1; i < len(arms); i++ {
+		if arms[i].Tasks == 0 || base.Tasks == 0 {
+			continue
+		}
+		arms[i].Delta = arms[i].PassRate - base.PassRate
+		arms[i].PValue = fisherExact(arms[i].Passed, arms[i].Tasks-arms[i].Passed, base.Passed, base.Tasks-base.Passed)
+		arms[i].Significant = arms[i].PValue < experimentAlpha
+	}
+	return arms
+}
+
+// fisherExact returns the two-sided p-value of Fisher's exact test for the
+// 2x2 table [[a, b], [c, d]]: the probability, with the margins fixed, of a
+// table at most as likely as the observed one.
+func fisherExact(a, b, c, d int) float64 {
+	n := a + b + c + d
+	row, col := a+b, a+c
+	logP := func(x int) float64 {
+		return logChoose(row, x) + logChoose(n-row, col-x) - logChoose(n, col)
+	}
+	observed := logP(a)
+	var p float64
+	for x := max(0, col-(n-row)); x <= min(row, col); x++ {
+		// The tolerance keeps tables tied with the observed one in the tail.
+		if lp := logP(x); lp <= observed+1e-7 {
+			p += math.Exp(lp)
+		}
+	}
+	return min(p, 1)
+}
+
+func logChoose(n, k int) float64 {
+	ln, _ := math.Lgamma(float64(n + 1))
+	lk, _ := math.Lgamma(float64(k + 1))
+	lnk, _ := math.Lgamma(float64(n - k + 1))
+	return ln - lk - lnk
+}
+
+// writeVariantTable writes a Markdown table comparing experiment arms.
+func writeVariantTable(sb *strings.Builder, arms []VariantResult) {
+	sb.WriteString("| Variant | Tasks | Passed | Pass Rate | Δ vs Baseline | p-value | Weighted Score |\n")
+	sb.WriteString("|---------|-------|--------|-----------|---------------|---------|----------------|\n")
+	for i, a := range arms {
+		delta, pValue := "baseline", "—"
+		if i > 0 {
+			delta = fmt.Sprintf("%+.1f pp", a.Delta)
+			if a.Tasks > 0 && arms[0].Tasks > 0 {
+				pValue = fmt.Sprintf("%.3f", a.PValue)
+				if a.Significant {
+					pValue = "**" + pValue + "**"
+				}
+			}
+		}
+		fmt.Fprintf(sb, "| %s | %d | %d | %.1f%% | %s | %s | %.2f |\n",
+			a.Variant, a.Tasks, a.Passed, a.PassRate, delta, pValue, a.WeightedScore)
+	}
+	sb.WriteString("\n")
+}
+
+// writeReportExperiment compares the prompt variants of an experiment run.
+func writeReportExperiment(sb *strings.Builder, summary EvalSummary) {
+	exp := summary.PromptExperiment
+	if exp == nil {
+		return
+	}
+	sb.WriteString("## Prompt Experiment\n\n")
+	fmt.Fprintf(sb, "Variants were assigned to tasks at random (seed %d); **%s** is the baseline. p-values are from Fisher's exact test on pass counts; bold ones are below %.2f.\n\n",
+		exp.Seed, exp.Variants[0], experimentAlpha)
+	writeVariantTable(sb, exp.Arms)
+	if len(summary.Results) < 20*len(exp.Variants) {
+		sb.WriteString("*Few tasks per variant; use `--repeat` to gather more samples before drawing conclusions.*\n\n")
+	}
+}
+
+// newVariantSeed picks a seed when --variant-seed is not given. It is
+// recorded in the run config and summary so the assignment can be repeated.
+func newVariantSeed() uint64 {
+	for {
+		if seed := rand.Uint64(); seed != 0 {
+			return seed
+		}
+	}
+}
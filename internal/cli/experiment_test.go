@@ -0,0 +1,116 @@
+package cli
+
+import (
+	"math"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+func TestAssignPromptVariants(t *testing.T) {
+	t.Parallel()
+
+	allTasks, err := task.NewLoader(tasks.FS, "").LoadAll()
+	if err != nil {
+		t.Fatalf("LoadAll() error = %v", err)
+	}
+	names := []string{"control", "terse", "verbose"}
+	got := assignPromptVariants(allTasks, names, 42, "run-1")
+	if len(got) != len(allTasks) {
+		t.Fatalf("assigned %d tasks, want %d", len(got), len(allTasks))
+	}
+	counts := make(map[string]int)
+	for _, v := range got {
+		counts[v]++
+	}
+	for _, name := range names {
+		if n := counts[name]; n < len(allTasks)/len(names) || n > len(allTasks)/len(names)+1 {
+			t.Errorf("variant %s has %d tasks; want an equal share of %d", name, n, len(allTasks))
+		}
+	}
+
+	if again := assignPromptVariants(allTasks, names, 42, "run-1"); !reflect.DeepEqual(got, again) {
+		t.Error("the same seed and run key gave a different assignment")
+	}
+	if other := assignPromptVariants(allTasks, names, 42, "run-2"); reflect.DeepEqual(got, other) {
+		t.Error("another run key gave the same assignment")
+	}
+}
+
+func TestCompareVariants(t *testing.T) {
+	t.Parallel()
+
+	var results []EvalResult
+	add := func(variant string, passed, failed int) {
+		for i := range passed + failed {
+			results = append(results, EvalResult{PromptVariant: variant, Passed: i < passed, WeightedScore: 1})
+		}
+	}
+	add("control", 3, 11)
+	add("terse", 9, 1)
+	results = append(results, EvalResult{Task: "go/unassigned", Passed: true})
+
+	arms := compareVariants(results, []string{"control", "terse", "unused"})
+	if len(arms) != 3 || arms[0].Tasks != 14 || arms[1].Passed != 9 || arms[2].Tasks != 0 {
+		t.Fatalf("compareVariants() = %+v", arms)
+	}
+	if arms[0].PValue != 0 || arms[0].Significant {
+		t.Errorf("baseline should not be tested: %+v", arms[0])
+	}
+	if math.Abs(arms[1].Delta-(90-300.0/14)) > 1e-9 {
+		t.Errorf("delta = %v", arms[1].Delta)
+	}
+	// Fisher's tea-tasting style example: [[9, 1], [3, 11]] has p ≈ 0.0028.
+	if math.Abs(arms[1].PValue-0.002759) > 1e-5 || !arms[1].Significant {
+		t.Errorf("p-value = %v, significant = %v; want 0.002759", arms[1].PValue, arms[1].Significant)
+	}
+
+	var sb strings.Builder
+	writeVariantTable(&sb, arms)
+	if !strings.Contains(sb.String(), "| terse | 10 | 9 | 90.0% | +68.6 pp | **0.003** |") {
+		t.Errorf("table missing terse row:\n%s", sb.String())
+	}
+}
+
+func TestFisherExact(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		a, b, c, d int
+		want       float64
+	}{
+		{5, 5, 5, 5, 1},
+		{0, 5, 5, 0, 0.007937},
+		{1, 9, 11, 3, 0.002759},
+		{2, 3, 3, 2, 1},
+	}
+	for _, tc := range tests {
+		if got := fisherExact(tc.a, tc.b, tc.c, tc.d); math.Abs(got-tc.want) > 1e-5 {
+			t.Errorf("fisherExact(%d, %d, %d, %d) = %v, want %v", tc.a, tc.b, tc.c, tc.d, got, tc.want)
+		}
+	}
+}
+
+func TestPromptVariantRender(t *testing.T) {
+	t.Parallel()
+
+	allTasks, err := task.NewLoader(tasks.FS, "").LoadAll()
+	if err != nil {
+		t.Fatalf("LoadAll() error = %v", err)
+	}
+	bank, err := task.ResolveRef(allTasks, "go/bank-account")
+	if err != nil {
+		t.Fatal(err)
+	}
+	plan := &promptVariantPlan{templates: map[string]config.PromptVariant{
+		"terse": {Template: "Implement {task} in {language}; make {test_files} pass.\n\n{prompt}"},
+	}}
+	got := plan.render("terse", "STANDARD", bank)
+	if !strings.HasPrefix(got, "Implement "+bank.Name+" in go; make bank_account_test.go pass.") || !strings.HasSuffix(got, "\n\nSTANDARD") {
+		t.Errorf("render() = %q", got)
+	}
+}
@@ -0,0 +1,343 @@
+package cli
+
+import (
+	"bufio"
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// DatasetSchemaVersion is bumped whenever DatasetRow changes incompatibly.
+const DatasetSchemaVersion = "1"
+
+var (
+	exportOutput        string
+	exportNoTranscripts bool
+	exportAnonymize     bool
+	exportFormat        string
+)
+
+// DatasetRow is one line of an exported JSONL dataset: a single task attempt
+// from a single eval run. See docs/DATASET.md for the field reference.
+type DatasetRow struct {
+	SchemaVersion  string         `json:"schema_version"`
+	RunID          string         `json:"run_id"`
+	HarnessVersion string         `json:"harness_version,omitempty"`
+	WeightVersion  string         `json:"weight_version,omitempty"`
+	Agent          string         `json:"agent"`
+	Model          string         `json:"model"`
+	Reasoning      string         `json:"reasoning,omitempty"`
+	Timestamp      string         `json:"timestamp"`
+	Task           string         `json:"task"`
+	Language       string         `json:"language"`
+	Tier           string         `json:"tier,omitempty"`
+	Difficulty     string         `json:"difficulty,omitempty"`
+	PromptLang     string         `json:"prompt_lang,omitempty"`
+	PromptVariant  string         `json:"prompt_variant,omitempty"`
+	Prompt         string         `json:"prompt"`
+	Transcript     string         `json:"transcript"`
+	SolutionDiff   string         `json:"solution_diff"`
+	Outcome        DatasetOutcome `json:"outcome"`
+	Metrics        DatasetMetrics `json:"metrics"`
+}
+
+// DatasetOutcome captures how a task attempt was scored.
+type DatasetOutcome struct {
+	Passed        bool              `json:"passed"`
+	Status        task.ResultStatus `json:"status"`
+	FailureClass  FailureClass      `json:"failure_class"`
+	AgentTimedOut bool              `json:"agent_timed_out"`
+	Error         string            `json:"error,omitempty"`
+}
+
+// DatasetMetrics holds numeric measurements for a task attempt.
+type DatasetMetrics struct {
+	Duration            float64 `json:"duration_seconds"`
+	AgentTime           float64 `json:"agent_duration_seconds"`
+	ValidateTime        float64 `json:"validation_duration_seconds"`
+	PromptChars         int     `json:"prompt_chars"`
+	Weight              float64 `json:"weight"`
+	WeightedScore       float64 `json:"weighted_score"`
+	QuotaRetries        int     `json:"quota_retries"`
+	InfraRetries        int     `json:"infra_retries"`
+	AgentTimeoutRetries int     `json:"agent_timeout_retries"`
+	ValidationRetries   int     `json:"validation_retries"`
+	SelfTestCommands    int     `json:"self_test_commands"`
+	ToolchainInstalls   int     `json:"toolchain_install_attempts"`
+	OutOfWorkspaceReads int     `json:"out_of_workspace_read_attempts"`
+	SkillsUsed          bool    `json:"skills_used"`
+}
+
+var exportCmd = &cobra.Command{
+	Use:   "export <eval-dir> [eval-dir...]",
+	Short: "Export eval results as a JSONL dataset",
+	// Stdout is the data itself, so the output theme must not touch it.
+	Annotations: map[string]string{annotationRawStdout: "true"},
+	Long: `Exports one or more eval result directories as a JSONL dataset with one row
+per task attempt: task, prompt, model, agent transcript, solution diff,
+outcome, and metrics.
+
+Multi-run umbrella directories are expanded to every run they contain.
+With --anonymize, user names, host names, paths, and the [anonymize]
+identifiers from sanity.toml are replaced with placeholders.
+The output loads directly with standard dataset tooling, e.g.
+datasets.load_dataset("json", data_files="results.jsonl"), and can be
+converted to parquet from there.
+
+--format lm-eval and --format openai-evals instead write the record formats
+of lm-evaluation-harness and OpenAI evals, so scores can be aggregated with
+academic benchmark results. -o is then a directory; without it, only the
+results file (lm-eval) or record log (openai-evals) of each run is printed.
+
+See docs/DATASET.md for the schema.`,
+	Example: `  sanity export eval-results/2026-01-07T120000-gemini -o gemini.jsonl
+  sanity export eval-results/* -o all-runs.jsonl
+  sanity export eval-results/multi-2026-02-21T024300 --no-transcripts
+  sanity export eval-results/* --format lm-eval -o lm-eval-results/`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		var runDirs []string
+		for _, dir := range args {
+			found, err := findRunDirs(dir)
+			if err != nil {
+				return err
+			}
+			runDirs = append(runDirs, found...)
+		}
+		if len(runDirs) == 0 {
+			return fmt.Errorf("no summary.json found under %v", args)
+		}
+
+		switch exportFormat {
+		case exportFormatDataset:
+		case exportFormatLMEval, exportFormatOpenAIEvals:
+			return exportInterchange(runDirs, exportFormat, exportOutput)
+		default:
+			return fmt.Errorf("invalid --format %q (valid: %s, %s, %s)", exportFormat, exportFormatDataset, exportFormatLMEval, exportFormatOpenAIEvals)
+		}
+
+		var out io.Writer = os.Stdout
+		if exportOutput != "" && exportOutput != "-" {
+			f, err := os.Create(exportOutput)
+			if err != nil {
+				return fmt.Errorf("creating output file: %w", err)
+			}
+			defer func() { _ = f.Close() }()
+			out = f
+		}
+
+		w := bufio.NewWriter(out)
+		loader := task.NewLoader(tasks.FS, tasksDir)
+		var anon *anonymizer
+		if exportAnonymize {
+			anon = newAnonymizer(cfg.Anonymize.Identifiers)
+		}
+		total := 0
+		for _, dir := range runDirs {
+			rows, err := buildDatasetRows(dir, loader, !exportNoTranscripts)
+			if err != nil {
+				return fmt.Errorf("exporting %s: %w", dir, err)
+			}
+			var buf bytes.Buffer
+			if err := writeDatasetRows(&buf, rows); err != nil {
+				return err
+			}
+			if _, err := w.Write(anon.apply(buf.Bytes())); err != nil {
+				return fmt.Errorf("writing dataset: %w", err)
+			}
+			total += len(rows)
+		}
+		if err := w.Flush(); err != nil {
+			return fmt.Errorf("writing dataset: %w", err)
+		}
+
+		if exportOutput != "" && exportOutput != "-" {
+			fmt.Printf(" Exported %d rows from %d run(s) to %s\n", total, len(runDirs), exportOutput)
+		}
+		return nil
+	},
+}
+
+func init() {
+	exportCmd.Flags().StringVarP(&exportOutput, "output", "o", "", "output JSONL file, or directory for lm-eval and openai-evals (default: stdout)")
+	exportCmd.Flags().StringVar(&exportFormat, "format", exportFormatDataset, "output format: dataset, lm-eval, or openai-evals")
+	exportCmd.Flags().BoolVar(&exportNoTranscripts, "no-transcripts", false, "omit agent transcripts to reduce dataset size")
+	exportCmd.Flags().BoolVar(&exportAnonymize, "anonymize", false, "strip user names, host names, paths, and configured identifiers")
+}
+
+// findRunDirs returns every directory under root that contains a summary.json,
+// so both single runs and multi-run umbrellas can be exported.
+func findRunDirs(root string) ([]string, error) {
+	var dirs []string
+	err := filepath.WalkDir(root, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if !d.IsDir() && d.Name() == "summary.json" {
+			dirs = append(dirs, filepath.Dir(path))
+		}
+		return nil
+	})
+	if err != nil {
+		return nil, fmt.Errorf("scanning %s: %w", root, err)
+	}
+	sort.Strings(dirs)
+	return dirs, nil
+}
+
+// buildDatasetRows converts a single eval run directory into dataset rows.
+func buildDatasetRows(runDir string, loader *task.Loader, includeTranscripts bool) ([]DatasetRow, error) {
+	summary, err := loadSummaryFromDir(runDir)
+	if err != nil {
+		return nil, err
+	}
+
+	var harnessVersion, weightVersion string
+	if attestation, err := loadPreviousAttestation(runDir); err == nil && attestation != nil {
+		harnessVersion = attestation.Harness.Version
+		weightVersion = attestation.Harness.WeightVersion
+	}
+
+	allTasks, err := loader.LoadAll()
+	if err != nil {
+		return nil, fmt.Errorf("loading tasks: %w", err)
+	}
+	taskByID := make(map[string]*task.Task, len(allTasks))
+	for _, t := range allTasks {
+		taskByID[t.ID()] = t
+	}
+
+	mcpPrompt := ""
+	if cfg != nil {
+		if agentCfg := cfg.GetAgent(summary.Agent); agentCfg != nil {
+			mcpPrompt = agentCfg.MCPPrompt
+		}
+	}
+
+	rows := make([]DatasetRow, 0, len(summary.Results))
+	for _, r := range summary.Results {
+		row := DatasetRow{
+			SchemaVersion:  DatasetSchemaVersion,
+			RunID:          filepath.Base(runDir),
+			HarnessVersion: harnessVersion,
+			WeightVersion:  weightVersion,
+			Agent:          summary.Agent,
+			Model:          summary.Model,
+			Reasoning:      summary.Reasoning,
+			Timestamp:      summary.Timestamp,
+			Task:           r.Task,
+			Language:       r.Language,
+			Tier:           r.Tier,
+			Difficulty:     r.Difficulty,
+			PromptLang:     r.PromptLang,
+			PromptVariant:  r.PromptVariant,
+			Outcome: DatasetOutcome{
+				Passed:        r.Passed,
+				Status:        r.Status,
+				FailureClass:  r.FailureClass,
+				AgentTimedOut: r.AgentTimedOut,
+				Error:         r.Error,
+			},
+			Metrics: DatasetMetrics{
+				Duration:            r.Duration,
+				AgentTime:           r.AgentTime,
+				ValidateTime:        r.ValidateTime,
+				PromptChars:         r.PromptChars,
+				Weight:              r.Weight,
+				WeightedScore:       r.WeightedScore,
+				QuotaRetries:        r.QuotaRetries,
+				InfraRetries:        r.InfraRetries,
+				AgentTimeoutRetries: r.AgentTimeoutRetries,
+				ValidationRetries:   r.ValidationRetries,
+				SelfTestCommands:    r.SelfTestCommands,
+				ToolchainInstalls:   r.ToolchainInstallAttempts,
+				OutOfWorkspaceReads: r.OutOfWorkspaceReadAttempts,
+				SkillsUsed:          r.SkillsUsed,
+			},
+		}
+
+		// Task output directories are named "<lang>-<slug>".
+		taskDir := filepath.Join(runDir, strings.Replace(r.Task, "/", "-", 1))
+		t := taskByID[r.Task]
+		if t != nil {
+			promptTask, _ := t.Localized(r.PromptLang)
+			if summaryTestVisibility(*summary) == config.TestVisibilityNone {
+				promptTask = withoutVisibleTests(promptTask)
+			}
+			row.Prompt = buildAgentPrompt(promptTask, summary.UseMCPTools, summary.UseSkills, mcpPrompt) + skillPackPrompt(summary.SkillPacks)
+		}
+		// Variant templates may have changed since the run, so use the prompt
+		// replay.json recorded instead of rebuilding it.
+		if r.PromptVariant != "" {
+			row.Prompt = ""
+			if data, err := os.ReadFile(filepath.Join(taskDir, replayRecordName)); err == nil {
+				var rec ReplayRecord
+				if json.Unmarshal(data, &rec) == nil {
+					row.Prompt = rec.Prompt
+				}
+			}
+		}
+
+		if includeTranscripts {
+			if data, err := readArtifact(filepath.Join(taskDir, "agent.log")); err == nil {
+				row.Transcript = string(data)
+			} else if !errors.Is(err, fs.ErrNotExist) {
+				logger.Warn("skipping transcript", "task", r.Task, "error", err)
+			}
+		}
+
+		if data, err := os.ReadFile(filepath.Join(taskDir, "solution.diff")); err == nil {
+			row.SolutionDiff = string(data)
+		} else if t != nil && hasStubSources(t, taskDir) {
+			// Runs from before solution.diff was recorded only have a diff
+			// when --keep-workspaces preserved the sources.
+			if diff, err := buildSolutionDiff(loader, t, taskDir); err == nil {
+				row.SolutionDiff = diff
+			}
+		}
+
+		rows = append(rows, row)
+	}
+	return rows, nil
+}
+
+// hasStubSources reports whether any of the task's stub files are present in dir.
+func hasStubSources(t *task.Task, dir string) bool {
+	for _, f := range t.Files.Stub {
+		if _, err := os.Stat(filepath.Join(dir, task.StripTxtExtension(f))); err == nil {
+			return true
+		}
+	}
+	return false
+}
+
+// writeDatasetRows encodes rows as JSONL.
+func writeDatasetRows(w io.Writer, rows []DatasetRow) error {
+	enc := json.NewEncoder(w)
+	enc.SetEscapeHTML(false)
+	for _, row := range rows {
+		if err := enc.Encode(row); err != nil {
+			return fmt.Errorf("encoding row for %s: %w", row.Task, err)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,393 @@
+package cli
+
+import (
+	"bufio"
+	"encoding/json"
+	"fmt"
+	"math"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// Export formats accepted by --format.
+const (
+	exportFormatDataset     = "dataset"
+	exportFormatLMEval      = "lm-eval"
+	exportFormatOpenAIEvals = "openai-evals"
+)
+
+// lmEvalGroup is the lm-evaluation-harness group every SanityHarness task
+// belongs to; each language is a subtask named lmEvalGroup_<language>.
+const lmEvalGroup = "sanityharness"
+
+// exportFile is one file of an interchange export. Primary files are also
+// what goes to stdout when no output directory is given.
+type exportFile struct {
+	name    string
+	data    []byte
+	primary bool
+}
+
+// lmEvalResults mirrors the results_<date>.json file lm-evaluation-harness
+// writes, so its aggregation tooling can read SanityHarness runs.
+type lmEvalResults struct {
+	Results            map[string]map[string]any    `json:"results"`
+	Groups             map[string]map[string]any    `json:"groups"`
+	GroupSubtasks      map[string][]string          `json:"group_subtasks"`
+	Configs            map[string]lmEvalTaskConfig  `json:"configs"`
+	Versions           map[string]string            `json:"versions"`
+	NShot              map[string]int               `json:"n-shot"`
+	HigherIsBetter     map[string]map[string]bool   `json:"higher_is_better"`
+	NSamples           map[string]lmEvalSampleCount `json:"n-samples"`
+	Config             lmEvalModelConfig            `json:"config"`
+	ModelName          string                       `json:"model_name"`
+	ModelNameSanitized string                       `json:"model_name_sanitized"`
+	Date               float64                      `json:"date"`
+	HarnessVersion     string                       `json:"sanityharness_version,omitempty"`
+}
+
+type lmEvalTaskConfig struct {
+	Task       string            `json:"task"`
+	Tag        []string          `json:"tag"`
+	OutputType string            `json:"output_type"`
+	MetricList []lmEvalMetric    `json:"metric_list"`
+	Metadata   map[string]string `json:"metadata"`
+}
+
+type lmEvalMetric struct {
+	Metric         string `json:"metric"`
+	Aggregation    string `json:"aggregation"`
+	HigherIsBetter bool   `json:"higher_is_better"`
+}
+
+type lmEvalSampleCount struct {
+	Original  int `json:"original"`
+	Effective int `json:"effective"`
+}
+
+type lmEvalModelConfig struct {
+	Model     string `json:"model"`
+	ModelArgs string `json:"model_args"`
+	BatchSize int    `json:"batch_size"`
+}
+
+// lmEvalSample is one line of a samples_<task>_<date>.jsonl file.
+type lmEvalSample struct {
+	DocID         int                       `json:"doc_id"`
+	Doc           map[string]string         `json:"doc"`
+	Target        string                    `json:"target"`
+	Arguments     map[string]map[string]any `json:"arguments"`
+	Resps         [][]string                `json:"resps"`
+	FilteredResps []string                  `json:"filtered_resps"`
+	Filter        string                    `json:"filter"`
+	Metrics       []string                  `json:"metrics"`
+	PassAt1       float64                   `json:"pass@1"`
+}
+
+// openAIEvalsEvent is one event line of an OpenAI evals record log.
+type openAIEvalsEvent struct {
+	RunID     string         `json:"run_id"`
+	EventID   int            `json:"event_id"`
+	SampleID  string         `json:"sample_id"`
+	Type      string         `json:"type"`
+	Data      map[string]any `json:"data"`
+	CreatedBy string         `json:"created_by"`
+	CreatedAt string         `json:"created_at"`
+}
+
+// runExportTime parses a run's timestamp, falling back to now for runs
+// without one.
+func runExportTime(timestamp string) time.Time {
+	if t, err := time.ParseInLocation("2006-01-02T150405", timestamp, time.Local); err == nil {
+		return t
+	}
+	return time.Now()
+}
+
+// exportModelLabel names the configuration a run evaluated, as the
+// "model" of benchmark tooling.
+func exportModelLabel(row DatasetRow) string {
+	label := row.Agent
+	if row.Model != "" && row.Model != "unknown" {
+		label += "/" + row.Model
+	}
+	return label
+}
+
+// passStderr is the standard error of the mean of n pass/fail samples with
+// pass rate p, as lm-evaluation-harness computes it, or "N/A" below two
+// samples.
+func passStderr(p float64, n int) any {
+	if n < 2 {
+		return "N/A"
+	}
+	return math.Sqrt(p * (1 - p) / float64(n-1))
+}
+
+// lmEvalMetrics aggregates rows into lm-evaluation-harness metric entries.
+func lmEvalMetrics(alias string, rows []DatasetRow) map[string]any {
+	var passed int
+	var earned, possible float64
+	for _, r := range rows {
+		if r.Outcome.Passed {
+			passed++
+		}
+		earned += r.Metrics.WeightedScore
+		possible += r.Metrics.Weight
+	}
+	rate := float64(passed) / float64(len(rows))
+	weighted := 0.0
+	if possible > 0 {
+		weighted = earned / possible
+	}
+	return map[string]any{
+		"alias":                       alias,
+		"pass@1,none":                 rate,
+		"pass@1_stderr,none":          passStderr(rate, len(rows)),
+		"weighted_pass@1,none":        weighted,
+		"weighted_pass@1_stderr,none": "N/A",
+	}
+}
+
+// buildLMEvalExport converts one run's rows into an lm-evaluation-harness
+// results file and one samples file per language subtask.
+func buildLMEvalExport(rows []DatasetRow, harnessVersion string) []exportFile {
+	byLang := make(map[string][]DatasetRow)
+	for _, r := range rows {
+		byLang[r.Language] = append(byLang[r.Language], r)
+	}
+	langs := make([]string, 0, len(byLang))
+	for lang := range byLang {
+		langs = append(langs, lang)
+	}
+	sort.Strings(langs)
+
+	model := exportModelLabel(rows[0])
+	sanitized := strings.NewReplacer("/", "__", ":", "-", " ", "_").Replace(model)
+	when := runExportTime(rows[0].Timestamp)
+	date := when.Format("2006-01-02T15-04-05")
+	modelArgs := "agent=" + rows[0].Agent
+	if rows[0].Model != "" {
+		modelArgs += ",model=" + rows[0].Model
+	}
+	if rows[0].Reasoning != "" {
+		modelArgs += ",reasoning=" + rows[0].Reasoning
+	}
+
+	res := lmEvalResults{
+		Results:            map[string]map[string]any{lmEvalGroup: lmEvalMetrics(lmEvalGroup, rows)},
+		Groups:             map[string]map[string]any{lmEvalGroup: lmEvalMetrics(lmEvalGroup, rows)},
+		GroupSubtasks:      map[string][]string{lmEvalGroup: nil},
+		Configs:            make(map[string]lmEvalTaskConfig),
+		Versions:           map[string]string{lmEvalGroup: task.WeightVersion},
+		NShot:              make(map[string]int),
+		HigherIsBetter:     make(map[string]map[string]bool),
+		NSamples:           make(map[string]lmEvalSampleCount),
+		Config:             lmEvalModelConfig{Model: rows[0].Agent, ModelArgs: modelArgs, BatchSize: 1},
+		ModelName:          model,
+		ModelNameSanitized: sanitized,
+		Date:               float64(when.Unix()),
+		HarnessVersion:     harnessVersion,
+	}
+	files := make([]exportFile, 0, len(langs))
+	for _, lang := range langs {
+		name := lmEvalGroup + "_" + lang
+		langRows := byLang[lang]
+		res.Results[name] = lmEvalMetrics(" - "+name, langRows)
+		res.GroupSubtasks[lmEvalGroup] = append(res.GroupSubtasks[lmEvalGroup], name)
+		res.Configs[name] = lmEvalTaskConfig{
+			Task:       name,
+			Tag:        []string{lmEvalGroup},
+			OutputType: "generate_until",
+			MetricList: []lmEvalMetric{
+				{Metric: "pass@1", Aggregation: "mean", HigherIsBetter: true},
+				{Metric: "weighted_pass@1", Aggregation: "weighted_mean", HigherIsBetter: true},
+			},
+			Metadata: map[string]string{"version": task.WeightVersion},
+		}
+		res.Versions[name] = task.WeightVersion
+		res.NShot[name] = 0
+		res.HigherIsBetter[name] = map[string]bool{"pass@1": true, "weighted_pass@1": true}
+		res.NSamples[name] = lmEvalSampleCount{Original: len(langRows), Effective: len(langRows)}
+
+		var samples strings.Builder
+		enc := json.NewEncoder(&samples)
+		enc.SetEscapeHTML(false)
+		for i, r := range langRows {
+			_ = enc.Encode(lmEvalSampleFromRow(i, r))
+		}
+		files = append(files, exportFile{
+			name: filepath.Join(sanitized, fmt.Sprintf("samples_%s_%s.jsonl", name, date)),
+			data: []byte(samples.String()),
+		})
+	}
+
+	data, _ := json.MarshalIndent(res, "", "  ")
+	results := exportFile{
+		name:    filepath.Join(sanitized, fmt.Sprintf("results_%s.json", date)),
+		data:    append(data, '\n'),
+		primary: true,
+	}
+	return append([]exportFile{results}, files...)
+}
+
+func lmEvalSampleFromRow(docID int, r DatasetRow) lmEvalSample {
+	pass := 0.0
+	if r.Outcome.Passed {
+		pass = 1
+	}
+	return lmEvalSample{
+		DocID: docID,
+		Doc: map[string]string{
+			"task":       r.Task,
+			"language":   r.Language,
+			"tier":       r.Tier,
+			"difficulty": r.Difficulty,
+		},
+		Target: string(task.StatusPass),
+		Arguments: map[string]map[string]any{
+			"gen_args_0": {"arg_0": r.Prompt, "arg_1": map[string]any{}},
+		},
+		Resps:         [][]string{{r.SolutionDiff}},
+		FilteredResps: []string{string(r.Outcome.Status)},
+		Filter:        "none",
+		Metrics:       []string{"pass@1"},
+		PassAt1:       pass,
+	}
+}
+
+// buildOpenAIEvalsLog converts one run's rows into an OpenAI evals record
+// log: a spec line, sampling and match events per task, and a final report.
+func buildOpenAIEvalsLog(runID string, rows []DatasetRow, includeTranscripts bool) exportFile {
+	split := "all"
+	model := exportModelLabel(rows[0])
+	createdAt := runExportTime(rows[0].Timestamp).UTC().Format("2006-01-02 15:04:05.000000+00:00")
+	evalName := fmt.Sprintf("%s.%s.v%s", lmEvalGroup, split, task.WeightVersion)
+
+	var sb strings.Builder
+	enc := json.NewEncoder(&sb)
+	enc.SetEscapeHTML(false)
+	_ = enc.Encode(map[string]any{"spec": map[string]any{
+		"completion_fns": []string{model},
+		"eval_name":      evalName,
+		"base_eval":      lmEvalGroup,
+		"split":          split,
+		"run_config": map[string]any{
+			"completion_fns": []string{model},
+			"eval_spec":      map[string]any{"cls": "sanityharness", "args": map[string]any{"tasks": len(rows)}},
+			"agent":          rows[0].Agent,
+			"model":          rows[0].Model,
+			"reasoning":      rows[0].Reasoning,
+		},
+		"created_by": "",
+		"run_id":     runID,
+		"created_at": createdAt,
+	}})
+
+	var passed int
+	var earned, possible float64
+	eventID := 0
+	emit := func(sampleID, typ string, data map[string]any) {
+		_ = enc.Encode(openAIEvalsEvent{
+			RunID: runID, EventID: eventID, SampleID: sampleID, Type: typ,
+			Data: data, CreatedAt: createdAt,
+		})
+		eventID++
+	}
+	for i, r := range rows {
+		sampleID := fmt.Sprintf("%s.%s.%d", lmEvalGroup, split, i)
+		if includeTranscripts {
+			emit(sampleID, "sampling", map[string]any{"prompt": r.Prompt, "sampled": []string{r.Transcript}})
+		}
+		emit(sampleID, "match", map[string]any{
+			"correct":  r.Outcome.Passed,
+			"expected": string(task.StatusPass),
+			"picked":   string(r.Outcome.Status),
+			"sampled":  r.SolutionDiff,
+			"task":     r.Task,
+		})
+		emit(sampleID, "metrics", map[string]any{
+			"weight":         r.Metrics.Weight,
+			"weighted_score": r.Metrics.WeightedScore,
+		})
+		if r.Outcome.Passed {
+			passed++
+		}
+		earned += r.Metrics.WeightedScore
+		possible += r.Metrics.Weight
+	}
+
+	report := map[string]any{"accuracy": float64(passed) / float64(len(rows))}
+	if possible > 0 {
+		report["weighted_accuracy"] = earned / possible
+	}
+	_ = enc.Encode(map[string]any{"final_report": report})
+	return exportFile{name: runID + ".jsonl", data: []byte(sb.String()), primary: true}
+}
+
+// exportInterchange exports runs in an external benchmark format. With an
+// output directory every file is written under it; otherwise only each
+// run's primary file goes to stdout.
+func exportInterchange(runDirs []string, format, outDir string) error {
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	var anon *anonymizer
+	if exportAnonymize {
+		anon = newAnonymizer(cfg.Anonymize.Identifiers)
+	}
+	toStdout := outDir == "" || outDir == "-"
+	w := bufio.NewWriter(os.Stdout)
+
+	var written int
+	for _, dir := range runDirs {
+		rows, err := buildDatasetRows(dir, loader, !exportNoTranscripts)
+		if err != nil {
+			return fmt.Errorf("exporting %s: %w", dir, err)
+		}
+		if len(rows) == 0 {
+			continue
+		}
+		var files []exportFile
+		switch format {
+		case exportFormatLMEval:
+			var harnessVersion string
+			if a, err := loadPreviousAttestation(dir); err == nil && a != nil {
+				harnessVersion = a.Harness.Version
+			}
+			files = buildLMEvalExport(rows, harnessVersion)
+		case exportFormatOpenAIEvals:
+			files = []exportFile{buildOpenAIEvalsLog(filepath.Base(dir), rows, !exportNoTranscripts)}
+		}
+		for _, f := range files {
+			data := anon.apply(f.data)
+			if toStdout {
+				if f.primary {
+					if _, err := w.Write(data); err != nil {
+						return fmt.Errorf("writing export: %w", err)
+					}
+				}
+				continue
+			}
+			path := filepath.Join(outDir, f.name)
+			if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+				return fmt.Errorf("creating output directory: %w", err)
+			}
+			if err := os.WriteFile(path, data, 0o644); err != nil {
+				return fmt.Errorf("writing %s: %w", path, err)
+			}
+			written++
+		}
+	}
+	if err := w.Flush(); err != nil {
+		return fmt.Errorf("writing export: %w", err)
+	}
+	if !toStdout {
+		fmt.Printf(" Exported %d run(s) as %s to %s (%d files)\n", len(runDirs), format, outDir, written)
+	}
+	return nil
+}
@@ -0,0 +1,124 @@
+package cli
+
+import (
+	"encoding/json"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func exportFormatRows() []DatasetRow {
+	row := func(id, lang string, passed bool, weight float64) DatasetRow {
+		r := DatasetRow{
+			Agent: "codex", Model: "gpt-5", Timestamp: "2026-02-22T010203",
+			Task: id, Language: lang, Prompt: "Implement " + id, Transcript: "transcript",
+			SolutionDiff: "--- a/" + id,
+			Outcome:      DatasetOutcome{Passed: passed, Status: task.StatusFail},
+			Metrics:      DatasetMetrics{Weight: weight},
+		}
+		if passed {
+			r.Outcome.Status = task.StatusPass
+			r.Metrics.WeightedScore = weight
+		}
+		return r
+	}
+	return []DatasetRow{
+		row("go/bank-account", "go", true, 1.0),
+		row("go/react", "go", false, 1.2),
+		row("zig/comptime-json", "zig", true, 1.5),
+	}
+}
+
+func TestBuildLMEvalExport(t *testing.T) {
+	t.Parallel()
+
+	files := buildLMEvalExport(exportFormatRows(), "v1.9.0")
+	if len(files) != 3 || !files[0].primary {
+		t.Fatalf("files = %d, want a primary results file and two samples files", len(files))
+	}
+	if want := filepath.Join("codex__gpt-5", "results_2026-02-22T01-02-03.json"); files[0].name != want {
+		t.Errorf("results file = %q, want %q", files[0].name, want)
+	}
+
+	var res lmEvalResults
+	if err := json.Unmarshal(files[0].data, &res); err != nil {
+		t.Fatalf("results are not JSON: %v", err)
+	}
+	group := res.Results[lmEvalGroup]
+	if rate := group["pass@1,none"].(float64); rate < 0.66 || rate > 0.67 {
+		t.Errorf("group pass@1 = %v, want 2/3", rate)
+	}
+	if weighted := group["weighted_pass@1,none"].(float64); weighted < 0.675 || weighted > 0.676 {
+		t.Errorf("group weighted_pass@1 = %v, want 2.5/3.7", weighted)
+	}
+	if got := res.GroupSubtasks[lmEvalGroup]; len(got) != 2 || got[0] != "sanityharness_go" || got[1] != "sanityharness_zig" {
+		t.Errorf("group_subtasks = %v", got)
+	}
+	if res.NSamples["sanityharness_go"].Effective != 2 || res.Results["sanityharness_zig"]["pass@1_stderr,none"] != "N/A" {
+		t.Errorf("unexpected subtask stats: %+v %+v", res.NSamples, res.Results["sanityharness_zig"])
+	}
+	if res.ModelName != "codex/gpt-5" || res.Config.ModelArgs != "agent=codex,model=gpt-5" || res.HarnessVersion != "v1.9.0" {
+		t.Errorf("model = %q, args = %q, version = %q", res.ModelName, res.Config.ModelArgs, res.HarnessVersion)
+	}
+
+	lines := strings.Split(strings.TrimSpace(string(files[1].data)), "\n")
+	if !strings.HasSuffix(files[1].name, "samples_sanityharness_go_2026-02-22T01-02-03.jsonl") || len(lines) != 2 {
+		t.Fatalf("go samples = %q with %d lines", files[1].name, len(lines))
+	}
+	var sample lmEvalSample
+	if err := json.Unmarshal([]byte(lines[1]), &sample); err != nil {
+		t.Fatalf("sample is not JSON: %v", err)
+	}
+	if sample.DocID != 1 || sample.PassAt1 != 0 || sample.Doc["task"] != "go/react" || sample.FilteredResps[0] != "fail" {
+		t.Errorf("sample = %+v", sample)
+	}
+}
+
+func TestBuildOpenAIEvalsLog(t *testing.T) {
+	t.Parallel()
+
+	f := buildOpenAIEvalsLog("2026-02-22T010203-codex", exportFormatRows(), false)
+	lines := strings.Split(strings.TrimSpace(string(f.data)), "\n")
+	// Spec, match and metrics events for three tasks, and the final report.
+	if len(lines) != 8 {
+		t.Fatalf("record log has %d lines, want 8:\n%s", len(lines), f.data)
+	}
+	var spec struct {
+		Spec struct {
+			EvalName      string   `json:"eval_name"`
+			CompletionFns []string `json:"completion_fns"`
+			RunID         string   `json:"run_id"`
+		} `json:"spec"`
+	}
+	if err := json.Unmarshal([]byte(lines[0]), &spec); err != nil {
+		t.Fatalf("spec line: %v", err)
+	}
+	if spec.Spec.EvalName != "sanityharness.all.v"+task.WeightVersion || spec.Spec.CompletionFns[0] != "codex/gpt-5" || spec.Spec.RunID != "2026-02-22T010203-codex" {
+		t.Errorf("spec = %+v", spec.Spec)
+	}
+
+	var match openAIEvalsEvent
+	if err := json.Unmarshal([]byte(lines[3]), &match); err != nil {
+		t.Fatalf("event line: %v", err)
+	}
+	if match.Type != "match" || match.SampleID != "sanityharness.all.1" || match.EventID != 2 || match.Data["correct"] != false {
+		t.Errorf("second match event = %+v", match)
+	}
+
+	var final struct {
+		FinalReport map[string]float64 `json:"final_report"`
+	}
+	if err := json.Unmarshal([]byte(lines[7]), &final); err != nil {
+		t.Fatalf("final report: %v", err)
+	}
+	if acc := final.FinalReport["accuracy"]; acc < 0.66 || acc > 0.67 {
+		t.Errorf("accuracy = %v, want 2/3", acc)
+	}
+
+	withTranscripts := buildOpenAIEvalsLog("run", exportFormatRows(), true)
+	if !strings.Contains(string(withTranscripts.data), `"type":"sampling"`) {
+		t.Error("transcripts requested but no sampling events written")
+	}
+}
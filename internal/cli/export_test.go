@@ -0,0 +1,113 @@
+package cli
+
+import (
+	"bytes"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+func TestBuildSolutionDiff(t *testing.T) {
+	t.Parallel()
+
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	taskDef, err := loader.Load("bank-account")
+	if err != nil {
+		t.Fatalf("load task: %v", err)
+	}
+
+	workspaceDir := t.TempDir()
+	if err := os.WriteFile(filepath.Join(workspaceDir, "bank_account.go"), []byte("package account\n\n// solved\n"), 0o644); err != nil {
+		t.Fatalf("write solution: %v", err)
+	}
+
+	diff, err := buildSolutionDiff(loader, taskDef, workspaceDir)
+	if err != nil {
+		t.Fatalf("buildSolutionDiff() error = %v", err)
+	}
+	if !strings.Contains(diff, "+++ b/bank_account.go") {
+		t.Fatalf("expected b/ label in diff, got: %s", diff)
+	}
+	if !strings.Contains(diff, "+// solved") {
+		t.Fatalf("expected added line in diff, got: %s", diff)
+	}
+}
+
+func TestBuildDatasetRows(t *testing.T) {
+	t.Parallel()
+
+	runDir := filepath.Join(t.TempDir(), "2026-02-22T010203-codex")
+	taskDir := filepath.Join(runDir, "go-bank-account")
+	if err := os.MkdirAll(taskDir, 0o755); err != nil {
+		t.Fatalf("mkdir task dir: %v", err)
+	}
+
+	summary := EvalSummary{
+		Agent:     "codex",
+		Model:     "gpt-5",
+		Timestamp: "2026-02-22T010203",
+		Results: []EvalResult{{
+			Task:          "go/bank-account",
+			Language:      "go",
+			Tier:          "core",
+			Difficulty:    "hard",
+			Passed:        true,
+			Status:        task.StatusPass,
+			FailureClass:  FailureClassNone,
+			Duration:      12.5,
+			Weight:        1.2,
+			WeightedScore: 1.2,
+		}},
+	}
+	data, _ := json.Marshal(summary)
+	if err := os.WriteFile(filepath.Join(runDir, "summary.json"), data, 0o644); err != nil {
+		t.Fatalf("write summary: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(taskDir, "agent.log"), []byte("agent transcript"), 0o644); err != nil {
+		t.Fatalf("write agent.log: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(taskDir, "solution.diff"), []byte("--- a/bank_account.go\n"), 0o644); err != nil {
+		t.Fatalf("write solution.diff: %v", err)
+	}
+
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	rows, err := buildDatasetRows(runDir, loader, true)
+	if err != nil {
+		t.Fatalf("buildDatasetRows() error = %v", err)
+	}
+	if len(rows) != 1 {
+		t.Fatalf("rows len = %d, want 1", len(rows))
+	}
+	row := rows[0]
+	if row.SchemaVersion != DatasetSchemaVersion {
+		t.Fatalf("schema_version = %q, want %q", row.SchemaVersion, DatasetSchemaVersion)
+	}
+	if row.RunID != "2026-02-22T010203-codex" {
+		t.Fatalf("run_id = %q", row.RunID)
+	}
+	if row.Transcript != "agent transcript" {
+		t.Fatalf("transcript = %q", row.Transcript)
+	}
+	if !strings.HasPrefix(row.SolutionDiff, "--- a/bank_account.go") {
+		t.Fatalf("solution_diff = %q", row.SolutionDiff)
+	}
+	if !strings.Contains(row.Prompt, "bank_account.go") {
+		t.Fatalf("expected prompt to mention stub file, got: %s", row.Prompt)
+	}
+	if !row.Outcome.Passed || row.Metrics.WeightedScore != 1.2 {
+		t.Fatalf("unexpected outcome/metrics: %+v %+v", row.Outcome, row.Metrics)
+	}
+
+	var buf bytes.Buffer
+	if err := writeDatasetRows(&buf, rows); err != nil {
+		t.Fatalf("writeDatasetRows() error = %v", err)
+	}
+	if lines := strings.Count(buf.String(), "\n"); lines != 1 {
+		t.Fatalf("expected 1 JSONL line, got %d", lines)
+	}
+}
@@ -0,0 +1,209 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// followUpDirName holds each follow-up's agent.log, validation.log, and
+// attempt snapshots in the task output directory, as follow-ups/<n>/.
+const followUpDirName = "follow-ups"
+
+// FollowUpResult is the outcome of one of a task's scripted follow-up
+// requests. Number counts from 1.
+type FollowUpResult struct {
+	Number        int          `json:"number"`
+	Passed        bool         `json:"passed"`
+	AgentTimedOut bool         `json:"agent_timed_out,omitempty"`
+	AgentTime     float64      `json:"agent_duration_seconds"`
+	ValidateTime  float64      `json:"validation_duration_seconds"`
+	Error         string       `json:"error,omitempty"`
+	FailureClass  FailureClass `json:"failure_class,omitempty"`
+}
+
+// followUpSession is what a task's follow-ups share with its first turn: the
+// agent, and the workspace it keeps working in.
+type followUpSession struct {
+	agentCfg          *config.AgentConfig
+	agent, model      string
+	agentWorkDir      string
+	workspaceDir      string
+	agentTimeout      time.Duration
+	validationTimeout int
+}
+
+// runFollowUps gives the agent t's follow-up requests in order once its
+// solution passes, validating each against the hidden tests so far. The
+// first follow-up that fails ends the conversation. Follow-ups are scored
+// separately and never change the task's own result.
+func runFollowUps(ctx context.Context, result *EvalResult, r *runner.Runner, loader *task.Loader, t *task.Task, s followUpSession) {
+	if len(t.FollowUps) == 0 || !result.Passed {
+		return
+	}
+	for i := range t.FollowUps {
+		if checkInterrupted(ctx) {
+			break
+		}
+		fu := runFollowUp(ctx, result, r, loader, t, i, s)
+		result.FollowUps = append(result.FollowUps, fu)
+		if !fu.Passed {
+			logger.Debug("follow-up failed", "task", t.ID(), "follow_up", fu.Number, "error", fu.Error)
+			break
+		}
+	}
+	result.FollowUpScore = float64(followUpsPassedIn(*result)) / float64(len(t.FollowUps))
+}
+
+// followUpsPassedIn counts the follow-ups r passed.
+func followUpsPassedIn(r EvalResult) int {
+	passed := 0
+	for _, fu := range r.FollowUps {
+		if fu.Passed {
+			passed++
+		}
+	}
+	return passed
+}
+
+// runFollowUp sends follow-up n to the agent in its workspace from the
+// previous turn, then validates the result.
+func runFollowUp(ctx context.Context, result *EvalResult, r *runner.Runner, loader *task.Loader, t *task.Task, n int, s followUpSession) FollowUpResult {
+	fu := FollowUpResult{Number: n + 1}
+	dir := filepath.Join(s.workspaceDir, followUpDirName, strconv.Itoa(fu.Number))
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		fu.Error = fmt.Sprintf("creating follow-up output dir: %v", err)
+		return fu
+	}
+	agentLogPath := filepath.Join(dir, "agent.log")
+	validationLogPath := filepath.Join(dir, "validation.log")
+
+	if err := evalChaos.install(s.agentWorkDir, t.Language); err != nil {
+		fu.Error = fmt.Sprintf("installing chaos shims: %v", err)
+		return fu
+	}
+	agentResult := executeAgentWithRetries(ctx, t, s.agentCfg, buildFollowUpPrompt(t, n), s.model,
+		s.agentWorkDir, agentLogPath, s.agentTimeout, s.agent, time.Now())
+	if evalChaos != nil {
+		injections, err := removeChaosShims(s.agentWorkDir)
+		if err != nil {
+			logger.Warn("failed to remove chaos shims", "task", t.ID(), "error", err)
+		}
+		result.ChaosInjections += injections
+	}
+	fu.AgentTime = agentResult.totalTime
+	fu.AgentTimedOut = agentResult.timedOut
+	switch agentResult.failureClass {
+	case FailureClassInfra, FailureClassAuth, FailureClassQuotaExhausted:
+		fu.FailureClass = agentResult.failureClass
+		fu.Error = strings.ToLower(externalFailureLabel(agentResult.failureClass))
+		return fu
+	}
+
+	modified, err := detectModifiedTaskFiles(loader, t, s.agentWorkDir)
+	if err != nil {
+		fu.Error = fmt.Sprintf("integrity check failed: %v", err)
+		return fu
+	}
+	if len(modified) > 0 {
+		fu.FailureClass = FailureClassIntegrity
+		fu.Error = fmt.Sprintf("modified task files: %s", strings.Join(modified, ", "))
+		return fu
+	}
+
+	// Replace the previous turn's sources and build output with the agent's
+	// current work, then add the hidden tests so far.
+	cleanupWorkspaceFiles(s.workspaceDir)
+	if err := copyDirContents(s.agentWorkDir, s.workspaceDir); err != nil {
+		fu.Error = fmt.Sprintf("copying agent workspace: %v", err)
+		return fu
+	}
+	if err := restoreVisibleTests(loader, t, s.workspaceDir); err != nil {
+		fu.Error = err.Error()
+		return fu
+	}
+	stage := t.FollowUpTask(n)
+	if err := writeTaskFilesToWorkspace(loader, stage, s.workspaceDir, stage.HiddenTestFiles()); err != nil {
+		fu.Error = fmt.Sprintf("writing hidden tests: %v", err)
+		return fu
+	}
+	validationCmd, effectiveValidationCmd := buildValidationCommands(stage)
+	session, validateDuration, err := runValidationSession(ctx, r, stage, s.workspaceDir, s.validationTimeout, validationCmd)
+	fu.ValidateTime = validateDuration
+	if err != nil {
+		rawOutput, exitCode, duration := validationErrorEvidence(session, validateDuration)
+		writeValidationLog(validationLogPath, rawOutput, effectiveValidationCmd, exitCode, duration,
+			strings.Contains(strings.ToLower(err.Error()), "timed out"), err)
+		fu.Error = err.Error()
+		fu.FailureClass = FailureClassValidationError
+		return fu
+	}
+	writeValidationSessionLog(validationLogPath, effectiveValidationCmd, session)
+	fu.Passed = session.Passed()
+	return fu
+}
+
+// buildFollowUpPrompt returns the prompt for follow-up n of t. The agent
+// starts a new session, so the prompt restates where it left off.
+func buildFollowUpPrompt(t *task.Task, n int) string {
+	stubFiles := make([]string, 0, len(t.Files.Stub))
+	for _, f := range t.Files.Stub {
+		stubFiles = append(stubFiles, task.StripTxtExtension(f))
+	}
+	return fmt.Sprintf(`You are continuing work on a coding task called "%s" (%s).
+The workspace holds your solution so far, and it passes every test up to this point.
+
+FOLLOW-UP REQUEST (%d of %d):
+%s
+
+RULES:
+- Change the existing solution in place; it must keep passing the earlier tests.
+- There may be hidden tests that check this request.
+- ONLY edit the stub/solution source file(s): %s. You may add new helper source files if needed.
+- Do NOT modify test files or support files. Evaluation fails if you modify protected files.
+- Do NOT navigate to parent directories or read files outside the workspace.`,
+		t.Name, t.Language, n+1, len(t.FollowUps), strings.TrimSpace(t.FollowUps[n].Prompt), strings.Join(stubFiles, ", "))
+}
+
+// writeReportFollowUps lists how far each task with follow-ups got.
+func writeReportFollowUps(sb *strings.Builder, summary EvalSummary) {
+	if summary.FollowUpTasks == 0 {
+		return
+	}
+	sb.WriteString("## Follow-ups\n\n")
+	fmt.Fprintf(sb, "Scripted follow-up requests given after a task's solution passed; %d of %d passed (%.1f%%). "+
+		"A failed follow-up ends the task's conversation. Follow-ups do not affect pass rate or weighted score; "+
+		"their logs are kept in `%s/<n>/`.\n\n",
+		summary.FollowUpsPassed, summary.FollowUpsTotal, summary.FollowUpPassRate, followUpDirName)
+	sb.WriteString("| Task | Initial | Follow-ups Passed | Stopped At |\n")
+	sb.WriteString("|------|---------|-------------------|------------|\n")
+	for _, r := range summary.Results {
+		if r.FollowUpCount == 0 {
+			continue
+		}
+		stopped := "-"
+		for _, fu := range r.FollowUps {
+			if fu.Passed {
+				continue
+			}
+			reason := "failed"
+			if fu.Error != "" {
+				reason = strings.ReplaceAll(fu.Error, "|", `\|`)
+			}
+			stopped = fmt.Sprintf("#%d: %s", fu.Number, reason)
+		}
+		if !r.Passed {
+			stopped = "not reached"
+		}
+		fmt.Fprintf(sb, "| %s | %s | %d/%d | %s |\n", r.Task, passLabel(r.Passed), followUpsPassedIn(r), r.FollowUpCount, stopped)
+	}
+	sb.WriteString("\n")
+}
@@ -0,0 +1,73 @@
+package cli
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestBuildFollowUpPrompt(t *testing.T) {
+	t.Parallel()
+
+	tk := &task.Task{
+		Name:     "Bank Account",
+		Language: task.Go,
+		Files:    task.TaskFiles{Stub: []string{"bank_account.go.txt"}},
+		FollowUps: []task.FollowUp{
+			{Prompt: "Now make it thread-safe.", HiddenTest: []string{"concurrent_test.go.txt"}},
+			{Prompt: "  Now add an iterator over the transactions.\n", HiddenTest: []string{"iter_test.go.txt"}},
+		},
+	}
+	prompt := buildFollowUpPrompt(tk, 1)
+	for _, want := range []string{
+		`"Bank Account" (go)`,
+		"FOLLOW-UP REQUEST (2 of 2):\nNow add an iterator over the transactions.\n",
+		"source file(s): bank_account.go.",
+	} {
+		if !strings.Contains(prompt, want) {
+			t.Errorf("prompt missing %q:\n%s", want, prompt)
+		}
+	}
+	if strings.Contains(prompt, "thread-safe") {
+		t.Errorf("prompt includes an earlier follow-up:\n%s", prompt)
+	}
+}
+
+func TestSummarizeAndReportFollowUps(t *testing.T) {
+	t.Parallel()
+
+	results := []EvalResult{
+		{Task: "go/bank-account", Passed: true, FollowUpCount: 2, FollowUps: []FollowUpResult{
+			{Number: 1, Passed: true},
+			{Number: 2, Error: "validation timed out"},
+		}},
+		{Task: "go/react", Passed: true, FollowUpCount: 1, FollowUps: []FollowUpResult{{Number: 1, Passed: true}}},
+		{Task: "rust/regex-lite", FollowUpCount: 1},
+		{Task: "go/singleflight", Passed: true},
+	}
+	var summary EvalSummary
+	summarizeResults(&summary, results, nil)
+	if summary.FollowUpTasks != 3 || summary.FollowUpsPassed != 2 || summary.FollowUpsTotal != 4 || summary.FollowUpPassRate != 50 {
+		t.Fatalf("follow-up totals = %d tasks, %d/%d passed, %.1f%%",
+			summary.FollowUpTasks, summary.FollowUpsPassed, summary.FollowUpsTotal, summary.FollowUpPassRate)
+	}
+
+	var sb strings.Builder
+	writeReportFollowUps(&sb, summary)
+	out := sb.String()
+	for _, want := range []string{
+		"## Follow-ups",
+		"2 of 4 passed (50.0%)",
+		"| go/bank-account | pass | 1/2 | #2: validation timed out |",
+		"| go/react | pass | 1/1 | - |",
+		"| rust/regex-lite | fail | 0/1 | not reached |",
+	} {
+		if !strings.Contains(out, want) {
+			t.Errorf("report missing %q:\n%s", want, out)
+		}
+	}
+	if strings.Contains(out, "go/singleflight") {
+		t.Errorf("task without follow-ups in report:\n%s", out)
+	}
+}
@@ -0,0 +1,155 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+)
+
+// fsAuditName is the per-task file listing what the agent changed.
+const fsAuditName = "fs-audit.json"
+
+// FileActivity counts the workspace files an agent created, modified, and
+// deleted.
+type FileActivity struct {
+	Created  int `json:"created"`
+	Modified int `json:"modified"`
+	Deleted  int `json:"deleted"`
+}
+
+// NoOp reports whether the agent left the workspace unchanged.
+func (a FileActivity) NoOp() bool {
+	return a.Created == 0 && a.Modified == 0 && a.Deleted == 0
+}
+
+// FSAudit is the per-task audit log of workspace changes, derived by hashing
+// the workspace before and after the agent ran.
+type FSAudit struct {
+	Task     string         `json:"task"`
+	Created  []FSAuditEntry `json:"created"`
+	Modified []FSAuditEntry `json:"modified"`
+	Deleted  []FSAuditEntry `json:"deleted"`
+}
+
+// FSAuditEntry is one changed file with its content hash before and after.
+type FSAuditEntry struct {
+	Path   string `json:"path"`
+	Before string `json:"before,omitempty"`
+	After  string `json:"after,omitempty"`
+}
+
+// Activity returns the change counts of the audit.
+func (a FSAudit) Activity() FileActivity {
+	return FileActivity{Created: len(a.Created), Modified: len(a.Modified), Deleted: len(a.Deleted)}
+}
+
+// snapshotWorkspace hashes every regular file under dir, keyed by
+// slash-separated relative path. Build and cache directories are skipped like
+// they are for replay records, so toolchain output doesn't swamp the audit.
+func snapshotWorkspace(dir string) (map[string]string, error) {
+	hashes := make(map[string]string)
+	err := filepath.WalkDir(dir, func(p string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if d.IsDir() {
+			if p != dir && replaySkipDirs[d.Name()] {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		rel, err := filepath.Rel(dir, p)
+		if err != nil {
+			return err
+		}
+		info, err := d.Info()
+		if err != nil {
+			return err
+		}
+		data, err := readFilePreservingAtime(p, info)
+		if err != nil {
+			return fmt.Errorf("reading %s: %w", rel, err)
+		}
+		hashes[filepath.ToSlash(rel)] = hashBytes(data)
+		return nil
+	})
+	if err != nil {
+		return nil, fmt.Errorf("snapshotting workspace: %w", err)
+	}
+	return hashes, nil
+}
+
+// readFilePreservingAtime reads the file at path and then restores its access
+// time, so harness reads during an eval don't look like agent reads to
+// hidden-test detection on relatime mounts.
+func readFilePreservingAtime(path string, info fs.FileInfo) ([]byte, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, err
+	}
+	if atime, ok := fileAccessTime(info); ok {
+		_ = os.Chtimes(path, atime, info.ModTime())
+	}
+	return data, nil
+}
+
+// diffWorkspaceSnapshots compares two snapshots. Entries are sorted by path.
+func diffWorkspaceSnapshots(taskID string, before, after map[string]string) FSAudit {
+	audit := FSAudit{Task: taskID, Created: []FSAuditEntry{}, Modified: []FSAuditEntry{}, Deleted: []FSAuditEntry{}}
+	for p, hash := range after {
+		prev, ok := before[p]
+		switch {
+		case !ok:
+			audit.Created = append(audit.Created, FSAuditEntry{Path: p, After: hash})
+		case prev != hash:
+			audit.Modified = append(audit.Modified, FSAuditEntry{Path: p, Before: prev, After: hash})
+		}
+	}
+	for p, hash := range before {
+		if _, ok := after[p]; !ok {
+			audit.Deleted = append(audit.Deleted, FSAuditEntry{Path: p, Before: hash})
+		}
+	}
+	for _, entries := range [][]FSAuditEntry{audit.Created, audit.Modified, audit.Deleted} {
+		sort.Slice(entries, func(i, j int) bool { return entries[i].Path < entries[j].Path })
+	}
+	return audit
+}
+
+// auditWorkspaceChanges compares workspace against the snapshot taken before
+// the agent ran, records the change counts on result, and writes the audit log
+// to taskOutputDir. Auditing is best-effort and never fails the task.
+func auditWorkspaceChanges(result *EvalResult, before map[string]string, workspace, taskOutputDir string) {
+	if before == nil {
+		return
+	}
+	after, err := snapshotWorkspace(workspace)
+	if err != nil {
+		logger.Debug("failed to audit workspace changes", "task", result.Task, "error", err)
+		return
+	}
+	audit := diffWorkspaceSnapshots(result.Task, before, after)
+	activity := audit.Activity()
+	result.FileActivity = &activity
+	if err := writeFSAudit(taskOutputDir, audit); err != nil {
+		logger.Debug("failed to write fs audit", "task", result.Task, "error", err)
+	}
+}
+
+// writeFSAudit writes the audit log into the task output directory.
+func writeFSAudit(taskOutputDir string, audit FSAudit) error {
+	data, err := json.MarshalIndent(audit, "", "  ")
+	if err != nil {
+		return fmt.Errorf("marshaling fs audit: %w", err)
+	}
+	if err := os.WriteFile(filepath.Join(taskOutputDir, fsAuditName), data, 0o644); err != nil {
+		return fmt.Errorf("writing fs audit: %w", err)
+	}
+	return nil
+}
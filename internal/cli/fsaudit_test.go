@@ -0,0 +1,91 @@
+package cli
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestWorkspaceAudit(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	write := func(rel, content string) {
+		t.Helper()
+		path := filepath.Join(dir, filepath.FromSlash(rel))
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	write("main.go", "package main\n")
+	write("util/util.go", "package util\n")
+	write("README.md", "stub\n")
+
+	before, err := snapshotWorkspace(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	write("main.go", "package main\n\nfunc main() {}\n")
+	write("util/extra.go", "package util\n")
+	write("node_modules/dep/index.js", "module.exports = {}\n")
+	if err := os.Remove(filepath.Join(dir, "README.md")); err != nil {
+		t.Fatal(err)
+	}
+
+	after, err := snapshotWorkspace(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	audit := diffWorkspaceSnapshots("go/example", before, after)
+
+	if got := audit.Activity(); got != (FileActivity{Created: 1, Modified: 1, Deleted: 1}) || got.NoOp() {
+		t.Fatalf("Activity() = %+v", got)
+	}
+	if audit.Created[0].Path != "util/extra.go" || audit.Created[0].Before != "" {
+		t.Fatalf("unexpected created entry: %+v", audit.Created[0])
+	}
+	if m := audit.Modified[0]; m.Path != "main.go" || m.Before == m.After || m.Before != before["main.go"] {
+		t.Fatalf("unexpected modified entry: %+v", m)
+	}
+	if audit.Deleted[0].Path != "README.md" || audit.Deleted[0].After != "" {
+		t.Fatalf("unexpected deleted entry: %+v", audit.Deleted[0])
+	}
+
+	out := t.TempDir()
+	if err := writeFSAudit(out, audit); err != nil {
+		t.Fatal(err)
+	}
+	data, err := os.ReadFile(filepath.Join(out, fsAuditName))
+	if err != nil {
+		t.Fatal(err)
+	}
+	var decoded FSAudit
+	if err := json.Unmarshal(data, &decoded); err != nil {
+		t.Fatal(err)
+	}
+	if decoded.Task != "go/example" || len(decoded.Created) != 1 {
+		t.Fatalf("unexpected decoded audit: %+v", decoded)
+	}
+}
+
+func TestWorkspaceAuditNoOp(t *testing.T) {
+	t.Parallel()
+
+	snapshot := map[string]string{"main.go": "blake3:00"}
+	audit := diffWorkspaceSnapshots("go/example", snapshot, snapshot)
+	if !audit.Activity().NoOp() {
+		t.Fatalf("expected no-op, got %+v", audit.Activity())
+	}
+	data, err := json.Marshal(audit)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if string(data) != `{"task":"go/example","created":[],"modified":[],"deleted":[]}` {
+		t.Fatalf("unexpected JSON: %s", data)
+	}
+}
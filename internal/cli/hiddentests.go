@@ -0,0 +1,137 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// Sources of hidden test access evidence.
+const (
+	hiddenAccessTranscript = "transcript"
+	hiddenAccessFilesystem = "filesystem"
+)
+
+// maxHiddenAccessEvidence bounds the transcript line kept as evidence.
+const maxHiddenAccessEvidence = 200
+
+// HiddenAccess records evidence that an agent opened or searched a hidden
+// test file that was present in its workspace.
+type HiddenAccess struct {
+	Source   string `json:"source"`
+	File     string `json:"file"`
+	Evidence string `json:"evidence,omitempty"`
+}
+
+// hiddenTestPaths returns the workspace paths of t's hidden test files.
+func hiddenTestPaths(t *task.Task) []string {
+	paths := make([]string, 0, len(t.HiddenTestFiles()))
+	for _, f := range t.HiddenTestFiles() {
+		paths = append(paths, task.StripTxtExtension(f))
+	}
+	return paths
+}
+
+// detectHiddenTestAccess checks the agent transcript and the workspace for
+// signs that the agent looked at hidden test files. It only makes sense when
+// hidden tests were written into the workspace (legacy mode). since is when
+// the workspace was ready for the agent.
+func detectHiddenTestAccess(t *task.Task, agentLogPath, workspace string, since time.Time) []HiddenAccess {
+	hidden := hiddenTestPaths(t)
+	if len(hidden) == 0 {
+		return nil
+	}
+	var accesses []HiddenAccess
+	if data, err := os.ReadFile(agentLogPath); err == nil {
+		accesses = append(accesses, scanTranscriptForHiddenAccess(strings.Split(string(data), "\n"), hidden)...)
+	}
+	return append(accesses, auditHiddenTestAtimes(workspace, hidden, since)...)
+}
+
+// scanTranscriptForHiddenAccess flags read, search, and shell tool calls that
+// name a hidden test file, and search output that reports matches inside one.
+// Write and edit calls are ignored so an agent creating a file of the same
+// name isn't flagged. Each file is reported at most once.
+func scanTranscriptForHiddenAccess(lines, hidden []string) []HiddenAccess {
+	names := make([]string, len(hidden))
+	hitPatterns := make([]*regexp.Regexp, len(hidden))
+	for i, h := range hidden {
+		names[i] = filepath.Base(filepath.FromSlash(h))
+		hitPatterns[i] = regexp.MustCompile(`(^|[/\s])` + regexp.QuoteMeta(names[i]) + `:\d+[:-]`)
+	}
+
+	var accesses []HiddenAccess
+	seen := make(map[string]bool)
+	lastTool := ""
+	for _, rawLine := range lines {
+		line := strings.TrimSpace(ansiEscapePattern.ReplaceAllString(rawLine, ""))
+		if line == "" {
+			continue
+		}
+		category := classifyToolLine(line)
+		for i, h := range hidden {
+			if seen[h] || !strings.Contains(line, names[i]) {
+				continue
+			}
+			flagged := false
+			switch category {
+			case toolRead, toolSearch, toolShell:
+				flagged = true
+			case "":
+				// Search output only counts right after a search call, so test
+				// failures that cite hidden test lines aren't mistaken for reads.
+				flagged = lastTool == toolSearch && hitPatterns[i].MatchString(line)
+			}
+			if flagged {
+				seen[h] = true
+				accesses = append(accesses, HiddenAccess{
+					Source:   hiddenAccessTranscript,
+					File:     h,
+					Evidence: truncateEvidence(line),
+				})
+			}
+		}
+		if category != "" {
+			lastTool = category
+		}
+	}
+	return accesses
+}
+
+// auditHiddenTestAtimes flags hidden test files whose access time moved past
+// since. This is best-effort: filesystems mounted noatime never update it, and
+// anything that reads the file counts, including test runs that compile it.
+func auditHiddenTestAtimes(workspace string, hidden []string, since time.Time) []HiddenAccess {
+	var accesses []HiddenAccess
+	for _, h := range hidden {
+		info, err := os.Stat(filepath.Join(workspace, filepath.FromSlash(h)))
+		if err != nil {
+			continue
+		}
+		atime, ok := fileAccessTime(info)
+		if !ok {
+			return nil
+		}
+		if atime.After(since) {
+			accesses = append(accesses, HiddenAccess{
+				Source:   hiddenAccessFilesystem,
+				File:     h,
+				Evidence: fmt.Sprintf("accessed at %s", atime.UTC().Format(time.RFC3339)),
+			})
+		}
+	}
+	return accesses
+}
+
+func truncateEvidence(line string) string {
+	runes := []rune(line)
+	if len(runes) <= maxHiddenAccessEvidence {
+		return line
+	}
+	return string(runes[:maxHiddenAccessEvidence]) + "..."
+}
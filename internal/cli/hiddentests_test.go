@@ -0,0 +1,80 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestScanTranscriptForHiddenAccess(t *testing.T) {
+	t.Parallel()
+
+	hidden := []string{"pool_hidden_test.go", "test/parser_hidden_test.dart"}
+	lines := []string{
+		"✔ WriteFile pool_hidden_test.go",
+		"--- FAIL: TestHidden (0.00s)",
+		"    pool_hidden_test.go:12: expected 3, got 2",
+		"$ grep -rn Parse test/",
+		"test/parser_hidden_test.dart:40:  expect(parse('x'), isNull);",
+		"$ cat pool_hidden_test.go",
+		"$ cat pool_hidden_test.go",
+	}
+
+	got := scanTranscriptForHiddenAccess(lines, hidden)
+	if len(got) != 2 {
+		t.Fatalf("got %d accesses, want 2: %+v", len(got), got)
+	}
+	if got[0].File != "test/parser_hidden_test.dart" || got[0].Source != hiddenAccessTranscript ||
+		!strings.HasPrefix(got[0].Evidence, "test/parser_hidden_test.dart:40:") {
+		t.Fatalf("unexpected search hit access: %+v", got[0])
+	}
+	if got[1].File != "pool_hidden_test.go" || got[1].Evidence != "$ cat pool_hidden_test.go" {
+		t.Fatalf("unexpected read access: %+v", got[1])
+	}
+}
+
+func TestScanTranscriptForHiddenAccessIgnoresTestOutput(t *testing.T) {
+	t.Parallel()
+
+	lines := []string{
+		"$ go test ./...",
+		"    pool_hidden_test.go:12: expected 3, got 2",
+		"FAIL",
+	}
+	if got := scanTranscriptForHiddenAccess(lines, []string{"pool_hidden_test.go"}); len(got) != 0 {
+		t.Fatalf("test output flagged as access: %+v", got)
+	}
+}
+
+func TestAuditHiddenTestAtimes(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	since := time.Now().Add(-time.Minute)
+	for name, atime := range map[string]time.Time{
+		"read_hidden_test.go":   since.Add(30 * time.Second),
+		"unread_hidden_test.go": since.Add(-time.Hour),
+	} {
+		path := filepath.Join(dir, name)
+		if err := os.WriteFile(path, []byte("package main\n"), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.Chtimes(path, atime, since.Add(-time.Hour)); err != nil {
+			t.Fatal(err)
+		}
+	}
+	info, err := os.Stat(filepath.Join(dir, "read_hidden_test.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, ok := fileAccessTime(info); !ok {
+		t.Skip("access times not supported on this platform")
+	}
+
+	got := auditHiddenTestAtimes(dir, []string{"read_hidden_test.go", "unread_hidden_test.go", "missing_hidden_test.go"}, since)
+	if len(got) != 1 || got[0].File != "read_hidden_test.go" || got[0].Source != hiddenAccessFilesystem {
+		t.Fatalf("unexpected accesses: %+v", got)
+	}
+}
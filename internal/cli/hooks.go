@@ -0,0 +1,148 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/exec"
+	"runtime"
+	"strings"
+	"time"
+)
+
+// Lifecycle hook events, matching the [hooks] config keys.
+const (
+	hookPreRun      = "pre_run"
+	hookPreTask     = "pre_task"
+	hookPostAttempt = "post_attempt"
+	hookPostRun     = "post_run"
+)
+
+// hookOutputTail bounds how much hook output is quoted in error messages.
+const hookOutputTail = 500
+
+// HookEvent is the JSON payload written to a hook command's stdin.
+type HookEvent struct {
+	Event       string       `json:"event"`
+	Agent       string       `json:"agent"`
+	Model       string       `json:"model,omitempty"`
+	Reasoning   string       `json:"reasoning,omitempty"`
+	Timestamp   string       `json:"timestamp"`
+	OutputDir   string       `json:"output_dir"`
+	Tasks       []string     `json:"tasks,omitempty"`
+	Task        string       `json:"task,omitempty"`
+	Workspace   string       `json:"workspace_dir,omitempty"`
+	Attempt     *HookAttempt `json:"attempt,omitempty"`
+	Summary     *EvalSummary `json:"summary,omitempty"`
+	Interrupted bool         `json:"interrupted,omitempty"`
+}
+
+// HookAttempt describes a single agent attempt for post_attempt hooks.
+type HookAttempt struct {
+	Number          int     `json:"number"`
+	DurationSeconds float64 `json:"duration_seconds"`
+	TimedOut        bool    `json:"timed_out"`
+	AgentLog        string  `json:"agent_log"`
+}
+
+// evalHookRun carries the run-level fields of every hook event. Like the
+// other eval globals it is set by evalRunSingle before tasks start.
+var evalHookRun HookEvent
+
+// hookCommands returns the configured commands for event.
+func hookCommands(event string) []string {
+	if cfg == nil {
+		return nil
+	}
+	switch event {
+	case hookPreRun:
+		return cfg.Hooks.PreRun
+	case hookPreTask:
+		return cfg.Hooks.PreTask
+	case hookPostAttempt:
+		return cfg.Hooks.PostAttempt
+	case hookPostRun:
+		return cfg.Hooks.PostRun
+	default:
+		return nil
+	}
+}
+
+// runHooks runs the commands configured for ev.Event in order, stopping at
+// the first failure. Run-level fields are filled from evalHookRun.
+func runHooks(ctx context.Context, ev HookEvent) error {
+	commands := hookCommands(ev.Event)
+	if len(commands) == 0 {
+		return nil
+	}
+	ev.Agent = evalHookRun.Agent
+	ev.Model = evalHookRun.Model
+	ev.Reasoning = evalHookRun.Reasoning
+	ev.Timestamp = evalHookRun.Timestamp
+	ev.OutputDir = evalHookRun.OutputDir
+	payload, err := json.Marshal(ev)
+	if err != nil {
+		return fmt.Errorf("encoding %s hook payload: %w", ev.Event, err)
+	}
+
+	timeout := time.Duration(cfg.Hooks.Timeout) * time.Second
+	for _, command := range commands {
+		start := time.Now()
+		out, err := runHookCommand(ctx, command, ev, payload, timeout)
+		logger.Debug("hook finished", "event", ev.Event, "command", command, "task", ev.Task,
+			"duration", time.Since(start).Round(time.Millisecond), "output", strings.TrimSpace(string(out)))
+		if err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// runHookCommand runs one hook command with payload on stdin and returns its
+// combined output.
+func runHookCommand(ctx context.Context, command string, ev HookEvent, payload []byte, timeout time.Duration) ([]byte, error) {
+	hookCtx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+
+	c := hookShellCommand(hookCtx, command)
+	c.Stdin = bytes.NewReader(payload)
+	c.Env = append(os.Environ(),
+		"SANITY_HOOK_EVENT="+ev.Event,
+		"SANITY_OUTPUT_DIR="+ev.OutputDir,
+		"SANITY_TASK="+ev.Task,
+	)
+	// Don't wait on grandchildren that inherited the output pipe after a kill.
+	c.WaitDelay = time.Second
+	out, err := c.CombinedOutput()
+	if err == nil {
+		return out, nil
+	}
+	if hookCtx.Err() != nil && ctx.Err() == nil {
+		err = fmt.Errorf("timed out after %s", timeout)
+	}
+	tail := strings.TrimSpace(string(out))
+	if len(tail) > hookOutputTail {
+		tail = "..." + tail[len(tail)-hookOutputTail:]
+	}
+	if tail != "" {
+		return out, fmt.Errorf("%s hook %q failed: %w: %s", ev.Event, command, err, tail)
+	}
+	return out, fmt.Errorf("%s hook %q failed: %w", ev.Event, command, err)
+}
+
+// hookShellCommand runs command through the platform shell.
+func hookShellCommand(ctx context.Context, command string) *exec.Cmd {
+	if runtime.GOOS == "windows" {
+		return exec.CommandContext(ctx, "cmd", "/C", command)
+	}
+	return exec.CommandContext(ctx, "sh", "-c", command)
+}
+
+// warnOnHookError logs a non-fatal hook failure.
+func warnOnHookError(err error) {
+	if err != nil {
+		logger.Warn("hook failed", "error", err)
+	}
+}
@@ -0,0 +1,66 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestRunHookCommand(t *testing.T) {
+	t.Parallel()
+	if runtime.GOOS == "windows" {
+		t.Skip("hook tests use sh")
+	}
+
+	dir := t.TempDir()
+	out := filepath.Join(dir, "payload.json")
+	ev := HookEvent{Event: hookPostAttempt, Agent: "gemini", Task: "go/react", OutputDir: dir,
+		Attempt: &HookAttempt{Number: 2, TimedOut: true}}
+	payload, _ := json.Marshal(ev)
+
+	cmd := `cat > "$SANITY_OUTPUT_DIR/payload.json"; echo "$SANITY_HOOK_EVENT $SANITY_TASK"`
+	got, err := runHookCommand(context.Background(), cmd, ev, payload, 5*time.Second)
+	if err != nil {
+		t.Fatalf("runHookCommand() error = %v", err)
+	}
+	if strings.TrimSpace(string(got)) != "post_attempt go/react" {
+		t.Fatalf("hook env output = %q", got)
+	}
+	data, err := os.ReadFile(out)
+	if err != nil {
+		t.Fatalf("hook did not receive payload: %v", err)
+	}
+	var received HookEvent
+	if err := json.Unmarshal(data, &received); err != nil {
+		t.Fatalf("payload is not JSON: %v", err)
+	}
+	if received.Attempt == nil || received.Attempt.Number != 2 || !received.Attempt.TimedOut || received.Agent != "gemini" {
+		t.Fatalf("unexpected payload: %+v", received)
+	}
+
+	_, err = runHookCommand(context.Background(), "echo boom >&2; exit 3", ev, payload, 5*time.Second)
+	if err == nil || !strings.Contains(err.Error(), "boom") || !strings.Contains(err.Error(), "post_attempt") {
+		t.Fatalf("expected failure with output, got %v", err)
+	}
+
+	_, err = runHookCommand(context.Background(), "sleep 5", ev, payload, 50*time.Millisecond)
+	if err == nil || !strings.Contains(err.Error(), "timed out") {
+		t.Fatalf("expected timeout, got %v", err)
+	}
+}
+
+func TestHookCommandsWithoutConfig(t *testing.T) {
+	t.Parallel()
+
+	if cfg != nil {
+		t.Skip("global config is set")
+	}
+	if err := runHooks(context.Background(), HookEvent{Event: hookPreRun}); err != nil {
+		t.Fatalf("runHooks() with no config = %v, want nil", err)
+	}
+}
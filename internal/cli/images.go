@@ -0,0 +1,551 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"text/tabwriter"
+	"time"
+
+	"github.com/docker/go-units"
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+var (
+	imagesDir           string
+	imagesTasks         string
+	imagesLang          string
+	imagesTier          string
+	imagesWarmTimeout   int
+	imagesContainersDir string
+	imagesPruneAll      bool
+	imagesPruneDryRun   bool
+)
+
+var imagesCmd = &cobra.Command{
+	Use:   "images",
+	Short: "Manage the task container images",
+	Long: `Manages the images tasks are graded in, so fetching and building them does
+not have to happen as a side effect of the first eval task.
+
+'images pull' fetches the images of the selected tasks, 'images build' builds
+the ones that come from local Dockerfiles, 'images list' shows which are
+present, and 'images prune' removes the ones no task uses any more.
+
+'images export' saves each image as a tarball in .sanity-cache/images. When a
+pull fails after all retries and mirrors, eval and run load the image from
+there, so a registry outage does not abort a run. 'images import' loads
+tarballs ahead of time, e.g. on a machine without registry access. 'images
+warm' pre-compiles task dependencies into the build caches.`,
+}
+
+// Sources of a task's image, in the order TaskImage resolves them.
+const (
+	imageSourceOverride   = "override"   // [task] image
+	imageSourceManifest   = "manifest"   // The task manifest's image
+	imageSourceDockerfile = "dockerfile" // Built from the task manifest's dockerfile
+	imageSourceLanguage   = "language"   // The [docker] image for the task's language
+)
+
+// taskImage is an image some of the selected tasks are graded in.
+type taskImage struct {
+	ref     string
+	source  string
+	first   *task.Task // The first task graded in it, whose language or Dockerfile it comes from
+	taskIDs []string
+}
+
+var imagesPullCmd = &cobra.Command{
+	Use:   "pull",
+	Short: "Pull the images of the selected tasks",
+	Long: `Pulls every image the selected tasks are graded in, all tasks by default, so
+the first eval task does not wait on a download. Images that already exist
+are pulled again, which refreshes moving tags like latest. Pulls use the
+[docker] pull_retries and mirrors, then fall back to the tarballs in --dir.
+Images built from a task's Dockerfile are skipped; see 'images build'.`,
+	Example: `  sanity images pull
+  sanity images pull --lang rust
+  sanity images pull --tasks bank-account,regex-lite`,
+	RunE: func(cmd *cobra.Command, _ []string) error {
+		images, err := selectedTaskImages()
+		if err != nil {
+			return err
+		}
+		docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = docker.Close() }()
+
+		ctx := cmd.Context()
+		policy := runner.NewPullPolicy(cfg.Docker)
+		policy.CacheDir = imagesDir
+		policy.OnRetry = func(ref string, attempt int, err error) {
+			if attempt == 0 {
+				logger.Warn("image pull failed, trying fallback", "source", ref, "error", err)
+				return
+			}
+			logger.Warn("image pull failed, retrying", "image", ref, "attempt", attempt, "error", err)
+		}
+		var pulled, failed, built int
+		for _, img := range images {
+			if checkInterrupted(ctx) {
+				return ctx.Err()
+			}
+			if img.source == imageSourceDockerfile {
+				built++
+				continue
+			}
+			start := time.Now()
+			if err := docker.RefreshImage(ctx, img.ref, policy); err != nil {
+				failed++
+				fmt.Printf(" ✗ %s: %v\n", img.ref, err)
+				continue
+			}
+			pulled++
+			fmt.Printf(" ✓ %-48s %s\n", img.ref, time.Since(start).Round(time.Second))
+		}
+		fmt.Printf("\n Pulled %d of %d image(s)\n", pulled, len(images)-built)
+		if built > 0 {
+			fmt.Printf(" Skipped %d image(s) built from task Dockerfiles; run 'sanity images build'\n", built)
+		}
+		if failed > 0 {
+			return fmt.Errorf("%d image(s) failed to pull", failed)
+		}
+		return nil
+	},
+}
+
+var imagesBuildCmd = &cobra.Command{
+	Use:   "build",
+	Short: "Build the images of the selected tasks from local Dockerfiles",
+	Long: `Builds the images of the selected tasks, all tasks by default, that come from
+local Dockerfiles:
+
+  - A language image from containers/Dockerfile-<language> (see
+    --containers-dir), tagged as the [docker] image for that language. This
+    needs a checkout of the harness; languages without a Dockerfile there are
+    skipped. Language images are rebuilt every time, to pick up new base
+    images.
+  - The image of each task whose manifest names a dockerfile. Its tag is a
+    hash of the build context, so an existing image is up to date and is not
+    rebuilt.
+
+Images named by a [task] override or a task manifest are pulled, not built;
+see 'images pull'.`,
+	Example: `  sanity images build
+  sanity images build --lang go
+  sanity images build --tasks nightly-simd`,
+	RunE: func(cmd *cobra.Command, _ []string) error {
+		images, err := selectedTaskImages()
+		if err != nil {
+			return err
+		}
+		docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = docker.Close() }()
+
+		ctx := cmd.Context()
+		loader := task.NewLoader(tasks.FS, tasksDir)
+		var built, failed, skipped int
+		for _, img := range images {
+			if checkInterrupted(ctx) {
+				return ctx.Err()
+			}
+			start := time.Now()
+			status, err := buildTaskImage(ctx, docker, loader, img)
+			switch {
+			case err != nil:
+				failed++
+				fmt.Printf(" ✗ %s: %v\n", img.ref, err)
+			case status == "":
+				skipped++
+			default:
+				built++
+				fmt.Printf(" ✓ %-48s %s in %s\n", img.ref, status, time.Since(start).Round(time.Second))
+			}
+		}
+		if skipped == len(images) {
+			return fmt.Errorf("nothing to build: no selected language has a Dockerfile in %s and no selected task names a dockerfile", imagesContainersDir)
+		}
+		fmt.Printf("\n Built %d of %d image(s)\n", built, len(images)-skipped)
+		if failed > 0 {
+			return fmt.Errorf("%d image(s) failed to build", failed)
+		}
+		return nil
+	},
+}
+
+var imagesListCmd = &cobra.Command{
+	Use:   "list",
+	Short: "List the images of the selected tasks",
+	Long: `Lists each image the selected tasks are graded in, all tasks by default, with
+where it comes from, how many tasks use it, and whether it is present
+locally. SOURCE is one of:
+
+  language    the [docker] image for the tasks' language
+  override    a [task] image override
+  manifest    the image the task manifest names
+  dockerfile  built from the task manifest's dockerfile`,
+	Example: `  sanity images list
+  sanity images list --lang kotlin`,
+	RunE: func(cmd *cobra.Command, _ []string) error {
+		images, err := selectedTaskImages()
+		if err != nil {
+			return err
+		}
+		docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = docker.Close() }()
+		local, err := docker.LocalImages(cmd.Context())
+		if err != nil {
+			return err
+		}
+
+		w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+		_, _ = fmt.Fprintln(w, "IMAGE\tSOURCE\tTASKS\tLOCAL\tSIZE\tCREATED")
+		for _, img := range images {
+			info, ok := local[img.ref]
+			if !ok {
+				_, _ = fmt.Fprintf(w, "%s\t%s\t%d\tmissing\t-\t-\n", img.ref, img.source, len(img.taskIDs))
+				continue
+			}
+			_, _ = fmt.Fprintf(w, "%s\t%s\t%d\tyes\t%s\t%s\n", img.ref, img.source, len(img.taskIDs),
+				units.HumanSize(float64(info.Size)), info.Created.Format("2006-01-02"))
+		}
+		return w.Flush()
+	},
+}
+
+var imagesPruneCmd = &cobra.Command{
+	Use:   "prune",
+	Short: "Remove task images no task uses any more",
+	Long: `Removes the images built from task Dockerfiles that no task is graded in any
+more. Each change to a task's build context builds a new tag and leaves the
+old image behind.
+
+With --all, the images every task is graded in are removed too, pulled ones
+included, e.g. to free disk space; later runs pull or build them again.
+Images a container still uses are reported and kept.`,
+	Example: `  sanity images prune --dry-run
+  sanity images prune --all`,
+	RunE: func(cmd *cobra.Command, _ []string) error {
+		allTasks, err := selectTasks("", "", "all", "")
+		if err != nil {
+			return err
+		}
+		images, err := groupTaskImages(allTasks)
+		if err != nil {
+			return err
+		}
+		inUse := make(map[string]bool, len(images))
+		for _, img := range images {
+			inUse[img.ref] = true
+		}
+		docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = docker.Close() }()
+
+		ctx := cmd.Context()
+		local, err := docker.LocalImages(ctx)
+		if err != nil {
+			return err
+		}
+		var stale []string
+		for ref := range local {
+			if (runner.BuiltTaskImage(ref) && !inUse[ref]) || (imagesPruneAll && inUse[ref]) {
+				stale = append(stale, ref)
+			}
+		}
+		if len(stale) == 0 {
+			fmt.Println("No images to prune.")
+			return nil
+		}
+		slices.Sort(stale)
+		var removed int
+		for _, ref := range stale {
+			size := units.HumanSize(float64(local[ref].Size))
+			if imagesPruneDryRun {
+				fmt.Printf(" - %-56s %s\n", ref, size)
+				continue
+			}
+			if err := docker.RemoveImage(ctx, ref); err != nil {
+				fmt.Printf(" ✗ %v\n", err)
+				continue
+			}
+			removed++
+			fmt.Printf(" ✓ Removed %-48s %s\n", ref, size)
+		}
+		if imagesPruneDryRun {
+			fmt.Printf("\n Would remove %d image(s)\n", len(stale))
+			return nil
+		}
+		fmt.Printf("\n Removed %d of %d image(s)\n", removed, len(stale))
+		return nil
+	},
+}
+
+var imagesExportCmd = &cobra.Command{
+	Use:   "export [language...]",
+	Short: "Save the configured images as tarballs",
+	Example: `  sanity images export
+  sanity images export go rust --dir /mnt/shared/sanity-images`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		langs, err := imageLanguages(args)
+		if err != nil {
+			return err
+		}
+		docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = docker.Close() }()
+
+		ctx := cmd.Context()
+		policy := runner.NewPullPolicy(cfg.Docker)
+		policy.CacheDir = ""
+		for _, lang := range langs {
+			ref := cfg.ImageForLanguage(lang)
+			if err := docker.EnsureImage(ctx, ref, policy); err != nil {
+				return fmt.Errorf("%s image: %w", lang, err)
+			}
+			path := runner.ImageTarballPath(imagesDir, ref)
+			if err := docker.SaveImageFile(ctx, ref, path); err != nil {
+				return err
+			}
+			fmt.Printf(" ✓ %-10s %s → %s\n", lang, ref, path)
+		}
+		return nil
+	},
+}
+
+var imagesImportCmd = &cobra.Command{
+	Use:   "import [tarball...]",
+	Short: "Load image tarballs into Docker",
+	Long: `Loads image tarballs written by 'images export' (or 'docker save'). Without
+arguments, every tarball in --dir is loaded.`,
+	Example: `  sanity images import
+  sanity images import /mnt/shared/sanity-images/ghcr.io_lemon07r_sanity-go_latest.tar`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		paths := args
+		if len(paths) == 0 {
+			var err error
+			paths, err = filepath.Glob(filepath.Join(imagesDir, "*.tar"))
+			if err != nil {
+				return err
+			}
+			if len(paths) == 0 {
+				return fmt.Errorf("no image tarballs in %s", imagesDir)
+			}
+		}
+		docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = docker.Close() }()
+
+		ctx := cmd.Context()
+		for _, p := range paths {
+			if err := docker.LoadImageFile(ctx, p); err != nil {
+				return err
+			}
+			fmt.Printf(" ✓ Loaded %s\n", p)
+		}
+		return nil
+	},
+}
+
+var imagesWarmCmd = &cobra.Command{
+	Use:   "warm",
+	Short: "Pre-compile task dependencies into the build caches",
+	Long: `Builds each selected Rust and Go task, hidden tests included, in its task
+image without running any tests. The compiled dependencies (and, for Go, the
+standard library built with the task's flags) land in the build caches under
+.sanity-cache/ that every validation container mounts, so validation only has
+to compile the agent's code. Other languages are skipped.
+
+Warming is incremental: rerun it after updating images or tasks. A task whose
+stubs do not compile is reported but still warms what it can.`,
+	Example: `  sanity images warm
+  sanity images warm --lang rust
+  sanity images warm --tasks bank-account,regex-lite`,
+	RunE: func(cmd *cobra.Command, _ []string) error {
+		selected, err := selectTasks(imagesTasks, imagesLang, imagesTier, "")
+		if err != nil {
+			return err
+		}
+		warmable := make([]*task.Task, 0, len(selected))
+		for _, t := range selected {
+			if t.WarmCommand() != nil {
+				warmable = append(warmable, t)
+			}
+		}
+		if len(warmable) == 0 {
+			return fmt.Errorf("no Rust or Go tasks match the selection")
+		}
+
+		r, err := runner.NewRunner(cfg, tasks.FS, tasksDir, logger)
+		if err != nil {
+			return err
+		}
+		defer func() { _ = r.Close() }()
+
+		ctx := cmd.Context()
+		var failed int
+		for _, t := range warmable {
+			if checkInterrupted(ctx) {
+				return ctx.Err()
+			}
+			start := time.Now()
+			res, err := r.WarmTask(ctx, t, time.Duration(imagesWarmTimeout)*time.Second)
+			elapsed := time.Since(start).Round(time.Second)
+			switch {
+			case err != nil:
+				failed++
+				fmt.Printf(" ✗ %-36s %v\n", t.ID(), err)
+			case res.ExitCode != 0:
+				failed++
+				fmt.Printf(" ! %-36s built with errors (exit %d) in %s\n", t.ID(), res.ExitCode, elapsed)
+			default:
+				fmt.Printf(" ✓ %-36s %s\n", t.ID(), elapsed)
+			}
+		}
+		fmt.Printf("\n Warmed %d of %d task(s)\n", len(warmable)-failed, len(warmable))
+		return nil
+	},
+}
+
+func init() {
+	imagesCmd.PersistentFlags().StringVar(&imagesDir, "dir", runner.ImageCacheDir, "directory of image tarballs")
+	for _, c := range []*cobra.Command{imagesPullCmd, imagesBuildCmd, imagesListCmd, imagesWarmCmd} {
+		langHelp := "filter by language"
+		if c == imagesWarmCmd {
+			langHelp = "filter by language (go, rust)"
+		}
+		c.Flags().StringVar(&imagesTasks, "tasks", "", "comma-separated list of task slugs")
+		c.Flags().StringVar(&imagesLang, "lang", "", langHelp)
+		c.Flags().StringVar(&imagesTier, "tier", "all", "filter by tier (core, extended, all)")
+	}
+	imagesBuildCmd.Flags().StringVar(&imagesContainersDir, "containers-dir", "containers", "directory of the language Dockerfiles (Dockerfile-go, Dockerfile-ts, ...)")
+	imagesPruneCmd.Flags().BoolVar(&imagesPruneAll, "all", false, "also remove the images tasks are graded in now")
+	imagesPruneCmd.Flags().BoolVar(&imagesPruneDryRun, "dry-run", false, "list the images without removing them")
+	imagesWarmCmd.Flags().IntVar(&imagesWarmTimeout, "timeout", 1800, "seconds allowed to build each task")
+	imagesCmd.AddCommand(imagesPullCmd)
+	imagesCmd.AddCommand(imagesBuildCmd)
+	imagesCmd.AddCommand(imagesListCmd)
+	imagesCmd.AddCommand(imagesPruneCmd)
+	imagesCmd.AddCommand(imagesExportCmd)
+	imagesCmd.AddCommand(imagesImportCmd)
+	imagesCmd.AddCommand(imagesWarmCmd)
+}
+
+// selectedTaskImages returns the images of the tasks selected by --tasks,
+// --lang, and --tier.
+func selectedTaskImages() ([]*taskImage, error) {
+	selected, err := selectTasks(imagesTasks, imagesLang, imagesTier, "")
+	if err != nil {
+		return nil, err
+	}
+	if len(selected) == 0 {
+		return nil, fmt.Errorf("no tasks match the selection")
+	}
+	return groupTaskImages(selected)
+}
+
+// groupTaskImages groups taskList by the image each task is graded in, in
+// order of first use.
+func groupTaskImages(taskList []*task.Task) ([]*taskImage, error) {
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	byRef := make(map[string]*taskImage)
+	images := make([]*taskImage, 0, len(task.AllLanguages))
+	for _, t := range taskList {
+		ref, err := runner.TaskImage(cfg, loader, t)
+		if err != nil {
+			return nil, err
+		}
+		img := byRef[ref]
+		if img == nil {
+			img = &taskImage{ref: ref, source: taskImageSource(t), first: t}
+			byRef[ref] = img
+			images = append(images, img)
+		}
+		img.taskIDs = append(img.taskIDs, t.ID())
+	}
+	return images, nil
+}
+
+// taskImageSource returns where t's image comes from, one of the
+// imageSource constants.
+func taskImageSource(t *task.Task) string {
+	switch {
+	case cfg.TaskOverride(t.ID()).Image != "":
+		return imageSourceOverride
+	case t.Image != "":
+		return imageSourceManifest
+	case t.Dockerfile != "":
+		return imageSourceDockerfile
+	default:
+		return imageSourceLanguage
+	}
+}
+
+// buildTaskImage builds img if it comes from a local Dockerfile and reports
+// what it did, or "" if img is not built locally.
+func buildTaskImage(ctx context.Context, docker *runner.DockerClient, loader *task.Loader, img *taskImage) (string, error) {
+	switch img.source {
+	case imageSourceLanguage:
+		path := languageDockerfile(imagesContainersDir, img.first.Language)
+		if _, err := os.Stat(path); err != nil {
+			return "", nil
+		}
+		return "built from " + path, runner.BuildDockerfileImage(ctx, docker, path, img.ref)
+	case imageSourceDockerfile:
+		exists, err := docker.ImageExists(ctx, img.ref)
+		if err != nil || exists {
+			return "up to date", err
+		}
+		_, err = runner.BuildTaskImage(ctx, docker, cfg, loader, img.first)
+		return "built for " + img.first.ID(), err
+	default:
+		return "", nil
+	}
+}
+
+// languageDockerfile returns the path of a language image's Dockerfile in
+// dir, named as in the harness's containers/ directory.
+func languageDockerfile(dir string, lang task.Language) string {
+	suffix := string(lang)
+	if lang == task.TypeScript {
+		suffix = "ts"
+	}
+	return filepath.Join(dir, "Dockerfile-"+suffix)
+}
+
+// imageLanguages validates language arguments, defaulting to every language.
+func imageLanguages(args []string) ([]string, error) {
+	if len(args) == 0 {
+		langs := make([]string, 0, len(task.AllLanguages))
+		for _, l := range task.AllLanguages {
+			langs = append(langs, string(l))
+		}
+		return langs, nil
+	}
+	for _, a := range args {
+		if cfg.ImageForLanguage(a) == "" {
+			return nil, fmt.Errorf("unknown language %q", a)
+		}
+	}
+	return args, nil
+}
@@ -0,0 +1,56 @@
+package cli
+
+import (
+	"path/filepath"
+	"slices"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestGroupTaskImages(t *testing.T) {
+	c := config.Default
+	c.Tasks = map[string]config.TaskOverride{"go/react": {Image: "custom-go:1"}}
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+
+	taskList := []*task.Task{
+		{Slug: "bank-account", Language: task.Go},
+		{Slug: "react", Language: task.Go},
+		{Slug: "dining-philosophers", Language: task.Go},
+		{Slug: "nightly-simd", Language: task.Rust, Image: "rust:nightly"},
+	}
+	images, err := groupTaskImages(taskList)
+	if err != nil {
+		t.Fatalf("groupTaskImages() error = %v", err)
+	}
+	type row struct {
+		ref, source string
+		taskIDs     []string
+	}
+	want := []row{
+		{c.ImageForLanguage("go"), imageSourceLanguage, []string{"go/bank-account", "go/dining-philosophers"}},
+		{"custom-go:1", imageSourceOverride, []string{"go/react"}},
+		{"rust:nightly", imageSourceManifest, []string{"rust/nightly-simd"}},
+	}
+	if len(images) != len(want) {
+		t.Fatalf("groupTaskImages() = %d images, want %d", len(images), len(want))
+	}
+	for i, img := range images {
+		if img.ref != want[i].ref || img.source != want[i].source || !slices.Equal(img.taskIDs, want[i].taskIDs) {
+			t.Errorf("image %d = %s %s %v, want %+v", i, img.ref, img.source, img.taskIDs, want[i])
+		}
+	}
+}
+
+func TestLanguageDockerfile(t *testing.T) {
+	t.Parallel()
+
+	for lang, want := range map[task.Language]string{task.Go: "Dockerfile-go", task.TypeScript: "Dockerfile-ts", task.Zig: "Dockerfile-zig"} {
+		if got := languageDockerfile("containers", lang); got != filepath.Join("containers", want) {
+			t.Errorf("languageDockerfile(%s) = %q, want %q", lang, got, want)
+		}
+	}
+}
@@ -0,0 +1,131 @@
+package cli
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+)
+
+// importRecordName records when and from which archive a run was imported.
+const importRecordName = "import.json"
+
+var (
+	importResultsDir string
+	importForce      bool
+)
+
+// ImportRecord is written to an imported run directory.
+type ImportRecord struct {
+	Archive    string           `json:"archive"`
+	ImportedAt string           `json:"imported_at"`
+	Origin     *ArchiveManifest `json:"origin,omitempty"`
+}
+
+var importCmd = &cobra.Command{
+	Use:   "import <archive> [archive...]",
+	Short: "Import archived eval runs into the local results directory",
+	Long: `Unpacks archives written by 'sanity archive' into the results directory, so
+runs from other bench machines show up in compare, analyze, serve, and ETA
+history alongside local ones.
+
+Each run keeps its original directory name, timestamps, and the archive.json
+naming the machine it came from; import.json records when it was imported.
+Re-importing an identical run is a no-op. A different run with the same name
+is refused unless --force replaces it.`,
+	Example: `  sanity import bench-01/2026-01-07T120000-gemini.tar.gz
+  sanity import runs/*.tar.gz --results-dir /srv/sanity/eval-results`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if err := os.MkdirAll(importResultsDir, 0o755); err != nil {
+			return fmt.Errorf("creating results directory: %w", err)
+		}
+		for _, archivePath := range args {
+			dest, imported, err := importRunArchive(archivePath, importResultsDir, importForce, time.Now())
+			if err != nil {
+				return err
+			}
+			if imported {
+				fmt.Printf(" Imported %s → %s\n", archivePath, dest)
+			} else {
+				fmt.Printf(" Already imported: %s\n", dest)
+			}
+		}
+		return nil
+	},
+}
+
+func init() {
+	importCmd.Flags().StringVar(&importResultsDir, "results-dir", defaultEvalResultsDir, "directory to import runs into")
+	importCmd.Flags().BoolVar(&importForce, "force", false, "replace an existing run with the same name")
+}
+
+// importRunArchive unpacks archivePath into resultsDir and returns the run's
+// new directory. imported is false when an identical run was already there.
+func importRunArchive(archivePath, resultsDir string, force bool, now time.Time) (dest string, imported bool, err error) {
+	runDir, tmp, err := extractRunArchive(archivePath, resultsDir)
+	if err != nil {
+		return "", false, err
+	}
+	defer func() { _ = os.RemoveAll(tmp) }()
+
+	summary, err := os.ReadFile(filepath.Join(runDir, "summary.json"))
+	if err != nil {
+		return "", false, fmt.Errorf("%s is not an eval run archive: %w", archivePath, err)
+	}
+	dest = filepath.Join(resultsDir, filepath.Base(runDir))
+	if runDir == tmp {
+		dest = filepath.Join(resultsDir, archiveBaseName(archivePath))
+	}
+
+	existing, err := os.ReadFile(filepath.Join(dest, "summary.json"))
+	switch {
+	case err == nil && bytes.Equal(existing, summary):
+		return dest, false, nil
+	case err == nil && !force:
+		return "", false, fmt.Errorf("%s already holds a different run; use --force to replace it", dest)
+	case err != nil && !errors.Is(err, fs.ErrNotExist):
+		return "", false, fmt.Errorf("reading existing run: %w", err)
+	}
+
+	record := ImportRecord{Archive: filepath.Base(archivePath), ImportedAt: now.UTC().Format(time.RFC3339)}
+	if data, err := os.ReadFile(filepath.Join(runDir, archiveManifestName)); err == nil {
+		var origin ArchiveManifest
+		if json.Unmarshal(data, &origin) == nil {
+			record.Origin = &origin
+		}
+	}
+	data, err := json.MarshalIndent(record, "", "  ")
+	if err != nil {
+		return "", false, fmt.Errorf("marshal import record: %w", err)
+	}
+	if err := os.WriteFile(filepath.Join(runDir, importRecordName), data, 0o644); err != nil {
+		return "", false, fmt.Errorf("writing %s: %w", importRecordName, err)
+	}
+
+	if err := os.RemoveAll(dest); err != nil {
+		return "", false, fmt.Errorf("replacing %s: %w", dest, err)
+	}
+	if err := os.Rename(runDir, dest); err != nil {
+		return "", false, fmt.Errorf("moving run into %s: %w", resultsDir, err)
+	}
+	return dest, true, nil
+}
+
+// archiveBaseName strips the archive extension from path's base name.
+func archiveBaseName(p string) string {
+	base := filepath.Base(p)
+	for _, ext := range []string{".tar.gz", ".tgz"} {
+		if trimmed, ok := strings.CutSuffix(base, ext); ok {
+			return trimmed
+		}
+	}
+	return base
+}
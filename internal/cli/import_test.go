@@ -0,0 +1,75 @@
+package cli
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestImportRunArchive(t *testing.T) {
+	t.Parallel()
+
+	evalDir := filepath.Join(t.TempDir(), "2026-01-07T120000-gemini")
+	if err := os.MkdirAll(filepath.Join(evalDir, "go-bank-account"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	writeSummary := func(content string) {
+		t.Helper()
+		if err := os.WriteFile(filepath.Join(evalDir, "summary.json"), []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	writeSummary(`{"agent":"gemini"}`)
+	if err := os.WriteFile(filepath.Join(evalDir, "go-bank-account", "agent.log"), []byte("transcript"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	archive := filepath.Join(t.TempDir(), "gemini.tar.gz")
+	if err := writeRunArchive(evalDir, archive, archiveOptions{}); err != nil {
+		t.Fatal(err)
+	}
+
+	resultsDir := t.TempDir()
+	now := time.Date(2026, 1, 8, 9, 0, 0, 0, time.UTC)
+	dest, imported, err := importRunArchive(archive, resultsDir, false, now)
+	if err != nil || !imported {
+		t.Fatalf("importRunArchive() = %v, %v; want imported", imported, err)
+	}
+	if dest != filepath.Join(resultsDir, "2026-01-07T120000-gemini") {
+		t.Errorf("dest = %s", dest)
+	}
+	if data, err := os.ReadFile(filepath.Join(dest, "go-bank-account", "agent.log")); err != nil || string(data) != "transcript" {
+		t.Errorf("agent.log = %q, %v", data, err)
+	}
+	data, err := os.ReadFile(filepath.Join(dest, importRecordName))
+	if err != nil {
+		t.Fatal(err)
+	}
+	var record ImportRecord
+	if err := json.Unmarshal(data, &record); err != nil {
+		t.Fatal(err)
+	}
+	if record.Archive != "gemini.tar.gz" || record.ImportedAt != "2026-01-08T09:00:00Z" || record.Origin == nil {
+		t.Errorf("import record = %+v", record)
+	}
+	entries, _ := os.ReadDir(resultsDir)
+	if len(entries) != 1 {
+		t.Errorf("results dir has %d entries, want only the imported run", len(entries))
+	}
+
+	if _, imported, err := importRunArchive(archive, resultsDir, false, now); err != nil || imported {
+		t.Errorf("re-import = %v, %v; want a no-op", imported, err)
+	}
+
+	writeSummary(`{"agent":"codex"}`)
+	if err := writeRunArchive(evalDir, archive, archiveOptions{}); err != nil {
+		t.Fatal(err)
+	}
+	if _, _, err := importRunArchive(archive, resultsDir, false, now); err == nil {
+		t.Error("importing a different run with the same name succeeded without force")
+	}
+	if _, imported, err := importRunArchive(archive, resultsDir, true, now); err != nil || !imported {
+		t.Errorf("forced import = %v, %v; want imported", imported, err)
+	}
+}
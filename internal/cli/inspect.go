@@ -0,0 +1,212 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+var (
+	inspectShell bool
+	inspectImage string
+)
+
+var inspectCmd = &cobra.Command{
+	Use:   "inspect <session> [task]",
+	Short: "Open a preserved task workspace in an editor or container shell",
+	Long: `Opens the workspace of a past run so failures can be reproduced interactively.
+
+<session> is either an eval results directory (then [task] selects the task,
+e.g. go/bank-account) or a run session directory containing result.json.
+
+By default the workspace is opened in $VISUAL or $EDITOR. With --shell, a fresh
+container is started from the task's language image with the workspace mounted
+at /workspace, exactly as during validation.
+
+Eval only keeps workspace source files when run with --keep-workspaces.`,
+	Example: `  sanity inspect eval-results/2026-01-07T120000-gemini go/bank-account
+  sanity inspect eval-results/2026-01-07T120000-gemini rust/regex-lite --shell
+  sanity inspect sessions/go-bank-account-2026-01-07T120000-ab12`,
+	Args: cobra.RangeArgs(1, 2),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		taskRef := ""
+		if len(args) == 2 {
+			taskRef = args[1]
+		}
+		sessionArgs, cleanup, err := resolveRunArgs(args[:1])
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		loader := task.NewLoader(tasks.FS, tasksDir)
+		allTasks, err := loader.LoadAll()
+		if err != nil {
+			return fmt.Errorf("loading tasks: %w", err)
+		}
+		workspace, t, err := resolveInspectTarget(sessionArgs[0], taskRef, allTasks)
+		if err != nil {
+			return err
+		}
+
+		if !inspectShell {
+			editor, err := editorCommand()
+			if err != nil {
+				return err
+			}
+			return runInteractive(append(editor, workspace))
+		}
+
+		if _, err := exec.LookPath(containerCLI()); err != nil {
+			return fmt.Errorf("--shell requires the %s CLI: %w", containerCLI(), err)
+		}
+		image := inspectImage
+		if image == "" {
+			if image, err = shellTaskImage(cmd.Context(), loader, t); err != nil {
+				return err
+			}
+		}
+		absWorkspace, err := filepath.Abs(workspace)
+		if err != nil {
+			return fmt.Errorf("resolving workspace path: %w", err)
+		}
+
+		fmt.Printf(" Task:       %s\n", t.ID())
+		fmt.Printf(" Workspace:  %s → /workspace\n", absWorkspace)
+		fmt.Printf(" Image:      %s\n", image)
+		fmt.Printf(" Validate:   %s\n", strings.Join(t.ValidationCommand(), " "))
+		fmt.Println()
+		return runInteractive(dockerShellArgs(image, absWorkspace))
+	},
+}
+
+func init() {
+	inspectCmd.Flags().BoolVar(&inspectShell, "shell", false, "open a shell in a fresh task container instead of an editor")
+	inspectCmd.Flags().StringVar(&inspectImage, "image", "", "override the container image used by --shell")
+}
+
+// resolveInspectTarget locates the workspace directory and task for a run
+// session or an eval results directory plus task reference.
+func resolveInspectTarget(sessionPath, taskRef string, allTasks []*task.Task) (string, *task.Task, error) {
+	if data, err := os.ReadFile(filepath.Join(sessionPath, "result.json")); err == nil {
+		session, err := result.ParseSession(data)
+		if err != nil {
+			return "", nil, err
+		}
+		t, err := task.ResolveRef(allTasks, session.Language+"/"+session.TaskSlug)
+		if err != nil {
+			return "", nil, err
+		}
+		workspace := filepath.Join(sessionPath, "workspace")
+		if !hasWorkspaceSources(workspace) {
+			return "", nil, fmt.Errorf("session %s has no preserved workspace", sessionPath)
+		}
+		return workspace, t, nil
+	}
+
+	if taskRef == "" {
+		return "", nil, errors.New("a task is required when inspecting an eval results directory")
+	}
+	t, err := task.ResolveRef(allTasks, taskRef)
+	if err != nil {
+		return "", nil, err
+	}
+	_, workspace := evalWorkspacePaths(sessionPath, t)
+	if _, err := os.Stat(workspace); err != nil {
+		return "", nil, fmt.Errorf("%s was not run in %s", t.ID(), sessionPath)
+	}
+	if !hasWorkspaceSources(workspace) {
+		return "", nil, fmt.Errorf("workspace for %s was cleaned up; re-run eval with --keep-workspaces to preserve it", t.ID())
+	}
+	return workspace, t, nil
+}
+
+// hasWorkspaceSources reports whether dir contains anything besides eval
+// artifacts such as agent.log.
+func hasWorkspaceSources(dir string) bool {
+	entries, err := os.ReadDir(dir)
+	if err != nil {
+		return false
+	}
+	for _, e := range entries {
+		if !evalOutputFiles[e.Name()] {
+			return true
+		}
+	}
+	return false
+}
+
+// editorCommand returns $VISUAL or $EDITOR split into a command and arguments.
+func editorCommand() ([]string, error) {
+	for _, env := range []string{"VISUAL", "EDITOR"} {
+		if fields := strings.Fields(os.Getenv(env)); len(fields) > 0 {
+			return fields, nil
+		}
+	}
+	return nil, errors.New("neither $VISUAL nor $EDITOR is set; set one or use --shell")
+}
+
+// containerCLI returns the CLI of the configured container backend, which
+// interactive shells run.
+func containerCLI() string {
+	if cfg != nil && cfg.Docker.ContainerBackend == config.ContainerBackendPodman {
+		return "podman"
+	}
+	return "docker"
+}
+
+// shellTaskImage returns the image t is graded in, building it from t's
+// Dockerfile first if needed.
+func shellTaskImage(ctx context.Context, loader *task.Loader, t *task.Task) (string, error) {
+	if t.Dockerfile == "" || cfg.TaskOverride(t.ID()).Image != "" {
+		return runner.TaskImage(cfg, loader, t)
+	}
+	docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend)
+	if err != nil {
+		return "", err
+	}
+	defer func() { _ = docker.Close() }()
+	fmt.Printf(" Building image from %s...\n", t.Dockerfile)
+	return runner.BuildTaskImage(ctx, docker, cfg, loader, t)
+}
+
+// dockerShellArgs builds a docker (or podman) run invocation that opens an
+// interactive shell in image with workspace mounted at /workspace.
+func dockerShellArgs(image, workspace string) []string {
+	return []string{
+		containerCLI(), "run", "--rm", "-it",
+		"-v", workspace + ":/workspace",
+		"-w", "/workspace",
+		"--entrypoint", "sh",
+		image,
+		"-c", "if command -v bash >/dev/null 2>&1; then exec bash; else exec sh; fi",
+	}
+}
+
+// runInteractive runs argv attached to the current terminal.
+func runInteractive(argv []string) error {
+	c := exec.Command(argv[0], argv[1:]...)
+	c.Stdin = os.Stdin
+	c.Stdout = rawStdout
+	c.Stderr = rawStderr
+	if err := c.Run(); err != nil {
+		var exitErr *exec.ExitError
+		if errors.As(err, &exitErr) {
+			return &exitError{code: exitErr.ExitCode()}
+		}
+		return fmt.Errorf("running %s: %w", argv[0], err)
+	}
+	return nil
+}
@@ -0,0 +1,83 @@
+package cli
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+func TestResolveInspectTarget(t *testing.T) {
+	t.Parallel()
+
+	allTasks, err := task.NewLoader(tasks.FS, "").LoadAll()
+	if err != nil {
+		t.Fatalf("LoadAll() error = %v", err)
+	}
+
+	// Eval results directory: workspace lives in <lang>-<slug>.
+	evalDir := t.TempDir()
+	taskDir := filepath.Join(evalDir, "go-bank-account")
+	if err := os.MkdirAll(taskDir, 0o755); err != nil {
+		t.Fatalf("mkdir: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(taskDir, "agent.log"), []byte("log"), 0o644); err != nil {
+		t.Fatalf("write: %v", err)
+	}
+	if _, _, err := resolveInspectTarget(evalDir, "go/bank-account", allTasks); err == nil || !strings.Contains(err.Error(), "--keep-workspaces") {
+		t.Fatalf("expected cleaned-up workspace error, got %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(taskDir, "bank_account.go"), []byte("package bank"), 0o644); err != nil {
+		t.Fatalf("write: %v", err)
+	}
+	ws, tk, err := resolveInspectTarget(evalDir, "go/bank-account", allTasks)
+	if err != nil {
+		t.Fatalf("resolveInspectTarget(eval) error = %v", err)
+	}
+	if ws != taskDir || tk.ID() != "go/bank-account" {
+		t.Fatalf("got (%q, %s), want (%q, go/bank-account)", ws, tk.ID(), taskDir)
+	}
+	if _, _, err := resolveInspectTarget(evalDir, "", allTasks); err == nil {
+		t.Fatal("expected error when task is missing")
+	}
+	if _, _, err := resolveInspectTarget(evalDir, "rust/regex-lite", allTasks); err == nil {
+		t.Fatal("expected error for task not in the run")
+	}
+
+	// Run session directory: result.json plus workspace/.
+	sessionDir := t.TempDir()
+	data, _ := json.Marshal(result.Session{Language: "go", TaskSlug: "bank-account"})
+	if err := os.WriteFile(filepath.Join(sessionDir, "result.json"), data, 0o644); err != nil {
+		t.Fatalf("write: %v", err)
+	}
+	wsDir := filepath.Join(sessionDir, "workspace")
+	if err := os.MkdirAll(wsDir, 0o755); err != nil {
+		t.Fatalf("mkdir: %v", err)
+	}
+	if err := os.WriteFile(filepath.Join(wsDir, "bank_account.go"), []byte("package bank"), 0o644); err != nil {
+		t.Fatalf("write: %v", err)
+	}
+	ws, tk, err = resolveInspectTarget(sessionDir, "", allTasks)
+	if err != nil {
+		t.Fatalf("resolveInspectTarget(session) error = %v", err)
+	}
+	if ws != wsDir || tk.ID() != "go/bank-account" {
+		t.Fatalf("got (%q, %s), want (%q, go/bank-account)", ws, tk.ID(), wsDir)
+	}
+}
+
+func TestDockerShellArgs(t *testing.T) {
+	t.Parallel()
+
+	args := strings.Join(dockerShellArgs("ghcr.io/lemon07r/sanity-go:latest", "/tmp/ws"), " ")
+	for _, want := range []string{"run --rm -it", "-v /tmp/ws:/workspace", "-w /workspace", "ghcr.io/lemon07r/sanity-go:latest"} {
+		if !strings.Contains(args, want) {
+			t.Errorf("dockerShellArgs() = %q, missing %q", args, want)
+		}
+	}
+}
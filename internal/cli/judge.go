@@ -0,0 +1,275 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+const (
+	// judgeAuditName is the judge request and raw reply kept for audit in the
+	// task output directory.
+	judgeAuditName = "judge.json"
+
+	// judgeMaxDiffChars caps the solution diff sent to the judge.
+	judgeMaxDiffChars = 60000
+)
+
+// JudgeScore is the judge model's qualitative assessment of a solution. It is
+// reported alongside, and never mixed into, the functional score.
+type JudgeScore struct {
+	Model    string             `json:"model"`
+	Rubric   string             `json:"rubric,omitempty"`
+	ScaleMin int                `json:"scale_min,omitempty"`
+	ScaleMax int                `json:"scale_max,omitempty"`
+	Scores   map[string]float64 `json:"scores,omitempty"`
+	Overall  float64            `json:"overall,omitempty"`
+	Error    string             `json:"error,omitempty"`
+}
+
+// Normalized returns the overall score as a percentage of the rubric's scale,
+// so scores from rubrics with different scales can be averaged.
+func (s *JudgeScore) Normalized() float64 {
+	if s.ScaleMax <= s.ScaleMin {
+		return 0
+	}
+	return (s.Overall - float64(s.ScaleMin)) / float64(s.ScaleMax-s.ScaleMin) * 100
+}
+
+// JudgeAudit records exactly what the judge was asked and what it replied.
+type JudgeAudit struct {
+	Task      string         `json:"task"`
+	Rubric    string         `json:"rubric"`
+	Messages  []judgeMessage `json:"messages"`
+	Reply     string         `json:"reply,omitempty"`
+	Reasoning string         `json:"reasoning,omitempty"`
+	Score     *JudgeScore    `json:"score"`
+}
+
+// judgeTask scores the task's solution.diff with the configured judge model
+// when --judge is set, using the rubric for the task's category. Tasks without
+// changes are not judged.
+func judgeTask(ctx context.Context, result *EvalResult, t *task.Task, prompt, taskOutputDir string) {
+	if !evalJudge {
+		return
+	}
+	diff, err := os.ReadFile(filepath.Join(taskOutputDir, "solution.diff"))
+	if err != nil || len(bytes.TrimSpace(diff)) == 0 {
+		return
+	}
+	rubric := cfg.Judge.RubricFor(t.ID(), string(t.Language), t.Tier, t.Difficulty, t.Tags)
+	score, audit := requestJudgeScore(ctx, cfg.Judge, rubric, result.Language, prompt, string(diff))
+	audit.Task = result.Task
+	result.Judge = score
+	if score.Error != "" {
+		logger.Warn("judge failed", "task", result.Task, "error", score.Error)
+	}
+	if err := writeJudgeAudit(taskOutputDir, audit); err != nil {
+		logger.Debug("failed to write judge audit", "task", result.Task, "error", err)
+	}
+}
+
+// requestJudgeScore sends the task prompt and solution diff to the judge
+// endpoint. Failures are recorded in the returned score's Error.
+func requestJudgeScore(ctx context.Context, jc config.JudgeConfig, rubric config.JudgeRubric, lang, prompt, diff string) (*JudgeScore, *JudgeAudit) {
+	score := &JudgeScore{Model: jc.Model, Rubric: rubric.Name, ScaleMin: rubric.ScaleMin, ScaleMax: rubric.ScaleMax}
+	audit := &JudgeAudit{Rubric: rubric.Name, Messages: buildJudgeMessages(rubric, lang, prompt, diff), Score: score}
+
+	reply, err := callJudge(ctx, jc, audit.Messages)
+	audit.Reply = reply
+	if err == nil {
+		score.Scores, audit.Reasoning, err = parseJudgeReply(rubric, reply)
+	}
+	if err != nil {
+		score.Error = err.Error()
+		return score, audit
+	}
+	var total, weights float64
+	for _, c := range rubric.Criteria {
+		total += score.Scores[c.Name] * c.Weight
+		weights += c.Weight
+	}
+	score.Overall = total / weights
+	return score, audit
+}
+
+type judgeMessage struct {
+	Role    string `json:"role"`
+	Content string `json:"content"`
+}
+
+// buildJudgeMessages renders the rubric's prompt template as the system
+// message and the solution under review as the user message.
+func buildJudgeMessages(rubric config.JudgeRubric, lang, prompt, diff string) []judgeMessage {
+	var criteria strings.Builder
+	fields := []string{fmt.Sprintf("%q: \"<brief justification>\"", config.JudgeReasoningKey)}
+	for _, c := range rubric.Criteria {
+		fmt.Fprintf(&criteria, "- %s: %s\n", c.Name, c.Guidance)
+		fields = append(fields, fmt.Sprintf("%q: <score>", c.Name))
+	}
+	system := strings.NewReplacer(
+		config.JudgePlaceholderCriteria, strings.TrimRight(criteria.String(), "\n"),
+		config.JudgePlaceholderScaleMin, strconv.Itoa(rubric.ScaleMin),
+		config.JudgePlaceholderScaleMax, strconv.Itoa(rubric.ScaleMax),
+		config.JudgePlaceholderLanguage, lang,
+		config.JudgePlaceholderReplyFormat, fmt.Sprintf("Reply with only a JSON object: {%s}", strings.Join(fields, ", ")),
+	).Replace(rubric.Prompt)
+
+	if len(diff) > judgeMaxDiffChars {
+		diff = diff[:judgeMaxDiffChars] + "\n[diff truncated]\n"
+	}
+	user := fmt.Sprintf("Language: %s\n\n## Task\n\n%s\n\n## Solution diff\n\n```diff\n%s\n```\n",
+		lang, strings.TrimSpace(prompt), strings.TrimRight(diff, "\n"))
+
+	return []judgeMessage{
+		{Role: "system", Content: system},
+		{Role: "user", Content: user},
+	}
+}
+
+// callJudge posts a chat completion request and returns the reply content.
+func callJudge(ctx context.Context, jc config.JudgeConfig, messages []judgeMessage) (string, error) {
+	body, err := json.Marshal(map[string]any{
+		"model":       jc.Model,
+		"messages":    messages,
+		"temperature": 0,
+	})
+	if err != nil {
+		return "", fmt.Errorf("encoding judge request: %w", err)
+	}
+	if jc.Timeout > 0 {
+		var cancel context.CancelFunc
+		ctx, cancel = context.WithTimeout(ctx, time.Duration(jc.Timeout)*time.Second)
+		defer cancel()
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, jc.Endpoint, bytes.NewReader(body))
+	if err != nil {
+		return "", err
+	}
+	req.Header.Set("Content-Type", "application/json")
+	req.Header.Set("User-Agent", "sanity/"+Version)
+	if key := os.Getenv(jc.APIKeyEnv); jc.APIKeyEnv != "" && key != "" {
+		req.Header.Set("Authorization", "Bearer "+key)
+	}
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return "", fmt.Errorf("calling judge: %w", err)
+	}
+	defer func() { _ = resp.Body.Close() }()
+	data, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return "", fmt.Errorf("reading judge response: %w", err)
+	}
+	if resp.StatusCode < 200 || resp.StatusCode > 299 {
+		return "", fmt.Errorf("judge returned %s: %s", resp.Status, truncateEvidence(string(data)))
+	}
+
+	var completion struct {
+		Choices []struct {
+			Message judgeMessage `json:"message"`
+		} `json:"choices"`
+	}
+	if err := json.Unmarshal(data, &completion); err != nil {
+		return "", fmt.Errorf("parsing judge response: %w", err)
+	}
+	if len(completion.Choices) == 0 {
+		return "", errors.New("judge response has no choices")
+	}
+	return completion.Choices[0].Message.Content, nil
+}
+
+// parseJudgeReply extracts the criterion scores and the judge's reasoning
+// from its reply, tolerating surrounding prose or code fences.
+func parseJudgeReply(rubric config.JudgeRubric, content string) (map[string]float64, string, error) {
+	start, end := strings.Index(content, "{"), strings.LastIndex(content, "}")
+	if start < 0 || end < start {
+		return nil, "", fmt.Errorf("judge reply has no JSON object: %s", truncateEvidence(content))
+	}
+	var fields map[string]json.RawMessage
+	if err := json.Unmarshal([]byte(content[start:end+1]), &fields); err != nil {
+		return nil, "", fmt.Errorf("parsing judge reply: %w", err)
+	}
+	var reasoning string
+	if raw, ok := fields[config.JudgeReasoningKey]; ok {
+		_ = json.Unmarshal(raw, &reasoning)
+	}
+	scores := make(map[string]float64, len(rubric.Criteria))
+	for _, c := range rubric.Criteria {
+		raw, ok := fields[c.Name]
+		if !ok {
+			return nil, reasoning, fmt.Errorf("judge reply is missing %q", c.Name)
+		}
+		var s float64
+		if err := json.Unmarshal(raw, &s); err != nil {
+			return nil, reasoning, fmt.Errorf("judge score %q: %w", c.Name, err)
+		}
+		if s < float64(rubric.ScaleMin) || s > float64(rubric.ScaleMax) {
+			return nil, reasoning, fmt.Errorf("judge score %q = %g is outside %d-%d", c.Name, s, rubric.ScaleMin, rubric.ScaleMax)
+		}
+		scores[c.Name] = s
+	}
+	return scores, reasoning, nil
+}
+
+func writeJudgeAudit(taskOutputDir string, audit *JudgeAudit) error {
+	data, err := json.MarshalIndent(audit, "", "  ")
+	if err != nil {
+		return fmt.Errorf("marshaling judge audit: %w", err)
+	}
+	return os.WriteFile(filepath.Join(taskOutputDir, judgeAuditName), data, 0o644)
+}
+
+// writeReportJudge lists the judge's qualitative scores, kept apart from the
+// functional results.
+func writeReportJudge(sb *strings.Builder, summary EvalSummary) {
+	if summary.JudgeModel == "" {
+		return
+	}
+	sb.WriteString("## Qualitative Scores (LLM Judge)\n\n")
+	fmt.Fprintf(sb, "Scored by `%s`. These scores are advisory and do not affect pass rate or weighted score. "+
+		"Each task's prompt, raw reply, and reasoning are kept in `%s`.\n\n", summary.JudgeModel, judgeAuditName)
+	if summary.JudgedTasks == 0 {
+		sb.WriteString("No solutions were judged.\n\n")
+		return
+	}
+	fmt.Fprintf(sb, "**Average:** %.1f%% of scale across %d tasks\n\n", summary.JudgeScore, summary.JudgedTasks)
+
+	sb.WriteString("| Task | Functional | Rubric | Scores | Overall |\n")
+	sb.WriteString("|------|------------|--------|--------|---------|\n")
+	for _, r := range summary.Results {
+		if r.Judge == nil {
+			continue
+		}
+		statusIcon, status := getResultStatusDisplay(r)
+		if r.Judge.Error != "" {
+			fmt.Fprintf(sb, "| %s | %s %s | %s | error: %s | - |\n",
+				r.Task, statusIcon, status, r.Judge.Rubric, strings.ReplaceAll(r.Judge.Error, "|", `\|`))
+			continue
+		}
+		names := make([]string, 0, len(r.Judge.Scores))
+		for name := range r.Judge.Scores {
+			names = append(names, name)
+		}
+		sort.Strings(names)
+		scores := make([]string, 0, len(names))
+		for _, name := range names {
+			scores = append(scores, fmt.Sprintf("%s %g", name, r.Judge.Scores[name]))
+		}
+		fmt.Fprintf(sb, "| %s | %s %s | %s | %s | %.2f / %d |\n",
+			r.Task, statusIcon, status, r.Judge.Rubric, strings.Join(scores, ", "), r.Judge.Overall, r.Judge.ScaleMax)
+	}
+	sb.WriteString("\n")
+}
@@ -0,0 +1,139 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"net/http"
+	"net/http/httptest"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestRequestJudgeScore(t *testing.T) {
+	t.Setenv("TEST_JUDGE_KEY", "secret")
+
+	var got struct {
+		Model    string         `json:"model"`
+		Messages []judgeMessage `json:"messages"`
+	}
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if auth := r.Header.Get("Authorization"); auth != "Bearer secret" {
+			t.Errorf("Authorization = %q", auth)
+		}
+		if err := json.NewDecoder(r.Body).Decode(&got); err != nil {
+			t.Errorf("decoding request: %v", err)
+		}
+		reply := "Here you go:\n```json\n{\"reasoning\": \"Clear but verbose.\", \"readability\": 4, \"idiomatic_style\": 1}\n```"
+		_ = json.NewEncoder(w).Encode(map[string]any{
+			"choices": []map[string]any{{"message": map[string]string{"role": "assistant", "content": reply}}},
+		})
+	}))
+	defer srv.Close()
+
+	jc := config.JudgeConfig{Endpoint: srv.URL, Model: "judge-1", APIKeyEnv: "TEST_JUDGE_KEY", Timeout: 5}
+	rubric := config.JudgeRubric{
+		Name:     "strict",
+		ScaleMin: 1,
+		ScaleMax: 4,
+		Prompt:   "Review this {language} code from {scale_min} to {scale_max}.\n{criteria}\n{reply_format}",
+		Criteria: []config.JudgeCriterion{
+			{Name: "readability", Guidance: "easy to follow", Weight: 3},
+			{Name: "idiomatic_style", Guidance: "uses Go idioms", Weight: 1},
+		},
+	}
+	score, audit := requestJudgeScore(context.Background(), jc, rubric, "go", "Implement Add.", "+func Add(a, b int) int { return a + b }\n")
+	if score.Error != "" {
+		t.Fatalf("unexpected error: %s", score.Error)
+	}
+	if score.Model != "judge-1" || score.Rubric != "strict" || score.Scores["readability"] != 4 || score.Scores["idiomatic_style"] != 1 || score.Overall != 3.25 {
+		t.Fatalf("score = %+v", score)
+	}
+	if n := score.Normalized(); n != 75 {
+		t.Fatalf("Normalized() = %v, want 75", n)
+	}
+	if audit.Reasoning != "Clear but verbose." || !strings.Contains(audit.Reply, "```json") {
+		t.Fatalf("audit = %+v", audit)
+	}
+	if got.Model != "judge-1" || len(got.Messages) != 2 {
+		t.Fatalf("request = %+v", got)
+	}
+	system := got.Messages[0].Content
+	for _, want := range []string{"Review this go code from 1 to 4.", "- idiomatic_style: uses Go idioms", `"reasoning": "<brief justification>"`} {
+		if !strings.Contains(system, want) {
+			t.Fatalf("system prompt missing %q:\n%s", want, system)
+		}
+	}
+	if !strings.Contains(got.Messages[1].Content, "func Add") {
+		t.Fatalf("user message missing diff: %s", got.Messages[1].Content)
+	}
+}
+
+func TestRequestJudgeScoreHTTPError(t *testing.T) {
+	t.Parallel()
+
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		http.Error(w, "overloaded", http.StatusServiceUnavailable)
+	}))
+	defer srv.Close()
+
+	jc := config.JudgeConfig{Endpoint: srv.URL, Model: "judge-1"}
+	score, _ := requestJudgeScore(context.Background(), jc, jc.RubricFor("go/a", "go", "", "", nil), "go", "p", "d")
+	if !strings.Contains(score.Error, "503") || score.Scores != nil {
+		t.Fatalf("score = %+v", score)
+	}
+}
+
+func TestParseJudgeReply(t *testing.T) {
+	t.Parallel()
+
+	rubric := config.DefaultJudgeRubric
+	for _, bad := range []string{
+		"no json here",
+		`{"readability": 4}`,
+		`{"readability": 4, "idiomatic_style": 9}`,
+		`{"readability": "good", "idiomatic_style": 3}`,
+	} {
+		if _, _, err := parseJudgeReply(rubric, bad); err == nil {
+			t.Errorf("parseJudgeReply(%q) should fail", bad)
+		}
+	}
+	scores, reasoning, err := parseJudgeReply(rubric, `{"readability": 5, "idiomatic_style": 1, "reasoning": "terse"}`)
+	if err != nil || scores["readability"] != 5 || scores["idiomatic_style"] != 1 || reasoning != "terse" {
+		t.Fatalf("parseJudgeReply() = %v, %q, %v", scores, reasoning, err)
+	}
+}
+
+func TestWriteReportJudge(t *testing.T) {
+	t.Parallel()
+
+	summary := EvalSummary{
+		JudgeModel:  "judge-1",
+		JudgedTasks: 1,
+		JudgeScore:  62.5,
+		Results: []EvalResult{
+			{Task: "go/a", Passed: true, Judge: &JudgeScore{Model: "judge-1", Rubric: "default", ScaleMin: 1, ScaleMax: 5,
+				Scores: map[string]float64{"readability": 4, "idiomatic_style": 3}, Overall: 3.5}},
+			{Task: "go/b", Judge: &JudgeScore{Model: "judge-1", Rubric: "default", Error: "judge returned 500 | oops"}},
+			{Task: "go/c"},
+		},
+	}
+	var sb strings.Builder
+	writeReportJudge(&sb, summary)
+	out := sb.String()
+	for _, want := range []string{
+		"## Qualitative Scores (LLM Judge)",
+		"do not affect pass rate",
+		"62.5% of scale across 1 tasks",
+		"| go/a | ✅ PASS | default | idiomatic_style 3, readability 4 | 3.50 / 5 |",
+		`500 \| oops`,
+	} {
+		if !strings.Contains(out, want) {
+			t.Errorf("report missing %q:\n%s", want, out)
+		}
+	}
+	if strings.Contains(out, "go/c") {
+		t.Errorf("unjudged task in report:\n%s", out)
+	}
+}
@@ -0,0 +1,157 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"maps"
+	"slices"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// mcpServersAll selects every configured MCP server.
+const mcpServersAll = "all"
+
+// mcpServer is an [mcp_servers] entry selected with --mcp-servers.
+type mcpServer struct {
+	Name string
+	config.MCPServer
+}
+
+// evalMCPServerSet holds the MCP servers of the current run. Like the other
+// eval globals it is set once per run, before any task starts.
+var evalMCPServerSet []mcpServer
+
+// resolveMCPServers returns the servers a --mcp-servers value selects, a
+// comma-separated list of names or "all", and checks that agent's adapter can
+// provision them.
+func resolveMCPServers(spec, agent string, agentCfg *config.AgentConfig, disableMCP bool) ([]mcpServer, error) {
+	if spec == "" {
+		return nil, nil
+	}
+	if disableMCP {
+		return nil, fmt.Errorf("--mcp-servers cannot be combined with --disable-mcp")
+	}
+	if adapterFor(agent, agentCfg).mcp == nil {
+		return nil, fmt.Errorf("--mcp-servers: agent %q cannot be given MCP servers (supported: %s)", agent, strings.Join(mcpAgents(), ", "))
+	}
+	var configured map[string]config.MCPServer
+	if cfg != nil {
+		configured = cfg.MCPServers
+	}
+	names := splitCommaList(spec)
+	if spec == mcpServersAll {
+		names = slices.Sorted(maps.Keys(configured))
+	}
+	if len(names) == 0 {
+		return nil, fmt.Errorf("--mcp-servers: no [mcp_servers] are configured")
+	}
+	servers := make([]mcpServer, 0, len(names))
+	for _, name := range names {
+		s, ok := configured[name]
+		if !ok {
+			return nil, fmt.Errorf("--mcp-servers: unknown server %q (configured: %s)", name, strings.Join(slices.Sorted(maps.Keys(configured)), ", "))
+		}
+		servers = append(servers, mcpServer{Name: name, MCPServer: s})
+	}
+	return servers, nil
+}
+
+// mcpServerNames returns the names of servers, for summaries and reports.
+func mcpServerNames(servers []mcpServer) []string {
+	names := make([]string, 0, len(servers))
+	for _, s := range servers {
+		names = append(names, s.Name)
+	}
+	return names
+}
+
+// mcpAgents lists the built-in agents whose adapter provisions MCP servers.
+func mcpAgents() []string {
+	var agents []string
+	for name, a := range agentAdapters {
+		if a.mcp != nil {
+			agents = append(agents, name)
+		}
+	}
+	slices.Sort(agents)
+	return agents
+}
+
+// mcpServersFor expands {workspace} in the servers' args and env.
+func mcpServersFor(servers []mcpServer, workspaceDir string) []mcpServer {
+	expanded := make([]mcpServer, 0, len(servers))
+	for _, s := range servers {
+		s.Args = slices.Clone(s.Args)
+		for i, arg := range s.Args {
+			s.Args[i] = strings.ReplaceAll(arg, "{workspace}", workspaceDir)
+		}
+		env := make(map[string]string, len(s.Env))
+		for k, v := range s.Env {
+			env[k] = strings.ReplaceAll(v, "{workspace}", workspaceDir)
+		}
+		s.Env = env
+		expanded = append(expanded, s)
+	}
+	return expanded
+}
+
+// mcpServersJSON is the "mcpServers" object shared by Claude Code and most
+// MCP clients.
+func mcpServersJSON(servers []mcpServer) map[string]any {
+	out := make(map[string]any, len(servers))
+	for _, s := range servers {
+		entry := map[string]any{"command": s.Command, "args": nonNil(s.Args)}
+		if len(s.Env) > 0 {
+			entry["env"] = s.Env
+		}
+		out[s.Name] = entry
+	}
+	return out
+}
+
+// claudeMCPArgs passes the servers inline with --mcp-config.
+func claudeMCPArgs(servers []mcpServer) ([]string, map[string]string) {
+	data, _ := json.Marshal(map[string]any{"mcpServers": mcpServersJSON(servers)})
+	return []string{"--mcp-config", string(data)}, nil
+}
+
+// codexMCPArgs sets each server with -c overrides of codex's config.toml
+// mcp_servers table. Values are TOML; JSON strings and string arrays are
+// valid TOML.
+func codexMCPArgs(servers []mcpServer) ([]string, map[string]string) {
+	var args []string
+	for _, s := range servers {
+		command, _ := json.Marshal(s.Command)
+		list, _ := json.Marshal(nonNil(s.Args))
+		args = append(args,
+			"-c", fmt.Sprintf("mcp_servers.%s.command=%s", s.Name, command),
+			"-c", fmt.Sprintf("mcp_servers.%s.args=%s", s.Name, list))
+		if len(s.Env) > 0 {
+			pairs := make([]string, 0, len(s.Env))
+			for _, k := range slices.Sorted(maps.Keys(s.Env)) {
+				key, _ := json.Marshal(k)
+				value, _ := json.Marshal(s.Env[k])
+				pairs = append(pairs, fmt.Sprintf("%s = %s", key, value))
+			}
+			args = append(args, "-c", fmt.Sprintf("mcp_servers.%s.env={ %s }", s.Name, strings.Join(pairs, ", ")))
+		}
+	}
+	return args, nil
+}
+
+// openCodeMCPEnv adds the servers to OpenCode's runtime config. The config
+// also raises the MCP timeout, as --use-mcp-tools does, and replaces the one
+// buildAgentEnv sets.
+func openCodeMCPEnv(servers []mcpServer) ([]string, map[string]string) {
+	return nil, map[string]string{"OPENCODE_CONFIG_CONTENT": buildOpenCodeMCPConfig(false, true, servers)}
+}
+
+// nonNil returns s, or an empty slice for nil, so it encodes as [].
+func nonNil(s []string) []string {
+	if s == nil {
+		return []string{}
+	}
+	return s
+}
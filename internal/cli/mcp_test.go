@@ -0,0 +1,110 @@
+package cli
+
+import (
+	"encoding/json"
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestResolveMCPServers(t *testing.T) {
+	c := config.Default
+	c.MCPServers = map[string]config.MCPServer{
+		"fs":       {Command: "mcp-server-filesystem", Args: []string{"{workspace}"}},
+		"context7": {Command: "npx", Args: []string{"-y", "@upstash/context7-mcp"}},
+	}
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+
+	servers, err := resolveMCPServers("all", "claude", nil, false)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got, want := mcpServerNames(servers), []string{"context7", "fs"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("all = %v, want %v", got, want)
+	}
+	servers, err = resolveMCPServers("fs", "codex", nil, false)
+	if err != nil || len(servers) != 1 || servers[0].Command != "mcp-server-filesystem" {
+		t.Errorf("fs = %+v, %v", servers, err)
+	}
+	if servers, err = resolveMCPServers("", "gemini", nil, false); servers != nil || err != nil {
+		t.Errorf("empty = %+v, %v", servers, err)
+	}
+
+	for _, tc := range []struct {
+		spec, agent string
+		disable     bool
+		want        string
+	}{
+		{"fs", "claude", true, "--disable-mcp"},
+		{"fs", "gemini", false, "cannot be given MCP servers"},
+		{"docs", "claude", false, `unknown server "docs"`},
+	} {
+		if _, err := resolveMCPServers(tc.spec, tc.agent, nil, tc.disable); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("resolveMCPServers(%q, %q) error = %v, want it to mention %q", tc.spec, tc.agent, err, tc.want)
+		}
+	}
+	plugin := &config.AgentConfig{Command: "my-agent", Protocol: config.AgentProtocolPlugin}
+	if _, err := resolveMCPServers("fs", "claude", plugin, false); err == nil {
+		t.Error("plugin agent: want error")
+	}
+}
+
+func TestMCPAgentArgs(t *testing.T) {
+	t.Parallel()
+
+	servers := mcpServersFor([]mcpServer{
+		{Name: "fs", MCPServer: config.MCPServer{Command: "mcp-server-filesystem", Args: []string{"{workspace}"}, Env: map[string]string{"ROOT": "{workspace}/src", "LOG": "warn"}}},
+		{Name: "ctx", MCPServer: config.MCPServer{Command: "ctx-mcp"}},
+	}, "/ws")
+
+	args, env := claudeMCPArgs(servers)
+	if len(args) != 2 || args[0] != "--mcp-config" || env != nil {
+		t.Fatalf("claudeMCPArgs() = %q, %v", args, env)
+	}
+	var claude struct {
+		MCPServers map[string]struct {
+			Command string            `json:"command"`
+			Args    []string          `json:"args"`
+			Env     map[string]string `json:"env"`
+		} `json:"mcpServers"`
+	}
+	if err := json.Unmarshal([]byte(args[1]), &claude); err != nil {
+		t.Fatal(err)
+	}
+	if fs := claude.MCPServers["fs"]; !reflect.DeepEqual(fs.Args, []string{"/ws"}) || fs.Env["ROOT"] != "/ws/src" {
+		t.Errorf("claude fs = %+v", fs)
+	}
+	if ctx := claude.MCPServers["ctx"]; ctx.Command != "ctx-mcp" || ctx.Args == nil {
+		t.Errorf("claude ctx = %+v", ctx)
+	}
+
+	args, _ = codexMCPArgs(servers)
+	want := []string{
+		"-c", `mcp_servers.fs.command="mcp-server-filesystem"`,
+		"-c", `mcp_servers.fs.args=["/ws"]`,
+		"-c", `mcp_servers.fs.env={ "LOG" = "warn", "ROOT" = "/ws/src" }`,
+		"-c", `mcp_servers.ctx.command="ctx-mcp"`,
+		"-c", `mcp_servers.ctx.args=[]`,
+	}
+	if !reflect.DeepEqual(args, want) {
+		t.Errorf("codexMCPArgs() = %q, want %q", args, want)
+	}
+
+	_, env = openCodeMCPEnv(servers)
+	var opencode struct {
+		MCP map[string]struct {
+			Command     []string          `json:"command"`
+			Environment map[string]string `json:"environment"`
+		} `json:"mcp"`
+	}
+	if err := json.Unmarshal([]byte(env["OPENCODE_CONFIG_CONTENT"]), &opencode); err != nil {
+		t.Fatal(err)
+	}
+	if fs := opencode.MCP["fs"]; !reflect.DeepEqual(fs.Command, []string{"mcp-server-filesystem", "/ws"}) || fs.Environment["LOG"] != "warn" {
+		t.Errorf("opencode fs = %+v", fs)
+	}
+}
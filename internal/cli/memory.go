@@ -0,0 +1,117 @@
+package cli
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"strconv"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// agentMemory scopes the directories an agent keeps session history and
+// memory in (its memory_paths). In the sandbox each path is bind-mounted over
+// a fresh directory: one per run that every task shares, or one per task that
+// only its retries share. The host's copies are neither read nor changed.
+type agentMemory struct {
+	scope   string
+	targets []string // Absolute memory paths
+	runDir  string   // Backing directory for the "run" scope
+}
+
+// evalAgentMemory holds the memory scope of the current run, or nil when the
+// agent uses its host memory. Like the other eval globals it is set by
+// evalRunSingle before tasks start.
+var evalAgentMemory *agentMemory
+
+// newAgentMemory prepares memory scope for an agent. The host scope, or an
+// empty one, needs nothing and returns nil.
+func newAgentMemory(scope, agentName string, agentCfg *config.AgentConfig) (*agentMemory, error) {
+	if scope == "" || scope == config.AgentMemoryHost {
+		return nil, nil
+	}
+	if !slices.Contains(config.AgentMemoryScopes, scope) {
+		return nil, fmt.Errorf("invalid agent memory scope %q (valid: %s)", scope, strings.Join(config.AgentMemoryScopes, ", "))
+	}
+	if !evalSandboxActive {
+		return nil, fmt.Errorf("agent memory scope %q needs the bwrap sandbox", scope)
+	}
+	if agentCfg == nil || len(agentCfg.MemoryPaths) == 0 {
+		return nil, fmt.Errorf("agent %q has no memory_paths; set them under [agents.%s] to scope its memory", agentName, agentName)
+	}
+	homeDir, err := os.UserHomeDir()
+	if err != nil {
+		return nil, fmt.Errorf("resolving home directory: %w", err)
+	}
+	m := &agentMemory{scope: scope, targets: resolveSandboxMountPaths(homeDir, agentCfg.MemoryPaths)}
+	if scope == config.AgentMemoryRun {
+		if m.runDir, err = os.MkdirTemp("", "sanity-memory-*"); err != nil {
+			return nil, fmt.Errorf("creating agent memory directory: %w", err)
+		}
+	}
+	return m, nil
+}
+
+// close removes the run's memory.
+func (m *agentMemory) close() {
+	if m != nil && m.runDir != "" {
+		_ = os.RemoveAll(m.runDir)
+	}
+}
+
+// dir returns the backing directory for the task whose agent workspace is
+// workspaceDir. Task memory sits beside the workspace, out of the agent's
+// view and out of what is copied back for validation.
+func (m *agentMemory) dir(workspaceDir string) string {
+	if m.scope == config.AgentMemoryRun {
+		return m.runDir
+	}
+	return workspaceDir + ".memory"
+}
+
+// prepare creates a task's backing directories. The returned cleanup removes
+// them for the "task" scope.
+func (m *agentMemory) prepare(workspaceDir string) (cleanup func(), err error) {
+	if m == nil {
+		return func() {}, nil
+	}
+	dir := m.dir(workspaceDir)
+	for i := range m.targets {
+		if err := os.MkdirAll(filepath.Join(dir, strconv.Itoa(i)), 0o700); err != nil {
+			return func() {}, fmt.Errorf("creating agent memory directory: %w", err)
+		}
+	}
+	if m.scope == config.AgentMemoryTask {
+		return func() { _ = os.RemoveAll(dir) }, nil
+	}
+	return func() {}, nil
+}
+
+// sandboxArgs bind-mounts the backing directories over the memory paths.
+// They come after the sandbox's own mounts, so they also replace any mask on
+// the same path.
+func (m *agentMemory) sandboxArgs(workspaceDir string) []string {
+	if m == nil {
+		return nil
+	}
+	dir := m.dir(workspaceDir)
+	args := make([]string, 0, 3*len(m.targets))
+	for i, target := range m.targets {
+		args = append(args, "--bind", filepath.Join(dir, strconv.Itoa(i)), target)
+	}
+	return args
+}
+
+// agentMemoryLabel describes a scope for headers and reports.
+func agentMemoryLabel(scope string) string {
+	switch scope {
+	case config.AgentMemoryRun:
+		return "run (fresh, shared across tasks)"
+	case config.AgentMemoryTask:
+		return "task (fresh for each task)"
+	default:
+		return "host (agent's own memory)"
+	}
+}
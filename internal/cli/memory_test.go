@@ -0,0 +1,85 @@
+package cli
+
+import (
+	"context"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestNewAgentMemoryErrors(t *testing.T) {
+	t.Parallel()
+
+	agentCfg := &config.AgentConfig{Command: "x", MemoryPaths: []string{".x/sessions"}}
+	for _, scope := range []string{"", config.AgentMemoryHost} {
+		if m, err := newAgentMemory(scope, "x", agentCfg); m != nil || err != nil {
+			t.Errorf("newAgentMemory(%q) = %v, %v; want nil, nil", scope, m, err)
+		}
+	}
+	if _, err := newAgentMemory("forever", "x", agentCfg); err == nil || !strings.Contains(err.Error(), "invalid agent memory scope") {
+		t.Errorf("expected invalid scope error, got %v", err)
+	}
+	// Tests run without the sandbox.
+	if _, err := newAgentMemory(config.AgentMemoryTask, "x", agentCfg); err == nil || !strings.Contains(err.Error(), "sandbox") {
+		t.Errorf("expected sandbox error, got %v", err)
+	}
+}
+
+func TestAgentMemorySandboxArgs(t *testing.T) {
+	t.Parallel()
+
+	workspace := filepath.Join(t.TempDir(), "sanity-eval-go-x")
+	target := "/home/u/.x/sessions"
+	m := &agentMemory{scope: config.AgentMemoryTask, targets: []string{target}}
+	cleanup, err := m.prepare(workspace)
+	if err != nil {
+		t.Fatalf("prepare() error = %v", err)
+	}
+	backing := filepath.Join(workspace+".memory", "0")
+	if info, err := os.Stat(backing); err != nil || !info.IsDir() {
+		t.Fatalf("backing directory missing: %v", err)
+	}
+	if got, want := m.sandboxArgs(workspace), []string{"--bind", backing, target}; !slices.Equal(got, want) {
+		t.Errorf("sandboxArgs() = %v, want %v", got, want)
+	}
+	cleanup()
+	if _, err := os.Stat(workspace + ".memory"); !os.IsNotExist(err) {
+		t.Errorf("task memory not removed: %v", err)
+	}
+
+	runDir := t.TempDir()
+	shared := &agentMemory{scope: config.AgentMemoryRun, targets: []string{target}, runDir: runDir}
+	cleanup, err = shared.prepare(workspace)
+	if err != nil {
+		t.Fatalf("prepare() error = %v", err)
+	}
+	cleanup()
+	if got := shared.sandboxArgs("/tmp/another-task"); got[1] != filepath.Join(runDir, "0") {
+		t.Errorf("run memory bound from %s, want %s", got[1], runDir)
+	}
+	if _, err := os.Stat(filepath.Join(runDir, "0")); err != nil {
+		t.Errorf("run memory removed after a task: %v", err)
+	}
+
+	var none *agentMemory
+	if args := none.sandboxArgs(workspace); args != nil {
+		t.Errorf("nil memory sandboxArgs() = %v", args)
+	}
+}
+
+func TestWrapCommandWithSandboxExtraArgs(t *testing.T) {
+	t.Parallel()
+
+	cmd := exec.CommandContext(context.Background(), "/bin/true", "arg")
+	cmd.Dir = t.TempDir()
+	wrapped := wrapCommandWithSandbox(context.Background(), cmd, nil, nil, nil, nil, "--bind", "/a", "/b")
+	sep := slices.Index(wrapped.Args, "--")
+	if sep < 3 || !slices.Equal(wrapped.Args[sep-3:sep], []string{"--bind", "/a", "/b"}) {
+		t.Errorf("extra args not placed before the command: %v", wrapped.Args)
+	}
+}
@@ -0,0 +1,336 @@
+package cli
+
+import (
+	"cmp"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// mergeRecordName lists the shards a merged session was built from.
+const mergeRecordName = "merge.json"
+
+var mergeOutput string
+
+// MergeRecord is written to a merged session directory.
+type MergeRecord struct {
+	MergedAt string       `json:"merged_at"`
+	Shards   []MergeShard `json:"shards"`
+}
+
+// MergeShard is one source run of a merged session. Tasks lists the tasks
+// whose result was taken from it.
+type MergeShard struct {
+	Dir       string   `json:"dir"`
+	Timestamp string   `json:"timestamp"`
+	Tasks     []string `json:"tasks"`
+}
+
+// mergeInput is a loaded shard.
+type mergeInput struct {
+	dir         string
+	summary     *EvalSummary
+	attestation *EvalAttestation
+}
+
+var mergeCmd = &cobra.Command{
+	Use:   "merge <session> <session> [session...]",
+	Short: "Combine sharded or repeated eval runs into one session",
+	Long: `Combines eval runs of the same configuration, such as shards of a task list
+run on different machines, into one session with a single summary, report,
+attestation, and submission. Aggregate scores are recomputed from the merged
+results rather than averaged across shards.
+
+Shards must share agent, model, reasoning, timeout, tool and sandbox
+settings, harness and weight versions, and the task hashes of any task they
+both ran; otherwise nothing is merged. When several shards ran the same task,
+the result of the most recent shard wins, as with eval --resume.
+
+Task artifacts are copied from the shard each result came from, and
+merge.json records which tasks came from which shard. Archives from
+'sanity archive' are accepted in place of directories.`,
+	Example: `  sanity merge eval-results/2026-01-07T120000-gemini-shard1 eval-results/2026-01-07T120500-gemini-shard2
+  sanity merge bench-01.tar.gz bench-02.tar.gz -o eval-results/gemini-merged`,
+	Args: cobra.MinimumNArgs(2),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		inputs, err := loadMergeInputs(args)
+		if err != nil {
+			return err
+		}
+		if problems := checkMergeCompatible(inputs); len(problems) > 0 {
+			for _, p := range problems {
+				fmt.Printf(" ✗ %s\n", p)
+			}
+			return errors.New("shards are not compatible; nothing was merged")
+		}
+
+		out := mergeOutput
+		if out == "" {
+			out = filepath.Join(defaultEvalResultsDir, "merged-"+time.Now().Format("2006-01-02T150405"))
+		}
+		if _, err := os.Stat(out); err == nil {
+			return fmt.Errorf("%s already exists", out)
+		}
+
+		var taskOrder map[string]int
+		if allTasks, err := task.NewLoader(tasks.FS, tasksDir).LoadAll(); err == nil {
+			taskOrder = make(map[string]int, len(allTasks))
+			for i, t := range allTasks {
+				taskOrder[t.ID()] = i
+			}
+		}
+		summary, attestation, record := mergeSessions(inputs, taskOrder, time.Now())
+		if err := writeMergedSession(out, inputs, summary, attestation, record); err != nil {
+			return err
+		}
+
+		fmt.Printf(" Merged %d shards: %d tasks, %d passed (%.1f%%), weighted %.1f%%\n",
+			len(inputs), summary.Total, summary.Passed, summary.PassRate, summary.WeightedPassRate)
+		fmt.Printf(" Results saved to: %s\n", out)
+		return nil
+	},
+}
+
+func init() {
+	mergeCmd.Flags().StringVarP(&mergeOutput, "output", "o", "", "merged session directory (default: eval-results/merged-<timestamp>)")
+}
+
+// loadMergeInputs loads each shard's summary and attestation, oldest first.
+func loadMergeInputs(dirs []string) ([]mergeInput, error) {
+	inputs := make([]mergeInput, 0, len(dirs))
+	for _, dir := range dirs {
+		summary, err := loadSummaryFromDir(dir)
+		if err != nil {
+			return nil, fmt.Errorf("loading summary from %s: %w", dir, err)
+		}
+		attestation, err := loadPreviousAttestation(dir)
+		if err != nil {
+			return nil, fmt.Errorf("loading attestation from %s: %w", dir, err)
+		}
+		inputs = append(inputs, mergeInput{dir: dir, summary: summary, attestation: attestation})
+	}
+	sort.SliceStable(inputs, func(i, j int) bool { return inputs[i].summary.Timestamp < inputs[j].summary.Timestamp })
+	return inputs, nil
+}
+
+// checkMergeCompatible lists every way the shards differ in configuration or
+// task versions. An empty list means they can be merged.
+func checkMergeCompatible(inputs []mergeInput) []string {
+	var problems []string
+	for _, in := range inputs {
+		if in.attestation == nil {
+			problems = append(problems, fmt.Sprintf("%s has no attestation.json", in.dir))
+		}
+	}
+	if len(problems) > 0 {
+		return problems
+	}
+
+	fields := []struct {
+		name string
+		get  func(mergeInput) string
+	}{
+		{"agent", func(in mergeInput) string { return in.summary.Agent }},
+		{"model", func(in mergeInput) string { return in.summary.Model }},
+		{"reasoning", func(in mergeInput) string { return in.summary.Reasoning }},
+		{"timeout", func(in mergeInput) string { return strconv.Itoa(in.summary.Timeout) }},
+		{"MCP settings", func(in mergeInput) string {
+			return fmt.Sprint(in.summary.UseMCPTools, in.summary.DisableMCP, in.summary.MCPServers)
+		}},
+		{"sampling", func(in mergeInput) string {
+			if in.summary.Sampling == nil {
+				return ""
+			}
+			return in.summary.Sampling.label()
+		}},
+		{"skills", func(in mergeInput) string { return fmt.Sprint(in.summary.UseSkills, in.summary.SkillPacks) }},
+		{"sandbox", func(in mergeInput) string { return strconv.FormatBool(in.summary.Sandbox) }},
+		{"legacy mode", func(in mergeInput) string { return strconv.FormatBool(in.summary.Legacy) }},
+		{"prompt language", func(in mergeInput) string { return in.summary.PromptLang }},
+		{"distractor pack", func(in mergeInput) string {
+			if in.summary.DistractorPack == nil {
+				return ""
+			}
+			return in.summary.DistractorPack.Hash
+		}},
+		{"chaos", func(in mergeInput) string {
+			if in.summary.Chaos == nil {
+				return ""
+			}
+			return in.summary.Chaos.String()
+		}},
+		{"agent memory", func(in mergeInput) string { return cmp.Or(in.summary.AgentMemory, config.AgentMemoryHost) }},
+		{"test visibility", func(in mergeInput) string { return summaryTestVisibility(in.summary) }},
+		{"prompt variants", func(in mergeInput) string {
+			if in.summary.PromptExperiment == nil {
+				return ""
+			}
+			return strings.Join(in.summary.PromptExperiment.Variants, ",")
+		}},
+		{"harness version", func(in mergeInput) string { return in.attestation.Harness.Version }},
+		{"weight version", func(in mergeInput) string { return in.attestation.Harness.WeightVersion }},
+	}
+	first := inputs[0]
+	for _, in := range inputs[1:] {
+		for _, f := range fields {
+			if want, got := f.get(first), f.get(in); want != got {
+				problems = append(problems, fmt.Sprintf("%s: %s has %q, %s has %q", f.name, first.dir, want, in.dir, got))
+			}
+		}
+	}
+
+	taskHashes := make(map[string]string)
+	taskSources := make(map[string]string)
+	for _, in := range inputs {
+		for id, at := range in.attestation.Tasks {
+			if prev, ok := taskHashes[id]; ok && prev != at.TaskHash {
+				problems = append(problems, fmt.Sprintf("task %s: %s and %s ran different task versions", id, taskSources[id], in.dir))
+				continue
+			}
+			taskHashes[id] = at.TaskHash
+			taskSources[id] = in.dir
+		}
+	}
+	sort.Strings(problems)
+	return problems
+}
+
+// mergeSessions combines compatible shards, ordered oldest first. A task's
+// result comes from the newest shard that scored it; external failures are
+// kept only for tasks no shard scored. Results are ordered by taskOrder, or
+// by ID for tasks it lacks.
+func mergeSessions(inputs []mergeInput, taskOrder map[string]int, now time.Time) (EvalSummary, *EvalAttestation, MergeRecord) {
+	source := make(map[string]int)
+	byTask := make(map[string]EvalResult)
+	failures := make(map[string]ExternalFailure)
+	var duration float64
+	for i, in := range inputs {
+		for _, r := range in.summary.Results {
+			source[r.Task] = i
+			byTask[r.Task] = r
+		}
+		for _, f := range in.summary.ExternalFailures {
+			failures[f.Task] = f
+		}
+		duration += in.attestation.Eval.Duration
+	}
+
+	results := make([]EvalResult, 0, len(byTask))
+	for _, r := range byTask {
+		results = append(results, r)
+	}
+	sort.Slice(results, func(i, j int) bool {
+		oi, iok := taskOrder[results[i].Task]
+		oj, jok := taskOrder[results[j].Task]
+		if iok && jok && oi != oj {
+			return oi < oj
+		}
+		if iok != jok {
+			return iok
+		}
+		return results[i].Task < results[j].Task
+	})
+	var externalFailures []ExternalFailure
+	for id, f := range failures {
+		if _, scored := byTask[id]; !scored {
+			externalFailures = append(externalFailures, f)
+		}
+	}
+	sort.Slice(externalFailures, func(i, j int) bool { return externalFailures[i].Task < externalFailures[j].Task })
+
+	first := inputs[0]
+	summary := *first.summary
+	summary.SchemaVersion = summarySchemaVersion
+	summarizeResults(&summary, results, externalFailures)
+
+	attestation := &EvalAttestation{
+		Version: first.attestation.Version,
+		Harness: first.attestation.Harness,
+		Eval: AttestationEval{
+			Agent:     summary.Agent,
+			Model:     first.attestation.Eval.Model,
+			Timestamp: summary.Timestamp,
+			Duration:  duration,
+		},
+		Tasks: make(map[string]AttestationTask, len(results)),
+	}
+	var allTaskHashes []byte
+	record := MergeRecord{MergedAt: now.UTC().Format(time.RFC3339)}
+	for _, in := range inputs {
+		record.Shards = append(record.Shards, MergeShard{Dir: in.dir, Timestamp: in.summary.Timestamp, Tasks: []string{}})
+	}
+	for _, r := range results {
+		i := source[r.Task]
+		if at, ok := inputs[i].attestation.Tasks[r.Task]; ok {
+			attestation.Tasks[r.Task] = at
+			allTaskHashes = append(allTaskHashes, []byte(at.TaskHash)...)
+		}
+		record.Shards[i].Tasks = append(record.Shards[i].Tasks, r.Task)
+	}
+	attestation.Integrity.TasksHash = hashBytes(allTaskHashes)
+	resultsJSON, _ := json.Marshal(results)
+	attestation.Integrity.ResultsHash = hashBytes(resultsJSON)
+	return summary, attestation, record
+}
+
+// writeMergedSession writes the merged summary, attestation, report, and
+// submission to out and copies each task's artifacts from its shard.
+func writeMergedSession(out string, inputs []mergeInput, summary EvalSummary, attestation *EvalAttestation, record MergeRecord) error {
+	if err := os.MkdirAll(out, 0o755); err != nil {
+		return fmt.Errorf("creating %s: %w", out, err)
+	}
+	for i, shard := range record.Shards {
+		for _, id := range shard.Tasks {
+			name := strings.Replace(id, "/", "-", 1)
+			src := filepath.Join(inputs[i].dir, name)
+			if _, err := os.Stat(src); err != nil {
+				continue
+			}
+			if err := copyDirContents(src, filepath.Join(out, name)); err != nil {
+				return fmt.Errorf("copying %s artifacts: %w", id, err)
+			}
+		}
+	}
+
+	submission := generateLeaderboardSubmission(summary, attestation)
+	files := []struct {
+		name string
+		v    any
+	}{
+		{"summary.json", summary},
+		{"attestation.json", attestation},
+		{"submission.json", submission},
+		{mergeRecordName, record},
+	}
+	for _, f := range files {
+		data, err := json.MarshalIndent(f.v, "", "  ")
+		if err != nil {
+			return fmt.Errorf("marshal %s: %w", f.name, err)
+		}
+		if err := os.WriteFile(filepath.Join(out, f.name), data, 0o644); err != nil {
+			return fmt.Errorf("writing %s: %w", f.name, err)
+		}
+	}
+	if err := os.WriteFile(filepath.Join(out, "report.md"), []byte(generateEvalReport(summary, attestation)), 0o644); err != nil {
+		return fmt.Errorf("writing report.md: %w", err)
+	}
+	return nil
+}
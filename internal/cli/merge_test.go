@@ -0,0 +1,72 @@
+package cli
+
+import (
+	"strings"
+	"testing"
+	"time"
+)
+
+func mergeTestInput(dir, timestamp string, results []EvalResult, hashes map[string]string) mergeInput {
+	at := &EvalAttestation{
+		Version: "1",
+		Harness: AttestationHarness{Version: "v1.0.0", WeightVersion: "2"},
+		Eval:    AttestationEval{Agent: "gemini", Timestamp: timestamp, Duration: 100},
+		Tasks:   make(map[string]AttestationTask),
+	}
+	for id, h := range hashes {
+		at.Tasks[id] = AttestationTask{TaskHash: h}
+	}
+	return mergeInput{
+		dir:         dir,
+		summary:     &EvalSummary{Agent: "gemini", Model: "gemini-3", Timestamp: timestamp, Timeout: 600, Results: results},
+		attestation: at,
+	}
+}
+
+func TestMergeSessions(t *testing.T) {
+	t.Parallel()
+
+	shard1 := mergeTestInput("shard1", "2026-01-07T120000", []EvalResult{
+		{Task: "go/bank-account", Language: "go", Passed: true, Weight: 1, WeightedScore: 1},
+		{Task: "rust/regex-lite", Language: "rust", Passed: false, Weight: 2},
+	}, map[string]string{"go/bank-account": "h1", "rust/regex-lite": "h2"})
+	shard1.summary.ExternalFailures = []ExternalFailure{{Task: "zig/lru", FailureClass: FailureClassInfra}}
+	shard2 := mergeTestInput("shard2", "2026-01-07T130000", []EvalResult{
+		{Task: "rust/regex-lite", Language: "rust", Passed: true, Weight: 2, WeightedScore: 2},
+		{Task: "zig/lru", Language: "zig", Passed: false, Weight: 1},
+	}, map[string]string{"rust/regex-lite": "h2", "zig/lru": "h3"})
+	inputs := []mergeInput{shard1, shard2}
+
+	if problems := checkMergeCompatible(inputs); len(problems) > 0 {
+		t.Fatalf("checkMergeCompatible() = %v, want none", problems)
+	}
+	order := map[string]int{"zig/lru": 0, "go/bank-account": 1, "rust/regex-lite": 2}
+	summary, attestation, record := mergeSessions(inputs, order, time.Now())
+
+	var ids []string
+	for _, r := range summary.Results {
+		ids = append(ids, r.Task)
+	}
+	if got := strings.Join(ids, ","); got != "zig/lru,go/bank-account,rust/regex-lite" {
+		t.Errorf("results = %s", got)
+	}
+	if summary.Total != 3 || summary.Passed != 2 || summary.WeightedScore != 3 || summary.MaxPossibleScore != 4 {
+		t.Errorf("summary = total %d passed %d weighted %v/%v", summary.Total, summary.Passed, summary.WeightedScore, summary.MaxPossibleScore)
+	}
+	if len(summary.ExternalFailures) != 0 {
+		t.Errorf("external failures = %v; zig/lru was scored by shard2", summary.ExternalFailures)
+	}
+	if len(attestation.Tasks) != 3 || attestation.Eval.Duration != 200 {
+		t.Errorf("attestation = %+v", attestation)
+	}
+	if got := strings.Join(record.Shards[0].Tasks, ","); got != "go/bank-account" {
+		t.Errorf("shard1 tasks = %s, want only go/bank-account", got)
+	}
+
+	shard2.summary.Model = "gemini-3-flash"
+	shard2.attestation.Tasks["rust/regex-lite"] = AttestationTask{TaskHash: "changed"}
+	problems := checkMergeCompatible([]mergeInput{shard1, shard2})
+	if len(problems) != 2 {
+		t.Fatalf("checkMergeCompatible() = %v, want model and task version problems", problems)
+	}
+}
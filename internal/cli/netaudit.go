@@ -0,0 +1,327 @@
+package cli
+
+import (
+	"bufio"
+	"context"
+	"encoding/binary"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"regexp"
+	"runtime"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+// networkTraceName is the raw strace output written next to agent.log during
+// an attempt. It is removed once summarized because it contains the prompt.
+const networkTraceName = "network.trace"
+
+// NetworkActivity summarizes the DNS lookups and outbound connections made by
+// the agent and every process it spawned during the solve phase.
+type NetworkActivity struct {
+	DNSLookups  int                 `json:"dns_lookups"`
+	Hostnames   []string            `json:"hostnames,omitempty"`
+	Connections []NetworkConnection `json:"connections,omitempty"`
+}
+
+// NetworkConnection counts connection attempts from one program to one
+// address.
+type NetworkConnection struct {
+	Program string `json:"program"`
+	Address string `json:"address"`
+	Count   int    `json:"count"`
+}
+
+// merge folds another attempt's activity into a.
+func (a *NetworkActivity) merge(other *NetworkActivity) {
+	if other == nil {
+		return
+	}
+	a.DNSLookups += other.DNSLookups
+	a.Hostnames = mergeSorted(a.Hostnames, other.Hostnames)
+	counts := make(map[NetworkConnection]int)
+	for _, c := range append(a.Connections, other.Connections...) {
+		counts[NetworkConnection{Program: c.Program, Address: c.Address}] += c.Count
+	}
+	a.Connections = sortedConnections(counts)
+}
+
+func mergeSorted(a, b []string) []string {
+	set := make(map[string]bool, len(a)+len(b))
+	for _, s := range append(a, b...) {
+		set[s] = true
+	}
+	if len(set) == 0 {
+		return nil
+	}
+	out := make([]string, 0, len(set))
+	for s := range set {
+		out = append(out, s)
+	}
+	sort.Strings(out)
+	return out
+}
+
+func sortedConnections(counts map[NetworkConnection]int) []NetworkConnection {
+	if len(counts) == 0 {
+		return nil
+	}
+	conns := make([]NetworkConnection, 0, len(counts))
+	for c, n := range counts {
+		c.Count = n
+		conns = append(conns, c)
+	}
+	sort.Slice(conns, func(i, j int) bool {
+		if conns[i].Program != conns[j].Program {
+			return conns[i].Program < conns[j].Program
+		}
+		return conns[i].Address < conns[j].Address
+	})
+	return conns
+}
+
+// checkNetworkAudit reports whether --network-audit can run on this host.
+func checkNetworkAudit() error {
+	if runtime.GOOS != "linux" {
+		return errors.New("--network-audit is only supported on Linux")
+	}
+	if _, err := exec.LookPath("strace"); err != nil {
+		return errors.New("--network-audit requires strace on PATH")
+	}
+	return nil
+}
+
+// wrapCommandWithNetworkTrace runs cmd under strace, following forks, and
+// records process starts and socket calls to tracePath.
+func wrapCommandWithNetworkTrace(ctx context.Context, cmd *exec.Cmd, tracePath string) *exec.Cmd {
+	args := []string{
+		"-f", "-qq", "-s", "512",
+		"-e", "signal=none",
+		"-e", "trace=execve,clone,clone3,fork,vfork,connect,sendto,sendmmsg",
+		"-o", tracePath,
+		"--", cmd.Path,
+	}
+	args = append(args, cmd.Args[1:]...)
+
+	wrapped := exec.CommandContext(ctx, "strace", args...)
+	wrapped.Dir = cmd.Dir
+	wrapped.Env = cmd.Env
+	wrapped.Stdin = cmd.Stdin
+	wrapped.Stdout = cmd.Stdout
+	wrapped.Stderr = cmd.Stderr
+	return wrapped
+}
+
+var (
+	straceLinePattern   = regexp.MustCompile(`^(\d+)\s+(.*)$`)
+	straceExecvePattern = regexp.MustCompile(`^execve\("([^"]+)"`)
+	straceForkPattern   = regexp.MustCompile(`^(?:<\.\.\. )?(?:clone3?|v?fork)\b.*= (\d+)$`)
+	straceInet4Pattern  = regexp.MustCompile(`sin_port=htons\((\d+)\), sin_addr=inet_addr\("([^"]+)"\)`)
+	straceInet6Pattern  = regexp.MustCompile(`sin6_port=htons\((\d+)\).*?inet_pton\(AF_INET6, "([^"]+)"`)
+	straceStringPattern = regexp.MustCompile(`"((?:[^"\\]|\\.)*)"`)
+)
+
+// networkTraceParser accumulates activity from strace output lines.
+type networkTraceParser struct {
+	programs    map[string]string // pid -> program basename
+	connections map[NetworkConnection]int
+	hostnames   map[string]bool
+	dnsLookups  int
+}
+
+// parseNetworkTrace summarizes a strace log written by
+// wrapCommandWithNetworkTrace. It returns nil when nothing touched the network.
+func parseNetworkTrace(path string) (*NetworkActivity, error) {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil, fmt.Errorf("opening network trace: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+
+	p := &networkTraceParser{
+		programs:    make(map[string]string),
+		connections: make(map[NetworkConnection]int),
+		hostnames:   make(map[string]bool),
+	}
+	scanner := bufio.NewScanner(f)
+	scanner.Buffer(make([]byte, 0, 64<<10), 1<<20)
+	for scanner.Scan() {
+		p.parseLine(scanner.Text())
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("reading network trace: %w", err)
+	}
+
+	if p.dnsLookups == 0 && len(p.connections) == 0 && len(p.hostnames) == 0 {
+		return nil, nil
+	}
+	hostnames := make([]string, 0, len(p.hostnames))
+	for h := range p.hostnames {
+		hostnames = append(hostnames, h)
+	}
+	return &NetworkActivity{
+		DNSLookups:  p.dnsLookups,
+		Hostnames:   mergeSorted(nil, hostnames),
+		Connections: sortedConnections(p.connections),
+	}, nil
+}
+
+func (p *networkTraceParser) parseLine(line string) {
+	m := straceLinePattern.FindStringSubmatch(line)
+	if m == nil {
+		return
+	}
+	pid, call := m[1], m[2]
+	switch {
+	case strings.HasPrefix(call, "execve("):
+		if em := straceExecvePattern.FindStringSubmatch(call); em != nil && !strings.Contains(call, ") = -1 ") {
+			p.programs[pid] = em[1][strings.LastIndex(em[1], "/")+1:]
+		}
+	case strings.HasPrefix(call, "connect("):
+		p.recordAddress(pid, call)
+	case strings.HasPrefix(call, "sendto("), strings.HasPrefix(call, "sendmmsg("):
+		if strings.HasPrefix(call, "sendto(") && strings.Contains(call, "sin") {
+			p.recordAddress(pid, call)
+		}
+		p.recordDNSQueries(call)
+	default:
+		if fm := straceForkPattern.FindStringSubmatch(call); fm != nil {
+			if program, ok := p.programs[pid]; ok {
+				p.programs[fm[1]] = program
+			}
+		}
+	}
+}
+
+// recordAddress counts an IPv4 or IPv6 destination. Port 53 is counted as a
+// DNS lookup rather than a connection.
+func (p *networkTraceParser) recordAddress(pid, call string) {
+	var port, host string
+	if m := straceInet4Pattern.FindStringSubmatch(call); m != nil {
+		port, host = m[1], m[2]
+	} else if m := straceInet6Pattern.FindStringSubmatch(call); m != nil {
+		port, host = m[1], "["+m[2]+"]"
+	} else {
+		return
+	}
+	if port == "53" {
+		p.dnsLookups++
+		return
+	}
+	program := p.programs[pid]
+	if program == "" {
+		program = "unknown"
+	}
+	p.connections[NetworkConnection{Program: program, Address: host + ":" + port}]++
+}
+
+// recordDNSQueries decodes any DNS query messages in the call's buffers.
+func (p *networkTraceParser) recordDNSQueries(call string) {
+	for _, m := range straceStringPattern.FindAllStringSubmatch(call, -1) {
+		if name, ok := decodeDNSQuery(unescapeStraceString(m[1])); ok {
+			p.hostnames[name] = true
+		}
+	}
+}
+
+// decodeDNSQuery returns the queried name of a standard DNS query message.
+func decodeDNSQuery(msg []byte) (string, bool) {
+	if len(msg) < 17 {
+		return "", false
+	}
+	// QR and opcode must be zero, with exactly one question and no answers.
+	if binary.BigEndian.Uint16(msg[2:4])&0xf800 != 0 || binary.BigEndian.Uint16(msg[4:6]) != 1 ||
+		binary.BigEndian.Uint16(msg[6:8]) != 0 || binary.BigEndian.Uint16(msg[8:10]) != 0 {
+		return "", false
+	}
+	var labels []string
+	i := 12
+	for {
+		if i >= len(msg) {
+			return "", false
+		}
+		n := int(msg[i])
+		i++
+		if n == 0 {
+			break
+		}
+		if n > 63 || i+n > len(msg) {
+			return "", false
+		}
+		labels = append(labels, string(msg[i:i+n]))
+		i += n
+	}
+	// The name must be followed by a QTYPE and a QCLASS of IN.
+	if len(labels) == 0 || i+4 > len(msg) || binary.BigEndian.Uint16(msg[i+2:i+4]) != 1 {
+		return "", false
+	}
+	return strings.ToLower(strings.Join(labels, ".")), true
+}
+
+var straceEscapes = map[byte]byte{'n': '\n', 't': '\t', 'r': '\r', 'v': '\v', 'f': '\f'}
+
+// unescapeStraceString decodes the C-style escapes strace uses for buffers.
+func unescapeStraceString(s string) []byte {
+	out := make([]byte, 0, len(s))
+	for i := 0; i < len(s); i++ {
+		if s[i] != '\\' || i+1 >= len(s) {
+			out = append(out, s[i])
+			continue
+		}
+		i++
+		e := s[i]
+		switch {
+		case e >= '0' && e <= '7':
+			v, j := 0, i
+			for ; j < len(s) && j < i+3 && s[j] >= '0' && s[j] <= '7'; j++ {
+				v = v*8 + int(s[j]-'0')
+			}
+			out = append(out, byte(v))
+			i = j - 1
+		case e == 'x' && i+2 < len(s):
+			v, err := strconv.ParseUint(s[i+1:i+3], 16, 8)
+			if err != nil {
+				out = append(out, e)
+				continue
+			}
+			out = append(out, byte(v))
+			i += 2
+		default:
+			if c, ok := straceEscapes[e]; ok {
+				e = c
+			}
+			out = append(out, e)
+		}
+	}
+	return out
+}
+
+// writeReportNetwork lists per-task network activity when the run was
+// audited.
+func writeReportNetwork(sb *strings.Builder, summary EvalSummary) {
+	if !summary.NetworkAudit {
+		return
+	}
+	sb.WriteString("## Network Activity\n\n")
+	if summary.TasksWithNetwork == 0 {
+		sb.WriteString("No DNS lookups or outbound connections were observed during the solve phase.\n\n")
+		return
+	}
+	sb.WriteString("| Task | DNS Lookups | Hostnames | Connections |\n")
+	sb.WriteString("|------|-------------|-----------|-------------|\n")
+	for _, r := range summary.Results {
+		if r.Network == nil {
+			continue
+		}
+		conns := make([]string, 0, len(r.Network.Connections))
+		for _, c := range r.Network.Connections {
+			conns = append(conns, fmt.Sprintf("%s → %s ×%d", c.Program, c.Address, c.Count))
+		}
+		fmt.Fprintf(sb, "| %s | %d | %s | %s |\n", r.Task, r.Network.DNSLookups,
+			strings.Join(r.Network.Hostnames, ", "), strings.Join(conns, "<br>"))
+	}
+	sb.WriteString("\n")
+}
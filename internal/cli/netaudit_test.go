@@ -0,0 +1,88 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+)
+
+func TestParseNetworkTrace(t *testing.T) {
+	t.Parallel()
+
+	trace := `100   execve("/usr/bin/node", ["node", "agent.js"], 0x7ffd /* 30 vars */) = 0
+100   clone(child_stack=NULL, flags=CLONE_VM|CLONE_FS <unfinished ...>
+100   <... clone resumed>, parent_tid=[101]) = 101
+101   connect(5, {sa_family=AF_INET, sin_port=htons(443), sin_addr=inet_addr("104.18.0.1")}, 16) = 0
+100   clone3({flags=CLONE_VM|CLONE_VFORK, exit_signal=SIGCHLD, stack=0x7f, stack_size=0x9000}, 88) = 102
+102   execve("/usr/local/bin/curl", ["curl", "-sL", "https://example.com/x.go"], 0x5 /* 30 vars */) = -1 ENOENT (No such file or directory)
+102   execve("/usr/bin/curl", ["curl", "-sL", "https://example.com/x.go"], 0x5 /* 30 vars */) = 0
+102   connect(3, {sa_family=AF_INET, sin_port=htons(53), sin_addr=inet_addr("127.0.0.53")}, 16) = 0
+102   sendmmsg(3, [{msg_hdr={msg_name=NULL, msg_namelen=0, msg_iov=[{iov_base="\22\64\1\0\0\1\0\0\0\0\0\0\7example\3com\0\0\1\0\1", iov_len=29}], msg_iovlen=1, msg_controllen=0, msg_flags=0}, msg_len=29}], 1, MSG_NOSIGNAL) = 1
+102   connect(4, {sa_family=AF_INET6, sin6_port=htons(443), sin6_flowinfo=htonl(0), inet_pton(AF_INET6, "2606:2800:220:1::1", &sin6_addr), sin6_scope_id=0}, 28) = -1 ENETUNREACH (Network is unreachable)
+102   connect(4, {sa_family=AF_INET, sin_port=htons(443), sin_addr=inet_addr("93.184.216.34")}, 16) = 0
+101   connect(5, {sa_family=AF_INET, sin_port=htons(443), sin_addr=inet_addr("104.18.0.1")}, 16) = 0
+103   connect(7, {sa_family=AF_UNIX, sun_path="/run/dbus/system_bus_socket"}, 110) = 0
+`
+	path := filepath.Join(t.TempDir(), networkTraceName)
+	if err := os.WriteFile(path, []byte(trace), 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	got, err := parseNetworkTrace(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	want := &NetworkActivity{
+		DNSLookups: 1,
+		Hostnames:  []string{"example.com"},
+		Connections: []NetworkConnection{
+			{Program: "curl", Address: "93.184.216.34:443", Count: 1},
+			{Program: "curl", Address: "[2606:2800:220:1::1]:443", Count: 1},
+			{Program: "node", Address: "104.18.0.1:443", Count: 2},
+		},
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Fatalf("parseNetworkTrace() = %+v, want %+v", got, want)
+	}
+}
+
+func TestParseNetworkTraceQuiet(t *testing.T) {
+	t.Parallel()
+
+	path := filepath.Join(t.TempDir(), networkTraceName)
+	trace := "100   execve(\"/usr/bin/node\", [\"node\"], 0x7ffd /* 30 vars */) = 0\n"
+	if err := os.WriteFile(path, []byte(trace), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	got, err := parseNetworkTrace(path)
+	if err != nil || got != nil {
+		t.Fatalf("parseNetworkTrace() = %+v, %v; want nil", got, err)
+	}
+}
+
+func TestNetworkActivityMerge(t *testing.T) {
+	t.Parallel()
+
+	a := &NetworkActivity{}
+	a.merge(&NetworkActivity{DNSLookups: 1, Hostnames: []string{"b.com"},
+		Connections: []NetworkConnection{{Program: "node", Address: "1.1.1.1:443", Count: 2}}})
+	a.merge(&NetworkActivity{DNSLookups: 2, Hostnames: []string{"a.com", "b.com"},
+		Connections: []NetworkConnection{{Program: "node", Address: "1.1.1.1:443", Count: 1}}})
+
+	want := &NetworkActivity{DNSLookups: 3, Hostnames: []string{"a.com", "b.com"},
+		Connections: []NetworkConnection{{Program: "node", Address: "1.1.1.1:443", Count: 3}}}
+	if !reflect.DeepEqual(a, want) {
+		t.Fatalf("merged = %+v, want %+v", a, want)
+	}
+}
+
+func TestUnescapeStraceString(t *testing.T) {
+	t.Parallel()
+
+	got := unescapeStraceString(`a\0\n\x41\1234\"\\`)
+	want := []byte{'a', 0, '\n', 'A', 0o123, '4', '"', '\\'}
+	if !reflect.DeepEqual(got, want) {
+		t.Fatalf("unescapeStraceString() = %q, want %q", got, want)
+	}
+}
@@ -0,0 +1,120 @@
+package cli
+
+import (
+	"context"
+	"fmt"
+	"strings"
+	"text/tabwriter"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// runNotification is a finished eval run as delivered to notification
+// channels.
+type runNotification struct {
+	Summary     *EvalSummary
+	OutputDir   string
+	Interrupted bool
+}
+
+// notifier delivers run notifications over one channel.
+type notifier interface {
+	channel() string
+	notify(ctx context.Context, n runNotification) error
+}
+
+// configuredNotifiers returns the channels enabled in [notify].
+func configuredNotifiers() []notifier {
+	if cfg == nil {
+		return nil
+	}
+	var ns []notifier
+	if cfg.Notify.Email.Enabled() {
+		ns = append(ns, emailNotifier{cfg: cfg.Notify.Email})
+	}
+	return ns
+}
+
+// shouldNotify reports whether n matches the notify.on trigger.
+func shouldNotify(on string, n runNotification) bool {
+	if on != config.NotifyFailure {
+		return true
+	}
+	return n.Interrupted || n.Summary.Failed > 0 || n.Summary.SkippedExternalTasks > 0
+}
+
+// sendNotifications delivers n on every configured channel. Failed
+// deliveries are logged and never fail the run.
+func sendNotifications(ctx context.Context, n runNotification) {
+	notifiers := configuredNotifiers()
+	if len(notifiers) == 0 || !shouldNotify(cfg.Notify.On, n) {
+		return
+	}
+	for _, nt := range notifiers {
+		if err := nt.notify(ctx, n); err != nil {
+			logger.Warn("failed to send notification", "channel", nt.channel(), "error", err)
+			continue
+		}
+		fmt.Printf(" Notification sent via %s\n", nt.channel())
+	}
+}
+
+// notificationSubject is the one-line headline of a run.
+func notificationSubject(n runNotification) string {
+	s := n.Summary
+	subject := "SanityHarness: " + s.Agent
+	if s.Model != "" && s.Model != "unknown" {
+		subject += " (" + s.Model + ")"
+	}
+	subject += fmt.Sprintf(" passed %d/%d (%.1f%%)", s.Passed, s.Total, s.PassRate)
+	if n.Interrupted {
+		subject += " [interrupted]"
+	}
+	return subject
+}
+
+// notificationTable renders a run's headline metrics and per-task results as
+// a plain-text table.
+func notificationTable(n runNotification) string {
+	s := n.Summary
+	var sb strings.Builder
+	w := tabwriter.NewWriter(&sb, 0, 0, 2, ' ', 0)
+	fmt.Fprintf(w, "Agent:\t%s\n", s.Agent)
+	if s.Model != "" {
+		fmt.Fprintf(w, "Model:\t%s\n", s.Model)
+	}
+	if s.Reasoning != "" {
+		fmt.Fprintf(w, "Reasoning:\t%s\n", s.Reasoning)
+	}
+	fmt.Fprintf(w, "Started:\t%s\n", s.Timestamp)
+	fmt.Fprintf(w, "Passed:\t%d/%d (%.1f%%)\n", s.Passed, s.Total, s.PassRate)
+	if s.MaxPossibleScore > 0 {
+		fmt.Fprintf(w, "Weighted:\t%.2f/%.2f (%.1f%%)\n", s.WeightedScore, s.MaxPossibleScore, s.WeightedPassRate)
+	}
+	if s.SkippedExternalTasks > 0 {
+		fmt.Fprintf(w, "Skipped:\t%d (external auth/quota/infra)\n", s.SkippedExternalTasks)
+	}
+	if s.Duration > 0 {
+		fmt.Fprintf(w, "Duration:\t%s\n", formatDuration(s.Duration))
+	}
+	if n.Interrupted {
+		fmt.Fprintf(w, "Interrupted:\tyes\n")
+	}
+	if n.OutputDir != "" {
+		fmt.Fprintf(w, "Results:\t%s\n", n.OutputDir)
+	}
+	_ = w.Flush()
+
+	sb.WriteString("\n")
+	w = tabwriter.NewWriter(&sb, 0, 0, 2, ' ', 0)
+	fmt.Fprintln(w, "TASK\tSTATUS\tATTEMPTS\tDURATION")
+	for _, r := range s.Results {
+		_, status := getResultStatusDisplay(r)
+		fmt.Fprintf(w, "%s\t%s\t%d\t%.1fs\n", r.Task, status, r.Attempts, r.Duration)
+	}
+	for _, f := range s.ExternalFailures {
+		fmt.Fprintf(w, "%s\tSKIPPED (%s)\t-\t-\n", f.Task, f.FailureClass)
+	}
+	_ = w.Flush()
+	return sb.String()
+}
@@ -0,0 +1,183 @@
+package cli
+
+import (
+	"bufio"
+	"context"
+	"encoding/base64"
+	"io"
+	"mime"
+	"mime/multipart"
+	"net"
+	"net/mail"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// fakeSMTPServer accepts one SMTP session and returns its envelope and
+// message.
+func fakeSMTPServer(t *testing.T) (port int, received <-chan []string) {
+	t.Helper()
+	ln, err := net.Listen("tcp", "127.0.0.1:0")
+	if err != nil {
+		t.Fatal(err)
+	}
+	t.Cleanup(func() { _ = ln.Close() })
+	ch := make(chan []string, 1)
+	go func() {
+		conn, err := ln.Accept()
+		if err != nil {
+			return
+		}
+		defer func() { _ = conn.Close() }()
+		r := bufio.NewReader(conn)
+		reply := func(line string) { _, _ = io.WriteString(conn, line+"\r\n") }
+		var session []string
+		reply("220 localhost ESMTP")
+		for {
+			line, err := r.ReadString('\n')
+			if err != nil {
+				return
+			}
+			line = strings.TrimRight(line, "\r\n")
+			switch cmd := strings.ToUpper(strings.SplitN(line, " ", 2)[0]); cmd {
+			case "EHLO", "HELO":
+				reply("250 localhost")
+			case "MAIL", "RCPT":
+				session = append(session, line)
+				reply("250 OK")
+			case "DATA":
+				reply("354 End data with <CR><LF>.<CR><LF>")
+				var data strings.Builder
+				for {
+					l, err := r.ReadString('\n')
+					if err != nil {
+						return
+					}
+					if l == ".\r\n" {
+						break
+					}
+					data.WriteString(strings.TrimPrefix(l, "."))
+				}
+				session = append(session, data.String())
+				reply("250 OK")
+			case "QUIT":
+				reply("221 Bye")
+				ch <- session
+				return
+			default:
+				reply("502 Command not implemented")
+			}
+		}
+	}()
+	return ln.Addr().(*net.TCPAddr).Port, ch
+}
+
+func TestEmailNotifier(t *testing.T) {
+	t.Parallel()
+
+	port, received := fakeSMTPServer(t)
+	ec := config.EmailConfig{
+		SMTPHost:    "127.0.0.1",
+		SMTPPort:    port,
+		TLS:         config.SMTPNone,
+		From:        "SanityHarness <bench@example.com>",
+		To:          []string{"team@example.com", "Lead <lead@example.com>"},
+		UsernameEnv: "SANITY_TEST_UNSET_SMTP_USERNAME",
+	}
+	n := runNotification{
+		Summary: &EvalSummary{
+			Agent:     "gemini",
+			Model:     "gemini-2.5-pro",
+			Timestamp: "2026-01-07T120000",
+			Passed:    1,
+			Failed:    1,
+			Total:     2,
+			PassRate:  50,
+			Results: []EvalResult{
+				{Task: "go/bank-account", Passed: true, Status: task.StatusPass, Attempts: 1, Duration: 12.5},
+				{Task: "rust/<react>", Status: task.StatusFail, Attempts: 2, Duration: 40},
+			},
+		},
+		OutputDir: "eval-results/gemini-2026-01-07T120000",
+	}
+	if err := (emailNotifier{cfg: ec}).notify(context.Background(), n); err != nil {
+		t.Fatalf("notify() error = %v", err)
+	}
+	session := <-received
+	if len(session) != 4 {
+		t.Fatalf("session = %q, want MAIL, 2 RCPT, and DATA", session)
+	}
+	if session[0] != "MAIL FROM:<bench@example.com>" || session[2] != "RCPT TO:<lead@example.com>" {
+		t.Errorf("envelope = %q", session[:3])
+	}
+
+	msg, err := mail.ReadMessage(strings.NewReader(session[3]))
+	if err != nil {
+		t.Fatalf("ReadMessage() error = %v", err)
+	}
+	if got := msg.Header.Get("Subject"); got != "SanityHarness: gemini (gemini-2.5-pro) passed 1/2 (50.0%)" {
+		t.Errorf("Subject = %q", got)
+	}
+	mediaType, params, err := mime.ParseMediaType(msg.Header.Get("Content-Type"))
+	if err != nil || mediaType != "multipart/mixed" {
+		t.Fatalf("Content-Type = %q, %v", mediaType, err)
+	}
+	mr := multipart.NewReader(msg.Body, params["boundary"])
+	body, err := mr.NextPart()
+	if err != nil {
+		t.Fatal(err)
+	}
+	text, _ := io.ReadAll(body)
+	for _, want := range []string{"Passed:", "1/2 (50.0%)", "go/bank-account", "PASS", "FAIL"} {
+		if !strings.Contains(string(text), want) {
+			t.Errorf("body missing %q:\n%s", want, text)
+		}
+	}
+	attachment, err := mr.NextPart()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got := attachment.FileName(); got != "sanity-report-2026-01-07T120000.html" {
+		t.Errorf("attachment name = %q", got)
+	}
+	// multipart.Reader does not decode base64 parts.
+	encoded, _ := io.ReadAll(attachment)
+	report, err := decodeBase64Lines(string(encoded))
+	if err != nil {
+		t.Fatalf("decoding attachment: %v", err)
+	}
+	if !strings.Contains(report, "rust/&lt;react&gt;") || !strings.Contains(report, "<h1>Evaluation Report</h1>") {
+		t.Errorf("report is not the escaped HTML report:\n%s", report)
+	}
+}
+
+func decodeBase64Lines(s string) (string, error) {
+	data, err := base64.StdEncoding.DecodeString(strings.ReplaceAll(s, "\r\n", ""))
+	return string(data), err
+}
+
+func TestShouldNotify(t *testing.T) {
+	t.Parallel()
+
+	clean := runNotification{Summary: &EvalSummary{Passed: 3, Total: 3}}
+	failed := runNotification{Summary: &EvalSummary{Passed: 2, Failed: 1, Total: 3}}
+	interrupted := runNotification{Summary: &EvalSummary{Passed: 1, Total: 1}, Interrupted: true}
+	tests := []struct {
+		on   string
+		n    runNotification
+		want bool
+	}{
+		{config.NotifyAlways, clean, true},
+		{config.NotifyFailure, clean, false},
+		{config.NotifyFailure, failed, true},
+		{config.NotifyFailure, interrupted, true},
+	}
+	for i, tt := range tests {
+		if got := shouldNotify(tt.on, tt.n); got != tt.want {
+			t.Errorf("case %d: shouldNotify(%q) = %v, want %v", i, tt.on, got, tt.want)
+		}
+	}
+}
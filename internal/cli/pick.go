@@ -0,0 +1,204 @@
+package cli
+
+import (
+	"bufio"
+	"bytes"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"strconv"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+var errNoTasksPicked = errors.New("no tasks selected")
+
+// pickTasks lets the user choose tasks interactively and returns their IDs.
+// It uses fzf when available and falls back to a built-in prompt otherwise.
+func pickTasks(candidates []*task.Task) ([]string, error) {
+	if len(candidates) == 0 {
+		return nil, fmt.Errorf("no tasks match the current filters")
+	}
+	if _, err := exec.LookPath("fzf"); err == nil {
+		return pickTasksFzf(candidates)
+	}
+	return promptPickTasks(os.Stdin, os.Stdout, candidates)
+}
+
+// pickTasksFzf runs fzf in multi-select mode over the candidates.
+func pickTasksFzf(candidates []*task.Task) ([]string, error) {
+	var input strings.Builder
+	for _, t := range candidates {
+		fmt.Fprintf(&input, "%s\t%s\t%s\t%s\n", t.ID(), t.Tier, t.Difficulty, t.Description)
+	}
+
+	cmd := exec.Command("fzf", "--multi", "--delimiter", "\t",
+		"--prompt", "tasks> ", "--header", "TAB: select  ENTER: run selected")
+	cmd.Stdin = strings.NewReader(input.String())
+	cmd.Stderr = rawStderr
+	var out bytes.Buffer
+	cmd.Stdout = &out
+	if err := cmd.Run(); err != nil {
+		var exitErr *exec.ExitError
+		// fzf exits 1 for no match and 130 when cancelled.
+		if errors.As(err, &exitErr) && (exitErr.ExitCode() == 1 || exitErr.ExitCode() == 130) {
+			return nil, errNoTasksPicked
+		}
+		return nil, fmt.Errorf("running fzf: %w", err)
+	}
+
+	lines := strings.Split(strings.TrimSpace(out.String()), "\n")
+	ids := make([]string, 0, len(lines))
+	for _, line := range lines {
+		if id, _, _ := strings.Cut(line, "\t"); id != "" {
+			ids = append(ids, id)
+		}
+	}
+	if len(ids) == 0 {
+		return nil, errNoTasksPicked
+	}
+	return ids, nil
+}
+
+// promptPickTasks is the built-in picker: the user narrows the list with a
+// fuzzy filter, then selects entries by number.
+func promptPickTasks(in io.Reader, out io.Writer, candidates []*task.Task) ([]string, error) {
+	reader := bufio.NewReader(in)
+	matches := candidates
+	for {
+		fmt.Fprintln(out)
+		for i, t := range matches {
+			fmt.Fprintf(out, " %3d) %-36s %-9s %s\n", i+1, t.ID(), t.Tier, t.Difficulty)
+		}
+		fmt.Fprintf(out, "\n Select by number (e.g. 1,3-5), 'a' for all shown, or type to filter: ")
+
+		line, err := reader.ReadString('\n')
+		line = strings.TrimSpace(line)
+		if line == "" && err != nil {
+			return nil, errNoTasksPicked
+		}
+
+		switch {
+		case line == "":
+			continue
+		case line == "a":
+			return taskIDsOf(matches), nil
+		case looksLikeSelection(line):
+			idx, selErr := parseSelection(line, len(matches))
+			if selErr != nil {
+				fmt.Fprintf(out, " %v\n", selErr)
+				continue
+			}
+			picked := make([]*task.Task, 0, len(idx))
+			for _, i := range idx {
+				picked = append(picked, matches[i])
+			}
+			return taskIDsOf(picked), nil
+		default:
+			filtered := fuzzyFilterTasks(candidates, line)
+			if len(filtered) == 0 {
+				fmt.Fprintf(out, " No tasks match %q\n", line)
+				continue
+			}
+			matches = filtered
+		}
+	}
+}
+
+// taskIDsOf returns the IDs of ts in order.
+func taskIDsOf(ts []*task.Task) []string {
+	ids := make([]string, 0, len(ts))
+	for _, t := range ts {
+		ids = append(ids, t.ID())
+	}
+	return ids
+}
+
+// looksLikeSelection reports whether s consists only of digits, commas,
+// dashes, and spaces.
+func looksLikeSelection(s string) bool {
+	return strings.Trim(s, "0123456789,- ") == ""
+}
+
+// parseSelection parses "1,3-5" into zero-based indices below n, keeping the
+// first occurrence of each.
+func parseSelection(s string, n int) ([]int, error) {
+	seen := make(map[int]bool)
+	var idx []int
+	add := func(i int) error {
+		if i < 1 || i > n {
+			return fmt.Errorf("selection %d out of range (1-%d)", i, n)
+		}
+		if !seen[i] {
+			seen[i] = true
+			idx = append(idx, i-1)
+		}
+		return nil
+	}
+
+	for _, part := range strings.Split(s, ",") {
+		part = strings.TrimSpace(part)
+		if part == "" {
+			continue
+		}
+		lo, hi, isRange := strings.Cut(part, "-")
+		start, err := strconv.Atoi(strings.TrimSpace(lo))
+		if err != nil {
+			return nil, fmt.Errorf("invalid selection %q", part)
+		}
+		end := start
+		if isRange {
+			if end, err = strconv.Atoi(strings.TrimSpace(hi)); err != nil || end < start {
+				return nil, fmt.Errorf("invalid range %q", part)
+			}
+		}
+		for i := start; i <= end; i++ {
+			if err := add(i); err != nil {
+				return nil, err
+			}
+		}
+	}
+	if len(idx) == 0 {
+		return nil, errNoTasksPicked
+	}
+	return idx, nil
+}
+
+// fuzzyFilterTasks keeps tasks whose ID, tags, or description contain every
+// whitespace-separated term of query as a case-insensitive subsequence.
+func fuzzyFilterTasks(candidates []*task.Task, query string) []*task.Task {
+	terms := strings.Fields(strings.ToLower(query))
+	out := make([]*task.Task, 0, len(candidates))
+	for _, t := range candidates {
+		haystack := strings.ToLower(t.ID() + " " + strings.Join(t.Tags, " ") + " " + t.Description)
+		matched := true
+		for _, term := range terms {
+			if !fuzzyContains(haystack, term) {
+				matched = false
+				break
+			}
+		}
+		if matched {
+			out = append(out, t)
+		}
+	}
+	return out
+}
+
+// fuzzyContains reports whether needle's characters appear in order in haystack.
+func fuzzyContains(haystack, needle string) bool {
+	want := []rune(needle)
+	i := 0
+	for _, r := range haystack {
+		if i == len(want) {
+			break
+		}
+		if r == want[i] {
+			i++
+		}
+	}
+	return i == len(want)
+}
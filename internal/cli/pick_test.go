@@ -0,0 +1,64 @@
+package cli
+
+import (
+	"bytes"
+	"errors"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestParseSelection(t *testing.T) {
+	t.Parallel()
+
+	got, err := parseSelection("3, 1-2,2", 5)
+	if err != nil {
+		t.Fatalf("parseSelection() error = %v", err)
+	}
+	if !slices.Equal(got, []int{2, 0, 1}) {
+		t.Fatalf("parseSelection() = %v, want [2 0 1]", got)
+	}
+
+	for _, bad := range []string{"0", "6", "4-2", "x"} {
+		if _, err := parseSelection(bad, 5); err == nil {
+			t.Fatalf("parseSelection(%q) expected error", bad)
+		}
+	}
+	if _, err := parseSelection(",", 5); !errors.Is(err, errNoTasksPicked) {
+		t.Fatalf("expected errNoTasksPicked, got %v", err)
+	}
+}
+
+func TestPromptPickTasks(t *testing.T) {
+	t.Parallel()
+
+	candidates := []*task.Task{
+		{Slug: "bank-account", Language: task.Go, Tags: []string{"concurrency"}},
+		{Slug: "regex-lite", Language: task.Rust, Tags: []string{"parsing"}},
+		{Slug: "glob", Language: task.TypeScript, Tags: []string{"parsing"}},
+	}
+
+	// Filter by a fuzzy term, then pick the second match.
+	var out bytes.Buffer
+	got, err := promptPickTasks(strings.NewReader("prsng\n2\n"), &out, candidates)
+	if err != nil {
+		t.Fatalf("promptPickTasks() error = %v", err)
+	}
+	if !slices.Equal(got, []string{"typescript/glob"}) {
+		t.Fatalf("promptPickTasks() = %v, want [typescript/glob]", got)
+	}
+
+	got, err = promptPickTasks(strings.NewReader("go/bnk\na\n"), &out, candidates)
+	if err != nil {
+		t.Fatalf("promptPickTasks() error = %v", err)
+	}
+	if !slices.Equal(got, []string{"go/bank-account"}) {
+		t.Fatalf("promptPickTasks() = %v, want [go/bank-account]", got)
+	}
+
+	if _, err := promptPickTasks(strings.NewReader(""), &out, candidates); !errors.Is(err, errNoTasksPicked) {
+		t.Fatalf("expected errNoTasksPicked on EOF, got %v", err)
+	}
+}
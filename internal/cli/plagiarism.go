@@ -0,0 +1,147 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+const (
+	// plagiarismShingleSize is the number of consecutive tokens compared as
+	// one unit.
+	plagiarismShingleSize = 8
+
+	// plagiarismMinShingles is the least new code, in shingles, worth
+	// comparing. Smaller solutions match everything that implements the same
+	// signatures.
+	plagiarismMinShingles = 20
+)
+
+// PlagiarismMatch is the known public solution closest to the agent's code.
+// Similarity is the share of the agent's new token sequences that also appear
+// in that solution.
+type PlagiarismMatch struct {
+	Source     string  `json:"source"`
+	Similarity float64 `json:"similarity"`
+	Flagged    bool    `json:"flagged"`
+}
+
+var (
+	sourceCommentPattern = regexp.MustCompile(`(?s)/\*.*?\*/|//[^\n]*`)
+	sourceTokenPattern   = regexp.MustCompile(`[A-Za-z_][A-Za-z0-9_]*|[0-9][0-9A-Za-z_.]*|"(?:[^"\\\n]|\\.)*"|\S`)
+)
+
+// checkKnownSolution compares the agent's solution with the configured corpus
+// of public solutions. Detection is best-effort and never affects the score.
+func checkKnownSolution(result *EvalResult, loader *task.Loader, t *task.Task, workspaceDir string) {
+	if cfg.Plagiarism.Corpus == "" {
+		return
+	}
+	match, err := detectKnownSolution(cfg.Plagiarism, loader, t, workspaceDir)
+	if err != nil {
+		logger.Debug("failed to compare with known solutions", "task", t.ID(), "error", err)
+		return
+	}
+	result.Plagiarism = match
+	if match != nil && match.Flagged {
+		logger.Warn("solution closely matches a known public solution",
+			"task", t.ID(), "source", match.Source, "similarity", fmt.Sprintf("%.2f", match.Similarity))
+	}
+}
+
+// detectKnownSolution scores the agent's versions of the task's stub files
+// against every reference under <corpus>/<language>/<slug>/. Each entry there
+// is one reference solution: a single file, or a directory of files. Token
+// sequences already present in the stubs are ignored. It returns nil when the
+// corpus has no reference for the task or the agent wrote too little code.
+func detectKnownSolution(pc config.PlagiarismConfig, loader *task.Loader, t *task.Task, workspaceDir string) (*PlagiarismMatch, error) {
+	refDir := filepath.Join(pc.Corpus, string(t.Language), t.Slug)
+	entries, err := os.ReadDir(refDir)
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("reading solution corpus: %w", err)
+	}
+
+	agent := make(map[string]bool)
+	stub := make(map[string]bool)
+	for _, filename := range t.Files.Stub {
+		stubData, err := loader.ReadTaskFile(t, filename)
+		if err != nil {
+			return nil, fmt.Errorf("reading canonical %s: %w", filename, err)
+		}
+		addShingles(stub, string(stubData))
+		data, err := os.ReadFile(filepath.Join(workspaceDir, filepath.FromSlash(task.StripTxtExtension(filename))))
+		if err != nil {
+			continue
+		}
+		addShingles(agent, string(data))
+	}
+	for s := range stub {
+		delete(agent, s)
+	}
+	if len(agent) < plagiarismMinShingles {
+		return nil, nil
+	}
+
+	var best *PlagiarismMatch
+	for _, entry := range entries {
+		ref, err := referenceShingles(filepath.Join(refDir, entry.Name()))
+		if err != nil {
+			return nil, err
+		}
+		shared := 0
+		for s := range agent {
+			if ref[s] {
+				shared++
+			}
+		}
+		similarity := float64(shared) / float64(len(agent))
+		if best == nil || similarity > best.Similarity {
+			best = &PlagiarismMatch{
+				Source:     filepath.ToSlash(filepath.Join(string(t.Language), t.Slug, entry.Name())),
+				Similarity: similarity,
+				Flagged:    similarity >= pc.Threshold,
+			}
+		}
+	}
+	return best, nil
+}
+
+// referenceShingles returns the shingles of a reference solution file, or of
+// every file below a reference solution directory.
+func referenceShingles(path string) (map[string]bool, error) {
+	set := make(map[string]bool)
+	err := filepath.WalkDir(path, func(p string, d fs.DirEntry, err error) error {
+		if err != nil || !d.Type().IsRegular() {
+			return err
+		}
+		data, err := os.ReadFile(p)
+		if err != nil {
+			return err
+		}
+		addShingles(set, string(data))
+		return nil
+	})
+	if err != nil {
+		return nil, fmt.Errorf("reading reference solution: %w", err)
+	}
+	return set, nil
+}
+
+// addShingles adds every run of plagiarismShingleSize consecutive tokens in
+// src to set. Comments are dropped so they cannot dilute or inflate a match.
+func addShingles(set map[string]bool, src string) {
+	tokens := sourceTokenPattern.FindAllString(sourceCommentPattern.ReplaceAllString(src, " "), -1)
+	for i := 0; i+plagiarismShingleSize <= len(tokens); i++ {
+		set[strings.Join(tokens[i:i+plagiarismShingleSize], "\x00")] = true
+	}
+}
@@ -0,0 +1,119 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+const bankAccountSolution = `package account
+
+import "sync"
+
+// Account is a bank account safe for concurrent use.
+type Account struct {
+	mu      sync.Mutex
+	balance int64
+	open    bool
+}
+
+func Open(amount int64) *Account {
+	if amount < 0 {
+		return nil
+	}
+	return &Account{balance: amount, open: true}
+}
+
+func (a *Account) Balance() (int64, bool) {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	return a.balance, a.open
+}
+
+func (a *Account) Deposit(amount int64) (int64, bool) {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	if !a.open || a.balance+amount < 0 {
+		return a.balance, false
+	}
+	a.balance += amount
+	return a.balance, true
+}
+
+func (a *Account) Close() (int64, bool) {
+	a.mu.Lock()
+	defer a.mu.Unlock()
+	if !a.open {
+		return 0, false
+	}
+	a.open = false
+	return a.balance, true
+}
+`
+
+func TestDetectKnownSolution(t *testing.T) {
+	t.Parallel()
+
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	taskDef, err := loader.Load("bank-account")
+	if err != nil {
+		t.Fatalf("load task: %v", err)
+	}
+	workspace := t.TempDir()
+	if err := os.WriteFile(filepath.Join(workspace, "bank_account.go"), []byte(bankAccountSolution), 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	corpus := t.TempDir()
+	pc := config.PlagiarismConfig{Corpus: corpus, Threshold: 0.8}
+	if match, err := detectKnownSolution(pc, loader, taskDef, workspace); err != nil || match != nil {
+		t.Fatalf("empty corpus = %+v, %v; want nil", match, err)
+	}
+
+	refDir := filepath.Join(corpus, "go", "bank-account")
+	if err := os.MkdirAll(filepath.Join(refDir, "community"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	// The same code with different comments and layout is still a verbatim copy.
+	verbatim := "// Package account from a public solutions site.\n" + bankAccountSolution
+	if err := os.WriteFile(filepath.Join(refDir, "exercism.go"), []byte(verbatim), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	different := `package account
+
+import "sync/atomic"
+
+type Account struct{ state atomic.Pointer[state] }
+
+type state struct {
+	balance int64
+	closed  bool
+}
+`
+	if err := os.WriteFile(filepath.Join(refDir, "community", "account.go"), []byte(different), 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	match, err := detectKnownSolution(pc, loader, taskDef, workspace)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if match == nil || match.Source != "go/bank-account/exercism.go" || match.Similarity != 1 || !match.Flagged {
+		t.Fatalf("match = %+v", match)
+	}
+
+	if err := os.Remove(filepath.Join(refDir, "exercism.go")); err != nil {
+		t.Fatal(err)
+	}
+	match, err = detectKnownSolution(pc, loader, taskDef, workspace)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if match == nil || match.Source != "go/bank-account/community" || match.Flagged {
+		t.Fatalf("match = %+v", match)
+	}
+}
@@ -0,0 +1,222 @@
+package cli
+
+import (
+	"bytes"
+	"cmp"
+	"encoding/json"
+	"io"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// pluginProtocolVersion is the version of the agent plugin protocol sent in
+// every task request. See docs/PLUGINS.md.
+const pluginProtocolVersion = 1
+
+// Translated plugin events in agent.log. The turn and tool prefixes double
+// as the default turn_pattern and tool_call_pattern of plugin agents.
+const (
+	pluginTurnPrefix  = "[turn]"
+	pluginToolPrefix  = "[tool] "
+	pluginUsagePrefix = "[usage] "
+	pluginDonePrefix  = "[done] "
+)
+
+// pluginRequest is the single line a plugin agent reads from stdin.
+type pluginRequest struct {
+	Type           string `json:"type"` // Always "task"
+	Version        int    `json:"version"`
+	Prompt         string `json:"prompt"`
+	Workspace      string `json:"workspace"`
+	Model          string `json:"model,omitempty"`
+	Reasoning      string `json:"reasoning,omitempty"`
+	TimeoutSeconds int    `json:"timeout_seconds"`
+	Attempt        int    `json:"attempt"` // 1-based
+	samplingParams
+}
+
+// pluginEvent is one NDJSON line a plugin agent writes to stdout. Usage
+// events carry the token counts inline; done carries them under usage.
+type pluginEvent struct {
+	Type    string      `json:"type"`
+	Message string      `json:"message,omitempty"`
+	Name    string      `json:"name,omitempty"`
+	Input   string      `json:"input,omitempty"`
+	Status  string      `json:"status,omitempty"`
+	Usage   *TokenUsage `json:"usage,omitempty"`
+	*TokenUsage
+}
+
+// pluginDone is the record of a done event kept in agent.log.
+type pluginDone struct {
+	Status  string `json:"status"`
+	Message string `json:"message,omitempty"`
+}
+
+// newPluginRequest encodes the task request for one attempt.
+func newPluginRequest(prompt, workspaceDir, model, reasoning string, sampling samplingParams, timeout time.Duration, attempt int) []byte {
+	data, _ := json.Marshal(pluginRequest{
+		Type:           "task",
+		Version:        pluginProtocolVersion,
+		Prompt:         prompt,
+		Workspace:      workspaceDir,
+		Model:          model,
+		Reasoning:      reasoning,
+		TimeoutSeconds: int(timeout.Seconds()),
+		Attempt:        attempt + 1,
+		samplingParams: sampling,
+	})
+	return append(data, '\n')
+}
+
+// pluginAgentConfig returns agentCfg with turn and tool-call patterns that
+// match translated plugin events, unless the config sets its own.
+func pluginAgentConfig(agentCfg *config.AgentConfig) *config.AgentConfig {
+	c := *agentCfg
+	if c.TurnPattern == "" {
+		c.TurnPattern = `^\[turn\]`
+	}
+	if c.ToolCallPattern == "" {
+		c.ToolCallPattern = `^\[tool\] `
+	}
+	return &c
+}
+
+// pluginDecoder translates a plugin's NDJSON events into readable agent.log
+// lines, so transcripts, activity limits, and behavior metrics work as they
+// do for CLI agents. Lines that are not events pass through unchanged.
+// onDone is called once, on the first done event.
+type pluginDecoder struct {
+	out     io.Writer
+	onDone  func()
+	mu      sync.Mutex
+	pending []byte
+	done    bool
+}
+
+func newPluginDecoder(out io.Writer, onDone func()) *pluginDecoder {
+	return &pluginDecoder{out: out, onDone: onDone}
+}
+
+func (d *pluginDecoder) Write(p []byte) (int, error) {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	d.pending = append(d.pending, p...)
+	for {
+		i := bytes.IndexByte(d.pending, '\n')
+		if i < 0 {
+			break
+		}
+		if err := d.writeLine(d.pending[:i]); err != nil {
+			return len(p), err
+		}
+		d.pending = d.pending[i+1:]
+	}
+	if len(d.pending) > maxActivityLine {
+		err := d.writeLine(d.pending)
+		d.pending = d.pending[:0]
+		return len(p), err
+	}
+	return len(p), nil
+}
+
+// Flush translates a final unterminated line.
+func (d *pluginDecoder) Flush() {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+	if len(d.pending) > 0 {
+		_ = d.writeLine(d.pending)
+		d.pending = d.pending[:0]
+	}
+}
+
+func (d *pluginDecoder) writeLine(line []byte) error {
+	text, done := translatePluginEvent(line)
+	if _, err := io.WriteString(d.out, text+"\n"); err != nil {
+		return err
+	}
+	if done && !d.done {
+		d.done = true
+		if d.onDone != nil {
+			d.onDone()
+		}
+	}
+	return nil
+}
+
+// translatePluginEvent returns the agent.log text for one output line and
+// whether it was a done event.
+func translatePluginEvent(line []byte) (string, bool) {
+	var event pluginEvent
+	trimmed := bytes.TrimSpace(line)
+	if !bytes.HasPrefix(trimmed, []byte("{")) || json.Unmarshal(trimmed, &event) != nil || event.Type == "" {
+		return string(line), false
+	}
+	switch event.Type {
+	case "progress":
+		return event.Message, false
+	case "turn":
+		return strings.TrimSpace(pluginTurnPrefix + " " + event.Message), false
+	case "tool_call":
+		text := pluginToolPrefix + event.Name
+		if event.Input != "" {
+			text += ": " + event.Input
+		}
+		// Shell commands are also logged the way behavior metrics read them.
+		if event.Name == "shell" && event.Input != "" {
+			text += "\n$ " + event.Input
+		}
+		return text, false
+	case "usage":
+		data, _ := json.Marshal(cmp.Or(event.TokenUsage, &TokenUsage{}))
+		return pluginUsagePrefix + string(data), false
+	case "error":
+		return "[error] " + event.Message, false
+	case "done":
+		var text string
+		if event.Usage != nil {
+			data, _ := json.Marshal(event.Usage)
+			text = pluginUsagePrefix + string(data) + "\n"
+		}
+		data, _ := json.Marshal(pluginDone{Status: event.Status, Message: event.Message})
+		return text + pluginDonePrefix + string(data), true
+	}
+	return string(line), false
+}
+
+// parsePluginUsage sums the usage a plugin reported, as translated into
+// agent.log.
+func parsePluginUsage(data []byte) *TokenUsage {
+	var total *TokenUsage
+	for line := range bytes.Lines(data) {
+		rest, ok := bytes.CutPrefix(bytes.TrimSpace(line), []byte(pluginUsagePrefix))
+		if !ok {
+			continue
+		}
+		var u TokenUsage
+		if err := json.Unmarshal(rest, &u); err == nil {
+			total = addUsage(total, &u)
+		}
+	}
+	return total
+}
+
+// pluginFinalMessage returns the message of the last done event in
+// agent.log.
+func pluginFinalMessage(data []byte) string {
+	var message string
+	for line := range bytes.Lines(data) {
+		rest, ok := bytes.CutPrefix(bytes.TrimSpace(line), []byte(pluginDonePrefix))
+		if !ok {
+			continue
+		}
+		var done pluginDone
+		if err := json.Unmarshal(rest, &done); err == nil && done.Message != "" {
+			message = done.Message
+		}
+	}
+	return message
+}
@@ -0,0 +1,119 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"log/slog"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestPluginDecoder(t *testing.T) {
+	t.Parallel()
+
+	var out bytes.Buffer
+	done := 0
+	d := newPluginDecoder(&out, func() { done++ })
+	events := `plain stderr-like line
+{"type":"progress","message":"reading files"}
+{"type":"turn","message":"2"}
+{"type":"tool_call","name":"shell","input":"go test ./..."}
+{"type":"usage","input_tokens":100,"output_tokens":20}
+{"type":"done","status":"completed","message":"Implemented it.","usage":{"input_tokens":5,"output_tokens":1,"cost_usd":0.01}}
+{"type":"done","status":"completed"}
+{"type":"future_event","x":1}`
+	for _, chunk := range []string{events[:30], events[30:200], events[200:]} {
+		if _, err := d.Write([]byte(chunk)); err != nil {
+			t.Fatal(err)
+		}
+	}
+	d.Flush()
+
+	want := `plain stderr-like line
+reading files
+[turn] 2
+[tool] shell: go test ./...
+$ go test ./...
+[usage] {"input_tokens":100,"output_tokens":20}
+[usage] {"input_tokens":5,"output_tokens":1,"cost_usd":0.01}
+[done] {"status":"completed","message":"Implemented it."}
+[done] {"status":"completed"}
+{"type":"future_event","x":1}
+`
+	if out.String() != want {
+		t.Errorf("translated log:\n%s\nwant:\n%s", out.String(), want)
+	}
+	if done != 1 {
+		t.Errorf("onDone called %d times, want 1", done)
+	}
+
+	usage := parsePluginUsage(out.Bytes())
+	if usage == nil || usage.InputTokens != 105 || usage.OutputTokens != 21 {
+		t.Errorf("parsePluginUsage() = %+v, want 105 in, 21 out", usage)
+	}
+	if got := pluginFinalMessage(out.Bytes()); got != "Implemented it." {
+		t.Errorf("pluginFinalMessage() = %q", got)
+	}
+}
+
+func TestRunAgentAttemptPlugin(t *testing.T) {
+	if runtime.GOOS == "windows" {
+		t.Skip("needs /bin/sh")
+	}
+	prev := logger
+	logger = slog.New(slog.DiscardHandler)
+	t.Cleanup(func() { logger = prev })
+
+	dir := t.TempDir()
+	workspace := filepath.Join(dir, "workspace")
+	if err := os.Mkdir(workspace, 0o755); err != nil {
+		t.Fatal(err)
+	}
+	script := filepath.Join(dir, "plugin.sh")
+	body := `#!/bin/sh
+read -r request
+printf '%s\n' "$request" > request.json
+echo '{"type":"turn"}'
+echo '{"type":"tool_call","name":"shell","input":"go test ./..."}'
+echo '{"type":"done","status":"completed","message":"ok"}'
+sleep 30
+`
+	if err := os.WriteFile(script, []byte(body), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	agentCfg := &config.AgentConfig{Command: script, Protocol: config.AgentProtocolPlugin}
+	logPath := filepath.Join(dir, "agent.log")
+
+	start := time.Now()
+	result := runAgentAttempt(context.Background(), agentCfg, "Implement it.", "qwen3-coder", workspace, logPath, 20*time.Second, "my-plugin", 0)
+	if result.timedOut || time.Since(start) > 15*time.Second {
+		t.Fatalf("attempt was not stopped on done (timed out %v after %v)", result.timedOut, time.Since(start))
+	}
+
+	data, err := os.ReadFile(filepath.Join(workspace, "request.json"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	var req pluginRequest
+	if err := json.Unmarshal(data, &req); err != nil {
+		t.Fatalf("request %q: %v", data, err)
+	}
+	if req.Type != "task" || req.Version != pluginProtocolVersion || req.Prompt != "Implement it." ||
+		req.Workspace != workspace || req.Model != "qwen3-coder" || req.TimeoutSeconds != 20 || req.Attempt != 1 {
+		t.Errorf("request = %+v", req)
+	}
+	log, err := os.ReadFile(logPath)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !strings.Contains(string(log), "[tool] shell: go test ./...\n$ go test ./...\n") {
+		t.Errorf("agent.log not translated:\n%s", log)
+	}
+}
@@ -5,18 +5,56 @@ package cli
 import (
 	"os/exec"
 	"syscall"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/result"
 )
 
+// terminationSignals maps [termination] signal names to signals.
+var terminationSignals = map[string]syscall.Signal{
+	"SIGTERM": syscall.SIGTERM,
+	"SIGINT":  syscall.SIGINT,
+	"SIGHUP":  syscall.SIGHUP,
+	"SIGQUIT": syscall.SIGQUIT,
+	"SIGKILL": syscall.SIGKILL,
+}
+
 // setupProcessGroup configures the command to run in its own process group so
-// the entire tree can be killed on timeout or interrupt, preventing orphaned
-// child processes.
-func setupProcessGroup(cmd *exec.Cmd) {
+// the entire tree can be stopped on timeout or interrupt, preventing orphaned
+// child processes. The group gets the [termination] signal first and SIGKILL
+// once the grace period runs out.
+func setupProcessGroup(cmd *exec.Cmd) *processTermination {
+	term := &processTermination{}
 	cmd.SysProcAttr = &syscall.SysProcAttr{Setpgid: true}
 	cmd.Cancel = func() error {
-		// Kill the entire process group (negative PID).
-		if cmd.Process != nil {
-			return syscall.Kill(-cmd.Process.Pid, syscall.SIGKILL)
+		if cmd.Process == nil {
+			return nil
 		}
-		return nil
+		term.mu.Lock()
+		defer term.mu.Unlock()
+		// A negative PID signals the entire process group.
+		term.pgid = -cmd.Process.Pid
+		tc := terminationConfig()
+		sig, ok := terminationSignals[tc.Signal]
+		if !ok || !tc.Graceful() || tc.GracePeriod <= 0 {
+			term.path = result.TerminationKilled
+			return syscall.Kill(term.pgid, syscall.SIGKILL)
+		}
+		term.path = result.TerminationGraceful
+		term.timer = time.AfterFunc(time.Duration(tc.GracePeriod)*time.Second, func() {
+			term.mu.Lock()
+			defer term.mu.Unlock()
+			if !term.done {
+				term.path = result.TerminationKilled
+				_ = syscall.Kill(term.pgid, syscall.SIGKILL)
+			}
+		})
+		return syscall.Kill(term.pgid, sig)
 	}
+	return term
+}
+
+// killProcessGroup kills whatever is left of a signaled process group.
+func killProcessGroup(pgid int) {
+	_ = syscall.Kill(pgid, syscall.SIGKILL)
 }
@@ -6,5 +6,9 @@ import "os/exec"
 
 // setupProcessGroup is a no-op on Windows. Process group management is not
 // supported in the same way; the context cancellation will still kill the
-// direct child process.
-func setupProcessGroup(_ *exec.Cmd) {}
+// direct child process, so there is no grace period to record.
+func setupProcessGroup(_ *exec.Cmd) *processTermination {
+	return &processTermination{}
+}
+
+func killProcessGroup(_ int) {}
@@ -0,0 +1,303 @@
+package cli
+
+import (
+	"fmt"
+	"io"
+	"os"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+)
+
+// progressBarWidth is the number of cells in the live progress bar.
+const progressBarWidth = 20
+
+// evalReporter receives task lifecycle events during an eval run. It is
+// implemented by evalProgress and, with --tui, by evalTUI.
+type evalReporter interface {
+	Start(id string)
+	Finish(r EvalResult)
+	Skip(r EvalResult)
+	Stop()
+}
+
+// evalProgress reports per-task progress during eval. In live mode (a terminal
+// without --verbose) it keeps a single status line with completed/total, the
+// longest-running task's elapsed time, and an ETA, printing one compact line
+// per finished task above it. Otherwise it prints the classic banners.
+type evalProgress struct {
+	mu        sync.Mutex
+	out       io.Writer
+	live      bool
+	parallel  int
+	total     int
+	started   int
+	done      int
+	passed    int
+	failed    int
+	running   map[string]time.Time
+	pending   map[string]bool
+	history   map[string]float64
+	durations []float64
+	stop      chan struct{}
+	stopped   bool
+}
+
+// newEvalProgress creates a reporter for taskIDs. history maps task IDs to
+// typical durations in seconds and may be nil.
+func newEvalProgress(out io.Writer, taskIDs []string, parallel int, live bool, history map[string]float64) *evalProgress {
+	p := &evalProgress{
+		out:      out,
+		live:     live,
+		parallel: max(parallel, 1),
+		total:    len(taskIDs),
+		running:  make(map[string]time.Time),
+		pending:  make(map[string]bool, len(taskIDs)),
+		history:  history,
+		stop:     make(chan struct{}),
+	}
+	for _, id := range taskIDs {
+		p.pending[id] = true
+	}
+	if live {
+		go p.tick()
+	}
+	return p
+}
+
+// tick redraws the status line once a second so elapsed time keeps moving.
+func (p *evalProgress) tick() {
+	ticker := time.NewTicker(time.Second)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-p.stop:
+			return
+		case <-ticker.C:
+			p.mu.Lock()
+			p.redraw()
+			p.mu.Unlock()
+		}
+	}
+}
+
+// Start records that id began running.
+func (p *evalProgress) Start(id string) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.started++
+	delete(p.pending, id)
+	p.running[id] = time.Now()
+	if p.live {
+		p.redraw()
+		return
+	}
+	if p.parallel == 1 {
+		fmt.Fprintln(p.out, "─────────────────────────────────────────────────────────────")
+		fmt.Fprintf(p.out, " [%d/%d] %s\n", p.started, p.total, id)
+		fmt.Fprintln(p.out, "─────────────────────────────────────────────────────────────")
+	}
+}
+
+// Finish reports a scored result.
+func (p *evalProgress) Finish(r EvalResult) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.complete(r.Task, r.Duration)
+	if r.Passed {
+		p.passed++
+	} else {
+		p.failed++
+	}
+
+	status, mark := "FAILED", "✗"
+	if r.Passed {
+		status, mark = "PASSED", "✓"
+	}
+	switch {
+	case p.live:
+		p.clearLine()
+		fmt.Fprintf(p.out, " %s %-40s %8s\n", mark, r.Task, formatDuration(r.Duration))
+	case p.parallel == 1:
+		fmt.Fprintf(p.out, " %s %s (%.2fs)\n", mark, status, r.Duration)
+	default:
+		fmt.Fprintf(p.out, " [%d/%d] %s %s (%.2fs)\n", p.done, p.total, r.Task, status, r.Duration)
+	}
+	if !r.Passed && r.Error != "" {
+		fmt.Fprintf(p.out, "   Error: %s\n", r.Error)
+	}
+	if r.Passed && r.FollowUpCount > 0 {
+		fmt.Fprintf(p.out, "   Follow-ups: %d/%d passed\n", followUpsPassedIn(r), r.FollowUpCount)
+	}
+	if note := durationAnomalyNote(r); note != "" {
+		fmt.Fprintf(p.out, "   \033[33m⚠ %s\033[0m\n", note)
+	}
+	if p.live {
+		p.redraw()
+	} else if p.parallel == 1 {
+		fmt.Fprintln(p.out)
+	}
+}
+
+// Skip reports a task excluded from results as a resumable external failure.
+func (p *evalProgress) Skip(r EvalResult) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.complete(r.Task, r.Duration)
+	label := externalFailureLabel(r.FailureClass)
+	switch {
+	case p.live:
+		p.clearLine()
+		fmt.Fprintf(p.out, " ⚠ %-40s %s — will be skipped (resumable)\n", r.Task, label)
+		p.redraw()
+	case p.parallel == 1:
+		fmt.Fprintf(p.out, " ⚠ %s — will be skipped (resumable)\n\n", label)
+	default:
+		fmt.Fprintf(p.out, " [%d/%d] %s ⚠ %s — will be skipped (resumable)\n", p.done, p.total, r.Task, label)
+	}
+}
+
+// Stop stops redrawing and clears the status line. It is safe to call more
+// than once and must be called before printing anything else.
+func (p *evalProgress) Stop() {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	if p.stopped {
+		return
+	}
+	p.stopped = true
+	close(p.stop)
+	if p.live {
+		p.clearLine()
+	}
+}
+
+func (p *evalProgress) complete(id string, seconds float64) {
+	p.done++
+	delete(p.running, id)
+	delete(p.pending, id)
+	if seconds > 0 {
+		p.durations = append(p.durations, seconds)
+	}
+}
+
+func (p *evalProgress) clearLine() {
+	fmt.Fprint(p.out, "\r\033[K")
+}
+
+func (p *evalProgress) redraw() {
+	if !p.live || p.stopped {
+		return
+	}
+	p.clearLine()
+	fmt.Fprint(p.out, p.statusLine(time.Now()))
+}
+
+// statusLine renders the live status line at now.
+func (p *evalProgress) statusLine(now time.Time) string {
+	filled := 0
+	if p.total > 0 {
+		filled = p.done * progressBarWidth / p.total
+	}
+	var sb strings.Builder
+	fmt.Fprintf(&sb, " [%s%s] %d/%d  ✓%d ✗%d",
+		strings.Repeat("█", filled), strings.Repeat("░", progressBarWidth-filled),
+		p.done, p.total, p.passed, p.failed)
+
+	if len(p.running) > 0 {
+		ids := make([]string, 0, len(p.running))
+		for id := range p.running {
+			ids = append(ids, id)
+		}
+		// Show the longest-running task; ties break by ID for stable output.
+		sort.Slice(ids, func(i, j int) bool {
+			ti, tj := p.running[ids[i]], p.running[ids[j]]
+			if !ti.Equal(tj) {
+				return ti.Before(tj)
+			}
+			return ids[i] < ids[j]
+		})
+		fmt.Fprintf(&sb, "  │ %s %s", ids[0], formatDuration(now.Sub(p.running[ids[0]]).Seconds()))
+		if len(ids) > 1 {
+			fmt.Fprintf(&sb, " (+%d)", len(ids)-1)
+		}
+	}
+
+	if eta, ok := p.eta(now); ok {
+		fmt.Fprintf(&sb, "  │ ETA %s", formatDuration(eta))
+	} else {
+		sb.WriteString("  │ ETA --")
+	}
+	return sb.String()
+}
+
+// eta estimates the remaining seconds from historical per-task durations,
+// falling back to the mean duration of tasks finished in this run.
+func (p *evalProgress) eta(now time.Time) (float64, bool) {
+	fallback := mean(p.durations)
+	if fallback == 0 && len(p.history) > 0 {
+		values := make([]float64, 0, len(p.history))
+		for _, v := range p.history {
+			values = append(values, v)
+		}
+		fallback = mean(values)
+	}
+	estimate := func(id string) (float64, bool) {
+		if v, ok := p.history[id]; ok && v > 0 {
+			return v, true
+		}
+		return fallback, fallback > 0
+	}
+
+	var remaining float64
+	for id := range p.pending {
+		v, ok := estimate(id)
+		if !ok {
+			return 0, false
+		}
+		remaining += v
+	}
+	for id, startedAt := range p.running {
+		v, ok := estimate(id)
+		if !ok {
+			return 0, false
+		}
+		remaining += max(v-now.Sub(startedAt).Seconds(), 0)
+	}
+	return remaining / float64(p.parallel), true
+}
+
+// loadTaskDurationHistory returns the mean duration per task for agent across
+// the eval runs under root. Unreadable runs are ignored.
+func loadTaskDurationHistory(root, agent string) map[string]float64 {
+	dirs, err := findRunDirs(root)
+	if err != nil {
+		return nil
+	}
+	sums := make(map[string]float64)
+	counts := make(map[string]int)
+	for _, dir := range dirs {
+		summary, err := loadSummaryFromDir(dir)
+		if err != nil || summary.Agent != agent {
+			continue
+		}
+		for _, r := range summary.Results {
+			if r.Duration > 0 {
+				sums[r.Task] += r.Duration
+				counts[r.Task]++
+			}
+		}
+	}
+	history := make(map[string]float64, len(sums))
+	for id, sum := range sums {
+		history[id] = sum / float64(counts[id])
+	}
+	return history
+}
+
+// isTerminal reports whether f is attached to a terminal.
+func isTerminal(f *os.File) bool {
+	info, err := f.Stat()
+	return err == nil && info.Mode()&os.ModeCharDevice != 0
+}
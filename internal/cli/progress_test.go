@@ -0,0 +1,64 @@
+package cli
+
+import (
+	"bytes"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestEvalProgressETA(t *testing.T) {
+	t.Parallel()
+
+	ids := []string{"go/a", "go/b", "go/c", "go/d"}
+	history := map[string]float64{"go/a": 60, "go/b": 120, "go/c": 30}
+	var out bytes.Buffer
+	p := newEvalProgress(&out, ids, 2, false, history)
+	defer p.Stop()
+
+	// go/d has no history, so it falls back to the historical mean (70s).
+	now := time.Now()
+	eta, ok := p.eta(now)
+	if !ok || eta != (60+120+30+70)/2.0 {
+		t.Fatalf("eta() = %v, %v; want 140, true", eta, ok)
+	}
+
+	p.Start("go/a")
+	p.running["go/a"] = now.Add(-20 * time.Second)
+	if eta, _ := p.eta(now); eta != (40+120+30+70)/2.0 {
+		t.Fatalf("eta() with running task = %v, want 130", eta)
+	}
+
+	p.Finish(EvalResult{Task: "go/a", Passed: true, Duration: 50})
+	line := p.statusLine(now)
+	for _, want := range []string{"1/4", "✓1 ✗0", "ETA"} {
+		if !strings.Contains(line, want) {
+			t.Errorf("statusLine() = %q, missing %q", line, want)
+		}
+	}
+}
+
+func TestEvalProgressWithoutHistory(t *testing.T) {
+	t.Parallel()
+
+	var out bytes.Buffer
+	p := newEvalProgress(&out, []string{"go/a", "go/b"}, 1, false, nil)
+	defer p.Stop()
+
+	if _, ok := p.eta(time.Now()); ok {
+		t.Fatal("expected no ETA before any task finishes")
+	}
+	p.Start("go/a")
+	p.Finish(EvalResult{Task: "go/a", Passed: false, Duration: 30, Error: "boom"})
+	if eta, ok := p.eta(time.Now()); !ok || eta != 30 {
+		t.Fatalf("eta() = %v, %v; want 30, true", eta, ok)
+	}
+
+	// Non-live sequential output keeps the classic banner format.
+	got := out.String()
+	for _, want := range []string{" [1/2] go/a\n", " ✗ FAILED (30.00s)\n", "   Error: boom\n"} {
+		if !strings.Contains(got, want) {
+			t.Errorf("output = %q, missing %q", got, want)
+		}
+	}
+}
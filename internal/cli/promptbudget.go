@@ -0,0 +1,93 @@
+package cli
+
+import (
+	"unicode/utf8"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// PromptBudget records how a task's prompt and injected context measured
+// against the configured budget for its model.
+type PromptBudget struct {
+	Limit         int      `json:"limit"`
+	Chars         int      `json:"chars"`
+	OriginalChars int      `json:"original_chars"`
+	Trimmed       []string `json:"trimmed,omitempty"`
+	OverBudget    bool     `json:"over_budget"`
+}
+
+// promptParts are the measured pieces of what an agent is given up front.
+type promptParts struct {
+	Base         string // Task prompt from buildAgentPrompt; never trimmed
+	SkillPacks   string // Prompt text contributed by skill packs
+	ContextChars int    // Total size of placed context files
+}
+
+func (p promptParts) prompt() string {
+	return p.Base + p.SkillPacks
+}
+
+func (p promptParts) chars() int {
+	return utf8.RuneCountInString(p.Base) + utf8.RuneCountInString(p.SkillPacks) + p.ContextChars
+}
+
+// fitPromptBudget measures parts against limit. With the trim action it drops
+// the configured optional sections, in order, until the input fits. The task
+// prompt itself is never cut, so an input that still doesn't fit is reported
+// as over budget rather than silently truncated.
+func fitPromptBudget(parts promptParts, limit int, budget config.PromptBudgetConfig) (promptParts, PromptBudget) {
+	rec := PromptBudget{Limit: limit, OriginalChars: parts.chars()}
+	if budget.Action == config.PromptBudgetTrim {
+		for _, section := range budget.Optional {
+			if parts.chars() <= limit {
+				break
+			}
+			switch section {
+			case config.PromptSectionSkillPacks:
+				if parts.SkillPacks == "" {
+					continue
+				}
+				parts.SkillPacks = ""
+			case config.PromptSectionContextFiles:
+				if parts.ContextChars == 0 {
+					continue
+				}
+				parts.ContextChars = 0
+			default:
+				continue
+			}
+			rec.Trimmed = append(rec.Trimmed, section)
+		}
+	}
+	rec.Chars = parts.chars()
+	rec.OverBudget = rec.Chars > limit
+	return parts, rec
+}
+
+// applyPromptBudget enforces the model's prompt budget for a task, removing
+// trimmed context files from workspace, and returns the final prompt. The
+// measured size is always recorded on result.
+func applyPromptBudget(result *EvalResult, parts promptParts, model, workspace string) (string, error) {
+	if limit := cfg.PromptBudget.LimitFor(model); limit > 0 {
+		var budget PromptBudget
+		parts, budget = fitPromptBudget(parts, limit, cfg.PromptBudget)
+		result.PromptBudget = &budget
+		if parts.ContextChars == 0 && len(result.ContextFiles) > 0 {
+			if err := removeContextFiles(result.ContextFiles, workspace); err != nil {
+				return "", err
+			}
+			result.ContextFiles = nil
+		}
+		if len(budget.Trimmed) > 0 {
+			logger.Info("trimmed prompt to fit budget", "task", result.Task, "sections", budget.Trimmed,
+				"chars", budget.Chars, "original_chars", budget.OriginalChars, "limit", limit)
+		}
+		if budget.OverBudget {
+			logger.Warn("prompt exceeds budget", "task", result.Task, "chars", budget.Chars, "limit", limit)
+		}
+	}
+	prompt := parts.prompt()
+	result.PromptChars = utf8.RuneCountInString(prompt)
+	result.ContextChars = parts.ContextChars
+	return prompt, nil
+}
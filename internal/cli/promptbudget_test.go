@@ -0,0 +1,59 @@
+package cli
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestFitPromptBudget(t *testing.T) {
+	t.Parallel()
+
+	parts := promptParts{
+		Base:         strings.Repeat("b", 100),
+		SkillPacks:   strings.Repeat("s", 50),
+		ContextChars: 200,
+	}
+	trim := config.PromptBudgetConfig{
+		Action:   config.PromptBudgetTrim,
+		Optional: []string{config.PromptSectionContextFiles, config.PromptSectionSkillPacks},
+	}
+
+	tests := []struct {
+		name        string
+		limit       int
+		budget      config.PromptBudgetConfig
+		wantChars   int
+		wantTrimmed []string
+		wantOver    bool
+	}{
+		{"fits", 1000, trim, 350, nil, false},
+		{"warn only", 300, config.PromptBudgetConfig{Action: config.PromptBudgetWarn, Optional: trim.Optional}, 350, nil, true},
+		{"trim first section", 200, trim, 150, []string{config.PromptSectionContextFiles}, false},
+		{"trim everything", 120, trim, 100, []string{config.PromptSectionContextFiles, config.PromptSectionSkillPacks}, false},
+		{"base never cut", 50, trim, 100, []string{config.PromptSectionContextFiles, config.PromptSectionSkillPacks}, true},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+			got, rec := fitPromptBudget(parts, tt.limit, tt.budget)
+			if rec.Chars != tt.wantChars || got.chars() != tt.wantChars {
+				t.Errorf("chars = %d (parts %d), want %d", rec.Chars, got.chars(), tt.wantChars)
+			}
+			if rec.OriginalChars != 350 || rec.Limit != tt.limit {
+				t.Errorf("record = %+v", rec)
+			}
+			if !reflect.DeepEqual(rec.Trimmed, tt.wantTrimmed) {
+				t.Errorf("trimmed = %v, want %v", rec.Trimmed, tt.wantTrimmed)
+			}
+			if rec.OverBudget != tt.wantOver {
+				t.Errorf("over budget = %v, want %v", rec.OverBudget, tt.wantOver)
+			}
+			if got.Base != parts.Base {
+				t.Error("base prompt was modified")
+			}
+		})
+	}
+}
@@ -0,0 +1,305 @@
+package cli
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"slices"
+	"strconv"
+	"strings"
+	"time"
+	"unicode/utf8"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	resultpkg "github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// repairDirName holds each repair attempt's agent.log and validation.log in
+// the task output directory, as repairs/<n>/.
+const repairDirName = "repairs"
+
+// maxFeedbackChars caps the test output or diff quoted in a repair prompt.
+// Longer text keeps its end, where test runners print their summaries.
+const maxFeedbackChars = 8000
+
+// RepairAttempt is the outcome of one repair turn: the agent given another
+// try at a task it failed, with feedback on the failure. Number counts
+// attempts, so the first repair is attempt 2. FailingTests lists the tests
+// that failed before the attempt, as far as the output shows them.
+type RepairAttempt struct {
+	Number        int          `json:"number"`
+	FailingTests  []string     `json:"failing_tests,omitempty"`
+	Passed        bool         `json:"passed"`
+	AgentTimedOut bool         `json:"agent_timed_out,omitempty"`
+	AgentTime     float64      `json:"agent_duration_seconds"`
+	ValidateTime  float64      `json:"validation_duration_seconds"`
+	Error         string       `json:"error,omitempty"`
+	FailureClass  FailureClass `json:"failure_class,omitempty"`
+}
+
+// resolveRepairFlags validates --repair-attempts and --attempt-feedback,
+// and fills in the default feedback mode.
+func resolveRepairFlags() error {
+	if evalRepairAttempts < 0 {
+		return errors.New("--repair-attempts must not be negative")
+	}
+	if evalAttemptFeedback == "" {
+		evalAttemptFeedback = config.AttemptFeedbackTestNames
+	}
+	if !slices.Contains(config.AttemptFeedbackModes, evalAttemptFeedback) {
+		return fmt.Errorf("--attempt-feedback must be one of %s", strings.Join(config.AttemptFeedbackModes, ", "))
+	}
+	return nil
+}
+
+// runRepairAttempts gives the agent up to evalRepairAttempts more turns at a
+// task whose solution failed validation, each told about the last failure
+// as evalAttemptFeedback sets. A passing repair makes the task pass, and
+// PassedOnAttempt records which attempt did it. session is the failed
+// validation of the first attempt. An interrupt during a repair leaves the
+// task to --resume, like one during the first attempt.
+func runRepairAttempts(ctx context.Context, result *EvalResult, r *runner.Runner, loader *task.Loader, t *task.Task, s followUpSession, session *resultpkg.Session) {
+	if evalRepairAttempts == 0 || result.Error != "" || session == nil {
+		return
+	}
+	if result.Passed {
+		result.PassedOnAttempt = 1
+		return
+	}
+	for i := range evalRepairAttempts {
+		if checkInterrupted(ctx) {
+			result.FailureClass = FailureClassInterrupted
+			result.Error = "interrupted during a repair attempt"
+			return
+		}
+		feedback, err := buildRepairPrompt(loader, t, s.agentWorkDir, session)
+		if err != nil {
+			logger.Warn("failed to build repair feedback", "task", t.ID(), "error", err)
+			break
+		}
+		attempt, next := runRepairAttempt(ctx, result, r, loader, t, i+2, feedback, s)
+		attempt.FailingTests = failingTestNames(t.Language, lastOutput(session))
+		result.Repairs = append(result.Repairs, attempt)
+		if attempt.FailureClass == FailureClassInterrupted {
+			result.FailureClass = FailureClassInterrupted
+			result.Error = "interrupted during a repair attempt"
+			return
+		}
+		if attempt.Passed {
+			applyValidationSessionResult(result, next)
+			result.PassedOnAttempt = attempt.Number
+			return
+		}
+		if next == nil {
+			logger.Debug("repair attempt failed", "task", t.ID(), "attempt", attempt.Number, "error", attempt.Error)
+			break
+		}
+		session = next
+	}
+}
+
+// runRepairAttempt runs repair attempt n in the agent's workspace from the
+// previous attempt, then validates the result. It returns the validation
+// session, or nil if validation did not run.
+func runRepairAttempt(ctx context.Context, result *EvalResult, r *runner.Runner, loader *task.Loader, t *task.Task, n int, prompt string, s followUpSession) (RepairAttempt, *resultpkg.Session) {
+	attempt := RepairAttempt{Number: n}
+	dir := filepath.Join(s.workspaceDir, repairDirName, strconv.Itoa(n))
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		attempt.Error = fmt.Sprintf("creating repair output dir: %v", err)
+		return attempt, nil
+	}
+	agentLogPath := filepath.Join(dir, "agent.log")
+	validationLogPath := filepath.Join(dir, "validation.log")
+
+	if err := evalChaos.install(s.agentWorkDir, t.Language); err != nil {
+		attempt.Error = fmt.Sprintf("installing chaos shims: %v", err)
+		return attempt, nil
+	}
+	agentResult := executeAgentWithRetries(ctx, t, s.agentCfg, prompt, s.model,
+		s.agentWorkDir, agentLogPath, s.agentTimeout, s.agent, time.Now())
+	if evalChaos != nil {
+		injections, err := removeChaosShims(s.agentWorkDir)
+		if err != nil {
+			logger.Warn("failed to remove chaos shims", "task", t.ID(), "error", err)
+		}
+		result.ChaosInjections += injections
+	}
+	attempt.AgentTime = agentResult.totalTime
+	attempt.AgentTimedOut = agentResult.timedOut
+	switch agentResult.failureClass {
+	case FailureClassInterrupted:
+		attempt.FailureClass = FailureClassInterrupted
+		return attempt, nil
+	case FailureClassInfra, FailureClassAuth, FailureClassQuotaExhausted:
+		attempt.FailureClass = agentResult.failureClass
+		attempt.Error = strings.ToLower(externalFailureLabel(agentResult.failureClass))
+		return attempt, nil
+	}
+
+	modified, err := detectModifiedTaskFiles(loader, t, s.agentWorkDir)
+	if err != nil {
+		attempt.Error = fmt.Sprintf("integrity check failed: %v", err)
+		return attempt, nil
+	}
+	if len(modified) > 0 {
+		attempt.FailureClass = FailureClassIntegrity
+		attempt.Error = fmt.Sprintf("modified task files: %s", strings.Join(modified, ", "))
+		return attempt, nil
+	}
+
+	// Replace the previous attempt's sources and build output with the
+	// agent's current work, then add the hidden tests back.
+	cleanupWorkspaceFiles(s.workspaceDir)
+	if err := copyDirContents(s.agentWorkDir, s.workspaceDir); err != nil {
+		attempt.Error = fmt.Sprintf("copying agent workspace: %v", err)
+		return attempt, nil
+	}
+	if err := restoreVisibleTests(loader, t, s.workspaceDir); err != nil {
+		attempt.Error = err.Error()
+		return attempt, nil
+	}
+	if err := writeHiddenTestsIfNeeded(loader, t, s.workspaceDir); err != nil {
+		attempt.Error = fmt.Sprintf("writing hidden tests: %v", err)
+		return attempt, nil
+	}
+	validationCmd, effectiveValidationCmd := buildValidationCommands(t)
+	session, validateDuration, err := runValidationSession(ctx, r, t, s.workspaceDir, s.validationTimeout, validationCmd)
+	attempt.ValidateTime = validateDuration
+	if err != nil {
+		rawOutput, exitCode, duration := validationErrorEvidence(session, validateDuration)
+		writeValidationLog(validationLogPath, rawOutput, effectiveValidationCmd, exitCode, duration,
+			strings.Contains(strings.ToLower(err.Error()), "timed out"), err)
+		attempt.Error = err.Error()
+		attempt.FailureClass = FailureClassValidationError
+		return attempt, nil
+	}
+	writeValidationSessionLog(validationLogPath, effectiveValidationCmd, session)
+	attempt.Passed = session.Passed()
+	return attempt, session
+}
+
+// buildRepairPrompt returns the prompt for a repair attempt after the
+// failed validation in session. The agent starts a new session, so the
+// prompt restates the task.
+func buildRepairPrompt(loader *task.Loader, t *task.Task, agentWorkDir string, session *resultpkg.Session) (string, error) {
+	stubFiles := make([]string, 0, len(t.Files.Stub))
+	for _, f := range t.Files.Stub {
+		stubFiles = append(stubFiles, task.StripTxtExtension(f))
+	}
+	var feedback string
+	switch evalAttemptFeedback {
+	case config.AttemptFeedbackNone:
+	case config.AttemptFeedbackTestOutput:
+		feedback = "TEST OUTPUT:\n" + tailChars(lastOutput(session), maxFeedbackChars)
+	case config.AttemptFeedbackPreviousDiff:
+		diff, err := buildSolutionDiff(loader, t, agentWorkDir)
+		if err != nil {
+			return "", err
+		}
+		feedback = "YOUR PREVIOUS CHANGES (diff against the original stubs):\n" + tailChars(diff, maxFeedbackChars)
+	default:
+		names := failingTestNames(t.Language, lastOutput(session))
+		if len(names) == 0 {
+			feedback = "FAILING TESTS: could not be determined from the test output."
+		} else {
+			feedback = "FAILING TESTS:\n- " + strings.Join(names, "\n- ")
+		}
+	}
+	prompt := fmt.Sprintf(`You are continuing work on a coding task called "%s" (%s).
+The workspace holds your previous attempt, and it does NOT pass the tests. Fix it.
+
+TASK:
+%s
+
+RULES:
+- Change the existing solution in place.
+- There may be hidden tests that check behavior beyond the visible tests.
+- ONLY edit the stub/solution source file(s): %s. You may add new helper source files if needed.
+- Do NOT modify test files or support files. Evaluation fails if you modify protected files.
+- Do NOT navigate to parent directories or read files outside the workspace.`,
+		t.Name, t.Language, strings.TrimSpace(t.Description), strings.Join(stubFiles, ", "))
+	if feedback != "" {
+		prompt += "\n\n" + strings.TrimRight(feedback, "\n")
+	}
+	return prompt, nil
+}
+
+// lastOutput returns the output of session's last validation attempt.
+func lastOutput(session *resultpkg.Session) string {
+	rawOutput, _, _, _ := lastSessionAttempt(session)
+	return rawOutput
+}
+
+// tailChars returns the last n bytes of s, marked as cut when it is longer.
+func tailChars(s string, n int) string {
+	if len(s) <= n {
+		return s
+	}
+	i := len(s) - n
+	for i < len(s) && !utf8.RuneStart(s[i]) {
+		i++
+	}
+	return "[... earlier output omitted ...]\n" + s[i:]
+}
+
+// failingTestPatterns match a failing test's name in the validation output
+// of each language's test runner.
+var failingTestPatterns = map[task.Language]*regexp.Regexp{
+	task.Go:         regexp.MustCompile(`(?m)^\s*--- FAIL: (\S+)`),
+	task.Rust:       regexp.MustCompile(`(?m)^test (\S+) \.\.\. FAILED`),
+	task.TypeScript: regexp.MustCompile(`(?m)^\s*✖ (.+?)(?: \([\d.]+m?s\))?\s*$`),
+	task.Kotlin:     regexp.MustCompile(`(?m)^(\S+ > .+?) FAILED\s*$`),
+	task.Dart:       regexp.MustCompile(`(?m)^\d+:\d+ \+\d+(?: ~\d+)? -\d+: (.+?) \[E\]\s*$`),
+	task.Zig:        regexp.MustCompile(`(?m)(\S+)\.\.\.FAIL`),
+}
+
+// failingTestNames returns the names of the failing tests in validation
+// output, in order and without repeats.
+func failingTestNames(lang task.Language, output string) []string {
+	re := failingTestPatterns[lang]
+	if re == nil {
+		return nil
+	}
+	var names []string
+	for _, m := range re.FindAllStringSubmatch(output, -1) {
+		name := strings.TrimSpace(m[1])
+		// node --test repeats failures under this heading.
+		if name == "failing tests:" || slices.Contains(names, name) {
+			continue
+		}
+		names = append(names, name)
+	}
+	return names
+}
+
+// writeReportRepairs lists the tasks that got repair attempts.
+func writeReportRepairs(sb *strings.Builder, summary EvalSummary) {
+	if summary.RepairedTasks+summary.UnrepairedTasks == 0 {
+		return
+	}
+	sb.WriteString("## Repair Attempts\n\n")
+	fmt.Fprintf(sb, "Tasks whose first solution failed were given up to %d more attempts with `%s` feedback; "+
+		"%d of %d then passed. Their logs are kept in `%s/<n>/`.\n\n",
+		summary.RepairAttempts, summary.AttemptFeedback, summary.RepairedTasks,
+		summary.RepairedTasks+summary.UnrepairedTasks, repairDirName)
+	sb.WriteString("| Task | Attempts | Result |\n")
+	sb.WriteString("|------|----------|--------|\n")
+	for _, r := range summary.Results {
+		if len(r.Repairs) == 0 {
+			continue
+		}
+		outcome := "failed"
+		if r.PassedOnAttempt > 1 {
+			outcome = fmt.Sprintf("passed on attempt %d", r.PassedOnAttempt)
+		} else if last := r.Repairs[len(r.Repairs)-1]; last.Error != "" {
+			outcome = strings.ReplaceAll(last.Error, "|", `\|`)
+		}
+		fmt.Fprintf(sb, "| %s | %d | %s |\n", r.Task, len(r.Repairs)+1, outcome)
+	}
+	sb.WriteString("\n")
+}
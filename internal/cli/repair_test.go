@@ -0,0 +1,92 @@
+package cli
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	resultpkg "github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestFailingTestNames(t *testing.T) {
+	t.Parallel()
+
+	for _, tc := range []struct {
+		lang   task.Language
+		output string
+		want   []string
+	}{
+		{task.Go, "=== RUN   TestDeposit\n--- FAIL: TestDeposit (0.00s)\n    --- FAIL: TestDeposit/negative (0.00s)\n--- PASS: TestOpen (0.00s)\nFAIL\n", []string{"TestDeposit", "TestDeposit/negative"}},
+		{task.Rust, "test tests::push ... ok\ntest tests::pop ... FAILED\n\nfailures:\n    tests::pop\n", []string{"tests::pop"}},
+		{task.TypeScript, "✖ parses quoted fields (1.52ms)\n✔ parses rows (0.3ms)\n✖ failing tests:\n\n✖ parses quoted fields (1.52ms)\n", []string{"parses quoted fields"}},
+		{task.Kotlin, "StackTest > popEmpty() FAILED\n    java.lang.AssertionError\n", []string{"StackTest > popEmpty()"}},
+		{task.Dart, "00:01 +3 -1: handles empty input [E]\n", []string{"handles empty input"}},
+		{task.Zig, "1/3 main.test.basic...OK\n2/3 main.test.overflow...FAIL (TestExpectedEqual)\n", []string{"main.test.overflow"}},
+		{task.Go, "ok  \texample\t0.01s\n", nil},
+	} {
+		if got := failingTestNames(tc.lang, tc.output); !reflect.DeepEqual(got, tc.want) {
+			t.Errorf("failingTestNames(%s) = %q, want %q", tc.lang, got, tc.want)
+		}
+	}
+}
+
+func TestBuildRepairPrompt(t *testing.T) {
+	prev := evalAttemptFeedback
+	t.Cleanup(func() { evalAttemptFeedback = prev })
+
+	tk := &task.Task{Slug: "bank-account", Name: "Bank Account", Language: task.Go, Description: "Implement a bank account.",
+		Files: task.TaskFiles{Stub: []string{"bank_account.go.txt"}}}
+	session := resultpkg.NewSession("bank-account", "go", resultpkg.SessionConfig{})
+	session.AddAttempt(1, time.Second, "--- FAIL: TestDeposit (0.00s)\n    bank_account_test.go:12: got 0, want 10\nFAIL\n", nil)
+
+	for mode, want := range map[string]string{
+		config.AttemptFeedbackNone:       "does NOT pass",
+		config.AttemptFeedbackTestNames:  "FAILING TESTS:\n- TestDeposit",
+		config.AttemptFeedbackTestOutput: "got 0, want 10",
+	} {
+		evalAttemptFeedback = mode
+		prompt, err := buildRepairPrompt(nil, tk, t.TempDir(), session)
+		if err != nil {
+			t.Fatalf("%s: %v", mode, err)
+		}
+		if !strings.Contains(prompt, want) || !strings.Contains(prompt, "bank_account.go") {
+			t.Errorf("%s prompt = %q, want it to contain %q", mode, prompt, want)
+		}
+		if mode != config.AttemptFeedbackTestOutput && strings.Contains(prompt, "got 0, want 10") {
+			t.Errorf("%s prompt quotes the test output", mode)
+		}
+	}
+}
+
+func TestResolveRepairFlags(t *testing.T) {
+	prevAttempts, prevFeedback := evalRepairAttempts, evalAttemptFeedback
+	t.Cleanup(func() { evalRepairAttempts, evalAttemptFeedback = prevAttempts, prevFeedback })
+
+	evalRepairAttempts, evalAttemptFeedback = 2, ""
+	if err := resolveRepairFlags(); err != nil || evalAttemptFeedback != config.AttemptFeedbackTestNames {
+		t.Errorf("resolveRepairFlags() = %v, feedback %q, want the failing_test_names default", err, evalAttemptFeedback)
+	}
+	evalAttemptFeedback = "hints"
+	if err := resolveRepairFlags(); err == nil {
+		t.Error("unknown feedback mode: want error")
+	}
+	evalRepairAttempts, evalAttemptFeedback = -1, ""
+	if err := resolveRepairFlags(); err == nil {
+		t.Error("negative repair attempts: want error")
+	}
+}
+
+func TestTailChars(t *testing.T) {
+	t.Parallel()
+
+	if got := tailChars("short", 10); got != "short" {
+		t.Errorf("tailChars() = %q", got)
+	}
+	// Cutting inside the two-byte é moves to the next rune.
+	if got := tailChars("aé bc", 4); got != "[... earlier output omitted ...]\n bc" {
+		t.Errorf("tailChars() = %q", got)
+	}
+}
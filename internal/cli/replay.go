@@ -0,0 +1,506 @@
+package cli
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+const (
+	// replayRecordName is the per-task file holding validation inputs.
+	replayRecordName = "replay.json"
+	// replayFormatVersion is bumped when ReplayRecord changes incompatibly.
+	replayFormatVersion = 1
+	// replayMaxFileSize skips larger files, which are build output rather than source.
+	replayMaxFileSize = 1 << 20
+)
+
+// replaySkipDirs are build and cache directories left behind by agents' own
+// test runs. Validation regenerates them, so they are not recorded.
+var replaySkipDirs = map[string]bool{
+	".git":          true,
+	".gradle":       true,
+	".dart_tool":    true,
+	".sanity-cache": true,
+	".sanity-chaos": true,
+	".zig-cache":    true,
+	"build":         true,
+	"node_modules":  true,
+	"target":        true,
+	"zig-cache":     true,
+	"zig-out":       true,
+}
+
+// ReplayRecord captures everything the validation phase of one task consumed,
+// so `sanity replay` can re-run it without invoking the agent.
+type ReplayRecord struct {
+	Version           int               `json:"version"`
+	Task              string            `json:"task"`
+	HarnessVersion    string            `json:"harness_version"`
+	Prompt            string            `json:"prompt"`
+	PromptHash        string            `json:"prompt_hash"`
+	AgentLogHash      string            `json:"agent_log_hash,omitempty"`
+	Image             string            `json:"image"`
+	ImageID           string            `json:"image_id,omitempty"`
+	ImageRepoDigests  []string          `json:"image_repo_digests,omitempty"`
+	ValidationCommand []string          `json:"validation_command"`
+	ValidationTimeout int               `json:"validation_timeout_seconds"`
+	Legacy            bool              `json:"legacy,omitempty"`
+	HiddenTestHashes  map[string]string `json:"hidden_test_hashes,omitempty"`
+	Files             map[string][]byte `json:"files"`
+}
+
+// ReplayOutcome compares a replayed validation with the recorded result.
+type ReplayOutcome struct {
+	Task           string  `json:"task"`
+	RecordedPassed bool    `json:"recorded_passed"`
+	Passed         bool    `json:"passed"`
+	Match          bool    `json:"match"`
+	ImageDrift     bool    `json:"image_drift,omitempty"`
+	TestsChanged   bool    `json:"tests_changed,omitempty"`
+	Duration       float64 `json:"duration_seconds"`
+	Error          string  `json:"error,omitempty"`
+}
+
+var (
+	replayTasks  string
+	replayOutput string
+	replayJSON   bool
+)
+
+var replayCmd = &cobra.Command{
+	Use:   "replay <eval-dir>",
+	Short: "Re-run the validation phase of a past eval without the agent",
+	Long: `Re-runs validation for each task of a past eval from its recorded inputs
+(replay.json): the exact workspace files the agent produced, the validation
+command and timeout, and the container image pinned by digest when known.
+No model is invoked, so validation pipeline changes can be checked against
+historical runs.
+
+Each task's replayed pass/fail is compared with the recorded result. Hidden
+tests come from the current harness; tasks whose hidden tests changed since
+the run are flagged. The command exits non-zero if any outcome differs.
+
+Only evals run with a harness that writes replay.json can be replayed.`,
+	Example: `  sanity replay eval-results/2026-01-07T120000-gemini
+  sanity replay eval-results/2026-01-07T120000-gemini --tasks go/bank-account --json`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		evalDir := args[0]
+		records, err := loadReplayRecords(evalDir, replayTasks)
+		if err != nil {
+			return err
+		}
+		if len(records) == 0 {
+			return fmt.Errorf("no %s files in %s; the run predates replay recording", replayRecordName, evalDir)
+		}
+
+		recorded := make(map[string]bool)
+		if data, err := os.ReadFile(filepath.Join(evalDir, "summary.json")); err == nil {
+			summary, err := parseEvalSummary(data)
+			if err != nil {
+				return fmt.Errorf("parsing summary.json: %w", err)
+			}
+			for _, r := range summary.Results {
+				recorded[r.Task] = r.Passed
+			}
+		}
+
+		outDir := replayOutput
+		if outDir == "" {
+			outDir = filepath.Join(evalDir, "replay-"+time.Now().Format("2006-01-02T150405"))
+		}
+
+		loader := task.NewLoader(tasks.FS, tasksDir)
+		allTasks, err := loader.LoadAll()
+		if err != nil {
+			return fmt.Errorf("loading tasks: %w", err)
+		}
+
+		r, err := newReplayRunner()
+		if err != nil {
+			return err
+		}
+		defer func() { _ = r.Close() }()
+
+		ctx := cmd.Context()
+		outcomes := make([]ReplayOutcome, 0, len(records))
+		for _, rec := range records {
+			t, err := task.ResolveRef(allTasks, rec.Task)
+			if err != nil {
+				outcomes = append(outcomes, ReplayOutcome{Task: rec.Task, Error: err.Error()})
+				continue
+			}
+			r.pin(t, rec)
+			if !replayJSON {
+				fmt.Printf(" ▶ %s\n", t.ID())
+			}
+			outcome := replayTask(ctx, r.Runner, loader, t, rec, outDir)
+			outcome.RecordedPassed = recorded[rec.Task]
+			outcome.Match = outcome.Error == "" && outcome.Passed == outcome.RecordedPassed
+			outcomes = append(outcomes, outcome)
+		}
+
+		mismatches := 0
+		for _, o := range outcomes {
+			if !o.Match {
+				mismatches++
+			}
+		}
+
+		if replayJSON {
+			enc := json.NewEncoder(os.Stdout)
+			enc.SetIndent("", "  ")
+			if err := enc.Encode(outcomes); err != nil {
+				return err
+			}
+		} else {
+			printReplayOutcomes(outcomes, outDir)
+		}
+		if mismatches > 0 {
+			return &exitError{code: 1}
+		}
+		return nil
+	},
+}
+
+func init() {
+	replayCmd.Flags().StringVar(&replayTasks, "tasks", "", "comma-separated task IDs to replay (default: all)")
+	replayCmd.Flags().StringVarP(&replayOutput, "output", "o", "", "directory for replay validation logs (default: <eval-dir>/replay-<timestamp>)")
+	replayCmd.Flags().BoolVar(&replayJSON, "json", false, "output outcomes as JSON")
+}
+
+// replayImageCache memoizes image digests across tasks of one eval.
+var replayImageCache = struct {
+	sync.Mutex
+	images map[string]BundleImage
+}{images: make(map[string]BundleImage)}
+
+// writeReplayRecord records the validation inputs for t. It must run after the
+// agent's files are in workspaceDir and before hidden tests are overlaid.
+func writeReplayRecord(ctx context.Context, loader *task.Loader, t *task.Task, prompt, workspaceDir, taskOutputDir string, validationCmd []string, validationTimeout int) error {
+	files, err := collectReplayFiles(workspaceDir)
+	if err != nil {
+		return err
+	}
+
+	rec := ReplayRecord{
+		Version:           replayFormatVersion,
+		Task:              t.ID(),
+		HarnessVersion:    Version,
+		Prompt:            prompt,
+		PromptHash:        hashBytes([]byte(prompt)),
+		ValidationCommand: validationCmd,
+		ValidationTimeout: validationTimeout,
+		Legacy:            evalLegacy,
+		Files:             files,
+	}
+	if data, err := os.ReadFile(filepath.Join(taskOutputDir, "agent.log")); err == nil {
+		rec.AgentLogHash = hashBytes(data)
+	}
+	if !evalLegacy {
+		rec.HiddenTestHashes = hiddenTestHashes(loader, t)
+	}
+	if cfg != nil {
+		ref := cfg.ImageForTask(t.ID(), string(t.Language))
+		if taskRef, err := runner.TaskImage(cfg, loader, t); err == nil {
+			ref = taskRef
+		}
+		img := resolveReplayImage(ctx, ref)
+		rec.Image, rec.ImageID, rec.ImageRepoDigests = img.Ref, img.ID, img.RepoDigests
+	}
+
+	data, err := json.MarshalIndent(rec, "", "  ")
+	if err != nil {
+		return fmt.Errorf("marshaling replay record: %w", err)
+	}
+	return os.WriteFile(filepath.Join(taskOutputDir, replayRecordName), data, 0o644)
+}
+
+// collectReplayFiles returns the workspace files validation will see, keyed by
+// slash-separated relative path. Harness artifacts and build output are skipped.
+func collectReplayFiles(workspaceDir string) (map[string][]byte, error) {
+	files := make(map[string][]byte)
+	err := filepath.WalkDir(workspaceDir, func(p string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		rel, err := filepath.Rel(workspaceDir, p)
+		if err != nil || rel == "." {
+			return err
+		}
+		rel = filepath.ToSlash(rel)
+		if !strings.Contains(rel, "/") && (evalOutputFiles[rel] || rel == replayRecordName) {
+			if d.IsDir() {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if d.IsDir() {
+			if replaySkipDirs[d.Name()] {
+				return filepath.SkipDir
+			}
+			return nil
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		if info, err := d.Info(); err != nil || info.Size() > replayMaxFileSize {
+			return nil
+		}
+		data, err := os.ReadFile(p)
+		if err != nil {
+			return err
+		}
+		files[rel] = data
+		return nil
+	})
+	if err != nil {
+		return nil, fmt.Errorf("collecting replay files: %w", err)
+	}
+	return files, nil
+}
+
+// hiddenTestHashes hashes the hidden test files of t as shipped by this harness.
+func hiddenTestHashes(loader *task.Loader, t *task.Task) map[string]string {
+	hidden := t.HiddenTestFiles()
+	if len(hidden) == 0 {
+		return nil
+	}
+	hashes := make(map[string]string, len(hidden))
+	for _, name := range hidden {
+		if data, err := loader.ReadTaskFile(t, name); err == nil {
+			hashes[name] = hashBytes(data)
+		}
+	}
+	return hashes
+}
+
+// resolveReplayImage returns ref with its local image ID and digests when
+// Docker can inspect it. Failures leave only the ref recorded.
+func resolveReplayImage(ctx context.Context, ref string) BundleImage {
+	replayImageCache.Lock()
+	defer replayImageCache.Unlock()
+	if img, ok := replayImageCache.images[ref]; ok {
+		return img
+	}
+
+	img := BundleImage{Ref: ref}
+	if docker, err := runner.NewDockerClient(cfg.Docker.ContainerBackend); err == nil {
+		inspectCtx, cancel := context.WithTimeout(ctx, 10*time.Second)
+		if id, digests, err := docker.ImageDigest(inspectCtx, ref); err == nil {
+			img.ID, img.RepoDigests = id, digests
+		}
+		cancel()
+		_ = docker.Close()
+	}
+	replayImageCache.images[ref] = img
+	return img
+}
+
+// replayRunner grades recorded tasks the way their records ran them.
+type replayRunner struct {
+	*runner.Runner
+	cfg config.Config // The runner's config; pin rewrites its task overrides
+}
+
+// newReplayRunner creates the runner replay and debug grade records with. It
+// pins images on a copy of the loaded config, which is left untouched, and
+// drops task overrides: each record names the image its task ran with.
+func newReplayRunner() (*replayRunner, error) {
+	rr := &replayRunner{cfg: *cfg}
+	rr.cfg.Tasks = nil
+	r, err := runner.NewRunner(&rr.cfg, tasks.FS, tasksDir, logger)
+	if err != nil {
+		return nil, err
+	}
+	rr.Runner = r
+	return rr, nil
+}
+
+// pin makes the runner grade t as rec ran it: in the image rec names, in
+// place of the one t's manifest or language would use, and with the hidden
+// test layout of the harness that recorded it.
+func (rr *replayRunner) pin(t *task.Task, rec *ReplayRecord) {
+	rr.cfg.Tasks = map[string]config.TaskOverride{t.ID(): {Image: pinnedReplayImage(rec)}}
+	rr.LegacyHiddenTests = rec.Legacy
+}
+
+// pinnedReplayImage prefers a registry digest over the mutable tag recorded.
+func pinnedReplayImage(rec *ReplayRecord) string {
+	if len(rec.ImageRepoDigests) > 0 {
+		return rec.ImageRepoDigests[0]
+	}
+	return rec.Image
+}
+
+// loadReplayRecords reads replay.json from each task directory of evalDir,
+// optionally limited to the comma-separated task IDs in only.
+func loadReplayRecords(evalDir, only string) ([]*ReplayRecord, error) {
+	want := make(map[string]bool)
+	for _, id := range strings.Split(only, ",") {
+		if id = strings.TrimSpace(id); id != "" {
+			want[id] = true
+		}
+	}
+
+	paths, err := filepath.Glob(filepath.Join(evalDir, "*", replayRecordName))
+	if err != nil {
+		return nil, err
+	}
+	sort.Strings(paths)
+
+	records := make([]*ReplayRecord, 0, len(paths))
+	for _, p := range paths {
+		data, err := os.ReadFile(p)
+		if err != nil {
+			return nil, fmt.Errorf("reading %s: %w", p, err)
+		}
+		var rec ReplayRecord
+		if err := json.Unmarshal(data, &rec); err != nil {
+			return nil, fmt.Errorf("parsing %s: %w", p, err)
+		}
+		if rec.Version > replayFormatVersion {
+			return nil, fmt.Errorf("%s has replay format %d; upgrade sanity to replay it", p, rec.Version)
+		}
+		if len(want) > 0 && !want[rec.Task] {
+			continue
+		}
+		records = append(records, &rec)
+	}
+	return records, nil
+}
+
+// replayTask rebuilds the recorded workspace for t and runs validation on it,
+// writing the validation log under outDir.
+func replayTask(ctx context.Context, r *runner.Runner, loader *task.Loader, t *task.Task, rec *ReplayRecord, outDir string) ReplayOutcome {
+	outcome := ReplayOutcome{Task: rec.Task}
+
+	workDir, err := os.MkdirTemp("", fmt.Sprintf("sanity-replay-%s-%s-*", t.Language, t.Slug))
+	if err != nil {
+		outcome.Error = fmt.Sprintf("creating temp workspace: %v", err)
+		return outcome
+	}
+	defer func() { _ = os.RemoveAll(workDir) }()
+
+	if err := writeRecordedFiles(workDir, rec.Files); err != nil {
+		outcome.Error = err.Error()
+		return outcome
+	}
+
+	if !rec.Legacy {
+		current := hiddenTestHashes(loader, t)
+		for name, hash := range rec.HiddenTestHashes {
+			if current[name] != hash {
+				outcome.TestsChanged = true
+			}
+		}
+		if err := writeTaskFilesToWorkspace(loader, t, workDir, t.HiddenTestFiles()); err != nil {
+			outcome.Error = fmt.Sprintf("writing hidden tests: %v", err)
+			return outcome
+		}
+	}
+
+	if rec.ImageID != "" {
+		if img := resolveReplayImage(ctx, pinnedReplayImage(rec)); img.ID != "" && img.ID != rec.ImageID {
+			outcome.ImageDrift = true
+		}
+	}
+
+	session, duration, err := runValidationSession(ctx, r, t, workDir, rec.ValidationTimeout, rec.ValidationCommand)
+	outcome.Duration = duration
+
+	logPath := filepath.Join(outDir, fmt.Sprintf("%s-%s", t.Language, t.Slug), "validation.log")
+	if mkErr := os.MkdirAll(filepath.Dir(logPath), 0o755); mkErr == nil {
+		if err != nil {
+			rawOutput, exitCode, d := validationErrorEvidence(session, duration)
+			writeValidationLog(logPath, rawOutput, rec.ValidationCommand, exitCode, d, false, err)
+		} else {
+			writeValidationSessionLog(logPath, rec.ValidationCommand, session)
+		}
+	}
+
+	if err != nil {
+		if isValidationInfraError(err) {
+			outcome.Error = err.Error()
+		}
+		return outcome
+	}
+	outcome.Passed = session.Passed()
+	return outcome
+}
+
+// writeRecordedFiles writes files recorded by collectReplayFiles into dir,
+// rejecting paths that would land outside it.
+func writeRecordedFiles(dir string, files map[string][]byte) error {
+	for rel, data := range files {
+		dest := filepath.Join(dir, filepath.FromSlash(rel))
+		if !strings.HasPrefix(dest, filepath.Clean(dir)+string(filepath.Separator)) {
+			return fmt.Errorf("recorded path escapes workspace: %s", rel)
+		}
+		if err := os.MkdirAll(filepath.Dir(dest), 0o755); err != nil {
+			return err
+		}
+		if err := os.WriteFile(dest, data, 0o644); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+func printReplayOutcomes(outcomes []ReplayOutcome, outDir string) {
+	fmt.Println()
+	fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+	fmt.Println(" SANITY HARNESS - REPLAY")
+	fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+	fmt.Println()
+
+	matched := 0
+	for _, o := range outcomes {
+		mark := "✓"
+		if o.Match {
+			matched++
+		} else {
+			mark = "✗"
+		}
+		var notes []string
+		if o.ImageDrift {
+			notes = append(notes, "image changed")
+		}
+		if o.TestsChanged {
+			notes = append(notes, "hidden tests changed")
+		}
+		if o.Error != "" {
+			notes = append(notes, o.Error)
+		}
+		fmt.Printf(" %s %-36s recorded=%-5s replayed=%-5s %s\n",
+			mark, o.Task, passLabel(o.RecordedPassed), passLabel(o.Passed), strings.Join(notes, "; "))
+	}
+
+	fmt.Println()
+	fmt.Println(" ─────────────────────────────────────────────────────────")
+	fmt.Printf(" Matched: %d/%d\n", matched, len(outcomes))
+	fmt.Printf(" Logs:    %s\n", outDir)
+	fmt.Println()
+}
+
+func passLabel(passed bool) string {
+	if passed {
+		return "pass"
+	}
+	return "fail"
+}
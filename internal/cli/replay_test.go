@@ -0,0 +1,87 @@
+package cli
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"slices"
+	"testing"
+)
+
+func TestCollectReplayFiles(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	write := func(rel, content string) {
+		t.Helper()
+		p := filepath.Join(dir, filepath.FromSlash(rel))
+		if err := os.MkdirAll(filepath.Dir(p), 0o755); err != nil {
+			t.Fatalf("mkdir: %v", err)
+		}
+		if err := os.WriteFile(p, []byte(content), 0o644); err != nil {
+			t.Fatalf("write: %v", err)
+		}
+	}
+	write("src/lib.rs", "pub fn f() {}")
+	write("Cargo.toml", "[package]")
+	write("agent.log", "transcript")
+	write("solution.diff", "diff")
+	write("replay.json", "{}")
+	write("target/debug/app", "binary")
+	write("integrity-files/lib.rs", "copy")
+
+	files, err := collectReplayFiles(dir)
+	if err != nil {
+		t.Fatalf("collectReplayFiles() error = %v", err)
+	}
+	got := make([]string, 0, len(files))
+	for rel := range files {
+		got = append(got, rel)
+	}
+	slices.Sort(got)
+	want := []string{"Cargo.toml", "src/lib.rs"}
+	if !slices.Equal(got, want) {
+		t.Fatalf("collectReplayFiles() = %v, want %v", got, want)
+	}
+	if string(files["src/lib.rs"]) != "pub fn f() {}" {
+		t.Fatalf("unexpected content: %q", files["src/lib.rs"])
+	}
+}
+
+func TestLoadReplayRecords(t *testing.T) {
+	t.Parallel()
+
+	evalDir := t.TempDir()
+	for _, rec := range []ReplayRecord{
+		{Version: replayFormatVersion, Task: "go/bank-account", Image: "go-img"},
+		{Version: replayFormatVersion, Task: "rust/parallel-letter-frequency", Image: "rust-img", ImageRepoDigests: []string{"rust-img@sha256:abc"}},
+	} {
+		dir := filepath.Join(evalDir, filepath.Base(rec.Task))
+		if err := os.MkdirAll(dir, 0o755); err != nil {
+			t.Fatalf("mkdir: %v", err)
+		}
+		data, _ := json.Marshal(rec)
+		if err := os.WriteFile(filepath.Join(dir, replayRecordName), data, 0o644); err != nil {
+			t.Fatalf("write: %v", err)
+		}
+	}
+
+	all, err := loadReplayRecords(evalDir, "")
+	if err != nil {
+		t.Fatalf("loadReplayRecords() error = %v", err)
+	}
+	if len(all) != 2 {
+		t.Fatalf("loaded %d records, want 2", len(all))
+	}
+
+	only, err := loadReplayRecords(evalDir, "rust/parallel-letter-frequency")
+	if err != nil {
+		t.Fatalf("loadReplayRecords() error = %v", err)
+	}
+	if len(only) != 1 || pinnedReplayImage(only[0]) != "rust-img@sha256:abc" {
+		t.Fatalf("unexpected filtered records: %+v", only)
+	}
+	if got := pinnedReplayImage(all[0]); got != "go-img" {
+		t.Fatalf("pinnedReplayImage() = %q, want go-img", got)
+	}
+}
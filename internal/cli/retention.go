@@ -0,0 +1,175 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// expiredArtifact is a file or directory past its retention.
+type expiredArtifact struct {
+	Path string
+	Kind string
+}
+
+// findExpiredArtifacts lists the artifacts past their retention at now: those
+// of every finished eval run below evalRoot, aged from the run's
+// summary.json, and the session directories in sessionDir. Either root may
+// be empty or missing.
+func findExpiredArtifacts(rc config.RetentionConfig, evalRoot, sessionDir string, now time.Time) ([]expiredArtifact, error) {
+	policies := rc.Policies()
+	var expired []expiredArtifact
+
+	if evalRoot != "" {
+		err := filepath.WalkDir(evalRoot, func(path string, d fs.DirEntry, err error) error {
+			if err != nil {
+				if errors.Is(err, fs.ErrNotExist) {
+					return nil
+				}
+				return err
+			}
+			if !d.IsDir() {
+				return nil
+			}
+			info, err := os.Stat(filepath.Join(path, "summary.json"))
+			if err != nil {
+				// Task directories of unfinished runs hold nothing to expire.
+				if path != evalRoot && isTaskOutputDirName(d.Name()) {
+					return filepath.SkipDir
+				}
+				return nil
+			}
+			runExpired, err := expiredRunArtifacts(path, policies, info.ModTime(), now)
+			if err != nil {
+				return err
+			}
+			expired = append(expired, runExpired...)
+			return filepath.SkipDir
+		})
+		if err != nil {
+			return nil, fmt.Errorf("scanning %s: %w", evalRoot, err)
+		}
+	}
+
+	if sessionDir != "" && !policies["sessions"].Forever {
+		sessions, err := expiredSessions(sessionDir, policies["sessions"], now)
+		if err != nil {
+			return nil, err
+		}
+		expired = append(expired, sessions...)
+	}
+
+	sort.Slice(expired, func(i, j int) bool { return expired[i].Path < expired[j].Path })
+	return expired, nil
+}
+
+// expiredSessions lists the session directories in sessionDir past policy,
+// aged from their last modification.
+func expiredSessions(sessionDir string, policy config.RetentionPolicy, now time.Time) ([]expiredArtifact, error) {
+	entries, err := os.ReadDir(sessionDir)
+	if err != nil && !errors.Is(err, fs.ErrNotExist) {
+		return nil, fmt.Errorf("reading %s: %w", sessionDir, err)
+	}
+	var expired []expiredArtifact
+	for _, e := range entries {
+		info, err := e.Info()
+		if err != nil || !e.IsDir() {
+			continue
+		}
+		if policy.Expired(info.ModTime(), now) {
+			expired = append(expired, expiredArtifact{Path: filepath.Join(sessionDir, e.Name()), Kind: "sessions"})
+		}
+	}
+	return expired, nil
+}
+
+// expiredRunArtifacts lists the expired artifacts in one run's task output
+// directories.
+func expiredRunArtifacts(runDir string, policies map[string]config.RetentionPolicy, finishedAt, now time.Time) ([]expiredArtifact, error) {
+	expiredKinds := make(map[string]bool)
+	for kind, p := range policies {
+		expiredKinds[kind] = p.Expired(finishedAt, now)
+	}
+	taskDirs, err := os.ReadDir(runDir)
+	if err != nil {
+		return nil, err
+	}
+	var expired []expiredArtifact
+	for _, td := range taskDirs {
+		if !td.IsDir() || !isTaskOutputDirName(td.Name()) {
+			continue
+		}
+		dir := filepath.Join(runDir, td.Name())
+		entries, err := os.ReadDir(dir)
+		if err != nil {
+			return nil, err
+		}
+		for _, e := range entries {
+			kind := artifactKind(e.Name())
+			if kind != "" && expiredKinds[kind] {
+				expired = append(expired, expiredArtifact{Path: filepath.Join(dir, e.Name()), Kind: kind})
+			}
+		}
+	}
+	return expired, nil
+}
+
+// artifactKind maps a task output directory entry to its retention kind, or
+// "" for artifacts that are always kept.
+func artifactKind(name string) string {
+	switch strings.TrimSuffix(name, encryptedSuffix) {
+	case "agent.log":
+		return "transcripts"
+	case judgeAuditName:
+		return "provider_logs"
+	case attemptSnapshotsDir:
+		return "snapshots"
+	}
+	if evalOutputFiles[name] {
+		return ""
+	}
+	return "workspaces"
+}
+
+// isTaskOutputDirName reports whether name looks like "<lang>-<slug>".
+func isTaskOutputDirName(name string) bool {
+	for _, lang := range task.AllLanguages {
+		if strings.HasPrefix(name, string(lang)+"-") {
+			return true
+		}
+	}
+	return false
+}
+
+// applyRetention removes artifacts past their retention from the eval runs
+// next to outputDir and from the session directory. It runs when an eval
+// finishes so long-running rigs need no external cleanup.
+func applyRetention(outputDir string) {
+	if cfg == nil || !cfg.Retention.Enabled() {
+		return
+	}
+	expired, err := findExpiredArtifacts(cfg.Retention, filepath.Dir(outputDir), cfg.Harness.SessionDir, time.Now())
+	if err != nil {
+		logger.Warn("failed to apply retention", "error", err)
+		return
+	}
+	removed := 0
+	for _, a := range expired {
+		if err := os.RemoveAll(a.Path); err != nil {
+			logger.Warn("failed to remove expired artifact", "path", a.Path, "error", err)
+			continue
+		}
+		removed++
+	}
+	if removed > 0 {
+		logger.Info("removed artifacts past retention", "count", removed)
+	}
+}
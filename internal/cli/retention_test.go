@@ -0,0 +1,71 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestFindExpiredArtifacts(t *testing.T) {
+	t.Parallel()
+
+	now := time.Now()
+	root := t.TempDir()
+	writeRun := func(name string, age time.Duration) string {
+		t.Helper()
+		taskDir := filepath.Join(root, name, "go-bank-account")
+		for _, p := range []string{"agent.log", "validation.log", "judge.json", "bank_account.go", "snapshots/attempt-1/bank_account.go"} {
+			path := filepath.Join(taskDir, filepath.FromSlash(p))
+			if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+				t.Fatal(err)
+			}
+			if err := os.WriteFile(path, []byte("x"), 0o644); err != nil {
+				t.Fatal(err)
+			}
+		}
+		summary := filepath.Join(root, name, "summary.json")
+		if err := os.WriteFile(summary, []byte("{}"), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.Chtimes(summary, now.Add(-age), now.Add(-age)); err != nil {
+			t.Fatal(err)
+		}
+		return taskDir
+	}
+	oldTask := writeRun("old-run", 10*24*time.Hour)
+	newTask := writeRun("new-run", time.Hour)
+	// Runs without summary.json are still in progress and left alone.
+	if err := os.MkdirAll(filepath.Join(root, "running", "go-react"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(root, "running", "go-react", "react.go"), []byte("x"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+
+	rc := config.RetentionConfig{Transcripts: "forever", Workspaces: "7d", Snapshots: "7d", ProviderLogs: "never"}
+	expired, err := findExpiredArtifacts(rc, root, filepath.Join(root, "no-sessions"), now)
+	if err != nil {
+		t.Fatalf("findExpiredArtifacts() error = %v", err)
+	}
+	got := make(map[string]string, len(expired))
+	for _, a := range expired {
+		got[a.Path] = a.Kind
+	}
+	want := map[string]string{
+		filepath.Join(oldTask, "bank_account.go"): "workspaces",
+		filepath.Join(oldTask, "snapshots"):       "snapshots",
+		filepath.Join(oldTask, "judge.json"):      "provider_logs",
+		filepath.Join(newTask, "judge.json"):      "provider_logs",
+	}
+	if len(got) != len(want) {
+		t.Fatalf("expired = %v, want %v", got, want)
+	}
+	for path, kind := range want {
+		if got[path] != kind {
+			t.Errorf("expired[%s] = %q, want %q", path, got[path], kind)
+		}
+	}
+}
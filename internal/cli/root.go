@@ -6,6 +6,7 @@ import (
 	"fmt"
 	"log/slog"
 	"os"
+	"strings"
 
 	"github.com/spf13/cobra"
 
@@ -13,11 +14,14 @@ import (
 )
 
 var (
-	cfgFile  string
-	tasksDir string
-	verbose  bool
-	cfg      *config.Config
-	logger   *slog.Logger
+	cfgFile          string
+	cfgOverrides     []string
+	tasksDir         string
+	verbose          bool
+	allowUnsandboxed bool
+	cfg              *config.Config
+	cfgOrigins       *config.Origins
+	logger           *slog.Logger
 )
 
 // rootCmd represents the base command.
@@ -36,8 +40,30 @@ Features:
   - File-based agent interface (works with any agent)
   - Error summarization per language`,
 	PersistentPreRunE: func(cmd *cobra.Command, args []string) error {
+		// Apply the output theme before anything is printed. Completion
+		// scripts and shell completion results are emitted verbatim, as is
+		// data written to stdout by export, config show, --json, and --csv.
+		if cmd.Name() != "completions" && !strings.HasPrefix(cmd.Name(), "__complete") {
+			style, err := resolveOutputStyle(outputTheme, noColor, isTerminal(rawStdout), os.Getenv)
+			if err != nil {
+				return err
+			}
+			if err := installOutputStyle(style, !rawOutputRequested(cmd)); err != nil {
+				return err
+			}
+		}
+
 		// Skip config loading for commands that don't need it
-		if cmd.Name() == "help" || cmd.Name() == "completion" || cmd.Name() == "version" {
+		if cmd.Name() == "help" || cmd.Name() == "completion" || cmd.Name() == "completions" || cmd.Name() == "version" || cmd.Name() == "self-update" {
+			return nil
+		}
+		// config validate and doctor load the config themselves, to report
+		// every problem.
+		if cmd == configValidateCmd || cmd == doctorCmd {
+			return nil
+		}
+		// The baseline agent runs in a task workspace, as eval's child.
+		if cmd == baselineAgentCmd {
 			return nil
 		}
 
@@ -52,10 +78,16 @@ Features:
 
 		// Load config
 		var err error
-		cfg, err = config.Load(cfgFile)
+		cfg, cfgOrigins, err = config.LoadLayered(config.LoadOptions{File: cfgFile, Overrides: cfgOverrides, Strict: evalStrictConfig})
 		if err != nil {
 			return fmt.Errorf("loading config: %w", err)
 		}
+		cfg.Docker.AllowUnsandboxed = allowUnsandboxed
+
+		// Scores depend on task and test fixes, so flag stale harnesses.
+		if cmd.Name() == "eval" || cmd.Name() == "batch" {
+			warnIfOutdated()
+		}
 
 		return nil
 	},
@@ -63,7 +95,10 @@ Features:
 
 // Execute runs the root command.
 func Execute() {
-	if err := rootCmd.Execute(); err != nil {
+	registerCompletions()
+	err := rootCmd.Execute()
+	restoreOutputStyle()
+	if err != nil {
 		// Check for exitError to get specific exit code
 		var exitErr *exitError
 		if errors.As(err, &exitErr) {
@@ -74,9 +109,13 @@ func Execute() {
 }
 
 func init() {
-	rootCmd.PersistentFlags().StringVar(&cfgFile, "config", "", "config file (default: ./sanity.toml)")
+	rootCmd.PersistentFlags().StringVar(&cfgFile, "config", "", "config file applied over the system, user, and project config files")
+	rootCmd.PersistentFlags().StringArrayVar(&cfgOverrides, "set", nil, "override a config value as key=value, e.g. harness.max_attempts=3 (repeatable)")
 	rootCmd.PersistentFlags().StringVar(&tasksDir, "tasks-dir", "", "external tasks directory (for development)")
 	rootCmd.PersistentFlags().BoolVarP(&verbose, "verbose", "v", false, "verbose output")
+	rootCmd.PersistentFlags().BoolVar(&noColor, "no-color", false, "disable colored output (also honors NO_COLOR)")
+	rootCmd.PersistentFlags().StringVar(&outputTheme, "theme", themeAuto, "output theme: auto, unicode, ascii, plain (auto uses ascii when not a terminal)")
+	rootCmd.PersistentFlags().BoolVar(&allowUnsandboxed, "i-understand-the-risks", false, "let container_backend = \"local\" run task commands on the host without a sandbox")
 
 	// Add subcommands
 	rootCmd.AddCommand(listCmd)
@@ -88,6 +127,27 @@ func init() {
 	rootCmd.AddCommand(versionCmd)
 	rootCmd.AddCommand(compareCmd)
 	rootCmd.AddCommand(batchCmd)
+	rootCmd.AddCommand(exportCmd)
+	rootCmd.AddCommand(submitBundleCmd)
+	rootCmd.AddCommand(verifyBundleCmd)
+	rootCmd.AddCommand(archiveCmd)
+	rootCmd.AddCommand(importCmd)
+	rootCmd.AddCommand(mergeCmd)
+	rootCmd.AddCommand(imagesCmd)
+	rootCmd.AddCommand(analyzeCmd)
+	rootCmd.AddCommand(calibrateCmd)
+	rootCmd.AddCommand(configCmd)
+	rootCmd.AddCommand(doctorCmd)
+	rootCmd.AddCommand(completionsCmd)
+	rootCmd.AddCommand(tasksCmd)
+	rootCmd.AddCommand(selfUpdateCmd)
+	rootCmd.AddCommand(inspectCmd)
+	rootCmd.AddCommand(replayCmd)
+	rootCmd.AddCommand(debugCmd)
+	rootCmd.AddCommand(shellCmd)
+	rootCmd.AddCommand(bisectCmd)
+	rootCmd.AddCommand(serveCmd)
+	rootCmd.AddCommand(baselineAgentCmd)
 }
 
 // Version information (set by build flags).
@@ -1,16 +1,24 @@
 package cli
 
 import (
+	"bytes"
+	"cmp"
 	"context"
 	"fmt"
 	"os"
 	"os/signal"
+	"path/filepath"
+	"slices"
+	"strings"
+	"sync"
 	"syscall"
+	"time"
 
 	"github.com/spf13/cobra"
 
 	"github.com/lemon07r/sanityharness/internal/result"
 	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
 	"github.com/lemon07r/sanityharness/tasks"
 )
 
@@ -20,11 +28,19 @@ var (
 	runTimeout     int
 	runOutput      string
 	runWorkspace   string
+	runAgent       string
+	runModel       string
+	runReasoning   string
+	runKeep        bool
+	runNoSandbox   bool
+	runJobs        int
+	runLang        string
+	runTier        string
 )
 
 var runCmd = &cobra.Command{
-	Use:   "run <task>",
-	Short: "Run evaluation for a task",
+	Use:   "run <task>...",
+	Short: "Run evaluation for one or more tasks",
 	Long: `Executes the validation tests for a task in an isolated Docker container.
 
 The workspace is created inside the session directory by default.
@@ -33,14 +49,29 @@ Use --workspace to specify an existing workspace or custom location.
 In watch mode (--watch), the harness monitors the workspace for file changes
 and automatically re-runs validation after each change.
 
+With --agent, runs a quick single-task eval instead: the agent solves the task
+once in a temporary workspace, the task is validated (including hidden tests),
+and the result is printed. No session or eval-results directory is created;
+use --keep to preserve the workspace and logs for inspection.
+
+Several tasks, given as arguments or selected with --lang and --tier, are
+validated in separate containers, up to --jobs at a time. Each task's output
+is printed as one block when it finishes, and the sessions are saved under a
+run-<timestamp> directory of the session directory.
+
 Examples:
   sanity run bank-account
   sanity run bank-account --watch
   sanity run bank-account --watch --max-attempts 10
-  sanity run bank-account -w ./my-workspace`,
-	Args: cobra.ExactArgs(1),
+  sanity run bank-account -w ./my-workspace
+  sanity run go/bank-account --agent opencode --model qwen3-coder
+  sanity run rust/regex-lite --agent codex --keep
+  sanity run bank-account react regex-lite --jobs 3
+  sanity run --tier core --jobs 4`,
 	RunE: func(cmd *cobra.Command, args []string) error {
-		taskRef := args[0]
+		if runJobs < 1 {
+			return fmt.Errorf("--jobs must be at least 1")
+		}
 
 		r, err := runner.NewRunner(cfg, tasks.FS, tasksDir, logger)
 		if err != nil {
@@ -48,7 +79,7 @@ Examples:
 		}
 		defer func() { _ = r.Close() }()
 
-		t, err := r.ResolveTaskRef(taskRef)
+		selected, err := runSelection(r, args)
 		if err != nil {
 			return err
 		}
@@ -71,6 +102,18 @@ Examples:
 			}
 		}()
 
+		if len(selected) > 1 {
+			if runAgent != "" || runWatch || runWorkspace != "" {
+				return fmt.Errorf("--agent, --watch, and --workspace take a single task; use sanity eval --parallel to run an agent on several")
+			}
+			return runTasks(ctx, r, selected)
+		}
+		t := selected[0]
+
+		if runAgent != "" {
+			return runAgentOnce(ctx, r, t)
+		}
+
 		// Run the task - workspace is created inside session by default
 		session, err := r.Run(ctx, runner.RunOptions{
 			Task:         t,
@@ -122,4 +165,213 @@ func init() {
 	runCmd.Flags().IntVar(&runTimeout, "timeout", 0, "timeout per attempt in seconds (default from config)")
 	runCmd.Flags().StringVar(&runOutput, "output", "", "session output directory (default from config)")
 	runCmd.Flags().StringVarP(&runWorkspace, "workspace", "w", "", "workspace directory (default: inside session)")
+	runCmd.Flags().StringVar(&runAgent, "agent", "", "run this agent once on the task instead of validating a workspace")
+	runCmd.Flags().StringVar(&runModel, "model", "", "model to use with --agent")
+	runCmd.Flags().StringVar(&runReasoning, "reasoning", "", "reasoning effort level with --agent")
+	runCmd.Flags().BoolVar(&runKeep, "keep", false, "keep the --agent workspace and logs instead of deleting them")
+	runCmd.Flags().BoolVar(&runNoSandbox, "no-sandbox", false, "disable bubblewrap sandbox for the --agent process")
+	runCmd.Flags().IntVarP(&runJobs, "jobs", "j", 1, "validate up to N tasks at a time, each in its own container")
+	runCmd.Flags().StringVar(&runLang, "lang", "", "run every task of this language (go, rust, typescript, kotlin, dart, zig)")
+	runCmd.Flags().StringVar(&runTier, "tier", "", "run every task of this tier (core, extended, all)")
+}
+
+// runSelection resolves the task arguments, or the --lang and --tier
+// selection when there are none.
+func runSelection(r *runner.Runner, args []string) ([]*task.Task, error) {
+	if len(args) > 0 {
+		if runLang != "" || runTier != "" {
+			return nil, fmt.Errorf("--lang and --tier select tasks only when no task is given")
+		}
+		selected := make([]*task.Task, 0, len(args))
+		for _, ref := range args {
+			t, err := r.ResolveTaskRef(ref)
+			if err != nil {
+				return nil, err
+			}
+			selected = append(selected, t)
+		}
+		return selected, nil
+	}
+	if runLang == "" && runTier == "" {
+		return nil, fmt.Errorf("specify a task, or select tasks with --lang or --tier")
+	}
+	selected, err := selectTasks("", runLang, runTier, "")
+	if err != nil {
+		return nil, err
+	}
+	if len(selected) == 0 {
+		return nil, fmt.Errorf("no tasks match the selection")
+	}
+	return selected, nil
+}
+
+// runTasks validates tasks in up to --jobs containers at a time. Each task's
+// attempt output is buffered and printed whole when it finishes, so
+// concurrent tasks never interleave, and every task saves its own session
+// under one run directory.
+func runTasks(ctx context.Context, r *runner.Runner, selected []*task.Task) error {
+	baseDir := cmp.Or(runOutput, cfg.Harness.SessionDir)
+	runDir := filepath.Join(baseDir, "run-"+time.Now().Format("2006-01-02T150405"))
+	jobs := min(runJobs, len(selected))
+
+	fmt.Println()
+	fmt.Printf(" Running %d task(s), %d at a time\n", len(selected), jobs)
+	fmt.Printf(" Sessions: %s\n\n", runDir)
+
+	type outcome struct {
+		t       *task.Task
+		session *result.Session
+		err     error
+	}
+	work := make(chan *task.Task)
+	outcomes := make(chan outcome)
+	var printMu sync.Mutex
+	var wg sync.WaitGroup
+	for range jobs {
+		wg.Add(1)
+		go func() {
+			defer wg.Done()
+			for t := range work {
+				var buf bytes.Buffer
+				session, err := r.Run(ctx, runner.RunOptions{
+					Task:        t,
+					MaxAttempts: runMaxAttempts,
+					Timeout:     runTimeout,
+					OutputDir:   runDir,
+					Output:      &buf,
+				})
+
+				printMu.Lock()
+				fmt.Printf("━━ %s ━━\n", t.ID())
+				fmt.Print(buf.String())
+				switch {
+				case session != nil:
+					fmt.Print(result.FormatFinalResult(session))
+				case err != nil && ctx.Err() == nil:
+					fmt.Printf(" ✗ %v\n\n", err)
+				}
+				printMu.Unlock()
+
+				outcomes <- outcome{t: t, session: session, err: err}
+			}
+		}()
+	}
+	go func() {
+		defer close(work)
+		for _, t := range selected {
+			select {
+			case work <- t:
+			case <-ctx.Done():
+				return
+			}
+		}
+	}()
+	go func() {
+		wg.Wait()
+		close(outcomes)
+	}()
+
+	var passed, finished int
+	var failed []string
+	for o := range outcomes {
+		finished++
+		if o.session != nil && o.session.Passed() {
+			passed++
+		} else {
+			failed = append(failed, o.t.ID())
+		}
+	}
+
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	fmt.Printf(" Passed %d of %d task(s)\n", passed, len(selected))
+	if len(failed) > 0 {
+		slices.Sort(failed)
+		fmt.Printf(" Failed: %s\n", strings.Join(failed, ", "))
+	}
+	if skipped := len(selected) - finished; skipped > 0 {
+		fmt.Printf(" Not run: %d (interrupted)\n", skipped)
+	}
+	fmt.Printf(" Sessions saved to: %s\n\n", runDir)
+
+	if passed < len(selected) {
+		return &exitError{code: 1}
+	}
+	return nil
+}
+
+// runAgentOnce runs a single agent attempt on t in a throwaway output
+// directory and prints the verified result.
+func runAgentOnce(ctx context.Context, r *runner.Runner, t *task.Task) error {
+	if runWatch || runWorkspace != "" {
+		return fmt.Errorf("--agent cannot be combined with --watch or --workspace")
+	}
+	if cfg.GetAgent(runAgent) == nil {
+		return fmt.Errorf("unknown agent: %s", runAgent)
+	}
+
+	timeout := runTimeout
+	if timeout <= 0 {
+		timeout = cfg.Harness.DefaultTimeout
+	}
+
+	outputDir, err := os.MkdirTemp("", "sanity-run-*")
+	if err != nil {
+		return fmt.Errorf("creating output directory: %w", err)
+	}
+	if !runKeep {
+		defer func() { _ = os.RemoveAll(outputDir) }()
+	}
+
+	// runTaskWithAgent reads the eval globals.
+	evalAgent, evalModel, evalReasoning = runAgent, runModel, runReasoning
+	evalTimeout = timeout
+	evalNoSandbox = runNoSandbox
+	evalSandboxActive = initSandbox()
+	evalSandboxDenylist = resolveSandboxDenylistPaths(cfg.Sandbox.ReadableDenylist, outputDir)
+	evalSandboxSharedRW = append([]string(nil), cfg.Sandbox.SharedReadWriteDirs...)
+	evalSandboxSharedRO = append([]string(nil), cfg.Sandbox.SharedReadOnlyDirs...)
+
+	fmt.Println()
+	fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+	fmt.Println(" SANITY HARNESS - Quick Run")
+	fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+	fmt.Printf(" Task:    %s\n", t.ID())
+	fmt.Printf(" Agent:   %s\n", runAgent)
+	if runModel != "" {
+		fmt.Printf(" Model:   %s\n", runModel)
+	}
+	fmt.Println()
+
+	res := runTaskWithAgent(ctx, r, t, runAgent, runModel, outputDir, timeout)
+	printQuickRunResult(res)
+
+	if runKeep {
+		fmt.Printf(" Artifacts kept in: %s\n\n", filepath.Join(outputDir, strings.Replace(res.Task, "/", "-", 1)))
+	}
+	if !res.Passed {
+		return &exitError{code: 1}
+	}
+	return nil
+}
+
+// printQuickRunResult prints the outcome of a single quick run.
+func printQuickRunResult(res EvalResult) {
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	if res.Passed {
+		fmt.Printf(" ✓ PASSED  %s\n", res.Task)
+	} else {
+		fmt.Printf(" ✗ %s  %s\n", strings.ToUpper(string(res.Status)), res.Task)
+	}
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	fmt.Printf(" Duration:   %.1fs (agent %.1fs, validation %.1fs)\n", res.Duration, res.AgentTime, res.ValidateTime)
+	if res.AgentTimedOut {
+		fmt.Println(" Agent:      timed out")
+	}
+	if res.FailureClass != "" && res.FailureClass != FailureClassNone {
+		fmt.Printf(" Failure:    %s\n", res.FailureClass)
+	}
+	if res.Error != "" {
+		fmt.Printf(" Error:      %s\n", res.Error)
+	}
+	fmt.Println()
 }
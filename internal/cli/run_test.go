@@ -0,0 +1,33 @@
+package cli
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestRunSelection(t *testing.T) {
+	prevLang, prevTier := runLang, runTier
+	t.Cleanup(func() { runLang, runTier = prevLang, prevTier })
+
+	runLang, runTier = "go", "core"
+	selected, err := runSelection(nil, nil)
+	if err != nil {
+		t.Fatalf("runSelection() error = %v", err)
+	}
+	if len(selected) == 0 {
+		t.Fatal("expected core Go tasks")
+	}
+	for _, tk := range selected {
+		if tk.Language != "go" || tk.Tier != "core" {
+			t.Errorf("selected %s (%s), want core Go tasks", tk.ID(), tk.Tier)
+		}
+	}
+
+	if _, err := runSelection(nil, []string{"bank-account"}); err == nil || !strings.Contains(err.Error(), "only when no task is given") {
+		t.Errorf("task with --lang: error = %v", err)
+	}
+	runLang, runTier = "", ""
+	if _, err := runSelection(nil, nil); err == nil || !strings.Contains(err.Error(), "specify a task") {
+		t.Errorf("no selection: error = %v", err)
+	}
+}
@@ -0,0 +1,152 @@
+package cli
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// samplingParams are the model sampling parameters of a run. A nil value
+// leaves the agent's own default in place.
+type samplingParams struct {
+	Temperature *float64 `json:"temperature,omitempty" toml:"temperature"`
+	TopP        *float64 `json:"top_p,omitempty"       toml:"top_p"`
+	MinP        *float64 `json:"min_p,omitempty"       toml:"min_p"`
+}
+
+// evalSamplingSet holds the sampling parameters of the current run. Like the
+// other eval globals it is set once per run, before any task starts.
+var evalSamplingSet samplingParams
+
+// field returns a pointer to the named parameter's value.
+func (p *samplingParams) field(name string) **float64 {
+	switch name {
+	case config.SamplingTemperature:
+		return &p.Temperature
+	case config.SamplingTopP:
+		return &p.TopP
+	default:
+		return &p.MinP
+	}
+}
+
+// pairs returns the set parameters as name and formatted value, in
+// config.SamplingParams order.
+func (p samplingParams) pairs() [][2]string {
+	var pairs [][2]string
+	for _, name := range config.SamplingParams {
+		if v := p.flag(name); v != "" {
+			pairs = append(pairs, [2]string{name, v})
+		}
+	}
+	return pairs
+}
+
+// flag returns the named parameter as an eval flag value, or "" if unset.
+func (p samplingParams) flag(name string) string {
+	if v := *p.field(name); v != nil {
+		return strconv.FormatFloat(*v, 'f', -1, 64)
+	}
+	return ""
+}
+
+// samplingFlagName returns the eval flag of the named parameter.
+func samplingFlagName(name string) string {
+	return "--" + strings.ReplaceAll(name, "_", "-")
+}
+
+// label formats the set parameters as "temperature=0.2, top_p=0.9".
+func (p samplingParams) label() string {
+	parts := make([]string, 0, 3)
+	for _, kv := range p.pairs() {
+		parts = append(parts, kv[0]+"="+kv[1])
+	}
+	return strings.Join(parts, ", ")
+}
+
+// orNil returns &p, or nil when no parameter is set, for omitempty fields.
+func (p samplingParams) orNil() *samplingParams {
+	if len(p.pairs()) == 0 {
+		return nil
+	}
+	return &p
+}
+
+// samplingFlagValues maps each parameter to its eval flag value.
+func samplingFlagValues(shared SharedConfig) map[string]string {
+	return map[string]string{
+		config.SamplingTemperature: shared.Temperature,
+		config.SamplingTopP:        shared.TopP,
+		config.SamplingMinP:        shared.MinP,
+	}
+}
+
+// resolveSampling returns the run's sampling parameters: the eval flags, then
+// the [sampling] defaults. Every parameter that is set must be one the agent
+// can take, so runs that claim a setting really used it.
+func resolveSampling(shared SharedConfig, agent string, agentCfg *config.AgentConfig) (samplingParams, error) {
+	var p samplingParams
+	flags := samplingFlagValues(shared)
+	for _, name := range config.SamplingParams {
+		source := samplingFlagName(name)
+		var value *float64
+		if raw := flags[name]; raw != "" {
+			v, err := strconv.ParseFloat(raw, 64)
+			if err != nil {
+				return samplingParams{}, fmt.Errorf("%s: %q is not a number", source, raw)
+			}
+			value = &v
+		} else if cfg != nil && cfg.Sampling.Value(name) != nil {
+			source = "sampling." + name
+			v := *cfg.Sampling.Value(name)
+			value = &v
+		}
+		if value == nil {
+			continue
+		}
+		if err := config.ValidateSamplingValue(name, *value); err != nil {
+			return samplingParams{}, fmt.Errorf("%s: %w", source, err)
+		}
+		if !samplingSupported(name, agent, agentCfg) {
+			return samplingParams{}, fmt.Errorf("%s: agent %q has no %s setting; set sampling_flags.%s in [agents.%s]", source, agent, name, name, agent)
+		}
+		*p.field(name) = value
+	}
+	return p, nil
+}
+
+// samplingSupported reports whether agent can be given the named parameter:
+// through the plugin task request, a sampling_flags entry, or its adapter.
+func samplingSupported(name, agent string, agentCfg *config.AgentConfig) bool {
+	if agentCfg != nil && (agentCfg.Protocol == config.AgentProtocolPlugin || agentCfg.SamplingFlags[name] != "") {
+		return true
+	}
+	return adapterFor(agent, agentCfg).samplingEnv[name] != ""
+}
+
+// samplingArgs returns the arguments and environment that pass p to a CLI
+// agent. A sampling_flags entry wins over the adapter's variable.
+func samplingArgs(agentCfg *config.AgentConfig, adapter agentAdapter, p samplingParams) ([]string, map[string]string) {
+	var args []string
+	var env map[string]string
+	for _, kv := range p.pairs() {
+		name, value := kv[0], kv[1]
+		if flag := agentCfg.SamplingFlags[name]; flag != "" {
+			if strings.Contains(flag, "{value}") {
+				args = append(args, strings.ReplaceAll(flag, "{value}", value))
+			} else {
+				args = append(args, flag, value)
+			}
+			continue
+		}
+		if key := adapter.samplingEnv[name]; key != "" {
+			if env == nil {
+				env = map[string]string{}
+			}
+			env[key] = value
+		}
+	}
+	return args, env
+}
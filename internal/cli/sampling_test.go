@@ -0,0 +1,67 @@
+package cli
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestResolveSampling(t *testing.T) {
+	c := config.Default
+	topP := 0.9
+	c.Sampling = config.SamplingConfig{TopP: &topP}
+	c.Agents = map[string]config.AgentConfig{
+		"local":  {Command: "local-agent", SamplingFlags: map[string]string{"temperature": "--temp={value}", "top_p": "--top-p"}},
+		"plugin": {Command: "plugin-agent", Protocol: config.AgentProtocolPlugin},
+	}
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+
+	p, err := resolveSampling(SharedConfig{Temperature: "0.2"}, "local", cfg.GetAgent("local"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got := p.label(); got != "temperature=0.2, top_p=0.9" {
+		t.Errorf("label() = %q", got)
+	}
+	args, env := samplingArgs(cfg.GetAgent("local"), agentAdapter{}, p)
+	if want := []string{"--temp=0.2", "--top-p", "0.9"}; !reflect.DeepEqual(args, want) || env != nil {
+		t.Errorf("samplingArgs() = %q, %v; want %q", args, env, want)
+	}
+
+	p, err = resolveSampling(SharedConfig{MinP: "0.05"}, "plugin", cfg.GetAgent("plugin"))
+	if err != nil || p.flag(config.SamplingMinP) != "0.05" || p.flag(config.SamplingTopP) != "0.9" {
+		t.Errorf("plugin = %+v, %v", p, err)
+	}
+
+	c.Sampling = config.SamplingConfig{}
+	p, err = resolveSampling(SharedConfig{Temperature: "0.7"}, "goose", cfg.GetAgent("goose"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, env = samplingArgs(cfg.GetAgent("goose"), agentAdapters["goose"], p); env["GOOSE_TEMPERATURE"] != "0.7" {
+		t.Errorf("goose env = %v", env)
+	}
+	if p, err = resolveSampling(SharedConfig{}, "claude", cfg.GetAgent("claude")); err != nil || p.orNil() != nil {
+		t.Errorf("unset = %+v, %v", p, err)
+	}
+
+	for _, tc := range []struct {
+		shared SharedConfig
+		agent  string
+		want   string
+	}{
+		{SharedConfig{Temperature: "warm"}, "local", "not a number"},
+		{SharedConfig{Temperature: "2.5"}, "local", "between 0 and 2"},
+		{SharedConfig{TopP: "1.5"}, "local", "between 0 and 1"},
+		{SharedConfig{MinP: "0.1"}, "local", "set sampling_flags.min_p in [agents.local]"},
+		{SharedConfig{TopP: "0.5"}, "claude", `agent "claude" has no top_p setting`},
+	} {
+		if _, err := resolveSampling(tc.shared, tc.agent, cfg.GetAgent(tc.agent)); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("resolveSampling(%+v, %q) error = %v, want it to mention %q", tc.shared, tc.agent, err, tc.want)
+		}
+	}
+}
@@ -0,0 +1,87 @@
+package cli
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// summaryMigrations upgrades summary.json files written by older harnesses so
+// compare, analyze, export, and resume can read them. Append a migration
+// whenever the summary format changes incompatibly.
+var summaryMigrations = result.Migrations{
+	migrateSummaryV0,
+}
+
+// summarySchemaVersion is the current summary.json schema version.
+var summarySchemaVersion = summaryMigrations.Current()
+
+// parseEvalSummary decodes summary.json, upgrading older formats. Integrity
+// checks (verify, bundles) must hash the file as written and not use this.
+func parseEvalSummary(data []byte) (*EvalSummary, error) {
+	var s EvalSummary
+	if err := summaryMigrations.Decode(data, &s); err != nil {
+		return nil, err
+	}
+	return &s, nil
+}
+
+// migrateSummaryV0 fills per-result fields that unversioned summaries could
+// lack: status, failure_class, language, and the telemetry confidence flags.
+func migrateSummaryV0(doc map[string]any) error {
+	results, _ := doc["results"].([]any)
+	for i, raw := range results {
+		r, ok := raw.(map[string]any)
+		if !ok {
+			return fmt.Errorf("results[%d] is not an object", i)
+		}
+		passed, _ := r["passed"].(bool)
+		timedOut, _ := r["agent_timed_out"].(bool)
+		errMsg, _ := r["error"].(string)
+		quotaExhausted, _ := r["quota_exhausted"].(bool)
+
+		if s, _ := r["status"].(string); s == "" {
+			r["status"] = string(task.DetermineStatus(passed, timedOut, errMsg))
+		}
+		if c, _ := r["failure_class"].(string); c == "" {
+			r["failure_class"] = string(inferFailureClass(errMsg, quotaExhausted))
+		}
+		if l, _ := r["language"].(string); l == "" {
+			id, _ := r["task"].(string)
+			if lang, _, ok := strings.Cut(id, "/"); ok {
+				r["language"] = lang
+			}
+		}
+		for count, confident := range map[string]string{
+			"self_test_commands":             "self_test_commands_confident",
+			"out_of_workspace_read_attempts": "out_of_workspace_read_attempts_confident",
+		} {
+			if _, ok := r[confident]; !ok {
+				n, _ := r[count].(float64)
+				r[confident] = n == 0
+			}
+		}
+	}
+	return nil
+}
+
+// inferFailureClass reconstructs a failure class from a result's error text
+// for results recorded before failure classes existed.
+func inferFailureClass(errMsg string, quotaExhausted bool) FailureClass {
+	switch {
+	case strings.Contains(errMsg, "modified task files"):
+		return FailureClassIntegrity
+	case strings.Contains(errMsg, "infra failure"):
+		return FailureClassInfra
+	case strings.Contains(strings.ToLower(errMsg), "timed out"):
+		return FailureClassValidationTimeout
+	case errMsg != "":
+		return FailureClassValidationError
+	case quotaExhausted:
+		return FailureClassQuotaExhausted
+	default:
+		return FailureClassNone
+	}
+}
@@ -0,0 +1,59 @@
+package cli
+
+import (
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestParseEvalSummaryMigratesUnversioned(t *testing.T) {
+	t.Parallel()
+
+	legacy := `{
+		"agent": "gemini",
+		"timestamp": "2025-11-01T120000",
+		"passed": 1, "failed": 2, "total": 3,
+		"results": [
+			{"task": "go/bank-account", "passed": true},
+			{"task": "rust/regex-lite", "passed": false, "error": "modified task files: src/lib.rs"},
+			{"task": "zig/small-vector", "passed": false, "self_test_commands": 2}
+		]
+	}`
+	s, err := parseEvalSummary([]byte(legacy))
+	if err != nil {
+		t.Fatalf("parseEvalSummary() error = %v", err)
+	}
+	if s.SchemaVersion != summarySchemaVersion {
+		t.Fatalf("SchemaVersion = %d, want %d", s.SchemaVersion, summarySchemaVersion)
+	}
+
+	pass, violation, fail := s.Results[0], s.Results[1], s.Results[2]
+	if pass.Status != task.StatusPass || pass.FailureClass != FailureClassNone || pass.Language != "go" {
+		t.Errorf("pass result = %+v", pass)
+	}
+	if violation.Status != task.StatusIntegrityViolation || violation.FailureClass != FailureClassIntegrity {
+		t.Errorf("violation result = %+v", violation)
+	}
+	if fail.Status != task.StatusFail || !pass.SelfTestCommandsConfident || fail.SelfTestCommandsConfident {
+		t.Errorf("fail result = %+v", fail)
+	}
+}
+
+func TestParseEvalSummaryKeepsRecordedFields(t *testing.T) {
+	t.Parallel()
+
+	current := `{"schema_version": 1, "agent": "codex", "results": [
+		{"task": "go/react", "language": "go", "passed": false, "status": "error", "failure_class": "infra"}
+	]}`
+	s, err := parseEvalSummary([]byte(current))
+	if err != nil {
+		t.Fatalf("parseEvalSummary() error = %v", err)
+	}
+	if r := s.Results[0]; r.Status != task.StatusError || r.FailureClass != FailureClassInfra {
+		t.Fatalf("recorded fields were rewritten: %+v", r)
+	}
+
+	if _, err := parseEvalSummary([]byte(`{"schema_version": 99}`)); err == nil {
+		t.Fatal("expected an error for a summary from a newer harness")
+	}
+}
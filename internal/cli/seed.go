@@ -0,0 +1,44 @@
+package cli
+
+import (
+	"encoding/binary"
+	"hash/fnv"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// shuffleTasks returns tasks ordered by a hash of the seed, runKey, and task
+// ID. The order depends only on those, so the same seed gives the same order
+// on every machine, and a subset of the tasks keeps its relative order.
+func shuffleTasks(tasks []*task.Task, seed uint64, runKey string) []*task.Task {
+	keys := make(map[*task.Task]uint64, len(tasks))
+	for _, t := range tasks {
+		h := fnv.New64a()
+		_ = binary.Write(h, binary.LittleEndian, seed)
+		_, _ = h.Write([]byte(runKey + "\x00" + t.ID()))
+		keys[t] = h.Sum64()
+	}
+	shuffled := append([]*task.Task(nil), tasks...)
+	sort.SliceStable(shuffled, func(i, j int) bool {
+		a, b := shuffled[i], shuffled[j]
+		if keys[a] != keys[b] {
+			return keys[a] < keys[b]
+		}
+		return a.ID() < b.ID()
+	})
+	return shuffled
+}
+
+// seededRunKey returns the shuffle key for a run under --seed. Unlike
+// variantRunKey it ignores the timestamped output directory, so runs started
+// at different times or on different machines match; only repeats, which
+// live in run-<n> directories, get keys of their own.
+func seededRunKey(outputDir string) string {
+	if base := filepath.Base(filepath.Clean(outputDir)); strings.HasPrefix(base, "run-") {
+		return base
+	}
+	return ""
+}
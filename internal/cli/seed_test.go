@@ -0,0 +1,58 @@
+package cli
+
+import (
+	"slices"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+func TestShuffleTasks(t *testing.T) {
+	t.Parallel()
+
+	allTasks, err := task.NewLoader(tasks.FS, "").LoadAll()
+	if err != nil {
+		t.Fatalf("LoadAll() error = %v", err)
+	}
+	got := shuffleTasks(allTasks, 7, "")
+	if len(got) != len(allTasks) {
+		t.Fatalf("shuffled %d tasks, want %d", len(got), len(allTasks))
+	}
+	if slices.Equal(taskIDsOf(got), taskIDsOf(allTasks)) {
+		t.Error("shuffle kept the catalog order")
+	}
+	if again := shuffleTasks(allTasks, 7, ""); !slices.Equal(taskIDsOf(got), taskIDsOf(again)) {
+		t.Error("the same seed gave a different order")
+	}
+	if other := shuffleTasks(allTasks, 8, ""); slices.Equal(taskIDsOf(got), taskIDsOf(other)) {
+		t.Error("another seed gave the same order")
+	}
+
+	// A resumed run shuffles only the remaining tasks; they keep their order.
+	remaining := allTasks[len(allTasks)/2:]
+	var want []string
+	for _, id := range taskIDsOf(got) {
+		if slices.Contains(taskIDsOf(remaining), id) {
+			want = append(want, id)
+		}
+	}
+	if resumed := taskIDsOf(shuffleTasks(remaining, 7, "")); !slices.Equal(resumed, want) {
+		t.Errorf("resumed order = %v, want %v", resumed, want)
+	}
+}
+
+func TestSeededRunKey(t *testing.T) {
+	t.Parallel()
+
+	cases := map[string]string{
+		"eval-results/2026-01-07T120000-claude":        "",
+		"eval-results/multi-2026-01-07T120000/x":       "",
+		"eval-results/multi-2026-01-07T120000/x/run-2": "run-2",
+	}
+	for dir, want := range cases {
+		if got := seededRunKey(dir); got != want {
+			t.Errorf("seededRunKey(%q) = %q, want %q", dir, got, want)
+		}
+	}
+}
@@ -0,0 +1,400 @@
+package cli
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"crypto/ed25519"
+	"crypto/sha256"
+	"encoding/base64"
+	"encoding/hex"
+	"encoding/json"
+	"encoding/pem"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"os"
+	"path/filepath"
+	"runtime"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/spf13/cobra"
+)
+
+// Release locations. Variables so tests can point them at a local server.
+var (
+	githubAPIBase  = "https://api.github.com"
+	releaseRepo    = "lemon07r/SanityHarness"
+	checksumsAsset = "checksums.txt"
+)
+
+// ReleasePublicKey is the base64 PKIX DER Ed25519 key that release checksums
+// are signed with (set by build flags). When set, self-update refuses unsigned
+// releases.
+var ReleasePublicKey = ""
+
+var (
+	selfUpdateCheck   bool
+	selfUpdateVersion string
+	selfUpdatePubKey  string
+)
+
+// updateCheckInterval is how often eval checks for a newer release.
+const updateCheckInterval = 24 * time.Hour
+
+// GitHubRelease is the subset of the GitHub releases API response we use.
+type GitHubRelease struct {
+	TagName    string        `json:"tag_name"`
+	HTMLURL    string        `json:"html_url"`
+	Prerelease bool          `json:"prerelease"`
+	Assets     []GitHubAsset `json:"assets"`
+}
+
+// GitHubAsset is a downloadable file attached to a release.
+type GitHubAsset struct {
+	Name string `json:"name"`
+	URL  string `json:"browser_download_url"`
+}
+
+var selfUpdateCmd = &cobra.Command{
+	Use:   "self-update",
+	Short: "Update sanity to the latest release",
+	Long: `Downloads the latest (or a specific) release from GitHub, verifies its
+SHA-256 checksum against the release's checksums.txt, and replaces the running
+binary.
+
+If the binary was built with a release signing key, or --pubkey is given, the
+checksums file's Ed25519 signature (checksums.txt.sig) must also verify.
+
+Task and test fixes between releases change scores, so eval warns when the
+installed harness is a minor version or more behind the latest release. Set
+SANITY_NO_UPDATE_CHECK=1 to disable that check.`,
+	Example: `  sanity self-update --check
+  sanity self-update
+  sanity self-update --version v1.7.0`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		ctx, cancel := context.WithTimeout(cmd.Context(), 5*time.Minute)
+		defer cancel()
+
+		release, err := fetchRelease(ctx, selfUpdateVersion)
+		if err != nil {
+			return err
+		}
+
+		fmt.Printf(" Installed: %s\n", Version)
+		fmt.Printf(" Release:   %s\n", release.TagName)
+		if selfUpdateVersion == "" && Version != "dev" && compareVersions(Version, release.TagName) >= 0 {
+			fmt.Println(" Already up to date.")
+			return nil
+		}
+		if selfUpdateCheck {
+			fmt.Printf(" Update available: %s\n", release.HTMLURL)
+			return nil
+		}
+
+		var trusted ed25519.PublicKey
+		switch {
+		case selfUpdatePubKey != "":
+			if trusted, err = loadEd25519PublicKey(selfUpdatePubKey); err != nil {
+				return err
+			}
+		case ReleasePublicKey != "":
+			if trusted, err = decodeReleasePublicKey(ReleasePublicKey); err != nil {
+				return fmt.Errorf("built-in release key: %w", err)
+			}
+		}
+
+		exe, err := os.Executable()
+		if err != nil {
+			return fmt.Errorf("locating current binary: %w", err)
+		}
+		if resolved, err := filepath.EvalSymlinks(exe); err == nil {
+			exe = resolved
+		}
+
+		if err := installRelease(ctx, release, exe, trusted); err != nil {
+			return err
+		}
+		fmt.Printf(" ✓ Updated %s to %s\n", exe, release.TagName)
+		return nil
+	},
+}
+
+func init() {
+	selfUpdateCmd.Flags().BoolVar(&selfUpdateCheck, "check", false, "only report whether an update is available")
+	selfUpdateCmd.Flags().StringVar(&selfUpdateVersion, "version", "", "install a specific release tag instead of the latest")
+	selfUpdateCmd.Flags().StringVar(&selfUpdatePubKey, "pubkey", "", "Ed25519 public key (PEM) to verify checksums.txt.sig against")
+}
+
+// releaseAssetName returns the release binary name for this platform.
+func releaseAssetName() string {
+	name := fmt.Sprintf("sanity-%s-%s", runtime.GOOS, runtime.GOARCH)
+	if runtime.GOOS == "windows" {
+		name += ".exe"
+	}
+	return name
+}
+
+// fetchRelease returns the latest stable release, or the release for tag if set.
+func fetchRelease(ctx context.Context, tag string) (*GitHubRelease, error) {
+	url := fmt.Sprintf("%s/repos/%s/releases/latest", githubAPIBase, releaseRepo)
+	if tag != "" {
+		url = fmt.Sprintf("%s/repos/%s/releases/tags/%s", githubAPIBase, releaseRepo, tag)
+	}
+	data, err := httpGet(ctx, url)
+	if err != nil {
+		return nil, fmt.Errorf("fetching release info: %w", err)
+	}
+	var release GitHubRelease
+	if err := json.Unmarshal(data, &release); err != nil {
+		return nil, fmt.Errorf("parsing release info: %w", err)
+	}
+	if release.TagName == "" {
+		return nil, fmt.Errorf("release info from %s has no tag", url)
+	}
+	return &release, nil
+}
+
+// installRelease downloads, verifies, and installs the platform binary from
+// release over exe.
+func installRelease(ctx context.Context, release *GitHubRelease, exe string, trusted ed25519.PublicKey) error {
+	assets := make(map[string]string, len(release.Assets))
+	for _, a := range release.Assets {
+		assets[a.Name] = a.URL
+	}
+	name := releaseAssetName()
+	binURL, ok := assets[name]
+	if !ok {
+		return fmt.Errorf("release %s has no binary for %s/%s", release.TagName, runtime.GOOS, runtime.GOARCH)
+	}
+	sumsURL, ok := assets[checksumsAsset]
+	if !ok {
+		return fmt.Errorf("release %s has no %s; refusing to install unverified binary", release.TagName, checksumsAsset)
+	}
+
+	sums, err := httpGet(ctx, sumsURL)
+	if err != nil {
+		return fmt.Errorf("downloading checksums: %w", err)
+	}
+	if trusted != nil {
+		sigURL, ok := assets[checksumsAsset+".sig"]
+		if !ok {
+			return fmt.Errorf("release %s is not signed", release.TagName)
+		}
+		sig, err := httpGet(ctx, sigURL)
+		if err != nil {
+			return fmt.Errorf("downloading signature: %w", err)
+		}
+		if err := verifyChecksumsSignature(sums, sig, trusted); err != nil {
+			return err
+		}
+	}
+
+	want, err := lookupChecksum(sums, name)
+	if err != nil {
+		return err
+	}
+	bin, err := httpGet(ctx, binURL)
+	if err != nil {
+		return fmt.Errorf("downloading %s: %w", name, err)
+	}
+	if got := sha256.Sum256(bin); hex.EncodeToString(got[:]) != want {
+		return fmt.Errorf("checksum mismatch for %s: got %x, want %s", name, got, want)
+	}
+
+	return replaceBinary(exe, bin)
+}
+
+// lookupChecksum finds name's SHA-256 in sha256sum-formatted output.
+func lookupChecksum(sums []byte, name string) (string, error) {
+	scanner := bufio.NewScanner(bytes.NewReader(sums))
+	for scanner.Scan() {
+		fields := strings.Fields(scanner.Text())
+		if len(fields) == 2 && strings.TrimPrefix(fields[1], "*") == name {
+			return strings.ToLower(fields[0]), nil
+		}
+	}
+	return "", fmt.Errorf("no checksum for %s in %s", name, checksumsAsset)
+}
+
+// verifyChecksumsSignature checks an Ed25519 signature over sums. The
+// signature may be raw (as written by openssl pkeyutl) or base64 encoded.
+func verifyChecksumsSignature(sums, sig []byte, trusted ed25519.PublicKey) error {
+	if len(sig) != ed25519.SignatureSize {
+		decoded, err := base64.StdEncoding.DecodeString(string(bytes.TrimSpace(sig)))
+		if err != nil {
+			return fmt.Errorf("decoding checksums signature: %w", err)
+		}
+		sig = decoded
+	}
+	if !ed25519.Verify(trusted, sums, sig) {
+		return errors.New("checksums signature verification failed")
+	}
+	return nil
+}
+
+// decodeReleasePublicKey decodes a base64 PKIX DER Ed25519 public key.
+func decodeReleasePublicKey(s string) (ed25519.PublicKey, error) {
+	der, err := base64.StdEncoding.DecodeString(strings.TrimSpace(s))
+	if err != nil {
+		return nil, fmt.Errorf("decoding: %w", err)
+	}
+	return parseEd25519PublicKey(pem.EncodeToMemory(&pem.Block{Type: "PUBLIC KEY", Bytes: der}))
+}
+
+// replaceBinary atomically replaces exe with data, keeping its permissions.
+func replaceBinary(exe string, data []byte) error {
+	info, err := os.Stat(exe)
+	if err != nil {
+		return fmt.Errorf("stat %s: %w", exe, err)
+	}
+	tmp, err := os.CreateTemp(filepath.Dir(exe), ".sanity-update-*")
+	if err != nil {
+		return fmt.Errorf("creating temp file next to %s (try running with write access to its directory): %w", exe, err)
+	}
+	tmpPath := tmp.Name()
+	defer func() { _ = os.Remove(tmpPath) }()
+
+	if _, err := tmp.Write(data); err != nil {
+		_ = tmp.Close()
+		return fmt.Errorf("writing update: %w", err)
+	}
+	if err := tmp.Close(); err != nil {
+		return fmt.Errorf("writing update: %w", err)
+	}
+	if err := os.Chmod(tmpPath, info.Mode().Perm()); err != nil {
+		return fmt.Errorf("setting permissions: %w", err)
+	}
+
+	// Windows cannot overwrite a running executable, but it can rename it.
+	if runtime.GOOS == "windows" {
+		old := exe + ".old"
+		_ = os.Remove(old)
+		if err := os.Rename(exe, old); err != nil {
+			return fmt.Errorf("moving old binary aside: %w", err)
+		}
+	}
+	if err := os.Rename(tmpPath, exe); err != nil {
+		return fmt.Errorf("replacing binary: %w", err)
+	}
+	return nil
+}
+
+// httpGet fetches url and returns the body, failing on non-2xx responses.
+func httpGet(ctx context.Context, url string) ([]byte, error) {
+	req, err := http.NewRequestWithContext(ctx, http.MethodGet, url, nil)
+	if err != nil {
+		return nil, err
+	}
+	req.Header.Set("User-Agent", "sanity/"+Version)
+	resp, err := http.DefaultClient.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	defer func() { _ = resp.Body.Close() }()
+	if resp.StatusCode < 200 || resp.StatusCode > 299 {
+		return nil, fmt.Errorf("GET %s: %s", url, resp.Status)
+	}
+	return io.ReadAll(resp.Body)
+}
+
+// parseVersion parses "v1.7.0" or "1.7.0-beta.1" into major, minor, patch.
+func parseVersion(v string) ([3]int, bool) {
+	var out [3]int
+	v = strings.TrimPrefix(strings.TrimSpace(v), "v")
+	if i := strings.IndexAny(v, "-+"); i >= 0 {
+		v = v[:i]
+	}
+	parts := strings.Split(v, ".")
+	if len(parts) == 0 || len(parts) > 3 {
+		return out, false
+	}
+	for i, p := range parts {
+		n, err := strconv.Atoi(p)
+		if err != nil {
+			return out, false
+		}
+		out[i] = n
+	}
+	return out, true
+}
+
+// compareVersions returns -1, 0, or 1 as a is older than, equal to, or newer
+// than b. Unparseable versions compare equal.
+func compareVersions(a, b string) int {
+	va, okA := parseVersion(a)
+	vb, okB := parseVersion(b)
+	if !okA || !okB {
+		return 0
+	}
+	for i := range va {
+		switch {
+		case va[i] < vb[i]:
+			return -1
+		case va[i] > vb[i]:
+			return 1
+		}
+	}
+	return 0
+}
+
+// significantlyBehind reports whether installed trails latest by at least one
+// minor version.
+func significantlyBehind(installed, latest string) bool {
+	vi, okI := parseVersion(installed)
+	vl, okL := parseVersion(latest)
+	if !okI || !okL {
+		return false
+	}
+	return vl[0] > vi[0] || (vl[0] == vi[0] && vl[1] > vi[1])
+}
+
+// updateCheckState caches the last release check.
+type updateCheckState struct {
+	CheckedAt time.Time `json:"checked_at"`
+	Latest    string    `json:"latest"`
+}
+
+// warnIfOutdated prints a warning when a newer minor release exists. It checks
+// GitHub at most once per updateCheckInterval and never fails the command.
+func warnIfOutdated() {
+	if Version == "dev" || os.Getenv("SANITY_NO_UPDATE_CHECK") != "" {
+		return
+	}
+	if _, ok := parseVersion(Version); !ok {
+		return
+	}
+
+	var state updateCheckState
+	statePath := ""
+	if dir, err := os.UserCacheDir(); err == nil {
+		statePath = filepath.Join(dir, "sanity", "update-check.json")
+		if data, err := os.ReadFile(statePath); err == nil {
+			_ = json.Unmarshal(data, &state)
+		}
+	}
+
+	if time.Since(state.CheckedAt) > updateCheckInterval {
+		ctx, cancel := context.WithTimeout(context.Background(), 2*time.Second)
+		defer cancel()
+		release, err := fetchRelease(ctx, "")
+		if err != nil {
+			return
+		}
+		state = updateCheckState{CheckedAt: time.Now(), Latest: release.TagName}
+		if statePath != "" {
+			_ = os.MkdirAll(filepath.Dir(statePath), 0o755)
+			if data, err := json.Marshal(state); err == nil {
+				_ = os.WriteFile(statePath, data, 0o644)
+			}
+		}
+	}
+
+	if significantlyBehind(Version, state.Latest) {
+		fmt.Fprintf(os.Stderr, "\033[33m⚠ sanity %s is behind the latest release %s; task and test fixes may change scores. Run 'sanity self-update'.\033[0m\n",
+			Version, state.Latest)
+	}
+}
@@ -0,0 +1,147 @@
+package cli
+
+import (
+	"context"
+	"crypto/ed25519"
+	"crypto/rand"
+	"crypto/sha256"
+	"encoding/base64"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestCompareVersions(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		a, b string
+		want int
+	}{
+		{"v1.6.0", "v1.6.0", 0},
+		{"v1.6.0", "v1.7.0", -1},
+		{"1.10.0", "v1.9.3", 1},
+		{"v1.6.0-beta.1", "v1.6.0", 0},
+		{"v2.0", "v1.99.99", 1},
+		{"dev", "v1.0.0", 0},
+	}
+	for _, tt := range tests {
+		if got := compareVersions(tt.a, tt.b); got != tt.want {
+			t.Errorf("compareVersions(%q, %q) = %d, want %d", tt.a, tt.b, got, tt.want)
+		}
+	}
+}
+
+func TestSignificantlyBehind(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		installed, latest string
+		want              bool
+	}{
+		{"v1.6.0", "v1.6.3", false},
+		{"v1.6.3", "v1.7.0", true},
+		{"v1.9.0", "v2.0.0", true},
+		{"v1.7.0", "v1.6.0", false},
+		{"dev", "v1.7.0", false},
+		{"v1.6.0", "", false},
+	}
+	for _, tt := range tests {
+		if got := significantlyBehind(tt.installed, tt.latest); got != tt.want {
+			t.Errorf("significantlyBehind(%q, %q) = %v, want %v", tt.installed, tt.latest, got, tt.want)
+		}
+	}
+}
+
+func TestLookupChecksum(t *testing.T) {
+	t.Parallel()
+
+	sums := []byte("ABC123  sanity-linux-amd64\ndef456 *sanity-windows-amd64.exe\n")
+	if got, err := lookupChecksum(sums, "sanity-linux-amd64"); err != nil || got != "abc123" {
+		t.Errorf("lookupChecksum(linux) = %q, %v", got, err)
+	}
+	if got, err := lookupChecksum(sums, "sanity-windows-amd64.exe"); err != nil || got != "def456" {
+		t.Errorf("lookupChecksum(windows) = %q, %v", got, err)
+	}
+	if _, err := lookupChecksum(sums, "sanity-darwin-arm64"); err == nil {
+		t.Error("lookupChecksum(missing) error = nil")
+	}
+}
+
+func TestVerifyChecksumsSignature(t *testing.T) {
+	t.Parallel()
+
+	pub, priv, err := ed25519.GenerateKey(rand.Reader)
+	if err != nil {
+		t.Fatalf("generate key: %v", err)
+	}
+	sums := []byte("abc  sanity-linux-amd64\n")
+	sig := ed25519.Sign(priv, sums)
+
+	if err := verifyChecksumsSignature(sums, sig, pub); err != nil {
+		t.Errorf("raw signature: %v", err)
+	}
+	if err := verifyChecksumsSignature(sums, []byte(base64.StdEncoding.EncodeToString(sig)+"\n"), pub); err != nil {
+		t.Errorf("base64 signature: %v", err)
+	}
+	if err := verifyChecksumsSignature([]byte("tampered"), sig, pub); err == nil {
+		t.Error("tampered checksums verified")
+	}
+}
+
+func TestInstallRelease(t *testing.T) {
+	t.Parallel()
+
+	bin := []byte("new sanity binary")
+	sum := sha256.Sum256(bin)
+	name := releaseAssetName()
+	files := map[string][]byte{
+		"/" + name:           bin,
+		"/" + checksumsAsset: []byte(fmt.Sprintf("%x  %s\n", sum, name)),
+		"/bad/" + name:       []byte("corrupted"),
+	}
+	srv := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		data, ok := files[r.URL.Path]
+		if !ok {
+			http.NotFound(w, r)
+			return
+		}
+		_, _ = w.Write(data)
+	}))
+	defer srv.Close()
+
+	release := &GitHubRelease{
+		TagName: "v9.9.9",
+		Assets: []GitHubAsset{
+			{Name: name, URL: srv.URL + "/" + name},
+			{Name: checksumsAsset, URL: srv.URL + "/" + checksumsAsset},
+		},
+	}
+
+	exe := filepath.Join(t.TempDir(), "sanity")
+	if err := os.WriteFile(exe, []byte("old"), 0o755); err != nil {
+		t.Fatalf("write exe: %v", err)
+	}
+	if err := installRelease(context.Background(), release, exe, nil); err != nil {
+		t.Fatalf("installRelease() error = %v", err)
+	}
+	got, err := os.ReadFile(exe)
+	if err != nil || string(got) != string(bin) {
+		t.Fatalf("installed binary = %q, %v", got, err)
+	}
+
+	// A release key requires a signature, which this release lacks.
+	pub, _, _ := ed25519.GenerateKey(rand.Reader)
+	if err := installRelease(context.Background(), release, exe, pub); err == nil {
+		t.Error("installRelease() with key accepted unsigned release")
+	}
+
+	// A corrupted binary fails the checksum.
+	release.Assets[0].URL = srv.URL + "/bad/" + name
+	if err := installRelease(context.Background(), release, exe, nil); err == nil {
+		t.Error("installRelease() accepted checksum mismatch")
+	}
+}
@@ -0,0 +1,688 @@
+package cli
+
+import (
+	"context"
+	"crypto/rand"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"net"
+	"net/http"
+	"os"
+	"os/exec"
+	"os/signal"
+	"path/filepath"
+	"runtime"
+	"slices"
+	"strconv"
+	"strings"
+	"sync"
+	"syscall"
+	"time"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// serveLogName is the child eval's console output inside a run's output dir.
+const serveLogName = "serve.log"
+
+// Run states reported by the API, matching sanity.v1.RunState.
+const (
+	apiRunQueued    = "queued"
+	apiRunRunning   = "running"
+	apiRunSucceeded = "succeeded"
+	apiRunFailed    = "failed"
+	apiRunCanceled  = "canceled"
+)
+
+var (
+	serveAPI        bool
+	serveAddr       string
+	serveToken      string
+	serveResultsDir string
+	serveMaxRuns    int
+	serveUsersFile  string
+)
+
+var serveCmd = &cobra.Command{
+	Use:   "serve",
+	Short: "Serve the harness over HTTP for CI systems and dashboards",
+	Long: `Starts an HTTP server that exposes the harness to remote callers.
+
+With --api, a JSON REST API is served under /api/v1:
+
+  GET  /api/v1/tasks                 list tasks (?language=&tier=&difficulty=&tag=)
+  POST /api/v1/runs                  start an eval run
+  GET  /api/v1/runs                  list runs started by this server
+  GET  /api/v1/runs/{id}             run status
+  GET  /api/v1/runs/{id}/results     summary.json once the run has finished
+  GET  /api/v1/runs/{id}/log         console output of the run
+  POST /api/v1/runs/{id}/cancel      interrupt a run, keeping completed results
+  GET  /api/v1/sessions              list eval results under --results-dir
+  GET  /api/v1/sessions/{path...}    summary.json of a historical eval
+  POST /api/v1/submissions           record a run's submission.json
+  GET  /api/v1/submissions           list recorded results (?user=)
+  GET  /api/v1/leaderboard           best result per agent, model, and reasoning
+  GET  /api/v1/whoami                the authenticated user
+
+Every /api/v1 request needs "Authorization: Bearer <token>". The token comes
+from --token or SANITY_API_TOKEN; if neither is set, a random token is
+generated and printed at startup. GET /healthz is unauthenticated.
+
+With --users, the server runs in team mode: each user has their own token
+(see "sanity serve add-user"), runs and submissions are recorded under the
+user who made them, and only a run's owner or an admin may cancel it.
+
+Finished runs and uploaded submissions are appended to
+<results-dir>/team-results.jsonl, which is never rewritten; the leaderboard is
+computed from it.
+
+Each run executes as a separate "sanity eval" process writing to
+<results-dir>/<run id>, so results are identical to CLI runs.`,
+	Example: `  sanity serve --api
+  SANITY_API_TOKEN=secret sanity serve --api --addr 0.0.0.0:8080 --max-runs 2
+  curl -H "Authorization: Bearer secret" -d '{"agent":"gemini","tasks":["go/bank-account"]}' localhost:8080/api/v1/runs
+  sanity serve --api --users team.toml --addr 0.0.0.0:8080
+  curl -H "Authorization: Bearer $TOKEN" --data-binary @eval-results/run/submission.json localhost:8080/api/v1/submissions`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if !serveAPI {
+			return errors.New("only the REST API is available; pass --api")
+		}
+		if serveMaxRuns < 1 {
+			return errors.New("--max-runs must be at least 1")
+		}
+		if serveUsersFile != "" && serveToken != "" {
+			return errors.New("--token cannot be combined with --users; each user has their own token")
+		}
+		token := serveToken
+		if token == "" {
+			token = os.Getenv("SANITY_API_TOKEN")
+		}
+		generated := token == "" && serveUsersFile == ""
+		if generated {
+			buf := make([]byte, 24)
+			if _, err := rand.Read(buf); err != nil {
+				return fmt.Errorf("generating API token: %w", err)
+			}
+			token = hex.EncodeToString(buf)
+		}
+		exe, err := os.Executable()
+		if err != nil {
+			return fmt.Errorf("locating sanity binary: %w", err)
+		}
+
+		srv := newAPIServer(token, serveResultsDir, exe, serveMaxRuns)
+		if err := srv.loadTeam(serveUsersFile); err != nil {
+			return err
+		}
+		httpServer := &http.Server{
+			Addr:              serveAddr,
+			Handler:           srv.routes(),
+			ReadHeaderTimeout: 10 * time.Second,
+		}
+
+		ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt, syscall.SIGTERM)
+		defer stop()
+
+		ln, err := net.Listen("tcp", serveAddr)
+		if err != nil {
+			return fmt.Errorf("listening on %s: %w", serveAddr, err)
+		}
+		errCh := make(chan error, 1)
+		go func() { errCh <- httpServer.Serve(ln) }()
+
+		fmt.Println()
+		fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+		fmt.Println(" SANITY HARNESS - API Server")
+		fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+		fmt.Println()
+		fmt.Printf(" Listening:  http://%s/api/v1\n", ln.Addr())
+		fmt.Printf(" Results:    %s\n", serveResultsDir)
+		fmt.Printf(" Max runs:   %d concurrent\n", serveMaxRuns)
+		if srv.users != nil {
+			fmt.Printf(" Team:       %d users from %s\n", len(srv.users), serveUsersFile)
+		}
+		if generated {
+			fmt.Printf(" Token:      %s\n", token)
+		}
+		fmt.Println()
+
+		select {
+		case err := <-errCh:
+			if !errors.Is(err, http.ErrServerClosed) {
+				return fmt.Errorf("serving API: %w", err)
+			}
+			return nil
+		case <-ctx.Done():
+		}
+
+		fmt.Println(" Shutting down; interrupting active runs...")
+		shutdownCtx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+		defer cancel()
+		_ = httpServer.Shutdown(shutdownCtx)
+		srv.cancelAll()
+		srv.wg.Wait()
+		return nil
+	},
+}
+
+func init() {
+	serveCmd.Flags().BoolVar(&serveAPI, "api", false, "serve the JSON REST API")
+	serveCmd.Flags().StringVar(&serveAddr, "addr", "127.0.0.1:8080", "address to listen on")
+	serveCmd.Flags().StringVar(&serveToken, "token", "", "bearer token required by API clients (default: $SANITY_API_TOKEN or generated)")
+	serveCmd.Flags().StringVar(&serveResultsDir, "results-dir", defaultEvalResultsDir, "directory for run output and historical sessions")
+	serveCmd.Flags().IntVar(&serveMaxRuns, "max-runs", 1, "maximum runs executing at once; further runs are queued")
+	serveCmd.Flags().StringVar(&serveUsersFile, "users", "", "team users file; enables per-user tokens (see add-user)")
+	serveCmd.AddCommand(serveAddUserCmd)
+}
+
+// APIRunSpec is the body of POST /api/v1/runs. Fields mirror sanity eval flags.
+type APIRunSpec struct {
+	Agent          string   `json:"agent"`
+	Model          string   `json:"model,omitempty"`
+	Reasoning      string   `json:"reasoning,omitempty"`
+	Tasks          []string `json:"tasks,omitempty"`
+	Lang           string   `json:"lang,omitempty"`
+	Tier           string   `json:"tier,omitempty"`
+	Difficulty     string   `json:"difficulty,omitempty"`
+	Timeout        int      `json:"timeout,omitempty"`
+	Parallel       int      `json:"parallel,omitempty"`
+	KeepWorkspaces bool     `json:"keep_workspaces,omitempty"`
+	UseMCPTools    bool     `json:"use_mcp_tools,omitempty"`
+	UseSkills      bool     `json:"use_skills,omitempty"`
+	SkillPacks     []string `json:"skill_packs,omitempty"`
+	DisableMCP     bool     `json:"disable_mcp,omitempty"`
+	MCPServers     string   `json:"mcp_servers,omitempty"`
+	NoSandbox      bool     `json:"no_sandbox,omitempty"`
+	Legacy         bool     `json:"legacy,omitempty"`
+	samplingParams
+}
+
+// validate checks the spec before a run is queued.
+func (s APIRunSpec) validate() error {
+	if s.Agent == "" {
+		return errors.New("agent is required")
+	}
+	if cfg != nil && cfg.GetAgent(s.Agent) == nil {
+		return fmt.Errorf("unknown agent: %s (available: %s)", s.Agent, strings.Join(cfg.ListAgents(), ", "))
+	}
+	if s.Timeout < 0 || s.Parallel < 0 {
+		return errors.New("timeout and parallel must not be negative")
+	}
+	return nil
+}
+
+// evalArgs returns the sanity command line that executes the spec.
+func (s APIRunSpec) evalArgs(outputDir string) []string {
+	var args []string
+	if cfgFile != "" {
+		args = append(args, "--config", cfgFile)
+	}
+	if tasksDir != "" {
+		args = append(args, "--tasks-dir", tasksDir)
+	}
+	args = append(args, "--theme", themePlain, "eval", "--agent", s.Agent, "--output", outputDir, "--no-progress")
+	for _, f := range []struct{ flag, value string }{
+		{"--model", s.Model},
+		{"--reasoning", s.Reasoning},
+		{"--tasks", strings.Join(s.Tasks, ",")},
+		{"--lang", s.Lang},
+		{"--tier", s.Tier},
+		{"--difficulty", s.Difficulty},
+		{"--mcp-servers", s.MCPServers},
+	} {
+		if f.value != "" {
+			args = append(args, f.flag, f.value)
+		}
+	}
+	for _, kv := range s.pairs() {
+		args = append(args, samplingFlagName(kv[0]), kv[1])
+	}
+	if s.Timeout > 0 {
+		args = append(args, "--timeout", strconv.Itoa(s.Timeout))
+	}
+	if s.Parallel > 1 {
+		args = append(args, "--parallel", strconv.Itoa(s.Parallel))
+	}
+	for _, pack := range s.SkillPacks {
+		args = append(args, "--skill-pack", pack)
+	}
+	for _, f := range []struct {
+		flag string
+		on   bool
+	}{
+		{"--keep-workspaces", s.KeepWorkspaces},
+		{"--use-mcp-tools", s.UseMCPTools},
+		{"--use-skills", s.UseSkills},
+		{"--disable-mcp", s.DisableMCP},
+		{"--no-sandbox", s.NoSandbox},
+		{"--legacy", s.Legacy},
+	} {
+		if f.on {
+			args = append(args, f.flag)
+		}
+	}
+	return args
+}
+
+// APIRun is the status of a run started through the API.
+type APIRun struct {
+	ID         string       `json:"id"`
+	User       string       `json:"user"`
+	Spec       APIRunSpec   `json:"spec"`
+	State      string       `json:"state"`
+	CreatedAt  time.Time    `json:"created_at"`
+	StartedAt  *time.Time   `json:"started_at,omitempty"`
+	FinishedAt *time.Time   `json:"finished_at,omitempty"`
+	OutputDir  string       `json:"output_dir"`
+	Error      string       `json:"error,omitempty"`
+	Summary    *EvalSummary `json:"summary,omitempty"`
+
+	cancel func()
+	done   chan struct{}
+}
+
+// APISession is one historical eval listed by GET /api/v1/sessions.
+type APISession struct {
+	Path             string  `json:"path"`
+	Agent            string  `json:"agent"`
+	Model            string  `json:"model,omitempty"`
+	Timestamp        string  `json:"timestamp"`
+	Passed           int     `json:"passed"`
+	Total            int     `json:"total"`
+	PassRate         float64 `json:"pass_rate"`
+	WeightedPassRate float64 `json:"weighted_pass_rate,omitempty"`
+}
+
+// apiTask is a task as returned by GET /api/v1/tasks.
+type apiTask struct {
+	ID string `json:"id"`
+	*task.Task
+	Weight float64 `json:"weight"`
+}
+
+// apiServer implements the REST API.
+type apiServer struct {
+	token      string
+	users      []teamUser // Team mode when set; token is unused
+	resultsDir string
+	exe        string
+	slots      chan struct{}
+	store      *teamStore
+
+	mu    sync.Mutex
+	runs  map[string]*APIRun
+	order []string
+	wg    sync.WaitGroup
+}
+
+func newAPIServer(token, resultsDir, exe string, maxRuns int) *apiServer {
+	return &apiServer{
+		token:      token,
+		resultsDir: resultsDir,
+		exe:        exe,
+		slots:      make(chan struct{}, maxRuns),
+		store:      &teamStore{path: filepath.Join(resultsDir, teamStoreName)},
+		runs:       make(map[string]*APIRun),
+	}
+}
+
+func (s *apiServer) routes() http.Handler {
+	api := http.NewServeMux()
+	api.HandleFunc("GET /api/v1/tasks", s.handleListTasks)
+	api.HandleFunc("POST /api/v1/runs", s.handleStartRun)
+	api.HandleFunc("GET /api/v1/runs", s.handleListRuns)
+	api.HandleFunc("GET /api/v1/runs/{id}", s.handleGetRun)
+	api.HandleFunc("GET /api/v1/runs/{id}/results", s.handleRunResults)
+	api.HandleFunc("GET /api/v1/runs/{id}/log", s.handleRunLog)
+	api.HandleFunc("POST /api/v1/runs/{id}/cancel", s.handleCancelRun)
+	api.HandleFunc("GET /api/v1/sessions", s.handleListSessions)
+	api.HandleFunc("GET /api/v1/sessions/{path...}", s.handleGetSession)
+	api.HandleFunc("POST /api/v1/submissions", s.handleSubmit)
+	api.HandleFunc("GET /api/v1/submissions", s.handleListSubmissions)
+	api.HandleFunc("GET /api/v1/leaderboard", s.handleLeaderboard)
+	api.HandleFunc("GET /api/v1/whoami", s.handleWhoAmI)
+
+	mux := http.NewServeMux()
+	mux.HandleFunc("GET /healthz", func(w http.ResponseWriter, _ *http.Request) {
+		writeJSON(w, http.StatusOK, map[string]string{"status": "ok", "version": Version})
+	})
+	mux.Handle("/api/", s.requireToken(api))
+	return mux
+}
+
+// requireToken rejects requests without a valid bearer token and passes on
+// the user the token belongs to.
+func (s *apiServer) requireToken(next http.Handler) http.Handler {
+	return http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		got, ok := strings.CutPrefix(r.Header.Get("Authorization"), "Bearer ")
+		var user apiUser
+		if ok {
+			user, ok = s.authenticate(got)
+		}
+		if !ok {
+			w.Header().Set("WWW-Authenticate", `Bearer realm="sanity"`)
+			writeAPIError(w, http.StatusUnauthorized, "missing or invalid bearer token")
+			return
+		}
+		next.ServeHTTP(w, withUser(r, user))
+	})
+}
+
+func (s *apiServer) handleListTasks(w http.ResponseWriter, r *http.Request) {
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	all, err := loader.LoadAll()
+	if err != nil {
+		writeAPIError(w, http.StatusInternalServerError, fmt.Sprintf("loading tasks: %v", err))
+		return
+	}
+	q := r.URL.Query()
+	if v := q.Get("language"); v != "" {
+		all = filterByLanguage(all, v)
+	}
+	if v := q.Get("tier"); v != "" && v != "all" {
+		all = filterByTier(all, v)
+	}
+	if v := q.Get("difficulty"); v != "" {
+		all = filterByDifficulty(all, v)
+	}
+	tag := q.Get("tag")
+	out := make([]apiTask, 0, len(all))
+	for _, t := range all {
+		if tag != "" && !slices.Contains(t.Tags, tag) {
+			continue
+		}
+		out = append(out, apiTask{ID: t.ID(), Task: t, Weight: taskWeight(t).Base})
+	}
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (s *apiServer) handleStartRun(w http.ResponseWriter, r *http.Request) {
+	var spec APIRunSpec
+	dec := json.NewDecoder(http.MaxBytesReader(w, r.Body, 1<<20))
+	dec.DisallowUnknownFields()
+	if err := dec.Decode(&spec); err != nil {
+		writeAPIError(w, http.StatusBadRequest, fmt.Sprintf("invalid run spec: %v", err))
+		return
+	}
+	if err := spec.validate(); err != nil {
+		writeAPIError(w, http.StatusBadRequest, err.Error())
+		return
+	}
+	run, err := s.startRun(spec, requestUser(r).Name)
+	if err != nil {
+		writeAPIError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusAccepted, run)
+}
+
+func (s *apiServer) handleListRuns(w http.ResponseWriter, _ *http.Request) {
+	s.mu.Lock()
+	out := make([]APIRun, 0, len(s.order))
+	for i := len(s.order) - 1; i >= 0; i-- {
+		snap := *s.runs[s.order[i]]
+		snap.Summary = nil
+		out = append(out, snap)
+	}
+	s.mu.Unlock()
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (s *apiServer) handleGetRun(w http.ResponseWriter, r *http.Request) {
+	run, ok := s.snapshot(r.PathValue("id"))
+	if !ok {
+		writeAPIError(w, http.StatusNotFound, "run not found")
+		return
+	}
+	writeJSON(w, http.StatusOK, run)
+}
+
+func (s *apiServer) handleRunResults(w http.ResponseWriter, r *http.Request) {
+	run, ok := s.snapshot(r.PathValue("id"))
+	if !ok {
+		writeAPIError(w, http.StatusNotFound, "run not found")
+		return
+	}
+	if run.FinishedAt == nil {
+		writeAPIError(w, http.StatusConflict, fmt.Sprintf("run is %s", run.State))
+		return
+	}
+	serveSummaryFile(w, run.OutputDir)
+}
+
+func (s *apiServer) handleRunLog(w http.ResponseWriter, r *http.Request) {
+	run, ok := s.snapshot(r.PathValue("id"))
+	if !ok {
+		writeAPIError(w, http.StatusNotFound, "run not found")
+		return
+	}
+	data, err := os.ReadFile(filepath.Join(run.OutputDir, serveLogName))
+	if err != nil && !errors.Is(err, fs.ErrNotExist) {
+		writeAPIError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	w.Header().Set("Content-Type", "text/plain; charset=utf-8")
+	_, _ = w.Write(data)
+}
+
+func (s *apiServer) handleCancelRun(w http.ResponseWriter, r *http.Request) {
+	user := requestUser(r)
+	s.mu.Lock()
+	run, ok := s.runs[r.PathValue("id")]
+	allowed := ok && (user.Admin || run.User == user.Name)
+	if allowed && run.FinishedAt == nil {
+		run.cancel()
+	}
+	s.mu.Unlock()
+	if !ok {
+		writeAPIError(w, http.StatusNotFound, "run not found")
+		return
+	}
+	if !allowed {
+		writeAPIError(w, http.StatusForbidden, fmt.Sprintf("run belongs to %s", run.User))
+		return
+	}
+	// Wait briefly so the response usually reflects the final state.
+	select {
+	case <-run.done:
+	case <-time.After(5 * time.Second):
+	case <-r.Context().Done():
+	}
+	snap, _ := s.snapshot(run.ID)
+	writeJSON(w, http.StatusOK, snap)
+}
+
+func (s *apiServer) handleListSessions(w http.ResponseWriter, _ *http.Request) {
+	dirs, err := findRunDirs(s.resultsDir)
+	if err != nil && !errors.Is(err, fs.ErrNotExist) {
+		writeAPIError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	out := make([]APISession, 0, len(dirs))
+	for _, dir := range dirs {
+		summary, err := loadSummaryFromDir(dir)
+		if err != nil {
+			continue
+		}
+		rel, err := filepath.Rel(s.resultsDir, dir)
+		if err != nil {
+			continue
+		}
+		out = append(out, APISession{
+			Path:             filepath.ToSlash(rel),
+			Agent:            summary.Agent,
+			Model:            summary.Model,
+			Timestamp:        summary.Timestamp,
+			Passed:           summary.Passed,
+			Total:            summary.Total,
+			PassRate:         summary.PassRate,
+			WeightedPassRate: summary.WeightedPassRate,
+		})
+	}
+	// Newest first.
+	slices.SortStableFunc(out, func(a, b APISession) int { return strings.Compare(b.Timestamp, a.Timestamp) })
+	writeJSON(w, http.StatusOK, out)
+}
+
+func (s *apiServer) handleGetSession(w http.ResponseWriter, r *http.Request) {
+	rel := filepath.FromSlash(r.PathValue("path"))
+	if !filepath.IsLocal(rel) {
+		writeAPIError(w, http.StatusBadRequest, "invalid session path")
+		return
+	}
+	serveSummaryFile(w, filepath.Join(s.resultsDir, rel))
+}
+
+// startRun registers a run and executes it in the background.
+func (s *apiServer) startRun(spec APIRunSpec, user string) (APIRun, error) {
+	suffix := make([]byte, 3)
+	if _, err := rand.Read(suffix); err != nil {
+		return APIRun{}, fmt.Errorf("generating run id: %w", err)
+	}
+	now := time.Now()
+	id := fmt.Sprintf("%s-%s-%s", now.Format("2006-01-02T150405"), spec.Agent, hex.EncodeToString(suffix))
+	ctx, cancel := context.WithCancel(context.Background())
+	run := &APIRun{
+		ID:        id,
+		User:      user,
+		Spec:      spec,
+		State:     apiRunQueued,
+		CreatedAt: now,
+		OutputDir: filepath.Join(s.resultsDir, id),
+		cancel:    cancel,
+		done:      make(chan struct{}),
+	}
+
+	s.mu.Lock()
+	s.runs[id] = run
+	s.order = append(s.order, id)
+	s.mu.Unlock()
+
+	s.wg.Add(1)
+	go func() {
+		defer s.wg.Done()
+		defer close(run.done)
+		defer cancel()
+		s.execute(ctx, run)
+	}()
+	snap, _ := s.snapshot(id)
+	return snap, nil
+}
+
+// execute waits for a free slot, then runs sanity eval for the run.
+func (s *apiServer) execute(ctx context.Context, run *APIRun) {
+	select {
+	case s.slots <- struct{}{}:
+		defer func() { <-s.slots }()
+	case <-ctx.Done():
+		s.finish(run, apiRunCanceled, "", nil)
+		return
+	}
+
+	s.mu.Lock()
+	started := time.Now()
+	run.State = apiRunRunning
+	run.StartedAt = &started
+	s.mu.Unlock()
+
+	if err := os.MkdirAll(run.OutputDir, 0o755); err != nil {
+		s.finish(run, apiRunFailed, fmt.Sprintf("creating output directory: %v", err), nil)
+		return
+	}
+	logFile, err := os.Create(filepath.Join(run.OutputDir, serveLogName))
+	if err != nil {
+		s.finish(run, apiRunFailed, fmt.Sprintf("creating log: %v", err), nil)
+		return
+	}
+	defer func() { _ = logFile.Close() }()
+
+	c := exec.CommandContext(ctx, s.exe, run.Spec.evalArgs(run.OutputDir)...)
+	c.Stdout = logFile
+	c.Stderr = logFile
+	c.Env = append(os.Environ(), "SANITY_NO_UPDATE_CHECK=1")
+	// Interrupt rather than kill so eval saves partial results.
+	c.Cancel = func() error {
+		if runtime.GOOS == "windows" {
+			return c.Process.Kill()
+		}
+		return c.Process.Signal(os.Interrupt)
+	}
+	c.WaitDelay = time.Minute
+	runErr := c.Run()
+
+	summary, _ := loadSummaryFromDir(run.OutputDir)
+	switch {
+	case ctx.Err() != nil:
+		s.finish(run, apiRunCanceled, "", summary)
+	case runErr != nil:
+		s.finish(run, apiRunFailed, fmt.Sprintf("eval exited: %v (see %s)", runErr, serveLogName), summary)
+	default:
+		s.finish(run, apiRunSucceeded, "", summary)
+		s.recordRun(run)
+	}
+}
+
+func (s *apiServer) finish(run *APIRun, state, errMsg string, summary *EvalSummary) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	now := time.Now()
+	run.State = state
+	run.Error = errMsg
+	run.FinishedAt = &now
+	run.Summary = summary
+}
+
+// snapshot returns a copy of the run with id, safe to encode without the lock.
+func (s *apiServer) snapshot(id string) (APIRun, bool) {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	run, ok := s.runs[id]
+	if !ok {
+		return APIRun{}, false
+	}
+	return *run, true
+}
+
+// cancelAll interrupts every queued or running run.
+func (s *apiServer) cancelAll() {
+	s.mu.Lock()
+	defer s.mu.Unlock()
+	for _, run := range s.runs {
+		if run.FinishedAt == nil {
+			run.cancel()
+		}
+	}
+}
+
+// serveSummaryFile writes dir/summary.json as the response.
+func serveSummaryFile(w http.ResponseWriter, dir string) {
+	data, err := os.ReadFile(filepath.Join(dir, "summary.json"))
+	if errors.Is(err, fs.ErrNotExist) {
+		writeAPIError(w, http.StatusNotFound, "no summary.json for this run")
+		return
+	}
+	if err != nil {
+		writeAPIError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	w.Header().Set("Content-Type", "application/json")
+	_, _ = w.Write(data)
+}
+
+func writeJSON(w http.ResponseWriter, status int, v any) {
+	w.Header().Set("Content-Type", "application/json")
+	w.WriteHeader(status)
+	enc := json.NewEncoder(w)
+	enc.SetIndent("", "  ")
+	_ = enc.Encode(v)
+}
+
+func writeAPIError(w http.ResponseWriter, status int, msg string) {
+	writeJSON(w, status, map[string]string{"error": msg})
+}
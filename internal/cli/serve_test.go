@@ -0,0 +1,211 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+)
+
+func apiRequest(t *testing.T, h http.Handler, method, path, token, body string) *httptest.ResponseRecorder {
+	t.Helper()
+	req := httptest.NewRequest(method, path, strings.NewReader(body))
+	if token != "" {
+		req.Header.Set("Authorization", "Bearer "+token)
+	}
+	rec := httptest.NewRecorder()
+	h.ServeHTTP(rec, req)
+	return rec
+}
+
+func TestAPIServerAuth(t *testing.T) {
+	t.Parallel()
+
+	h := newAPIServer("secret", t.TempDir(), "sanity", 1).routes()
+	if rec := apiRequest(t, h, "GET", "/api/v1/runs", "", ""); rec.Code != http.StatusUnauthorized {
+		t.Fatalf("no token: status %d, want 401", rec.Code)
+	}
+	if rec := apiRequest(t, h, "GET", "/api/v1/runs", "wrong", ""); rec.Code != http.StatusUnauthorized {
+		t.Fatalf("wrong token: status %d, want 401", rec.Code)
+	}
+	if rec := apiRequest(t, h, "GET", "/api/v1/runs", "secret", ""); rec.Code != http.StatusOK {
+		t.Fatalf("valid token: status %d, want 200", rec.Code)
+	}
+	if rec := apiRequest(t, h, "GET", "/healthz", "", ""); rec.Code != http.StatusOK {
+		t.Fatalf("healthz: status %d, want 200", rec.Code)
+	}
+}
+
+func TestAPIServerTasksAndSessions(t *testing.T) {
+	t.Parallel()
+
+	root := t.TempDir()
+	runDir := filepath.Join(root, "batch-x", "gemini")
+	if err := os.MkdirAll(runDir, 0o755); err != nil {
+		t.Fatalf("mkdir: %v", err)
+	}
+	summary := `{"agent":"gemini","timestamp":"2026-01-07T120000","passed":1,"total":2,"pass_rate":50}`
+	if err := os.WriteFile(filepath.Join(runDir, "summary.json"), []byte(summary), 0o644); err != nil {
+		t.Fatalf("write: %v", err)
+	}
+	h := newAPIServer("tok", root, "sanity", 1).routes()
+
+	rec := apiRequest(t, h, "GET", "/api/v1/tasks?language=go", "tok", "")
+	var tasksOut []map[string]any
+	if err := json.Unmarshal(rec.Body.Bytes(), &tasksOut); err != nil || len(tasksOut) == 0 {
+		t.Fatalf("tasks: status %d, err %v, body %s", rec.Code, err, rec.Body)
+	}
+	for _, tk := range tasksOut {
+		if !strings.HasPrefix(tk["id"].(string), "go/") || tk["weight"].(float64) <= 0 {
+			t.Fatalf("unexpected task entry: %v", tk)
+		}
+	}
+
+	rec = apiRequest(t, h, "GET", "/api/v1/sessions", "tok", "")
+	var sessions []APISession
+	if err := json.Unmarshal(rec.Body.Bytes(), &sessions); err != nil {
+		t.Fatalf("sessions: %v (%s)", err, rec.Body)
+	}
+	if len(sessions) != 1 || sessions[0].Path != "batch-x/gemini" || sessions[0].Passed != 1 {
+		t.Fatalf("unexpected sessions: %+v", sessions)
+	}
+
+	rec = apiRequest(t, h, "GET", "/api/v1/sessions/batch-x/gemini", "tok", "")
+	if rec.Code != http.StatusOK || !strings.Contains(rec.Body.String(), `"agent":"gemini"`) {
+		t.Fatalf("session summary: status %d, body %s", rec.Code, rec.Body)
+	}
+	if rec := apiRequest(t, h, "GET", "/api/v1/sessions/missing", "tok", ""); rec.Code != http.StatusNotFound {
+		t.Fatalf("missing session: status %d, want 404", rec.Code)
+	}
+}
+
+func TestAPIServerRejectsInvalidRunSpec(t *testing.T) {
+	t.Parallel()
+
+	h := newAPIServer("tok", t.TempDir(), "sanity", 1).routes()
+	for _, body := range []string{`{}`, `{"agent":"x","bogus":1}`, `not json`} {
+		if rec := apiRequest(t, h, "POST", "/api/v1/runs", "tok", body); rec.Code != http.StatusBadRequest {
+			t.Errorf("POST %s: status %d, want 400", body, rec.Code)
+		}
+	}
+	if rec := apiRequest(t, h, "GET", "/api/v1/runs/nope", "tok", ""); rec.Code != http.StatusNotFound {
+		t.Fatalf("unknown run: status %d, want 404", rec.Code)
+	}
+}
+
+func TestAPIRunSpecEvalArgs(t *testing.T) {
+	t.Parallel()
+
+	spec := APIRunSpec{
+		Agent:     "gemini",
+		Model:     "gemini-2.5-pro",
+		Tasks:     []string{"go/bank-account", "rust/regex-lite"},
+		Timeout:   300,
+		Parallel:  4,
+		UseSkills: true,
+	}
+	args := spec.evalArgs("out/run")
+	i := slices.Index(args, "eval")
+	if i < 0 {
+		t.Fatalf("evalArgs() = %v, missing eval", args)
+	}
+	got := strings.Join(args[i:], " ")
+	want := "eval --agent gemini --output out/run --no-progress --model gemini-2.5-pro --tasks go/bank-account,rust/regex-lite --timeout 300 --parallel 4 --use-skills"
+	if got != want {
+		t.Fatalf("evalArgs() = %q\nwant %q", got, want)
+	}
+}
+
+func TestAPIServerTeamMode(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	usersFile := filepath.Join(dir, "team.toml")
+	users := fmt.Sprintf("[[users]]\nname = \"alice\"\ntoken_sha256 = %q\n\n[[users]]\nname = \"bob\"\ntoken_sha256 = %q\nadmin = true\n",
+		hashToken("alice-token"), hashToken("bob-token"))
+	if err := os.WriteFile(usersFile, []byte(users), 0o600); err != nil {
+		t.Fatal(err)
+	}
+	srv := newAPIServer("", dir, "sanity", 1)
+	if err := srv.loadTeam(usersFile); err != nil {
+		t.Fatalf("loadTeam() error = %v", err)
+	}
+	h := srv.routes()
+
+	if rec := apiRequest(t, h, "GET", "/api/v1/runs", "", ""); rec.Code != http.StatusUnauthorized {
+		t.Fatalf("empty token in team mode: status %d, want 401", rec.Code)
+	}
+	rec := apiRequest(t, h, "GET", "/api/v1/whoami", "alice-token", "")
+	if rec.Code != http.StatusOK || !strings.Contains(rec.Body.String(), `"user": "alice"`) {
+		t.Fatalf("whoami: status %d, body %s", rec.Code, rec.Body)
+	}
+
+	for _, sub := range []struct{ token, body string }{
+		{"alice-token", `{"agent":"gemini","model":"pro","pass_rate":50,"weighted_pass_rate":40,"passed":1,"total":2}`},
+		{"bob-token", `{"agent":"gemini","model":"pro","pass_rate":100,"weighted_pass_rate":100,"passed":2,"total":2}`},
+		{"alice-token", `{"agent":"codex","pass_rate":50,"weighted_pass_rate":60,"passed":1,"total":2}`},
+	} {
+		if rec := apiRequest(t, h, "POST", "/api/v1/submissions", sub.token, sub.body); rec.Code != http.StatusCreated {
+			t.Fatalf("submit %s: status %d, body %s", sub.body, rec.Code, rec.Body)
+		}
+	}
+	if rec := apiRequest(t, h, "POST", "/api/v1/submissions", "alice-token", `{"agent":"gemini"}`); rec.Code != http.StatusBadRequest {
+		t.Fatalf("empty submission: status %d, want 400", rec.Code)
+	}
+
+	rec = apiRequest(t, h, "GET", "/api/v1/leaderboard", "alice-token", "")
+	var board []LeaderboardEntry
+	if err := json.Unmarshal(rec.Body.Bytes(), &board); err != nil {
+		t.Fatalf("leaderboard: %v (%s)", err, rec.Body)
+	}
+	if len(board) != 2 || board[0].Agent != "gemini" || board[0].User != "bob" || board[0].Submissions != 2 || board[1].Agent != "codex" {
+		t.Fatalf("unexpected leaderboard: %+v", board)
+	}
+
+	rec = apiRequest(t, h, "GET", "/api/v1/submissions?user=alice", "bob-token", "")
+	var records []TeamRecord
+	if err := json.Unmarshal(rec.Body.Bytes(), &records); err != nil || len(records) != 2 || records[0].Submission.Agent != "codex" {
+		t.Fatalf("alice's submissions: err %v, %+v", err, records)
+	}
+
+	// The store is append-only and survives a restart.
+	reopened, err := openTeamStore(filepath.Join(dir, teamStoreName))
+	if err != nil {
+		t.Fatalf("openTeamStore() error = %v", err)
+	}
+	if got := len(reopened.list("")); got != 3 {
+		t.Fatalf("reopened store has %d records, want 3", got)
+	}
+
+	srv.mu.Lock()
+	srv.runs["r1"] = &APIRun{ID: "r1", User: "bob", cancel: func() {}, done: make(chan struct{})}
+	srv.mu.Unlock()
+	if rec := apiRequest(t, h, "POST", "/api/v1/runs/r1/cancel", "alice-token", ""); rec.Code != http.StatusForbidden {
+		t.Fatalf("cancel another user's run: status %d, want 403", rec.Code)
+	}
+}
+
+func TestLoadTeamUsers(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	for name, body := range map[string]string{
+		"empty":     "",
+		"no-name":   fmt.Sprintf("[[users]]\ntoken_sha256 = %q\n", hashToken("x")),
+		"bad-hash":  "[[users]]\nname = \"alice\"\ntoken_sha256 = \"plaintext\"\n",
+		"duplicate": fmt.Sprintf("[[users]]\nname = \"a\"\ntoken_sha256 = %q\n[[users]]\nname = \"a\"\ntoken_sha256 = %q\n", hashToken("x"), hashToken("y")),
+	} {
+		path := filepath.Join(dir, name+".toml")
+		if err := os.WriteFile(path, []byte(body), 0o600); err != nil {
+			t.Fatal(err)
+		}
+		if _, err := loadTeamUsers(path); err == nil {
+			t.Errorf("loadTeamUsers(%s) succeeded, want an error", name)
+		}
+	}
+}
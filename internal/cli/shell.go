@@ -0,0 +1,174 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+var (
+	shellFrom  string
+	shellImage string
+	shellKeep  bool
+)
+
+var shellCmd = &cobra.Command{
+	Use:   "shell <task>",
+	Short: "Open a shell in a task's verification container",
+	Long: `Builds a task workspace as validation sees it and opens an interactive shell
+in the container image used for verification, with the workspace mounted at
+/workspace. Use it to debug solutions that pass locally but fail in the
+harness.
+
+The workspace holds the task's stub files and its hidden tests. With --from,
+the solution of a past eval run or run session is laid over the stubs: the
+exact files recorded in replay.json when present, otherwise the workspace kept
+with --keep-workspaces. The image recorded by that run is used, pinned by
+digest when known.
+
+The workspace is a temporary directory, removed when the shell exits unless
+--keep is given. Requires the docker CLI, or podman with
+container_backend = "podman".`,
+	Example: `  sanity shell go/bank-account
+  sanity shell rust/regex-lite --from eval-results/2026-01-07T120000-gemini
+  sanity shell react --from sessions/typescript-react-2026-01-07T120000-ab12 --keep`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if _, err := exec.LookPath(containerCLI()); err != nil {
+			return fmt.Errorf("shell requires the %s CLI: %w", containerCLI(), err)
+		}
+		loader := task.NewLoader(tasks.FS, tasksDir)
+		allTasks, err := loader.LoadAll()
+		if err != nil {
+			return fmt.Errorf("loading tasks: %w", err)
+		}
+		t, err := task.ResolveRef(allTasks, args[0])
+		if err != nil {
+			return err
+		}
+
+		var solution map[string][]byte
+		var rec *ReplayRecord
+		if shellFrom != "" {
+			fromArgs, cleanup, err := resolveRunArgs([]string{shellFrom})
+			if err != nil {
+				return err
+			}
+			defer cleanup()
+			if solution, rec, err = shellSolutionFiles(fromArgs[0], t); err != nil {
+				return err
+			}
+		}
+
+		workspace, err := buildShellWorkspace(loader, t, solution)
+		if err != nil {
+			return err
+		}
+		if !shellKeep {
+			defer func() { _ = os.RemoveAll(workspace) }()
+		}
+
+		image := shellImage
+		validate := t.ValidationCommand()
+		if rec != nil {
+			if image == "" {
+				image = pinnedReplayImage(rec)
+			}
+			validate = rec.ValidationCommand
+		}
+		if image == "" {
+			if image, err = shellTaskImage(cmd.Context(), loader, t); err != nil {
+				return err
+			}
+		}
+
+		fmt.Printf(" Task:       %s\n", t.ID())
+		if shellFrom != "" {
+			fmt.Printf(" Solution:   %s (%d files)\n", shellFrom, len(solution))
+		}
+		fmt.Printf(" Workspace:  %s → /workspace\n", workspace)
+		fmt.Printf(" Image:      %s\n", image)
+		fmt.Printf(" Validate:   %s\n", strings.Join(validate, " "))
+		fmt.Println()
+		err = runInteractive(dockerShellArgs(image, workspace))
+		if shellKeep {
+			fmt.Printf(" Workspace kept at %s\n", workspace)
+		}
+		return err
+	},
+}
+
+func init() {
+	shellCmd.Flags().StringVar(&shellFrom, "from", "", "eval results directory, archive, or run session whose solution to load")
+	shellCmd.Flags().StringVar(&shellImage, "image", "", "override the container image")
+	shellCmd.Flags().BoolVar(&shellKeep, "keep", false, "keep the workspace after the shell exits")
+}
+
+// shellSolutionFiles returns the solution files t had in a past run, and the
+// run's replay record when it has one. sessionPath is an eval results
+// directory or a run session directory.
+func shellSolutionFiles(sessionPath string, t *task.Task) (map[string][]byte, *ReplayRecord, error) {
+	if data, err := os.ReadFile(filepath.Join(sessionPath, "result.json")); err == nil {
+		session, err := result.ParseSession(data)
+		if err != nil {
+			return nil, nil, err
+		}
+		if session.Language+"/"+session.TaskSlug != t.ID() {
+			return nil, nil, fmt.Errorf("session %s ran %s/%s, not %s", sessionPath, session.Language, session.TaskSlug, t.ID())
+		}
+		workspace := filepath.Join(sessionPath, "workspace")
+		if !hasWorkspaceSources(workspace) {
+			return nil, nil, fmt.Errorf("session %s has no preserved workspace", sessionPath)
+		}
+		files, err := collectReplayFiles(workspace)
+		return files, nil, err
+	}
+
+	_, taskDir := evalWorkspacePaths(sessionPath, t)
+	if data, err := os.ReadFile(filepath.Join(taskDir, replayRecordName)); err == nil {
+		var rec ReplayRecord
+		if err := json.Unmarshal(data, &rec); err != nil {
+			return nil, nil, fmt.Errorf("parsing %s: %w", replayRecordName, err)
+		}
+		return rec.Files, &rec, nil
+	}
+	if _, err := os.Stat(taskDir); err != nil {
+		return nil, nil, fmt.Errorf("%s was not run in %s", t.ID(), sessionPath)
+	}
+	if !hasWorkspaceSources(taskDir) {
+		return nil, nil, fmt.Errorf("%s in %s has neither %s nor a kept workspace", t.ID(), sessionPath, replayRecordName)
+	}
+	files, err := collectReplayFiles(taskDir)
+	return files, nil, err
+}
+
+// buildShellWorkspace writes t's stubs, the solution files over them, and the
+// hidden tests into a new temporary directory, as validation would see it.
+func buildShellWorkspace(loader *task.Loader, t *task.Task, solution map[string][]byte) (string, error) {
+	dir, err := os.MkdirTemp("", fmt.Sprintf("sanity-shell-%s-%s-*", t.Language, t.Slug))
+	if err != nil {
+		return "", fmt.Errorf("creating temp workspace: %w", err)
+	}
+	if err := writeTaskFilesToWorkspace(loader, t, dir, t.VisibleFiles()); err != nil {
+		_ = os.RemoveAll(dir)
+		return "", err
+	}
+	if err := writeRecordedFiles(dir, solution); err != nil {
+		_ = os.RemoveAll(dir)
+		return "", err
+	}
+	if err := writeTaskFilesToWorkspace(loader, t, dir, t.HiddenTestFiles()); err != nil {
+		_ = os.RemoveAll(dir)
+		return "", fmt.Errorf("writing hidden tests: %w", err)
+	}
+	return dir, nil
+}
@@ -0,0 +1,71 @@
+package cli
+
+import (
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+func TestShellWorkspaceFromEvalRun(t *testing.T) {
+	t.Parallel()
+
+	loader := task.NewLoader(tasks.FS, "")
+	allTasks, err := loader.LoadAll()
+	if err != nil {
+		t.Fatalf("LoadAll() error = %v", err)
+	}
+	react, err := task.ResolveRef(allTasks, "go/react")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	evalDir := t.TempDir()
+	rec, err := json.Marshal(ReplayRecord{
+		Task:              "go/react",
+		Image:             "ghcr.io/lemon07r/sanity-go:latest",
+		ImageRepoDigests:  []string{"ghcr.io/lemon07r/sanity-go@sha256:abc"},
+		ValidationCommand: []string{"go", "test", "./..."},
+		Files:             map[string][]byte{"react.go": []byte("package react // solved\n"), "notes/plan.md": []byte("plan\n")},
+	})
+	if err != nil {
+		t.Fatal(err)
+	}
+	writePackFiles(t, evalDir, map[string]string{"go-react/replay.json": string(rec)})
+
+	files, got, err := shellSolutionFiles(evalDir, react)
+	if err != nil {
+		t.Fatalf("shellSolutionFiles() error = %v", err)
+	}
+	if got == nil || pinnedReplayImage(got) != "ghcr.io/lemon07r/sanity-go@sha256:abc" || len(files) != 2 {
+		t.Fatalf("shellSolutionFiles() = %v, %+v", files, got)
+	}
+	bank, err := task.ResolveRef(allTasks, "go/bank-account")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, _, err := shellSolutionFiles(evalDir, bank); err == nil || !strings.Contains(err.Error(), "was not run") {
+		t.Fatalf("expected not-run error, got %v", err)
+	}
+
+	workspace, err := buildShellWorkspace(loader, react, files)
+	if err != nil {
+		t.Fatalf("buildShellWorkspace() error = %v", err)
+	}
+	defer func() { _ = os.RemoveAll(workspace) }()
+	for name, want := range map[string]string{"react.go": "package react // solved\n", "notes/plan.md": "plan\n"} {
+		data, err := os.ReadFile(filepath.Join(workspace, filepath.FromSlash(name)))
+		if err != nil || string(data) != want {
+			t.Errorf("%s = %q, %v; want %q", name, data, err, want)
+		}
+	}
+	for _, name := range []string{"react_test.go", "react_hidden_test.go", "go.mod"} {
+		if _, err := os.Stat(filepath.Join(workspace, name)); err != nil {
+			t.Errorf("%s missing from workspace: %v", name, err)
+		}
+	}
+}
@@ -33,9 +33,9 @@ Example:
 			return fmt.Errorf("reading session: %w", err)
 		}
 
-		var session result.Session
-		if err := json.Unmarshal(data, &session); err != nil {
-			return fmt.Errorf("parsing session: %w", err)
+		session, err := result.ParseSession(data)
+		if err != nil {
+			return err
 		}
 
 		if showJSON {
@@ -45,7 +45,7 @@ Example:
 		}
 
 		// Display formatted output
-		return displaySession(&session, sessionPath)
+		return displaySession(session, sessionPath)
 	},
 }
 
@@ -0,0 +1,252 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/spf13/cobra"
+)
+
+var analyzeSimilarityThreshold float64
+
+// SolutionRef identifies one stored solution to a task.
+type SolutionRef struct {
+	RunID  string `json:"run_id"`
+	Config string `json:"config"`
+	Passed bool   `json:"passed"`
+}
+
+// SimilarityCluster is a group of solutions to one task in which every
+// solution is linked to another by similarity at or above the threshold.
+type SimilarityCluster struct {
+	Configs int           `json:"configs"`
+	Members []SolutionRef `json:"members"`
+}
+
+// TaskSimilarity summarizes how alike the stored solutions to a task are.
+// Convergence is the share of configurations whose solutions fall in the
+// largest cluster; values near 1 mean most models wrote the same code.
+type TaskSimilarity struct {
+	Task           string              `json:"task"`
+	Solutions      int                 `json:"solutions"`
+	Configs        int                 `json:"configs"`
+	MeanSimilarity float64             `json:"mean_similarity"`
+	Convergence    float64             `json:"convergence"`
+	Clusters       []SimilarityCluster `json:"clusters"`
+}
+
+var analyzeSimilarityCmd = &cobra.Command{
+	Use:   "similarity <dir> [dir...]",
+	Short: "Cluster solutions to each task by similarity across runs",
+	Long: `Compares the solutions stored in each run's solution.diff for the same task
+and clusters them by token-level similarity (Jaccard over runs of 8 tokens of
+added code). Tasks where most agent/model configurations land in one cluster
+are likely solved from a memorized implementation; many small clusters mean
+genuinely diverse approaches.
+
+Repeated runs of one configuration count once toward convergence. Solutions
+with too little added code to compare are skipped.`,
+	Example: `  sanity analyze similarity eval-results/
+  sanity analyze similarity eval-results/ --threshold 0.7 --json`,
+	Args: cobra.MinimumNArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if analyzeSimilarityThreshold <= 0 || analyzeSimilarityThreshold > 1 {
+			return fmt.Errorf("invalid --threshold %g (must be in (0, 1])", analyzeSimilarityThreshold)
+		}
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
+		summaries, runDirs, err := loadRunSummaries(args)
+		if err != nil {
+			return err
+		}
+		tasks := clusterSolutions(summaries, runDirs, analyzeSimilarityThreshold)
+		if analyzeJSON {
+			data, _ := json.MarshalIndent(tasks, "", "  ")
+			fmt.Println(string(data))
+			return nil
+		}
+		fmt.Print(buildSimilarityReport(tasks, analyzeSimilarityThreshold))
+		return nil
+	},
+}
+
+// storedSolution is a solution's reference and the shingles of its added code.
+type storedSolution struct {
+	ref      SolutionRef
+	shingles map[string]bool
+}
+
+// clusterSolutions groups each task's stored solutions by single-linkage
+// clustering and orders tasks from most to least convergent.
+func clusterSolutions(summaries []EvalSummary, runDirs []string, threshold float64) []TaskSimilarity {
+	byTask := loadStoredSolutions(summaries, runDirs)
+	tasks := make([]TaskSimilarity, 0, len(byTask))
+	for id, sols := range byTask {
+		tasks = append(tasks, clusterTaskSolutions(id, sols, threshold))
+	}
+	sort.Slice(tasks, func(i, j int) bool {
+		if tasks[i].Convergence != tasks[j].Convergence {
+			return tasks[i].Convergence > tasks[j].Convergence
+		}
+		return tasks[i].Task < tasks[j].Task
+	})
+	return tasks
+}
+
+// loadStoredSolutions reads every run's solution.diff files, grouped by task.
+func loadStoredSolutions(summaries []EvalSummary, runDirs []string) map[string][]storedSolution {
+	byTask := make(map[string][]storedSolution)
+	for i, s := range summaries {
+		for _, r := range s.Results {
+			// Task output directories are named "<lang>-<slug>".
+			data, err := os.ReadFile(filepath.Join(runDirs[i], strings.Replace(r.Task, "/", "-", 1), "solution.diff"))
+			if err != nil {
+				continue
+			}
+			set := make(map[string]bool)
+			addShingles(set, addedDiffLines(string(data)))
+			if len(set) < plagiarismMinShingles {
+				continue
+			}
+			byTask[r.Task] = append(byTask[r.Task], storedSolution{
+				ref:      SolutionRef{RunID: filepath.Base(runDirs[i]), Config: summaryConfigLabel(s), Passed: r.Passed},
+				shingles: set,
+			})
+		}
+	}
+	return byTask
+}
+
+// clusterTaskSolutions links solutions whose similarity reaches threshold and
+// returns the resulting clusters, largest by configuration count first.
+func clusterTaskSolutions(id string, sols []storedSolution, threshold float64) TaskSimilarity {
+	parent := make([]int, len(sols))
+	for i := range parent {
+		parent[i] = i
+	}
+	var find func(int) int
+	find = func(i int) int {
+		if parent[i] != i {
+			parent[i] = find(parent[i])
+		}
+		return parent[i]
+	}
+	var total float64
+	pairs := 0
+	for i := range sols {
+		for j := i + 1; j < len(sols); j++ {
+			sim := jaccard(sols[i].shingles, sols[j].shingles)
+			total += sim
+			pairs++
+			if sim >= threshold {
+				parent[find(i)] = find(j)
+			}
+		}
+	}
+
+	groups := make(map[int][]SolutionRef)
+	allConfigs := make(map[string]bool)
+	for i, sol := range sols {
+		groups[find(i)] = append(groups[find(i)], sol.ref)
+		allConfigs[sol.ref.Config] = true
+	}
+	ts := TaskSimilarity{Task: id, Solutions: len(sols), Configs: len(allConfigs)}
+	if pairs > 0 {
+		ts.MeanSimilarity = total / float64(pairs)
+	}
+	for _, members := range groups {
+		configs := make(map[string]bool)
+		for _, m := range members {
+			configs[m.Config] = true
+		}
+		sort.Slice(members, func(i, j int) bool { return members[i].RunID < members[j].RunID })
+		ts.Clusters = append(ts.Clusters, SimilarityCluster{Configs: len(configs), Members: members})
+	}
+	sort.Slice(ts.Clusters, func(i, j int) bool {
+		a, b := ts.Clusters[i], ts.Clusters[j]
+		if a.Configs != b.Configs {
+			return a.Configs > b.Configs
+		}
+		if len(a.Members) != len(b.Members) {
+			return len(a.Members) > len(b.Members)
+		}
+		return a.Members[0].RunID < b.Members[0].RunID
+	})
+	ts.Convergence = float64(ts.Clusters[0].Configs) / float64(ts.Configs)
+	return ts
+}
+
+// addedDiffLines returns the lines a unified diff adds, without the marker.
+func addedDiffLines(diff string) string {
+	var sb strings.Builder
+	for _, line := range strings.Split(diff, "\n") {
+		if strings.HasPrefix(line, "+") && !strings.HasPrefix(line, "+++ ") {
+			sb.WriteString(line[1:])
+			sb.WriteByte('\n')
+		}
+	}
+	return sb.String()
+}
+
+func jaccard(a, b map[string]bool) float64 {
+	if len(a) > len(b) {
+		a, b = b, a
+	}
+	shared := 0
+	for s := range a {
+		if b[s] {
+			shared++
+		}
+	}
+	union := len(a) + len(b) - shared
+	if union == 0 {
+		return 0
+	}
+	return float64(shared) / float64(union)
+}
+
+// buildSimilarityReport renders task similarity clusters as Markdown.
+func buildSimilarityReport(tasks []TaskSimilarity, threshold float64) string {
+	var sb strings.Builder
+	sb.WriteString("# Solution Similarity\n\n")
+	fmt.Fprintf(&sb, "Solutions are clustered when their similarity is at least %.2f. "+
+		"High convergence means most configurations wrote the same implementation.\n\n", threshold)
+	if len(tasks) == 0 {
+		sb.WriteString("No comparable solutions found.\n")
+		return sb.String()
+	}
+
+	sb.WriteString("| Task | Solutions | Configs | Clusters | Largest Cluster | Convergence | Mean Similarity |\n")
+	sb.WriteString("|------|-----------|---------|----------|-----------------|-------------|-----------------|\n")
+	for _, ts := range tasks {
+		fmt.Fprintf(&sb, "| %s | %d | %d | %d | %d configs | %.0f%% | %.2f |\n",
+			ts.Task, ts.Solutions, ts.Configs, len(ts.Clusters), ts.Clusters[0].Configs, ts.Convergence*100, ts.MeanSimilarity)
+	}
+	sb.WriteString("\n")
+
+	for _, ts := range tasks {
+		largest := ts.Clusters[0]
+		if largest.Configs < 2 {
+			continue
+		}
+		fmt.Fprintf(&sb, "## %s\n\n", ts.Task)
+		fmt.Fprintf(&sb, "Largest cluster (%d of %d configs):\n\n", largest.Configs, ts.Configs)
+		for _, m := range largest.Members {
+			status := "FAIL"
+			if m.Passed {
+				status = "PASS"
+			}
+			fmt.Fprintf(&sb, "- %s (%s, %s)\n", m.Config, m.RunID, status)
+		}
+		sb.WriteString("\n")
+	}
+	return sb.String()
+}
@@ -0,0 +1,105 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestClusterSolutions(t *testing.T) {
+	t.Parallel()
+
+	memorized := addedLines(bankAccountSolution)
+	// A different design: same API, different state handling.
+	diverse := addedLines(`package account
+
+import "sync"
+
+type Account struct {
+	sync.RWMutex
+	cents  int64
+	closed bool
+}
+
+func Open(initial int64) *Account {
+	if initial >= 0 {
+		acct := new(Account)
+		acct.cents = initial
+		return acct
+	}
+	return nil
+}
+
+func (acct *Account) Balance() (int64, bool) {
+	acct.RLock()
+	cents, closed := acct.cents, acct.closed
+	acct.RUnlock()
+	return cents, !closed
+}
+
+func (acct *Account) Deposit(delta int64) (int64, bool) {
+	acct.Lock()
+	defer acct.Unlock()
+	switch {
+	case acct.closed:
+		return 0, false
+	case acct.cents+delta < 0:
+		return acct.cents, false
+	}
+	acct.cents += delta
+	return acct.cents, true
+}
+`)
+
+	root := t.TempDir()
+	var summaries []EvalSummary
+	var runDirs []string
+	addRun := func(id, model, diff string, passed bool) {
+		t.Helper()
+		runDir := filepath.Join(root, id)
+		taskDir := filepath.Join(runDir, "go-bank-account")
+		if err := os.MkdirAll(taskDir, 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(filepath.Join(taskDir, "solution.diff"), []byte(diff), 0o644); err != nil {
+			t.Fatal(err)
+		}
+		summaries = append(summaries, EvalSummary{Agent: "opencode", Model: model,
+			Results: []EvalResult{{Task: "go/bank-account", Passed: passed}, {Task: "go/missing"}}})
+		runDirs = append(runDirs, runDir)
+	}
+	addRun("run-a", "model-a", memorized, true)
+	addRun("run-a2", "model-a", memorized, true)
+	addRun("run-b", "model-b", memorized+"+// trailing note\n", false)
+	addRun("run-c", "model-c", diverse, true)
+	addRun("run-d", "model-d", "+package account\n", true)
+
+	tasks := clusterSolutions(summaries, runDirs, 0.6)
+	if len(tasks) != 1 {
+		t.Fatalf("tasks = %+v", tasks)
+	}
+	ts := tasks[0]
+	if ts.Task != "go/bank-account" || ts.Solutions != 4 || ts.Configs != 3 || len(ts.Clusters) != 2 {
+		t.Fatalf("similarity = %+v", ts)
+	}
+	largest := ts.Clusters[0]
+	if largest.Configs != 2 || len(largest.Members) != 3 || largest.Members[0].RunID != "run-a" {
+		t.Fatalf("largest cluster = %+v", largest)
+	}
+	if ts.Convergence < 0.66 || ts.Convergence > 0.67 {
+		t.Fatalf("convergence = %v", ts.Convergence)
+	}
+
+	report := buildSimilarityReport(tasks, 0.6)
+	for _, want := range []string{"| go/bank-account | 4 | 3 | 2 | 2 configs | 67% |", "- opencode/model-b (run-b, FAIL)"} {
+		if !strings.Contains(report, want) {
+			t.Errorf("report missing %q:\n%s", want, report)
+		}
+	}
+}
+
+// addedLines renders src as the added lines of a unified diff.
+func addedLines(src string) string {
+	return "+" + strings.ReplaceAll(strings.TrimSuffix(src, "\n"), "\n", "\n+") + "\n"
+}
@@ -0,0 +1,216 @@
+package cli
+
+import (
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/BurntSushi/toml"
+	"github.com/zeebo/blake3"
+)
+
+// skillPackManifestName is the optional metadata file at a pack's root. It is
+// never copied into the workspace.
+const skillPackManifestName = "pack.toml"
+
+// SkillPack is a directory of extra instruction files, helper scripts, or
+// subagent definitions overlaid onto every agent workspace in a run.
+type SkillPack struct {
+	Name        string   `json:"name"`
+	Description string   `json:"description,omitempty"`
+	Path        string   `json:"path"`
+	Hash        string   `json:"hash"`
+	Files       []string `json:"files"`
+	Prompt      string   `json:"prompt,omitempty"`
+}
+
+// skillPackManifest is the schema of pack.toml.
+type skillPackManifest struct {
+	Name        string `toml:"name"`
+	Description string `toml:"description"`
+	Prompt      string `toml:"prompt"` // Appended to the agent prompt
+}
+
+// evalSkillPackSet holds the packs loaded for the current run. Like the other
+// eval globals it is set by evalRunSingle before tasks start.
+var evalSkillPackSet []SkillPack
+
+// loadSkillPacks loads each pack directory, rejecting duplicate names and
+// packs that would write the same workspace file.
+func loadSkillPacks(dirs []string) ([]SkillPack, error) {
+	packs := make([]SkillPack, 0, len(dirs))
+	names := make(map[string]bool, len(dirs))
+	owners := make(map[string]string)
+	for _, dir := range dirs {
+		p, err := loadSkillPack(dir)
+		if err != nil {
+			return nil, err
+		}
+		if names[p.Name] {
+			return nil, fmt.Errorf("skill pack %q given more than once", p.Name)
+		}
+		names[p.Name] = true
+		for _, f := range p.Files {
+			if other, ok := owners[f]; ok {
+				return nil, fmt.Errorf("skill packs %q and %q both provide %s", other, p.Name, f)
+			}
+			owners[f] = p.Name
+		}
+		packs = append(packs, p)
+	}
+	return packs, nil
+}
+
+// loadSkillPack reads a pack directory. The pack's hash covers every file's
+// relative path and contents, including pack.toml, so results can be tied to
+// the exact pack revision they were produced with.
+func loadSkillPack(dir string) (SkillPack, error) {
+	return loadPackDir(dir, "skill pack")
+}
+
+// loadPackDir reads a skill or distractor pack directory; kind names the pack
+// type in errors.
+func loadPackDir(dir, kind string) (SkillPack, error) {
+	abs, err := filepath.Abs(dir)
+	if err != nil {
+		return SkillPack{}, fmt.Errorf("resolving %s %s: %w", kind, dir, err)
+	}
+	info, err := os.Stat(abs)
+	if err != nil {
+		return SkillPack{}, fmt.Errorf("reading %s: %w", kind, err)
+	}
+	if !info.IsDir() {
+		return SkillPack{}, fmt.Errorf("%s %s is not a directory", kind, dir)
+	}
+
+	pack := SkillPack{Name: filepath.Base(abs), Path: abs}
+	manifestPath := filepath.Join(abs, skillPackManifestName)
+	if _, err := os.Stat(manifestPath); err == nil {
+		var m skillPackManifest
+		if _, err := toml.DecodeFile(manifestPath, &m); err != nil {
+			return SkillPack{}, fmt.Errorf("parsing %s: %w", manifestPath, err)
+		}
+		if m.Name != "" {
+			pack.Name = m.Name
+		}
+		pack.Description = m.Description
+		pack.Prompt = strings.TrimSpace(m.Prompt)
+	}
+
+	var hashed []string
+	err = filepath.WalkDir(abs, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		if d.IsDir() || !d.Type().IsRegular() {
+			return nil
+		}
+		rel, err := filepath.Rel(abs, path)
+		if err != nil {
+			return err
+		}
+		rel = filepath.ToSlash(rel)
+		hashed = append(hashed, rel)
+		if rel != skillPackManifestName {
+			pack.Files = append(pack.Files, rel)
+		}
+		return nil
+	})
+	if err != nil {
+		return SkillPack{}, fmt.Errorf("reading %s %s: %w", kind, dir, err)
+	}
+	if len(pack.Files) == 0 {
+		return SkillPack{}, fmt.Errorf("%s %s has no files to install", kind, dir)
+	}
+
+	sort.Strings(hashed)
+	sort.Strings(pack.Files)
+	hasher := blake3.New()
+	for _, rel := range hashed {
+		data, err := os.ReadFile(filepath.Join(abs, filepath.FromSlash(rel)))
+		if err != nil {
+			return SkillPack{}, fmt.Errorf("reading %s file: %w", kind, err)
+		}
+		_, _ = fmt.Fprintf(hasher, "%s\x00%d\x00", rel, len(data))
+		_, _ = hasher.Write(data)
+	}
+	pack.Hash = "blake3:" + hex.EncodeToString(hasher.Sum(nil))
+	return pack, nil
+}
+
+// install copies the pack's files into workspace, preserving their layout.
+// Packs may add files but never replace task files.
+func (p SkillPack) install(workspace string) error {
+	for _, rel := range p.Files {
+		dest := filepath.Join(workspace, filepath.FromSlash(rel))
+		if _, err := os.Lstat(dest); err == nil {
+			return fmt.Errorf("skill pack %q would overwrite %s", p.Name, rel)
+		} else if !errors.Is(err, fs.ErrNotExist) {
+			return fmt.Errorf("checking %s: %w", rel, err)
+		}
+		if err := copyPackFile(filepath.Join(p.Path, filepath.FromSlash(rel)), dest, rel); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// copyPackFile copies a pack file to dest, which is rel within the workspace.
+func copyPackFile(src, dest, rel string) error {
+	info, err := os.Stat(src)
+	if err != nil {
+		return fmt.Errorf("reading pack file: %w", err)
+	}
+	data, err := os.ReadFile(src)
+	if err != nil {
+		return fmt.Errorf("reading pack file: %w", err)
+	}
+	if err := os.MkdirAll(filepath.Dir(dest), 0o755); err != nil {
+		return fmt.Errorf("creating directory for %s: %w", rel, err)
+	}
+	// Keep the executable bit so helper scripts stay runnable.
+	if err := os.WriteFile(dest, data, info.Mode().Perm()); err != nil {
+		return fmt.Errorf("writing %s: %w", rel, err)
+	}
+	return nil
+}
+
+// installSkillPacks installs every pack into workspace.
+func installSkillPacks(packs []SkillPack, workspace string) error {
+	for _, p := range packs {
+		if err := p.install(workspace); err != nil {
+			return err
+		}
+	}
+	return nil
+}
+
+// skillPackPrompt returns the prompt section describing the installed packs,
+// or "" when no pack supplies prompt text.
+func skillPackPrompt(packs []SkillPack) string {
+	var sb strings.Builder
+	for _, p := range packs {
+		if p.Prompt == "" {
+			continue
+		}
+		if sb.Len() == 0 {
+			sb.WriteString("\n\nADDITIONAL RESOURCES:")
+		}
+		fmt.Fprintf(&sb, "\n\n[%s]\n%s", p.Name, p.Prompt)
+	}
+	return sb.String()
+}
+
+// skillPackNames returns the pack names in order.
+func skillPackNames(packs []SkillPack) []string {
+	names := make([]string, 0, len(packs))
+	for _, p := range packs {
+		names = append(names, p.Name)
+	}
+	return names
+}
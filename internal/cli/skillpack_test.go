@@ -0,0 +1,128 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func writePackFiles(t *testing.T, dir string, files map[string]string) {
+	t.Helper()
+	for rel, content := range files {
+		path := filepath.Join(dir, filepath.FromSlash(rel))
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+}
+
+func TestLoadSkillPack(t *testing.T) {
+	t.Parallel()
+
+	dir := filepath.Join(t.TempDir(), "reviewer")
+	writePackFiles(t, dir, map[string]string{
+		"pack.toml":                  "name = \"review-kit\"\ndescription = \"Self-review helpers\"\nprompt = \"\"\"\nRun ./scripts/check.sh before finishing.\n\"\"\"\n",
+		"scripts/check.sh":           "#!/bin/sh\n",
+		".claude/agents/reviewer.md": "You review diffs.\n",
+	})
+
+	pack, err := loadSkillPack(dir)
+	if err != nil {
+		t.Fatalf("loadSkillPack() error = %v", err)
+	}
+	if pack.Name != "review-kit" || pack.Description != "Self-review helpers" {
+		t.Fatalf("manifest not applied: %+v", pack)
+	}
+	if pack.Prompt != "Run ./scripts/check.sh before finishing." {
+		t.Fatalf("Prompt = %q", pack.Prompt)
+	}
+	if want := []string{".claude/agents/reviewer.md", "scripts/check.sh"}; !reflect.DeepEqual(pack.Files, want) {
+		t.Fatalf("Files = %v, want %v", pack.Files, want)
+	}
+	if !strings.HasPrefix(pack.Hash, "blake3:") {
+		t.Fatalf("Hash = %q", pack.Hash)
+	}
+
+	again, err := loadSkillPack(dir)
+	if err != nil || again.Hash != pack.Hash {
+		t.Fatalf("hash not stable: %q vs %q (%v)", again.Hash, pack.Hash, err)
+	}
+	writePackFiles(t, dir, map[string]string{"scripts/check.sh": "#!/bin/sh\nexit 1\n"})
+	changed, err := loadSkillPack(dir)
+	if err != nil || changed.Hash == pack.Hash {
+		t.Fatalf("hash did not change with contents (%v)", err)
+	}
+}
+
+func TestLoadSkillPacksRejectsConflicts(t *testing.T) {
+	t.Parallel()
+
+	root := t.TempDir()
+	a := filepath.Join(root, "a")
+	b := filepath.Join(root, "b")
+	writePackFiles(t, a, map[string]string{"AGENTS.md": "a"})
+	writePackFiles(t, b, map[string]string{"AGENTS.md": "b"})
+
+	if _, err := loadSkillPacks([]string{a, b}); err == nil || !strings.Contains(err.Error(), "AGENTS.md") {
+		t.Fatalf("expected file conflict, got %v", err)
+	}
+	if _, err := loadSkillPacks([]string{a, a}); err == nil || !strings.Contains(err.Error(), "more than once") {
+		t.Fatalf("expected duplicate pack error, got %v", err)
+	}
+	empty := filepath.Join(root, "empty")
+	writePackFiles(t, empty, map[string]string{"pack.toml": "name = \"empty\"\n"})
+	if _, err := loadSkillPacks([]string{empty}); err == nil {
+		t.Fatal("expected error for pack without files")
+	}
+}
+
+func TestSkillPackInstall(t *testing.T) {
+	t.Parallel()
+
+	dir := filepath.Join(t.TempDir(), "helpers")
+	writePackFiles(t, dir, map[string]string{"bin/run.sh": "#!/bin/sh\n", "NOTES.md": "notes"})
+	if err := os.Chmod(filepath.Join(dir, "bin", "run.sh"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	pack, err := loadSkillPack(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	workspace := t.TempDir()
+	if err := installSkillPacks([]SkillPack{pack}, workspace); err != nil {
+		t.Fatalf("installSkillPacks() error = %v", err)
+	}
+	info, err := os.Stat(filepath.Join(workspace, "bin", "run.sh"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	if info.Mode().Perm()&0o100 == 0 {
+		t.Fatalf("executable bit lost: %v", info.Mode())
+	}
+	if _, err := os.Stat(filepath.Join(workspace, skillPackManifestName)); err == nil {
+		t.Fatal("pack.toml should not be installed")
+	}
+
+	// A second install would replace files, which packs must never do.
+	if err := pack.install(workspace); err == nil || !strings.Contains(err.Error(), "overwrite") {
+		t.Fatalf("expected overwrite error, got %v", err)
+	}
+}
+
+func TestSkillPackPrompt(t *testing.T) {
+	t.Parallel()
+
+	if got := skillPackPrompt([]SkillPack{{Name: "silent"}}); got != "" {
+		t.Fatalf("skillPackPrompt() = %q, want empty", got)
+	}
+	got := skillPackPrompt([]SkillPack{{Name: "silent"}, {Name: "kit", Prompt: "Use ./check.sh."}})
+	if got != "\n\nADDITIONAL RESOURCES:\n\n[kit]\nUse ./check.sh." {
+		t.Fatalf("skillPackPrompt() = %q", got)
+	}
+}
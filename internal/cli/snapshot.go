@@ -0,0 +1,222 @@
+package cli
+
+import (
+	"bytes"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// attemptSnapshotsDir holds the per-attempt workspace snapshots in a task's
+// output directory.
+const attemptSnapshotsDir = "snapshots"
+
+// AttemptChange summarizes how the workspace changed from one agent attempt to
+// the next.
+type AttemptChange struct {
+	From    int    `json:"from"`
+	To      int    `json:"to"`
+	Files   int    `json:"files"`
+	Added   int    `json:"added"`
+	Removed int    `json:"removed"`
+	Diff    string `json:"diff"`
+}
+
+// attemptSnapshotPath returns the snapshot directory of a 1-based attempt,
+// relative to the task output directory.
+func attemptSnapshotPath(attempt int) string {
+	return filepath.Join(attemptSnapshotsDir, fmt.Sprintf("attempt-%d", attempt))
+}
+
+// snapshotAttempt preserves the workspace as the agent left it after an
+// attempt under taskOutputDir/snapshots/attempt-N. Files unchanged since the
+// previous attempt are hardlinked to its snapshot, so retries that change
+// little cost little disk. Build and cache directories are skipped. It returns
+// the snapshot path relative to taskOutputDir.
+func snapshotAttempt(workspace, taskOutputDir string, attempt int) (string, error) {
+	rel := attemptSnapshotPath(attempt)
+	dest := filepath.Join(taskOutputDir, rel)
+	prev := ""
+	if attempt > 1 {
+		prev = filepath.Join(taskOutputDir, attemptSnapshotPath(attempt-1))
+	} else if err := os.RemoveAll(filepath.Join(taskOutputDir, attemptSnapshotsDir)); err != nil {
+		// A resumed task starts over, so drop snapshots from the earlier run.
+		return "", fmt.Errorf("clearing old snapshots: %w", err)
+	}
+
+	err := filepath.WalkDir(workspace, func(p string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		name, err := filepath.Rel(workspace, p)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dest, name)
+		if d.IsDir() {
+			if p != workspace && replaySkipDirs[d.Name()] {
+				return filepath.SkipDir
+			}
+			return os.MkdirAll(target, 0o755)
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		info, err := d.Info()
+		if err != nil {
+			return err
+		}
+		data, err := readFilePreservingAtime(p, info)
+		if err != nil {
+			return fmt.Errorf("reading %s: %w", name, err)
+		}
+		if prev != "" {
+			prevPath := filepath.Join(prev, name)
+			if prevData, err := os.ReadFile(prevPath); err == nil && bytes.Equal(prevData, data) {
+				if err := os.Link(prevPath, target); err == nil {
+					return nil
+				}
+			}
+		}
+		return os.WriteFile(target, data, info.Mode().Perm())
+	})
+	if err != nil {
+		return "", fmt.Errorf("snapshotting attempt %d: %w", attempt, err)
+	}
+	return filepath.ToSlash(rel), nil
+}
+
+// recordAttemptSnapshot snapshots the workspace after an attempt and, from the
+// second attempt on, diffs it against the previous snapshot. Snapshots are
+// best-effort: failures are logged and never affect the task. It returns the
+// change from the previous attempt, if one was recorded.
+func recordAttemptSnapshot(result *agentExecutionResult, taskID, workspaceDir, taskOutputDir string, attempt int) *AttemptChange {
+	snapshot, err := snapshotAttempt(workspaceDir, taskOutputDir, attempt)
+	if err != nil {
+		logger.Debug("failed to snapshot attempt", "task", taskID, "attempt", attempt, "error", err)
+		return nil
+	}
+	result.snapshots = append(result.snapshots, snapshot)
+	if attempt == 1 {
+		return nil
+	}
+	change, err := diffAttemptSnapshots(taskOutputDir, attempt)
+	if err != nil {
+		logger.Debug("failed to diff attempt snapshots", "task", taskID, "attempt", attempt, "error", err)
+		return nil
+	}
+	result.attemptChanges = append(result.attemptChanges, change)
+	return &change
+}
+
+// diffAttemptSnapshots writes a unified diff from the snapshot of attempt
+// to-1 to that of attempt to as snapshots/attempt-N.diff and summarizes it.
+func diffAttemptSnapshots(taskOutputDir string, to int) (AttemptChange, error) {
+	oldDir := filepath.Join(taskOutputDir, attemptSnapshotPath(to-1))
+	newDir := filepath.Join(taskOutputDir, attemptSnapshotPath(to))
+	change := AttemptChange{From: to - 1, To: to, Diff: filepath.ToSlash(attemptSnapshotPath(to)) + ".diff"}
+
+	names, err := snapshotFileNames(oldDir, newDir)
+	if err != nil {
+		return change, err
+	}
+	var sb strings.Builder
+	for _, name := range names {
+		oldPath := filepath.Join(oldDir, filepath.FromSlash(name))
+		newPath := filepath.Join(newDir, filepath.FromSlash(name))
+		// Unchanged files are hardlinked between snapshots.
+		if a, err := os.Stat(oldPath); err == nil {
+			if b, err := os.Stat(newPath); err == nil && os.SameFile(a, b) {
+				continue
+			}
+		}
+		out, err := unifiedDiff(oldPath, newPath, name)
+		if err != nil {
+			return change, err
+		}
+		if out == "" {
+			continue
+		}
+		change.Files++
+		for _, line := range strings.Split(out, "\n") {
+			switch {
+			case strings.HasPrefix(line, "+++ "), strings.HasPrefix(line, "--- "):
+			case strings.HasPrefix(line, "+"):
+				change.Added++
+			case strings.HasPrefix(line, "-"):
+				change.Removed++
+			}
+		}
+		sb.WriteString(out)
+	}
+	if err := os.WriteFile(filepath.Join(taskOutputDir, filepath.FromSlash(change.Diff)), []byte(sb.String()), 0o644); err != nil {
+		return change, fmt.Errorf("writing attempt diff: %w", err)
+	}
+	return change, nil
+}
+
+// snapshotFileNames returns the sorted, slash-separated paths of files in
+// either snapshot directory.
+func snapshotFileNames(dirs ...string) ([]string, error) {
+	seen := make(map[string]bool)
+	for _, dir := range dirs {
+		err := filepath.WalkDir(dir, func(p string, d fs.DirEntry, err error) error {
+			if err != nil || d.IsDir() {
+				return err
+			}
+			rel, err := filepath.Rel(dir, p)
+			if err != nil {
+				return err
+			}
+			seen[filepath.ToSlash(rel)] = true
+			return nil
+		})
+		if err != nil {
+			return nil, fmt.Errorf("listing snapshot files: %w", err)
+		}
+	}
+	names := make([]string, 0, len(seen))
+	for name := range seen {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names, nil
+}
+
+// writeAgentAttemptChangeFooter notes in the agent log what the attempt changed
+// relative to the previous one.
+func writeAgentAttemptChangeFooter(agentLogPath string, change AttemptChange) {
+	f, err := os.OpenFile(agentLogPath, os.O_APPEND|os.O_WRONLY, 0o644)
+	if err != nil {
+		return
+	}
+	defer func() { _ = f.Close() }()
+	_, _ = fmt.Fprintf(f,
+		"\n\nHARNESS: attempt changes (from=%d to=%d files=%d added=%d removed=%d diff=%s)\n",
+		change.From, change.To, change.Files, change.Added, change.Removed, change.Diff)
+}
+
+// writeReportAttemptChanges shows, for tasks that needed more than one agent
+// attempt, how much the workspace changed between consecutive attempts.
+func writeReportAttemptChanges(sb *strings.Builder, summary EvalSummary) {
+	var rows []string
+	for _, r := range summary.Results {
+		for _, c := range r.AttemptChanges {
+			rows = append(rows, fmt.Sprintf("| %s | %d → %d | %d | +%d / -%d | %s |",
+				r.Task, c.From, c.To, c.Files, c.Added, c.Removed, c.Diff))
+		}
+	}
+	if len(rows) == 0 {
+		return
+	}
+	sb.WriteString("## Attempt Changes\n\n")
+	sb.WriteString("| Task | Attempts | Files | Lines | Diff |\n")
+	sb.WriteString("|------|----------|-------|-------|------|\n")
+	for _, row := range rows {
+		sb.WriteString(row + "\n")
+	}
+	sb.WriteString("\n")
+}
@@ -0,0 +1,119 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestSnapshotAttempt(t *testing.T) {
+	t.Parallel()
+
+	workspace := t.TempDir()
+	out := t.TempDir()
+	write := func(rel, content string) {
+		t.Helper()
+		path := filepath.Join(workspace, filepath.FromSlash(rel))
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	write("main.go", "package main\n")
+	write("pkg/util.go", "package pkg\n")
+	write("node_modules/dep/index.js", "module.exports = {}\n")
+
+	rel, err := snapshotAttempt(workspace, out, 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if rel != "snapshots/attempt-1" {
+		t.Fatalf("snapshot path = %q", rel)
+	}
+	if _, err := os.Stat(filepath.Join(out, rel, "node_modules")); !os.IsNotExist(err) {
+		t.Fatalf("node_modules should be skipped, stat err = %v", err)
+	}
+
+	write("main.go", "package main\n\nfunc main() {}\n")
+	if _, err := snapshotAttempt(workspace, out, 2); err != nil {
+		t.Fatal(err)
+	}
+
+	first := filepath.Join(out, "snapshots", "attempt-1")
+	second := filepath.Join(out, "snapshots", "attempt-2")
+	if data, err := os.ReadFile(filepath.Join(first, "main.go")); err != nil || string(data) != "package main\n" {
+		t.Fatalf("attempt-1 main.go = %q, %v", data, err)
+	}
+	if data, err := os.ReadFile(filepath.Join(second, "main.go")); err != nil || string(data) != "package main\n\nfunc main() {}\n" {
+		t.Fatalf("attempt-2 main.go = %q, %v", data, err)
+	}
+	a, errA := os.Stat(filepath.Join(first, "pkg", "util.go"))
+	b, errB := os.Stat(filepath.Join(second, "pkg", "util.go"))
+	if errA != nil || errB != nil {
+		t.Fatalf("stat unchanged file: %v, %v", errA, errB)
+	}
+	if !os.SameFile(a, b) {
+		t.Fatal("unchanged file should be hardlinked to the previous snapshot")
+	}
+
+	// Starting over from attempt 1 drops snapshots of the earlier run.
+	if _, err := snapshotAttempt(workspace, out, 1); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := os.Stat(second); !os.IsNotExist(err) {
+		t.Fatalf("stale attempt-2 snapshot should be removed, stat err = %v", err)
+	}
+}
+
+func TestDiffAttemptSnapshots(t *testing.T) {
+	t.Parallel()
+
+	workspace := t.TempDir()
+	out := t.TempDir()
+	write := func(rel, content string) {
+		t.Helper()
+		if err := os.WriteFile(filepath.Join(workspace, rel), []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	write("main.go", "package main\n\nfunc a() {}\n")
+	write("same.go", "package main\n")
+	write("old.go", "package main\n")
+
+	var result agentExecutionResult
+	if change := recordAttemptSnapshot(&result, "go/example", workspace, out, 1); change != nil {
+		t.Fatalf("first attempt should have no change, got %+v", change)
+	}
+	write("main.go", "package main\n\nfunc b() {}\n")
+	write("new.go", "package main\n\nvar x = 1\n")
+	if err := os.Remove(filepath.Join(workspace, "old.go")); err != nil {
+		t.Fatal(err)
+	}
+	change := recordAttemptSnapshot(&result, "go/example", workspace, out, 2)
+	if change == nil {
+		t.Fatal("expected a change for the second attempt")
+	}
+
+	want := AttemptChange{From: 1, To: 2, Files: 3, Added: 4, Removed: 2, Diff: "snapshots/attempt-2.diff"}
+	if *change != want {
+		t.Fatalf("change = %+v, want %+v", *change, want)
+	}
+	if len(result.snapshots) != 2 || len(result.attemptChanges) != 1 {
+		t.Fatalf("result = %+v", result)
+	}
+	diff, err := os.ReadFile(filepath.Join(out, "snapshots", "attempt-2.diff"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, label := range []string{"+++ b/main.go", "+++ b/new.go", "--- a/old.go"} {
+		if !strings.Contains(string(diff), label) {
+			t.Fatalf("diff missing %q:\n%s", label, diff)
+		}
+	}
+	if strings.Contains(string(diff), "same.go") {
+		t.Fatalf("unchanged file in diff:\n%s", diff)
+	}
+}
@@ -0,0 +1,359 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"os"
+	"regexp"
+	"strings"
+	"text/tabwriter"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+var (
+	tasksListTasks      string
+	tasksListLang       string
+	tasksListTier       string
+	tasksListDifficulty string
+	tasksListFilter     string
+	tasksListExclude    string
+	tasksListTags       string
+	tasksListJSON       bool
+	tasksShowJSON       bool
+	tasksShowMCPTools   bool
+	tasksShowSkills     bool
+)
+
+// testNamePatterns extract test names from visible test files per language.
+var testNamePatterns = map[task.Language]*regexp.Regexp{
+	task.Go:         regexp.MustCompile(`(?m)^func (Test\w+)\(`),
+	task.Rust:       regexp.MustCompile(`#\[(?:tokio::)?test\]\s*(?:async\s+)?fn\s+(\w+)`),
+	task.TypeScript: regexp.MustCompile("\\b(?:it|test)\\(\\s*(?:\"([^\"]+)\"|'([^']+)'|`([^`]+)`)"),
+	task.Kotlin:     regexp.MustCompile("@Test\\s+fun\\s+`?([^`(]+?)`?\\s*\\("),
+	task.Dart:       regexp.MustCompile(`\btest\(\s*(?:"([^"]+)"|'([^']+)')`),
+	task.Zig:        regexp.MustCompile(`(?m)^test\s+"([^"]+)"`),
+}
+
+// TaskDetail is the information shown by tasks show. Hidden test contents
+// are deliberately excluded.
+type TaskDetail struct {
+	ID                string      `json:"id"`
+	Name              string      `json:"name"`
+	Language          string      `json:"language"`
+	Tier              string      `json:"tier"`
+	Difficulty        string      `json:"difficulty"`
+	Tags              []string    `json:"tags,omitempty"`
+	Description       string      `json:"description"`
+	Weight            task.Weight `json:"weight"`
+	Image             string      `json:"image"`
+	ValidationTimeout int         `json:"validation_timeout_seconds"`
+	AgentTimeoutFloor int         `json:"agent_timeout_floor_seconds,omitempty"`
+	ValidationCommand string      `json:"validation_command"`
+	StubFiles         []string    `json:"stub_files"`
+	TestFiles         []string    `json:"test_files"`
+	TestNames         []string    `json:"test_names"`
+	SupportFiles      []string    `json:"support_files,omitempty"`
+	HasHiddenTests    bool        `json:"has_hidden_tests"`
+	Prompt            string      `json:"prompt"`
+	FollowUps         []string    `json:"follow_ups,omitempty"`
+}
+
+// TaskListing is a task with its computed scoring weight, as shown by tasks list.
+type TaskListing struct {
+	*task.Task
+	ID     string  `json:"id"`
+	Weight float64 `json:"weight"`
+}
+
+var tasksCmd = &cobra.Command{
+	Use:   "tasks",
+	Short: "Inspect the task registry",
+}
+
+var tasksListCmd = &cobra.Command{
+	Use:   "list",
+	Short: "List tasks with tier, tags, and weight",
+	Long: `Lists tasks with their language, tier, difficulty, scoring weight, tags, and
+description.
+
+Accepts the same selectors as eval, so you can see exactly what a run would
+contain before starting it. Unlike eval, the default tier is all.`,
+	Example: `  sanity tasks list
+  sanity tasks list --tier core
+  sanity tasks list --lang rust --difficulty expert
+  sanity tasks list --filter "go/errgroup-*" --tag concurrency
+  sanity tasks list --tasks go/bank-account,react --json`,
+	RunE: func(cmd *cobra.Command, args []string) error {
+		selected, err := selectTasks(tasksListTasks, tasksListLang, tasksListTier, tasksListDifficulty)
+		if err != nil {
+			return err
+		}
+		filter, err := task.ParseFilter(tasksListFilter, tasksListExclude, tasksListTags)
+		if err != nil {
+			return err
+		}
+		selected = filter.Apply(selected)
+
+		listings := make([]TaskListing, 0, len(selected))
+		for _, t := range selected {
+			listings = append(listings, TaskListing{Task: t, ID: t.ID(), Weight: taskWeight(t).Base})
+		}
+
+		if tasksListJSON {
+			enc := json.NewEncoder(os.Stdout)
+			enc.SetIndent("", "  ")
+			return enc.Encode(listings)
+		}
+		return outputTaskListings(listings)
+	},
+}
+
+var tasksShowCmd = &cobra.Command{
+	Use:   "show <task>",
+	Short: "Show a task's prompt, files, tests, and settings",
+	Long: `Shows a task's rendered agent prompt, stub files, visible test names,
+timeouts, scoring weight, and the container image it runs in.
+
+Hidden tests are only reported as present or absent; their contents and
+names are never shown.`,
+	Example: `  sanity tasks show go/bank-account
+  sanity tasks show regex-lite --use-skills
+  sanity tasks show zig/comptime-json --json`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		loader := task.NewLoader(tasks.FS, tasksDir)
+		allTasks, err := loader.LoadAll()
+		if err != nil {
+			return fmt.Errorf("loading tasks: %w", err)
+		}
+		t, err := task.ResolveRef(allTasks, args[0])
+		if err != nil {
+			return err
+		}
+
+		detail := buildTaskDetail(loader, t, tasksShowMCPTools, tasksShowSkills)
+		if tasksShowJSON {
+			enc := json.NewEncoder(os.Stdout)
+			enc.SetIndent("", "  ")
+			return enc.Encode(detail)
+		}
+		printTaskDetail(detail)
+		return nil
+	},
+}
+
+func init() {
+	tasksListCmd.Flags().StringVar(&tasksListTasks, "tasks", "", "comma-separated list of task slugs or IDs")
+	tasksListCmd.Flags().StringVar(&tasksListLang, "lang", "", "filter by language (go, rust, typescript, kotlin, dart, zig)")
+	tasksListCmd.Flags().StringVar(&tasksListTier, "tier", "all", "filter by tier (core, extended, all)")
+	tasksListCmd.Flags().StringVar(&tasksListDifficulty, "difficulty", "", "filter by difficulty (comma-separated)")
+	tasksListCmd.Flags().StringVar(&tasksListFilter, "filter", "", "comma-separated task ID globs, e.g. \"rust/*\"")
+	tasksListCmd.Flags().StringVar(&tasksListTags, "tag", "", "only tasks with one of these comma-separated tags")
+	tasksListCmd.Flags().StringVar(&tasksListExclude, "exclude", "", "comma-separated task ID globs to leave out")
+	tasksListCmd.Flags().BoolVar(&tasksListJSON, "json", false, "output as JSON")
+
+	tasksShowCmd.Flags().BoolVar(&tasksShowJSON, "json", false, "output as JSON")
+	tasksShowCmd.Flags().BoolVar(&tasksShowMCPTools, "use-mcp-tools", false, "render the prompt as eval --use-mcp-tools would")
+	tasksShowCmd.Flags().BoolVar(&tasksShowSkills, "use-skills", false, "render the prompt as eval --use-skills would")
+
+	tasksCmd.AddCommand(tasksListCmd)
+	tasksCmd.AddCommand(tasksShowCmd)
+}
+
+// selectTasks loads all tasks and applies eval-style selectors, validating
+// the language and tier values instead of silently ignoring them.
+func selectTasks(refs, lang, tier, difficulty string) ([]*task.Task, error) {
+	if lang != "" {
+		if _, err := task.ParseLanguage(lang); err != nil {
+			return nil, err
+		}
+	}
+	if tier != "" && tier != "all" && tier != "core" && tier != "extended" {
+		return nil, fmt.Errorf("invalid tier %q: must be 'core', 'extended', or 'all'", tier)
+	}
+
+	allTasks, err := task.NewLoader(tasks.FS, tasksDir).LoadAll()
+	if err != nil {
+		return nil, fmt.Errorf("loading tasks: %w", err)
+	}
+	return filterTasksForShared(allTasks, SharedConfig{
+		Tasks:      refs,
+		Lang:       lang,
+		Tier:       tier,
+		Difficulty: difficulty,
+	}), nil
+}
+
+func outputTaskListings(listings []TaskListing) error {
+	if len(listings) == 0 {
+		fmt.Println("No tasks found.")
+		return nil
+	}
+
+	w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+	_, _ = fmt.Fprintln(w, "ID\tLANGUAGE\tTIER\tDIFFICULTY\tWEIGHT\tTAGS\tDESCRIPTION")
+	_, _ = fmt.Fprintln(w, "--\t--------\t----\t----------\t------\t----\t-----------")
+
+	totalWeight := 0.0
+	for _, l := range listings {
+		_, _ = fmt.Fprintf(w, "%s\t%s\t%s\t%s\t%.2f\t%s\t%s\n",
+			l.ID, l.Language, l.Tier, l.Difficulty, l.Weight, strings.Join(l.Tags, ","), truncateDescription(l.Description))
+		totalWeight += l.Weight
+	}
+	if err := w.Flush(); err != nil {
+		return err
+	}
+
+	fmt.Printf("\n%d tasks, total weight %.2f\n", len(listings), totalWeight)
+	return nil
+}
+
+// truncateDescription shortens a task description to the 50 characters the
+// tasks list column allows, cutting between runes so the text stays valid UTF-8.
+func truncateDescription(desc string) string {
+	runes := []rune(desc)
+	if len(runes) <= 50 {
+		return desc
+	}
+	return string(runes[:47]) + "..."
+}
+
+// buildTaskDetail collects everything tasks show prints for t.
+func buildTaskDetail(loader *task.Loader, t *task.Task, useMCPTools, useSkills bool) TaskDetail {
+	detail := TaskDetail{
+		ID:                t.ID(),
+		Name:              t.Name,
+		Language:          string(t.Language),
+		Tier:              t.Tier,
+		Difficulty:        t.Difficulty,
+		Tags:              t.Tags,
+		Description:       t.Description,
+		Weight:            taskWeight(t),
+		ValidationTimeout: t.Timeout,
+		AgentTimeoutFloor: t.AgentTimeout,
+		ValidationCommand: strings.TrimSpace(t.Validation.Command + " " + strings.Join(t.Validation.Args, " ")),
+		HasHiddenTests:    len(t.Files.HiddenTest) > 0,
+		Prompt:            buildAgentPrompt(t, useMCPTools, useSkills, ""),
+	}
+
+	c := cfg
+	if c == nil {
+		c = &config.Default
+	}
+	detail.Image = c.ImageForTask(t.ID(), string(t.Language))
+	if image, err := runner.TaskImage(c, loader, t); err == nil {
+		detail.Image = image
+	}
+	if o := c.TaskOverride(t.ID()); o.Timeout > 0 {
+		detail.ValidationTimeout = o.Timeout
+	}
+	if detail.ValidationTimeout <= 0 {
+		detail.ValidationTimeout = c.Harness.DefaultTimeout
+	}
+
+	for _, f := range t.Files.Stub {
+		detail.StubFiles = append(detail.StubFiles, task.StripTxtExtension(f))
+	}
+	for _, f := range t.Files.Support {
+		detail.SupportFiles = append(detail.SupportFiles, task.StripTxtExtension(f))
+	}
+	for _, f := range t.Files.Test {
+		detail.TestFiles = append(detail.TestFiles, task.StripTxtExtension(f))
+		data, err := loader.ReadTaskFile(t, f)
+		if err != nil {
+			continue
+		}
+		detail.TestNames = append(detail.TestNames, extractTestNames(t.Language, string(data))...)
+	}
+	for _, f := range t.FollowUps {
+		detail.FollowUps = append(detail.FollowUps, strings.TrimSpace(f.Prompt))
+	}
+	return detail
+}
+
+// extractTestNames returns the test names declared in a test file's source.
+func extractTestNames(lang task.Language, src string) []string {
+	re := testNamePatterns[lang]
+	if re == nil {
+		return nil
+	}
+	matches := re.FindAllStringSubmatch(src, -1)
+	names := make([]string, 0, len(matches))
+	for _, m := range matches {
+		// Patterns with one alternative per quote style capture into different groups.
+		for _, g := range m[1:] {
+			if g != "" {
+				names = append(names, strings.TrimSpace(g))
+				break
+			}
+		}
+	}
+	return names
+}
+
+// printTaskDetail prints a task detail in the terminal layout used by show.
+func printTaskDetail(d TaskDetail) {
+	fmt.Println()
+	fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+	fmt.Printf(" %s (%s)\n", d.Name, d.ID)
+	fmt.Println("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")
+	fmt.Printf(" %s\n\n", d.Description)
+	fmt.Printf(" Tier:        %s\n", d.Tier)
+	fmt.Printf(" Difficulty:  %s\n", d.Difficulty)
+	if len(d.Tags) > 0 {
+		fmt.Printf(" Tags:        %s\n", strings.Join(d.Tags, ", "))
+	}
+	fmt.Printf(" Weight:      %.2f (lang rarity %.1f, esoteric %.1f, algorithm %.1f, edge cases %.1f, novelty %.1f)\n",
+		d.Weight.Base, d.Weight.LangRarity, d.Weight.EsotericFeature, d.Weight.NovelAlgorithm,
+		d.Weight.EdgeCaseDensity, d.Weight.NovelProblem)
+	fmt.Printf(" Image:       %s\n", d.Image)
+	fmt.Printf(" Validation:  %s (timeout %ds)\n", d.ValidationCommand, d.ValidationTimeout)
+	if d.AgentTimeoutFloor > 0 {
+		fmt.Printf(" Agent floor: %ds\n", d.AgentTimeoutFloor)
+	}
+	hidden := "no"
+	if d.HasHiddenTests {
+		hidden = "yes (run during eval only; not shown)"
+	}
+	fmt.Printf(" Hidden tests: %s\n", hidden)
+
+	fmt.Println()
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	fmt.Println(" FILES")
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	fmt.Printf(" Stub:    %s\n", strings.Join(d.StubFiles, ", "))
+	fmt.Printf(" Tests:   %s\n", strings.Join(d.TestFiles, ", "))
+	if len(d.SupportFiles) > 0 {
+		fmt.Printf(" Support: %s\n", strings.Join(d.SupportFiles, ", "))
+	}
+
+	fmt.Println()
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	fmt.Printf(" VISIBLE TESTS (%d)\n", len(d.TestNames))
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	for _, name := range d.TestNames {
+		fmt.Printf("   • %s\n", name)
+	}
+
+	fmt.Println()
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	fmt.Println(" PROMPT")
+	fmt.Println("─────────────────────────────────────────────────────────────")
+	fmt.Println(d.Prompt)
+
+	if len(d.FollowUps) > 0 {
+		fmt.Println()
+		fmt.Println("─────────────────────────────────────────────────────────────")
+		fmt.Printf(" FOLLOW-UPS (%d, each given once the previous turn passes)\n", len(d.FollowUps))
+		fmt.Println("─────────────────────────────────────────────────────────────")
+		for i, prompt := range d.FollowUps {
+			fmt.Printf("   %d. %s\n", i+1, prompt)
+		}
+	}
+}
@@ -0,0 +1,131 @@
+package cli
+
+import (
+	"slices"
+	"strings"
+	"testing"
+	"unicode/utf8"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+func TestSelectTasks(t *testing.T) {
+	t.Parallel()
+
+	core, err := selectTasks("", "", "core", "")
+	if err != nil {
+		t.Fatalf("selectTasks() error = %v", err)
+	}
+	all, err := selectTasks("", "", "all", "")
+	if err != nil {
+		t.Fatalf("selectTasks() error = %v", err)
+	}
+	if len(core) == 0 || len(core) >= len(all) {
+		t.Fatalf("expected core (%d) to be a non-empty subset of all (%d)", len(core), len(all))
+	}
+	for _, tk := range core {
+		if tk.Tier != "core" {
+			t.Fatalf("task %s has tier %q, want core", tk.ID(), tk.Tier)
+		}
+	}
+
+	picked, err := selectTasks("go/bank-account", "", "all", "")
+	if err != nil {
+		t.Fatalf("selectTasks() error = %v", err)
+	}
+	if len(picked) != 1 || picked[0].ID() != "go/bank-account" {
+		t.Fatalf("unexpected selection: %v", picked)
+	}
+	if len(picked[0].Tags) == 0 {
+		t.Fatal("expected go/bank-account to have tags")
+	}
+
+	if _, err := selectTasks("", "cobol", "", ""); err == nil {
+		t.Fatal("expected error for unknown language")
+	}
+	if _, err := selectTasks("", "", "premium", ""); err == nil {
+		t.Fatal("expected error for unknown tier")
+	}
+}
+
+func TestBuildTaskDetail(t *testing.T) {
+	t.Parallel()
+
+	loader := task.NewLoader(tasks.FS, tasksDir)
+	allTasks, err := loader.LoadAll()
+	if err != nil {
+		t.Fatalf("load tasks: %v", err)
+	}
+	glob, err := task.ResolveRef(allTasks, "typescript/glob")
+	if err != nil {
+		t.Fatalf("resolve task: %v", err)
+	}
+
+	detail := buildTaskDetail(loader, glob, false, false)
+	if !detail.HasHiddenTests {
+		t.Fatal("expected hidden tests to be reported as present")
+	}
+	if !slices.Contains(detail.TestNames, "matches exact strings") {
+		t.Fatalf("expected visible test name, got %v", detail.TestNames)
+	}
+	if slices.Contains(detail.TestNames, "treats consecutive '*' as a single '*'") {
+		t.Fatal("hidden test name leaked into detail")
+	}
+	if detail.Image == "" || detail.ValidationTimeout <= 0 {
+		t.Fatalf("expected image and timeout, got %q / %d", detail.Image, detail.ValidationTimeout)
+	}
+	if !strings.Contains(detail.Prompt, "glob.ts") {
+		t.Fatalf("expected prompt to mention stub file, got: %s", detail.Prompt)
+	}
+}
+
+func TestExtractTestNames(t *testing.T) {
+	t.Parallel()
+
+	rust := "#[test]\nfn hashmap_empty() {}\n\n#[tokio::test]\nasync fn runs_async() {}\n"
+	if got := extractTestNames(task.Rust, rust); !slices.Equal(got, []string{"hashmap_empty", "runs_async"}) {
+		t.Fatalf("rust names = %v", got)
+	}
+	kotlin := "@Test\n    fun `capacity must be positive`() {}\n@Test\nfun plainName() {}\n"
+	if got := extractTestNames(task.Kotlin, kotlin); !slices.Equal(got, []string{"capacity must be positive", "plainName"}) {
+		t.Fatalf("kotlin names = %v", got)
+	}
+}
+
+func TestFilterTasksForSharedGlobs(t *testing.T) {
+	t.Parallel()
+
+	allTasks, err := task.NewLoader(tasks.FS, tasksDir).LoadAll()
+	if err != nil {
+		t.Fatalf("load tasks: %v", err)
+	}
+	selected := filterTasksForShared(allTasks, SharedConfig{Filter: "rust/*", Exclude: "rust/macros"})
+	if len(selected) == 0 {
+		t.Fatal("expected rust tasks")
+	}
+	for _, tk := range selected {
+		if tk.Language != task.Rust || tk.ID() == "rust/macros" {
+			t.Errorf("unexpected task %s", tk.ID())
+		}
+	}
+
+	tagged := filterTasksForShared(allTasks, SharedConfig{Tags: "concurrency"})
+	if !slices.ContainsFunc(tagged, func(tk *task.Task) bool { return tk.ID() == "go/bank-account" }) {
+		t.Error("expected go/bank-account to be tagged concurrency")
+	}
+}
+
+func TestTruncateDescription(t *testing.T) {
+	t.Parallel()
+
+	short := "Implement a thread-safe bank account"
+	if got := truncateDescription(short); got != short {
+		t.Errorf("truncateDescription(%q) = %q, want it unchanged", short, got)
+	}
+	long := strings.Repeat("é", 46) + "→ and then some more"
+	got := truncateDescription(long)
+	if !utf8.ValidString(got) || utf8.RuneCountInString(got) != 50 || !strings.HasSuffix(got, "é→...") {
+		t.Errorf("truncateDescription(%q) = %q, want 47 runes and an ellipsis", long, got)
+	}
+}
@@ -0,0 +1,407 @@
+package cli
+
+import (
+	"bufio"
+	"cmp"
+	"context"
+	"crypto/rand"
+	"crypto/sha256"
+	"crypto/subtle"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/fs"
+	"net/http"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/BurntSushi/toml"
+	"github.com/spf13/cobra"
+)
+
+// teamStoreName is the append-only results store in the server's results
+// directory.
+const teamStoreName = "team-results.jsonl"
+
+// Sources of a team results record.
+const (
+	teamSourceRun    = "run"    // An eval the server ran
+	teamSourceUpload = "upload" // A submission.json a user uploaded
+)
+
+// singleTokenUser is the user every request acts as without --users.
+const singleTokenUser = "admin"
+
+var addUserAdmin bool
+
+// teamUser is one [[users]] entry of the --users file. Only a hash of each
+// token is stored.
+type teamUser struct {
+	Name        string `toml:"name"`
+	TokenSHA256 string `toml:"token_sha256"`
+	Admin       bool   `toml:"admin"` // May cancel every user's runs
+}
+
+// teamUsersFile is the --users file.
+type teamUsersFile struct {
+	Users []teamUser `toml:"users"`
+}
+
+// apiUser is the user a request is authenticated as.
+type apiUser struct {
+	Name  string
+	Admin bool
+}
+
+type apiUserKey struct{}
+
+// requestUser returns the user requireToken authenticated.
+func requestUser(r *http.Request) apiUser {
+	u, _ := r.Context().Value(apiUserKey{}).(apiUser)
+	return u
+}
+
+// withUser returns r carrying the authenticated user.
+func withUser(r *http.Request, u apiUser) *http.Request {
+	return r.WithContext(context.WithValue(r.Context(), apiUserKey{}, u))
+}
+
+// hashToken returns the hex SHA-256 of an API token.
+func hashToken(token string) string {
+	sum := sha256.Sum256([]byte(token))
+	return hex.EncodeToString(sum[:])
+}
+
+// loadTeamUsers reads and checks a --users file.
+func loadTeamUsers(path string) ([]teamUser, error) {
+	var f teamUsersFile
+	if _, err := toml.DecodeFile(path, &f); err != nil {
+		return nil, fmt.Errorf("reading users file: %w", err)
+	}
+	seen := make(map[string]bool, len(f.Users))
+	for i, u := range f.Users {
+		if u.Name == "" {
+			return nil, fmt.Errorf("users file %s: users[%d] has no name", path, i)
+		}
+		if seen[u.Name] {
+			return nil, fmt.Errorf("users file %s: duplicate user %q", path, u.Name)
+		}
+		seen[u.Name] = true
+		if b, err := hex.DecodeString(u.TokenSHA256); err != nil || len(b) != sha256.Size {
+			return nil, fmt.Errorf("users file %s: user %q needs a hex token_sha256", path, u.Name)
+		}
+	}
+	if len(f.Users) == 0 {
+		return nil, fmt.Errorf("users file %s defines no users", path)
+	}
+	return f.Users, nil
+}
+
+// authenticate returns the user whose token matches, comparing every hash in
+// constant time.
+func (s *apiServer) authenticate(token string) (apiUser, bool) {
+	if s.users == nil {
+		if subtle.ConstantTimeCompare([]byte(token), []byte(s.token)) == 1 {
+			return apiUser{Name: singleTokenUser, Admin: true}, true
+		}
+		return apiUser{}, false
+	}
+	hash := []byte(hashToken(token))
+	var found apiUser
+	ok := false
+	for _, u := range s.users {
+		if subtle.ConstantTimeCompare(hash, []byte(strings.ToLower(u.TokenSHA256))) == 1 {
+			found, ok = apiUser{Name: u.Name, Admin: u.Admin}, true
+		}
+	}
+	return found, ok
+}
+
+// loadTeam switches the server to team mode with the users in usersFile, if
+// set, and loads the results store.
+func (s *apiServer) loadTeam(usersFile string) error {
+	if usersFile != "" {
+		users, err := loadTeamUsers(usersFile)
+		if err != nil {
+			return err
+		}
+		s.users = users
+	}
+	store, err := openTeamStore(s.store.path)
+	if err != nil {
+		return err
+	}
+	s.store = store
+	return nil
+}
+
+// TeamRecord is one entry of the append-only results store.
+type TeamRecord struct {
+	ID          string                `json:"id"`
+	User        string                `json:"user"`
+	Source      string                `json:"source"`
+	RunID       string                `json:"run_id,omitempty"`
+	SubmittedAt time.Time             `json:"submitted_at"`
+	Submission  LeaderboardSubmission `json:"submission"`
+}
+
+// LeaderboardEntry is one row of GET /api/v1/leaderboard: the best record of
+// an agent, model, and reasoning setting.
+type LeaderboardEntry struct {
+	Rank             int       `json:"rank"`
+	Agent            string    `json:"agent"`
+	Model            string    `json:"model,omitempty"`
+	Reasoning        string    `json:"reasoning,omitempty"`
+	WeightedPassRate float64   `json:"weighted_pass_rate"`
+	PassRate         float64   `json:"pass_rate"`
+	Passed           int       `json:"passed"`
+	Total            int       `json:"total"`
+	User             string    `json:"user"`
+	RecordID         string    `json:"record_id"`
+	SubmittedAt      time.Time `json:"submitted_at"`
+	Submissions      int       `json:"submissions"`
+}
+
+// teamStore is the append-only results store. Records are only ever
+// appended, one JSON line each, so the file doubles as an audit log.
+type teamStore struct {
+	path string
+
+	mu      sync.Mutex
+	records []TeamRecord
+}
+
+// openTeamStore loads the records already in the store at path.
+func openTeamStore(path string) (*teamStore, error) {
+	st := &teamStore{path: path}
+	f, err := os.Open(path)
+	if errors.Is(err, fs.ErrNotExist) {
+		return st, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("opening results store: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+	sc := bufio.NewScanner(f)
+	sc.Buffer(make([]byte, 0, 64*1024), 16*1024*1024)
+	for line := 1; sc.Scan(); line++ {
+		if len(strings.TrimSpace(sc.Text())) == 0 {
+			continue
+		}
+		var rec TeamRecord
+		if err := json.Unmarshal(sc.Bytes(), &rec); err != nil {
+			return nil, fmt.Errorf("results store %s line %d: %w", path, line, err)
+		}
+		st.records = append(st.records, rec)
+	}
+	if err := sc.Err(); err != nil {
+		return nil, fmt.Errorf("reading results store: %w", err)
+	}
+	return st, nil
+}
+
+// add durably appends a record to the store, assigning its ID and time.
+func (st *teamStore) add(user, source, runID string, sub LeaderboardSubmission) (TeamRecord, error) {
+	suffix := make([]byte, 4)
+	if _, err := rand.Read(suffix); err != nil {
+		return TeamRecord{}, fmt.Errorf("generating record id: %w", err)
+	}
+	now := time.Now().UTC()
+	rec := TeamRecord{
+		ID:          fmt.Sprintf("%s-%s", now.Format("20060102T150405"), hex.EncodeToString(suffix)),
+		User:        user,
+		Source:      source,
+		RunID:       runID,
+		SubmittedAt: now,
+		Submission:  sub,
+	}
+	line, err := json.Marshal(rec)
+	if err != nil {
+		return TeamRecord{}, fmt.Errorf("encoding record: %w", err)
+	}
+
+	st.mu.Lock()
+	defer st.mu.Unlock()
+	if err := os.MkdirAll(filepath.Dir(st.path), 0o755); err != nil {
+		return TeamRecord{}, fmt.Errorf("creating results store directory: %w", err)
+	}
+	f, err := os.OpenFile(st.path, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0o644)
+	if err != nil {
+		return TeamRecord{}, fmt.Errorf("opening results store: %w", err)
+	}
+	defer func() { _ = f.Close() }()
+	if _, err := f.Write(append(line, '\n')); err != nil {
+		return TeamRecord{}, fmt.Errorf("writing results store: %w", err)
+	}
+	if err := f.Sync(); err != nil {
+		return TeamRecord{}, fmt.Errorf("syncing results store: %w", err)
+	}
+	st.records = append(st.records, rec)
+	return rec, nil
+}
+
+// list returns the records, newest first, optionally only a user's.
+func (st *teamStore) list(user string) []TeamRecord {
+	st.mu.Lock()
+	defer st.mu.Unlock()
+	out := make([]TeamRecord, 0, len(st.records))
+	for i := len(st.records) - 1; i >= 0; i-- {
+		if user == "" || st.records[i].User == user {
+			out = append(out, st.records[i])
+		}
+	}
+	return out
+}
+
+// leaderboard ranks the best record of each agent, model, and reasoning
+// setting by weighted pass rate, then pass rate.
+func (st *teamStore) leaderboard() []LeaderboardEntry {
+	type key struct{ agent, model, reasoning string }
+	st.mu.Lock()
+	best := make(map[key]*LeaderboardEntry)
+	var order []key
+	for _, rec := range st.records {
+		sub := rec.Submission
+		k := key{sub.Agent, sub.Model, sub.Reasoning}
+		e, ok := best[k]
+		if !ok {
+			e = &LeaderboardEntry{Agent: sub.Agent, Model: sub.Model, Reasoning: sub.Reasoning}
+			best[k] = e
+			order = append(order, k)
+		}
+		e.Submissions++
+		if e.Submissions == 1 || sub.WeightedPassRate > e.WeightedPassRate ||
+			(sub.WeightedPassRate == e.WeightedPassRate && sub.PassRate > e.PassRate) {
+			e.WeightedPassRate = sub.WeightedPassRate
+			e.PassRate = sub.PassRate
+			e.Passed = sub.Passed
+			e.Total = sub.Total
+			e.User = rec.User
+			e.RecordID = rec.ID
+			e.SubmittedAt = rec.SubmittedAt
+		}
+	}
+	st.mu.Unlock()
+
+	out := make([]LeaderboardEntry, 0, len(order))
+	for _, k := range order {
+		out = append(out, *best[k])
+	}
+	slices.SortStableFunc(out, func(a, b LeaderboardEntry) int {
+		if c := cmp.Compare(b.WeightedPassRate, a.WeightedPassRate); c != 0 {
+			return c
+		}
+		return cmp.Compare(b.PassRate, a.PassRate)
+	})
+	for i := range out {
+		out[i].Rank = i + 1
+	}
+	return out
+}
+
+func (s *apiServer) handleSubmit(w http.ResponseWriter, r *http.Request) {
+	var sub LeaderboardSubmission
+	if err := json.NewDecoder(http.MaxBytesReader(w, r.Body, 8<<20)).Decode(&sub); err != nil {
+		writeAPIError(w, http.StatusBadRequest, fmt.Sprintf("invalid submission: %v", err))
+		return
+	}
+	if sub.Agent == "" || sub.Total <= 0 {
+		writeAPIError(w, http.StatusBadRequest, "submission needs an agent and at least one result; upload the run's submission.json")
+		return
+	}
+	rec, err := s.store.add(requestUser(r).Name, teamSourceUpload, "", sub)
+	if err != nil {
+		writeAPIError(w, http.StatusInternalServerError, err.Error())
+		return
+	}
+	writeJSON(w, http.StatusCreated, rec)
+}
+
+func (s *apiServer) handleListSubmissions(w http.ResponseWriter, r *http.Request) {
+	writeJSON(w, http.StatusOK, s.store.list(r.URL.Query().Get("user")))
+}
+
+func (s *apiServer) handleLeaderboard(w http.ResponseWriter, _ *http.Request) {
+	writeJSON(w, http.StatusOK, s.store.leaderboard())
+}
+
+func (s *apiServer) handleWhoAmI(w http.ResponseWriter, r *http.Request) {
+	u := requestUser(r)
+	writeJSON(w, http.StatusOK, map[string]any{"user": u.Name, "admin": u.Admin})
+}
+
+// recordRun adds a finished server run's submission.json to the results
+// store under the user who started it.
+func (s *apiServer) recordRun(run *APIRun) {
+	data, err := os.ReadFile(filepath.Join(run.OutputDir, "submission.json"))
+	if err != nil {
+		logger.Warn("run has no submission to record", "run", run.ID, "error", err)
+		return
+	}
+	var sub LeaderboardSubmission
+	if err := json.Unmarshal(data, &sub); err != nil {
+		logger.Warn("failed to parse run submission", "run", run.ID, "error", err)
+		return
+	}
+	if _, err := s.store.add(run.User, teamSourceRun, run.ID, sub); err != nil {
+		logger.Warn("failed to record run", "run", run.ID, "error", err)
+	}
+}
+
+var serveAddUserCmd = &cobra.Command{
+	Use:   "add-user <name>",
+	Short: "Add a user to a team users file and print their API token",
+	Long: `Generates an API token for a new user, appends the user with the token's
+SHA-256 hash to the --users file (creating it if needed), and prints the
+token. The token itself is not stored; hand it to the user now.`,
+	Example: `  sanity serve add-user alice --users team.toml --admin
+  sanity serve --api --users team.toml`,
+	Args: cobra.ExactArgs(1),
+	RunE: func(cmd *cobra.Command, args []string) error {
+		if serveUsersFile == "" {
+			return errors.New("--users is required")
+		}
+		name := args[0]
+		var existing teamUsersFile
+		if _, err := toml.DecodeFile(serveUsersFile, &existing); err != nil && !errors.Is(err, fs.ErrNotExist) {
+			return fmt.Errorf("reading users file: %w", err)
+		}
+		for _, u := range existing.Users {
+			if u.Name == name {
+				return fmt.Errorf("user %q already exists in %s", name, serveUsersFile)
+			}
+		}
+
+		buf := make([]byte, 24)
+		if _, err := rand.Read(buf); err != nil {
+			return fmt.Errorf("generating API token: %w", err)
+		}
+		token := hex.EncodeToString(buf)
+		entry := fmt.Sprintf("\n[[users]]\nname = %q\ntoken_sha256 = %q\nadmin = %t\n", name, hashToken(token), addUserAdmin)
+		f, err := os.OpenFile(serveUsersFile, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0o600)
+		if err != nil {
+			return fmt.Errorf("opening users file: %w", err)
+		}
+		if _, err := f.WriteString(entry); err != nil {
+			_ = f.Close()
+			return fmt.Errorf("writing users file: %w", err)
+		}
+		if err := f.Close(); err != nil {
+			return fmt.Errorf("writing users file: %w", err)
+		}
+
+		fmt.Printf("Added %s to %s\n", name, serveUsersFile)
+		fmt.Printf("Token: %s\n", token)
+		return nil
+	},
+}
+
+func init() {
+	serveAddUserCmd.Flags().StringVar(&serveUsersFile, "users", "", "team users file to add the user to")
+	serveAddUserCmd.Flags().BoolVar(&addUserAdmin, "admin", false, "let the user cancel every user's runs")
+}
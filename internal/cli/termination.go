@@ -0,0 +1,43 @@
+package cli
+
+import (
+	"sync"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/result"
+)
+
+// processTermination records how setupProcessGroup stopped a command whose
+// context ended.
+type processTermination struct {
+	mu    sync.Mutex
+	pgid  int
+	timer *time.Timer
+	path  string // "", result.TerminationGraceful, or result.TerminationKilled
+	done  bool
+}
+
+// finish is called once the command has exited. It stops the grace period,
+// kills any of the group's processes that outlived a graceful exit, and
+// returns how the command was stopped, or "" if it exited on its own.
+func (p *processTermination) finish() string {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+	p.done = true
+	if p.timer != nil {
+		p.timer.Stop()
+	}
+	if p.path == result.TerminationGraceful {
+		killProcessGroup(p.pgid)
+	}
+	return p.path
+}
+
+// terminationConfig returns the [termination] settings in effect.
+func terminationConfig() config.TerminationConfig {
+	if cfg != nil {
+		return cfg.Termination
+	}
+	return config.Default.Termination
+}
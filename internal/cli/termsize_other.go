@@ -0,0 +1,11 @@
+//go:build !linux && !darwin
+
+package cli
+
+import "os"
+
+// terminalSize is not implemented on this platform, so the eval TUI falls
+// back to a fixed size.
+func terminalSize(_ *os.File) (cols, rows int, ok bool) {
+	return 0, 0, false
+}
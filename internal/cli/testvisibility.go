@@ -0,0 +1,101 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// resolveTestVisibility validates a test visibility and reconciles it with
+// --legacy, which shows every test and so is the same as "all". An empty
+// visibility means "visible_only", or "all" under --legacy.
+func resolveTestVisibility(visibility string, legacy bool) (string, error) {
+	switch {
+	case visibility == "" && legacy:
+		return config.TestVisibilityAll, nil
+	case visibility == "":
+		return config.TestVisibilityVisibleOnly, nil
+	case !slices.Contains(config.TestVisibilities, visibility):
+		return "", fmt.Errorf("invalid test visibility %q (valid: %s)", visibility, strings.Join(config.TestVisibilities, ", "))
+	case legacy && visibility != config.TestVisibilityAll:
+		return "", fmt.Errorf("--legacy shows the agent every test and cannot be combined with test visibility %q", visibility)
+	}
+	return visibility, nil
+}
+
+// resolveTestVisibilityFlags resolves --test-visibility against --legacy and
+// sets --legacy for "all", which the hidden test handling already follows.
+func resolveTestVisibilityFlags() error {
+	visibility, err := resolveTestVisibility(evalTestVisibility, evalLegacy)
+	if err != nil {
+		return err
+	}
+	evalTestVisibility, evalLegacy = visibility, visibility == config.TestVisibilityAll
+	return nil
+}
+
+// summaryTestVisibility returns the test visibility of a run, including runs
+// recorded before it was, where only --legacy tells.
+func summaryTestVisibility(summary EvalSummary) string {
+	visibility, _ := resolveTestVisibility(summary.TestVisibility, summary.Legacy)
+	return visibility
+}
+
+// testsHidden reports whether the agent works without the visible tests.
+func testsHidden() bool {
+	return evalTestVisibility == config.TestVisibilityNone
+}
+
+// hideVisibleTests removes t's visible tests from a newly initialized agent
+// workspace when the agent is to work without them.
+func hideVisibleTests(t *task.Task, workspaceDir string) error {
+	if !testsHidden() {
+		return nil
+	}
+	for _, f := range t.Files.Test {
+		err := os.Remove(filepath.Join(workspaceDir, task.StripTxtExtension(f)))
+		if err != nil && !errors.Is(err, os.ErrNotExist) {
+			return fmt.Errorf("hiding visible tests: %w", err)
+		}
+	}
+	return nil
+}
+
+// withoutVisibleTests returns a copy of t without its visible tests, so the
+// prompt built from it names none.
+func withoutVisibleTests(t *task.Task) *task.Task {
+	out := *t
+	out.Files.Test = nil
+	return &out
+}
+
+// restoreVisibleTests writes t's visible tests into a workspace validated
+// after the agent worked without them. It does nothing when the agent saw
+// them.
+func restoreVisibleTests(loader *task.Loader, t *task.Task, workspaceDir string) error {
+	if !testsHidden() {
+		return nil
+	}
+	if err := writeTaskFilesToWorkspace(loader, t, workspaceDir, t.Files.Test); err != nil {
+		return fmt.Errorf("writing visible tests: %w", err)
+	}
+	return nil
+}
+
+// testVisibilityLabel describes a test visibility for headers and reports.
+func testVisibilityLabel(visibility string) string {
+	switch visibility {
+	case config.TestVisibilityAll:
+		return "all (visible and hidden tests shown)"
+	case config.TestVisibilityNone:
+		return "none (spec only; tests hidden)"
+	default:
+		return "visible only"
+	}
+}
@@ -0,0 +1,64 @@
+package cli
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+func TestResolveTestVisibility(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		visibility string
+		legacy     bool
+		want       string
+		wantErr    bool
+	}{
+		{visibility: "", want: config.TestVisibilityVisibleOnly},
+		{visibility: "", legacy: true, want: config.TestVisibilityAll},
+		{visibility: config.TestVisibilityNone, want: config.TestVisibilityNone},
+		{visibility: config.TestVisibilityAll, legacy: true, want: config.TestVisibilityAll},
+		{visibility: config.TestVisibilityNone, legacy: true, wantErr: true},
+		{visibility: "hidden", wantErr: true},
+	}
+	for _, tt := range tests {
+		got, err := resolveTestVisibility(tt.visibility, tt.legacy)
+		if (err != nil) != tt.wantErr {
+			t.Errorf("resolveTestVisibility(%q, %v) error = %v, wantErr %v", tt.visibility, tt.legacy, err, tt.wantErr)
+			continue
+		}
+		if got != tt.want {
+			t.Errorf("resolveTestVisibility(%q, %v) = %q, want %q", tt.visibility, tt.legacy, got, tt.want)
+		}
+	}
+}
+
+func TestBuildAgentPromptWithoutVisibleTests(t *testing.T) {
+	t.Parallel()
+
+	tk := &task.Task{
+		Name:     "Bank Account",
+		Language: task.Go,
+		Files: task.TaskFiles{
+			Stub: []string{"bank_account.go.txt"},
+			Test: []string{"bank_account_test.go.txt"},
+		},
+	}
+	if prompt := buildAgentPrompt(tk, false, false, ""); !strings.Contains(prompt, "bank_account_test.go") {
+		t.Fatalf("prompt with visible tests does not name them:\n%s", prompt)
+	}
+
+	prompt := buildAgentPrompt(withoutVisibleTests(tk), false, false, "")
+	if strings.Contains(prompt, "bank_account_test.go") || strings.Contains(prompt, "visible test file(s)") {
+		t.Errorf("prompt mentions the hidden visible tests:\n%s", prompt)
+	}
+	if !strings.Contains(prompt, "none provided; work from the description") {
+		t.Errorf("prompt does not say no tests are provided:\n%s", prompt)
+	}
+	if len(tk.Files.Test) != 1 {
+		t.Errorf("withoutVisibleTests changed the task's tests: %v", tk.Files.Test)
+	}
+}
@@ -0,0 +1,245 @@
+package cli
+
+import (
+	"bytes"
+	"fmt"
+	"io"
+	"os"
+	"regexp"
+	"strings"
+	"sync"
+	"unicode/utf8"
+
+	"github.com/spf13/cobra"
+)
+
+// Output themes. The unicode theme is the harness's native output; the others
+// are applied by filtering stdout and stderr.
+const (
+	themeAuto    = "auto"
+	themeUnicode = "unicode"
+	themeASCII   = "ascii"
+	themePlain   = "plain"
+)
+
+var validThemes = []string{themeAuto, themeUnicode, themeASCII, themePlain}
+
+var (
+	outputTheme string
+	noColor     bool
+)
+
+// rawStdout and rawStderr are the process's original streams, kept for
+// interactive children and terminal detection once output filtering is installed.
+var (
+	rawStdout = os.Stdout
+	rawStderr = os.Stderr
+)
+
+// asciiReplacer maps the decoration used across the CLI to ASCII.
+var asciiReplacer = strings.NewReplacer(
+	"━", "=", "─", "-", "│", "|", "█", "#", "░", ".",
+	"✓", "+", "✗", "x", "✅", "[PASS]", "❌", "[FAIL]", "🚫", "[X]", "🏆", "*",
+	"⚠️", "!", "⚠", "!", "⏱️", "~", "⏱", "~", "▶", ">", "•", "*", "★", "*",
+	"→", "->", "—", "-", "–", "-", "±", "+/-", "≈", "~", "×", "x", "²", "^2", "Δ", "d",
+	"️", "",
+)
+
+// sgrPattern matches ANSI color/style sequences. Cursor control such as the
+// progress line's erase-line is left intact.
+var sgrPattern = regexp.MustCompile("\x1b\\[[0-9;]*m")
+
+// ruleLinePattern matches banner and separator lines drawn with box characters.
+var ruleLinePattern = regexp.MustCompile(`^\s*(?:\x1b\[[0-9;]*m)*[━─]+(?:\x1b\[[0-9;]*m)*\s*$`)
+
+// rulePrefixPattern matches an unfinished line that may still become a rule.
+var rulePrefixPattern = regexp.MustCompile(`^\s*(?:\x1b\[[0-9;]*m)*[━─]*(?:\x1b\[[0-9;]*m)*\s*$`)
+
+// annotationRawStdout marks a command whose stdout is data, such as JSONL
+// records or TOML, rather than a report.
+const annotationRawStdout = "sanity:raw-stdout"
+
+// rawOutputRequested reports whether cmd writes machine-readable data to
+// stdout, which must never be restyled: commands annotated with
+// annotationRawStdout, and --json or --csv output.
+func rawOutputRequested(cmd *cobra.Command) bool {
+	if cmd.Annotations[annotationRawStdout] == "true" {
+		return true
+	}
+	for _, name := range []string{"json", "csv"} {
+		if f := cmd.Flags().Lookup(name); f != nil && f.Value.String() == "true" {
+			return true
+		}
+	}
+	return false
+}
+
+// outputStyle is the resolved presentation for a run.
+type outputStyle struct {
+	Theme string
+	Color bool
+}
+
+// resolveOutputStyle picks the theme and color setting from flags, the
+// NO_COLOR and TERM environment variables, and whether stdout is a terminal.
+func resolveOutputStyle(theme string, noColorFlag, tty bool, getenv func(string) string) (outputStyle, error) {
+	switch theme {
+	case "", themeAuto:
+		theme = themeUnicode
+		if !tty {
+			theme = themeASCII
+		}
+	case themeUnicode, themeASCII, themePlain:
+	default:
+		return outputStyle{}, fmt.Errorf("unknown theme %q (valid: %s)", theme, strings.Join(validThemes, ", "))
+	}
+	color := tty && !noColorFlag && getenv("NO_COLOR") == "" && getenv("TERM") != "dumb"
+	return outputStyle{Theme: theme, Color: color}, nil
+}
+
+// filter rewrites complete text for this style. Lines are only recognised as
+// rules when they end in a newline.
+func (s outputStyle) filter(text string) string {
+	if s.Theme == themePlain {
+		lines := strings.SplitAfter(text, "\n")
+		var sb strings.Builder
+		for _, line := range lines {
+			if strings.HasSuffix(line, "\n") && ruleLinePattern.MatchString(strings.TrimSuffix(line, "\n")) {
+				continue
+			}
+			sb.WriteString(line)
+		}
+		text = sb.String()
+	}
+	if !s.Color {
+		text = sgrPattern.ReplaceAllString(text, "")
+	}
+	if s.Theme != themeUnicode {
+		text = asciiReplacer.Replace(text)
+	}
+	return text
+}
+
+// styleWriter applies an outputStyle to a byte stream, holding back incomplete
+// UTF-8 sequences, escape sequences, and (in the plain theme) partial lines
+// that could still turn out to be rules.
+type styleWriter struct {
+	style   outputStyle
+	out     io.Writer
+	pending []byte
+}
+
+func (w *styleWriter) Write(p []byte) (int, error) {
+	w.pending = append(w.pending, p...)
+	n := completePrefix(w.pending)
+	if w.style.Theme == themePlain {
+		if i := bytes.LastIndexByte(w.pending[:n], '\n'); rulePrefixPattern.Match(w.pending[i+1 : n]) {
+			n = i + 1
+		}
+	}
+	if n > 0 {
+		if _, err := io.WriteString(w.out, w.style.filter(string(w.pending[:n]))); err != nil {
+			return 0, err
+		}
+		w.pending = append(w.pending[:0], w.pending[n:]...)
+	}
+	return len(p), nil
+}
+
+// Flush writes anything still held back.
+func (w *styleWriter) Flush() error {
+	if len(w.pending) == 0 {
+		return nil
+	}
+	_, err := io.WriteString(w.out, w.style.filter(string(w.pending)))
+	w.pending = w.pending[:0]
+	return err
+}
+
+// completePrefix returns the length of the longest prefix of b that does not
+// end inside a UTF-8 sequence or an escape sequence.
+func completePrefix(b []byte) int {
+	n := len(b)
+	// Back off an incomplete trailing rune.
+	for i := 1; i <= utf8.UTFMax && i <= n; i++ {
+		if utf8.RuneStart(b[n-i]) {
+			if !utf8.FullRune(b[n-i:]) {
+				n -= i
+			}
+			break
+		}
+	}
+	// Back off an unterminated escape sequence.
+	if i := bytes.LastIndexByte(b[:n], 0x1b); i >= 0 {
+		rest := b[i+1 : n]
+		if len(rest) == 0 || (rest[0] == '[' && bytes.IndexFunc(rest[1:], isCSIFinal) < 0) {
+			n = i
+		}
+	}
+	return n
+}
+
+// isCSIFinal reports whether r ends a CSI escape sequence.
+func isCSIFinal(r rune) bool {
+	return r >= '@' && r <= '~'
+}
+
+// styledOutput redirects os.Stdout and os.Stderr through styleWriters.
+type styledOutput struct {
+	origOut, origErr *os.File
+	wg               sync.WaitGroup
+	writers          []*os.File
+}
+
+var activeStyledOutput *styledOutput
+
+// installOutputStyle applies the style to what the process prints on stderr
+// and, unless stdout carries data (see rawOutputRequested), on stdout. It is
+// a no-op for the native unicode theme with color enabled.
+func installOutputStyle(style outputStyle, stdout bool) error {
+	if activeStyledOutput != nil || (style.Theme == themeUnicode && style.Color) {
+		return nil
+	}
+	so := &styledOutput{origOut: os.Stdout, origErr: os.Stderr}
+	targets := []**os.File{&os.Stderr}
+	if stdout {
+		targets = append(targets, &os.Stdout)
+	}
+	for _, target := range targets {
+		r, w, err := os.Pipe()
+		if err != nil {
+			so.restore()
+			return fmt.Errorf("creating output pipe: %w", err)
+		}
+		dest := *target
+		so.writers = append(so.writers, w)
+		so.wg.Add(1)
+		go func() {
+			defer so.wg.Done()
+			sw := &styleWriter{style: style, out: dest}
+			_, _ = io.Copy(sw, r)
+			_ = sw.Flush()
+			_ = r.Close()
+		}()
+		*target = w
+	}
+	activeStyledOutput = so
+	return nil
+}
+
+// restoreOutputStyle flushes filtered output and restores the original
+// streams. It must run before the process exits.
+func restoreOutputStyle() {
+	if activeStyledOutput != nil {
+		activeStyledOutput.restore()
+		activeStyledOutput = nil
+	}
+}
+
+func (so *styledOutput) restore() {
+	os.Stdout, os.Stderr = so.origOut, so.origErr
+	for _, w := range so.writers {
+		_ = w.Close()
+	}
+	so.wg.Wait()
+}
@@ -0,0 +1,166 @@
+package cli
+
+import (
+	"bytes"
+	"encoding/json"
+	"io"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/spf13/cobra"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+func TestResolveOutputStyle(t *testing.T) {
+	t.Parallel()
+
+	env := func(vars map[string]string) func(string) string {
+		return func(k string) string { return vars[k] }
+	}
+	tests := []struct {
+		name    string
+		theme   string
+		noColor bool
+		tty     bool
+		vars    map[string]string
+		want    outputStyle
+	}{
+		{"terminal", "auto", false, true, nil, outputStyle{Theme: themeUnicode, Color: true}},
+		{"piped", "auto", false, false, nil, outputStyle{Theme: themeASCII, Color: false}},
+		{"NO_COLOR", "auto", false, true, map[string]string{"NO_COLOR": "1"}, outputStyle{Theme: themeUnicode, Color: false}},
+		{"flag", "unicode", true, true, nil, outputStyle{Theme: themeUnicode, Color: false}},
+		{"dumb terminal", "", false, true, map[string]string{"TERM": "dumb"}, outputStyle{Theme: themeUnicode, Color: false}},
+		{"explicit plain", "plain", false, true, nil, outputStyle{Theme: themePlain, Color: true}},
+	}
+	for _, tt := range tests {
+		got, err := resolveOutputStyle(tt.theme, tt.noColor, tt.tty, env(tt.vars))
+		if err != nil || got != tt.want {
+			t.Errorf("%s: resolveOutputStyle() = %+v, %v; want %+v", tt.name, got, err, tt.want)
+		}
+	}
+	if _, err := resolveOutputStyle("neon", false, true, env(nil)); err == nil {
+		t.Error("expected error for unknown theme")
+	}
+}
+
+func TestStyleWriter(t *testing.T) {
+	t.Parallel()
+
+	input := "━━━━\n SANITY HARNESS\n━━━━\n \033[33m⚠ skipped\033[0m\n ✓ go/a\r\033[K"
+
+	tests := []struct {
+		style outputStyle
+		want  string
+	}{
+		{outputStyle{Theme: themeUnicode, Color: false}, "━━━━\n SANITY HARNESS\n━━━━\n ⚠ skipped\n ✓ go/a\r\033[K"},
+		{outputStyle{Theme: themeASCII, Color: false}, "====\n SANITY HARNESS\n====\n ! skipped\n + go/a\r\033[K"},
+		{outputStyle{Theme: themePlain, Color: false}, " SANITY HARNESS\n ! skipped\n + go/a\r\033[K"},
+	}
+	for _, tt := range tests {
+		var out bytes.Buffer
+		w := &styleWriter{style: tt.style, out: &out}
+		// Feed one byte at a time so runes and escapes are split across writes.
+		for i := range len(input) {
+			if _, err := w.Write([]byte{input[i]}); err != nil {
+				t.Fatalf("Write() error = %v", err)
+			}
+		}
+		if err := w.Flush(); err != nil {
+			t.Fatalf("Flush() error = %v", err)
+		}
+		if out.String() != tt.want {
+			t.Errorf("%s: got %q, want %q", tt.style.Theme, out.String(), tt.want)
+		}
+	}
+}
+
+func TestRawOutputRequested(t *testing.T) {
+	t.Parallel()
+
+	for _, name := range []string{"json", "csv"} {
+		cmd := &cobra.Command{Use: "x"}
+		var set bool
+		cmd.Flags().BoolVar(&set, name, false, "")
+		if rawOutputRequested(cmd) {
+			t.Fatalf("expected false before --%s is set", name)
+		}
+		if err := cmd.Flags().Set(name, "true"); err != nil {
+			t.Fatalf("Set() error = %v", err)
+		}
+		if !rawOutputRequested(cmd) {
+			t.Fatalf("expected true with --%s", name)
+		}
+	}
+	if rawOutputRequested(&cobra.Command{Use: "y"}) {
+		t.Fatal("expected false for a command without data flags")
+	}
+	for _, cmd := range []*cobra.Command{exportCmd, configShowCmd} {
+		if !rawOutputRequested(cmd) {
+			t.Errorf("expected %s to write raw stdout", cmd.CommandPath())
+		}
+	}
+}
+
+func TestExportOutputIsNotRestyled(t *testing.T) {
+	runDir := filepath.Join(t.TempDir(), "2026-02-22T010203-codex")
+	taskDir := filepath.Join(runDir, "go-bank-account")
+	if err := os.MkdirAll(taskDir, 0o755); err != nil {
+		t.Fatalf("mkdir task dir: %v", err)
+	}
+	summary := EvalSummary{
+		Agent:     "codex",
+		Model:     "gpt-5",
+		Timestamp: "2026-02-22T010203",
+		Results:   []EvalResult{{Task: "go/bank-account", Language: "go", Tier: "core", Passed: true, Status: task.StatusPass}},
+	}
+	data, _ := json.Marshal(summary)
+	if err := os.WriteFile(filepath.Join(runDir, "summary.json"), data, 0o644); err != nil {
+		t.Fatalf("write summary: %v", err)
+	}
+	transcript := "━━━━\n✓ balance → 100 — done ⚠️\n\x1b[32mok\x1b[0m\n"
+	if err := os.WriteFile(filepath.Join(taskDir, "agent.log"), []byte(transcript), 0o644); err != nil {
+		t.Fatalf("write agent.log: %v", err)
+	}
+	rows, err := buildDatasetRows(runDir, task.NewLoader(tasks.FS, tasksDir), true)
+	if err != nil {
+		t.Fatalf("buildDatasetRows() error = %v", err)
+	}
+	var want bytes.Buffer
+	if err := writeDatasetRows(&want, rows); err != nil {
+		t.Fatalf("writeDatasetRows() error = %v", err)
+	}
+
+	prevCfg, prevLogger, prevStdout := cfg, logger, os.Stdout
+	t.Cleanup(func() {
+		cfg, logger, os.Stdout = prevCfg, prevLogger, prevStdout
+		outputTheme = themeAuto
+		rootCmd.SetArgs(nil)
+	})
+	r, w, err := os.Pipe()
+	if err != nil {
+		t.Fatal(err)
+	}
+	os.Stdout = w
+	got := make(chan []byte)
+	go func() {
+		b, _ := io.ReadAll(r)
+		got <- b
+	}()
+	// The pipe is not a terminal, and the theme is forced to plain, the
+	// most aggressive one; the JSONL must still come through unchanged.
+	rootCmd.SetArgs([]string{"export", runDir, "--theme", "plain"})
+	execErr := rootCmd.Execute()
+	restoreOutputStyle()
+	_ = w.Close()
+	os.Stdout = prevStdout
+	out := <-got
+	if execErr != nil {
+		t.Fatalf("export error = %v", execErr)
+	}
+	if !bytes.Equal(out, want.Bytes()) {
+		t.Errorf("piped export output was rewritten:\n got %q\nwant %q", out, want.Bytes())
+	}
+}
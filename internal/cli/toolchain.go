@@ -0,0 +1,283 @@
+package cli
+
+import (
+	"encoding/json"
+	"fmt"
+	"maps"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+)
+
+// ToolchainComparison compares the runs of a toolchain matrix, per agent
+// configuration, to show which tasks a toolchain change breaks or fixes.
+type ToolchainComparison struct {
+	Toolchains []string                  `json:"toolchains"`
+	Configs    []ToolchainConfigOutcomes `json:"configs"`
+}
+
+// ToolchainConfigOutcomes holds one agent configuration's results on every
+// toolchain of the matrix.
+type ToolchainConfigOutcomes struct {
+	Config    string                       `json:"config"`
+	PassRates map[string]float64           `json:"pass_rates"`
+	Tasks     map[string]map[string]string `json:"tasks"`   // Task to toolchain to outcome: "pass", "fail", or "2/3 pass" across repeats
+	Changed   []string                     `json:"changed"` // Tasks whose outcome differs between toolchains
+}
+
+// expandToolchainMatrix runs every spec once per toolchain in the
+// comma-separated names. An empty list leaves specs unchanged.
+func expandToolchainMatrix(specs []RunSpec, names string) ([]RunSpec, error) {
+	if strings.TrimSpace(names) == "" {
+		return specs, nil
+	}
+	var toolchains []string
+	for _, name := range strings.Split(names, ",") {
+		name = strings.TrimSpace(name)
+		if name == "" {
+			continue
+		}
+		if _, ok := cfg.Toolchain(name); !ok {
+			return nil, fmt.Errorf("unknown toolchain %q (available: %s)", name, strings.Join(cfg.ToolchainNames(), ", "))
+		}
+		if slices.Contains(toolchains, name) {
+			return nil, fmt.Errorf("--toolchains lists %q twice", name)
+		}
+		toolchains = append(toolchains, name)
+	}
+	expanded := make([]RunSpec, 0, len(specs)*len(toolchains))
+	for _, spec := range specs {
+		for _, name := range toolchains {
+			spec.Toolchain = name
+			expanded = append(expanded, spec)
+		}
+	}
+	return expanded, nil
+}
+
+// applyToolchain switches the task images to the named toolchain variant and
+// returns the images it set and a function that restores the previous ones.
+func applyToolchain(name string) (images map[string]string, restore func(), err error) {
+	if name == "" {
+		return nil, func() {}, nil
+	}
+	tc, ok := cfg.Toolchain(name)
+	if !ok {
+		return nil, nil, fmt.Errorf("unknown toolchain %q (available: %s)", name, strings.Join(cfg.ToolchainNames(), ", "))
+	}
+	previous := make(map[string]string, len(tc.Images))
+	for lang, image := range tc.Images {
+		previous[lang] = cfg.ImageForLanguage(lang)
+		cfg.SetImageForLanguage(lang, image)
+	}
+	restore = func() {
+		for lang, image := range previous {
+			cfg.SetImageForLanguage(lang, image)
+		}
+	}
+	return maps.Clone(tc.Images), restore, nil
+}
+
+// toolchainConfigKey identifies a spec's agent configuration regardless of
+// its toolchain.
+func toolchainConfigKey(spec RunSpec) string {
+	key := spec.Agent
+	if spec.Model != "" {
+		key += "/" + spec.Model
+	}
+	if spec.Reasoning != "" {
+		key += " (" + spec.Reasoning + ")"
+	}
+	return key
+}
+
+// toolchainTally counts passes per toolchain.
+type toolchainTally map[string]*struct{ passed, runs int }
+
+func (t toolchainTally) add(toolchain string, passed bool) {
+	if t[toolchain] == nil {
+		t[toolchain] = &struct{ passed, runs int }{}
+	}
+	t[toolchain].runs++
+	if passed {
+		t[toolchain].passed++
+	}
+}
+
+// toolchainConfigTally is one agent configuration's results across a matrix.
+type toolchainConfigTally struct {
+	overall toolchainTally
+	tasks   map[string]toolchainTally
+}
+
+// generateToolchainComparison groups a matrix's runs by agent configuration
+// and compares each task's outcome across toolchains. It returns nil if the
+// runs span fewer than two toolchains.
+func generateToolchainComparison(results []runResult) *ToolchainComparison {
+	c := &ToolchainComparison{}
+	byConfig := make(map[string]*toolchainConfigTally)
+	var configs []string
+	for _, rr := range results {
+		if rr.summary == nil || rr.spec.Toolchain == "" {
+			continue
+		}
+		if !slices.Contains(c.Toolchains, rr.spec.Toolchain) {
+			c.Toolchains = append(c.Toolchains, rr.spec.Toolchain)
+		}
+		key := toolchainConfigKey(rr.spec)
+		ct := byConfig[key]
+		if ct == nil {
+			ct = &toolchainConfigTally{overall: toolchainTally{}, tasks: make(map[string]toolchainTally)}
+			byConfig[key] = ct
+			configs = append(configs, key)
+		}
+		for _, r := range rr.summary.Results {
+			if ct.tasks[r.Task] == nil {
+				ct.tasks[r.Task] = toolchainTally{}
+			}
+			ct.tasks[r.Task].add(rr.spec.Toolchain, r.Passed)
+			ct.overall.add(rr.spec.Toolchain, r.Passed)
+		}
+	}
+	if len(c.Toolchains) < 2 {
+		return nil
+	}
+	for _, key := range configs {
+		c.Configs = append(c.Configs, compareToolchainOutcomes(key, byConfig[key], c.Toolchains))
+	}
+	return c
+}
+
+func compareToolchainOutcomes(key string, ct *toolchainConfigTally, toolchains []string) ToolchainConfigOutcomes {
+	out := ToolchainConfigOutcomes{
+		Config:    key,
+		PassRates: make(map[string]float64),
+		Tasks:     make(map[string]map[string]string),
+	}
+	for name, t := range ct.overall {
+		out.PassRates[name] = float64(t.passed) / float64(t.runs) * 100
+	}
+	for taskID, perToolchain := range ct.tasks {
+		outcomes := make(map[string]string, len(perToolchain))
+		distinct := make(map[string]bool)
+		for _, name := range toolchains {
+			if t := perToolchain[name]; t != nil {
+				outcomes[name] = toolchainOutcome(t.passed, t.runs)
+				distinct[outcomes[name]] = true
+			}
+		}
+		out.Tasks[taskID] = outcomes
+		if len(distinct) > 1 {
+			out.Changed = append(out.Changed, taskID)
+		}
+	}
+	slices.Sort(out.Changed)
+	return out
+}
+
+// writeToolchainComparison writes toolchain-comparison.json and
+// toolchain-report.md to the umbrella directory of a toolchain matrix.
+func writeToolchainComparison(dir string, results []runResult) {
+	c := generateToolchainComparison(results)
+	if c == nil {
+		return
+	}
+	data, _ := json.MarshalIndent(c, "", "  ")
+	_ = os.WriteFile(filepath.Join(dir, "toolchain-comparison.json"), data, 0o644)
+	_ = os.WriteFile(filepath.Join(dir, "toolchain-report.md"), []byte(buildToolchainReport(*c)), 0o644)
+}
+
+// buildToolchainReport builds a human-readable toolchain comparison.
+func buildToolchainReport(c ToolchainComparison) string {
+	var sb strings.Builder
+	sb.WriteString("### Toolchain Comparison\n\n")
+	sb.WriteString("| Toolchain | Images |\n")
+	sb.WriteString("|-----------|--------|\n")
+	for _, name := range c.Toolchains {
+		fmt.Fprintf(&sb, "| %s | %s |\n", name, toolchainImagesCell(name))
+	}
+	sb.WriteString("\n")
+
+	for _, conf := range c.Configs {
+		fmt.Fprintf(&sb, "#### %s\n\n", conf.Config)
+		sb.WriteString("| Toolchain | Pass Rate |\n")
+		sb.WriteString("|-----------|-----------|\n")
+		for _, name := range c.Toolchains {
+			if rate, ok := conf.PassRates[name]; ok {
+				fmt.Fprintf(&sb, "| %s | %.1f%% |\n", name, rate)
+			}
+		}
+		sb.WriteString("\n")
+		if len(conf.Changed) == 0 {
+			sb.WriteString("Every task has the same outcome on every toolchain.\n\n")
+			continue
+		}
+		fmt.Fprintf(&sb, "%d task(s) changed outcome between toolchains:\n\n", len(conf.Changed))
+		sb.WriteString("| Task |")
+		for _, name := range c.Toolchains {
+			fmt.Fprintf(&sb, " %s |", name)
+		}
+		sb.WriteString("\n|------|")
+		for range c.Toolchains {
+			sb.WriteString("------|")
+		}
+		sb.WriteString("\n")
+		for _, taskID := range conf.Changed {
+			fmt.Fprintf(&sb, "| %s |", taskID)
+			for _, name := range c.Toolchains {
+				fmt.Fprintf(&sb, " %s |", toolchainOutcomeCell(conf.Tasks[taskID][name]))
+			}
+			sb.WriteString("\n")
+		}
+		sb.WriteString("\n")
+	}
+	return sb.String()
+}
+
+// toolchainOutcome describes a task's result on one toolchain, combining
+// repeats.
+func toolchainOutcome(passed, runs int) string {
+	switch passed {
+	case runs:
+		return "pass"
+	case 0:
+		return "fail"
+	default:
+		return fmt.Sprintf("%d/%d pass", passed, runs)
+	}
+}
+
+func toolchainImagesCell(name string) string {
+	tc, ok := cfg.Toolchain(name)
+	if !ok || len(tc.Images) == 0 {
+		return "[docker] defaults"
+	}
+	parts := make([]string, 0, len(tc.Images))
+	for _, lang := range slices.Sorted(maps.Keys(tc.Images)) {
+		parts = append(parts, fmt.Sprintf("%s: `%s`", lang, tc.Images[lang]))
+	}
+	return strings.Join(parts, ", ")
+}
+
+func toolchainOutcomeCell(outcome string) string {
+	switch outcome {
+	case "pass":
+		return "✅"
+	case "fail":
+		return "❌"
+	case "":
+		return "—"
+	default:
+		return outcome
+	}
+}
+
+// toolchainLabel describes the toolchain a run used, for headers.
+func toolchainLabel(name string) string {
+	tc, ok := cfg.Toolchain(name)
+	if !ok || tc.Description == "" {
+		return name
+	}
+	return fmt.Sprintf("%s (%s)", name, tc.Description)
+}
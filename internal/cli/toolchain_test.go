@@ -0,0 +1,115 @@
+package cli
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// withToolchainConfig points the global config at a copy of the defaults
+// with a rust-beta toolchain. Tests using it must not run in parallel.
+func withToolchainConfig(t *testing.T) {
+	t.Helper()
+	c := config.Default
+	c.Toolchains = map[string]config.Toolchain{
+		"rust-beta": {Description: "Rust beta", Images: map[string]string{"rust": "ghcr.io/lemon07r/sanity-rust:beta"}},
+	}
+	prev := cfg
+	cfg = &c
+	t.Cleanup(func() { cfg = prev })
+}
+
+func TestExpandToolchainMatrix(t *testing.T) {
+	withToolchainConfig(t)
+
+	specs := []RunSpec{{Agent: "gemini"}, {Agent: "codex", Model: "gpt-5.2"}}
+	got, err := expandToolchainMatrix(specs, "default, rust-beta")
+	if err != nil {
+		t.Fatalf("expandToolchainMatrix() error = %v", err)
+	}
+	want := []RunSpec{
+		{Agent: "gemini", Toolchain: "default"},
+		{Agent: "gemini", Toolchain: "rust-beta"},
+		{Agent: "codex", Model: "gpt-5.2", Toolchain: "default"},
+		{Agent: "codex", Model: "gpt-5.2", Toolchain: "rust-beta"},
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Errorf("expandToolchainMatrix() = %+v, want %+v", got, want)
+	}
+
+	if got, _ := expandToolchainMatrix(specs, ""); !reflect.DeepEqual(got, specs) {
+		t.Errorf("empty --toolchains changed specs: %+v", got)
+	}
+	if _, err := expandToolchainMatrix(specs, "rust-nightly"); err == nil || !strings.Contains(err.Error(), "unknown toolchain") {
+		t.Errorf("unknown toolchain error = %v", err)
+	}
+	if _, err := expandToolchainMatrix(specs, "default,default"); err == nil {
+		t.Error("duplicate toolchain should be rejected")
+	}
+}
+
+func TestApplyToolchain(t *testing.T) {
+	withToolchainConfig(t)
+
+	stable := cfg.ImageForLanguage("rust")
+	images, restore, err := applyToolchain("rust-beta")
+	if err != nil {
+		t.Fatalf("applyToolchain() error = %v", err)
+	}
+	if got := cfg.ImageForLanguage("rust"); got != "ghcr.io/lemon07r/sanity-rust:beta" {
+		t.Errorf("rust image = %q, want the beta image", got)
+	}
+	if images["rust"] != "ghcr.io/lemon07r/sanity-rust:beta" {
+		t.Errorf("applied images = %v", images)
+	}
+	restore()
+	if got := cfg.ImageForLanguage("rust"); got != stable {
+		t.Errorf("rust image after restore = %q, want %q", got, stable)
+	}
+}
+
+func TestGenerateToolchainComparison(t *testing.T) {
+	withToolchainConfig(t)
+
+	run := func(toolchain string, rep int, results ...EvalResult) runResult {
+		return runResult{
+			spec:    RunSpec{Agent: "gemini", Toolchain: toolchain},
+			repeat:  rep,
+			summary: &EvalSummary{Agent: "gemini", Toolchain: toolchain, Results: results},
+		}
+	}
+	results := []runResult{
+		run("default", 1, EvalResult{Task: "rust/regex", Passed: true}, EvalResult{Task: "rust/lru", Passed: true}),
+		run("default", 2, EvalResult{Task: "rust/regex", Passed: true}, EvalResult{Task: "rust/lru", Passed: true}),
+		run("rust-beta", 1, EvalResult{Task: "rust/regex", Passed: false}, EvalResult{Task: "rust/lru", Passed: true}),
+		run("rust-beta", 2, EvalResult{Task: "rust/regex", Passed: true}, EvalResult{Task: "rust/lru", Passed: true}),
+	}
+
+	c := generateToolchainComparison(results)
+	if c == nil || len(c.Configs) != 1 {
+		t.Fatalf("generateToolchainComparison() = %+v, want one config", c)
+	}
+	conf := c.Configs[0]
+	if !reflect.DeepEqual(conf.Changed, []string{"rust/regex"}) {
+		t.Errorf("Changed = %v, want [rust/regex]", conf.Changed)
+	}
+	if got := conf.Tasks["rust/regex"]["rust-beta"]; got != "1/2 pass" {
+		t.Errorf("rust/regex on rust-beta = %q, want 1/2 pass", got)
+	}
+	if conf.PassRates["default"] != 100 || conf.PassRates["rust-beta"] != 75 {
+		t.Errorf("PassRates = %v", conf.PassRates)
+	}
+
+	report := buildToolchainReport(*c)
+	for _, want := range []string{"| rust-beta | rust: `ghcr.io/lemon07r/sanity-rust:beta` |", "| rust/regex | ✅ | 1/2 pass |"} {
+		if !strings.Contains(report, want) {
+			t.Errorf("report missing %q:\n%s", want, report)
+		}
+	}
+
+	if generateToolchainComparison(results[:2]) != nil {
+		t.Error("a single toolchain should not produce a comparison")
+	}
+}
@@ -0,0 +1,189 @@
+package cli
+
+import (
+	"fmt"
+	"path/filepath"
+	"regexp"
+	"strings"
+)
+
+// Tool call categories reported in tool_calls.
+const (
+	toolRead   = "read"
+	toolWrite  = "write"
+	toolEdit   = "edit"
+	toolShell  = "shell"
+	toolSearch = "search"
+)
+
+// toolCategories lists the categories in report order.
+var toolCategories = []string{toolRead, toolWrite, toolEdit, toolShell, toolSearch}
+
+var (
+	// toolJSONNamePattern matches tool names in JSON-streaming transcripts.
+	toolJSONNamePattern = regexp.MustCompile(`"(?:tool_name|tool|name)"\s*:\s*"([A-Za-z_]+)"`)
+	// toolDecoratedPattern matches tool lines that agent CLIs prefix with a
+	// status glyph or box border, e.g. "| Read  main.go" or "✔ ReadFile main.go".
+	toolDecoratedPattern = regexp.MustCompile(`^[|│┃✓✔●⏺•]+\s*([A-Za-z_]+)\b`)
+	// toolIdentifierPattern matches undecorated tool names that can't be prose,
+	// such as read_file or WriteFile.
+	toolIdentifierPattern = regexp.MustCompile(`^([a-z]+_[a-z_]+|[A-Z][a-z]+[A-Z][A-Za-z]*)\b`)
+)
+
+// toolNameCategories maps lowercased tool names used by common agents to a
+// category.
+var toolNameCategories = map[string]string{
+	"read": toolRead, "readfile": toolRead, "read_file": toolRead, "view": toolRead,
+	"read_many_files": toolRead, "webfetch": toolRead, "web_fetch": toolRead,
+
+	"write": toolWrite, "writefile": toolWrite, "write_file": toolWrite, "create": toolWrite,
+	"createfile": toolWrite, "create_file": toolWrite,
+
+	"edit": toolEdit, "multiedit": toolEdit, "editfile": toolEdit, "edit_file": toolEdit,
+	"replace": toolEdit, "str_replace": toolEdit, "str_replace_editor": toolEdit,
+	"apply_patch": toolEdit, "patch": toolEdit, "notebookedit": toolEdit,
+
+	"bash": toolShell, "shell": toolShell, "run_shell_command": toolShell, "exec": toolShell,
+	"execute": toolShell, "local_shell": toolShell, "terminal": toolShell,
+
+	"grep": toolSearch, "glob": toolSearch, "search": toolSearch, "searchtext": toolSearch,
+	"search_file_content": toolSearch, "findfiles": toolSearch, "find_files": toolSearch,
+	"list": toolSearch, "ls": toolSearch, "list_directory": toolSearch,
+	"codebase_search": toolSearch, "websearch": toolSearch, "web_search": toolSearch,
+	"google_web_search": toolSearch,
+}
+
+// shellProgramCategories classifies shell commands by program so agents that
+// do everything through a shell still show what they were doing.
+var shellProgramCategories = map[string]string{
+	"cat": toolRead, "head": toolRead, "tail": toolRead, "less": toolRead, "more": toolRead,
+	"bat": toolRead, "nl": toolRead,
+
+	"grep": toolSearch, "rg": toolSearch, "ag": toolSearch, "find": toolSearch, "fd": toolSearch,
+	"ls": toolSearch, "tree": toolSearch,
+
+	"patch": toolEdit, "apply_patch": toolEdit,
+	"tee": toolWrite,
+}
+
+// countToolCalls classifies tool invocations in an agent transcript. Each
+// line counts at most once. Transcript formats differ per agent, so counts
+// are best-effort signals rather than exact totals.
+func countToolCalls(lines []string) map[string]int {
+	counts := make(map[string]int)
+	for _, rawLine := range lines {
+		line := strings.TrimSpace(ansiEscapePattern.ReplaceAllString(rawLine, ""))
+		if line == "" {
+			continue
+		}
+		if category := classifyToolLine(line); category != "" {
+			counts[category]++
+		}
+	}
+	if len(counts) == 0 {
+		return nil
+	}
+	return counts
+}
+
+func classifyToolLine(line string) string {
+	for _, p := range []*regexp.Regexp{toolJSONNamePattern, toolDecoratedPattern, toolIdentifierPattern} {
+		if m := p.FindStringSubmatch(line); m != nil {
+			if category, ok := toolNameCategories[strings.ToLower(m[1])]; ok {
+				return category
+			}
+		}
+	}
+	if commands := extractCommandLines([]string{line}); len(commands) > 0 {
+		return classifyShellCommand(commands[0])
+	}
+	return ""
+}
+
+// classifyShellCommand returns the category of a shell command line, using
+// its first program after any cd prefix.
+func classifyShellCommand(command string) string {
+	fields := strings.Fields(command)
+	for len(fields) > 0 && (fields[0] == "cd" || fields[0] == "&&") {
+		if fields[0] == "cd" && len(fields) > 1 {
+			fields = fields[2:]
+		} else {
+			fields = fields[1:]
+		}
+	}
+	if len(fields) == 0 {
+		return toolShell
+	}
+	program := filepath.Base(fields[0])
+	if program == "sed" {
+		for _, f := range fields[1:] {
+			if strings.HasPrefix(f, "-i") {
+				return toolEdit
+			}
+		}
+		return toolRead
+	}
+	if category, ok := shellProgramCategories[program]; ok {
+		return category
+	}
+	return toolShell
+}
+
+// sumToolCalls adds counts into total, allocating it when needed.
+func sumToolCalls(total, counts map[string]int) map[string]int {
+	for category, n := range counts {
+		if total == nil {
+			total = make(map[string]int, len(toolCategories))
+		}
+		total[category] += n
+	}
+	return total
+}
+
+// formatToolCalls renders counts in category order, e.g. "read 4, edit 2".
+func formatToolCalls(counts map[string]int) string {
+	parts := make([]string, 0, len(toolCategories))
+	for _, category := range toolCategories {
+		if n := counts[category]; n > 0 {
+			parts = append(parts, fmt.Sprintf("%s %d", category, n))
+		}
+	}
+	if len(parts) == 0 {
+		return "none"
+	}
+	return strings.Join(parts, ", ")
+}
+
+// writeReportToolUsage adds per-category tool call totals to the report and
+// compares average usage between passed and failed tasks.
+func writeReportToolUsage(sb *strings.Builder, summary EvalSummary) {
+	if len(summary.ToolCalls) == 0 {
+		return
+	}
+	var passed, failed map[string]int
+	var nPassed, nFailed int
+	for _, r := range summary.Results {
+		if r.Passed {
+			passed = sumToolCalls(passed, r.ToolCalls)
+			nPassed++
+		} else {
+			failed = sumToolCalls(failed, r.ToolCalls)
+			nFailed++
+		}
+	}
+	avg := func(counts map[string]int, n int, category string) string {
+		if n == 0 {
+			return "-"
+		}
+		return fmt.Sprintf("%.1f", float64(counts[category])/float64(n))
+	}
+
+	sb.WriteString("## Tool Usage\n\n")
+	sb.WriteString("| Tool | Total | Avg per Passed Task | Avg per Failed Task |\n")
+	sb.WriteString("|------|-------|---------------------|---------------------|\n")
+	for _, category := range toolCategories {
+		fmt.Fprintf(sb, "| %s | %d | %s | %s |\n", category, summary.ToolCalls[category],
+			avg(passed, nPassed, category), avg(failed, nFailed, category))
+	}
+	sb.WriteString("\n")
+}
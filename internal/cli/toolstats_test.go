@@ -0,0 +1,70 @@
+package cli
+
+import (
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestCountToolCalls(t *testing.T) {
+	t.Parallel()
+
+	transcript := strings.Join([]string{
+		"I'll read the stub first.",
+		"| Read     bank_account.go",
+		"\x1b[32m✔\x1b[0m ReadFile bank_account_test.go",
+		"| Edit     bank_account.go",
+		`{"type":"tool_use","name":"Write","input":{"file_path":"notes.md"}}`,
+		"apply_patch <<'EOF'",
+		"$ go test ./...",
+		"$ cd /tmp/ws && grep -rn Withdraw .",
+		"$ sed -n '1,40p' bank_account.go",
+		"$ sed -i 's/a/b/' bank_account.go",
+		"/usr/bin/bash -lc 'ls -la'",
+		"| Task | Status |",
+		"Edit the stub to handle overdrafts.",
+	}, "\n")
+
+	got := countToolCalls(strings.Split(transcript, "\n"))
+	want := map[string]int{
+		toolRead:   3,
+		toolEdit:   3,
+		toolWrite:  1,
+		toolShell:  1,
+		toolSearch: 2,
+	}
+	if !reflect.DeepEqual(got, want) {
+		t.Fatalf("countToolCalls() = %v, want %v", got, want)
+	}
+
+	if got := countToolCalls([]string{"no tools here", ""}); got != nil {
+		t.Fatalf("countToolCalls() = %v, want nil", got)
+	}
+}
+
+func TestWriteReportToolUsage(t *testing.T) {
+	t.Parallel()
+
+	summary := EvalSummary{
+		ToolCalls: map[string]int{toolRead: 6, toolShell: 2},
+		Results: []EvalResult{
+			{Task: "go/a", Passed: true, ToolCalls: map[string]int{toolRead: 4, toolShell: 2}},
+			{Task: "go/b", Passed: true},
+			{Task: "go/c", ToolCalls: map[string]int{toolRead: 2}},
+		},
+	}
+	var sb strings.Builder
+	writeReportToolUsage(&sb, summary)
+	report := sb.String()
+	for _, want := range []string{"## Tool Usage", "| read | 6 | 2.0 | 2.0 |", "| shell | 2 | 1.0 | 0.0 |", "| edit | 0 | 0.0 | 0.0 |"} {
+		if !strings.Contains(report, want) {
+			t.Errorf("report missing %q:\n%s", want, report)
+		}
+	}
+
+	sb.Reset()
+	writeReportToolUsage(&sb, EvalSummary{})
+	if sb.Len() != 0 {
+		t.Fatalf("expected no section without tool calls, got %q", sb.String())
+	}
+}
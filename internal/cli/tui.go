@@ -0,0 +1,397 @@
+package cli
+
+import (
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"regexp"
+	"strings"
+	"sync"
+	"time"
+	"unicode/utf8"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// TUI geometry. Smaller terminals get the fallback size, which may wrap.
+const (
+	tuiFallbackCols = 120
+	tuiFallbackRows = 40
+	tuiMinCols      = 60
+	tuiMinRows      = 12
+	tuiTailBytes    = 32 << 10
+	tuiRedraw       = 500 * time.Millisecond
+)
+
+// terminalEscapePattern matches the escape sequences agents use for colors and
+// cursor movement, which are stripped from the output pane.
+var terminalEscapePattern = regexp.MustCompile(`\x1b(?:\[[0-9;?]*[ -/]*[@-~]|\][^\x07]*\x07|[@-Z\\-_])`)
+
+// evalAttemptHook, when set, is told when each agent attempt starts. The eval
+// TUI sets it for the duration of a run to show per-attempt timers.
+var evalAttemptHook func(taskID string, attempt int)
+
+type tuiState int
+
+const (
+	tuiPending tuiState = iota
+	tuiRunning
+	tuiPassed
+	tuiFailed
+	tuiSkipped
+)
+
+// tuiTask is the TUI's view of one task.
+type tuiTask struct {
+	id             string
+	logPath        string
+	state          tuiState
+	started        time.Time
+	attempt        int // 1-based; 0 until the first attempt starts
+	attemptStarted time.Time
+	duration       float64
+	note           string
+}
+
+// evalTUI is a full-screen eval display: a task list pane, the live output
+// of the most recently started agent, per-attempt timers, and a running
+// score. It redraws the whole screen on every tick, so stray log lines are
+// overwritten rather than scrolling it away.
+type evalTUI struct {
+	mu           sync.Mutex
+	out          io.Writer
+	size         func() (cols, rows int)
+	title        string
+	tasks        []*tuiTask
+	byID         map[string]*tuiTask
+	focus        string
+	started      time.Time
+	passed       int
+	failed       int
+	skipped      int
+	weightPassed float64
+	weightDone   float64
+	stop         chan struct{}
+	stopped      bool
+}
+
+// newEvalTUI takes over the terminal on out and starts redrawing it. Agent
+// logs are read from the task output directories under outputDir.
+func newEvalTUI(out io.Writer, title, outputDir string, tasks []*task.Task) *evalTUI {
+	u := &evalTUI{
+		out:     out,
+		size:    tuiTerminalSize,
+		title:   title,
+		byID:    make(map[string]*tuiTask, len(tasks)),
+		started: time.Now(),
+		stop:    make(chan struct{}),
+	}
+	for _, t := range tasks {
+		_, dir := evalWorkspacePaths(outputDir, t)
+		tt := &tuiTask{id: t.ID(), logPath: filepath.Join(dir, "agent.log")}
+		u.tasks = append(u.tasks, tt)
+		u.byID[tt.id] = tt
+	}
+	_, _ = fmt.Fprint(out, "\033[?1049h\033[?25l")
+	go u.tick()
+	return u
+}
+
+func tuiTerminalSize() (cols, rows int) {
+	cols, rows, ok := terminalSize(rawStdout)
+	if !ok || cols < tuiMinCols || rows < tuiMinRows {
+		return tuiFallbackCols, tuiFallbackRows
+	}
+	return cols, rows
+}
+
+func (u *evalTUI) tick() {
+	ticker := time.NewTicker(tuiRedraw)
+	defer ticker.Stop()
+	for {
+		select {
+		case <-u.stop:
+			return
+		case <-ticker.C:
+			u.mu.Lock()
+			u.redraw()
+			u.mu.Unlock()
+		}
+	}
+}
+
+// Start records that id began running and focuses the output pane on it.
+func (u *evalTUI) Start(id string) {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	if t := u.byID[id]; t != nil {
+		t.state = tuiRunning
+		t.started = time.Now()
+		t.attemptStarted = t.started
+		u.focus = id
+	}
+	u.redraw()
+}
+
+// Attempt records that attempt (0-based) of id's agent started.
+func (u *evalTUI) Attempt(id string, attempt int) {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	if t := u.byID[id]; t != nil {
+		t.attempt = attempt + 1
+		t.attemptStarted = time.Now()
+	}
+}
+
+// Finish reports a scored result.
+func (u *evalTUI) Finish(r EvalResult) {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	state := tuiFailed
+	u.weightDone += r.Weight
+	if r.Passed {
+		state = tuiPassed
+		u.passed++
+		u.weightPassed += r.Weight
+	} else {
+		u.failed++
+	}
+	u.complete(r, state, r.Error)
+}
+
+// Skip reports a task excluded from results as a resumable external failure.
+func (u *evalTUI) Skip(r EvalResult) {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	u.skipped++
+	u.complete(r, tuiSkipped, externalFailureLabel(r.FailureClass)+" — will be skipped (resumable)")
+}
+
+func (u *evalTUI) complete(r EvalResult, state tuiState, note string) {
+	t := u.byID[r.Task]
+	if t == nil {
+		return
+	}
+	t.state = state
+	t.duration = r.Duration
+	t.note = note
+	if u.focus == r.Task {
+		u.focus = u.latestRunning()
+	}
+	u.redraw()
+}
+
+// latestRunning returns the most recently started running task, if any.
+func (u *evalTUI) latestRunning() string {
+	var latest *tuiTask
+	for _, t := range u.tasks {
+		if t.state == tuiRunning && (latest == nil || t.started.After(latest.started)) {
+			latest = t
+		}
+	}
+	if latest == nil {
+		return ""
+	}
+	return latest.id
+}
+
+// Stop restores the terminal and prints one line per finished task, so they
+// stay in the scrollback. It is safe to call more than once and must be
+// called before printing anything else.
+func (u *evalTUI) Stop() {
+	u.mu.Lock()
+	defer u.mu.Unlock()
+	if u.stopped {
+		return
+	}
+	u.stopped = true
+	close(u.stop)
+	_, _ = fmt.Fprint(u.out, "\033[?25h\033[?1049l")
+	for _, t := range u.tasks {
+		mark := map[tuiState]string{tuiPassed: "✓", tuiFailed: "✗", tuiSkipped: "⚠"}[t.state]
+		if mark == "" {
+			continue
+		}
+		_, _ = fmt.Fprintf(u.out, " %s %-40s %8s\n", mark, t.id, formatDuration(t.duration))
+		if t.state != tuiPassed && t.note != "" {
+			_, _ = fmt.Fprintf(u.out, "   %s\n", t.note)
+		}
+	}
+}
+
+func (u *evalTUI) redraw() {
+	if u.stopped {
+		return
+	}
+	cols, rows := u.size()
+	var tail []string
+	if t := u.byID[u.focus]; t != nil {
+		tail = tailLogLines(t.logPath, rows)
+	}
+	// No newline after the last line, which would scroll the screen.
+	screen := strings.Join(u.render(time.Now(), cols, rows, tail), "\033[K\r\n")
+	_, _ = io.WriteString(u.out, "\033[H"+screen+"\033[K\033[J")
+}
+
+// render lays out the screen at now as rows lines of at most cols cells.
+// tail is the focused task's recent agent output.
+func (u *evalTUI) render(now time.Time, cols, rows int, tail []string) []string {
+	done := u.passed + u.failed + u.skipped
+	header := fmt.Sprintf(" SANITY HARNESS │ %s │ %d/%d  ✓%d ✗%d ⚠%d", u.title, done, len(u.tasks), u.passed, u.failed, u.skipped)
+	if scored := u.passed + u.failed; scored > 0 {
+		header += fmt.Sprintf(" │ pass %.1f%%", float64(u.passed)*100/float64(scored))
+	}
+	if u.weightDone > 0 {
+		header += fmt.Sprintf(" │ weighted %.2f/%.2f", u.weightPassed, u.weightDone)
+	}
+	header += " │ " + formatDuration(now.Sub(u.started).Seconds())
+
+	leftWidth := min(max(cols*2/5, 30), 50)
+	rightWidth := max(cols-leftWidth-3, 10)
+	bodyRows := max(rows-4, 1)
+
+	left := u.renderTaskPane(now, leftWidth, bodyRows)
+	right := u.renderOutputPane(now, rightWidth, bodyRows, tail)
+
+	lines := make([]string, 0, rows)
+	lines = append(lines, fitCells(header, cols), strings.Repeat("─", cols))
+	for i := range bodyRows {
+		lines = append(lines, padCells(left[i], leftWidth)+" │ "+fitCells(right[i], rightWidth))
+	}
+	lines = append(lines, strings.Repeat("─", cols), fitCells(" Ctrl-C stops after in-flight tasks and saves partial results (resume with --resume)", cols))
+	return lines
+}
+
+// renderTaskPane lists the tasks, scrolled to keep the first unfinished one
+// in view.
+func (u *evalTUI) renderTaskPane(now time.Time, width, rows int) []string {
+	lines := make([]string, rows)
+	lines[0] = " Tasks"
+	visible := rows - 1
+	first := 0
+	if len(u.tasks) > visible {
+		for i, t := range u.tasks {
+			if t.state == tuiPending || t.state == tuiRunning {
+				first = max(i-2, 0)
+				break
+			}
+			first = i
+		}
+		first = min(first, len(u.tasks)-visible)
+	}
+	for i := 0; i < visible && first+i < len(u.tasks); i++ {
+		t := u.tasks[first+i]
+		var mark, status string
+		switch t.state {
+		case tuiPending:
+			mark = "·"
+		case tuiRunning:
+			mark = "▶"
+			status = formatDuration(now.Sub(t.attemptStarted).Seconds())
+			if t.attempt > 1 {
+				status = fmt.Sprintf("#%d %s", t.attempt, status)
+			}
+		case tuiPassed:
+			mark, status = "✓", formatDuration(t.duration)
+		case tuiFailed:
+			mark, status = "✗", formatDuration(t.duration)
+		case tuiSkipped:
+			mark, status = "⚠", "skipped"
+		}
+		name := fitCells(t.id, width-cellWidth(status)-4)
+		lines[i+1] = fmt.Sprintf(" %s %s %s", mark, padCells(name, width-cellWidth(status)-4), status)
+	}
+	return lines
+}
+
+// renderOutputPane shows the focused task's attempt timer and the last lines
+// of its agent output.
+func (u *evalTUI) renderOutputPane(now time.Time, width, rows int, tail []string) []string {
+	lines := make([]string, rows)
+	t := u.byID[u.focus]
+	if t == nil {
+		lines[0] = " Agent output"
+		return lines
+	}
+	attempt := max(t.attempt, 1)
+	lines[0] = fmt.Sprintf(" %s · attempt %d · %s (task %s)", t.id, attempt,
+		formatDuration(now.Sub(t.attemptStarted).Seconds()), formatDuration(now.Sub(t.started).Seconds()))
+	if len(tail) > rows-1 {
+		tail = tail[len(tail)-(rows-1):]
+	}
+	for i, line := range tail {
+		lines[i+1] = " " + fitCells(line, width-1)
+	}
+	return lines
+}
+
+// tailLogLines returns up to n printable lines from the end of path.
+func tailLogLines(path string, n int) []string {
+	f, err := os.Open(path)
+	if err != nil {
+		return nil
+	}
+	defer func() { _ = f.Close() }()
+	info, err := f.Stat()
+	if err != nil {
+		return nil
+	}
+	offset := max(info.Size()-tuiTailBytes, 0)
+	buf := make([]byte, info.Size()-offset)
+	if _, err := f.ReadAt(buf, offset); err != nil && !errors.Is(err, io.EOF) {
+		return nil
+	}
+	return cleanLogLines(string(buf), n)
+}
+
+// cleanLogLines keeps the last n lines of text, stripping escape sequences,
+// keeping only what follows the last carriage return of each line (as a
+// terminal would show it), and expanding tabs.
+func cleanLogLines(text string, n int) []string {
+	raw := strings.Split(strings.TrimRight(text, "\n"), "\n")
+	if len(raw) > n {
+		raw = raw[len(raw)-n:]
+	}
+	lines := make([]string, 0, len(raw))
+	for _, line := range raw {
+		line = terminalEscapePattern.ReplaceAllString(line, "")
+		if i := strings.LastIndexByte(line, '\r'); i >= 0 {
+			line = line[i+1:]
+		}
+		line = strings.ReplaceAll(line, "\t", "    ")
+		line = strings.Map(func(r rune) rune {
+			if r < ' ' || r == 0x7f {
+				return -1
+			}
+			return r
+		}, line)
+		lines = append(lines, line)
+	}
+	return lines
+}
+
+func cellWidth(s string) int {
+	return utf8.RuneCountInString(s)
+}
+
+// fitCells truncates s to width cells, marking the cut with an ellipsis.
+func fitCells(s string, width int) string {
+	if width <= 0 {
+		return ""
+	}
+	if cellWidth(s) <= width {
+		return s
+	}
+	runes := []rune(s)
+	return string(runes[:width-1]) + "…"
+}
+
+// padCells pads s with spaces to width cells.
+func padCells(s string, width int) string {
+	if pad := width - cellWidth(s); pad > 0 {
+		return s + strings.Repeat(" ", pad)
+	}
+	return s
+}
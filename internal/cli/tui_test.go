@@ -0,0 +1,120 @@
+package cli
+
+import (
+	"bytes"
+	"slices"
+	"strings"
+	"testing"
+	"time"
+	"unicode/utf8"
+)
+
+func newTestTUI(ids ...string) *evalTUI {
+	u := &evalTUI{
+		out:     &bytes.Buffer{},
+		size:    func() (int, int) { return 100, 12 },
+		title:   "opencode/qwen3-coder",
+		byID:    make(map[string]*tuiTask),
+		started: time.Now(),
+		stop:    make(chan struct{}),
+	}
+	for _, id := range ids {
+		t := &tuiTask{id: id}
+		u.tasks = append(u.tasks, t)
+		u.byID[id] = t
+	}
+	return u
+}
+
+func TestEvalTUIRender(t *testing.T) {
+	t.Parallel()
+
+	u := newTestTUI("go/bank-account", "go/react", "rust/macros")
+	u.Start("go/bank-account")
+	u.Finish(EvalResult{Task: "go/bank-account", Passed: true, Weight: 1.5, Duration: 62})
+	u.Start("go/react")
+	u.Attempt("go/react", 1)
+
+	now := time.Now()
+	lines := u.render(now, 100, 12, []string{"thinking...", "edited main.go"})
+	if len(lines) != 12 {
+		t.Fatalf("render() = %d lines, want 12", len(lines))
+	}
+	for i, line := range lines {
+		if n := utf8.RuneCountInString(line); n > 100 {
+			t.Errorf("line %d is %d cells, want at most 100: %q", i, n, line)
+		}
+	}
+	screen := strings.Join(lines, "\n")
+	for _, want := range []string{
+		"1/3  ✓1 ✗0 ⚠0",
+		"pass 100.0%",
+		"weighted 1.50/1.50",
+		"✓ go/bank-account",
+		"1m 02s",
+		"▶ go/react",
+		"#2 0s",
+		"· rust/macros",
+		"go/react · attempt 2",
+		"edited main.go",
+	} {
+		if !strings.Contains(screen, want) {
+			t.Errorf("screen missing %q:\n%s", want, screen)
+		}
+	}
+
+	// Finishing the focused task moves the output pane to another running one.
+	u.Finish(EvalResult{Task: "go/react", Error: "tests failed", Weight: 1})
+	if u.focus != "" {
+		t.Errorf("focus = %q, want none after the last running task finished", u.focus)
+	}
+}
+
+func TestEvalTUIStopPrintsResults(t *testing.T) {
+	t.Parallel()
+
+	u := newTestTUI("go/bank-account", "go/react", "rust/macros")
+	u.Start("go/bank-account")
+	u.Finish(EvalResult{Task: "go/bank-account", Passed: true, Duration: 5})
+	u.Start("go/react")
+	u.Finish(EvalResult{Task: "go/react", Error: "tests failed", Duration: 7})
+	out := u.out.(*bytes.Buffer)
+	out.Reset()
+	u.Stop()
+	u.Stop()
+
+	got := out.String()
+	if !strings.HasPrefix(got, "\033[?25h\033[?1049l") {
+		t.Errorf("Stop() did not restore the screen first: %q", got)
+	}
+	for _, want := range []string{"✓ go/bank-account", "✗ go/react", "   tests failed\n"} {
+		if !strings.Contains(got, want) {
+			t.Errorf("Stop() output missing %q: %q", want, got)
+		}
+	}
+	if strings.Contains(got, "rust/macros") {
+		t.Errorf("Stop() listed an unstarted task: %q", got)
+	}
+}
+
+func TestCleanLogLines(t *testing.T) {
+	t.Parallel()
+
+	text := "old\n\x1b[32mgreen\x1b[0m\tdone\nprogress 10%\rprogress 100%\n\x1b]0;title\x07bell\x07\n"
+	got := cleanLogLines(text, 3)
+	want := []string{"green    done", "progress 100%", "bell"}
+	if !slices.Equal(got, want) {
+		t.Errorf("cleanLogLines() = %q, want %q", got, want)
+	}
+}
+
+func TestFitCells(t *testing.T) {
+	t.Parallel()
+
+	if got := fitCells("go/dining-philosophers", 8); got != "go/dini…" {
+		t.Errorf("fitCells() = %q, want %q", got, "go/dini…")
+	}
+	if got := padCells("✓ ok", 6); got != "✓ ok  " {
+		t.Errorf("padCells() = %q", got)
+	}
+}
@@ -0,0 +1,305 @@
+package cli
+
+import (
+	"bytes"
+	"context"
+	"crypto/hmac"
+	"crypto/sha256"
+	"encoding/hex"
+	"errors"
+	"fmt"
+	"io"
+	"io/fs"
+	"net/http"
+	"net/url"
+	"os"
+	"path"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// uploadQueueSize bounds the directories waiting to be synced before Sync
+// blocks the eval loop.
+const uploadQueueSize = 64
+
+// uploadCredentials are the HMAC keys requests are signed with.
+type uploadCredentials struct {
+	AccessKey    string
+	SecretKey    string
+	SessionToken string
+}
+
+// fileStamp identifies the version of a file that was last uploaded.
+type fileStamp struct {
+	size    int64
+	modTime int64
+}
+
+// artifactUploader copies a run's artifacts to an S3-compatible bucket in the
+// background. Each sync uploads only the files that are new or changed since
+// the last one, so task directories can be synced as their tasks finish and
+// the whole run once more at the end. Failed uploads are retried by the next
+// sync that covers them and never fail the run.
+type artifactUploader struct {
+	client    *http.Client
+	endpoint  *url.URL
+	region    string
+	pathStyle bool
+	bucket    string
+	keyPrefix string
+	creds     uploadCredentials
+	root      string
+	dest      string // Destination URL of the run, for display
+
+	queue chan string
+	done  chan struct{}
+
+	// Owned by the worker goroutine until done is closed.
+	uploaded map[string]fileStamp
+	count    int
+	failed   map[string]bool
+}
+
+// newRunUploader starts an uploader for the run in outputDir, or returns nil
+// if rawURL is empty.
+func newRunUploader(rawURL, outputDir string) (*artifactUploader, error) {
+	if rawURL == "" {
+		return nil, nil
+	}
+	uc := config.Default.Upload
+	if cfg != nil {
+		uc = cfg.Upload
+	}
+	return newArtifactUploader(uc, rawURL, outputDir, uploadRunName(outputDir))
+}
+
+// newArtifactUploader starts an uploader that puts the files below root under
+// runName in the destination rawURL.
+func newArtifactUploader(uc config.UploadConfig, rawURL, root, runName string) (*artifactUploader, error) {
+	target, err := config.ParseUploadURL(rawURL)
+	if err != nil {
+		return nil, err
+	}
+	endpoint, err := url.Parse(uc.EndpointFor(target.Scheme))
+	if err != nil {
+		return nil, fmt.Errorf("invalid upload endpoint: %w", err)
+	}
+	creds := uploadCredentials{
+		AccessKey:    os.Getenv(uc.AccessKeyEnv),
+		SecretKey:    os.Getenv(uc.SecretKeyEnv),
+		SessionToken: os.Getenv(uc.SessionTokenEnv),
+	}
+	if creds.AccessKey == "" || creds.SecretKey == "" {
+		return nil, fmt.Errorf("uploading to %s needs credentials in $%s and $%s", rawURL, uc.AccessKeyEnv, uc.SecretKeyEnv)
+	}
+	keyPrefix := path.Join(target.Prefix, runName)
+
+	u := &artifactUploader{
+		client:    &http.Client{Timeout: 5 * time.Minute},
+		endpoint:  endpoint,
+		region:    uc.RegionFor(target.Scheme),
+		pathStyle: uc.PathStyle,
+		bucket:    target.Bucket,
+		keyPrefix: keyPrefix,
+		creds:     creds,
+		root:      root,
+		dest:      fmt.Sprintf("%s://%s/%s", target.Scheme, target.Bucket, keyPrefix),
+		queue:     make(chan string, uploadQueueSize),
+		done:      make(chan struct{}),
+		uploaded:  make(map[string]fileStamp),
+		failed:    make(map[string]bool),
+	}
+	go u.run()
+	return u, nil
+}
+
+// uploadRunName names a run in the bucket: its path below eval-results, so
+// the runs of a multi-run keep their umbrella directory, or else its base
+// name.
+func uploadRunName(outputDir string) string {
+	if rel, err := filepath.Rel("eval-results", outputDir); err == nil && rel != "." && !strings.HasPrefix(rel, "..") {
+		return filepath.ToSlash(rel)
+	}
+	return filepath.Base(outputDir)
+}
+
+// Sync queues dir, which must be inside the run, for upload. It is a no-op
+// on a nil uploader.
+func (u *artifactUploader) Sync(dir string) {
+	if u == nil || dir == "" {
+		return
+	}
+	u.queue <- dir
+}
+
+// Close syncs the whole run one last time, waits for the queue to drain, and
+// returns how many files were uploaded and how many are still failing. It is
+// a no-op on a nil uploader.
+func (u *artifactUploader) Close() (uploaded, failed int) {
+	if u == nil {
+		return 0, 0
+	}
+	u.queue <- u.root
+	close(u.queue)
+	<-u.done
+	return u.count, len(u.failed)
+}
+
+func (u *artifactUploader) run() {
+	defer close(u.done)
+	for dir := range u.queue {
+		u.syncDir(dir)
+	}
+}
+
+// syncDir uploads the files below dir that changed since their last upload.
+// Files removed mid-walk, such as workspace sources being cleaned up, are
+// skipped.
+func (u *artifactUploader) syncDir(dir string) {
+	err := filepath.WalkDir(dir, func(p string, d fs.DirEntry, err error) error {
+		if err != nil {
+			if errors.Is(err, fs.ErrNotExist) {
+				return nil
+			}
+			return err
+		}
+		if !d.Type().IsRegular() {
+			return nil
+		}
+		info, err := d.Info()
+		if err != nil {
+			return nil // Removed since the walk listed it.
+		}
+		rel, err := filepath.Rel(u.root, p)
+		if err != nil || strings.HasPrefix(rel, "..") {
+			return nil // Only files inside the run are uploaded.
+		}
+		rel = filepath.ToSlash(rel)
+		stamp := fileStamp{size: info.Size(), modTime: info.ModTime().UnixNano()}
+		if prev, ok := u.uploaded[rel]; ok && prev == stamp {
+			return nil
+		}
+		if err := u.putFile(p, path.Join(u.keyPrefix, rel)); err != nil {
+			if !errors.Is(err, fs.ErrNotExist) {
+				logger.Warn("failed to upload artifact", "file", p, "error", err)
+				u.failed[rel] = true
+			}
+			return nil
+		}
+		u.uploaded[rel] = stamp
+		delete(u.failed, rel)
+		u.count++
+		return nil
+	})
+	if err != nil {
+		logger.Warn("failed to sync artifacts for upload", "dir", dir, "error", err)
+	}
+}
+
+// putFile uploads the file at p as the object key.
+func (u *artifactUploader) putFile(p, key string) error {
+	data, err := os.ReadFile(p)
+	if err != nil {
+		return err
+	}
+	req, err := http.NewRequestWithContext(context.Background(), http.MethodPut, u.objectURL(key), bytes.NewReader(data))
+	if err != nil {
+		return fmt.Errorf("building request: %w", err)
+	}
+	sum := sha256.Sum256(data)
+	signV4(req, hex.EncodeToString(sum[:]), u.creds, u.region, time.Now())
+
+	resp, err := u.client.Do(req)
+	if err != nil {
+		return fmt.Errorf("uploading %s: %w", key, err)
+	}
+	defer func() { _ = resp.Body.Close() }()
+	if resp.StatusCode/100 != 2 {
+		body, _ := io.ReadAll(io.LimitReader(resp.Body, 512))
+		return fmt.Errorf("uploading %s: %s: %s", key, resp.Status, strings.TrimSpace(string(body)))
+	}
+	return nil
+}
+
+// objectURL addresses key in the bucket, in path style or virtual-host style.
+func (u *artifactUploader) objectURL(key string) string {
+	if u.pathStyle {
+		return fmt.Sprintf("%s://%s%s/%s/%s", u.endpoint.Scheme, u.endpoint.Host, strings.TrimRight(u.endpoint.Path, "/"), s3URIEncode(u.bucket, true), s3URIEncode(key, false))
+	}
+	return fmt.Sprintf("%s://%s.%s%s/%s", u.endpoint.Scheme, u.bucket, u.endpoint.Host, strings.TrimRight(u.endpoint.Path, "/"), s3URIEncode(key, false))
+}
+
+// signV4 signs req for the S3 API with AWS Signature Version 4, covering the
+// host and every header already set on req.
+func signV4(req *http.Request, payloadHash string, creds uploadCredentials, region string, now time.Time) {
+	now = now.UTC()
+	amzDate := now.Format("20060102T150405Z")
+	date := now.Format("20060102")
+	req.Header.Set("X-Amz-Date", amzDate)
+	req.Header.Set("X-Amz-Content-Sha256", payloadHash)
+	if creds.SessionToken != "" {
+		req.Header.Set("X-Amz-Security-Token", creds.SessionToken)
+	}
+
+	headers := map[string]string{"host": req.URL.Host}
+	for name, values := range req.Header {
+		headers[strings.ToLower(name)] = strings.TrimSpace(strings.Join(values, ","))
+	}
+	names := make([]string, 0, len(headers))
+	for name := range headers {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	var canonicalHeaders strings.Builder
+	for _, name := range names {
+		canonicalHeaders.WriteString(name + ":" + headers[name] + "\n")
+	}
+	signedHeaders := strings.Join(names, ";")
+
+	canonicalRequest := strings.Join([]string{
+		req.Method,
+		req.URL.EscapedPath(),
+		req.URL.RawQuery,
+		canonicalHeaders.String(),
+		signedHeaders,
+		payloadHash,
+	}, "\n")
+	scope := date + "/" + region + "/s3/aws4_request"
+	requestHash := sha256.Sum256([]byte(canonicalRequest))
+	stringToSign := "AWS4-HMAC-SHA256\n" + amzDate + "\n" + scope + "\n" + hex.EncodeToString(requestHash[:])
+
+	key := hmacSHA256([]byte("AWS4"+creds.SecretKey), date)
+	key = hmacSHA256(key, region)
+	key = hmacSHA256(key, "s3")
+	key = hmacSHA256(key, "aws4_request")
+	signature := hex.EncodeToString(hmacSHA256(key, stringToSign))
+
+	req.Header.Set("Authorization", fmt.Sprintf("AWS4-HMAC-SHA256 Credential=%s/%s, SignedHeaders=%s, Signature=%s",
+		creds.AccessKey, scope, signedHeaders, signature))
+}
+
+func hmacSHA256(key []byte, data string) []byte {
+	mac := hmac.New(sha256.New, key)
+	mac.Write([]byte(data))
+	return mac.Sum(nil)
+}
+
+// s3URIEncode percent-encodes s as SigV4 expects: everything but unreserved
+// characters, and slashes only if encodeSlash is set.
+func s3URIEncode(s string, encodeSlash bool) string {
+	var sb strings.Builder
+	for _, b := range []byte(s) {
+		switch {
+		case 'A' <= b && b <= 'Z', 'a' <= b && b <= 'z', '0' <= b && b <= '9',
+			b == '-', b == '_', b == '.', b == '~', b == '/' && !encodeSlash:
+			sb.WriteByte(b)
+		default:
+			fmt.Fprintf(&sb, "%%%02X", b)
+		}
+	}
+	return sb.String()
+}
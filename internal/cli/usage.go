@@ -0,0 +1,210 @@
+package cli
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"os"
+	"strings"
+)
+
+// TokenUsage is the model usage an agent reported for a task, summed over
+// its attempts. Only agents whose adapter can read usage from their output
+// report it.
+type TokenUsage struct {
+	InputTokens      int64   `json:"input_tokens"`
+	OutputTokens     int64   `json:"output_tokens"`
+	CacheReadTokens  int64   `json:"cache_read_tokens,omitempty"`
+	CacheWriteTokens int64   `json:"cache_write_tokens,omitempty"`
+	CostUSD          float64 `json:"cost_usd,omitempty"`
+}
+
+// addUsage returns total plus u. A nil u adds nothing; a nil total starts a
+// new sum, so u is never aliased.
+func addUsage(total, u *TokenUsage) *TokenUsage {
+	if u == nil {
+		return total
+	}
+	if total == nil {
+		total = &TokenUsage{}
+	}
+	total.InputTokens += u.InputTokens
+	total.OutputTokens += u.OutputTokens
+	total.CacheReadTokens += u.CacheReadTokens
+	total.CacheWriteTokens += u.CacheWriteTokens
+	total.CostUSD += u.CostUSD
+	return total
+}
+
+// String formats u for reports, e.g. "12,345 in, 2,100 out, $0.42".
+func (u *TokenUsage) String() string {
+	parts := []string{formatCount(u.InputTokens) + " in", formatCount(u.OutputTokens) + " out"}
+	if u.CacheReadTokens > 0 || u.CacheWriteTokens > 0 {
+		parts = append(parts, fmt.Sprintf("%s cache read, %s cache write", formatCount(u.CacheReadTokens), formatCount(u.CacheWriteTokens)))
+	}
+	if u.CostUSD > 0 {
+		parts = append(parts, fmt.Sprintf("$%.2f", u.CostUSD))
+	}
+	return strings.Join(parts, ", ")
+}
+
+// formatCount formats n with thousands separators.
+func formatCount(n int64) string {
+	if n < 0 {
+		return "-" + formatCount(-n)
+	}
+	s := fmt.Sprint(n)
+	for i := len(s) - 3; i > 0; i -= 3 {
+		s = s[:i] + "," + s[i:]
+	}
+	return s
+}
+
+// recordAgentOutput reads what the agent's adapter can parse from its log.
+// The token usage goes into result. The usage and the agent's final message
+// are also appended to the log, so a transcript of JSON events ends readably.
+func recordAgentOutput(result *EvalResult, adapter agentAdapter, agentLogPath string) {
+	if adapter.usage == nil && adapter.finalMessage == nil {
+		return
+	}
+	data, err := os.ReadFile(agentLogPath)
+	if err != nil {
+		return
+	}
+	var message string
+	if adapter.usage != nil {
+		result.Usage = adapter.usage(data)
+	}
+	if adapter.finalMessage != nil {
+		message = adapter.finalMessage(data)
+	}
+	if result.Usage == nil && message == "" {
+		return
+	}
+	logFile, err := os.OpenFile(agentLogPath, os.O_APPEND|os.O_WRONLY, 0o644)
+	if err != nil {
+		return
+	}
+	defer func() { _ = logFile.Close() }()
+	if u := result.Usage; u != nil {
+		_, _ = fmt.Fprintf(logFile, "\n\nHARNESS: agent token usage (input_tokens=%d output_tokens=%d cache_read_tokens=%d cache_write_tokens=%d cost_usd=%.4f)\n",
+			u.InputTokens, u.OutputTokens, u.CacheReadTokens, u.CacheWriteTokens, u.CostUSD)
+	}
+	if message != "" {
+		_, _ = fmt.Fprintf(logFile, "\n\nHARNESS: agent final message\n%s\n", message)
+	}
+}
+
+// claudeResult is the final event of claude's stream-json output.
+type claudeResult struct {
+	Type         string  `json:"type"`
+	Result       string  `json:"result"`
+	TotalCostUSD float64 `json:"total_cost_usd"`
+	Usage        struct {
+		InputTokens              int64 `json:"input_tokens"`
+		OutputTokens             int64 `json:"output_tokens"`
+		CacheCreationInputTokens int64 `json:"cache_creation_input_tokens"`
+		CacheReadInputTokens     int64 `json:"cache_read_input_tokens"`
+	} `json:"usage"`
+}
+
+// parseClaudeUsage sums the usage of every result event in a claude log, one
+// per attempt.
+func parseClaudeUsage(data []byte) *TokenUsage {
+	var total *TokenUsage
+	for line := range bytes.Lines(data) {
+		line = bytes.TrimSpace(line)
+		if !bytes.HasPrefix(line, []byte("{")) || !bytes.Contains(line, []byte(`"result"`)) {
+			continue
+		}
+		var event claudeResult
+		if err := json.Unmarshal(line, &event); err != nil || event.Type != "result" {
+			continue
+		}
+		total = addUsage(total, &TokenUsage{
+			InputTokens:      event.Usage.InputTokens,
+			OutputTokens:     event.Usage.OutputTokens,
+			CacheReadTokens:  event.Usage.CacheReadInputTokens,
+			CacheWriteTokens: event.Usage.CacheCreationInputTokens,
+			CostUSD:          event.TotalCostUSD,
+		})
+	}
+	return total
+}
+
+// claudeFinalMessage returns the result text of the last result event in a
+// claude log.
+func claudeFinalMessage(data []byte) string {
+	var message string
+	for line := range bytes.Lines(data) {
+		line = bytes.TrimSpace(line)
+		if !bytes.HasPrefix(line, []byte(`{"type":"result"`)) {
+			continue
+		}
+		var event claudeResult
+		if err := json.Unmarshal(line, &event); err == nil && event.Result != "" {
+			message = event.Result
+		}
+	}
+	return message
+}
+
+// codexEvent is one line of codex exec --json output.
+type codexEvent struct {
+	Type string `json:"type"`
+	Item struct {
+		Type string `json:"type"`
+		Text string `json:"text"`
+	} `json:"item"`
+	Usage struct {
+		InputTokens       int64 `json:"input_tokens"`
+		CachedInputTokens int64 `json:"cached_input_tokens"`
+		OutputTokens      int64 `json:"output_tokens"`
+	} `json:"usage"`
+}
+
+// codexEvents decodes the codex exec --json events in data, skipping other
+// lines such as retry separators.
+func codexEvents(data []byte) []codexEvent {
+	var events []codexEvent
+	for line := range bytes.Lines(data) {
+		line = bytes.TrimSpace(line)
+		if !bytes.HasPrefix(line, []byte(`{"type":"`)) {
+			continue
+		}
+		var event codexEvent
+		if err := json.Unmarshal(line, &event); err == nil {
+			events = append(events, event)
+		}
+	}
+	return events
+}
+
+// parseCodexUsage sums the usage of every completed turn in a codex log.
+// Codex counts cached input within input_tokens.
+func parseCodexUsage(data []byte) *TokenUsage {
+	var total *TokenUsage
+	for _, event := range codexEvents(data) {
+		if event.Type != "turn.completed" {
+			continue
+		}
+		total = addUsage(total, &TokenUsage{
+			InputTokens:     event.Usage.InputTokens,
+			OutputTokens:    event.Usage.OutputTokens,
+			CacheReadTokens: event.Usage.CachedInputTokens,
+		})
+	}
+	return total
+}
+
+// codexFinalMessage returns the text of the last agent message in a codex
+// log.
+func codexFinalMessage(data []byte) string {
+	var message string
+	for _, event := range codexEvents(data) {
+		if event.Type == "item.completed" && event.Item.Type == "agent_message" && event.Item.Text != "" {
+			message = event.Item.Text
+		}
+	}
+	return message
+}
@@ -0,0 +1,91 @@
+package cli
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestParseClaudeUsage(t *testing.T) {
+	t.Parallel()
+
+	log := `{"type":"system","subtype":"init","session_id":"a"}
+{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash"}]}}
+{"type":"result","subtype":"success","result":"done","total_cost_usd":0.25,"usage":{"input_tokens":1200,"output_tokens":300,"cache_creation_input_tokens":50,"cache_read_input_tokens":4000}}
+
+
+=== RETRY 1 (after 5s delay) ===
+
+{"type":"result","subtype":"error_max_turns","total_cost_usd":0.05,"usage":{"input_tokens":100,"output_tokens":20}}
+`
+	got := parseClaudeUsage([]byte(log))
+	want := TokenUsage{InputTokens: 1300, OutputTokens: 320, CacheReadTokens: 4000, CacheWriteTokens: 50, CostUSD: 0.30}
+	if got == nil || got.InputTokens != want.InputTokens || got.OutputTokens != want.OutputTokens ||
+		got.CacheReadTokens != want.CacheReadTokens || got.CacheWriteTokens != want.CacheWriteTokens ||
+		got.CostUSD < 0.2999 || got.CostUSD > 0.3001 {
+		t.Fatalf("parseClaudeUsage() = %+v, want %+v", got, want)
+	}
+	if got := parseClaudeUsage([]byte("plain text output\n")); got != nil {
+		t.Errorf("parseClaudeUsage(text) = %+v, want nil", got)
+	}
+}
+
+func TestTokenUsageString(t *testing.T) {
+	t.Parallel()
+
+	total := addUsage(nil, &TokenUsage{InputTokens: 1234567, OutputTokens: 890, CostUSD: 1.5})
+	total = addUsage(total, nil)
+	if got, want := total.String(), "1,234,567 in, 890 out, $1.50"; got != want {
+		t.Errorf("String() = %q, want %q", got, want)
+	}
+}
+
+func TestCodexOutput(t *testing.T) {
+	t.Parallel()
+
+	log := `{"type":"thread.started","thread_id":"t1"}
+{"type":"turn.started"}
+{"type":"item.started","item":{"id":"item_1","type":"command_execution","command":"bash -lc 'go test ./...'","status":"in_progress"}}
+{"type":"item.completed","item":{"id":"item_2","type":"agent_message","text":"Running the tests."}}
+{"type":"item.completed","item":{"id":"item_3","type":"agent_message","text":"Implemented Withdraw; all tests pass."}}
+{"type":"turn.completed","usage":{"input_tokens":24763,"cached_input_tokens":24448,"output_tokens":122}}
+`
+	usage := parseCodexUsage([]byte(log))
+	if usage == nil || usage.InputTokens != 24763 || usage.CacheReadTokens != 24448 || usage.OutputTokens != 122 {
+		t.Errorf("parseCodexUsage() = %+v", usage)
+	}
+	if got, want := codexFinalMessage([]byte(log)), "Implemented Withdraw; all tests pass."; got != want {
+		t.Errorf("codexFinalMessage() = %q, want %q", got, want)
+	}
+}
+
+func TestRecordAgentOutput(t *testing.T) {
+	t.Parallel()
+
+	path := filepath.Join(t.TempDir(), "agent.log")
+	log := `{"type":"result","subtype":"success","result":"All done.","total_cost_usd":0.1,"usage":{"input_tokens":10,"output_tokens":5}}` + "\n"
+	if err := os.WriteFile(path, []byte(log), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	var result EvalResult
+	recordAgentOutput(&result, agentAdapters["claude"], path)
+	if result.Usage == nil || result.Usage.InputTokens != 10 {
+		t.Fatalf("Usage = %+v, want input_tokens=10", result.Usage)
+	}
+	data, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+	for _, want := range []string{"HARNESS: agent token usage (input_tokens=10 output_tokens=5", "HARNESS: agent final message\nAll done.\n"} {
+		if !strings.Contains(string(data), want) {
+			t.Errorf("agent.log missing %q:\n%s", want, data)
+		}
+	}
+
+	var other EvalResult
+	recordAgentOutput(&other, agentAdapters["opencode"], path)
+	if other.Usage != nil {
+		t.Errorf("agent without an adapter got usage %+v", other.Usage)
+	}
+}
@@ -30,6 +30,12 @@ Examples:
   sanity verify /path/to/submission`,
 	Args: cobra.ExactArgs(1),
 	RunE: func(cmd *cobra.Command, args []string) error {
+		args, cleanup, err := resolveRunArgs(args)
+		if err != nil {
+			return err
+		}
+		defer cleanup()
+
 		evalDir := args[0]
 
 		// Load attestation.json
@@ -0,0 +1,71 @@
+package config
+
+import (
+	"fmt"
+	"regexp"
+	"sort"
+)
+
+// ChaosProfile describes the adversity `eval --chaos` injects into the agent's
+// environment. Wrapped commands are delayed and, at FailRate, fail before
+// running; CPUs pins the agent to that many cores.
+type ChaosProfile struct {
+	FailRate float64  `toml:"fail_rate"` // Probability, from 0 to 1, that a wrapped command fails
+	DelayMS  int      `toml:"delay_ms"`  // Latency added before every wrapped command
+	CPUs     int      `toml:"cpus"`      // Cores the agent may use (0 = no limit)
+	Commands []string `toml:"commands"`  // Commands to wrap (default: the task's toolchain and file tools)
+}
+
+// DefaultChaosProfiles are available without configuration. A [chaos.<name>]
+// table of the same name replaces the built-in profile.
+var DefaultChaosProfiles = map[string]ChaosProfile{
+	"mild":  {FailRate: 0.1, DelayMS: 200},
+	"harsh": {FailRate: 0.3, DelayMS: 1000, CPUs: 1},
+}
+
+// ChaosProfile returns the named profile, preferring the user's definition.
+func (c *Config) ChaosProfile(name string) (ChaosProfile, bool) {
+	if p, ok := c.Chaos[name]; ok {
+		return p, true
+	}
+	p, ok := DefaultChaosProfiles[name]
+	return p, ok
+}
+
+// ChaosProfileNames lists the built-in and configured profiles, sorted.
+func (c *Config) ChaosProfileNames() []string {
+	seen := make(map[string]bool)
+	names := make([]string, 0, len(DefaultChaosProfiles)+len(c.Chaos))
+	for _, profiles := range []map[string]ChaosProfile{DefaultChaosProfiles, c.Chaos} {
+		for name := range profiles {
+			if !seen[name] {
+				seen[name] = true
+				names = append(names, name)
+			}
+		}
+	}
+	sort.Strings(names)
+	return names
+}
+
+// chaosCommandPattern matches the bare command names a profile may wrap.
+var chaosCommandPattern = regexp.MustCompile(`^[A-Za-z0-9][A-Za-z0-9._+-]*$`)
+
+func validateChaos(profiles map[string]ChaosProfile) error {
+	for name, p := range profiles {
+		switch {
+		case p.FailRate < 0 || p.FailRate > 1:
+			return fmt.Errorf("chaos.%s.fail_rate must be between 0 and 1", name)
+		case p.DelayMS < 0:
+			return fmt.Errorf("chaos.%s.delay_ms must not be negative", name)
+		case p.CPUs < 0:
+			return fmt.Errorf("chaos.%s.cpus must not be negative", name)
+		}
+		for _, cmd := range p.Commands {
+			if !chaosCommandPattern.MatchString(cmd) {
+				return fmt.Errorf("chaos.%s.commands: %q must be a bare command name", name, cmd)
+			}
+		}
+	}
+	return nil
+}
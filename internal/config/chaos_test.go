@@ -0,0 +1,58 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestLoadChaos(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := "[chaos.mild]\nfail_rate = 0.05\n\n[chaos.flaky-go]\nfail_rate = 0.5\ncommands = [\"go\"]\n"
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if p, ok := cfg.ChaosProfile("mild"); !ok || p.FailRate != 0.05 || p.DelayMS != 0 {
+		t.Errorf("mild = %+v, %v; want the configured profile", p, ok)
+	}
+	if p, ok := cfg.ChaosProfile("harsh"); !ok || !reflect.DeepEqual(p, DefaultChaosProfiles["harsh"]) {
+		t.Errorf("harsh = %+v, %v; want the built-in profile", p, ok)
+	}
+	if _, ok := cfg.ChaosProfile("missing"); ok {
+		t.Error("ChaosProfile(missing) should not be found")
+	}
+	if got, want := cfg.ChaosProfileNames(), []string{"flaky-go", "harsh", "mild"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("ChaosProfileNames() = %v, want %v", got, want)
+	}
+}
+
+func TestLoadChaosInvalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[chaos.x]\nfail_rate = 1.5\n", "fail_rate"},
+		{"[chaos.x]\ndelay_ms = -1\n", "delay_ms"},
+		{"[chaos.x]\ncpus = -2\n", "cpus"},
+		{"[chaos.x]\ncommands = [\"/usr/bin/go\"]\n", "bare command"},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
@@ -2,26 +2,87 @@
 package config
 
 import (
+	"errors"
 	"fmt"
 	"os"
 	"path/filepath"
+	"regexp"
+	"slices"
 	"sort"
-
-	"github.com/BurntSushi/toml"
+	"strings"
 )
 
 // AgentConfig defines how to invoke a coding agent.
 type AgentConfig struct {
-	Command               string            `toml:"command"`                 // Binary name or path
-	Args                  []string          `toml:"args"`                    // Args with {prompt} placeholder
-	ModelFlag             string            `toml:"model_flag"`              // e.g., "--model", "-m"
-	ModelFlagPosition     string            `toml:"model_flag_position"`     // "before" or "after" {prompt} in args (default: "before")
-	ReasoningFlag         string            `toml:"reasoning_flag"`          // e.g., "-r", "--reasoning-effort"
-	ReasoningFlagPosition string            `toml:"reasoning_flag_position"` // "before" or "after" {prompt} in args (default: "before")
-	Env                   map[string]string `toml:"env"`                     // Environment variables
-	DefaultTimeout        int               `toml:"default_timeout"`         // Per-agent minimum timeout in seconds (overrides harness default if larger)
-	MCPPrompt             string            `toml:"mcp_prompt,omitempty"`    // Agent-specific MCP tool guidance (appended when --use-mcp-tools is set)
-	PromptPrefix          string            `toml:"prompt_prefix,omitempty"` // Prefix prepended to the prompt (e.g., "ulw" for ultrawork mode)
+	Command               string            `toml:"command"`                     // Binary name or path
+	Args                  []string          `toml:"args"`                        // Args with {prompt}, {prompt_file}, {model}, {reasoning}, and {workspace} placeholders
+	ModelFlag             string            `toml:"model_flag"`                  // e.g., "--model", "-m"
+	ModelFlagPosition     string            `toml:"model_flag_position"`         // "before" or "after" {prompt} in args (default: "before")
+	ReasoningFlag         string            `toml:"reasoning_flag"`              // e.g., "-r", "--reasoning-effort"
+	ReasoningFlagPosition string            `toml:"reasoning_flag_position"`     // "before" or "after" {prompt} in args (default: "before")
+	Env                   map[string]string `toml:"env"`                         // Environment variables
+	DefaultTimeout        int               `toml:"default_timeout"`             // Per-agent minimum timeout in seconds (overrides harness default if larger)
+	MCPPrompt             string            `toml:"mcp_prompt,omitempty"`        // Agent-specific MCP tool guidance (appended when --use-mcp-tools is set)
+	PromptPrefix          string            `toml:"prompt_prefix,omitempty"`     // Prefix prepended to the prompt (e.g., "ulw" for ultrawork mode)
+	TurnPattern           string            `toml:"turn_pattern,omitempty"`      // Regexp matching an output line that starts a new agent turn
+	ToolCallPattern       string            `toml:"tool_call_pattern,omitempty"` // Regexp matching an output line that reports a tool call
+	MemoryPaths           []string          `toml:"memory_paths,omitempty"`      // $HOME-relative dirs holding session history and memory, scoped by [harness] agent_memory
+	PromptMode            string            `toml:"prompt_mode,omitempty"`       // How the prompt is passed: "arg" (default), "stdin", or "file"
+	DonePattern           string            `toml:"done_pattern,omitempty"`      // Regexp matching an output line that means the agent finished; it is then stopped
+	Protocol              string            `toml:"protocol,omitempty"`          // "" for a plain CLI, or "plugin" for the stdio plugin protocol (docs/PLUGINS.md)
+	SamplingFlags         map[string]string `toml:"sampling_flags,omitempty"`    // Flag per sampling parameter (temperature, top_p, min_p), like reasoning_flag
+}
+
+// AgentProtocolPlugin marks an agent that speaks the stdio plugin protocol:
+// it reads a task request on stdin and writes NDJSON events to stdout.
+const AgentProtocolPlugin = "plugin"
+
+// Prompt modes for AgentConfig.PromptMode.
+const (
+	PromptModeArg   = "arg"   // The prompt replaces {prompt} in args
+	PromptModeStdin = "stdin" // The prompt is written to the agent's stdin
+	PromptModeFile  = "file"  // The prompt is written to AgentPromptFile in the workspace; {prompt_file} is its path
+)
+
+// PromptModes lists the valid prompt modes.
+var PromptModes = []string{PromptModeArg, PromptModeStdin, PromptModeFile}
+
+// AgentPromptFile is the workspace file that holds the prompt in file mode.
+// It is removed when the attempt ends.
+const AgentPromptFile = ".sanity-prompt.md"
+
+// validate checks the agent's output patterns and how its prompt is passed.
+func (a AgentConfig) validate() error {
+	for key, pattern := range map[string]string{"turn_pattern": a.TurnPattern, "tool_call_pattern": a.ToolCallPattern, "done_pattern": a.DonePattern} {
+		if _, err := regexp.Compile(pattern); err != nil {
+			return fmt.Errorf("%s: %w", key, err)
+		}
+	}
+	if a.PromptMode != "" && !slices.Contains(PromptModes, a.PromptMode) {
+		return fmt.Errorf("prompt_mode must be one of %s", strings.Join(PromptModes, ", "))
+	}
+	hasArg := func(placeholder string) bool {
+		return slices.ContainsFunc(a.Args, func(arg string) bool { return strings.Contains(arg, placeholder) })
+	}
+	if a.PromptMode != PromptModeFile && hasArg("{prompt_file}") {
+		return errors.New(`args: {prompt_file} requires prompt_mode = "file"`)
+	}
+	if a.PromptMode != "" && a.PromptMode != PromptModeArg && hasArg("{prompt}") {
+		return fmt.Errorf("args: {prompt} requires prompt_mode = %q", PromptModeArg)
+	}
+	if err := validateSamplingFlags(a.SamplingFlags); err != nil {
+		return err
+	}
+	switch a.Protocol {
+	case "":
+	case AgentProtocolPlugin:
+		if a.PromptMode != "" || hasArg("{prompt}") || hasArg("{prompt_file}") {
+			return errors.New("protocol: plugin agents receive the prompt in their task request; remove prompt_mode and {prompt} from args")
+		}
+	default:
+		return fmt.Errorf("protocol must be empty or %q", AgentProtocolPlugin)
+	}
+	return nil
 }
 
 // DefaultAgents provides built-in configurations for popular coding agents.
@@ -31,6 +92,7 @@ var DefaultAgents = map[string]AgentConfig{
 		Args:              []string{"--yolo", "{prompt}"},
 		ModelFlag:         "--model",
 		ModelFlagPosition: "before",
+		MemoryPaths:       []string{".gemini/tmp"},
 	},
 	"kilocode": {
 		Command:               "kilocode",
@@ -61,17 +123,22 @@ var DefaultAgents = map[string]AgentConfig{
 	},
 	"claude": {
 		Command:           "claude",
-		Args:              []string{"-p", "--dangerously-skip-permissions", "{prompt}"},
+		Args:              []string{"-p", "--dangerously-skip-permissions", "--output-format", "stream-json", "--verbose", "{prompt}"},
 		ModelFlag:         "--model",
 		ModelFlagPosition: "before",
+		ToolCallPattern:   `"type":"tool_use"`,
+		MemoryPaths:       []string{".claude/projects"},
 	},
 	"codex": {
 		Command:               "codex",
-		Args:                  []string{"exec", "--dangerously-bypass-approvals-and-sandbox", "{prompt}"},
+		Args:                  []string{"exec", "--dangerously-bypass-approvals-and-sandbox", "--skip-git-repo-check", "--json", "{prompt}"},
 		ModelFlag:             "-m",
 		ModelFlagPosition:     "before",
 		ReasoningFlag:         "-c model_reasoning_effort={value}", // Reasoning: minimal, low, medium, high, xhigh
 		ReasoningFlagPosition: "before",
+		TurnPattern:           `^\{"type":"turn\.started"`,
+		ToolCallPattern:       `^\{"type":"item\.started","item":\{.*"type":"(command_execution|mcp_tool_call|web_search)"`,
+		MemoryPaths:           []string{".codex/sessions"},
 	},
 	"kimi": {
 		Command:           "kimi",
@@ -99,6 +166,7 @@ var DefaultAgents = map[string]AgentConfig{
 		ReasoningFlag:         "-r",                            // Reasoning effort: off, none, low, medium, high
 		ReasoningFlagPosition: "after",                         // Must be after 'exec' subcommand
 		Env:                   map[string]string{"CI": "true"}, // Disable Ink TTY mode
+		MemoryPaths:           []string{".factory/sessions"},
 	},
 	"iflow": {
 		Command:           "iflow",
@@ -111,6 +179,7 @@ var DefaultAgents = map[string]AgentConfig{
 		Args:              []string{"--yolo", "{prompt}"},
 		ModelFlag:         "-m",
 		ModelFlagPosition: "before",
+		MemoryPaths:       []string{".qwen/projects"},
 	},
 	"amp": {
 		Command:           "amp",
@@ -142,6 +211,7 @@ var DefaultAgents = map[string]AgentConfig{
 		Args:              []string{"--skip-update-check", "--task", "{prompt}"},
 		ModelFlag:         "--model",
 		ModelFlagPosition: "before",
+		MemoryPaths:       []string{".junie/projects"},
 	},
 	"ccs": {
 		Command:               "ccs",
@@ -172,24 +242,113 @@ var DefaultAgents = map[string]AgentConfig{
 		ModelFlag:         "--model",
 		ModelFlagPosition: "before",
 	},
+	"aider": {
+		Command: "aider",
+		// No git repo, so no auto-commits; aider's history files go to
+		// /dev/null instead of the workspace. Workspace files are added to the
+		// chat by the aider adapter.
+		Args: []string{
+			"--yes-always", "--no-git", "--no-auto-commits", "--no-dirty-commits", "--no-gitignore",
+			"--no-check-update", "--no-show-model-warnings", "--analytics-disable", "--no-pretty",
+			"--chat-history-file", "/dev/null", "--input-history-file", "/dev/null",
+			"--message", "{prompt}",
+		},
+		ModelFlag:             "--model",
+		ModelFlagPosition:     "before",
+		ReasoningFlag:         "--reasoning-effort",
+		ReasoningFlagPosition: "before",
+		TurnPattern:           `^Tokens: .* sent, .* received`,
+		ToolCallPattern:       `^Applied edit to `,
+	},
+	// The baseline agent is the harness itself talking to an OpenAI-compatible
+	// endpoint. The cli package points Command at the running executable.
+	"baseline": {
+		Command:  "sanity",
+		Args:     []string{"baseline-agent"},
+		Protocol: AgentProtocolPlugin,
+	},
 }
 
 // Config holds all configuration for SanityHarness.
 type Config struct {
-	Harness HarnessConfig          `toml:"harness"`
-	Docker  DockerConfig           `toml:"docker"`
-	Sandbox SandboxConfig          `toml:"sandbox"`
-	Agents  map[string]AgentConfig `toml:"agents"`
+	Harness        HarnessConfig            `toml:"harness"`
+	Docker         DockerConfig             `toml:"docker"`
+	Container      ContainerConfig          `toml:"container"`
+	Sandbox        SandboxConfig            `toml:"sandbox"`
+	Hooks          HooksConfig              `toml:"hooks"`
+	Termination    TerminationConfig        `toml:"termination"`
+	Upload         UploadConfig             `toml:"upload"`
+	Encryption     EncryptionConfig         `toml:"encryption"`
+	Notify         NotifyConfig             `toml:"notify"`
+	ContextFiles   []ContextFile            `toml:"context_files"`
+	PromptBudget   PromptBudgetConfig       `toml:"prompt_budget"`
+	Judge          JudgeConfig              `toml:"judge"`
+	Plagiarism     PlagiarismConfig         `toml:"plagiarism"`
+	Retention      RetentionConfig          `toml:"retention"`
+	Anonymize      AnonymizeConfig          `toml:"anonymize"`
+	Chaos          map[string]ChaosProfile  `toml:"chaos"`
+	PromptVariants map[string]PromptVariant `toml:"prompt_variants"`
+	Toolchains     map[string]Toolchain     `toml:"toolchains"`
+	MCPServers     map[string]MCPServer     `toml:"mcp_servers"`
+	Sampling       SamplingConfig           `toml:"sampling"`
+	Retry          RetryConfig              `toml:"retry"`
+	Tasks          map[string]TaskOverride  `toml:"task"` // Keyed by task ID, e.g. [task."go/bank-account"]
+	Agents         map[string]AgentConfig   `toml:"agents"`
 }
 
 // HarnessConfig contains harness-specific settings.
 type HarnessConfig struct {
-	SessionDir     string `toml:"session_dir"`
-	DefaultTimeout int    `toml:"default_timeout"`
-	MaxAttempts    int    `toml:"max_attempts"`
-	OutputFormat   string `toml:"output_format"`
+	SessionDir          string `toml:"session_dir"`
+	DefaultTimeout      int    `toml:"default_timeout"`
+	MaxAttempts         int    `toml:"max_attempts"`
+	OutputFormat        string `toml:"output_format"`
+	MaxTurns            int    `toml:"max_turns"`              // Per-attempt agent turn cap during eval (0 = unlimited)
+	MaxToolCalls        int    `toml:"max_tool_calls"`         // Per-attempt agent tool-call cap during eval (0 = unlimited)
+	MaxTokensPerAttempt int64  `toml:"max_tokens_per_attempt"` // Per-attempt agent input+output token cap during eval (0 = unlimited)
+	AgentMemory         string `toml:"agent_memory"`           // Scope of agent session history and memory: "host" (default), "run", or "task"
+	TestVisibility      string `toml:"test_visibility"`        // Tests the agent sees during eval: "all", "visible_only" (default), or "none"
+	RepairAttempts      int    `toml:"repair_attempts"`        // Extra agent turns after a failed validation during eval (0 = none)
+	AttemptFeedback     string `toml:"attempt_feedback"`       // What a repair turn is told of the failure; see AttemptFeedbackModes
 }
 
+// Scopes for [harness] agent_memory. With "host" the agent uses its memory
+// paths on the host as is; "run" gives every task in a run one fresh, shared
+// copy, and "task" gives each task its own.
+const (
+	AgentMemoryHost = "host"
+	AgentMemoryRun  = "run"
+	AgentMemoryTask = "task"
+)
+
+// AgentMemoryScopes lists the valid agent_memory values.
+var AgentMemoryScopes = []string{AgentMemoryHost, AgentMemoryRun, AgentMemoryTask}
+
+// Values for [harness] test_visibility. "all" also shows the agent the hidden
+// tests, as --legacy does; "none" hides even the visible tests, which are only
+// added for validation.
+const (
+	TestVisibilityAll         = "all"
+	TestVisibilityVisibleOnly = "visible_only"
+	TestVisibilityNone        = "none"
+)
+
+// TestVisibilities lists the valid test_visibility values.
+var TestVisibilities = []string{TestVisibilityAll, TestVisibilityVisibleOnly, TestVisibilityNone}
+
+// Values for [harness] attempt_feedback, the feedback a repair turn gets on
+// its previous attempt: nothing beyond the fact that it failed, the names of
+// the failing tests (the default), the full validation output, or the diff
+// of the previous attempt's solution against the stubs.
+const (
+	AttemptFeedbackNone         = "none"
+	AttemptFeedbackTestNames    = "failing_test_names"
+	AttemptFeedbackTestOutput   = "full_test_output"
+	AttemptFeedbackPreviousDiff = "diff_of_previous_attempt"
+)
+
+// AttemptFeedbackModes lists the valid attempt_feedback values.
+var AttemptFeedbackModes = []string{AttemptFeedbackNone, AttemptFeedbackTestNames, AttemptFeedbackTestOutput, AttemptFeedbackPreviousDiff}
+
 // SandboxConfig contains bubblewrap sandbox settings.
 type SandboxConfig struct {
 	WritableDirs        []string `toml:"writable_dirs"`         // Additional $HOME-relative dirs to mount writable
@@ -198,17 +357,159 @@ type SandboxConfig struct {
 	SharedReadOnlyDirs  []string `toml:"shared_readonly_dirs"`  // Broad shared allowlist mounted read-only (home-relative or absolute)
 }
 
+// HooksConfig lists shell commands run at eval lifecycle points. Each command
+// receives a JSON event payload on stdin.
+type HooksConfig struct {
+	PreRun      []string `toml:"pre_run"`      // Before any task runs; failure aborts the eval
+	PreTask     []string `toml:"pre_task"`     // After a task's workspace is created; failure skips the task as an infra failure
+	PostAttempt []string `toml:"post_attempt"` // After each agent attempt, including retries
+	PostRun     []string `toml:"post_run"`     // After summary.json and other outputs are written
+	Timeout     int      `toml:"timeout"`      // Per-command timeout in seconds (default: 60)
+}
+
+// ContextFile is a file placed into every task workspace before the agent
+// starts, such as an AGENTS.md or a language style guide.
+type ContextFile struct {
+	Source    string   `toml:"source"`    // Host path; relative paths resolve against the config file's directory
+	Dest      string   `toml:"dest"`      // Workspace-relative destination (default: the source's base name)
+	Languages []string `toml:"languages"` // Only place the file for these languages (default: all)
+}
+
+// AppliesTo reports whether the file should be placed for lang.
+func (f ContextFile) AppliesTo(lang string) bool {
+	if len(f.Languages) == 0 {
+		return true
+	}
+	for _, l := range f.Languages {
+		if l == lang {
+			return true
+		}
+	}
+	return false
+}
+
+// Prompt budget actions.
+const (
+	PromptBudgetWarn = "warn"
+	PromptBudgetTrim = "trim"
+)
+
+// Optional prompt sections that a trimming budget may drop.
+const (
+	PromptSectionSkillPacks   = "skill_pack_prompts"
+	PromptSectionContextFiles = "context_files"
+)
+
+// PromptBudgetConfig limits the size, in characters, of the rendered agent
+// prompt plus injected context files.
+type PromptBudgetConfig struct {
+	Default  int            `toml:"default"`  // Limit for models without an entry in models (0 = unlimited)
+	Models   map[string]int `toml:"models"`   // Per-model limits keyed by model name or glob pattern
+	Action   string         `toml:"action"`   // "warn" (default) or "trim"
+	Optional []string       `toml:"optional"` // Sections trim may drop, in order
+}
+
+// LimitFor returns the budget for model, or 0 when it is unlimited. Exact
+// model names take precedence over patterns, which are tried in sorted order.
+func (b PromptBudgetConfig) LimitFor(model string) int {
+	if limit, ok := b.Models[model]; ok {
+		return limit
+	}
+	patterns := make([]string, 0, len(b.Models))
+	for p := range b.Models {
+		patterns = append(patterns, p)
+	}
+	sort.Strings(patterns)
+	for _, p := range patterns {
+		if ok, _ := filepath.Match(p, model); ok {
+			return b.Models[p]
+		}
+	}
+	return b.Default
+}
+
+func (b PromptBudgetConfig) validate() error {
+	switch b.Action {
+	case "", PromptBudgetWarn, PromptBudgetTrim:
+	default:
+		return fmt.Errorf("prompt_budget.action %q must be %q or %q", b.Action, PromptBudgetWarn, PromptBudgetTrim)
+	}
+	for _, s := range b.Optional {
+		if s != PromptSectionSkillPacks && s != PromptSectionContextFiles {
+			return fmt.Errorf("prompt_budget.optional: unknown section %q (valid: %s, %s)", s, PromptSectionSkillPacks, PromptSectionContextFiles)
+		}
+	}
+	for p, limit := range b.Models {
+		if _, err := filepath.Match(p, ""); err != nil {
+			return fmt.Errorf("prompt_budget.models: bad pattern %q: %w", p, err)
+		}
+		if limit < 0 {
+			return fmt.Errorf("prompt_budget.models: negative limit for %q", p)
+		}
+	}
+	if b.Default < 0 {
+		return errors.New("prompt_budget.default must not be negative")
+	}
+	return nil
+}
+
+// PlagiarismConfig points the known-solution detector at a corpus of public
+// reference solutions.
+type PlagiarismConfig struct {
+	Corpus    string  `toml:"corpus"`    // Directory laid out as <language>/<slug>/<solution>; relative paths resolve against the config file's directory
+	Threshold float64 `toml:"threshold"` // Token similarity at or above which a solution is flagged (default: 0.8)
+}
+
+// AnonymizeConfig lists identifiers removed from results by archive and export
+// --anonymize, on top of the user name, host name, and paths stripped always.
+type AnonymizeConfig struct {
+	Identifiers []string `toml:"identifiers"` // Literal strings replaced with <redacted>, e.g. team or project names
+}
+
 // DockerConfig contains Docker-related settings.
 type DockerConfig struct {
-	GoImage         string `toml:"go_image"`
-	RustImage       string `toml:"rust_image"`
-	TypeScriptImage string `toml:"typescript_image"`
-	KotlinImage     string `toml:"kotlin_image"`
-	DartImage       string `toml:"dart_image"`
-	ZigImage        string `toml:"zig_image"`
-	AutoPull        bool   `toml:"auto_pull"`
+	ContainerBackend string   `toml:"container_backend"` // Container engine: "docker" (default), "podman", or "local"
+	LocalSandbox     string   `toml:"local_sandbox"`     // Sandbox for the local backend: "auto" (default), "bwrap", "firejail", or "none"
+	GoImage          string   `toml:"go_image"`
+	RustImage        string   `toml:"rust_image"`
+	TypeScriptImage  string   `toml:"typescript_image"`
+	KotlinImage      string   `toml:"kotlin_image"`
+	DartImage        string   `toml:"dart_image"`
+	ZigImage         string   `toml:"zig_image"`
+	AutoPull         bool     `toml:"auto_pull"`
+	PullRetries      int      `toml:"pull_retries"` // Extra attempts per registry, with exponential backoff
+	Mirrors          []string `toml:"mirrors"`      // Registry mirrors tried in order when a pull keeps failing
+
+	// AllowUnsandboxed is set by --i-understand-the-risks, never from a
+	// config file: the local backend may then run task commands directly on
+	// the host.
+	AllowUnsandboxed bool `toml:"-"`
 }
 
+// Values for [docker] container_backend. Both engines are driven through the
+// Docker Engine API; Podman's socket is found automatically. The local
+// backend uses no engine: it grades with the host's toolchains in a sandbox.
+const (
+	ContainerBackendDocker = "docker"
+	ContainerBackendPodman = "podman"
+	ContainerBackendLocal  = "local"
+)
+
+// ContainerBackends lists the valid container_backend values.
+var ContainerBackends = []string{ContainerBackendDocker, ContainerBackendPodman, ContainerBackendLocal}
+
+// Values for [docker] local_sandbox. Auto uses bwrap, else firejail; none
+// runs task commands directly on the host and needs --i-understand-the-risks.
+const (
+	LocalSandboxAuto     = "auto"
+	LocalSandboxBwrap    = "bwrap"
+	LocalSandboxFirejail = "firejail"
+	LocalSandboxNone     = "none"
+)
+
+// LocalSandboxes lists the valid local_sandbox values.
+var LocalSandboxes = []string{LocalSandboxAuto, LocalSandboxBwrap, LocalSandboxFirejail, LocalSandboxNone}
+
 // Default configuration values.
 var Default = Config{
 	Harness: HarnessConfig{
@@ -217,14 +518,57 @@ var Default = Config{
 		MaxAttempts:    5,
 		OutputFormat:   "all",
 	},
+	Hooks: HooksConfig{
+		Timeout: 60,
+	},
+	Retry: RetryConfig{
+		AgentCrash:   RetryPolicy{MaxRetries: 5, Delay: 15, Backoff: 2},
+		AgentTimeout: RetryPolicy{MaxRetries: 1, Delay: 15, Backoff: 1},
+		Provider:     RetryPolicy{MaxRetries: 5, Delay: 30, Backoff: 2},
+		Container:    RetryPolicy{MaxRetries: 0, Delay: 10, Backoff: 2},
+		TestFailure:  RetryPolicy{MaxRetries: 0, Delay: 0, Backoff: 1},
+		Flaky:        RetryPolicy{MaxRetries: 0, Delay: 5, Backoff: 1},
+	},
+	Termination: TerminationConfig{
+		Signal:               "SIGTERM",
+		GracePeriod:          10,
+		ContainerStopTimeout: 10,
+	},
+	Upload: UploadConfig{
+		AccessKeyEnv:    "AWS_ACCESS_KEY_ID",
+		SecretKeyEnv:    "AWS_SECRET_ACCESS_KEY",
+		SessionTokenEnv: "AWS_SESSION_TOKEN",
+	},
+	Notify: NotifyConfig{
+		On: NotifyAlways,
+		Email: EmailConfig{
+			TLS:         SMTPStartTLS,
+			UsernameEnv: "SMTP_USERNAME",
+			PasswordEnv: "SMTP_PASSWORD",
+		},
+	},
+	Judge: JudgeConfig{
+		APIKeyEnv: "SANITY_JUDGE_API_KEY",
+		Timeout:   120,
+	},
+	Plagiarism: PlagiarismConfig{
+		Threshold: 0.8,
+	},
+	Container: ContainerConfig{
+		Network:      ContainerNetworkOpen,
+		AllowedHosts: DefaultAllowedHosts,
+	},
 	Docker: DockerConfig{
-		GoImage:         "ghcr.io/lemon07r/sanity-go:latest",
-		RustImage:       "ghcr.io/lemon07r/sanity-rust:latest",
-		TypeScriptImage: "ghcr.io/lemon07r/sanity-ts:latest",
-		KotlinImage:     "ghcr.io/lemon07r/sanity-kotlin:latest",
-		DartImage:       "ghcr.io/lemon07r/sanity-dart:latest",
-		ZigImage:        "ghcr.io/lemon07r/sanity-zig:latest",
-		AutoPull:        true,
+		ContainerBackend: ContainerBackendDocker,
+		LocalSandbox:     LocalSandboxAuto,
+		GoImage:          "ghcr.io/lemon07r/sanity-go:latest",
+		RustImage:        "ghcr.io/lemon07r/sanity-rust:latest",
+		TypeScriptImage:  "ghcr.io/lemon07r/sanity-ts:latest",
+		KotlinImage:      "ghcr.io/lemon07r/sanity-kotlin:latest",
+		DartImage:        "ghcr.io/lemon07r/sanity-dart:latest",
+		ZigImage:         "ghcr.io/lemon07r/sanity-zig:latest",
+		AutoPull:         true,
+		PullRetries:      3,
 	},
 	Sandbox: SandboxConfig{
 		// Compatibility-focused shared allowlist: keep common auth/config/cache/toolchain
@@ -242,6 +586,7 @@ var Default = Config{
 			".gradle",
 			".pub-cache",
 			".dart-tool",
+			".aider",
 			".amp",
 			".ccs",
 			".claude",
@@ -277,77 +622,208 @@ var Default = Config{
 	},
 }
 
-// configPaths returns the list of paths to search for config files.
-func configPaths() []string {
-	paths := []string{"./sanity.toml"}
-
-	if home, err := os.UserHomeDir(); err == nil {
-		paths = append(paths, filepath.Join(home, ".sanity.toml"))
-		paths = append(paths, filepath.Join(home, ".config", "sanity", "config.toml"))
+// Load loads configFile over the built-in defaults. If configFile is empty,
+// it resolves the layered config files instead; see LoadLayered.
+// Returns default config if no file is found.
+func Load(configFile string) (*Config, error) {
+	if configFile == "" {
+		cfg, _, err := LoadLayered(LoadOptions{})
+		return cfg, err
 	}
-
-	return paths
+	if _, err := os.Stat(configFile); err != nil {
+		return nil, fmt.Errorf("config file not found: %s", configFile)
+	}
+	cfg, _, err := loadLayers([]LayerFile{{Layer: LayerCLI, Path: configFile}}, nil, "", false)
+	return cfg, err
 }
 
-// Load loads configuration from a file or discovers it automatically.
-// If configFile is empty, it searches standard locations.
-// Returns default config if no file is found.
-func Load(configFile string) (*Config, error) {
-	cfg := Default // Start with defaults
+// resolvePaths makes relative paths absolute against baseDir, the directory
+// of the config file that set them. Paths that are already absolute are left
+// alone, so each layer only resolves the paths it set.
+func (c *Config) resolvePaths(baseDir string) error {
+	c.Encryption.resolvePaths(baseDir)
+	if c.Plagiarism.Corpus != "" && !filepath.IsAbs(c.Plagiarism.Corpus) {
+		c.Plagiarism.Corpus = filepath.Join(baseDir, c.Plagiarism.Corpus)
+	}
+	return resolveContextFiles(c.ContextFiles, baseDir)
+}
 
-	var path string
-	if configFile != "" {
-		path = configFile
-		if _, err := os.Stat(path); err != nil {
-			return nil, fmt.Errorf("config file not found: %s", path)
+// finalize fills in defaults that a partial config zeroed out and validates
+// the merged config.
+func (c *Config) finalize() error {
+	// Ensure critical fields aren't zeroed out by partial config
+	if c.Harness.SessionDir == "" {
+		c.Harness.SessionDir = Default.Harness.SessionDir
+	}
+	if c.Harness.DefaultTimeout <= 0 {
+		c.Harness.DefaultTimeout = Default.Harness.DefaultTimeout
+	}
+	if c.Harness.MaxAttempts <= 0 {
+		c.Harness.MaxAttempts = Default.Harness.MaxAttempts
+	}
+	if c.Harness.AgentMemory != "" && !slices.Contains(AgentMemoryScopes, c.Harness.AgentMemory) {
+		return fmt.Errorf("harness.agent_memory must be one of %s", strings.Join(AgentMemoryScopes, ", "))
+	}
+	if c.Harness.TestVisibility != "" && !slices.Contains(TestVisibilities, c.Harness.TestVisibility) {
+		return fmt.Errorf("harness.test_visibility must be one of %s", strings.Join(TestVisibilities, ", "))
+	}
+	if c.Harness.RepairAttempts < 0 {
+		return errors.New("harness.repair_attempts must not be negative")
+	}
+	if c.Harness.AttemptFeedback != "" && !slices.Contains(AttemptFeedbackModes, c.Harness.AttemptFeedback) {
+		return fmt.Errorf("harness.attempt_feedback must be one of %s", strings.Join(AttemptFeedbackModes, ", "))
+	}
+	if c.Hooks.Timeout <= 0 {
+		c.Hooks.Timeout = Default.Hooks.Timeout
+	}
+	c.Termination.normalize()
+	if c.Termination.Signal == "" {
+		c.Termination.Signal = Default.Termination.Signal
+	}
+	if err := c.Termination.validate(); err != nil {
+		return err
+	}
+	if err := c.Upload.validate(); err != nil {
+		return err
+	}
+	if err := c.Encryption.validate(); err != nil {
+		return err
+	}
+	c.Notify.normalize()
+	if err := c.Notify.validate(); err != nil {
+		return err
+	}
+	if c.Upload.AccessKeyEnv == "" {
+		c.Upload.AccessKeyEnv = Default.Upload.AccessKeyEnv
+	}
+	if c.Upload.SecretKeyEnv == "" {
+		c.Upload.SecretKeyEnv = Default.Upload.SecretKeyEnv
+	}
+	if c.Upload.SessionTokenEnv == "" {
+		c.Upload.SessionTokenEnv = Default.Upload.SessionTokenEnv
+	}
+	if err := c.Judge.validate(); err != nil {
+		return err
+	}
+	if c.Judge.APIKeyEnv == "" {
+		c.Judge.APIKeyEnv = Default.Judge.APIKeyEnv
+	}
+	if c.Judge.Timeout == 0 {
+		c.Judge.Timeout = Default.Judge.Timeout
+	}
+	if c.Plagiarism.Threshold < 0 || c.Plagiarism.Threshold > 1 {
+		return errors.New("plagiarism.threshold must be between 0 and 1")
+	}
+	if c.Plagiarism.Threshold == 0 {
+		c.Plagiarism.Threshold = Default.Plagiarism.Threshold
+	}
+	if err := c.Retention.validate(); err != nil {
+		return err
+	}
+	for _, id := range c.Anonymize.Identifiers {
+		if strings.TrimSpace(id) == "" {
+			return errors.New("anonymize.identifiers must not contain empty strings")
 		}
-	} else {
-		for _, p := range configPaths() {
-			if _, err := os.Stat(p); err == nil {
-				path = p
-				break
-			}
+	}
+	if err := validateChaos(c.Chaos); err != nil {
+		return err
+	}
+	if err := validatePromptVariants(c.PromptVariants); err != nil {
+		return err
+	}
+	if err := validateToolchains(c.Toolchains); err != nil {
+		return err
+	}
+	if err := validateMCPServers(c.MCPServers); err != nil {
+		return err
+	}
+	if err := c.Sampling.validate(); err != nil {
+		return err
+	}
+	if err := c.Retry.validate(); err != nil {
+		return err
+	}
+	if err := c.Container.validate("container"); err != nil {
+		return err
+	}
+	if err := c.Container.validateNetwork(); err != nil {
+		return err
+	}
+	if c.Container.Network == "" {
+		c.Container.Network = ContainerNetworkOpen
+	}
+	if err := validateTaskOverrides(c.Tasks); err != nil {
+		return err
+	}
+	if err := c.PromptBudget.validate(); err != nil {
+		return err
+	}
+	for name, agent := range c.Agents {
+		if err := agent.validate(); err != nil {
+			return fmt.Errorf("agents.%s.%w", name, err)
 		}
 	}
-
-	if path == "" {
-		return &cfg, nil
+	if c.PromptBudget.Action == "" {
+		c.PromptBudget.Action = PromptBudgetWarn
 	}
-
-	if _, err := toml.DecodeFile(path, &cfg); err != nil {
-		return nil, fmt.Errorf("failed to parse config %s: %w", path, err)
+	if c.Docker.GoImage == "" {
+		c.Docker.GoImage = Default.Docker.GoImage
 	}
-
-	// Ensure critical fields aren't zeroed out by partial config
-	if cfg.Harness.SessionDir == "" {
-		cfg.Harness.SessionDir = Default.Harness.SessionDir
+	if c.Docker.RustImage == "" {
+		c.Docker.RustImage = Default.Docker.RustImage
+	}
+	if c.Docker.TypeScriptImage == "" {
+		c.Docker.TypeScriptImage = Default.Docker.TypeScriptImage
 	}
-	if cfg.Harness.DefaultTimeout <= 0 {
-		cfg.Harness.DefaultTimeout = Default.Harness.DefaultTimeout
+	if c.Docker.KotlinImage == "" {
+		c.Docker.KotlinImage = Default.Docker.KotlinImage
 	}
-	if cfg.Harness.MaxAttempts <= 0 {
-		cfg.Harness.MaxAttempts = Default.Harness.MaxAttempts
+	if c.Docker.DartImage == "" {
+		c.Docker.DartImage = Default.Docker.DartImage
 	}
-	if cfg.Docker.GoImage == "" {
-		cfg.Docker.GoImage = Default.Docker.GoImage
+	if c.Docker.ZigImage == "" {
+		c.Docker.ZigImage = Default.Docker.ZigImage
 	}
-	if cfg.Docker.RustImage == "" {
-		cfg.Docker.RustImage = Default.Docker.RustImage
+	if c.Docker.PullRetries < 0 {
+		return errors.New("docker.pull_retries must not be negative")
 	}
-	if cfg.Docker.TypeScriptImage == "" {
-		cfg.Docker.TypeScriptImage = Default.Docker.TypeScriptImage
+	if c.Docker.ContainerBackend == "" {
+		c.Docker.ContainerBackend = ContainerBackendDocker
 	}
-	if cfg.Docker.KotlinImage == "" {
-		cfg.Docker.KotlinImage = Default.Docker.KotlinImage
+	if !slices.Contains(ContainerBackends, c.Docker.ContainerBackend) {
+		return fmt.Errorf("docker.container_backend must be one of %s", strings.Join(ContainerBackends, ", "))
 	}
-	if cfg.Docker.DartImage == "" {
-		cfg.Docker.DartImage = Default.Docker.DartImage
+	if c.Docker.LocalSandbox == "" {
+		c.Docker.LocalSandbox = LocalSandboxAuto
 	}
-	if cfg.Docker.ZigImage == "" {
-		cfg.Docker.ZigImage = Default.Docker.ZigImage
+	if !slices.Contains(LocalSandboxes, c.Docker.LocalSandbox) {
+		return fmt.Errorf("docker.local_sandbox must be one of %s", strings.Join(LocalSandboxes, ", "))
 	}
 
-	return &cfg, nil
+	return nil
+}
+
+// resolveContextFiles makes sources absolute relative to baseDir, fills in
+// default destinations, and rejects destinations outside the workspace.
+func resolveContextFiles(files []ContextFile, baseDir string) error {
+	for i := range files {
+		f := &files[i]
+		if f.Source == "" {
+			return fmt.Errorf("context_files[%d]: source is required", i)
+		}
+		if !filepath.IsAbs(f.Source) {
+			f.Source = filepath.Join(baseDir, f.Source)
+		}
+		if f.Dest == "" {
+			f.Dest = filepath.Base(f.Source)
+		}
+		dest := filepath.Clean(filepath.FromSlash(f.Dest))
+		if filepath.IsAbs(dest) || dest == "." || dest == ".." || strings.HasPrefix(dest, ".."+string(filepath.Separator)) {
+			return fmt.Errorf("context_files[%d]: dest %q must be inside the workspace", i, f.Dest)
+		}
+		f.Dest = filepath.ToSlash(dest)
+	}
+	return nil
 }
 
 // ImageForLanguage returns the Docker image for a given language.
@@ -370,6 +846,24 @@ func (c *Config) ImageForLanguage(lang string) string {
 	}
 }
 
+// SetImageForLanguage overrides the Docker image for a given language.
+func (c *Config) SetImageForLanguage(lang, image string) {
+	switch lang {
+	case "go":
+		c.Docker.GoImage = image
+	case "rust":
+		c.Docker.RustImage = image
+	case "typescript":
+		c.Docker.TypeScriptImage = image
+	case "kotlin":
+		c.Docker.KotlinImage = image
+	case "dart":
+		c.Docker.DartImage = image
+	case "zig":
+		c.Docker.ZigImage = image
+	}
+}
+
 // GetAgent returns the agent configuration for the given name.
 // User-configured agents take precedence over built-in defaults.
 // Returns nil if the agent is not found.
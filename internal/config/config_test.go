@@ -3,6 +3,7 @@ package config
 import (
 	"os"
 	"path/filepath"
+	"strings"
 	"testing"
 )
 
@@ -120,6 +121,221 @@ shared_readonly_dirs = [".local/bin", "bin"]
 	}
 }
 
+func TestLoadValidation(t *testing.T) {
+	t.Parallel()
+
+	const localAgent = "[agents.local]\ncommand = \"local-agent\"\n"
+	tests := []struct {
+		name  string
+		data  string
+		want  string                                      // Substring of the Load error; empty for a valid config
+		check func(t *testing.T, cfg *Config, dir string) // Inspects a valid config loaded from dir
+	}{
+		{"agent_memory", "[harness]\nagent_memory = \"task\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.Harness.AgentMemory != AgentMemoryTask {
+				t.Errorf("agent memory = %q, want task", cfg.Harness.AgentMemory)
+			}
+		}},
+		{"agent_memory invalid", "[harness]\nagent_memory = \"session\"\n", "agent_memory", nil},
+
+		{"prompt_mode", localAgent + "args = [\"--task\", \"{prompt_file}\", \"--cwd\", \"{workspace}\"]\nprompt_mode = \"file\"\ndone_pattern = \"^DONE$\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if got := cfg.Agents["local"]; got.PromptMode != PromptModeFile || got.DonePattern != "^DONE$" {
+				t.Errorf("agent = %+v, want file prompt mode and a done pattern", got)
+			}
+		}},
+		{"prompt_mode invalid", localAgent + "prompt_mode = \"pipe\"\n", "prompt_mode", nil},
+		{"prompt_file without file mode", localAgent + "args = [\"{prompt_file}\"]\n", "{prompt_file}", nil},
+		{"prompt with stdin mode", localAgent + "prompt_mode = \"stdin\"\nargs = [\"{prompt}\"]\n", "{prompt}", nil},
+		{"done_pattern invalid", localAgent + "done_pattern = \"(\"\n", "done_pattern", nil},
+		{"plugin protocol with prompt", localAgent + "protocol = \"plugin\"\nargs = [\"{prompt}\"]\n", "protocol", nil},
+		{"protocol invalid", localAgent + "protocol = \"grpc\"\n", "protocol", nil},
+
+		{"test_visibility", "[harness]\ntest_visibility = \"none\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.Harness.TestVisibility != TestVisibilityNone {
+				t.Errorf("test visibility = %q, want none", cfg.Harness.TestVisibility)
+			}
+		}},
+		{"test_visibility invalid", "[harness]\ntest_visibility = \"hidden\"\n", "test_visibility", nil},
+
+		{"attempt_feedback", "[harness]\nrepair_attempts = 2\nattempt_feedback = \"full_test_output\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.Harness.RepairAttempts != 2 || cfg.Harness.AttemptFeedback != AttemptFeedbackTestOutput {
+				t.Errorf("harness = %+v, want 2 repair attempts with full_test_output", cfg.Harness)
+			}
+		}},
+		{"attempt_feedback invalid", "[harness]\nattempt_feedback = \"hints\"\n", "attempt_feedback", nil},
+		{"repair_attempts negative", "[harness]\nrepair_attempts = -1\n", "repair_attempts", nil},
+
+		{"container_backend", "[docker]\ncontainer_backend = \"podman\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.Docker.ContainerBackend != ContainerBackendPodman {
+				t.Errorf("container_backend = %q, want podman", cfg.Docker.ContainerBackend)
+			}
+		}},
+		{"container_backend invalid", "[docker]\ncontainer_backend = \"lxc\"\n", "container_backend", nil},
+
+		{"local_sandbox", "[docker]\ncontainer_backend = \"local\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.Docker.ContainerBackend != ContainerBackendLocal || cfg.Docker.LocalSandbox != LocalSandboxAuto {
+				t.Errorf("container_backend, local_sandbox = %q, %q, want local, auto", cfg.Docker.ContainerBackend, cfg.Docker.LocalSandbox)
+			}
+		}},
+		{"local_sandbox invalid", "[docker]\nlocal_sandbox = \"chroot\"\n", "local_sandbox", nil},
+
+		{"termination", "[termination]\nsignal = \"int\"\ngrace_period = 30\n", "", func(t *testing.T, cfg *Config, _ string) {
+			want := TerminationConfig{Signal: "SIGINT", GracePeriod: 30, ContainerStopTimeout: Default.Termination.ContainerStopTimeout}
+			if cfg.Termination != want {
+				t.Errorf("termination = %+v, want %+v", cfg.Termination, want)
+			}
+		}},
+		{"termination signal invalid", "[termination]\nsignal = \"SIGSTOP\"\n", "termination.", nil},
+		{"termination grace_period negative", "[termination]\ngrace_period = -1\n", "termination.", nil},
+		{"termination container_stop_timeout negative", "[termination]\ncontainer_stop_timeout = -5\n", "termination.", nil},
+
+		{"upload", "[upload]\nurl = \"gs://bench-artifacts/nightly/\"\nsecret_key_env = \"GCS_SECRET\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.Upload.SecretKeyEnv != "GCS_SECRET" || cfg.Upload.AccessKeyEnv != Default.Upload.AccessKeyEnv {
+				t.Errorf("upload key envs = %q, %q", cfg.Upload.AccessKeyEnv, cfg.Upload.SecretKeyEnv)
+			}
+			target, err := ParseUploadURL(cfg.Upload.URL)
+			if err != nil {
+				t.Fatalf("ParseUploadURL() error = %v", err)
+			}
+			if want := (UploadTarget{Scheme: UploadSchemeGCS, Bucket: "bench-artifacts", Prefix: "nightly"}); target != want {
+				t.Errorf("target = %+v, want %+v", target, want)
+			}
+			if got := cfg.Upload.EndpointFor(target.Scheme); got != "https://storage.googleapis.com" {
+				t.Errorf("EndpointFor(gs) = %q", got)
+			}
+			if got := (UploadConfig{Region: "eu-west-1"}).EndpointFor(UploadSchemeS3); got != "https://s3.eu-west-1.amazonaws.com" {
+				t.Errorf("EndpointFor(s3) = %q", got)
+			}
+		}},
+		{"upload url scheme invalid", "[upload]\nurl = \"https://bucket/prefix\"\n", "upload.", nil},
+		{"upload url without bucket", "[upload]\nurl = \"s3:///prefix\"\n", "upload.", nil},
+		{"upload endpoint invalid", "[upload]\nendpoint = \"minio:9000\"\n", "upload.", nil},
+
+		{"notify", "[notify]\non = \"failure\"\n\n[notify.email]\nsmtp_host = \"smtp.example.com\"\ntls = \"tls\"\nfrom = \"SanityHarness <bench@example.com>\"\nto = [\"team@example.com\"]\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.Notify.On != NotifyFailure || !cfg.Notify.Email.Enabled() {
+				t.Errorf("notify = %+v", cfg.Notify)
+			}
+			if cfg.Notify.Email.SMTPPort != 465 || cfg.Notify.Email.UsernameEnv != "SMTP_USERNAME" {
+				t.Errorf("email defaults = port %d, username_env %q", cfg.Notify.Email.SMTPPort, cfg.Notify.Email.UsernameEnv)
+			}
+		}},
+		{"notify on invalid", "[notify]\non = \"sometimes\"\n", "notify.", nil},
+		{"notify email without smtp_host", "[notify.email]\nto = [\"team@example.com\"]\nfrom = \"bench@example.com\"\n", "notify.", nil},
+		{"notify email bad address", "[notify.email]\nsmtp_host = \"smtp.example.com\"\nto = [\"not an address\"]\nfrom = \"bench@example.com\"\n", "notify.", nil},
+		{"notify email tls invalid", "[notify.email]\ntls = \"ssl\"\n", "notify.", nil},
+
+		{"encryption", "[encryption]\nrecipients = [\"age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p\"]\nrecipients_file = \"keys/team.pub\"\nidentity = \"/secure/key.txt\"\n", "", func(t *testing.T, cfg *Config, dir string) {
+			if !cfg.Encryption.Enabled() {
+				t.Error("Enabled() = false with recipients set")
+			}
+			if want := filepath.Join(dir, "keys", "team.pub"); cfg.Encryption.RecipientsFile != want {
+				t.Errorf("recipients_file = %q, want %q", cfg.Encryption.RecipientsFile, want)
+			}
+			if cfg.Encryption.Identity != "/secure/key.txt" {
+				t.Errorf("identity = %q", cfg.Encryption.Identity)
+			}
+		}},
+		{"encryption recipients invalid", "[encryption]\nrecipients = [\"hunter2\"]\n", "encryption.recipients", nil},
+
+		{"context_files", "[[context_files]]\nsource = \"guides/AGENTS.md\"\n\n[[context_files]]\nsource = \"/opt/style/go.md\"\ndest = \"docs/STYLE.md\"\nlanguages = [\"go\"]\n", "", func(t *testing.T, cfg *Config, dir string) {
+			if len(cfg.ContextFiles) != 2 {
+				t.Fatalf("context files = %v, want 2 entries", cfg.ContextFiles)
+			}
+			first, second := cfg.ContextFiles[0], cfg.ContextFiles[1]
+			if first.Source != filepath.Join(dir, "guides", "AGENTS.md") || first.Dest != "AGENTS.md" {
+				t.Errorf("relative source not resolved: %+v", first)
+			}
+			if !first.AppliesTo("rust") {
+				t.Error("file without languages should apply to every language")
+			}
+			if second.Dest != "docs/STYLE.md" || !second.AppliesTo("go") || second.AppliesTo("rust") {
+				t.Errorf("unexpected second context file: %+v", second)
+			}
+		}},
+		{"context_files dest outside workspace", "[[context_files]]\nsource = \"a.md\"\ndest = \"../a.md\"\n", "must be inside the workspace", nil},
+
+		{"prompt_budget", "[prompt_budget]\ndefault = 8000\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if cfg.PromptBudget.Action != PromptBudgetWarn || cfg.PromptBudget.LimitFor("any") != 8000 {
+				t.Errorf("prompt budget = %+v", cfg.PromptBudget)
+			}
+		}},
+		{"prompt_budget action invalid", "[prompt_budget]\naction = \"truncate\"\n", "prompt_budget.action", nil},
+		{"prompt_budget optional invalid", "[prompt_budget]\noptional = [\"task_description\"]\n", "prompt_budget.optional", nil},
+		{"prompt_budget model pattern invalid", "[prompt_budget.models]\n\"[\" = 100\n", "prompt_budget.models", nil},
+
+		{"judge", "[judge]\nendpoint = \"http://localhost:8080/v1/chat/completions\"\nmodel = \"judge\"\n", "", func(t *testing.T, cfg *Config, _ string) {
+			if err := cfg.Judge.Ready(); err != nil {
+				t.Errorf("Ready() error = %v", err)
+			}
+			if cfg.Judge.APIKeyEnv != "SANITY_JUDGE_API_KEY" || cfg.Judge.Timeout != 120 {
+				t.Errorf("judge defaults not applied: %+v", cfg.Judge)
+			}
+			if err := Default.Judge.Ready(); err == nil {
+				t.Error("default judge config should not be ready")
+			}
+		}},
+		{"judge endpoint invalid", "[judge]\nendpoint = \"localhost:8080\"\n", "judge.endpoint", nil},
+		{"judge timeout negative", "[judge]\ntimeout = -1\n", "judge.timeout", nil},
+		{"judge rubric scale inverted", "[judge.rubric]\nscale_min = 5\nscale_max = 1\n", "scale_min", nil},
+		{"judge rubric prompt without reply format", "[judge.rubric]\nprompt = \"Rate the code.\"\n", "judge.rubric: prompt", nil},
+		{"judge rubrics without match", "[[judge.rubrics]]\nname = \"no-match\"\n", "match is required", nil},
+		{"judge rubrics reserved criterion", "[[judge.rubrics]]\nmatch = [\"rust\"]\n[[judge.rubrics.criteria]]\nname = \"reasoning\"\n", "judge.rubrics[0]", nil},
+
+		{"plagiarism", "[plagiarism]\ncorpus = \"solutions\"\n", "", func(t *testing.T, cfg *Config, dir string) {
+			if cfg.Plagiarism.Corpus != filepath.Join(dir, "solutions") || cfg.Plagiarism.Threshold != 0.8 {
+				t.Errorf("plagiarism = %+v", cfg.Plagiarism)
+			}
+		}},
+		{"plagiarism threshold above 1", "[plagiarism]\nthreshold = 1.5\n", "plagiarism.threshold", nil},
+	}
+	for _, tc := range tests {
+		t.Run(tc.name, func(t *testing.T) {
+			t.Parallel()
+
+			dir := t.TempDir()
+			cfgPath := filepath.Join(dir, "sanity.toml")
+			if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+				t.Fatalf("writing config: %v", err)
+			}
+			cfg, err := Load(cfgPath)
+			if tc.want != "" {
+				if err == nil || !strings.Contains(err.Error(), tc.want) {
+					t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("Load() error = %v", err)
+			}
+			if tc.check != nil {
+				tc.check(t, cfg, dir)
+			}
+		})
+	}
+}
+
+func TestPromptBudgetLimitFor(t *testing.T) {
+	t.Parallel()
+
+	b := PromptBudgetConfig{
+		Default: 100000,
+		Models:  map[string]int{"qwen3-coder-30b": 24000, "*-8b*": 12000, "local/*": 16000},
+	}
+	tests := map[string]int{
+		"qwen3-coder-30b":   24000,
+		"llama-3.1-8b-q4":   12000,
+		"local/mistral-7b":  16000,
+		"gemini-2.5-pro":    100000,
+		"":                  100000,
+		"openrouter/gpt-4o": 100000,
+	}
+	for model, want := range tests {
+		if got := b.LimitFor(model); got != want {
+			t.Errorf("LimitFor(%q) = %d, want %d", model, got, want)
+		}
+	}
+}
+
 func TestLoadMissingExplicitFile(t *testing.T) {
 	t.Parallel()
 
@@ -0,0 +1,132 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"math"
+	"slices"
+	"strings"
+
+	"github.com/docker/go-units"
+)
+
+// minContainerMemory is the smallest memory limit Docker accepts.
+const minContainerMemory = 6 << 20
+
+// ContainerConfig caps the resources of each task container, so a runaway
+// compile cannot starve the host or push other parallel tasks into the OOM
+// killer, and limits where it can connect. Zero values leave a resource
+// unlimited.
+type ContainerConfig struct {
+	CPUs         float64  `toml:"cpus"`          // CPU cores per container, fractions allowed (like docker --cpus)
+	Memory       string   `toml:"memory"`        // Memory per container, e.g. "512m" or "4g" (like docker --memory); no swap on top
+	Network      string   `toml:"network"`       // Egress: "open" (default), "none", or "allowlist"
+	AllowedHosts []string `toml:"allowed_hosts"` // Hosts reachable with network = "allowlist"; "*.example.com" matches subdomains
+}
+
+// Values for [container] network. With "allowlist", containers join an
+// internal network with no route out and reach AllowedHosts through a proxy
+// the harness runs.
+const (
+	ContainerNetworkOpen      = "open"
+	ContainerNetworkNone      = "none"
+	ContainerNetworkAllowlist = "allowlist"
+)
+
+// ContainerNetworks lists the valid [container] network values.
+var ContainerNetworks = []string{ContainerNetworkOpen, ContainerNetworkNone, ContainerNetworkAllowlist}
+
+// DefaultAllowedHosts are the package registries the bundled tasks fetch
+// dependencies from.
+var DefaultAllowedHosts = []string{
+	"crates.io",
+	"*.crates.io",
+	"proxy.golang.org",
+	"sum.golang.org",
+	"registry.npmjs.org",
+	"repo.maven.apache.org",
+	"repo1.maven.org",
+	"plugins.gradle.org",
+	"services.gradle.org",
+	"pub.dev",
+	"storage.googleapis.com",
+	"ziglang.org",
+}
+
+func (c ContainerConfig) validate(prefix string) error {
+	if c.CPUs < 0 || math.IsInf(c.CPUs, 0) || math.IsNaN(c.CPUs) {
+		return fmt.Errorf("%s.cpus must be a non-negative number", prefix)
+	}
+	if _, err := ParseMemoryLimit(c.Memory); err != nil {
+		return fmt.Errorf("%s.memory: %w", prefix, err)
+	}
+	return nil
+}
+
+// validateNetwork checks the [container] egress settings, which have no
+// per-task override.
+func (c ContainerConfig) validateNetwork() error {
+	if c.Network != "" && !slices.Contains(ContainerNetworks, c.Network) {
+		return fmt.Errorf("container.network must be one of %s", strings.Join(ContainerNetworks, ", "))
+	}
+	for _, host := range c.AllowedHosts {
+		name := strings.TrimPrefix(host, "*.")
+		if name == "" || strings.ContainsAny(name, "/:*@ ") {
+			return fmt.Errorf("container.allowed_hosts: %q must be a host name like \"crates.io\" or \"*.crates.io\", without a scheme or port", host)
+		}
+	}
+	return nil
+}
+
+// HostAllowed reports whether network = "allowlist" lets containers connect
+// to host. Names compare case-insensitively; "*.example.com" matches every
+// subdomain of example.com but not example.com itself.
+func (c ContainerConfig) HostAllowed(host string) bool {
+	host = strings.TrimSuffix(strings.ToLower(host), ".")
+	for _, allowed := range c.AllowedHosts {
+		allowed = strings.ToLower(allowed)
+		if suffix, ok := strings.CutPrefix(allowed, "*"); ok {
+			if strings.HasSuffix(host, suffix) && len(host) > len(suffix) {
+				return true
+			}
+		} else if host == allowed {
+			return true
+		}
+	}
+	return false
+}
+
+// ParseMemoryLimit parses a memory limit like "512m" or "4g" into bytes, with
+// the binary units docker --memory uses. "" is no limit and returns 0.
+func ParseMemoryLimit(s string) (int64, error) {
+	s = strings.TrimSpace(s)
+	if s == "" {
+		return 0, nil
+	}
+	n, err := units.RAMInBytes(s)
+	if err != nil {
+		return 0, fmt.Errorf("invalid size %q (use e.g. \"512m\" or \"4g\")", s)
+	}
+	if n < minContainerMemory {
+		return 0, errors.New("must be at least 6m")
+	}
+	return n, nil
+}
+
+// ContainerLimits returns the CPU limit, in billionths of a core, and the
+// memory limit, in bytes, of the task with the given "language/slug" ID: its
+// [task] override where set, otherwise [container]. Zero is no limit.
+func (c *Config) ContainerLimits(taskID string) (nanoCPUs, memory int64) {
+	o := c.Tasks[taskID]
+	cpus := c.Container.CPUs
+	if o.CPUs > 0 {
+		cpus = o.CPUs
+	}
+	limit := c.Container.Memory
+	if o.Memory != "" {
+		limit = o.Memory
+	}
+	// Both were validated on load.
+	memory, _ = ParseMemoryLimit(limit)
+	return int64(math.Round(cpus * 1e9)), memory
+}
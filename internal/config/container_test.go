@@ -0,0 +1,138 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestLoadContainerLimits(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := `[container]
+cpus = 1.5
+memory = "2g"
+
+[task."rust/regex-lite"]
+cpus = 4
+memory = "6g"
+
+[task."go/bank-account"]
+memory = "512m"
+`
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	tests := []struct {
+		task       string
+		wantCPUs   int64
+		wantMemory int64
+	}{
+		{"go/dining-philosophers", 1_500_000_000, 2 << 30},
+		{"rust/regex-lite", 4_000_000_000, 6 << 30},
+		{"go/bank-account", 1_500_000_000, 512 << 20},
+	}
+	for _, tc := range tests {
+		cpus, memory := cfg.ContainerLimits(tc.task)
+		if cpus != tc.wantCPUs || memory != tc.wantMemory {
+			t.Errorf("ContainerLimits(%s) = %d, %d, want %d, %d", tc.task, cpus, memory, tc.wantCPUs, tc.wantMemory)
+		}
+	}
+
+	if cpus, memory := Default.ContainerLimits("go/bank-account"); cpus != 0 || memory != 0 {
+		t.Errorf("default ContainerLimits() = %d, %d, want no limits", cpus, memory)
+	}
+}
+
+func TestLoadContainerLimitsInvalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[container]\ncpus = -1\n", "container.cpus"},
+		{"[container]\nmemory = \"lots\"\n", "container.memory"},
+		{"[container]\nmemory = \"1m\"\n", "at least 6m"},
+		{"[task.\"go/bank-account\"]\nmemory = \"2x\"\n", "task.\"go/bank-account\".memory"},
+		{"[task.\"go/bank-account\"]\ncpus = -0.5\n", "task.\"go/bank-account\".cpus"},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
+
+func TestParseMemoryLimit(t *testing.T) {
+	t.Parallel()
+
+	for in, want := range map[string]int64{"": 0, "512m": 512 << 20, "4g": 4 << 30, "4GB": 4 << 30, "1.5g": 3 << 29} {
+		if got, err := ParseMemoryLimit(in); err != nil || got != want {
+			t.Errorf("ParseMemoryLimit(%q) = %d, %v, want %d", in, got, err, want)
+		}
+	}
+}
+
+func TestLoadContainerNetwork(t *testing.T) {
+	t.Parallel()
+
+	dir := t.TempDir()
+	valid := filepath.Join(dir, "valid.toml")
+	if err := os.WriteFile(valid, []byte("[container]\nnetwork = \"allowlist\"\nallowed_hosts = [\"*.crates.io\", \"api.example.com\"]\n"), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(valid)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if cfg.Container.Network != ContainerNetworkAllowlist {
+		t.Errorf("network = %q, want allowlist", cfg.Container.Network)
+	}
+	for host, want := range map[string]bool{
+		"index.crates.io":  true,
+		"STATIC.crates.io": true,
+		"crates.io":        false,
+		"evilcrates.io":    false,
+		"api.example.com":  true,
+		"api.example.com.": true,
+		"example.com":      false,
+		"proxy.golang.org": false,
+	} {
+		if got := cfg.Container.HostAllowed(host); got != want {
+			t.Errorf("HostAllowed(%q) = %v, want %v", host, got, want)
+		}
+	}
+	if !Default.Container.HostAllowed("proxy.golang.org") || Default.Container.Network != ContainerNetworkOpen {
+		t.Errorf("default [container] = %+v, want open with the registries allowed", Default.Container)
+	}
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[container]\nnetwork = \"offline\"\n", "container.network must be one of"},
+		{"[container]\nallowed_hosts = [\"https://crates.io\"]\n", "without a scheme or port"},
+		{"[container]\nallowed_hosts = [\"crates.io:443\"]\n", "without a scheme or port"},
+		{"[container]\nallowed_hosts = [\"*\"]\n", "allowed_hosts"},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
@@ -0,0 +1,63 @@
+package config
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+// EncryptionConfig encrypts agent transcripts and provider logs at rest with
+// age once each task finishes, so they can only be read with a matching key.
+// Recipients are age public keys or SSH public keys.
+type EncryptionConfig struct {
+	Recipients     []string `toml:"recipients"`      // Public keys: "age1...", "ssh-ed25519 ...", or "ssh-rsa ..."
+	RecipientsFile string   `toml:"recipients_file"` // File of recipients, one per line, e.g. ~/.ssh/id_ed25519.pub
+	Identity       string   `toml:"identity"`        // age identity or SSH private key that debug and export decrypt with; may be set without recipients
+}
+
+// recipientPrefixes lists the public key formats age accepts as recipients.
+var recipientPrefixes = []string{"age1", "ssh-ed25519 ", "ssh-rsa "}
+
+// Enabled reports whether artifacts are encrypted.
+func (c EncryptionConfig) Enabled() bool {
+	return len(c.Recipients) > 0 || c.RecipientsFile != ""
+}
+
+// resolvePaths expands ~/ and resolves relative key paths against baseDir.
+func (c *EncryptionConfig) resolvePaths(baseDir string) {
+	c.RecipientsFile = resolveKeyPath(c.RecipientsFile, baseDir)
+	c.Identity = resolveKeyPath(c.Identity, baseDir)
+}
+
+func resolveKeyPath(p, baseDir string) string {
+	switch {
+	case p == "" || filepath.IsAbs(p):
+		return p
+	case strings.HasPrefix(p, "~/"):
+		if home, err := os.UserHomeDir(); err == nil {
+			return filepath.Join(home, strings.TrimPrefix(p, "~/"))
+		}
+		return p
+	default:
+		return filepath.Join(baseDir, p)
+	}
+}
+
+func (c EncryptionConfig) validate() error {
+	for _, r := range c.Recipients {
+		if !hasRecipientPrefix(strings.TrimSpace(r)) {
+			return fmt.Errorf("encryption.recipients: %q is not an age or SSH public key", r)
+		}
+	}
+	return nil
+}
+
+func hasRecipientPrefix(r string) bool {
+	for _, prefix := range recipientPrefixes {
+		if strings.HasPrefix(r, prefix) {
+			return true
+		}
+	}
+	return false
+}
@@ -0,0 +1,167 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"net/url"
+	"slices"
+	"strings"
+)
+
+// JudgeConfig points the optional post-verification judge stage at an
+// OpenAI-compatible chat completions endpoint and defines what it scores.
+type JudgeConfig struct {
+	Endpoint  string        `toml:"endpoint"`    // Chat completions URL, e.g. https://api.openai.com/v1/chat/completions
+	Model     string        `toml:"model"`       // Judge model name sent in the request
+	APIKeyEnv string        `toml:"api_key_env"` // Environment variable holding the bearer token (default: SANITY_JUDGE_API_KEY)
+	Timeout   int           `toml:"timeout"`     // Per-request timeout in seconds (default: 120)
+	Rubric    JudgeRubric   `toml:"rubric"`      // Default rubric; unset fields fall back to the built-in rubric
+	Rubrics   []JudgeRubric `toml:"rubrics"`     // Per-category rubrics; the first whose match selects a task wins
+}
+
+// JudgeRubric defines the criteria, scale, and system prompt used to judge a
+// solution. Unset fields of a per-category rubric fall back to the default
+// rubric.
+type JudgeRubric struct {
+	Name     string           `toml:"name"`      // Label recorded with each score (default: "default", or the match list)
+	Match    []string         `toml:"match"`     // Task IDs, languages, tiers, difficulties, or tags the rubric applies to
+	ScaleMin int              `toml:"scale_min"` // Lowest score (default: 1)
+	ScaleMax int              `toml:"scale_max"` // Highest score (default: 5)
+	Prompt   string           `toml:"prompt"`    // System prompt template; must contain {reply_format}
+	Criteria []JudgeCriterion `toml:"criteria"`  // Scored criteria (default: readability, idiomatic_style)
+}
+
+// JudgeCriterion is one scored dimension of a rubric.
+type JudgeCriterion struct {
+	Name     string  `toml:"name"`     // Key the judge replies with, e.g. "readability"
+	Guidance string  `toml:"guidance"` // What the judge should look for
+	Weight   float64 `toml:"weight"`   // Relative weight in the overall score (default: 1)
+}
+
+// Placeholders expanded in a rubric's prompt template.
+const (
+	JudgePlaceholderCriteria    = "{criteria}"
+	JudgePlaceholderScaleMin    = "{scale_min}"
+	JudgePlaceholderScaleMax    = "{scale_max}"
+	JudgePlaceholderLanguage    = "{language}"
+	JudgePlaceholderReplyFormat = "{reply_format}"
+)
+
+// JudgeReasoningKey is the reply field holding the judge's justification, so
+// criteria may not use it as a name.
+const JudgeReasoningKey = "reasoning"
+
+// DefaultJudgeRubric is used when the config does not override it.
+var DefaultJudgeRubric = JudgeRubric{
+	Name:     "default",
+	ScaleMin: 1,
+	ScaleMax: 5,
+	Prompt: `You are reviewing a solution to a programming task. Judge the code's quality only; correctness is measured separately by tests.
+
+Score each criterion as an integer from {scale_min} (poor) to {scale_max} (excellent):
+{criteria}
+
+{reply_format}`,
+	Criteria: []JudgeCriterion{
+		{Name: "readability", Guidance: "clear naming, structure, and control flow; comments where they help", Weight: 1},
+		{Name: "idiomatic_style", Guidance: "follows the language's conventions, idioms, and standard library", Weight: 1},
+	},
+}
+
+// Ready reports whether enough of the judge is configured to send requests.
+func (j JudgeConfig) Ready() error {
+	if j.Endpoint == "" || j.Model == "" {
+		return errors.New("judge.endpoint and judge.model must be set")
+	}
+	return nil
+}
+
+// RubricFor returns the rubric for a task: the first per-category rubric with
+// a match entry equal to the task's ID, language, tier, difficulty, or one of
+// its tags, otherwise the default rubric.
+func (j JudgeConfig) RubricFor(id, language, tier, difficulty string, tags []string) JudgeRubric {
+	base := j.Rubric.withDefaults(DefaultJudgeRubric)
+	for _, r := range j.Rubrics {
+		for _, m := range r.Match {
+			if m == id || m == language || m == tier || m == difficulty || slices.Contains(tags, m) {
+				if r.Name == "" {
+					r.Name = strings.Join(r.Match, ",")
+				}
+				return r.withDefaults(base)
+			}
+		}
+	}
+	return base
+}
+
+// withDefaults fills unset fields of r from base.
+func (r JudgeRubric) withDefaults(base JudgeRubric) JudgeRubric {
+	if r.Name == "" {
+		r.Name = base.Name
+	}
+	if r.ScaleMin == 0 && r.ScaleMax == 0 {
+		r.ScaleMin, r.ScaleMax = base.ScaleMin, base.ScaleMax
+	}
+	if r.Prompt == "" {
+		r.Prompt = base.Prompt
+	}
+	if len(r.Criteria) == 0 {
+		r.Criteria = base.Criteria
+	}
+	r.Criteria = slices.Clone(r.Criteria)
+	for i := range r.Criteria {
+		if r.Criteria[i].Weight == 0 {
+			r.Criteria[i].Weight = 1
+		}
+	}
+	return r
+}
+
+func (j JudgeConfig) validate() error {
+	if j.Timeout < 0 {
+		return errors.New("judge.timeout must not be negative")
+	}
+	if j.Endpoint != "" {
+		u, err := url.Parse(j.Endpoint)
+		if err != nil || (u.Scheme != "http" && u.Scheme != "https") || u.Host == "" {
+			return fmt.Errorf("judge.endpoint %q must be an http(s) URL", j.Endpoint)
+		}
+	}
+	base := j.Rubric.withDefaults(DefaultJudgeRubric)
+	if err := base.validate(); err != nil {
+		return fmt.Errorf("judge.rubric: %w", err)
+	}
+	for i, r := range j.Rubrics {
+		if len(r.Match) == 0 {
+			return fmt.Errorf("judge.rubrics[%d]: match is required", i)
+		}
+		if err := r.withDefaults(base).validate(); err != nil {
+			return fmt.Errorf("judge.rubrics[%d]: %w", i, err)
+		}
+	}
+	return nil
+}
+
+func (r JudgeRubric) validate() error {
+	if r.ScaleMin >= r.ScaleMax {
+		return fmt.Errorf("scale_min (%d) must be less than scale_max (%d)", r.ScaleMin, r.ScaleMax)
+	}
+	if !strings.Contains(r.Prompt, JudgePlaceholderReplyFormat) {
+		return fmt.Errorf("prompt must contain %s so the judge replies in a parseable format", JudgePlaceholderReplyFormat)
+	}
+	seen := make(map[string]bool, len(r.Criteria))
+	for i, c := range r.Criteria {
+		switch {
+		case c.Name == "":
+			return fmt.Errorf("criteria[%d]: name is required", i)
+		case c.Name == JudgeReasoningKey:
+			return fmt.Errorf("criteria[%d]: name %q is reserved", i, c.Name)
+		case seen[c.Name]:
+			return fmt.Errorf("criteria[%d]: duplicate name %q", i, c.Name)
+		case c.Weight < 0:
+			return fmt.Errorf("criteria[%d]: weight must not be negative", i)
+		}
+		seen[c.Name] = true
+	}
+	return nil
+}
@@ -0,0 +1,46 @@
+package config
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestJudgeRubricFor(t *testing.T) {
+	t.Parallel()
+
+	j := JudgeConfig{
+		Rubric: JudgeRubric{ScaleMin: 1, ScaleMax: 10},
+		Rubrics: []JudgeRubric{
+			{
+				Match:    []string{"rust", "go/bank-account"},
+				Criteria: []JudgeCriterion{{Name: "safety", Guidance: "no needless unsafe or unwrap", Weight: 2}, {Name: "readability"}},
+			},
+			{Name: "concurrency", Match: []string{"concurrency"}, ScaleMin: 0, ScaleMax: 3},
+		},
+	}
+
+	def := j.RubricFor("go/two-sum", "go", "core", "easy", nil)
+	if def.Name != "default" || def.ScaleMax != 10 || len(def.Criteria) != 2 || def.Prompt != DefaultJudgeRubric.Prompt {
+		t.Errorf("default rubric = %+v", def)
+	}
+
+	for _, id := range []string{"rust/lru", "go/bank-account"} {
+		lang, _, _ := strings.Cut(id, "/")
+		r := j.RubricFor(id, lang, "core", "hard", nil)
+		if r.Name != "rust,go/bank-account" || r.ScaleMin != 1 || r.ScaleMax != 10 {
+			t.Errorf("RubricFor(%q) = %+v", id, r)
+		}
+		if r.Criteria[0].Weight != 2 || r.Criteria[1].Weight != 1 {
+			t.Errorf("RubricFor(%q) weights = %+v", id, r.Criteria)
+		}
+	}
+
+	r := j.RubricFor("go/worker-pool", "go", "core", "hard", []string{"concurrency"})
+	if r.Name != "concurrency" || r.ScaleMin != 0 || r.ScaleMax != 3 || len(r.Criteria) != 2 {
+		t.Errorf("tag rubric = %+v", r)
+	}
+
+	if err := j.validate(); err != nil {
+		t.Errorf("validate() error = %v", err)
+	}
+}
@@ -0,0 +1,302 @@
+package config
+
+import (
+	"bytes"
+	"fmt"
+	"maps"
+	"os"
+	"path/filepath"
+	"slices"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/BurntSushi/toml"
+)
+
+// Config layers, lowest precedence first. Each layer's files are decoded
+// over the layers below it, so a file only needs the keys it changes.
+const (
+	LayerDefault = "default" // Built-in defaults
+	LayerSystem  = "system"  // /etc/sanity/config.toml
+	LayerUser    = "user"    // ~/.config/sanity/config.toml, then ~/.sanity.toml
+	LayerProject = "project" // sanity.toml in the working directory or the nearest parent, up to the repository root
+	LayerCLI     = "cli"     // --config file, then --set overrides
+)
+
+// systemConfigPath is the machine-wide config file.
+var systemConfigPath = "/etc/sanity/config.toml"
+
+// setOrigin is the path recorded for values set with --set.
+const setOrigin = "--set"
+
+// LayerFile is a config file and the layer it was loaded in.
+type LayerFile struct {
+	Layer string
+	Path  string // Empty for built-in defaults
+}
+
+func (f LayerFile) String() string {
+	if f.Path == "" {
+		return f.Layer
+	}
+	return fmt.Sprintf("%s (%s)", f.Layer, f.Path)
+}
+
+// LoadOptions selects the CLI layer of a layered load.
+type LoadOptions struct {
+	File      string   // Config file given with --config, applied over the project layer
+	Overrides []string // key=value pairs given with --set, applied last
+	Strict    bool     // Reject unknown keys, reporting every problem as a *ValidationError
+}
+
+// Origins records which layer set each config key.
+type Origins struct {
+	Files []LayerFile // Files that were loaded, lowest precedence first
+	keys  map[string]LayerFile
+}
+
+func newOrigins() *Origins {
+	return &Origins{keys: make(map[string]LayerFile)}
+}
+
+// record attributes the values md set to f. Tables are skipped: setting one
+// key of [harness] leaves the rest of the table to the layers below.
+func (o *Origins) record(f LayerFile, md toml.MetaData) {
+	if f.Path != setOrigin {
+		o.Files = append(o.Files, f)
+	}
+	for _, k := range md.Keys() {
+		if md.Type(k...) != "Hash" {
+			o.keys[k.String()] = f
+		}
+	}
+}
+
+// Of returns the layer that set key, or the nearest enclosing array of
+// tables. Keys no file set come from the built-in defaults.
+func (o *Origins) Of(key toml.Key) LayerFile {
+	for n := len(key); n > 0; n-- {
+		if f, ok := o.keys[key[:n].String()]; ok {
+			return f
+		}
+	}
+	return LayerFile{Layer: LayerDefault}
+}
+
+// DiscoverLayers lists the system, user, and project config files that exist,
+// lowest precedence first. The project file is the sanity.toml in dir or its
+// nearest parent, searching no further up than the repository root.
+func DiscoverLayers(dir string) []LayerFile {
+	var files []LayerFile
+	if fileExists(systemConfigPath) {
+		files = append(files, LayerFile{Layer: LayerSystem, Path: systemConfigPath})
+	}
+	if home, err := os.UserHomeDir(); err == nil {
+		for _, p := range []string{filepath.Join(home, ".config", "sanity", "config.toml"), filepath.Join(home, ".sanity.toml")} {
+			if fileExists(p) {
+				files = append(files, LayerFile{Layer: LayerUser, Path: p})
+			}
+		}
+	}
+	if p := findProjectConfig(dir); p != "" {
+		files = append(files, LayerFile{Layer: LayerProject, Path: p})
+	}
+	return files
+}
+
+// findProjectConfig walks up from dir to the first sanity.toml, stopping at
+// the directory that holds .git.
+func findProjectConfig(dir string) string {
+	dir, err := filepath.Abs(dir)
+	if err != nil {
+		return ""
+	}
+	for {
+		if p := filepath.Join(dir, "sanity.toml"); fileExists(p) {
+			return p
+		}
+		if _, err := os.Stat(filepath.Join(dir, ".git")); err == nil {
+			return ""
+		}
+		parent := filepath.Dir(dir)
+		if parent == dir {
+			return ""
+		}
+		dir = parent
+	}
+}
+
+func fileExists(path string) bool {
+	info, err := os.Stat(path)
+	return err == nil && !info.IsDir()
+}
+
+// LoadLayered resolves the config layers from the working directory: built-in
+// defaults, then the system, user, and project files, then the --config file
+// and --set overrides in opts. It also returns where each value came from.
+func LoadLayered(opts LoadOptions) (*Config, *Origins, error) {
+	wd, err := os.Getwd()
+	if err != nil {
+		return nil, nil, fmt.Errorf("getting working directory: %w", err)
+	}
+	files := DiscoverLayers(wd)
+	if opts.File != "" {
+		if _, err := os.Stat(opts.File); err != nil {
+			return nil, nil, fmt.Errorf("config file not found: %s", opts.File)
+		}
+		files = append(files, LayerFile{Layer: LayerCLI, Path: opts.File})
+	}
+	return loadLayers(files, opts.Overrides, wd, opts.Strict)
+}
+
+// loadLayers decodes files in order over the defaults, applies the key=value
+// overrides, and validates the result. Relative paths resolve against the
+// directory of the file that set them, or against wd for overrides. In strict
+// mode, parse errors, unknown keys, and invalid values across all files are
+// collected into one *ValidationError.
+func loadLayers(files []LayerFile, overrides []string, wd string, strict bool) (*Config, *Origins, error) {
+	cfg := Default // Start with defaults
+	origins := newOrigins()
+
+	var path string
+	var problems []Problem
+	for _, f := range files {
+		md, err := toml.DecodeFile(f.Path, &cfg)
+		if err != nil {
+			if strict {
+				problems = append(problems, parseProblem(f.Path, err))
+				continue
+			}
+			return nil, nil, fmt.Errorf("failed to parse config %s: %w", f.Path, err)
+		}
+		if strict {
+			problems = append(problems, unknownKeyProblems(f.Path, md.Undecoded())...)
+		}
+		if err := cfg.resolvePaths(filepath.Dir(f.Path)); err != nil {
+			return nil, nil, fmt.Errorf("invalid config %s: %w", f.Path, err)
+		}
+		origins.record(f, md)
+		path = f.Path
+	}
+	for _, o := range overrides {
+		md, err := applyOverride(&cfg, o)
+		if err != nil {
+			return nil, nil, err
+		}
+		if err := cfg.resolvePaths(wd); err != nil {
+			return nil, nil, fmt.Errorf("invalid --set %s: %w", o, err)
+		}
+		origins.record(LayerFile{Layer: LayerCLI, Path: setOrigin}, md)
+		if path == "" {
+			path = setOrigin
+		}
+	}
+
+	if len(problems) > 0 {
+		return nil, nil, &ValidationError{Problems: problems}
+	}
+	if path == "" {
+		return &cfg, origins, nil
+	}
+	if err := cfg.finalize(); err != nil {
+		if strict {
+			return nil, nil, &ValidationError{Problems: []Problem{{Path: path, Message: err.Error()}}}
+		}
+		return nil, nil, fmt.Errorf("invalid config %s: %w", path, err)
+	}
+	return &cfg, origins, nil
+}
+
+// applyOverride decodes one key=value override over cfg. The value is TOML,
+// and a value that does not parse as TOML is taken as a string, so
+// --set docker.go_image=golang:1.22 needs no quoting.
+func applyOverride(cfg *Config, override string) (toml.MetaData, error) {
+	key, value, ok := strings.Cut(override, "=")
+	key, value = strings.TrimSpace(key), strings.TrimSpace(value)
+	if !ok || key == "" {
+		return toml.MetaData{}, fmt.Errorf("invalid --set %q: want key=value", override)
+	}
+	var probe map[string]any
+	if _, err := toml.Decode(key+" = "+value, &probe); err != nil {
+		value = strconv.Quote(value)
+	}
+	md, err := toml.Decode(key+" = "+value, cfg)
+	if err != nil {
+		return toml.MetaData{}, fmt.Errorf("invalid --set %q: %w", override, err)
+	}
+	if undecoded := md.Undecoded(); len(undecoded) > 0 {
+		return toml.MetaData{}, fmt.Errorf("invalid --set %q: unknown config key %s", override, undecoded[0])
+	}
+	return md, nil
+}
+
+// Setting is one effective config value and the layer it came from.
+type Setting struct {
+	Key    string
+	Value  string // TOML representation
+	Origin LayerFile
+}
+
+// Settings flattens the config into one setting per leaf key, sorted by key.
+// Arrays, including arrays of tables, are single settings.
+func (c *Config) Settings(origins *Origins) ([]Setting, error) {
+	var buf bytes.Buffer
+	if err := toml.NewEncoder(&buf).Encode(c); err != nil {
+		return nil, fmt.Errorf("encoding config: %w", err)
+	}
+	var tree map[string]any
+	if _, err := toml.Decode(buf.String(), &tree); err != nil {
+		return nil, fmt.Errorf("decoding config: %w", err)
+	}
+	if origins == nil {
+		origins = newOrigins()
+	}
+	var settings []Setting
+	var walk func(prefix toml.Key, table map[string]any)
+	walk = func(prefix toml.Key, table map[string]any) {
+		for _, name := range slices.Sorted(maps.Keys(table)) {
+			key := append(slices.Clone(prefix), name)
+			if sub, ok := table[name].(map[string]any); ok {
+				walk(key, sub)
+				continue
+			}
+			settings = append(settings, Setting{Key: key.String(), Value: tomlValue(table[name]), Origin: origins.Of(key)})
+		}
+	}
+	walk(nil, tree)
+	return settings, nil
+}
+
+// tomlValue formats a decoded TOML value as TOML.
+func tomlValue(v any) string {
+	switch v := v.(type) {
+	case string:
+		return strconv.Quote(v)
+	case time.Time:
+		return v.Format(time.RFC3339)
+	case []map[string]any:
+		parts := make([]string, 0, len(v))
+		for _, t := range v {
+			parts = append(parts, tomlValue(t))
+		}
+		return "[" + strings.Join(parts, ", ") + "]"
+	case []any:
+		parts := make([]string, 0, len(v))
+		for _, e := range v {
+			parts = append(parts, tomlValue(e))
+		}
+		return "[" + strings.Join(parts, ", ") + "]"
+	case map[string]any:
+		if len(v) == 0 {
+			return "{}"
+		}
+		parts := make([]string, 0, len(v))
+		for _, k := range slices.Sorted(maps.Keys(v)) {
+			parts = append(parts, toml.Key{k}.String()+" = "+tomlValue(v[k]))
+		}
+		return "{ " + strings.Join(parts, ", ") + " }"
+	default:
+		return fmt.Sprint(v)
+	}
+}
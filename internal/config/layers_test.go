@@ -0,0 +1,139 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func writeLayer(t *testing.T, dir, data string) string {
+	t.Helper()
+	if err := os.MkdirAll(dir, 0o755); err != nil {
+		t.Fatalf("creating %s: %v", dir, err)
+	}
+	path := filepath.Join(dir, "config.toml")
+	if err := os.WriteFile(path, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	return path
+}
+
+func TestLoadLayersPrecedence(t *testing.T) {
+	t.Parallel()
+
+	root := t.TempDir()
+	system := writeLayer(t, filepath.Join(root, "etc"), "[harness]\nmax_attempts = 2\ndefault_timeout = 90\n")
+	user := writeLayer(t, filepath.Join(root, "home"), "[harness]\nmax_attempts = 3\n\n[plagiarism]\ncorpus = \"solutions\"\n")
+	project := writeLayer(t, filepath.Join(root, "repo"), "[docker]\ngo_image = \"custom-go:latest\"\n")
+
+	files := []LayerFile{
+		{Layer: LayerSystem, Path: system},
+		{Layer: LayerUser, Path: user},
+		{Layer: LayerProject, Path: project},
+	}
+	cfg, origins, err := loadLayers(files, []string{"harness.max_attempts=7", "docker.rust_image=rust:1.80"}, root, false)
+	if err != nil {
+		t.Fatalf("loadLayers() error = %v", err)
+	}
+
+	if cfg.Harness.MaxAttempts != 7 || cfg.Harness.DefaultTimeout != 90 {
+		t.Errorf("harness = %+v, want max_attempts 7 from --set and default_timeout 90 from system", cfg.Harness)
+	}
+	if cfg.Docker.GoImage != "custom-go:latest" || cfg.Docker.RustImage != "rust:1.80" {
+		t.Errorf("docker images = %q, %q", cfg.Docker.GoImage, cfg.Docker.RustImage)
+	}
+	if want := filepath.Join(root, "home", "solutions"); cfg.Plagiarism.Corpus != want {
+		t.Errorf("corpus = %q, want %q resolved against the user config", cfg.Plagiarism.Corpus, want)
+	}
+	if len(origins.Files) != 3 {
+		t.Errorf("origins.Files = %v, want the three config files", origins.Files)
+	}
+
+	for key, want := range map[string]LayerFile{
+		"harness.max_attempts":    {Layer: LayerCLI, Path: "--set"},
+		"harness.default_timeout": {Layer: LayerSystem, Path: system},
+		"plagiarism.corpus":       {Layer: LayerUser, Path: user},
+		"docker.go_image":         {Layer: LayerProject, Path: project},
+		"harness.session_dir":     {Layer: LayerDefault},
+	} {
+		if got := origins.Of(strings.Split(key, ".")); got != want {
+			t.Errorf("origin of %s = %v, want %v", key, got, want)
+		}
+	}
+}
+
+func TestLoadLayersInvalidOverride(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		override string
+		want     string
+	}{
+		{"harness.max_attempts", "want key=value"},
+		{"harness.no_such_key=1", "unknown config key"},
+		{"harness.max_attempts=many", "invalid --set"},
+		{"docker.pull_retries=-1", "pull_retries"},
+	}
+	for _, tc := range tests {
+		if _, _, err := loadLayers(nil, []string{tc.override}, t.TempDir(), false); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("--set %s error = %v, want it to mention %q", tc.override, err, tc.want)
+		}
+	}
+}
+
+func TestFindProjectConfig(t *testing.T) {
+	t.Parallel()
+
+	root := t.TempDir()
+	repo := filepath.Join(root, "repo")
+	nested := filepath.Join(repo, "tasks", "go")
+	if err := os.MkdirAll(nested, 0o755); err != nil {
+		t.Fatal(err)
+	}
+	if got := findProjectConfig(nested); got != "" {
+		t.Errorf("findProjectConfig() = %q, want none", got)
+	}
+
+	projectPath := filepath.Join(repo, "sanity.toml")
+	if err := os.WriteFile(projectPath, []byte("[harness]\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	if got := findProjectConfig(nested); got != projectPath {
+		t.Errorf("findProjectConfig() = %q, want %q", got, projectPath)
+	}
+
+	// The search stops at the repository root.
+	inner := filepath.Join(repo, "vendor", "other")
+	if err := os.MkdirAll(filepath.Join(inner, ".git"), 0o755); err != nil {
+		t.Fatal(err)
+	}
+	if got := findProjectConfig(inner); got != "" {
+		t.Errorf("findProjectConfig() = %q, want none past the repository root", got)
+	}
+}
+
+func TestSettings(t *testing.T) {
+	t.Parallel()
+
+	project := writeLayer(t, t.TempDir(), "[toolchains.rust-beta]\nimages = { rust = \"rust:beta\" }\n")
+	cfg, origins, err := loadLayers([]LayerFile{{Layer: LayerProject, Path: project}}, nil, "", false)
+	if err != nil {
+		t.Fatalf("loadLayers() error = %v", err)
+	}
+	settings, err := cfg.Settings(origins)
+	if err != nil {
+		t.Fatalf("Settings() error = %v", err)
+	}
+
+	byKey := make(map[string]Setting, len(settings))
+	for _, s := range settings {
+		byKey[s.Key] = s
+	}
+	if s := byKey["harness.max_attempts"]; s.Value != "5" || s.Origin.Layer != LayerDefault {
+		t.Errorf("harness.max_attempts = %+v, want 5 from defaults", s)
+	}
+	if s := byKey["toolchains.rust-beta.images.rust"]; s.Value != `"rust:beta"` || s.Origin.Path != project {
+		t.Errorf("toolchains.rust-beta.images.rust = %+v, want rust:beta from the project file", s)
+	}
+}
@@ -0,0 +1,41 @@
+package config
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+)
+
+// MCPServer is a stdio MCP server that eval --mcp-servers adds to the
+// agent's configuration for every task. The agent starts it like any server
+// of its own, inside the sandbox. {workspace} in Args and Env expands to the
+// task workspace.
+type MCPServer struct {
+	Command string            `toml:"command"` // Binary name or path
+	Args    []string          `toml:"args"`    // Arguments, e.g. ["-y", "@upstash/context7-mcp"]
+	Env     map[string]string `toml:"env"`     // Extra environment variables for the server
+}
+
+// MCPServerNames lists the configured MCP servers, sorted.
+func (c *Config) MCPServerNames() []string {
+	names := make([]string, 0, len(c.MCPServers))
+	for name := range c.MCPServers {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+func validateMCPServers(servers map[string]MCPServer) error {
+	for name, s := range servers {
+		switch {
+		case name == "all":
+			return fmt.Errorf("mcp_servers.%s: %q selects every server and cannot be a name", name, name)
+		case strings.ContainsAny(name, ", /.") || name == "":
+			return fmt.Errorf("mcp_servers.%s: name must not contain commas, slashes, dots, or spaces", name)
+		case strings.TrimSpace(s.Command) == "":
+			return fmt.Errorf("mcp_servers.%s.command is required", name)
+		}
+	}
+	return nil
+}
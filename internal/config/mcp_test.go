@@ -0,0 +1,60 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestLoadMCPServers(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := `[mcp_servers.context7]
+command = "npx"
+args = ["-y", "@upstash/context7-mcp"]
+
+[mcp_servers.fs]
+command = "mcp-server-filesystem"
+args = ["{workspace}"]
+env = { LOG_LEVEL = "warn" }
+`
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if got, want := cfg.MCPServerNames(), []string{"context7", "fs"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("MCPServerNames() = %v, want %v", got, want)
+	}
+	fs := cfg.MCPServers["fs"]
+	if fs.Command != "mcp-server-filesystem" || !reflect.DeepEqual(fs.Args, []string{"{workspace}"}) || fs.Env["LOG_LEVEL"] != "warn" {
+		t.Errorf("fs = %+v", fs)
+	}
+}
+
+func TestLoadMCPServersInvalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[mcp_servers.docs]\nargs = [\"serve\"]\n", "command is required"},
+		{"[mcp_servers.all]\ncommand = \"x\"\n", "cannot be a name"},
+		{"[mcp_servers.\"my docs\"]\ncommand = \"x\"\n", "must not contain"},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
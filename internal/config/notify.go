@@ -0,0 +1,102 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"net/mail"
+	"slices"
+	"strings"
+)
+
+// Notification triggers for NotifyConfig.On.
+const (
+	NotifyAlways  = "always"
+	NotifyFailure = "failure"
+)
+
+// NotifyTriggers lists the valid values of notify.on.
+var NotifyTriggers = []string{NotifyAlways, NotifyFailure}
+
+// SMTP connection security modes for EmailConfig.TLS.
+const (
+	SMTPStartTLS = "starttls"
+	SMTPTLS      = "tls"
+	SMTPNone     = "none"
+)
+
+// SMTPModes lists the valid values of notify.email.tls.
+var SMTPModes = []string{SMTPStartTLS, SMTPTLS, SMTPNone}
+
+// NotifyConfig sends a notification when an eval run completes. Every
+// configured channel receives the same run summary.
+type NotifyConfig struct {
+	On    string      `toml:"on"` // "always" (default) or "failure": only runs with failed tasks or an interrupt
+	Email EmailConfig `toml:"email"`
+}
+
+// EmailConfig delivers completion notifications over SMTP, for environments
+// where webhooks to chat tools aren't an option.
+type EmailConfig struct {
+	SMTPHost    string   `toml:"smtp_host"`    // SMTP server host
+	SMTPPort    int      `toml:"smtp_port"`    // SMTP server port (default: 587, or 465 with tls = "tls")
+	TLS         string   `toml:"tls"`          // "starttls" (default), "tls" for implicit TLS, or "none"
+	From        string   `toml:"from"`         // Sender address
+	To          []string `toml:"to"`           // Recipient addresses (empty = no email)
+	UsernameEnv string   `toml:"username_env"` // Environment variable holding the SMTP username (default: SMTP_USERNAME; unset = no auth)
+	PasswordEnv string   `toml:"password_env"` // Environment variable holding the SMTP password (default: SMTP_PASSWORD)
+}
+
+// Enabled reports whether email notifications are sent.
+func (c EmailConfig) Enabled() bool {
+	return len(c.To) > 0
+}
+
+func (c *NotifyConfig) normalize() {
+	c.On = strings.ToLower(strings.TrimSpace(c.On))
+	if c.On == "" {
+		c.On = Default.Notify.On
+	}
+	c.Email.TLS = strings.ToLower(strings.TrimSpace(c.Email.TLS))
+	if c.Email.TLS == "" {
+		c.Email.TLS = Default.Notify.Email.TLS
+	}
+	if c.Email.SMTPPort == 0 {
+		c.Email.SMTPPort = 587
+		if c.Email.TLS == SMTPTLS {
+			c.Email.SMTPPort = 465
+		}
+	}
+	if c.Email.UsernameEnv == "" {
+		c.Email.UsernameEnv = Default.Notify.Email.UsernameEnv
+	}
+	if c.Email.PasswordEnv == "" {
+		c.Email.PasswordEnv = Default.Notify.Email.PasswordEnv
+	}
+}
+
+func (c NotifyConfig) validate() error {
+	if !slices.Contains(NotifyTriggers, c.On) {
+		return fmt.Errorf("notify.on must be one of %s", strings.Join(NotifyTriggers, ", "))
+	}
+	if !slices.Contains(SMTPModes, c.Email.TLS) {
+		return fmt.Errorf("notify.email.tls must be one of %s", strings.Join(SMTPModes, ", "))
+	}
+	if c.Email.SMTPPort < 1 || c.Email.SMTPPort > 65535 {
+		return fmt.Errorf("notify.email.smtp_port %d is out of range", c.Email.SMTPPort)
+	}
+	if !c.Email.Enabled() {
+		return nil
+	}
+	if c.Email.SMTPHost == "" {
+		return errors.New("notify.email.smtp_host is required when notify.email.to is set")
+	}
+	if _, err := mail.ParseAddress(c.Email.From); err != nil {
+		return fmt.Errorf("notify.email.from %q is not an email address", c.Email.From)
+	}
+	for _, to := range c.Email.To {
+		if _, err := mail.ParseAddress(to); err != nil {
+			return fmt.Errorf("notify.email.to %q is not an email address", to)
+		}
+	}
+	return nil
+}
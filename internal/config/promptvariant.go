@@ -0,0 +1,63 @@
+package config
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+)
+
+// ControlPromptVariant names the built-in variant that sends the harness's
+// standard prompt unchanged.
+const ControlPromptVariant = "control"
+
+// PromptVariant is one arm of a prompt experiment. Template is the prompt sent
+// to the agent, with placeholders expanded per task.
+type PromptVariant struct {
+	Description string `toml:"description"` // Shown in reports
+	Template    string `toml:"template"`    // Prompt template; {prompt} expands to the standard prompt
+}
+
+// Placeholders expanded in a prompt variant's template.
+const (
+	PromptPlaceholderPrompt      = "{prompt}"
+	PromptPlaceholderTask        = "{task}"
+	PromptPlaceholderLanguage    = "{language}"
+	PromptPlaceholderDescription = "{description}"
+	PromptPlaceholderStubFiles   = "{stub_files}"
+	PromptPlaceholderTestFiles   = "{test_files}"
+)
+
+// PromptVariant returns the named variant. The control variant is always
+// available and cannot be redefined.
+func (c *Config) PromptVariant(name string) (PromptVariant, bool) {
+	if name == ControlPromptVariant {
+		return PromptVariant{Description: "standard prompt", Template: PromptPlaceholderPrompt}, true
+	}
+	v, ok := c.PromptVariants[name]
+	return v, ok
+}
+
+// PromptVariantNames lists the control variant and the configured variants,
+// sorted.
+func (c *Config) PromptVariantNames() []string {
+	names := []string{ControlPromptVariant}
+	for name := range c.PromptVariants {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+func validatePromptVariants(variants map[string]PromptVariant) error {
+	for name, v := range variants {
+		switch {
+		case name == ControlPromptVariant:
+			return fmt.Errorf("prompt_variants.%s: %q is built in and cannot be redefined", name, ControlPromptVariant)
+		case strings.ContainsAny(name, ", "):
+			return fmt.Errorf("prompt_variants.%s: name must not contain commas or spaces", name)
+		case strings.TrimSpace(v.Template) == "":
+			return fmt.Errorf("prompt_variants.%s.template must be set", name)
+		}
+	}
+	return nil
+}
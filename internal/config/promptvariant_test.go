@@ -0,0 +1,57 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestLoadPromptVariants(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := "[prompt_variants.terse]\ndescription = \"one line\"\ntemplate = \"Make the tests in {test_files} pass.\"\n"
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if v, ok := cfg.PromptVariant("terse"); !ok || v.Template != "Make the tests in {test_files} pass." {
+		t.Errorf("terse = %+v, %v", v, ok)
+	}
+	if v, ok := cfg.PromptVariant(ControlPromptVariant); !ok || v.Template != PromptPlaceholderPrompt {
+		t.Errorf("control = %+v, %v; want the standard prompt", v, ok)
+	}
+	if _, ok := cfg.PromptVariant("missing"); ok {
+		t.Error("PromptVariant(missing) should not be found")
+	}
+	if got, want := cfg.PromptVariantNames(), []string{"control", "terse"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("PromptVariantNames() = %v, want %v", got, want)
+	}
+}
+
+func TestLoadPromptVariantsInvalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[prompt_variants.control]\ntemplate = \"x\"\n", "built in"},
+		{"[prompt_variants.empty]\ndescription = \"no template\"\n", "template must be set"},
+		{"[prompt_variants.\"a,b\"]\ntemplate = \"x\"\n", "commas"},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
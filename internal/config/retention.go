@@ -0,0 +1,111 @@
+package config
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+	"time"
+)
+
+// Retention values that are not ages.
+const (
+	RetentionForever = "forever"
+	RetentionNever   = "never"
+)
+
+// RetentionConfig sets how long each kind of artifact is kept. Each value is
+// "forever" (the default), "never", or an age such as "30d" or "12h". Ages
+// count from when the run finished.
+type RetentionConfig struct {
+	Transcripts  string `toml:"transcripts"`   // Agent transcripts (agent.log)
+	Workspaces   string `toml:"workspaces"`    // Solution sources kept by --keep-workspaces
+	Snapshots    string `toml:"snapshots"`     // Per-attempt workspace snapshots (snapshots/)
+	ProviderLogs string `toml:"provider_logs"` // Raw model provider requests and replies (judge.json)
+	Sessions     string `toml:"sessions"`      // `sanity run` session directories
+}
+
+// RetentionPolicy is a parsed retention value. A zero MaxAge without Forever
+// means the artifact is not kept at all.
+type RetentionPolicy struct {
+	Forever bool
+	MaxAge  time.Duration
+}
+
+// ParseRetention parses a retention value; empty means forever.
+func ParseRetention(value string) (RetentionPolicy, error) {
+	switch v := strings.TrimSpace(value); v {
+	case "", RetentionForever:
+		return RetentionPolicy{Forever: true}, nil
+	case RetentionNever:
+		return RetentionPolicy{}, nil
+	default:
+		var age time.Duration
+		if days, ok := strings.CutSuffix(v, "d"); ok {
+			n, err := strconv.Atoi(days)
+			if err != nil {
+				return RetentionPolicy{}, fmt.Errorf("invalid retention %q", value)
+			}
+			age = time.Duration(n) * 24 * time.Hour
+		} else {
+			d, err := time.ParseDuration(v)
+			if err != nil {
+				return RetentionPolicy{}, fmt.Errorf("invalid retention %q (use forever, never, or an age like 30d)", value)
+			}
+			age = d
+		}
+		if age <= 0 {
+			return RetentionPolicy{}, fmt.Errorf("invalid retention %q: age must be positive", value)
+		}
+		return RetentionPolicy{MaxAge: age}, nil
+	}
+}
+
+// Expired reports whether an artifact finished at finishedAt is past its
+// retention at now.
+func (p RetentionPolicy) Expired(finishedAt, now time.Time) bool {
+	return !p.Forever && now.Sub(finishedAt) >= p.MaxAge
+}
+
+// Policies returns the parsed policy of each artifact kind, keyed by its
+// config name. Load has validated the values, so one that fails to parse is
+// kept forever.
+func (r RetentionConfig) Policies() map[string]RetentionPolicy {
+	policies := make(map[string]RetentionPolicy)
+	for kind, v := range r.values() {
+		p, err := ParseRetention(v)
+		if err != nil {
+			p = RetentionPolicy{Forever: true}
+		}
+		policies[kind] = p
+	}
+	return policies
+}
+
+// Enabled reports whether any artifact has a limited retention.
+func (r RetentionConfig) Enabled() bool {
+	for _, p := range r.Policies() {
+		if !p.Forever {
+			return true
+		}
+	}
+	return false
+}
+
+func (r RetentionConfig) values() map[string]string {
+	return map[string]string{
+		"transcripts":   r.Transcripts,
+		"workspaces":    r.Workspaces,
+		"snapshots":     r.Snapshots,
+		"provider_logs": r.ProviderLogs,
+		"sessions":      r.Sessions,
+	}
+}
+
+func (r RetentionConfig) validate() error {
+	for key, v := range r.values() {
+		if _, err := ParseRetention(v); err != nil {
+			return fmt.Errorf("retention.%s: %w", key, err)
+		}
+	}
+	return nil
+}
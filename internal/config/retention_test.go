@@ -0,0 +1,67 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+)
+
+func TestParseRetention(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		value string
+		want  RetentionPolicy
+	}{
+		{"", RetentionPolicy{Forever: true}},
+		{"forever", RetentionPolicy{Forever: true}},
+		{"never", RetentionPolicy{}},
+		{"30d", RetentionPolicy{MaxAge: 30 * 24 * time.Hour}},
+		{"12h", RetentionPolicy{MaxAge: 12 * time.Hour}},
+	}
+	for _, tc := range tests {
+		got, err := ParseRetention(tc.value)
+		if err != nil || got != tc.want {
+			t.Errorf("ParseRetention(%q) = %+v, %v; want %+v", tc.value, got, err, tc.want)
+		}
+	}
+	for _, bad := range []string{"soon", "0d", "-1h", "xd"} {
+		if _, err := ParseRetention(bad); err == nil {
+			t.Errorf("ParseRetention(%q) should fail", bad)
+		}
+	}
+
+	now := time.Now()
+	week, _ := ParseRetention("7d")
+	if week.Expired(now.Add(-6*24*time.Hour), now) || !week.Expired(now.Add(-8*24*time.Hour), now) {
+		t.Error("7d policy expired at the wrong age")
+	}
+	never, _ := ParseRetention("never")
+	if !never.Expired(now, now) {
+		t.Error("never policy should expire immediately")
+	}
+}
+
+func TestLoadRetention(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	if err := os.WriteFile(cfgPath, []byte("[retention]\nworkspaces = \"7d\"\nprovider_logs = \"never\"\n"), 0644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if cfg.Retention.Workspaces != "7d" || !cfg.Retention.Enabled() {
+		t.Errorf("retention = %+v", cfg.Retention)
+	}
+
+	if err := os.WriteFile(cfgPath, []byte("[retention]\ntranscripts = \"a while\"\n"), 0644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	if _, err := Load(cfgPath); err == nil {
+		t.Error("Load() should reject an invalid retention")
+	}
+}
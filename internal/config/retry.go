@@ -0,0 +1,85 @@
+package config
+
+import (
+	"fmt"
+	"math"
+	"time"
+)
+
+// Failure types that [retry] sets a policy for.
+const (
+	RetryAgentCrash   = "agent_crash"   // The agent exited without producing output
+	RetryAgentTimeout = "agent_timeout" // The agent stalled until its timeout
+	RetryProvider     = "provider"      // Rate limits and 429/5xx provider errors
+	RetryContainer    = "container"     // The validation container failed to run
+	RetryTestFailure  = "test_failure"  // Validation ran and tests failed
+	RetryFlaky        = "flaky"         // A test the task marks timing-sensitive failed
+)
+
+// RetryKinds lists the failure types in display order.
+var RetryKinds = []string{RetryAgentCrash, RetryAgentTimeout, RetryProvider, RetryContainer, RetryTestFailure, RetryFlaky}
+
+// RetryConfig sets how each type of failure is retried during eval. Agent
+// crashes, timeouts, and provider errors rerun the agent attempt; container
+// failures, test failures, and flaky timing tests rerun validation on the
+// same code.
+type RetryConfig struct {
+	AgentCrash   RetryPolicy `toml:"agent_crash"`
+	AgentTimeout RetryPolicy `toml:"agent_timeout"`
+	Provider     RetryPolicy `toml:"provider"`
+	Container    RetryPolicy `toml:"container"`
+	TestFailure  RetryPolicy `toml:"test_failure"`
+	Flaky        RetryPolicy `toml:"flaky"`
+}
+
+// RetryPolicy is the retry behavior for one failure type.
+type RetryPolicy struct {
+	MaxRetries int     `toml:"max_retries"` // Retries after the first failure (0 = never retry)
+	Delay      int     `toml:"delay"`       // Seconds before the first retry
+	Backoff    float64 `toml:"backoff"`     // Delay multiplier for each further retry (1 = constant)
+	MaxDelay   int     `toml:"max_delay"`   // Cap in seconds on any one delay (0 = none)
+}
+
+// Policy returns the policy for the named failure type.
+func (c RetryConfig) Policy(kind string) RetryPolicy {
+	switch kind {
+	case RetryAgentCrash:
+		return c.AgentCrash
+	case RetryAgentTimeout:
+		return c.AgentTimeout
+	case RetryProvider:
+		return c.Provider
+	case RetryContainer:
+		return c.Container
+	case RetryFlaky:
+		return c.Flaky
+	default:
+		return c.TestFailure
+	}
+}
+
+// Wait returns the delay before the nth retry (1-indexed).
+func (p RetryPolicy) Wait(n int) time.Duration {
+	seconds := float64(p.Delay) * math.Pow(p.Backoff, float64(max(n-1, 0)))
+	if p.MaxDelay > 0 {
+		seconds = min(seconds, float64(p.MaxDelay))
+	}
+	return time.Duration(seconds * float64(time.Second))
+}
+
+func (c RetryConfig) validate() error {
+	for _, kind := range RetryKinds {
+		p := c.Policy(kind)
+		switch {
+		case p.MaxRetries < 0:
+			return fmt.Errorf("retry.%s.max_retries must not be negative", kind)
+		case p.Delay < 0:
+			return fmt.Errorf("retry.%s.delay must not be negative", kind)
+		case p.Backoff < 1:
+			return fmt.Errorf("retry.%s.backoff must be at least 1", kind)
+		case p.MaxDelay < 0:
+			return fmt.Errorf("retry.%s.max_delay must not be negative", kind)
+		}
+	}
+	return nil
+}
@@ -0,0 +1,71 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestLoadRetry(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := `[retry.provider]
+max_retries = 8
+max_delay = 300
+
+[retry.test_failure]
+max_retries = 2
+delay = 5
+
+[retry.flaky]
+max_retries = 3
+`
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	provider := cfg.Retry.Policy(RetryProvider)
+	if provider.MaxRetries != 8 || provider.Delay != 30 || provider.Backoff != 2 {
+		t.Errorf("provider = %+v, want defaults kept for unset keys", provider)
+	}
+	for n, want := range map[int]time.Duration{1: 30 * time.Second, 2: time.Minute, 5: 300 * time.Second} {
+		if got := provider.Wait(n); got != want {
+			t.Errorf("provider.Wait(%d) = %v, want %v", n, got, want)
+		}
+	}
+	if p := cfg.Retry.Policy(RetryTestFailure); p.MaxRetries != 2 || p.Wait(3) != 5*time.Second {
+		t.Errorf("test_failure = %+v", p)
+	}
+	if p := cfg.Retry.Policy(RetryFlaky); p.MaxRetries != 3 || p.Delay != 5 {
+		t.Errorf("flaky = %+v, want defaults kept for unset keys", p)
+	}
+	if p := cfg.Retry.Policy(RetryContainer); p != Default.Retry.Container {
+		t.Errorf("container = %+v, want default", p)
+	}
+}
+
+func TestLoadRetryInvalid(t *testing.T) {
+	t.Parallel()
+
+	for _, tc := range []struct {
+		data, want string
+	}{
+		{"[retry.agent_crash]\nmax_retries = -1\n", "retry.agent_crash.max_retries"},
+		{"[retry.container]\nbackoff = 0.5\n", "retry.container.backoff"},
+		{"[retry.provider]\ndelay = -3\n", "retry.provider.delay"},
+	} {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
@@ -0,0 +1,76 @@
+package config
+
+import (
+	"fmt"
+	"slices"
+	"strings"
+)
+
+// Sampling parameter names, as used by [sampling], sampling_flags, and the
+// results JSON.
+const (
+	SamplingTemperature = "temperature"
+	SamplingTopP        = "top_p"
+	SamplingMinP        = "min_p"
+)
+
+// SamplingParams lists the sampling parameter names in display order.
+var SamplingParams = []string{SamplingTemperature, SamplingTopP, SamplingMinP}
+
+// SamplingConfig holds eval defaults for the model sampling parameters. Unset
+// values leave the agent's own defaults in place. --temperature, --top-p,
+// --min-p, and --reasoning-effort on eval override them.
+type SamplingConfig struct {
+	Temperature     *float64 `toml:"temperature"`      // 0 to 2
+	TopP            *float64 `toml:"top_p"`            // 0 to 1
+	MinP            *float64 `toml:"min_p"`            // 0 to 1
+	ReasoningEffort string   `toml:"reasoning_effort"` // Default for --reasoning
+}
+
+// Value returns the configured value of the named parameter, or nil.
+func (s SamplingConfig) Value(name string) *float64 {
+	switch name {
+	case SamplingTemperature:
+		return s.Temperature
+	case SamplingTopP:
+		return s.TopP
+	case SamplingMinP:
+		return s.MinP
+	}
+	return nil
+}
+
+// ValidateSamplingValue checks that v is in range for the named parameter.
+func ValidateSamplingValue(name string, v float64) error {
+	upper := 1.0
+	if name == SamplingTemperature {
+		upper = 2
+	}
+	if v < 0 || v > upper {
+		return fmt.Errorf("%s must be between 0 and %g", name, upper)
+	}
+	return nil
+}
+
+func (s SamplingConfig) validate() error {
+	for _, name := range SamplingParams {
+		if v := s.Value(name); v != nil {
+			if err := ValidateSamplingValue(name, *v); err != nil {
+				return fmt.Errorf("sampling.%w", err)
+			}
+		}
+	}
+	return nil
+}
+
+func validateSamplingFlags(flags map[string]string) error {
+	for name, flag := range flags {
+		if !slices.Contains(SamplingParams, name) {
+			return fmt.Errorf("sampling_flags: unknown parameter %q (valid: %s)", name, strings.Join(SamplingParams, ", "))
+		}
+		if strings.TrimSpace(flag) == "" {
+			return fmt.Errorf("sampling_flags.%s must not be empty", name)
+		}
+	}
+	return nil
+}
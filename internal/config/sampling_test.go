@@ -0,0 +1,61 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestLoadSampling(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := `[sampling]
+temperature = 0.2
+min_p = 0.05
+reasoning_effort = "high"
+
+[agents.local]
+command = "local-agent"
+sampling_flags = { temperature = "--temp" }
+`
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if v := cfg.Sampling.Value(SamplingTemperature); v == nil || *v != 0.2 {
+		t.Errorf("temperature = %v", v)
+	}
+	if cfg.Sampling.TopP != nil || cfg.Sampling.ReasoningEffort != "high" {
+		t.Errorf("sampling = %+v", cfg.Sampling)
+	}
+	if got := cfg.GetAgent("local").SamplingFlags[SamplingTemperature]; got != "--temp" {
+		t.Errorf("sampling_flags.temperature = %q", got)
+	}
+}
+
+func TestLoadSamplingInvalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[sampling]\ntemperature = 3.0\n", "sampling.temperature must be between 0 and 2"},
+		{"[sampling]\ntop_p = -0.1\n", "sampling.top_p must be between 0 and 1"},
+		{"[agents.local]\ncommand = \"x\"\nsampling_flags = { top_k = \"--top-k\" }\n", `unknown parameter "top_k"`},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
@@ -0,0 +1,161 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"strings"
+
+	"github.com/BurntSushi/toml"
+)
+
+// Problem is one issue found while strictly loading a config file.
+type Problem struct {
+	Path    string
+	Line    int // 1-based; 0 if unknown
+	Column  int // 1-based; 0 if unknown
+	Message string
+}
+
+func (p Problem) String() string {
+	var sb strings.Builder
+	sb.WriteString(p.Path)
+	if p.Line > 0 {
+		fmt.Fprintf(&sb, ":%d", p.Line)
+		if p.Column > 0 {
+			fmt.Fprintf(&sb, ":%d", p.Column)
+		}
+	}
+	sb.WriteString(": ")
+	sb.WriteString(p.Message)
+	return sb.String()
+}
+
+// ValidationError lists every problem a strict load found.
+type ValidationError struct {
+	Problems []Problem
+}
+
+func (e *ValidationError) Error() string {
+	lines := make([]string, 0, len(e.Problems))
+	for _, p := range e.Problems {
+		lines = append(lines, p.String())
+	}
+	return fmt.Sprintf("invalid config (%d problem(s)):\n  %s", len(e.Problems), strings.Join(lines, "\n  "))
+}
+
+// ValidateFile strictly loads path over the built-in defaults, returning a
+// *ValidationError that lists every problem found.
+func ValidateFile(path string) error {
+	if _, err := os.Stat(path); err != nil {
+		return fmt.Errorf("config file not found: %s", path)
+	}
+	_, _, err := loadLayers([]LayerFile{{Layer: LayerCLI, Path: path}}, nil, "", true)
+	return err
+}
+
+// parseProblem describes a file that failed to decode, with the position
+// the decoder reports.
+func parseProblem(path string, err error) Problem {
+	var perr toml.ParseError
+	if errors.As(err, &perr) {
+		return Problem{Path: path, Line: perr.Position.Line, Column: perr.Position.Col, Message: perr.Message}
+	}
+	return Problem{Path: path, Message: err.Error()}
+}
+
+// unknownKeyProblems reports the keys of a file that match no config
+// setting. A misspelled table is reported once, not once per key in it.
+func unknownKeyProblems(path string, undecoded []toml.Key) []Problem {
+	if len(undecoded) == 0 {
+		return nil
+	}
+	unknown := make(map[string]bool, len(undecoded))
+	for _, k := range undecoded {
+		unknown[k.String()] = true
+	}
+	data, _ := os.ReadFile(path)
+	problems := make([]Problem, 0, len(undecoded))
+	for _, k := range undecoded {
+		if hasUnknownParent(k, unknown) {
+			continue
+		}
+		line, col := locateKey(string(data), k)
+		problems = append(problems, Problem{Path: path, Line: line, Column: col, Message: "unknown key " + k.String()})
+	}
+	return problems
+}
+
+func hasUnknownParent(k toml.Key, unknown map[string]bool) bool {
+	for n := len(k) - 1; n > 0; n-- {
+		if unknown[k[:n].String()] {
+			return true
+		}
+	}
+	return false
+}
+
+// locateKey finds where key is set in a TOML document, returning a 1-based
+// line and column, or zeros if it cannot be found. It understands table
+// headers and dotted keys, which covers how config files set keys.
+func locateKey(doc string, key toml.Key) (line, col int) {
+	want := key.String()
+	var table toml.Key
+	for i, raw := range strings.Split(doc, "\n") {
+		trimmed := strings.TrimLeft(raw, " \t")
+		indent := len(raw) - len(trimmed)
+		switch {
+		case trimmed == "" || strings.HasPrefix(trimmed, "#"):
+			continue
+		case strings.HasPrefix(trimmed, "["):
+			header := strings.TrimLeft(trimmed, "[")
+			if end := strings.Index(header, "]"); end >= 0 {
+				header = header[:end]
+			}
+			table = splitKey(header)
+			if table.String() == want {
+				return i + 1, indent + 1
+			}
+		default:
+			lhs, _, ok := strings.Cut(trimmed, "=")
+			if !ok {
+				continue
+			}
+			full := append(append(toml.Key{}, table...), splitKey(lhs)...)
+			if full.String() == want {
+				return i + 1, indent + 1
+			}
+			// A key inside an inline table: point at its name on this line.
+			if len(key) > len(full) && key[:len(full)].String() == full.String() {
+				if at := strings.Index(raw, key[len(key)-1]); at >= 0 {
+					return i + 1, at + 1
+				}
+				return i + 1, indent + 1
+			}
+		}
+	}
+	return 0, 0
+}
+
+// splitKey splits a dotted TOML key, keeping quoted parts intact.
+func splitKey(s string) toml.Key {
+	key := make(toml.Key, 0, strings.Count(s, ".")+1)
+	var part strings.Builder
+	var quote rune
+	for _, r := range strings.TrimSpace(s) {
+		switch {
+		case quote != 0 && r == quote:
+			quote = 0
+		case quote != 0:
+			part.WriteRune(r)
+		case r == '"' || r == '\'':
+			quote = r
+		case r == '.':
+			key = append(key, strings.TrimSpace(part.String()))
+			part.Reset()
+		default:
+			part.WriteRune(r)
+		}
+	}
+	return append(key, strings.TrimSpace(part.String()))
+}
@@ -0,0 +1,94 @@
+package config
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func validateData(t *testing.T, data string) (string, error) {
+	t.Helper()
+	cfgPath := filepath.Join(t.TempDir(), "sanity.toml")
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	return cfgPath, ValidateFile(cfgPath)
+}
+
+func TestValidateFileUnknownKeys(t *testing.T) {
+	t.Parallel()
+
+	data := `[harness]
+max_attemps = 3
+
+[dockr]
+go_image = "golang:1.22"
+
+[toolchains.rust-beta]
+images = { rust = "rust:beta" }
+descripton = "typo"
+`
+	cfgPath, err := validateData(t, data)
+	var verr *ValidationError
+	if !errors.As(err, &verr) {
+		t.Fatalf("ValidateFile() error = %v, want a *ValidationError", err)
+	}
+	want := []Problem{
+		{Path: cfgPath, Line: 2, Column: 1, Message: "unknown key harness.max_attemps"},
+		{Path: cfgPath, Line: 4, Column: 1, Message: "unknown key dockr"},
+		{Path: cfgPath, Line: 9, Column: 1, Message: "unknown key toolchains.rust-beta.descripton"},
+	}
+	if !reflect.DeepEqual(verr.Problems, want) {
+		t.Errorf("problems = %+v, want %+v", verr.Problems, want)
+	}
+	if !strings.Contains(err.Error(), cfgPath+":2:1: unknown key harness.max_attemps") {
+		t.Errorf("Error() = %q, want the file, line, and column", err.Error())
+	}
+
+	// The same file loads without --strict-config, ignoring the unknown keys.
+	if _, err := Load(cfgPath); err != nil {
+		t.Errorf("Load() error = %v", err)
+	}
+}
+
+func TestValidateFileSyntaxAndValues(t *testing.T) {
+	t.Parallel()
+
+	_, err := validateData(t, "[harness]\nmax_attempts = 3 3\n")
+	var verr *ValidationError
+	if !errors.As(err, &verr) || len(verr.Problems) != 1 || verr.Problems[0].Line != 2 {
+		t.Errorf("syntax error = %v, want one problem on line 2", err)
+	}
+
+	_, err = validateData(t, "[docker]\npull_retries = -1\n")
+	if !errors.As(err, &verr) || !strings.Contains(err.Error(), "pull_retries must not be negative") {
+		t.Errorf("invalid value error = %v, want a validation problem", err)
+	}
+
+	if _, err := validateData(t, "[harness]\nmax_attempts = 3\n"); err != nil {
+		t.Errorf("valid config error = %v", err)
+	}
+}
+
+func TestLocateKey(t *testing.T) {
+	t.Parallel()
+
+	doc := "# comment\n[agents.\"my-agent\"]\n  command = \"x\"\nenv = { FOO = \"1\", BAR = \"2\" }\n"
+	tests := []struct {
+		key       []string
+		line, col int
+	}{
+		{[]string{"agents", "my-agent"}, 2, 1},
+		{[]string{"agents", "my-agent", "command"}, 3, 3},
+		{[]string{"agents", "my-agent", "env", "BAR"}, 4, 20},
+		{[]string{"agents", "other"}, 0, 0},
+	}
+	for _, tc := range tests {
+		if line, col := locateKey(doc, tc.key); line != tc.line || col != tc.col {
+			t.Errorf("locateKey(%v) = %d:%d, want %d:%d", tc.key, line, col, tc.line, tc.col)
+		}
+	}
+}
@@ -0,0 +1,57 @@
+package config
+
+import (
+	"fmt"
+	"strings"
+)
+
+// TaskOverride adjusts the settings of one task, e.g. a longer timeout for a
+// slow task, without changing them for every task. Zero values keep the
+// global settings.
+type TaskOverride struct {
+	Timeout     int     `toml:"timeout"`      // Seconds; replaces the task's timeout and the default or --timeout for this task
+	MaxAttempts int     `toml:"max_attempts"` // Replaces harness.max_attempts; --max-attempts still wins
+	Weight      float64 `toml:"weight"`       // Replaces the computed difficulty weight in weighted scores
+	Image       string  `toml:"image"`        // Replaces the [docker] image and any toolchain variant for this task
+	CPUs        float64 `toml:"cpus"`         // Replaces [container] cpus for this task's containers
+	Memory      string  `toml:"memory"`       // Replaces [container] memory for this task's containers
+}
+
+// TaskOverride returns the override for the task with the given
+// "language/slug" ID, or the zero value if it has none.
+func (c *Config) TaskOverride(taskID string) TaskOverride {
+	return c.Tasks[taskID]
+}
+
+// ImageForTask returns the Docker image for a task: its override image if
+// one is set, otherwise the image for its language.
+func (c *Config) ImageForTask(taskID, lang string) string {
+	if image := c.Tasks[taskID].Image; image != "" {
+		return image
+	}
+	return c.ImageForLanguage(lang)
+}
+
+func validateTaskOverrides(overrides map[string]TaskOverride) error {
+	for id, o := range overrides {
+		lang, slug, ok := strings.Cut(id, "/")
+		switch {
+		case !ok || slug == "" || strings.Contains(slug, "/"):
+			return fmt.Errorf("task.%q: key must be a task ID like \"go/bank-account\"", id)
+		case Default.ImageForLanguage(lang) == "":
+			return fmt.Errorf("task.%q: unknown language %q", id, lang)
+		case o.Timeout < 0:
+			return fmt.Errorf("task.%q.timeout must not be negative", id)
+		case o.MaxAttempts < 0:
+			return fmt.Errorf("task.%q.max_attempts must not be negative", id)
+		case o.Weight < 0:
+			return fmt.Errorf("task.%q.weight must not be negative", id)
+		case o.Image != "" && strings.TrimSpace(o.Image) == "":
+			return fmt.Errorf("task.%q.image must not be blank", id)
+		}
+		if err := (ContainerConfig{CPUs: o.CPUs, Memory: o.Memory}).validate(fmt.Sprintf("task.%q", id)); err != nil {
+			return err
+		}
+	}
+	return nil
+}
@@ -0,0 +1,59 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestLoadTaskOverrides(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := "[task.\"go/dining-philosophers\"]\ntimeout = 300\nmax_attempts = 2\nweight = 1.4\nimage = \"golang:1.22\"\n"
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	want := TaskOverride{Timeout: 300, MaxAttempts: 2, Weight: 1.4, Image: "golang:1.22"}
+	if got := cfg.TaskOverride("go/dining-philosophers"); got != want {
+		t.Errorf("TaskOverride() = %+v, want %+v", got, want)
+	}
+	if got := cfg.TaskOverride("go/bank-account"); got != (TaskOverride{}) {
+		t.Errorf("TaskOverride(go/bank-account) = %+v, want none", got)
+	}
+	if got := cfg.ImageForTask("go/dining-philosophers", "go"); got != "golang:1.22" {
+		t.Errorf("ImageForTask() = %q, want the override", got)
+	}
+	if got := cfg.ImageForTask("go/bank-account", "go"); got != cfg.Docker.GoImage {
+		t.Errorf("ImageForTask() = %q, want the [docker] image %q", got, cfg.Docker.GoImage)
+	}
+}
+
+func TestLoadTaskOverridesInvalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[task.\"bank-account\"]\ntimeout = 60\n", "task ID"},
+		{"[task.\"cobol/bank-account\"]\ntimeout = 60\n", "unknown language"},
+		{"[task.\"go/bank-account\"]\ntimeout = -1\n", "timeout must not be negative"},
+		{"[task.\"go/bank-account\"]\nweight = -0.5\n", "weight must not be negative"},
+		{"[task.\"go/bank-account\"]\nimage = \" \"\n", "image must not be blank"},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
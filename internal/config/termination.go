@@ -0,0 +1,50 @@
+package config
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+	"strings"
+)
+
+// SignalKill is the termination signal that stops a process at once.
+const SignalKill = "SIGKILL"
+
+// TerminationSignals lists the valid termination.signal values.
+var TerminationSignals = []string{"SIGTERM", "SIGINT", "SIGHUP", "SIGQUIT", SignalKill}
+
+// TerminationConfig sets how timed-out agent attempts and validation runs are
+// stopped. They get Signal first, so agents can flush transcripts and test
+// runners partial results, and SIGKILL once their grace period runs out.
+type TerminationConfig struct {
+	Signal               string `toml:"signal"`                 // Sent first: SIGTERM (default), SIGINT, SIGHUP, SIGQUIT, or SIGKILL to kill at once
+	GracePeriod          int    `toml:"grace_period"`           // Seconds a signaled agent gets before SIGKILL (default: 10; 0 = none)
+	ContainerStopTimeout int    `toml:"container_stop_timeout"` // Seconds signaled validation processes get before their container is killed (default: 10; 0 = none)
+}
+
+// Graceful reports whether a timed-out process gets a signal it can handle
+// before it is killed.
+func (c TerminationConfig) Graceful() bool {
+	return c.Signal != SignalKill
+}
+
+// normalize accepts signal names in any case and without the SIG prefix.
+func (c *TerminationConfig) normalize() {
+	c.Signal = strings.ToUpper(strings.TrimSpace(c.Signal))
+	if c.Signal != "" && !strings.HasPrefix(c.Signal, "SIG") {
+		c.Signal = "SIG" + c.Signal
+	}
+}
+
+func (c TerminationConfig) validate() error {
+	if !slices.Contains(TerminationSignals, c.Signal) {
+		return fmt.Errorf("termination.signal %q must be one of %s", c.Signal, strings.Join(TerminationSignals, ", "))
+	}
+	if c.GracePeriod < 0 {
+		return errors.New("termination.grace_period must not be negative")
+	}
+	if c.ContainerStopTimeout < 0 {
+		return errors.New("termination.container_stop_timeout must not be negative")
+	}
+	return nil
+}
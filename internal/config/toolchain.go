@@ -0,0 +1,62 @@
+package config
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+)
+
+// DefaultToolchain names the built-in toolchain variant that runs tasks in
+// the images configured under [docker].
+const DefaultToolchain = "default"
+
+// Toolchain is one toolchain variant of a matrix run: task images that
+// replace the [docker] images for some languages, such as a Rust beta image
+// or an older Go release.
+type Toolchain struct {
+	Description string            `toml:"description"` // Shown in reports
+	Images      map[string]string `toml:"images"`      // Language to image, e.g. rust = "ghcr.io/lemon07r/sanity-rust:beta"
+}
+
+// Toolchain returns the named variant. The default variant is always
+// available, keeps the [docker] images, and cannot be redefined.
+func (c *Config) Toolchain(name string) (Toolchain, bool) {
+	if name == DefaultToolchain {
+		return Toolchain{Description: "configured [docker] images"}, true
+	}
+	v, ok := c.Toolchains[name]
+	return v, ok
+}
+
+// ToolchainNames lists the default variant and the configured variants,
+// sorted.
+func (c *Config) ToolchainNames() []string {
+	names := []string{DefaultToolchain}
+	for name := range c.Toolchains {
+		names = append(names, name)
+	}
+	sort.Strings(names)
+	return names
+}
+
+func validateToolchains(toolchains map[string]Toolchain) error {
+	for name, tc := range toolchains {
+		switch {
+		case name == DefaultToolchain:
+			return fmt.Errorf("toolchains.%s: %q is built in and cannot be redefined", name, DefaultToolchain)
+		case strings.ContainsAny(name, ", /"):
+			return fmt.Errorf("toolchains.%s: name must not contain commas, slashes, or spaces", name)
+		case len(tc.Images) == 0:
+			return fmt.Errorf("toolchains.%s.images must set at least one language", name)
+		}
+		for lang, image := range tc.Images {
+			if Default.ImageForLanguage(lang) == "" {
+				return fmt.Errorf("toolchains.%s.images: unknown language %q", name, lang)
+			}
+			if strings.TrimSpace(image) == "" {
+				return fmt.Errorf("toolchains.%s.images.%s must not be empty", name, lang)
+			}
+		}
+	}
+	return nil
+}
@@ -0,0 +1,58 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+)
+
+func TestLoadToolchains(t *testing.T) {
+	t.Parallel()
+
+	cfgPath := filepath.Join(t.TempDir(), "test.toml")
+	data := "[toolchains.rust-beta]\ndescription = \"Rust beta channel\"\nimages = { rust = \"ghcr.io/lemon07r/sanity-rust:beta\" }\n"
+	if err := os.WriteFile(cfgPath, []byte(data), 0o644); err != nil {
+		t.Fatalf("writing config: %v", err)
+	}
+	cfg, err := Load(cfgPath)
+	if err != nil {
+		t.Fatalf("Load() error = %v", err)
+	}
+	if tc, ok := cfg.Toolchain("rust-beta"); !ok || tc.Images["rust"] != "ghcr.io/lemon07r/sanity-rust:beta" {
+		t.Errorf("rust-beta = %+v, %v", tc, ok)
+	}
+	if tc, ok := cfg.Toolchain(DefaultToolchain); !ok || len(tc.Images) != 0 {
+		t.Errorf("default = %+v, %v; want no image overrides", tc, ok)
+	}
+	if _, ok := cfg.Toolchain("missing"); ok {
+		t.Error("Toolchain(missing) should not be found")
+	}
+	if got, want := cfg.ToolchainNames(), []string{"default", "rust-beta"}; !reflect.DeepEqual(got, want) {
+		t.Errorf("ToolchainNames() = %v, want %v", got, want)
+	}
+}
+
+func TestLoadToolchainsInvalid(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		data string
+		want string
+	}{
+		{"[toolchains.default]\nimages = { go = \"golang:1.22\" }\n", "built in"},
+		{"[toolchains.empty]\ndescription = \"no images\"\n", "at least one language"},
+		{"[toolchains.cobol]\nimages = { cobol = \"cobol:latest\" }\n", "unknown language"},
+		{"[toolchains.\"go/1.22\"]\nimages = { go = \"golang:1.22\" }\n", "slashes"},
+	}
+	for _, tc := range tests {
+		cfgPath := filepath.Join(t.TempDir(), "test.toml")
+		if err := os.WriteFile(cfgPath, []byte(tc.data), 0o644); err != nil {
+			t.Fatalf("writing config: %v", err)
+		}
+		if _, err := Load(cfgPath); err == nil || !strings.Contains(err.Error(), tc.want) {
+			t.Errorf("Load(%q) error = %v, want it to mention %q", tc.data, err, tc.want)
+		}
+	}
+}
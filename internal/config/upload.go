@@ -0,0 +1,92 @@
+package config
+
+import (
+	"fmt"
+	"net/url"
+	"strings"
+)
+
+// Upload destination schemes.
+const (
+	UploadSchemeS3  = "s3"
+	UploadSchemeGCS = "gs"
+)
+
+// UploadConfig points eval at an S3-compatible bucket that receives each
+// run's artifacts as they are produced. Google Cloud Storage is reached
+// through its S3-compatible XML API with HMAC keys.
+type UploadConfig struct {
+	URL             string `toml:"url"`               // Destination, s3://bucket/prefix or gs://bucket/prefix (empty = no uploads)
+	Endpoint        string `toml:"endpoint"`          // API base URL (default: AWS for s3://, https://storage.googleapis.com for gs://)
+	Region          string `toml:"region"`            // Signing region (default: us-east-1 for s3://, auto for gs://)
+	PathStyle       bool   `toml:"path_style"`        // Address the bucket as endpoint/bucket instead of bucket.endpoint (MinIO, Ceph)
+	AccessKeyEnv    string `toml:"access_key_env"`    // Environment variable holding the access key ID (default: AWS_ACCESS_KEY_ID)
+	SecretKeyEnv    string `toml:"secret_key_env"`    // Environment variable holding the secret key (default: AWS_SECRET_ACCESS_KEY)
+	SessionTokenEnv string `toml:"session_token_env"` // Environment variable holding an optional session token (default: AWS_SESSION_TOKEN)
+}
+
+// UploadTarget is a parsed upload destination.
+type UploadTarget struct {
+	Scheme string
+	Bucket string
+	Prefix string // Key prefix without leading or trailing slashes
+}
+
+// ParseUploadURL parses an s3://bucket/prefix or gs://bucket/prefix URL.
+func ParseUploadURL(raw string) (UploadTarget, error) {
+	u, err := url.Parse(strings.TrimSpace(raw))
+	if err != nil {
+		return UploadTarget{}, fmt.Errorf("invalid upload URL %q: %w", raw, err)
+	}
+	if u.Scheme != UploadSchemeS3 && u.Scheme != UploadSchemeGCS {
+		return UploadTarget{}, fmt.Errorf("upload URL %q must start with s3:// or gs://", raw)
+	}
+	if u.Host == "" {
+		return UploadTarget{}, fmt.Errorf("upload URL %q has no bucket", raw)
+	}
+	return UploadTarget{
+		Scheme: u.Scheme,
+		Bucket: u.Host,
+		Prefix: strings.Trim(u.Path, "/"),
+	}, nil
+}
+
+// EndpointFor returns the API base URL for a destination scheme.
+func (c UploadConfig) EndpointFor(scheme string) string {
+	if c.Endpoint != "" {
+		return strings.TrimRight(c.Endpoint, "/")
+	}
+	if scheme == UploadSchemeGCS {
+		return "https://storage.googleapis.com"
+	}
+	if region := c.RegionFor(scheme); region != "us-east-1" {
+		return "https://s3." + region + ".amazonaws.com"
+	}
+	return "https://s3.amazonaws.com"
+}
+
+// RegionFor returns the signing region for a destination scheme.
+func (c UploadConfig) RegionFor(scheme string) string {
+	if c.Region != "" {
+		return c.Region
+	}
+	if scheme == UploadSchemeGCS {
+		return "auto"
+	}
+	return "us-east-1"
+}
+
+func (c UploadConfig) validate() error {
+	if c.URL != "" {
+		if _, err := ParseUploadURL(c.URL); err != nil {
+			return fmt.Errorf("upload.url: %w", err)
+		}
+	}
+	if c.Endpoint != "" {
+		u, err := url.Parse(c.Endpoint)
+		if err != nil || (u.Scheme != "http" && u.Scheme != "https") || u.Host == "" {
+			return fmt.Errorf("upload.endpoint %q must be an http(s) URL", c.Endpoint)
+		}
+	}
+	return nil
+}
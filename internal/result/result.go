@@ -22,6 +22,12 @@ const (
 	StatusError   Status = "error"
 )
 
+// How a timed-out process was stopped.
+const (
+	TerminationGraceful = "graceful" // Exited after the termination signal, within its grace period
+	TerminationKilled   = "killed"   // Killed with SIGKILL, at once or when its grace period ran out
+)
+
 // StatusEmoji maps status values to their emoji representations.
 var StatusEmoji = map[Status]string{
 	StatusPass:    "✅",
@@ -32,16 +38,17 @@ var StatusEmoji = map[Status]string{
 
 // Session represents a complete evaluation session.
 type Session struct {
-	ID          string            `json:"id"`
-	TaskSlug    string            `json:"task_slug"`
-	Language    string            `json:"language"`
-	Status      Status            `json:"status"`
-	Attempts    []Attempt         `json:"attempts"`
-	TotalTime   time.Duration     `json:"total_time_ns"`
-	StartedAt   time.Time         `json:"started_at"`
-	CompletedAt time.Time         `json:"completed_at"`
-	FinalCode   map[string]string `json:"final_code,omitempty"`
-	Config      SessionConfig     `json:"config"`
+	SchemaVersion int               `json:"schema_version"`
+	ID            string            `json:"id"`
+	TaskSlug      string            `json:"task_slug"`
+	Language      string            `json:"language"`
+	Status        Status            `json:"status"`
+	Attempts      []Attempt         `json:"attempts"`
+	TotalTime     time.Duration     `json:"total_time_ns"`
+	StartedAt     time.Time         `json:"started_at"`
+	CompletedAt   time.Time         `json:"completed_at"`
+	FinalCode     map[string]string `json:"final_code,omitempty"`
+	Config        SessionConfig     `json:"config"`
 }
 
 // SessionConfig captures the configuration used for a session.
@@ -54,13 +61,17 @@ type SessionConfig struct {
 
 // Attempt represents a single validation attempt.
 type Attempt struct {
-	Number       int           `json:"number"`
-	ExitCode     int           `json:"exit_code"`
-	Passed       bool          `json:"passed"`
-	Duration     time.Duration `json:"duration_ns"`
-	ErrorSummary []string      `json:"error_summary,omitempty"`
-	RawOutput    string        `json:"raw_output"`
-	Timestamp    time.Time     `json:"timestamp"`
+	Number          int           `json:"number"`
+	ExitCode        int           `json:"exit_code"`
+	Passed          bool          `json:"passed"`
+	Duration        time.Duration `json:"duration_ns"`
+	ErrorSummary    []string      `json:"error_summary,omitempty"`
+	RawOutput       string        `json:"raw_output"`
+	Timestamp       time.Time     `json:"timestamp"`
+	PeakMemoryBytes int64         `json:"peak_memory_bytes,omitempty"` // Container peak during the attempt; 0 if unmeasured
+	BinarySizeBytes int64         `json:"binary_size_bytes,omitempty"` // Size of the compiled test binaries; 0 if unmeasured
+	CPUSeconds      float64       `json:"cpu_seconds,omitempty"`       // Container CPU time used by the attempt; 0 if unmeasured
+	Termination     string        `json:"termination,omitempty"`       // How a timed-out attempt was stopped; empty if it finished
 }
 
 // NewSession creates a new session with the given parameters.
@@ -73,14 +84,15 @@ func NewSession(taskSlug, language string, cfg SessionConfig) *Session {
 	id := fmt.Sprintf("%s-%s-%s-%s", language, taskSlug, now.Format("2006-01-02T150405"), randSuffix)
 
 	return &Session{
-		ID:        id,
-		TaskSlug:  taskSlug,
-		Language:  language,
-		Status:    StatusFail,
-		Attempts:  make([]Attempt, 0),
-		StartedAt: now,
-		Config:    cfg,
-		FinalCode: make(map[string]string),
+		SchemaVersion: SessionSchemaVersion,
+		ID:            id,
+		TaskSlug:      taskSlug,
+		Language:      language,
+		Status:        StatusFail,
+		Attempts:      make([]Attempt, 0),
+		StartedAt:     now,
+		Config:        cfg,
+		FinalCode:     make(map[string]string),
 	}
 }
 
@@ -103,6 +115,50 @@ func (s *Session) AddAttempt(exitCode int, duration time.Duration, output string
 	}
 }
 
+// RecordPeakMemory stores the last attempt's peak memory usage in bytes and
+// fails a passing attempt whose peak exceeds limit. A limit of 0 means the
+// task has no memory bound.
+func (s *Session) RecordPeakMemory(peak, limit int64) {
+	attempt := s.LastAttempt()
+	if attempt == nil || peak <= 0 {
+		return
+	}
+	attempt.PeakMemoryBytes = peak
+	if limit > 0 && peak > limit && attempt.Passed {
+		attempt.Passed = false
+		attempt.ErrorSummary = append(attempt.ErrorSummary,
+			fmt.Sprintf("peak memory %s exceeds the task limit of %s", FormatMemory(peak), FormatMemory(limit)))
+		s.Status = StatusFail
+	}
+}
+
+// RecordBinarySize stores the size in bytes of the last attempt's compiled
+// test binaries and fails a passing attempt whose binaries exceed limit. A
+// limit of 0 means the task has no size bound.
+func (s *Session) RecordBinarySize(size, limit int64) {
+	attempt := s.LastAttempt()
+	if attempt == nil || size <= 0 {
+		return
+	}
+	attempt.BinarySizeBytes = size
+	if limit > 0 && size > limit && attempt.Passed {
+		attempt.Passed = false
+		attempt.ErrorSummary = append(attempt.ErrorSummary,
+			fmt.Sprintf("binary size %s exceeds the task limit of %s", FormatSize(size), FormatSize(limit)))
+		s.Status = StatusFail
+	}
+}
+
+// FormatSize renders a byte count in KiB.
+func FormatSize(bytes int64) string {
+	return fmt.Sprintf("%.1f KiB", float64(bytes)/(1<<10))
+}
+
+// FormatMemory renders a byte count in MiB.
+func FormatMemory(bytes int64) string {
+	return fmt.Sprintf("%.1f MiB", float64(bytes)/(1<<20))
+}
+
 // Complete finalizes the session.
 func (s *Session) Complete() {
 	s.CompletedAt = time.Now()
@@ -139,6 +195,7 @@ func (s *Session) Save(baseDir string) error {
 	}
 
 	// Write result.json
+	s.SchemaVersion = SessionSchemaVersion
 	resultJSON, err := json.MarshalIndent(s, "", "  ")
 	if err != nil {
 		return fmt.Errorf("marshaling result: %w", err)
@@ -193,6 +250,12 @@ func (s *Session) GenerateMarkdown() string {
 		fmt.Fprintf(&sb, "### Attempt %d - %s\n\n", attempt.Number, status)
 		fmt.Fprintf(&sb, "- **Exit Code:** %d\n", attempt.ExitCode)
 		fmt.Fprintf(&sb, "- **Duration:** %s\n", attempt.Duration.Round(time.Millisecond))
+		if attempt.PeakMemoryBytes > 0 {
+			fmt.Fprintf(&sb, "- **Peak Memory:** %s\n", FormatMemory(attempt.PeakMemoryBytes))
+		}
+		if attempt.BinarySizeBytes > 0 {
+			fmt.Fprintf(&sb, "- **Binary Size:** %s\n", FormatSize(attempt.BinarySizeBytes))
+		}
 		fmt.Fprintf(&sb, "- **Time:** %s\n\n", attempt.Timestamp.Format(time.RFC3339))
 
 		if len(attempt.ErrorSummary) > 0 {
@@ -84,6 +84,50 @@ func TestAddAttempt(t *testing.T) {
 	}
 }
 
+func TestRecordPeakMemory(t *testing.T) {
+	t.Parallel()
+
+	session := NewSession("test", "rust", SessionConfig{MaxAttempts: 1})
+	session.AddAttempt(0, time.Second, "ok", nil)
+	session.RecordPeakMemory(200<<20, 256<<20)
+	if !session.Passed() || session.Attempts[0].PeakMemoryBytes != 200<<20 {
+		t.Fatalf("within limit: status=%q attempt=%+v", session.Status, session.Attempts[0])
+	}
+
+	session = NewSession("test", "rust", SessionConfig{MaxAttempts: 1})
+	session.AddAttempt(0, time.Second, "ok", nil)
+	session.RecordPeakMemory(300<<20, 256<<20)
+	attempt := session.Attempts[0]
+	if session.Passed() || attempt.Passed {
+		t.Fatalf("over limit should fail: status=%q attempt=%+v", session.Status, attempt)
+	}
+	if len(attempt.ErrorSummary) != 1 || attempt.ErrorSummary[0] != "peak memory 300.0 MiB exceeds the task limit of 256.0 MiB" {
+		t.Fatalf("ErrorSummary = %v", attempt.ErrorSummary)
+	}
+}
+
+func TestRecordBinarySize(t *testing.T) {
+	t.Parallel()
+
+	session := NewSession("test", "go", SessionConfig{MaxAttempts: 1})
+	session.AddAttempt(0, time.Second, "ok", nil)
+	session.RecordBinarySize(3<<20, 2<<20)
+	attempt := session.Attempts[0]
+	if session.Passed() || attempt.BinarySizeBytes != 3<<20 {
+		t.Fatalf("over limit should fail: status=%q attempt=%+v", session.Status, attempt)
+	}
+	if len(attempt.ErrorSummary) != 1 || attempt.ErrorSummary[0] != "binary size 3072.0 KiB exceeds the task limit of 2048.0 KiB" {
+		t.Fatalf("ErrorSummary = %v", attempt.ErrorSummary)
+	}
+
+	session = NewSession("test", "go", SessionConfig{MaxAttempts: 1})
+	session.AddAttempt(0, time.Second, "ok", nil)
+	session.RecordBinarySize(3<<20, 0)
+	if !session.Passed() {
+		t.Fatal("no limit should not fail")
+	}
+}
+
 func TestComplete(t *testing.T) {
 	t.Parallel()
 
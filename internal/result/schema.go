@@ -0,0 +1,96 @@
+package result
+
+import (
+	"encoding/json"
+	"fmt"
+)
+
+// SchemaVersionKey is the JSON field that stamps a result file's format.
+// Files written before versioning was introduced lack it and are version 0.
+const SchemaVersionKey = "schema_version"
+
+// Migration upgrades a decoded JSON document by exactly one schema version.
+// Migrations only see generic JSON values, so they keep working after the Go
+// types they originally fed have changed.
+type Migration func(doc map[string]any) error
+
+// Migrations is an ordered upgrade path: element i upgrades a version-i
+// document to version i+1, so the current version is len(m).
+type Migrations []Migration
+
+// Current returns the schema version produced by the running harness.
+func (m Migrations) Current() int {
+	return len(m)
+}
+
+// Apply upgrades doc in place to the current version and returns the version
+// it started at. Documents from a newer harness are rejected rather than
+// silently misread.
+func (m Migrations) Apply(doc map[string]any) (int, error) {
+	from, err := documentVersion(doc)
+	if err != nil {
+		return 0, err
+	}
+	if from > m.Current() {
+		return from, fmt.Errorf("schema version %d is newer than supported version %d; upgrade sanity", from, m.Current())
+	}
+	for v := from; v < m.Current(); v++ {
+		if err := m[v](doc); err != nil {
+			return from, fmt.Errorf("migrating schema v%d to v%d: %w", v, v+1, err)
+		}
+		doc[SchemaVersionKey] = v + 1
+	}
+	return from, nil
+}
+
+// Decode unmarshals data into v after upgrading it to the current version.
+func (m Migrations) Decode(data []byte, v any) error {
+	var doc map[string]any
+	if err := json.Unmarshal(data, &doc); err != nil {
+		return err
+	}
+	if _, err := m.Apply(doc); err != nil {
+		return err
+	}
+	upgraded, err := json.Marshal(doc)
+	if err != nil {
+		return fmt.Errorf("re-encoding migrated document: %w", err)
+	}
+	return json.Unmarshal(upgraded, v)
+}
+
+func documentVersion(doc map[string]any) (int, error) {
+	raw, ok := doc[SchemaVersionKey]
+	if !ok || raw == nil {
+		return 0, nil
+	}
+	f, ok := raw.(float64)
+	if !ok || f < 0 || f != float64(int(f)) {
+		return 0, fmt.Errorf("invalid %s %v", SchemaVersionKey, raw)
+	}
+	return int(f), nil
+}
+
+// sessionMigrations upgrades result.json files.
+var sessionMigrations = Migrations{
+	// v0 -> v1: versioning introduced. Very early sessions could serialize a
+	// nil attempt list as null.
+	func(doc map[string]any) error {
+		if doc["attempts"] == nil {
+			doc["attempts"] = []any{}
+		}
+		return nil
+	},
+}
+
+// SessionSchemaVersion is the current result.json schema version.
+var SessionSchemaVersion = sessionMigrations.Current()
+
+// ParseSession decodes a result.json document, upgrading older formats.
+func ParseSession(data []byte) (*Session, error) {
+	var s Session
+	if err := sessionMigrations.Decode(data, &s); err != nil {
+		return nil, fmt.Errorf("parsing session: %w", err)
+	}
+	return &s, nil
+}
@@ -0,0 +1,63 @@
+package result
+
+import (
+	"encoding/json"
+	"strings"
+	"testing"
+)
+
+func TestMigrationsApply(t *testing.T) {
+	t.Parallel()
+
+	var calls []int
+	m := Migrations{
+		func(doc map[string]any) error { calls = append(calls, 0); doc["a"] = true; return nil },
+		func(doc map[string]any) error { calls = append(calls, 1); doc["b"] = true; return nil },
+	}
+
+	doc := map[string]any{}
+	from, err := m.Apply(doc)
+	if err != nil || from != 0 {
+		t.Fatalf("Apply() = %d, %v; want 0, nil", from, err)
+	}
+	if doc[SchemaVersionKey] != 2 || doc["a"] != true || doc["b"] != true {
+		t.Fatalf("unexpected migrated doc: %v", doc)
+	}
+
+	calls = nil
+	doc = map[string]any{SchemaVersionKey: float64(1)}
+	if _, err := m.Apply(doc); err != nil {
+		t.Fatalf("Apply() error = %v", err)
+	}
+	if len(calls) != 1 || calls[0] != 1 {
+		t.Fatalf("expected only the v1->v2 migration, ran %v", calls)
+	}
+
+	_, err = m.Apply(map[string]any{SchemaVersionKey: float64(3)})
+	if err == nil || !strings.Contains(err.Error(), "newer") {
+		t.Fatalf("expected newer-version error, got %v", err)
+	}
+	if _, err := m.Apply(map[string]any{SchemaVersionKey: "x"}); err == nil {
+		t.Fatal("expected error for non-numeric version")
+	}
+}
+
+func TestParseSessionUpgradesUnversioned(t *testing.T) {
+	t.Parallel()
+
+	legacy := `{"id":"go-x","task_slug":"x","language":"go","status":"pass","attempts":null}`
+	s, err := ParseSession([]byte(legacy))
+	if err != nil {
+		t.Fatalf("ParseSession() error = %v", err)
+	}
+	if s.SchemaVersion != SessionSchemaVersion || s.Attempts == nil || !s.Passed() {
+		t.Fatalf("unexpected session: %+v", s)
+	}
+
+	current := NewSession("x", "go", SessionConfig{})
+	data, _ := json.Marshal(current)
+	roundTrip, err := ParseSession(data)
+	if err != nil || roundTrip.ID != current.ID || roundTrip.SchemaVersion != SessionSchemaVersion {
+		t.Fatalf("round trip = %+v, %v", roundTrip, err)
+	}
+}
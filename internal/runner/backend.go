@@ -0,0 +1,143 @@
+package runner
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/docker/docker/api/types/container"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// Backend is a container engine the harness can run tasks on. Docker and
+// Podman both serve the Docker Engine API, so one client drives either; a
+// backend says where that API is and how task containers must be created on
+// it.
+type Backend interface {
+	// Name is the backend's [docker] container_backend value.
+	Name() string
+	// Host returns the engine's API address, or "" for the Docker client
+	// defaults (DOCKER_HOST, then the standard socket).
+	Host() (string, error)
+	// ConfigureHost adjusts a task container's host config. rootless reports
+	// whether the engine runs without root.
+	ConfigureHost(hc *container.HostConfig, rootless bool)
+}
+
+// NewBackend returns the backend for a [docker] container_backend value; ""
+// is Docker. The local backend has no engine, so commands that work with
+// images or containers directly fail with ErrNoContainerEngine.
+func NewBackend(name string) (Backend, error) {
+	switch name {
+	case "", config.ContainerBackendDocker:
+		return dockerBackend{}, nil
+	case config.ContainerBackendPodman:
+		return newPodmanBackend(), nil
+	case config.ContainerBackendLocal:
+		return nil, ErrNoContainerEngine
+	default:
+		return nil, fmt.Errorf("unknown container backend %q (valid: %s)", name, strings.Join(config.ContainerBackends, ", "))
+	}
+}
+
+// ErrNoContainerEngine is returned for the local backend by commands that need
+// a container engine.
+var ErrNoContainerEngine = errors.New("container_backend \"local\" grades on the host and has no container engine; set [docker] container_backend to docker or podman for this command")
+
+// dockerBackend is the Docker Engine, found the way the docker CLI finds it.
+type dockerBackend struct{}
+
+func (dockerBackend) Name() string { return config.ContainerBackendDocker }
+
+func (dockerBackend) Host() (string, error) { return "", nil }
+
+func (dockerBackend) ConfigureHost(*container.HostConfig, bool) {}
+
+// podmanBackend is Podman's Docker-compatible API service. Its fields are
+// the environment it searches, so tests can supply their own.
+type podmanBackend struct {
+	getenv func(string) string
+	uid    int
+	exists func(path string) bool
+	// remoteSocket asks the podman CLI for its API socket, which covers
+	// podman machine on macOS and Windows.
+	remoteSocket func() (string, error)
+}
+
+func newPodmanBackend() podmanBackend {
+	return podmanBackend{
+		getenv: os.Getenv,
+		uid:    os.Getuid(),
+		exists: func(path string) bool {
+			info, err := os.Stat(path)
+			return err == nil && info.Mode()&os.ModeSocket != 0
+		},
+		remoteSocket: podmanRemoteSocket,
+	}
+}
+
+func (podmanBackend) Name() string { return config.ContainerBackendPodman }
+
+// Host finds Podman's API socket: CONTAINER_HOST or DOCKER_HOST if set, then
+// the rootless socket of the current user, then the rootful one, then
+// wherever the podman CLI says it is.
+func (p podmanBackend) Host() (string, error) {
+	for _, env := range []string{"CONTAINER_HOST", "DOCKER_HOST"} {
+		if host := p.getenv(env); host != "" {
+			return host, nil
+		}
+	}
+	var candidates []string
+	if dir := p.getenv("XDG_RUNTIME_DIR"); dir != "" {
+		candidates = append(candidates, filepath.Join(dir, "podman", "podman.sock"))
+	}
+	if p.uid > 0 {
+		candidates = append(candidates, filepath.Join("/run/user", strconv.Itoa(p.uid), "podman", "podman.sock"))
+	}
+	candidates = append(candidates, "/run/podman/podman.sock")
+	for _, path := range candidates {
+		if p.exists(path) {
+			return "unix://" + path, nil
+		}
+	}
+	if p.remoteSocket != nil {
+		if path, err := p.remoteSocket(); err == nil && path != "" {
+			if !strings.Contains(path, "://") {
+				path = "unix://" + path
+			}
+			return path, nil
+		}
+	}
+	return "", fmt.Errorf("no Podman API socket found (tried %s); start it with 'systemctl --user start podman.socket' or set CONTAINER_HOST",
+		strings.Join(candidates, ", "))
+}
+
+// ConfigureHost keeps the host user's UID inside rootless containers. Rootless
+// Podman otherwise maps the host user to root in the container, so a task
+// running as that UID could not write its bind-mounted workspace.
+func (podmanBackend) ConfigureHost(hc *container.HostConfig, rootless bool) {
+	if rootless {
+		hc.UsernsMode = "keep-id"
+	}
+}
+
+// podmanRemoteSocket returns the API socket the podman CLI reports.
+func podmanRemoteSocket() (string, error) {
+	if _, err := exec.LookPath("podman"); err != nil {
+		return "", errors.New("podman is not on PATH")
+	}
+	ctx, cancel := context.WithTimeout(context.Background(), 10*time.Second)
+	defer cancel()
+	out, err := exec.CommandContext(ctx, "podman", "info", "--format", "{{.Host.RemoteSocket.Path}}").Output()
+	if err != nil {
+		return "", fmt.Errorf("podman info: %w", err)
+	}
+	return strings.TrimSpace(string(out)), nil
+}
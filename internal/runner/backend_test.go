@@ -0,0 +1,132 @@
+package runner
+
+import (
+	"errors"
+	"slices"
+	"strings"
+	"testing"
+
+	"github.com/docker/docker/api/types/container"
+)
+
+func TestNewBackend(t *testing.T) {
+	t.Parallel()
+
+	for name, want := range map[string]string{"": "docker", "docker": "docker", "podman": "podman"} {
+		b, err := NewBackend(name)
+		if err != nil {
+			t.Fatalf("NewBackend(%q) error = %v", name, err)
+		}
+		if b.Name() != want {
+			t.Errorf("NewBackend(%q).Name() = %q, want %q", name, b.Name(), want)
+		}
+	}
+	if _, err := NewBackend("local"); !errors.Is(err, ErrNoContainerEngine) {
+		t.Errorf("NewBackend(local) error = %v, want ErrNoContainerEngine", err)
+	}
+	if _, err := NewBackend("containerd"); err == nil {
+		t.Error("NewBackend(containerd) succeeded, want an error")
+	}
+}
+
+func TestPodmanHost(t *testing.T) {
+	t.Parallel()
+
+	noRemote := func() (string, error) { return "", errors.New("podman is not on PATH") }
+	tests := []struct {
+		name    string
+		env     map[string]string
+		uid     int
+		sockets []string
+		remote  func() (string, error)
+		want    string
+	}{
+		{
+			name: "CONTAINER_HOST wins",
+			env:  map[string]string{"CONTAINER_HOST": "ssh://core@vm/run/podman/podman.sock", "DOCKER_HOST": "unix:///var/run/docker.sock"},
+			want: "ssh://core@vm/run/podman/podman.sock",
+		},
+		{
+			name: "DOCKER_HOST",
+			env:  map[string]string{"DOCKER_HOST": "unix:///tmp/podman.sock"},
+			want: "unix:///tmp/podman.sock",
+		},
+		{
+			name:    "rootless socket under XDG_RUNTIME_DIR",
+			env:     map[string]string{"XDG_RUNTIME_DIR": "/run/user/1000"},
+			uid:     1000,
+			sockets: []string{"/run/user/1000/podman/podman.sock", "/run/podman/podman.sock"},
+			want:    "unix:///run/user/1000/podman/podman.sock",
+		},
+		{
+			name:    "rootless socket without XDG_RUNTIME_DIR",
+			uid:     1000,
+			sockets: []string{"/run/user/1000/podman/podman.sock"},
+			want:    "unix:///run/user/1000/podman/podman.sock",
+		},
+		{
+			name:    "rootful socket",
+			uid:     0,
+			sockets: []string{"/run/podman/podman.sock"},
+			want:    "unix:///run/podman/podman.sock",
+		},
+		{
+			name:   "podman machine",
+			uid:    501,
+			remote: func() (string, error) { return "/Users/me/.local/share/containers/podman/machine/podman.sock", nil },
+			want:   "unix:///Users/me/.local/share/containers/podman/machine/podman.sock",
+		},
+	}
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Parallel()
+
+			remote := tt.remote
+			if remote == nil {
+				remote = noRemote
+			}
+			p := podmanBackend{
+				getenv:       func(k string) string { return tt.env[k] },
+				uid:          tt.uid,
+				exists:       func(path string) bool { return slices.Contains(tt.sockets, path) },
+				remoteSocket: remote,
+			}
+			got, err := p.Host()
+			if err != nil {
+				t.Fatalf("Host() error = %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("Host() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+
+	p := podmanBackend{
+		getenv:       func(string) string { return "" },
+		uid:          1000,
+		exists:       func(string) bool { return false },
+		remoteSocket: noRemote,
+	}
+	if _, err := p.Host(); err == nil || !strings.Contains(err.Error(), "podman.socket") {
+		t.Errorf("Host() without a socket error = %v, want a hint to start podman.socket", err)
+	}
+}
+
+func TestPodmanConfigureHost(t *testing.T) {
+	t.Parallel()
+
+	var hc container.HostConfig
+	podmanBackend{}.ConfigureHost(&hc, false)
+	if hc.UsernsMode != "" {
+		t.Errorf("rootful UsernsMode = %q, want unset", hc.UsernsMode)
+	}
+	podmanBackend{}.ConfigureHost(&hc, true)
+	if hc.UsernsMode != "keep-id" {
+		t.Errorf("rootless UsernsMode = %q, want keep-id", hc.UsernsMode)
+	}
+	var docker container.HostConfig
+	dockerBackend{}.ConfigureHost(&docker, true)
+	if docker.UsernsMode != "" {
+		t.Errorf("docker UsernsMode = %q, want unset", docker.UsernsMode)
+	}
+}
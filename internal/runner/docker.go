@@ -4,18 +4,31 @@ package runner
 import (
 	"bytes"
 	"context"
+	"encoding/json"
+	"errors"
 	"fmt"
 	"io"
+	"os"
+	"path/filepath"
 	"runtime"
+	"slices"
+	"strconv"
+	"strings"
 	"sync"
 	"time"
 
+	"github.com/docker/docker/api/types/build"
 	"github.com/docker/docker/api/types/container"
+	"github.com/docker/docker/api/types/filters"
 	"github.com/docker/docker/api/types/image"
 	"github.com/docker/docker/api/types/mount"
+	"github.com/docker/docker/api/types/network"
 	"github.com/docker/docker/client"
 	"github.com/docker/docker/pkg/stdcopy"
 	ocispec "github.com/opencontainers/image-spec/specs-go/v1"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/result"
 )
 
 // ExecResult holds the result of executing a command in a container.
@@ -25,30 +38,69 @@ type ExecResult struct {
 	Stderr   string
 	Combined string
 	Duration time.Duration
+	// Termination is how a timed-out command was stopped: result.TerminationGraceful
+	// or result.TerminationKilled. It is empty when the command finished.
+	Termination string
 }
 
 // DockerClient wraps the Docker SDK client with harness-specific operations.
+// It drives any Backend that serves the Docker Engine API.
 type DockerClient struct {
-	client *client.Client
+	client   *client.Client
+	backend  Backend
+	rootless bool
+	// Termination sets how commands that exceed their Exec timeout are stopped.
+	// The zero value leaves them to be killed with the container.
+	Termination config.TerminationConfig
 }
 
-// NewDockerClient creates a new Docker client and verifies the daemon is accessible.
-func NewDockerClient() (*DockerClient, error) {
-	cli, err := client.NewClientWithOpts(client.FromEnv, client.WithAPIVersionNegotiation())
+// NewDockerClient creates a client for the named container backend ("" is
+// Docker) and verifies its engine is accessible.
+func NewDockerClient(backendName string) (*DockerClient, error) {
+	backend, err := NewBackend(backendName)
+	if err != nil {
+		return nil, err
+	}
+	host, err := backend.Host()
+	if err != nil {
+		return nil, err
+	}
+	opts := []client.Opt{client.FromEnv, client.WithAPIVersionNegotiation()}
+	if host != "" {
+		opts = append(opts, client.WithHost(host))
+	}
+	cli, err := client.NewClientWithOpts(opts...)
 	if err != nil {
-		return nil, fmt.Errorf("creating docker client: %w", err)
+		return nil, fmt.Errorf("creating %s client: %w", backend.Name(), err)
 	}
 
-	// Verify Docker daemon is accessible immediately to fail fast
+	// Verify the engine is accessible immediately to fail fast
 	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
 	defer cancel()
 
 	if _, err := cli.Ping(ctx); err != nil {
 		_ = cli.Close()
-		return nil, fmt.Errorf("docker daemon not accessible (is Docker running?): %w", err)
+		return nil, fmt.Errorf("%s engine not accessible (is %s running?): %w", backend.Name(), backendLabel(backend), err)
 	}
 
-	return &DockerClient{client: cli}, nil
+	d := &DockerClient{client: cli, backend: backend}
+	if info, err := cli.Info(ctx); err == nil {
+		d.rootless = slices.Contains(info.SecurityOptions, "name=rootless")
+	}
+	return d, nil
+}
+
+// backendLabel returns the engine's product name for messages.
+func backendLabel(b Backend) string {
+	if b.Name() == config.ContainerBackendPodman {
+		return "Podman"
+	}
+	return "Docker"
+}
+
+// Backend returns the container backend the client talks to.
+func (d *DockerClient) Backend() Backend {
+	return d.backend
 }
 
 // Close closes the Docker client.
@@ -65,6 +117,26 @@ func (d *DockerClient) Ping(ctx context.Context) error {
 	return nil
 }
 
+// ServerVersion returns the container engine's name (e.g. "Docker Engine -
+// Community" or "Podman Engine") and version.
+func (d *DockerClient) ServerVersion(ctx context.Context) (name, version string, err error) {
+	v, err := d.client.ServerVersion(ctx)
+	if err != nil {
+		return "", "", fmt.Errorf("querying engine version: %w", err)
+	}
+	return v.Platform.Name, v.Version, nil
+}
+
+// CheckPullable asks the image's registry for its manifest without pulling
+// it, which fails if the registry is unreachable, the image does not exist,
+// or access is denied.
+func (d *DockerClient) CheckPullable(ctx context.Context, imageName string) error {
+	if _, err := d.client.DistributionInspect(ctx, imageName, ""); err != nil {
+		return fmt.Errorf("inspecting %s in its registry: %w", imageName, err)
+	}
+	return nil
+}
+
 // ImageExists checks if an image exists locally.
 func (d *DockerClient) ImageExists(ctx context.Context, imageName string) (bool, error) {
 	images, err := d.client.ImageList(ctx, image.ListOptions{})
@@ -102,19 +174,49 @@ func (d *DockerClient) PullImage(ctx context.Context, imageName string) error {
 	return nil
 }
 
+// PullPolicy controls how EnsureImage obtains a missing or mismatched image.
+type PullPolicy struct {
+	AutoPull bool
+	Retries  int           // Extra attempts per registry after a failed pull
+	Backoff  time.Duration // Delay before the first retry, doubled after each
+	Mirrors  []string      // Registry mirrors tried, in order, after the image's own registry
+	CacheDir string        // Directory of image tarballs loaded when every pull fails
+
+	// OnRetry, when set, is called before each retry of ref (attempt 1 and
+	// up) and before falling back to a mirror or tarball ref (attempt 0).
+	OnRetry func(ref string, attempt int, err error)
+}
+
+// NewPullPolicy returns the pull policy configured in cfg.
+func NewPullPolicy(cfg config.DockerConfig) PullPolicy {
+	return PullPolicy{
+		AutoPull: cfg.AutoPull,
+		Retries:  cfg.PullRetries,
+		Backoff:  2 * time.Second,
+		Mirrors:  cfg.Mirrors,
+		CacheDir: ImageCacheDir,
+	}
+}
+
+// maxPullBackoff caps the delay between pull attempts.
+const maxPullBackoff = 30 * time.Second
+
+// ImageCacheDir holds image tarballs written by `sanity images export`.
+var ImageCacheDir = filepath.Join(".sanity-cache", "images")
+
 // EnsureImage ensures an image is available locally, pulling if necessary.
-func (d *DockerClient) EnsureImage(ctx context.Context, imageName string, autoPull bool) error {
+func (d *DockerClient) EnsureImage(ctx context.Context, imageName string, policy PullPolicy) error {
 	exists, err := d.ImageExists(ctx, imageName)
 	if err != nil {
 		return err
 	}
 
 	if !exists {
-		if !autoPull {
+		if !policy.AutoPull {
 			return fmt.Errorf("image %s not found locally and auto-pull is disabled", imageName)
 		}
 
-		if err := d.PullImage(ctx, imageName); err != nil {
+		if err := d.obtainImage(ctx, imageName, policy); err != nil {
 			return err
 		}
 
@@ -133,7 +235,7 @@ func (d *DockerClient) EnsureImage(ctx context.Context, imageName string, autoPu
 		return nil
 	}
 
-	if !autoPull {
+	if !policy.AutoPull {
 		return fmt.Errorf(
 			"image %s is %s but host platform is %s and auto-pull is disabled",
 			imageName,
@@ -142,7 +244,7 @@ func (d *DockerClient) EnsureImage(ctx context.Context, imageName string, autoPu
 		)
 	}
 
-	if err := d.PullImage(ctx, imageName); err != nil {
+	if err := d.obtainImage(ctx, imageName, policy); err != nil {
 		return err
 	}
 
@@ -153,6 +255,242 @@ func (d *DockerClient) EnsureImage(ctx context.Context, imageName string, autoPu
 	)
 }
 
+// RefreshImage pulls imageName even when it exists locally, so a moving tag
+// like latest picks up the registry's current image, with the same retries,
+// mirrors, and tarball fallback as EnsureImage.
+func (d *DockerClient) RefreshImage(ctx context.Context, imageName string, policy PullPolicy) error {
+	if err := d.obtainImage(ctx, imageName, policy); err != nil {
+		return err
+	}
+	return d.ensureImageMatchesHostPlatform(
+		ctx,
+		imageName,
+		"image %s resolved to %s but host platform is %s; build or publish a %s image, or override this image in config",
+	)
+}
+
+// ImageInfo describes a local image tag.
+type ImageInfo struct {
+	Ref     string
+	ID      string
+	Size    int64
+	Created time.Time
+}
+
+// LocalImages returns the engine's images, keyed by tag.
+func (d *DockerClient) LocalImages(ctx context.Context) (map[string]ImageInfo, error) {
+	images, err := d.client.ImageList(ctx, image.ListOptions{})
+	if err != nil {
+		return nil, fmt.Errorf("listing images: %w", err)
+	}
+	local := make(map[string]ImageInfo)
+	for _, img := range images {
+		for _, tag := range img.RepoTags {
+			local[tag] = ImageInfo{Ref: tag, ID: img.ID, Size: img.Size, Created: time.Unix(img.Created, 0)}
+		}
+	}
+	return local, nil
+}
+
+// RemoveImage untags ref and deletes the layers no other image uses.
+func (d *DockerClient) RemoveImage(ctx context.Context, ref string) error {
+	if _, err := d.client.ImageRemove(ctx, ref, image.RemoveOptions{PruneChildren: true}); err != nil {
+		return fmt.Errorf("removing image %s: %w", ref, err)
+	}
+	return nil
+}
+
+// obtainImage pulls imageName with retries, then from each mirror, and
+// finally loads a cached tarball, so a flaky registry does not abort a run.
+func (d *DockerClient) obtainImage(ctx context.Context, imageName string, policy PullPolicy) error {
+	err := d.pullWithRetry(ctx, imageName, policy)
+	if err == nil {
+		return nil
+	}
+	errs := []error{err}
+
+	for _, mirror := range policy.Mirrors {
+		ref, mirrorErr := MirrorImageRef(imageName, mirror)
+		if mirrorErr != nil {
+			errs = append(errs, mirrorErr)
+			continue
+		}
+		if policy.OnRetry != nil {
+			policy.OnRetry(ref, 0, errs[len(errs)-1])
+		}
+		if err := d.pullWithRetry(ctx, ref, policy); err != nil {
+			errs = append(errs, err)
+			continue
+		}
+		if err := d.client.ImageTag(ctx, ref, imageName); err != nil {
+			return fmt.Errorf("tagging %s as %s: %w", ref, imageName, err)
+		}
+		return nil
+	}
+
+	if policy.CacheDir != "" {
+		tarball := ImageTarballPath(policy.CacheDir, imageName)
+		if _, statErr := os.Stat(tarball); statErr == nil {
+			if policy.OnRetry != nil {
+				policy.OnRetry(tarball, 0, errs[len(errs)-1])
+			}
+			if err := d.LoadImageFile(ctx, tarball); err != nil {
+				errs = append(errs, err)
+			} else {
+				return nil
+			}
+		}
+	}
+	return errors.Join(errs...)
+}
+
+// pullWithRetry pulls ref, retrying with exponential backoff.
+func (d *DockerClient) pullWithRetry(ctx context.Context, ref string, policy PullPolicy) error {
+	delay := policy.Backoff
+	var err error
+	for attempt := 0; ; attempt++ {
+		if err = d.PullImage(ctx, ref); err == nil {
+			return nil
+		}
+		if attempt >= policy.Retries || ctx.Err() != nil {
+			return err
+		}
+		if policy.OnRetry != nil {
+			policy.OnRetry(ref, attempt+1, err)
+		}
+		select {
+		case <-ctx.Done():
+			return err
+		case <-time.After(delay):
+		}
+		delay = min(delay*2, maxPullBackoff)
+	}
+}
+
+// MirrorImageRef rewrites ref to be pulled through mirror, which is a
+// registry host optionally followed by a path prefix. Images without a
+// registry are Docker Hub images.
+func MirrorImageRef(ref, mirror string) (string, error) {
+	mirror = strings.TrimSuffix(strings.TrimPrefix(strings.TrimPrefix(mirror, "https://"), "http://"), "/")
+	if mirror == "" {
+		return "", fmt.Errorf("empty registry mirror for %s", ref)
+	}
+	repo := ref
+	if first, rest, ok := strings.Cut(ref, "/"); ok && (strings.ContainsAny(first, ".:") || first == "localhost") {
+		repo = rest
+	} else if !strings.Contains(ref, "/") {
+		repo = "library/" + ref
+	}
+	return mirror + "/" + repo, nil
+}
+
+// ImageTarballPath is where images export saves ref below dir and where
+// EnsureImage looks for it.
+func ImageTarballPath(dir, ref string) string {
+	name := strings.NewReplacer("/", "_", ":", "_", "@", "_").Replace(ref)
+	return filepath.Join(dir, name+".tar")
+}
+
+// SaveImageFile writes ref to path as a `docker save` tarball.
+func (d *DockerClient) SaveImageFile(ctx context.Context, ref, path string) error {
+	reader, err := d.client.ImageSave(ctx, []string{ref})
+	if err != nil {
+		return fmt.Errorf("saving image %s: %w", ref, err)
+	}
+	defer func() { _ = reader.Close() }()
+
+	if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+		return err
+	}
+	tmp := path + ".tmp"
+	f, err := os.Create(tmp)
+	if err != nil {
+		return fmt.Errorf("creating %s: %w", tmp, err)
+	}
+	if _, err := io.Copy(f, reader); err != nil {
+		_ = f.Close()
+		_ = os.Remove(tmp)
+		return fmt.Errorf("writing %s: %w", path, err)
+	}
+	if err := f.Close(); err != nil {
+		_ = os.Remove(tmp)
+		return err
+	}
+	return os.Rename(tmp, path)
+}
+
+// LoadImageFile loads a `docker save` tarball into the local image store.
+func (d *DockerClient) LoadImageFile(ctx context.Context, path string) error {
+	f, err := os.Open(path)
+	if err != nil {
+		return fmt.Errorf("opening %s: %w", path, err)
+	}
+	defer func() { _ = f.Close() }()
+
+	resp, err := d.client.ImageLoad(ctx, f, client.ImageLoadWithQuiet(true))
+	if err != nil {
+		return fmt.Errorf("loading image from %s: %w", path, err)
+	}
+	defer func() { _ = resp.Body.Close() }()
+	if _, err := io.Copy(io.Discard, resp.Body); err != nil {
+		return fmt.Errorf("reading load response: %w", err)
+	}
+	return nil
+}
+
+// BuildImage builds an image tagged tag for the host platform from a tar
+// build context containing dockerfile. A failed build's error ends with the
+// last lines of its output.
+func (d *DockerClient) BuildImage(ctx context.Context, buildContext io.Reader, dockerfile, tag string) error {
+	resp, err := d.client.ImageBuild(ctx, buildContext, build.ImageBuildOptions{
+		Tags:        []string{tag},
+		Dockerfile:  dockerfile,
+		Remove:      true,
+		ForceRemove: true,
+		Platform:    hostPlatformString(),
+	})
+	if err != nil {
+		return fmt.Errorf("building image %s: %w", tag, err)
+	}
+	defer func() { _ = resp.Body.Close() }()
+
+	// The response is a stream of JSON messages; a failed step sends one
+	// with an error instead of failing the request.
+	var output strings.Builder
+	dec := json.NewDecoder(resp.Body)
+	for {
+		var msg struct {
+			Stream string `json:"stream"`
+			Error  string `json:"error"`
+		}
+		if err := dec.Decode(&msg); err != nil {
+			if errors.Is(err, io.EOF) {
+				return nil
+			}
+			return fmt.Errorf("reading build response: %w", err)
+		}
+		output.WriteString(msg.Stream)
+		if msg.Error != "" {
+			return fmt.Errorf("building image %s: %s\n%s", tag, msg.Error, lastLines(output.String(), 20))
+		}
+	}
+}
+
+// lastLines returns the last n lines of s.
+func lastLines(s string, n int) string {
+	lines := strings.Split(strings.TrimRight(s, "\n"), "\n")
+	return strings.Join(lines[max(0, len(lines)-n):], "\n")
+}
+
+// ImageDigest returns the local image ID and any registry digests for an image.
+func (d *DockerClient) ImageDigest(ctx context.Context, imageName string) (id string, repoDigests []string, err error) {
+	inspect, err := d.client.ImageInspect(ctx, imageName)
+	if err != nil {
+		return "", nil, fmt.Errorf("inspecting image %s: %w", imageName, err)
+	}
+	return inspect.ID, inspect.RepoDigests, nil
+}
+
 // ContainerConfig holds configuration for creating a container.
 type ContainerConfig struct {
 	Image        string
@@ -161,6 +499,9 @@ type ContainerConfig struct {
 	User         string
 	Env          []string
 	Mounts       []mount.Mount
+	NanoCPUs     int64  // CPU limit in billionths of a core (0 = unlimited)
+	Memory       int64  // Memory limit in bytes, swap included (0 = unlimited)
+	Network      string // Network mode, e.g. "none" or a network name ("" = the engine default)
 }
 
 // CreateContainer creates a new container with the specified configuration.
@@ -181,6 +522,21 @@ func (d *DockerClient) CreateContainer(ctx context.Context, cfg ContainerConfig)
 				Target: "/workspace",
 			},
 		}, cfg.Mounts...),
+		Resources: container.Resources{
+			NanoCPUs: cfg.NanoCPUs,
+			Memory:   cfg.Memory,
+		},
+	}
+	if cfg.Memory > 0 {
+		// Equal to Memory, so the container cannot swap past its limit.
+		hostCfg.MemorySwap = cfg.Memory
+	}
+	if cfg.Network != "" {
+		hostCfg.NetworkMode = container.NetworkMode(cfg.Network)
+	}
+
+	if d.backend != nil {
+		d.backend.ConfigureHost(hostCfg, d.rootless)
 	}
 
 	resp, err := d.client.ContainerCreate(ctx, containerCfg, hostCfg, nil, hostPlatform(), cfg.Name)
@@ -191,6 +547,56 @@ func (d *DockerClient) CreateContainer(ctx context.Context, cfg ContainerConfig)
 	return resp.ID, nil
 }
 
+// egressNetwork is the internal network task containers join with
+// [container] network = "allowlist".
+const egressNetwork = "sanity-egress"
+
+// EgressNetwork returns the name and gateway address of the internal network
+// for network = "allowlist", creating it on first use. Internal networks have
+// no route out, but the host still answers on the gateway, where the harness
+// serves its egress proxy.
+func (d *DockerClient) EgressNetwork(ctx context.Context) (name, gateway string, err error) {
+	gateway, found, err := d.findEgressNetwork(ctx)
+	if err != nil || found {
+		return egressNetwork, gateway, err
+	}
+	_, createErr := d.client.NetworkCreate(ctx, egressNetwork, network.CreateOptions{Driver: "bridge", Internal: true})
+	// Another run may have created it meanwhile, so look again either way.
+	gateway, found, err = d.findEgressNetwork(ctx)
+	switch {
+	case err != nil:
+		return "", "", err
+	case !found && createErr != nil:
+		return "", "", fmt.Errorf("creating network %s: %w", egressNetwork, createErr)
+	case !found:
+		return "", "", fmt.Errorf("network %s not found after creating it", egressNetwork)
+	}
+	return egressNetwork, gateway, nil
+}
+
+// findEgressNetwork returns the gateway of the egress network, if it exists.
+func (d *DockerClient) findEgressNetwork(ctx context.Context) (string, bool, error) {
+	nets, err := d.client.NetworkList(ctx, network.ListOptions{Filters: filters.NewArgs(filters.Arg("name", egressNetwork))})
+	if err != nil {
+		return "", false, fmt.Errorf("listing networks: %w", err)
+	}
+	for _, n := range nets {
+		if n.Name != egressNetwork {
+			continue
+		}
+		if !n.Internal {
+			return "", false, fmt.Errorf("network %s exists but is not internal; remove it so the harness can recreate it", egressNetwork)
+		}
+		for _, c := range n.IPAM.Config {
+			if c.Gateway != "" {
+				return c.Gateway, true, nil
+			}
+		}
+		return "", false, fmt.Errorf("network %s has no gateway address", egressNetwork)
+	}
+	return "", false, nil
+}
+
 // StartContainer starts a container.
 func (d *DockerClient) StartContainer(ctx context.Context, containerID string) error {
 	if err := d.client.ContainerStart(ctx, containerID, container.StartOptions{}); err != nil {
@@ -199,6 +605,83 @@ func (d *DockerClient) StartContainer(ctx context.Context, containerID string) e
 	return nil
 }
 
+// peakMemoryCommand prints the container's peak memory usage in bytes from
+// cgroup v2 (memory.peak, Linux 5.19+) or cgroup v1.
+var peakMemoryCommand = []string{"sh", "-c",
+	"cat /sys/fs/cgroup/memory.peak 2>/dev/null || cat /sys/fs/cgroup/memory/memory.max_usage_in_bytes"}
+
+// PeakMemory returns the highest memory usage of the container so far, in
+// bytes. The cgroup high-water mark covers every process the container has
+// run, so it is the peak of the most demanding exec.
+func (d *DockerClient) PeakMemory(ctx context.Context, containerID string) (int64, error) {
+	res, err := d.Exec(ctx, containerID, peakMemoryCommand, "/", 10*time.Second)
+	if err != nil {
+		return 0, err
+	}
+	if res.ExitCode != 0 {
+		return 0, fmt.Errorf("reading cgroup memory peak: exit code %d: %s", res.ExitCode, strings.TrimSpace(res.Stderr))
+	}
+	return parseByteCount(res.Stdout)
+}
+
+// ResetPeakMemory starts a new memory high-water mark for the container. The
+// cgroup file system is read-only inside containers, so the peak cannot be
+// reset in place; the container is restarted instead, which gives it a fresh
+// cgroup. Its files and the workspace are kept, and its only process is sleep.
+func (d *DockerClient) ResetPeakMemory(ctx context.Context, containerID string) error {
+	timeout := 0
+	if err := d.client.ContainerRestart(ctx, containerID, container.StopOptions{Timeout: &timeout}); err != nil {
+		return fmt.Errorf("restarting container: %w", err)
+	}
+	return nil
+}
+
+// cpuUsageCommand prints the container's cumulative CPU usage from cgroup v2
+// (cpu.stat) or cgroup v1 (cpuacct.usage).
+var cpuUsageCommand = []string{"sh", "-c",
+	"cat /sys/fs/cgroup/cpu.stat 2>/dev/null || cat /sys/fs/cgroup/cpuacct/cpuacct.usage"}
+
+// CPUTime returns the CPU time, user plus system, used by every process the
+// container has run so far.
+func (d *DockerClient) CPUTime(ctx context.Context, containerID string) (time.Duration, error) {
+	res, err := d.Exec(ctx, containerID, cpuUsageCommand, "/", 10*time.Second)
+	if err != nil {
+		return 0, err
+	}
+	if res.ExitCode != 0 {
+		return 0, fmt.Errorf("reading cgroup cpu usage: exit code %d: %s", res.ExitCode, strings.TrimSpace(res.Stderr))
+	}
+	return parseCPUUsage(res.Stdout)
+}
+
+// parseCPUUsage parses cgroup v2 cpu.stat (usage_usec) or the cgroup v1
+// cpuacct.usage nanosecond counter.
+func parseCPUUsage(out string) (time.Duration, error) {
+	for _, line := range strings.Split(out, "\n") {
+		if v, ok := strings.CutPrefix(line, "usage_usec "); ok {
+			usec, err := strconv.ParseInt(strings.TrimSpace(v), 10, 64)
+			if err != nil {
+				return 0, fmt.Errorf("unexpected cgroup cpu usage %q", line)
+			}
+			return time.Duration(usec) * time.Microsecond, nil
+		}
+	}
+	ns, err := strconv.ParseInt(strings.TrimSpace(out), 10, 64)
+	if err != nil {
+		return 0, fmt.Errorf("unexpected cgroup cpu usage %q", strings.TrimSpace(out))
+	}
+	return time.Duration(ns), nil
+}
+
+// parseByteCount parses a positive byte count printed by a command.
+func parseByteCount(out string) (int64, error) {
+	n, err := strconv.ParseInt(strings.TrimSpace(out), 10, 64)
+	if err != nil || n <= 0 {
+		return 0, fmt.Errorf("unexpected byte count %q", strings.TrimSpace(out))
+	}
+	return n, nil
+}
+
 // RemoveContainer removes a container.
 func (d *DockerClient) RemoveContainer(ctx context.Context, containerID string, force bool) error {
 	if err := d.client.ContainerRemove(ctx, containerID, container.RemoveOptions{Force: force}); err != nil {
@@ -260,6 +743,7 @@ func (d *DockerClient) Exec(ctx context.Context, containerID string, cmd []strin
 
 	// Wait for either copy to complete or timeout
 	var timedOut bool
+	var termination string
 	select {
 	case res := <-copyDone:
 		// Normal completion
@@ -268,11 +752,9 @@ func (d *DockerClient) Exec(ctx context.Context, containerID string, cmd []strin
 			return nil, fmt.Errorf("reading exec output: %w", res.err)
 		}
 	case <-execCtx.Done():
-		// Timeout - close connection to unblock the goroutine
 		timedOut = true
+		termination = d.stopTimedOutExec(containerID, attachResp.Close, copyDone)
 		attachResp.Close()
-		// Wait for goroutine to finish (it will error due to closed connection)
-		<-copyDone
 	}
 
 	// If timed out, return immediately with what we have
@@ -282,11 +764,12 @@ func (d *DockerClient) Exec(ctx context.Context, containerID string, cmd []strin
 		stderrStr := stderr.String()
 		bufMu.Unlock()
 		return &ExecResult{
-			ExitCode: -1,
-			Stdout:   stdoutStr,
-			Stderr:   stderrStr,
-			Combined: stdoutStr + stderrStr,
-			Duration: time.Since(start),
+			ExitCode:    -1,
+			Stdout:      stdoutStr,
+			Stderr:      stderrStr,
+			Combined:    stdoutStr + stderrStr,
+			Duration:    time.Since(start),
+			Termination: termination,
 		}, fmt.Errorf("exec timed out after %v", timeout)
 	}
 
@@ -335,6 +818,48 @@ func (d *DockerClient) Exec(ctx context.Context, containerID string, cmd []strin
 	}, nil
 }
 
+// stopTimedOutExec ends a timed-out exec once its output has been read as far
+// as it will go. With a graceful termination config every process in the
+// container gets the termination signal and ContainerStopTimeout to exit and
+// flush its output. Otherwise, or once that runs out, the connection is closed
+// and whatever is left is killed when the container is removed. It returns
+// which of the two happened.
+func (d *DockerClient) stopTimedOutExec(containerID string, closeAttach func(), copyDone <-chan copyResult) string {
+	if d.signalContainerProcesses(containerID) {
+		select {
+		case <-copyDone:
+			return result.TerminationGraceful
+		case <-time.After(time.Duration(d.Termination.ContainerStopTimeout) * time.Second):
+		}
+	}
+	// Closing the connection unblocks the copy goroutine.
+	closeAttach()
+	<-copyDone
+	return result.TerminationKilled
+}
+
+// signalContainerProcesses sends the termination signal to every process in
+// the container except its init, which keeps the container up until it is
+// removed. It reports whether the signal was sent.
+func (d *DockerClient) signalContainerProcesses(containerID string) bool {
+	if d.Termination.Signal == "" || !d.Termination.Graceful() || d.Termination.ContainerStopTimeout <= 0 {
+		return false
+	}
+	ctx, cancel := context.WithTimeout(context.Background(), 5*time.Second)
+	defer cancel()
+
+	// kill -1 signals every process the user may signal except init and the
+	// shell itself.
+	signal := strings.TrimPrefix(d.Termination.Signal, "SIG")
+	execResp, err := d.client.ContainerExecCreate(ctx, containerID, container.ExecOptions{
+		Cmd: []string{"sh", "-c", "kill -s " + signal + " -1"},
+	})
+	if err != nil {
+		return false
+	}
+	return d.client.ContainerExecStart(ctx, execResp.ID, container.ExecStartOptions{Detach: true}) == nil
+}
+
 // hostPlatform returns the OCI platform spec matching the host architecture.
 func hostPlatform() *ocispec.Platform {
 	return &ocispec.Platform{
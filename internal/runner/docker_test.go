@@ -3,6 +3,7 @@ package runner
 import (
 	"runtime"
 	"testing"
+	"time"
 )
 
 func TestPlatformString(t *testing.T) {
@@ -61,3 +62,56 @@ func TestHostPlatformString(t *testing.T) {
 		t.Fatalf("hostPlatformString() = %q, want %q", got, want)
 	}
 }
+
+func TestParseByteCount(t *testing.T) {
+	t.Parallel()
+
+	if got, err := parseByteCount("268435456\n"); err != nil || got != 268435456 {
+		t.Fatalf("parseByteCount() = %d, %v", got, err)
+	}
+	for _, bad := range []string{"", "max\n", "0\n"} {
+		if _, err := parseByteCount(bad); err == nil {
+			t.Errorf("parseByteCount(%q) should fail", bad)
+		}
+	}
+}
+
+func TestParseCPUUsage(t *testing.T) {
+	t.Parallel()
+
+	v2 := "usage_usec 2500000\nuser_usec 2000000\nsystem_usec 500000\nnr_periods 0\n"
+	if got, err := parseCPUUsage(v2); err != nil || got != 2500*time.Millisecond {
+		t.Fatalf("parseCPUUsage(v2) = %v, %v", got, err)
+	}
+	if got, err := parseCPUUsage("1500000000\n"); err != nil || got != 1500*time.Millisecond {
+		t.Fatalf("parseCPUUsage(v1) = %v, %v", got, err)
+	}
+	if _, err := parseCPUUsage("nope"); err == nil {
+		t.Fatal("expected error for garbage input")
+	}
+}
+
+func TestMirrorImageRef(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		ref    string
+		mirror string
+		want   string
+	}{
+		{"ghcr.io/lemon07r/sanity-go:latest", "mirror.example.com", "mirror.example.com/lemon07r/sanity-go:latest"},
+		{"ghcr.io/lemon07r/sanity-go:latest", "https://mirror.example.com/ghcr/", "mirror.example.com/ghcr/lemon07r/sanity-go:latest"},
+		{"localhost:5000/sanity-zig:dev", "mirror.example.com", "mirror.example.com/sanity-zig:dev"},
+		{"golang:1.25", "mirror.gcr.io", "mirror.gcr.io/library/golang:1.25"},
+		{"lemon07r/sanity-go", "mirror.gcr.io", "mirror.gcr.io/lemon07r/sanity-go"},
+	}
+	for _, tc := range tests {
+		got, err := MirrorImageRef(tc.ref, tc.mirror)
+		if err != nil || got != tc.want {
+			t.Errorf("MirrorImageRef(%q, %q) = %q, %v; want %q", tc.ref, tc.mirror, got, err, tc.want)
+		}
+	}
+	if _, err := MirrorImageRef("golang:1.25", "https://"); err == nil {
+		t.Error("MirrorImageRef() with an empty mirror should fail")
+	}
+}
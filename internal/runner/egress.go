@@ -0,0 +1,142 @@
+package runner
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"log/slog"
+	"net"
+	"net/http"
+	"net/http/httputil"
+	"sync"
+	"time"
+)
+
+// egressNetworker is an engine that can isolate containers on an internal
+// network for [container] network = "allowlist".
+type egressNetworker interface {
+	EgressNetwork(ctx context.Context) (name, gateway string, err error)
+}
+
+// egressProxy is an HTTP proxy that only connects to allowed hosts. Task
+// containers with no route out send all their traffic through it: CONNECT
+// tunnels for HTTPS and absolute-form requests for plain HTTP.
+type egressProxy struct {
+	allowed func(host string) bool
+	logger  *slog.Logger
+	addr    string
+	server  *http.Server
+	forward *httputil.ReverseProxy
+
+	mu     sync.Mutex
+	denied map[string]bool // Hosts already logged as blocked
+}
+
+// startEgressProxy serves an egress proxy on addr until Close.
+func startEgressProxy(addr string, allowed func(host string) bool, logger *slog.Logger) (*egressProxy, error) {
+	ln, err := net.Listen("tcp", addr)
+	if err != nil {
+		return nil, fmt.Errorf("starting egress proxy: %w", err)
+	}
+	p := &egressProxy{
+		allowed: allowed,
+		logger:  logger,
+		addr:    ln.Addr().String(),
+		forward: &httputil.ReverseProxy{
+			// The request already carries its absolute target URL.
+			Rewrite:   func(*httputil.ProxyRequest) {},
+			Transport: &http.Transport{DialContext: (&net.Dialer{Timeout: 30 * time.Second}).DialContext},
+		},
+		denied: make(map[string]bool),
+	}
+	p.server = &http.Server{Handler: p, ReadHeaderTimeout: 30 * time.Second}
+	go func() { _ = p.server.Serve(ln) }()
+	return p, nil
+}
+
+// Env returns the environment that points a container's package managers at
+// the proxy.
+func (p *egressProxy) Env() []string {
+	host, port, _ := net.SplitHostPort(p.addr)
+	url := "http://" + p.addr
+	return []string{
+		"HTTP_PROXY=" + url,
+		"HTTPS_PROXY=" + url,
+		"http_proxy=" + url,
+		"https_proxy=" + url,
+		"NO_PROXY=localhost,127.0.0.1",
+		"no_proxy=localhost,127.0.0.1",
+		// The JVM, and so Gradle, ignores the variables above.
+		"GRADLE_OPTS=-Dhttp.proxyHost=" + host + " -Dhttp.proxyPort=" + port +
+			" -Dhttps.proxyHost=" + host + " -Dhttps.proxyPort=" + port,
+	}
+}
+
+// Close stops accepting connections. Open tunnels end with their requests.
+func (p *egressProxy) Close() error {
+	return p.server.Close()
+}
+
+func (p *egressProxy) ServeHTTP(w http.ResponseWriter, req *http.Request) {
+	host := req.URL.Hostname()
+	if host == "" {
+		http.Error(w, "sanity egress proxy: not a proxy request", http.StatusBadRequest)
+		return
+	}
+	if !p.allowed(host) {
+		p.deny(host)
+		http.Error(w, fmt.Sprintf("sanity egress proxy: %s is not in [container] allowed_hosts", host), http.StatusForbidden)
+		return
+	}
+	if req.Method != http.MethodConnect {
+		p.forward.ServeHTTP(w, req)
+		return
+	}
+
+	target := req.URL.Host
+	if req.URL.Port() == "" {
+		target = net.JoinHostPort(host, "443")
+	}
+	upstream, err := (&net.Dialer{Timeout: 30 * time.Second}).DialContext(req.Context(), "tcp", target)
+	if err != nil {
+		http.Error(w, err.Error(), http.StatusBadGateway)
+		return
+	}
+	defer func() { _ = upstream.Close() }()
+	hj, ok := w.(http.Hijacker)
+	if !ok {
+		http.Error(w, "sanity egress proxy: cannot tunnel", http.StatusInternalServerError)
+		return
+	}
+	conn, buf, err := hj.Hijack()
+	if err != nil {
+		return
+	}
+	defer func() { _ = conn.Close() }()
+	if _, err := io.WriteString(conn, "HTTP/1.1 200 Connection Established\r\n\r\n"); err != nil {
+		return
+	}
+	// Either side closing ends the tunnel; the deferred closes then unblock
+	// the other copy.
+	done := make(chan struct{}, 2)
+	go func() {
+		_, _ = io.Copy(upstream, buf.Reader)
+		done <- struct{}{}
+	}()
+	go func() {
+		_, _ = io.Copy(conn, upstream)
+		done <- struct{}{}
+	}()
+	<-done
+}
+
+// deny logs the first blocked request to each host.
+func (p *egressProxy) deny(host string) {
+	p.mu.Lock()
+	seen := p.denied[host]
+	p.denied[host] = true
+	p.mu.Unlock()
+	if !seen {
+		p.logger.Warn("container egress blocked; add the host to [container] allowed_hosts if tasks need it", "host", host)
+	}
+}
@@ -0,0 +1,89 @@
+package runner
+
+import (
+	"context"
+	"io"
+	"log/slog"
+	"net/http"
+	"net/http/httptest"
+	"net/url"
+	"slices"
+	"strings"
+	"testing"
+)
+
+func TestEgressProxy(t *testing.T) {
+	t.Parallel()
+
+	upstream := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		_, _ = io.WriteString(w, "ok")
+	}))
+	defer upstream.Close()
+	tlsUpstream := httptest.NewTLSServer(http.HandlerFunc(func(w http.ResponseWriter, _ *http.Request) {
+		_, _ = io.WriteString(w, "tls ok")
+	}))
+	defer tlsUpstream.Close()
+
+	allow := "127.0.0.1"
+	proxy, err := startEgressProxy("127.0.0.1:0", func(host string) bool { return host == allow }, slog.New(slog.DiscardHandler))
+	if err != nil {
+		t.Fatalf("startEgressProxy() error = %v", err)
+	}
+	defer func() { _ = proxy.Close() }()
+	proxyURL, _ := url.Parse("http://" + proxy.addr)
+
+	get := func(c *http.Client, target string) (int, string) {
+		t.Helper()
+		req, err := http.NewRequestWithContext(context.Background(), http.MethodGet, target, nil)
+		if err != nil {
+			t.Fatalf("NewRequest() error = %v", err)
+		}
+		resp, err := c.Do(req)
+		if err != nil {
+			return 0, err.Error()
+		}
+		defer func() { _ = resp.Body.Close() }()
+		body, _ := io.ReadAll(resp.Body)
+		return resp.StatusCode, string(body)
+	}
+	plain := &http.Client{Transport: &http.Transport{Proxy: http.ProxyURL(proxyURL)}}
+	base, ok := tlsUpstream.Client().Transport.(*http.Transport)
+	if !ok {
+		t.Fatal("httptest TLS client has no *http.Transport")
+	}
+	tlsTransport := base.Clone()
+	tlsTransport.Proxy = http.ProxyURL(proxyURL)
+	tunneled := &http.Client{Transport: tlsTransport}
+
+	if code, body := get(plain, upstream.URL); code != http.StatusOK || body != "ok" {
+		t.Errorf("allowed HTTP request = %d %q, want 200 ok", code, body)
+	}
+	if code, body := get(tunneled, tlsUpstream.URL); code != http.StatusOK || body != "tls ok" {
+		t.Errorf("allowed HTTPS request = %d %q, want 200 tls ok", code, body)
+	}
+
+	// Reach the same servers by a name the allowlist does not have.
+	blocked := strings.Replace(upstream.URL, "127.0.0.1", "localhost", 1)
+	if code, body := get(plain, blocked); code != http.StatusForbidden || !strings.Contains(body, "allowed_hosts") {
+		t.Errorf("blocked HTTP request = %d %q, want 403 naming allowed_hosts", code, body)
+	}
+	if code, body := get(tunneled, strings.Replace(tlsUpstream.URL, "127.0.0.1", "localhost", 1)); code != 0 || !strings.Contains(body, "Forbidden") {
+		t.Errorf("blocked HTTPS request = %d %q, want the CONNECT refused", code, body)
+	}
+}
+
+func TestEgressProxyEnv(t *testing.T) {
+	t.Parallel()
+
+	p := &egressProxy{addr: "172.30.0.1:40123"}
+	env := p.Env()
+	for _, want := range []string{
+		"HTTPS_PROXY=http://172.30.0.1:40123",
+		"http_proxy=http://172.30.0.1:40123",
+		"GRADLE_OPTS=-Dhttp.proxyHost=172.30.0.1 -Dhttp.proxyPort=40123 -Dhttps.proxyHost=172.30.0.1 -Dhttps.proxyPort=40123",
+	} {
+		if !slices.Contains(env, want) {
+			t.Errorf("Env() = %q, want it to contain %q", env, want)
+		}
+	}
+}
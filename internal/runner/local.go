@@ -0,0 +1,389 @@
+package runner
+
+import (
+	"bytes"
+	"context"
+	"errors"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"slices"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// containerWorkspace is where every task container mounts its workspace.
+const containerWorkspace = "/workspace"
+
+// localEngine runs task commands on the host with its installed toolchains,
+// for the local backend. A "container" is a record of the workspace, cache
+// mounts, and environment a real one would have had; each command runs in a
+// bubblewrap or firejail sandbox that leaves only those writable, or, when
+// explicitly allowed, directly on the host.
+type localEngine struct {
+	sandbox     string // Resolved [docker] local_sandbox: bwrap, firejail, or none
+	termination config.TerminationConfig
+	lookPath    func(string) (string, error)
+
+	mu    sync.Mutex
+	boxes map[string]*localBox
+}
+
+// localBox is one local "container".
+type localBox struct {
+	workspace string
+	mounts    [][2]string // Host source and container target of each cache mount
+	env       []string
+	tmp       string // Private temp dir standing in for /tmp outside bwrap
+	cpu       time.Duration
+	peak      int64
+}
+
+// newLocalEngine resolves the [docker] local_sandbox setting against the
+// tools on PATH. Running unsandboxed must be allowed explicitly.
+func newLocalEngine(sandbox string, allowUnsandboxed bool) (*localEngine, error) {
+	resolved, err := ResolveLocalSandbox(sandbox, allowUnsandboxed)
+	if err != nil {
+		return nil, err
+	}
+	return &localEngine{sandbox: resolved, lookPath: exec.LookPath, boxes: make(map[string]*localBox)}, nil
+}
+
+// ResolveLocalSandbox returns the sandbox the local backend would use for a
+// [docker] local_sandbox value, or why it cannot run.
+func ResolveLocalSandbox(sandbox string, allowUnsandboxed bool) (string, error) {
+	return resolveLocalSandbox(sandbox, allowUnsandboxed, exec.LookPath)
+}
+
+// resolveLocalSandbox returns the sandbox the local backend uses: the one
+// configured, or for "auto" the first of bwrap and firejail on PATH.
+func resolveLocalSandbox(sandbox string, allowUnsandboxed bool, lookPath func(string) (string, error)) (string, error) {
+	switch sandbox {
+	case "", config.LocalSandboxAuto:
+		for _, tool := range []string{config.LocalSandboxBwrap, config.LocalSandboxFirejail} {
+			if _, err := lookPath(tool); err == nil {
+				return tool, nil
+			}
+		}
+		if !allowUnsandboxed {
+			return "", errors.New("the local backend found neither bwrap nor firejail on PATH; install bubblewrap or firejail, or pass --i-understand-the-risks to run task commands directly on the host")
+		}
+		return config.LocalSandboxNone, nil
+	case config.LocalSandboxBwrap, config.LocalSandboxFirejail:
+		if _, err := lookPath(sandbox); err != nil {
+			return "", fmt.Errorf("[docker] local_sandbox is %s, but it is not on PATH: %w", sandbox, err)
+		}
+		return sandbox, nil
+	case config.LocalSandboxNone:
+		if !allowUnsandboxed {
+			return "", errors.New("[docker] local_sandbox = \"none\" runs task commands directly on the host; pass --i-understand-the-risks to confirm")
+		}
+		return sandbox, nil
+	default:
+		return "", fmt.Errorf("unknown local sandbox %q (valid: %s)", sandbox, strings.Join(config.LocalSandboxes, ", "))
+	}
+}
+
+// Label describes where tasks run, for the image field of results.
+func (l *localEngine) Label() string {
+	return config.ContainerBackendLocal + " (" + l.sandbox + ")"
+}
+
+// EnsureImage does nothing: the local backend uses the host's toolchains.
+func (l *localEngine) EnsureImage(context.Context, string, PullPolicy) error {
+	return nil
+}
+
+// ImageExists reports every image present: the host's toolchains stand in
+// for all of them, including those tasks would build from a Dockerfile.
+func (l *localEngine) ImageExists(context.Context, string) (bool, error) {
+	return true, nil
+}
+
+// BuildImage does nothing; see ImageExists.
+func (l *localEngine) BuildImage(context.Context, io.Reader, string, string) error {
+	return nil
+}
+
+// CreateContainer records cfg under its name.
+func (l *localEngine) CreateContainer(_ context.Context, cfg ContainerConfig) (string, error) {
+	box := &localBox{workspace: cfg.WorkspaceDir, env: cfg.Env}
+	for _, m := range cfg.Mounts {
+		box.mounts = append(box.mounts, [2]string{m.Source, m.Target})
+	}
+	if l.sandbox != config.LocalSandboxBwrap {
+		tmp, err := os.MkdirTemp("", "sanity-local-*")
+		if err != nil {
+			return "", fmt.Errorf("creating temp dir: %w", err)
+		}
+		box.tmp = tmp
+	}
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	if _, exists := l.boxes[cfg.Name]; exists {
+		return "", fmt.Errorf("creating container: %s already exists", cfg.Name)
+	}
+	l.boxes[cfg.Name] = box
+	return cfg.Name, nil
+}
+
+// StartContainer does nothing; commands start on Exec.
+func (l *localEngine) StartContainer(_ context.Context, containerID string) error {
+	_, err := l.box(containerID)
+	return err
+}
+
+// RemoveContainer forgets the container and deletes its temp dir.
+func (l *localEngine) RemoveContainer(_ context.Context, containerID string, _ bool) error {
+	l.mu.Lock()
+	box, ok := l.boxes[containerID]
+	delete(l.boxes, containerID)
+	l.mu.Unlock()
+	if !ok {
+		return fmt.Errorf("removing container: no such container %s", containerID)
+	}
+	if box.tmp != "" {
+		_ = os.RemoveAll(box.tmp)
+	}
+	return nil
+}
+
+// Close does nothing.
+func (l *localEngine) Close() error {
+	return nil
+}
+
+// CPUTime returns the CPU time, user plus system, of every command run in the
+// container so far.
+func (l *localEngine) CPUTime(_ context.Context, containerID string) (time.Duration, error) {
+	box, err := l.box(containerID)
+	if err != nil {
+		return 0, err
+	}
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	return box.cpu, nil
+}
+
+// PeakMemory returns the largest resident set of any command run in the
+// container so far, in bytes.
+func (l *localEngine) PeakMemory(_ context.Context, containerID string) (int64, error) {
+	box, err := l.box(containerID)
+	if err != nil {
+		return 0, err
+	}
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	if box.peak <= 0 {
+		return 0, errors.New("peak memory not available on this platform")
+	}
+	return box.peak, nil
+}
+
+// ResetPeakMemory forgets the commands run in the container so far, so
+// PeakMemory reports only the ones that follow.
+func (l *localEngine) ResetPeakMemory(_ context.Context, containerID string) error {
+	box, err := l.box(containerID)
+	if err != nil {
+		return err
+	}
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	box.peak = 0
+	return nil
+}
+
+func (l *localEngine) box(containerID string) (*localBox, error) {
+	l.mu.Lock()
+	defer l.mu.Unlock()
+	box, ok := l.boxes[containerID]
+	if !ok {
+		return nil, fmt.Errorf("no such container %s", containerID)
+	}
+	return box, nil
+}
+
+// Exec runs cmd in the container's sandbox. A command that outlives timeout
+// gets the [termination] signal, then SIGKILL once container_stop_timeout
+// runs out.
+func (l *localEngine) Exec(ctx context.Context, containerID string, cmd []string, workdir string, timeout time.Duration) (*ExecResult, error) {
+	box, err := l.box(containerID)
+	if err != nil {
+		return nil, err
+	}
+	if len(cmd) == 0 {
+		return nil, errors.New("creating exec: empty command")
+	}
+	if _, err := l.lookPath(cmd[0]); err != nil {
+		return nil, fmt.Errorf("creating exec: %s is not installed on the host (the local backend uses locally installed toolchains): %w", cmd[0], err)
+	}
+	argv := l.command(box, cmd, workdir)
+
+	execCtx, cancel := context.WithTimeout(ctx, timeout)
+	defer cancel()
+	c := exec.CommandContext(execCtx, argv[0], argv[1:]...)
+	c.Env = l.environ(box, os.Environ())
+	if l.sandbox != config.LocalSandboxBwrap {
+		c.Dir = box.translateString(workdir)
+	}
+	var stdout, stderr bytes.Buffer
+	c.Stdout = &stdout
+	c.Stderr = &stderr
+	term := setupLocalProcessGroup(c, l.termination)
+
+	start := time.Now()
+	runErr := c.Run()
+	duration := time.Since(start)
+	path := term.finish()
+
+	if c.ProcessState != nil {
+		l.mu.Lock()
+		box.cpu += c.ProcessState.UserTime() + c.ProcessState.SystemTime()
+		box.peak = max(box.peak, maxRSS(c.ProcessState))
+		l.mu.Unlock()
+	}
+	res := &ExecResult{
+		ExitCode: -1,
+		Stdout:   stdout.String(),
+		Stderr:   stderr.String(),
+		Combined: stdout.String() + stderr.String(),
+		Duration: duration,
+	}
+	if execCtx.Err() != nil {
+		res.Termination = path
+		return res, fmt.Errorf("exec timed out after %v", timeout)
+	}
+	var exitErr *exec.ExitError
+	switch {
+	case runErr == nil:
+		res.ExitCode = 0
+	case errors.As(runErr, &exitErr):
+		res.ExitCode = exitErr.ExitCode()
+	default:
+		return nil, fmt.Errorf("running command: %w", runErr)
+	}
+	return res, nil
+}
+
+// command returns the argv that runs cmd in workdir inside box's sandbox.
+func (l *localEngine) command(box *localBox, cmd []string, workdir string) []string {
+	switch l.sandbox {
+	case config.LocalSandboxBwrap:
+		return slices.Concat([]string{"bwrap"}, bwrapGradingArgs(box, workdir), []string{"--"}, box.translate(cmd))
+	case config.LocalSandboxFirejail:
+		return slices.Concat([]string{"firejail"}, firejailGradingArgs(box), []string{"--"}, box.translate(cmd))
+	default:
+		return box.translate(cmd)
+	}
+}
+
+// bwrapGradingArgs mounts the host read-only with a private /tmp, then the
+// workspace and cache directories writable. Cache directories keep their
+// container paths under /tmp; the workspace keeps its host path, since the
+// read-only root has nowhere to mount /workspace.
+func bwrapGradingArgs(box *localBox, workdir string) []string {
+	args := []string{"--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc", "--tmpfs", "/tmp"}
+	args = append(args, "--bind", box.workspace, box.workspace)
+	for _, m := range box.mounts {
+		args = append(args, "--bind", m[0], m[1])
+	}
+	return append(args,
+		"--unshare-all", "--share-net",
+		"--die-with-parent",
+		"--new-session",
+		"--chdir", box.translateString(workdir),
+	)
+}
+
+// firejailGradingArgs drops privileges and makes $HOME read-only except for
+// the workspace and cache directories. Firejail cannot mount at arbitrary
+// paths, so container paths are translated to host ones instead.
+func firejailGradingArgs(box *localBox) []string {
+	args := []string{"--quiet", "--noprofile", "--caps.drop=all", "--nonewprivs", "--noroot", "--seccomp", "--private-dev"}
+	if home, err := os.UserHomeDir(); err == nil {
+		args = append(args, "--read-only="+home)
+	}
+	args = append(args, "--read-write="+box.workspace, "--read-write="+box.tmp)
+	for _, m := range box.mounts {
+		args = append(args, "--read-write="+m[0])
+	}
+	return args
+}
+
+// environ returns the host environment overlaid with the container's. The
+// host's HOME is kept, since toolchains such as rustup find themselves
+// through it; caches that would land in it go to the container's /tmp.
+func (l *localEngine) environ(box *localBox, host []string) []string {
+	tmp := "/tmp"
+	if l.sandbox != config.LocalSandboxBwrap {
+		tmp = box.tmp
+	}
+	env := slices.Clone(host)
+	set := func(kv string) {
+		key, _, _ := strings.Cut(kv, "=")
+		env = slices.DeleteFunc(env, func(e string) bool { return strings.HasPrefix(e, key+"=") })
+		env = append(env, kv)
+	}
+	for _, kv := range box.env {
+		if strings.HasPrefix(kv, "HOME=") {
+			continue
+		}
+		if l.sandbox == config.LocalSandboxBwrap {
+			set(kv)
+		} else {
+			set(box.translateString(kv))
+		}
+	}
+	set("TMPDIR=" + tmp)
+	set("XDG_CACHE_HOME=" + filepath.Join(tmp, ".cache"))
+	return env
+}
+
+// translate maps container paths in cmd to host ones.
+func (b *localBox) translate(cmd []string) []string {
+	out := make([]string, len(cmd))
+	for i, arg := range cmd {
+		out[i] = b.translateString(arg)
+	}
+	return out
+}
+
+// translateString replaces the workspace path and, outside bwrap, the cache
+// mount targets in s with their host paths.
+func (b *localBox) translateString(s string) string {
+	pairs := [][2]string{{b.workspace, containerWorkspace}}
+	if b.tmp != "" {
+		pairs = append(pairs, b.mounts...)
+	}
+	// Longest targets first, so a target is never cut short by a prefix of it.
+	slices.SortFunc(pairs, func(a, c [2]string) int { return len(c[1]) - len(a[1]) })
+	for _, p := range pairs {
+		s = strings.ReplaceAll(s, p[1], p[0])
+	}
+	return s
+}
+
+// localTermination records how a timed-out local command was stopped.
+type localTermination struct {
+	mu    sync.Mutex
+	path  string
+	timer *time.Timer
+	done  bool
+}
+
+// finish stops any pending kill and returns the termination path, empty if
+// the command was never cancelled.
+func (t *localTermination) finish() string {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	t.done = true
+	if t.timer != nil {
+		t.timer.Stop()
+	}
+	return t.path
+}
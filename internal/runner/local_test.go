@@ -0,0 +1,196 @@
+package runner
+
+import (
+	"context"
+	"errors"
+	"os"
+	"runtime"
+	"slices"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/docker/docker/api/types/mount"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+func TestResolveLocalSandbox(t *testing.T) {
+	t.Parallel()
+
+	lookPath := func(installed ...string) func(string) (string, error) {
+		return func(name string) (string, error) {
+			if slices.Contains(installed, name) {
+				return "/usr/bin/" + name, nil
+			}
+			return "", errors.New("not found")
+		}
+	}
+	tests := []struct {
+		name      string
+		sandbox   string
+		allow     bool
+		installed []string
+		want      string
+		wantErr   string
+	}{
+		{name: "auto prefers bwrap", sandbox: "auto", installed: []string{"firejail", "bwrap"}, want: "bwrap"},
+		{name: "auto falls back to firejail", sandbox: "", installed: []string{"firejail"}, want: "firejail"},
+		{name: "auto without a sandbox", sandbox: "auto", wantErr: "--i-understand-the-risks"},
+		{name: "auto without a sandbox when allowed", sandbox: "auto", allow: true, want: "none"},
+		{name: "explicit firejail", sandbox: "firejail", installed: []string{"bwrap", "firejail"}, want: "firejail"},
+		{name: "explicit bwrap missing", sandbox: "bwrap", installed: []string{"firejail"}, wantErr: "not on PATH"},
+		{name: "none needs consent", sandbox: "none", installed: []string{"bwrap"}, wantErr: "--i-understand-the-risks"},
+		{name: "none with consent", sandbox: "none", allow: true, installed: []string{"bwrap"}, want: "none"},
+		{name: "unknown", sandbox: "chroot", wantErr: "unknown local sandbox"},
+	}
+	for _, tt := range tests {
+		got, err := resolveLocalSandbox(tt.sandbox, tt.allow, lookPath(tt.installed...))
+		if tt.wantErr != "" {
+			if err == nil || !strings.Contains(err.Error(), tt.wantErr) {
+				t.Errorf("%s: error = %v, want it to mention %q", tt.name, err, tt.wantErr)
+			}
+			continue
+		}
+		if err != nil || got != tt.want {
+			t.Errorf("%s: got %q, %v, want %q", tt.name, got, err, tt.want)
+		}
+	}
+}
+
+func TestLocalBoxTranslate(t *testing.T) {
+	t.Parallel()
+
+	box := &localBox{
+		workspace: "/home/u/sessions/ws",
+		mounts: [][2]string{
+			{"/home/u/.sanity-cache/go/gocache", "/tmp/sanity-go-build-cache"},
+			{"/home/u/.sanity-cache/go/gomodcache", "/tmp/sanity-go-mod-cache"},
+		},
+		tmp: "/tmp/sanity-local-1",
+	}
+	got := box.translate([]string{"env", "CRITERION_HOME=/workspace/.criterion", "GOCACHE=/tmp/sanity-go-build-cache"})
+	want := []string{"env", "CRITERION_HOME=/home/u/sessions/ws/.criterion", "GOCACHE=/home/u/.sanity-cache/go/gocache"}
+	if !slices.Equal(got, want) {
+		t.Errorf("translate() = %q, want %q", got, want)
+	}
+
+	// Under bwrap the caches are mounted at their container paths.
+	box.tmp = ""
+	if got := box.translateString("GOCACHE=/tmp/sanity-go-build-cache"); got != "GOCACHE=/tmp/sanity-go-build-cache" {
+		t.Errorf("translateString() under bwrap = %q, want the container path kept", got)
+	}
+}
+
+func TestLocalEngineEnviron(t *testing.T) {
+	t.Parallel()
+
+	box := &localBox{
+		workspace: "/ws",
+		mounts:    [][2]string{{"/cache/cargo-home", "/tmp/sanity-cargo-home"}},
+		env:       []string{"HOME=/tmp", "CARGO_HOME=/tmp/sanity-cargo-home"},
+		tmp:       "/tmp/sanity-local-1",
+	}
+	host := []string{"HOME=/home/u", "PATH=/usr/bin", "CARGO_HOME=/home/u/.cargo", "TMPDIR=/var/tmp"}
+
+	l := &localEngine{sandbox: config.LocalSandboxFirejail}
+	env := l.environ(box, host)
+	for _, want := range []string{"HOME=/home/u", "PATH=/usr/bin", "CARGO_HOME=/cache/cargo-home", "TMPDIR=/tmp/sanity-local-1"} {
+		if !slices.Contains(env, want) {
+			t.Errorf("environ() = %q, want it to contain %q", env, want)
+		}
+	}
+	if slices.Contains(env, "CARGO_HOME=/home/u/.cargo") {
+		t.Errorf("environ() = %q, want the host CARGO_HOME replaced", env)
+	}
+
+	l.sandbox = config.LocalSandboxBwrap
+	box.tmp = ""
+	env = l.environ(box, host)
+	for _, want := range []string{"CARGO_HOME=/tmp/sanity-cargo-home", "TMPDIR=/tmp"} {
+		if !slices.Contains(env, want) {
+			t.Errorf("environ() under bwrap = %q, want it to contain %q", env, want)
+		}
+	}
+}
+
+func TestBwrapGradingArgs(t *testing.T) {
+	t.Parallel()
+
+	box := &localBox{
+		workspace: "/tmp/sanity-warm-go-x",
+		mounts:    [][2]string{{"/cache/gocache", "/tmp/sanity-go-build-cache"}},
+	}
+	args := strings.Join(bwrapGradingArgs(box, "/workspace"), " ")
+	for _, want := range []string{
+		"--ro-bind / /",
+		// The workspace is bound after the private /tmp, so one inside
+		// /tmp stays visible.
+		"--tmpfs /tmp --bind /tmp/sanity-warm-go-x /tmp/sanity-warm-go-x",
+		"--bind /cache/gocache /tmp/sanity-go-build-cache",
+		"--chdir /tmp/sanity-warm-go-x",
+	} {
+		if !strings.Contains(args, want) {
+			t.Errorf("bwrapGradingArgs() = %q, want it to contain %q", args, want)
+		}
+	}
+}
+
+func TestLocalEngineExecUnsandboxed(t *testing.T) {
+	t.Parallel()
+	if runtime.GOOS == "windows" {
+		t.Skip("uses sh")
+	}
+
+	l, err := newLocalEngine(config.LocalSandboxNone, true)
+	if err != nil {
+		t.Fatalf("newLocalEngine() error = %v", err)
+	}
+	ctx := context.Background()
+	ws := t.TempDir()
+	cache := t.TempDir()
+	id, err := l.CreateContainer(ctx, ContainerConfig{
+		WorkspaceDir: ws,
+		Name:         "sanity-go-demo-1",
+		Env:          []string{"HOME=/tmp", "GOCACHE=/tmp/sanity-go-build-cache"},
+		Mounts:       []mount.Mount{{Type: mount.TypeBind, Source: cache, Target: "/tmp/sanity-go-build-cache"}},
+	})
+	if err != nil {
+		t.Fatalf("CreateContainer() error = %v", err)
+	}
+	defer func() { _ = l.RemoveContainer(ctx, id, true) }()
+
+	res, err := l.Exec(ctx, id, []string{"sh", "-c", "pwd; echo $GOCACHE; echo oops >&2; exit 3"}, "/workspace", 10*time.Second)
+	if err != nil {
+		t.Fatalf("Exec() error = %v", err)
+	}
+	if res.ExitCode != 3 {
+		t.Errorf("ExitCode = %d, want 3", res.ExitCode)
+	}
+	if want := ws + "\n" + cache + "\n"; res.Stdout != want {
+		t.Errorf("Stdout = %q, want %q", res.Stdout, want)
+	}
+	if res.Stderr != "oops\n" {
+		t.Errorf("Stderr = %q, want %q", res.Stderr, "oops\n")
+	}
+
+	res, err = l.Exec(ctx, id, []string{"sleep", "5"}, "/workspace", 100*time.Millisecond)
+	if err == nil || !strings.Contains(err.Error(), "timed out") {
+		t.Fatalf("Exec() error = %v, want a timeout", err)
+	}
+	if res.ExitCode != -1 {
+		t.Errorf("timed-out ExitCode = %d, want -1", res.ExitCode)
+	}
+
+	if _, err := l.Exec(ctx, id, []string{"sanity-no-such-toolchain"}, "/workspace", time.Second); err == nil || !strings.Contains(err.Error(), "not installed on the host") {
+		t.Errorf("Exec() of a missing command error = %v, want it to say so", err)
+	}
+
+	tmp := l.boxes[id].tmp
+	if err := l.RemoveContainer(ctx, id, true); err != nil {
+		t.Fatalf("RemoveContainer() error = %v", err)
+	}
+	if _, err := os.Stat(tmp); !os.IsNotExist(err) {
+		t.Errorf("temp dir %s left behind after RemoveContainer", tmp)
+	}
+}
@@ -0,0 +1,73 @@
+//go:build !windows
+
+package runner
+
+import (
+	"os"
+	"os/exec"
+	"runtime"
+	"syscall"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/result"
+)
+
+// localSignals maps [termination] signal names to signals.
+var localSignals = map[string]syscall.Signal{
+	"SIGTERM": syscall.SIGTERM,
+	"SIGINT":  syscall.SIGINT,
+	"SIGHUP":  syscall.SIGHUP,
+	"SIGQUIT": syscall.SIGQUIT,
+	"SIGKILL": syscall.SIGKILL,
+}
+
+// setupLocalProcessGroup runs cmd in its own process group and makes
+// cancelling it stop the whole group: with the [termination] signal first
+// when graceful, then SIGKILL once container_stop_timeout runs out.
+func setupLocalProcessGroup(cmd *exec.Cmd, tc config.TerminationConfig) *localTermination {
+	term := &localTermination{}
+	cmd.SysProcAttr = &syscall.SysProcAttr{Setpgid: true}
+	cmd.Cancel = func() error {
+		if cmd.Process == nil {
+			return nil
+		}
+		term.mu.Lock()
+		defer term.mu.Unlock()
+		// A negative PID signals the entire process group.
+		pgid := -cmd.Process.Pid
+		sig, ok := localSignals[tc.Signal]
+		if !ok || !tc.Graceful() || tc.ContainerStopTimeout <= 0 {
+			term.path = result.TerminationKilled
+			return syscall.Kill(pgid, syscall.SIGKILL)
+		}
+		term.path = result.TerminationGraceful
+		term.timer = time.AfterFunc(time.Duration(tc.ContainerStopTimeout)*time.Second, func() {
+			term.mu.Lock()
+			defer term.mu.Unlock()
+			if !term.done {
+				term.path = result.TerminationKilled
+				_ = syscall.Kill(pgid, syscall.SIGKILL)
+			}
+		})
+		return syscall.Kill(pgid, sig)
+	}
+	// Background processes holding the output pipes must not keep Wait
+	// from returning once the command itself has exited.
+	cmd.WaitDelay = time.Duration(tc.ContainerStopTimeout+5) * time.Second
+	return term
+}
+
+// maxRSS returns the peak resident set of a finished command in bytes, or 0
+// if it is unknown.
+func maxRSS(ps *os.ProcessState) int64 {
+	usage, ok := ps.SysUsage().(*syscall.Rusage)
+	if !ok {
+		return 0
+	}
+	// ru_maxrss is in bytes on macOS and kilobytes elsewhere.
+	if runtime.GOOS == "darwin" {
+		return usage.Maxrss
+	}
+	return usage.Maxrss << 10
+}
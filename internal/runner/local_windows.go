@@ -0,0 +1,21 @@
+//go:build windows
+
+package runner
+
+import (
+	"os"
+	"os/exec"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+)
+
+// setupLocalProcessGroup is a no-op on Windows. Cancelling the command still
+// kills the direct child process, so there is no grace period to record.
+func setupLocalProcessGroup(_ *exec.Cmd, _ config.TerminationConfig) *localTermination {
+	return &localTermination{}
+}
+
+// maxRSS is not measured on Windows.
+func maxRSS(_ *os.ProcessState) int64 {
+	return 0
+}
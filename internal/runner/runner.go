@@ -6,10 +6,13 @@ import (
 	"embed"
 	"errors"
 	"fmt"
+	"io"
 	"log/slog"
+	"net"
 	"os"
 	"path/filepath"
 	"strings"
+	"sync"
 	"time"
 
 	"github.com/docker/docker/api/types/mount"
@@ -20,26 +23,66 @@ import (
 	"github.com/lemon07r/sanityharness/internal/task"
 )
 
+// engine is what the runner needs from the place task commands run: a
+// container engine, or the host itself for the local backend.
+type engine interface {
+	imageBuilder
+	EnsureImage(ctx context.Context, imageName string, policy PullPolicy) error
+	CreateContainer(ctx context.Context, cfg ContainerConfig) (string, error)
+	StartContainer(ctx context.Context, containerID string) error
+	Exec(ctx context.Context, containerID string, cmd []string, workdir string, timeout time.Duration) (*ExecResult, error)
+	CPUTime(ctx context.Context, containerID string) (time.Duration, error)
+	PeakMemory(ctx context.Context, containerID string) (int64, error)
+	ResetPeakMemory(ctx context.Context, containerID string) error
+	RemoveContainer(ctx context.Context, containerID string, force bool) error
+	Close() error
+}
+
 // Runner orchestrates task execution.
 type Runner struct {
 	cfg               *config.Config
 	taskLoader        *task.Loader
-	docker            *DockerClient
+	docker            engine
 	logger            *slog.Logger
+	buildMu           sync.Mutex   // Serializes task image builds
+	egressMu          sync.Mutex   // Guards egress
+	egress            *egressProxy // Started by the first container with network = "allowlist"
+	egressNetwork     string
 	LegacyHiddenTests bool // When true, include hidden tests in workspace init (pre-v1.6.0 behavior)
 }
 
 // NewRunner creates a new runner.
 func NewRunner(cfg *config.Config, tasksFS embed.FS, tasksDir string, logger *slog.Logger) (*Runner, error) {
-	docker, err := NewDockerClient()
-	if err != nil {
-		return nil, fmt.Errorf("creating docker client: %w", err)
+	var eng engine
+	if cfg.Docker.ContainerBackend == config.ContainerBackendLocal {
+		local, err := newLocalEngine(cfg.Docker.LocalSandbox, cfg.Docker.AllowUnsandboxed)
+		if err != nil {
+			return nil, err
+		}
+		local.termination = cfg.Termination
+		if local.sandbox == config.LocalSandboxNone {
+			logger.Warn("local backend running task commands without a sandbox")
+		}
+		if cfg.Container.CPUs > 0 || cfg.Container.Memory != "" {
+			logger.Warn("local backend ignores [container] cpus and memory limits")
+		}
+		if n := cfg.Container.Network; n != "" && n != config.ContainerNetworkOpen {
+			return nil, fmt.Errorf("[container] network %q needs a container engine; the local backend only supports \"open\"", cfg.Container.Network)
+		}
+		eng = local
+	} else {
+		docker, err := NewDockerClient(cfg.Docker.ContainerBackend)
+		if err != nil {
+			return nil, fmt.Errorf("creating container client: %w", err)
+		}
+		docker.Termination = cfg.Termination
+		eng = docker
 	}
 
 	return &Runner{
 		cfg:        cfg,
 		taskLoader: task.NewLoader(tasksFS, tasksDir),
-		docker:     docker,
+		docker:     eng,
 		logger:     logger,
 	}, nil
 }
@@ -62,9 +105,28 @@ func (r *Runner) ResolveTaskRef(ref string) (*task.Task, error) {
 
 // Close cleans up runner resources.
 func (r *Runner) Close() error {
+	r.egressMu.Lock()
+	if r.egress != nil {
+		_ = r.egress.Close()
+		r.egress = nil
+	}
+	r.egressMu.Unlock()
 	return r.docker.Close()
 }
 
+// pullPolicy is the configured pull policy, logging retries and fallbacks.
+func (r *Runner) pullPolicy() PullPolicy {
+	policy := NewPullPolicy(r.cfg.Docker)
+	policy.OnRetry = func(ref string, attempt int, err error) {
+		if attempt == 0 {
+			r.logger.Warn("image pull failed, trying fallback", "source", ref, "error", err)
+			return
+		}
+		r.logger.Warn("image pull failed, retrying", "image", ref, "attempt", attempt, "error", err)
+	}
+	return policy
+}
+
 func (r *Runner) cacheMountsForLanguage(lang task.Language) ([]mount.Mount, error) {
 	// Cache directory lives alongside the workspace/session directories.
 	// It is safe to delete at any time; it only improves performance.
@@ -140,6 +202,19 @@ type RunOptions struct {
 	// ValidationCommand overrides the task's default validation command when set.
 	// The first element is the command, followed by args.
 	ValidationCommand []string
+
+	// Output receives the attempt results as they are printed. Nil means
+	// stdout; concurrent runs pass a buffer each so their output does not
+	// interleave.
+	Output io.Writer
+}
+
+// output returns where attempt results are printed.
+func (o RunOptions) output() io.Writer {
+	if o.Output != nil {
+		return o.Output
+	}
+	return os.Stdout
 }
 
 // Run executes a task and returns the session result.
@@ -156,14 +231,21 @@ func (r *Runner) Run(ctx context.Context, opts RunOptions) (*result.Session, err
 		}
 	}
 
-	// Set defaults
+	// Set defaults, preferring the task's [task."<id>"] override
+	override := r.cfg.TaskOverride(t.ID())
 	if opts.MaxAttempts == 0 {
 		opts.MaxAttempts = r.cfg.Harness.MaxAttempts
+		if override.MaxAttempts > 0 {
+			opts.MaxAttempts = override.MaxAttempts
+		}
 	}
 	if opts.Timeout == 0 {
-		if t.Timeout > 0 {
+		switch {
+		case override.Timeout > 0:
+			opts.Timeout = override.Timeout
+		case t.Timeout > 0:
 			opts.Timeout = t.Timeout
-		} else {
+		default:
 			opts.Timeout = r.cfg.Harness.DefaultTimeout
 		}
 	}
@@ -171,16 +253,16 @@ func (r *Runner) Run(ctx context.Context, opts RunOptions) (*result.Session, err
 		opts.OutputDir = r.cfg.Harness.SessionDir
 	}
 
-	// Get image for language
-	imageName := r.cfg.ImageForLanguage(string(t.Language))
-	if imageName == "" {
-		return nil, fmt.Errorf("no image configured for language: %s", t.Language)
+	// Get the task's image and ensure it is available
+	imageName, err := r.ensureTaskImage(ctx, t)
+	if err != nil {
+		return nil, err
 	}
 
-	// Ensure image is available
-	r.logger.Info("ensuring container image", "image", imageName)
-	if err := r.docker.EnsureImage(ctx, imageName, r.cfg.Docker.AutoPull); err != nil {
-		return nil, fmt.Errorf("ensuring image: %w", err)
+	// Results graded on the host record where they ran instead of an image.
+	sessionImage := imageName
+	if local, ok := r.docker.(*localEngine); ok {
+		sessionImage = local.Label()
 	}
 
 	// Create session first so we can put workspace inside session directory
@@ -188,7 +270,7 @@ func (r *Runner) Run(ctx context.Context, opts RunOptions) (*result.Session, err
 		Timeout:     opts.Timeout,
 		MaxAttempts: opts.MaxAttempts,
 		WatchMode:   opts.WatchMode,
-		Image:       imageName,
+		Image:       sessionImage,
 	})
 
 	// Determine workspace directory - now inside the session folder
@@ -211,13 +293,82 @@ func (r *Runner) Run(ctx context.Context, opts RunOptions) (*result.Session, err
 	}
 
 	// Create container
+	containerID, err := r.createTaskContainer(ctx, t, imageName, workspaceDir)
+	if err != nil {
+		return nil, err
+	}
+	defer func() {
+		r.logger.Debug("cleaning up container", "id", containerID[:12])
+		_ = r.docker.RemoveContainer(context.Background(), containerID, true)
+	}()
+
+	// Start container
+	if err := r.docker.StartContainer(ctx, containerID); err != nil {
+		return nil, fmt.Errorf("starting container: %w", err)
+	}
+
+	// Create error summarizer
+	summarizer := errsummary.NewSummarizer(string(t.Language))
+
+	// Touch stub files to invalidate build cache (prevents false positives from stale cached binaries).
+	// This is necessary because Cargo uses mtime-based fingerprinting - if an agent doesn't modify
+	// the stub file, Cargo may reuse a cached binary from a previous successful run.
+	if err := r.touchStubFiles(workspaceDir, t); err != nil {
+		r.logger.Warn("failed to touch stub files", "error", err)
+	}
+
+	// Run validation
+	if opts.WatchMode {
+		err = r.runWatchMode(ctx, t, containerID, session, summarizer, workspaceDir, opts)
+	} else {
+		err = r.runSingle(ctx, t, containerID, session, summarizer, opts)
+	}
+
+	// Complete session
+	session.Complete()
+
+	// Capture final code
+	if err := r.captureWorkspace(workspaceDir, t, session); err != nil {
+		r.logger.Warn("failed to capture workspace", "error", err)
+	}
+
+	// Save session
+	if saveErr := session.Save(opts.OutputDir); saveErr != nil {
+		r.logger.Error("failed to save session", "error", saveErr)
+	}
+
+	return session, err
+}
+
+// ensureTaskImage returns the image t is graded in, building it from t's
+// Dockerfile or pulling it as needed.
+func (r *Runner) ensureTaskImage(ctx context.Context, t *task.Task) (string, error) {
+	r.buildMu.Lock()
+	imageName, err := BuildTaskImage(ctx, r.docker, r.cfg, r.taskLoader, t)
+	r.buildMu.Unlock()
+	if err != nil {
+		return "", fmt.Errorf("preparing image: %w", err)
+	}
+	if imageName == "" {
+		return "", fmt.Errorf("no image configured for language: %s", t.Language)
+	}
+	r.logger.Info("ensuring container image", "image", imageName)
+	if err := r.docker.EnsureImage(ctx, imageName, r.pullPolicy()); err != nil {
+		return "", fmt.Errorf("ensuring image: %w", err)
+	}
+	return imageName, nil
+}
+
+// createTaskContainer creates a container for t with workspaceDir mounted at
+// /workspace and the language's build caches mounted and configured.
+func (r *Runner) createTaskContainer(ctx context.Context, t *task.Task, imageName, workspaceDir string) (string, error) {
 	r.logger.Info("creating container", "workspace", workspaceDir)
 	containerUser := fmt.Sprintf("%d:%d", os.Getuid(), os.Getgid())
 	containerEnv := []string{"HOME=/tmp"}
 
 	cacheMounts, err := r.cacheMountsForLanguage(t.Language)
 	if err != nil {
-		return nil, err
+		return "", err
 	}
 	switch t.Language {
 	case task.Rust:
@@ -243,6 +394,19 @@ func (r *Runner) Run(ctx context.Context, opts RunOptions) (*result.Session, err
 			"PUB_CACHE=/tmp/sanity-pub-cache",
 		)
 	}
+	var networkMode string
+	switch r.cfg.Container.Network {
+	case config.ContainerNetworkNone:
+		networkMode = "none"
+	case config.ContainerNetworkAllowlist:
+		var proxy *egressProxy
+		proxy, networkMode, err = r.ensureEgress(ctx)
+		if err != nil {
+			return "", err
+		}
+		containerEnv = append(containerEnv, proxy.Env()...)
+	}
+	nanoCPUs, memory := r.cfg.ContainerLimits(t.ID())
 	containerID, err := r.docker.CreateContainer(ctx, ContainerConfig{
 		Image:        imageName,
 		WorkspaceDir: workspaceDir,
@@ -250,51 +414,74 @@ func (r *Runner) Run(ctx context.Context, opts RunOptions) (*result.Session, err
 		User:         containerUser,
 		Env:          containerEnv,
 		Mounts:       cacheMounts,
+		NanoCPUs:     nanoCPUs,
+		Memory:       memory,
+		Network:      networkMode,
 	})
 	if err != nil {
-		return nil, fmt.Errorf("creating container: %w", err)
+		return "", fmt.Errorf("creating container: %w", err)
 	}
-	defer func() {
-		r.logger.Debug("cleaning up container", "id", containerID[:12])
-		_ = r.docker.RemoveContainer(context.Background(), containerID, true)
-	}()
+	return containerID, nil
+}
 
-	// Start container
-	if err := r.docker.StartContainer(ctx, containerID); err != nil {
-		return nil, fmt.Errorf("starting container: %w", err)
+// ensureEgress returns the proxy and the internal network for containers with
+// network = "allowlist", creating both on first use.
+func (r *Runner) ensureEgress(ctx context.Context) (*egressProxy, string, error) {
+	r.egressMu.Lock()
+	defer r.egressMu.Unlock()
+	if r.egress != nil {
+		return r.egress, r.egressNetwork, nil
 	}
-
-	// Create error summarizer
-	summarizer := errsummary.NewSummarizer(string(t.Language))
-
-	// Touch stub files to invalidate build cache (prevents false positives from stale cached binaries).
-	// This is necessary because Cargo uses mtime-based fingerprinting - if an agent doesn't modify
-	// the stub file, Cargo may reuse a cached binary from a previous successful run.
-	if err := r.touchStubFiles(workspaceDir, t); err != nil {
-		r.logger.Warn("failed to touch stub files", "error", err)
+	eng, ok := r.docker.(egressNetworker)
+	if !ok {
+		return nil, "", errors.New("[container] network \"allowlist\" needs a container engine")
 	}
-
-	// Run validation
-	if opts.WatchMode {
-		err = r.runWatchMode(ctx, t, containerID, session, summarizer, workspaceDir, opts)
-	} else {
-		err = r.runSingle(ctx, t, containerID, session, summarizer, opts)
+	name, gateway, err := eng.EgressNetwork(ctx)
+	if err != nil {
+		return nil, "", fmt.Errorf("preparing egress network: %w", err)
 	}
+	proxy, err := startEgressProxy(net.JoinHostPort(gateway, "0"), r.cfg.Container.HostAllowed, r.logger)
+	if err != nil {
+		return nil, "", fmt.Errorf("%w (network \"allowlist\" serves its proxy on the %s gateway, which must be a host address, as with Docker Engine on Linux)", err, name)
+	}
+	r.logger.Info("container egress limited to allowed hosts", "proxy", proxy.addr, "hosts", len(r.cfg.Container.AllowedHosts))
+	r.egress, r.egressNetwork = proxy, name
+	return proxy, name, nil
+}
 
-	// Complete session
-	session.Complete()
-
-	// Capture final code
-	if err := r.captureWorkspace(workspaceDir, t, session); err != nil {
-		r.logger.Warn("failed to capture workspace", "error", err)
+// WarmTask pre-compiles t's dependencies and tests into the language's build
+// cache (see Task.WarmCommand), so later validations of t only compile the
+// solution. Every task file is present, hidden tests included, so the whole
+// dependency graph gets built. It returns nil, nil for tasks that cannot be
+// warmed. A non-zero exit code is not an error; stubs may not compile.
+func (r *Runner) WarmTask(ctx context.Context, t *task.Task, timeout time.Duration) (*ExecResult, error) {
+	cmd := t.WarmCommand()
+	if cmd == nil {
+		return nil, nil
+	}
+	imageName, err := r.ensureTaskImage(ctx, t)
+	if err != nil {
+		return nil, err
 	}
 
-	// Save session
-	if saveErr := session.Save(opts.OutputDir); saveErr != nil {
-		r.logger.Error("failed to save session", "error", saveErr)
+	workspaceDir, err := os.MkdirTemp("", fmt.Sprintf("sanity-warm-%s-%s-*", t.Language, t.Slug))
+	if err != nil {
+		return nil, fmt.Errorf("creating temp workspace: %w", err)
+	}
+	defer func() { _ = os.RemoveAll(workspaceDir) }()
+	if err := r.copyTaskFiles(t, workspaceDir, t.AllFiles()); err != nil {
+		return nil, err
 	}
 
-	return session, err
+	containerID, err := r.createTaskContainer(ctx, t, imageName, workspaceDir)
+	if err != nil {
+		return nil, err
+	}
+	defer func() { _ = r.docker.RemoveContainer(context.Background(), containerID, true) }()
+	if err := r.docker.StartContainer(ctx, containerID); err != nil {
+		return nil, fmt.Errorf("starting container: %w", err)
+	}
+	return r.docker.Exec(ctx, containerID, cmd, "/workspace", timeout)
 }
 
 // runSingle runs a single validation attempt.
@@ -304,6 +491,7 @@ func (r *Runner) runSingle(ctx context.Context, t *task.Task, containerID string
 		cmd = opts.ValidationCommand
 	}
 
+	cpuBefore := r.cpuTime(ctx, containerID)
 	execResult, err := r.docker.Exec(ctx, containerID, cmd, "/workspace", time.Duration(opts.Timeout)*time.Second)
 	if err != nil {
 		recordExecErrorAttempt(session, summarizer, execResult)
@@ -313,9 +501,11 @@ func (r *Runner) runSingle(ctx context.Context, t *task.Task, containerID string
 
 	errorSummary := summarizer.Summarize(execResult.Combined)
 	session.AddAttempt(execResult.ExitCode, execResult.Duration, execResult.Combined, errorSummary)
+	r.recordResourceUsage(ctx, t, containerID, session, cpuBefore)
+	r.recordBinarySize(ctx, t, containerID, session, opts.Timeout)
 
 	// Print result
-	fmt.Print(result.FormatTerminal(session, session.LastAttempt(), false))
+	_, _ = fmt.Fprint(opts.output(), result.FormatTerminal(session, session.LastAttempt(), false))
 
 	return nil
 }
@@ -383,6 +573,15 @@ func (r *Runner) runAttempt(ctx context.Context, t *task.Task, containerID strin
 		cmd = opts.ValidationCommand
 	}
 
+	// The memory high-water mark only rises, so without a reset every
+	// attempt would be charged with the peak of the hungriest one before it.
+	if len(session.Attempts) > 0 {
+		if err := r.docker.ResetPeakMemory(ctx, containerID); err != nil {
+			return fmt.Errorf("resetting memory peak: %w", err)
+		}
+	}
+
+	cpuBefore := r.cpuTime(ctx, containerID)
 	execResult, err := r.docker.Exec(ctx, containerID, cmd, "/workspace", time.Duration(opts.Timeout)*time.Second)
 	if err != nil {
 		recordExecErrorAttempt(session, summarizer, execResult)
@@ -392,13 +591,68 @@ func (r *Runner) runAttempt(ctx context.Context, t *task.Task, containerID strin
 
 	errorSummary := summarizer.Summarize(execResult.Combined)
 	session.AddAttempt(execResult.ExitCode, execResult.Duration, execResult.Combined, errorSummary)
+	r.recordResourceUsage(ctx, t, containerID, session, cpuBefore)
+	r.recordBinarySize(ctx, t, containerID, session, opts.Timeout)
 
 	// Print result
-	fmt.Print(result.FormatTerminal(session, session.LastAttempt(), true))
+	_, _ = fmt.Fprint(opts.output(), result.FormatTerminal(session, session.LastAttempt(), true))
 
 	return nil
 }
 
+// cpuTime reads the container's cumulative CPU counter, or returns -1 if it
+// cannot be read.
+func (r *Runner) cpuTime(ctx context.Context, containerID string) time.Duration {
+	cpu, err := r.docker.CPUTime(ctx, containerID)
+	if err != nil {
+		r.logger.Debug("failed to read cpu time", "error", err)
+		return -1
+	}
+	return cpu
+}
+
+// recordResourceUsage stores the CPU time and peak memory usage of the latest
+// attempt and enforces the task's memory bound. The CPU counter is cumulative,
+// so the attempt gets the time used since cpuBefore, read just before it ran;
+// a negative cpuBefore leaves it unmeasured. The peak covers everything the
+// attempt ran, compiling the tests included; runAttempt resets it between
+// watch-mode attempts.
+func (r *Runner) recordResourceUsage(ctx context.Context, t *task.Task, containerID string, session *result.Session, cpuBefore time.Duration) {
+	if cpuBefore >= 0 {
+		if cpu := r.cpuTime(ctx, containerID); cpu >= cpuBefore {
+			if attempt := session.LastAttempt(); attempt != nil {
+				attempt.CPUSeconds = (cpu - cpuBefore).Seconds()
+			}
+		}
+	}
+	peak, err := r.docker.PeakMemory(ctx, containerID)
+	if err != nil {
+		r.logger.Debug("failed to read peak memory", "error", err)
+		return
+	}
+	session.RecordPeakMemory(peak, int64(t.Validation.MaxMemoryMB)<<20)
+}
+
+// recordBinarySize stores the size of the compiled test binaries of a passing
+// attempt and enforces the task's size bound. Failed builds are not measured.
+func (r *Runner) recordBinarySize(ctx context.Context, t *task.Task, containerID string, session *result.Session, timeout int) {
+	cmd := t.BinarySizeCommand()
+	if cmd == nil || !session.Passed() {
+		return
+	}
+	res, err := r.docker.Exec(ctx, containerID, cmd, "/workspace", time.Duration(timeout)*time.Second)
+	if err != nil {
+		r.logger.Debug("failed to measure binary size", "error", err)
+		return
+	}
+	size, err := parseByteCount(res.Stdout)
+	if err != nil {
+		r.logger.Debug("failed to measure binary size", "error", err)
+		return
+	}
+	session.RecordBinarySize(size, int64(t.Validation.MaxBinaryKB)<<10)
+}
+
 func setSessionStatusFromExecError(session *result.Session, runErr error) {
 	if session == nil {
 		return
@@ -416,6 +670,7 @@ func recordExecErrorAttempt(session *result.Session, summarizer *errsummary.Summ
 	}
 	errorSummary := summarizer.Summarize(execResult.Combined)
 	session.AddAttempt(execResult.ExitCode, execResult.Duration, execResult.Combined, errorSummary)
+	session.LastAttempt().Termination = execResult.Termination
 }
 
 // ensureWorkspace creates the workspace directory and copies task files.
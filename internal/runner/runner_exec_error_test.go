@@ -31,9 +31,10 @@ func TestRecordExecErrorAttempt(t *testing.T) {
 	session := result.NewSession("task", "go", result.SessionConfig{})
 	summarizer := errsummary.NewSummarizer("go")
 	execResult := &ExecResult{
-		ExitCode: -1,
-		Combined: "panic: timed out",
-		Duration: 2 * time.Second,
+		ExitCode:    -1,
+		Combined:    "panic: timed out",
+		Duration:    2 * time.Second,
+		Termination: result.TerminationGraceful,
 	}
 
 	recordExecErrorAttempt(session, summarizer, execResult)
@@ -51,4 +52,7 @@ func TestRecordExecErrorAttempt(t *testing.T) {
 	if got.Duration != execResult.Duration {
 		t.Fatalf("duration = %s, want %s", got.Duration, execResult.Duration)
 	}
+	if got.Termination != result.TerminationGraceful {
+		t.Fatalf("termination = %q, want %q", got.Termination, result.TerminationGraceful)
+	}
 }
@@ -0,0 +1,105 @@
+package runner
+
+import (
+	"context"
+	"io"
+	"log/slog"
+	"testing"
+	"time"
+
+	errsummary "github.com/lemon07r/sanityharness/internal/errors"
+	"github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// usageEngine runs scripted validation attempts and keeps cumulative,
+// cgroup-like usage counters. Methods the tests do not reach are left to the
+// nil embedded engine.
+type usageEngine struct {
+	engine
+	peaks  []int64         // Memory each validation exec uses, in order
+	cpus   []time.Duration // CPU time each validation exec uses, in order
+	peak   int64
+	cpu    time.Duration
+	resets int
+}
+
+func (e *usageEngine) Exec(_ context.Context, _ string, cmd []string, _ string, _ time.Duration) (*ExecResult, error) {
+	if cmd[0] == "validate" {
+		e.peak = max(e.peak, e.peaks[0])
+		e.peaks = e.peaks[1:]
+		if len(e.cpus) > 0 {
+			e.cpu += e.cpus[0]
+			e.cpus = e.cpus[1:]
+		}
+	}
+	return &ExecResult{Stdout: "1024", Combined: "ok"}, nil
+}
+
+func (e *usageEngine) CPUTime(context.Context, string) (time.Duration, error) {
+	return e.cpu, nil
+}
+
+func (e *usageEngine) PeakMemory(context.Context, string) (int64, error) {
+	return e.peak, nil
+}
+
+func (e *usageEngine) ResetPeakMemory(context.Context, string) error {
+	e.peak = 0
+	e.resets++
+	return nil
+}
+
+func TestRunAttemptMeasuresEachAttemptsPeak(t *testing.T) {
+	t.Parallel()
+
+	eng := &usageEngine{peaks: []int64{300 << 20, 100 << 20}}
+	r := &Runner{docker: eng, logger: slog.New(slog.DiscardHandler)}
+	tk := &task.Task{Slug: "bank-account", Language: task.Go, Validation: task.Validation{MaxMemoryMB: 256}}
+	session := result.NewSession(tk.Slug, string(tk.Language), result.SessionConfig{MaxAttempts: 2})
+	opts := RunOptions{Timeout: 10, ValidationCommand: []string{"validate"}, Output: io.Discard}
+	summarizer := errsummary.NewSummarizer("go")
+
+	if err := r.runAttempt(context.Background(), tk, "c1", session, summarizer, opts); err != nil {
+		t.Fatalf("first runAttempt() error = %v", err)
+	}
+	if session.Passed() {
+		t.Fatal("first attempt peaked at 300 MiB, want it over the 256 MiB limit")
+	}
+	if err := r.runAttempt(context.Background(), tk, "c1", session, summarizer, opts); err != nil {
+		t.Fatalf("second runAttempt() error = %v", err)
+	}
+	second := session.LastAttempt()
+	if !session.Passed() || !second.Passed || second.PeakMemoryBytes != 100<<20 {
+		t.Fatalf("second attempt = passed %v, peak %d, want a pass at its own 100 MiB peak", second.Passed, second.PeakMemoryBytes)
+	}
+	if eng.resets != 1 {
+		t.Errorf("peak resets = %d, want 1, before the second attempt only", eng.resets)
+	}
+}
+
+func TestRunAttemptRecordsCPUTimePerAttempt(t *testing.T) {
+	t.Parallel()
+
+	eng := &usageEngine{
+		peaks: []int64{100 << 20, 100 << 20},
+		cpus:  []time.Duration{2 * time.Second, 3 * time.Second},
+		cpu:   time.Second, // Container setup before the first attempt
+	}
+	r := &Runner{docker: eng, logger: slog.New(slog.DiscardHandler)}
+	tk := &task.Task{Slug: "bank-account", Language: task.Go}
+	session := result.NewSession(tk.Slug, string(tk.Language), result.SessionConfig{MaxAttempts: 2})
+	opts := RunOptions{Timeout: 10, ValidationCommand: []string{"validate"}, Output: io.Discard}
+	summarizer := errsummary.NewSummarizer("go")
+
+	for range 2 {
+		if err := r.runAttempt(context.Background(), tk, "c1", session, summarizer, opts); err != nil {
+			t.Fatalf("runAttempt() error = %v", err)
+		}
+	}
+	for i, want := range []float64{2, 3} {
+		if got := session.Attempts[i].CPUSeconds; got != want {
+			t.Errorf("attempt %d cpu_seconds = %v, want %v", i+1, got, want)
+		}
+	}
+}
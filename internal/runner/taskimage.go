@@ -0,0 +1,171 @@
+package runner
+
+import (
+	"archive/tar"
+	"bytes"
+	"context"
+	"crypto/sha256"
+	"fmt"
+	"io"
+	"io/fs"
+	"os"
+	"path"
+	"strings"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// taskImageRepo is the repository images built from a task's Dockerfile are
+// tagged in, as taskImageRepo/<language>-<slug>:<context hash>.
+const taskImageRepo = "sanity-task"
+
+// imageBuilder builds task images on demand.
+type imageBuilder interface {
+	ImageExists(ctx context.Context, imageName string) (bool, error)
+	BuildImage(ctx context.Context, buildContext io.Reader, dockerfile, tag string) error
+}
+
+// TaskImage returns the image t is graded in: its [task] override image, then
+// the image its manifest names, then the one built from its manifest's
+// Dockerfile, then the [docker] image for its language. Nothing is built; see
+// BuildTaskImage.
+func TaskImage(cfg *config.Config, loader *task.Loader, t *task.Task) (string, error) {
+	ref, _, err := resolveTaskImage(cfg, loader, t)
+	return ref, err
+}
+
+// BuildTaskImage returns the image t is graded in, as TaskImage does, first
+// building it from t's Dockerfile with b if it does not exist yet.
+func BuildTaskImage(ctx context.Context, b imageBuilder, cfg *config.Config, loader *task.Loader, t *task.Task) (string, error) {
+	ref, buildContext, err := resolveTaskImage(cfg, loader, t)
+	if err != nil || buildContext == nil {
+		return ref, err
+	}
+	exists, err := b.ImageExists(ctx, ref)
+	if err != nil {
+		return "", err
+	}
+	if !exists {
+		if err := b.BuildImage(ctx, bytes.NewReader(buildContext), path.Base(t.Dockerfile), ref); err != nil {
+			return "", fmt.Errorf("task %s: %w", t.ID(), err)
+		}
+	}
+	return ref, nil
+}
+
+// BuiltTaskImage reports whether ref is an image built from a task's
+// Dockerfile.
+func BuiltTaskImage(ref string) bool {
+	return strings.HasPrefix(ref, taskImageRepo+"/")
+}
+
+// BuildDockerfileImage builds tag from the Dockerfile at path, such as
+// containers/Dockerfile-go. The build context holds only the Dockerfile, so
+// it must not COPY or ADD local files.
+func BuildDockerfileImage(ctx context.Context, b imageBuilder, path, tag string) error {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return err
+	}
+	var buf bytes.Buffer
+	tw := tar.NewWriter(&buf)
+	hdr := &tar.Header{Name: "Dockerfile", Typeflag: tar.TypeReg, Mode: 0o644, Size: int64(len(data)), ModTime: time.Unix(0, 0)}
+	if err := tw.WriteHeader(hdr); err != nil {
+		return err
+	}
+	if _, err := tw.Write(data); err != nil {
+		return err
+	}
+	if err := tw.Close(); err != nil {
+		return err
+	}
+	if err := b.BuildImage(ctx, &buf, "Dockerfile", tag); err != nil {
+		return fmt.Errorf("%s: %w", path, err)
+	}
+	return nil
+}
+
+// resolveTaskImage returns the image t is graded in and, when that image is
+// built from t's Dockerfile, its build context.
+func resolveTaskImage(cfg *config.Config, loader *task.Loader, t *task.Task) (string, []byte, error) {
+	if image := cfg.TaskOverride(t.ID()).Image; image != "" {
+		return image, nil, nil
+	}
+	if t.Image != "" {
+		return t.Image, nil, nil
+	}
+	if t.Dockerfile == "" {
+		return cfg.ImageForLanguage(string(t.Language)), nil, nil
+	}
+	buildContext, err := taskBuildContext(loader, t)
+	if err != nil {
+		return "", nil, err
+	}
+	// The tag changes with the context, so editing the Dockerfile or the
+	// files next to it builds a new image instead of reusing a stale one.
+	sum := sha256.Sum256(buildContext)
+	return fmt.Sprintf("%s/%s-%s:%x", taskImageRepo, t.Language, t.Slug, sum[:6]), buildContext, nil
+}
+
+// taskBuildContext tars the directory holding t's Dockerfile. Entries are
+// written in lexical order with a fixed modification time, so the archive,
+// and the tag hashed from it, only change when the files do.
+func taskBuildContext(loader *task.Loader, t *task.Task) ([]byte, error) {
+	taskFS, err := loader.TaskFS(t)
+	if err != nil {
+		return nil, fmt.Errorf("task %s: %w", t.ID(), err)
+	}
+	root := path.Dir(t.Dockerfile)
+	if _, err := fs.Stat(taskFS, t.Dockerfile); err != nil {
+		return nil, fmt.Errorf("task %s dockerfile: %w", t.ID(), err)
+	}
+
+	var buf bytes.Buffer
+	tw := tar.NewWriter(&buf)
+	err = fs.WalkDir(taskFS, root, func(p string, d fs.DirEntry, err error) error {
+		if err != nil || p == root {
+			return err
+		}
+		info, err := d.Info()
+		if err != nil {
+			return err
+		}
+		name := p
+		if root != "." {
+			name = p[len(root)+1:]
+		}
+		hdr := &tar.Header{Name: name, ModTime: time.Unix(0, 0), Mode: 0o644}
+		if info.Mode()&0o111 != 0 {
+			hdr.Mode = 0o755
+		}
+		switch {
+		case d.IsDir():
+			hdr.Typeflag = tar.TypeDir
+			hdr.Name += "/"
+			hdr.Mode = 0o755
+			return tw.WriteHeader(hdr)
+		case !info.Mode().IsRegular():
+			return nil
+		}
+		data, err := fs.ReadFile(taskFS, p)
+		if err != nil {
+			return err
+		}
+		hdr.Typeflag = tar.TypeReg
+		hdr.Size = int64(len(data))
+		if err := tw.WriteHeader(hdr); err != nil {
+			return err
+		}
+		_, err = tw.Write(data)
+		return err
+	})
+	if err != nil {
+		return nil, fmt.Errorf("task %s build context: %w", t.ID(), err)
+	}
+	if err := tw.Close(); err != nil {
+		return nil, fmt.Errorf("task %s build context: %w", t.ID(), err)
+	}
+	return buf.Bytes(), nil
+}
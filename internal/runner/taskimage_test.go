@@ -0,0 +1,189 @@
+package runner
+
+import (
+	"archive/tar"
+	"bytes"
+	"context"
+	"embed"
+	"io"
+	"os"
+	"path/filepath"
+	"slices"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// fakeBuilder records builds instead of running them.
+type fakeBuilder struct {
+	images map[string]bool
+	builds []string
+	files  []string
+}
+
+func (f *fakeBuilder) ImageExists(_ context.Context, ref string) (bool, error) {
+	return f.images[ref], nil
+}
+
+func (f *fakeBuilder) BuildImage(_ context.Context, buildContext io.Reader, dockerfile, tag string) error {
+	f.builds = append(f.builds, dockerfile+" "+tag)
+	tr := tar.NewReader(buildContext)
+	for {
+		hdr, err := tr.Next()
+		if err != nil {
+			break
+		}
+		f.files = append(f.files, hdr.Name)
+	}
+	f.images[tag] = true
+	return nil
+}
+
+func writeTaskPack(t *testing.T, files map[string]string) string {
+	t.Helper()
+	dir := t.TempDir()
+	for name, content := range files {
+		path := filepath.Join(dir, "rust", "nightly-simd", filepath.FromSlash(name))
+		if err := os.MkdirAll(filepath.Dir(path), 0o755); err != nil {
+			t.Fatal(err)
+		}
+		if err := os.WriteFile(path, []byte(content), 0o644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	return dir
+}
+
+func TestTaskImage(t *testing.T) {
+	t.Parallel()
+
+	dir := writeTaskPack(t, map[string]string{"env/Dockerfile": "FROM rustlang/rust:nightly\n"})
+	loader := task.NewLoader(embed.FS{}, dir)
+	cfg := config.Default
+
+	plain := &task.Task{Slug: "nightly-simd", Language: task.Rust}
+	if got, _ := TaskImage(&cfg, loader, plain); got != cfg.Docker.RustImage {
+		t.Errorf("TaskImage() without a manifest image = %q, want the language image", got)
+	}
+
+	named := &task.Task{Slug: "nightly-simd", Language: task.Rust, Image: "rustlang/rust:nightly"}
+	if got, _ := TaskImage(&cfg, loader, named); got != "rustlang/rust:nightly" {
+		t.Errorf("TaskImage() with a manifest image = %q, want it", got)
+	}
+
+	built := &task.Task{Slug: "nightly-simd", Language: task.Rust, Dockerfile: "env/Dockerfile"}
+	ref, err := TaskImage(&cfg, loader, built)
+	if err != nil {
+		t.Fatalf("TaskImage() error = %v", err)
+	}
+	if !strings.HasPrefix(ref, "sanity-task/rust-nightly-simd:") {
+		t.Errorf("TaskImage() with a Dockerfile = %q, want a sanity-task/rust-nightly-simd tag", ref)
+	}
+	if again, _ := TaskImage(&cfg, loader, built); again != ref {
+		t.Errorf("TaskImage() = %q then %q, want a stable tag", ref, again)
+	}
+
+	// Editing the build context changes the tag.
+	if err := os.WriteFile(filepath.Join(dir, "rust", "nightly-simd", "env", "Dockerfile"), []byte("FROM rustlang/rust:nightly-slim\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	if edited, _ := TaskImage(&cfg, loader, built); edited == ref {
+		t.Errorf("TaskImage() after editing the Dockerfile = %q, want a new tag", edited)
+	}
+
+	// A [task] override wins over the manifest.
+	cfg.Tasks = map[string]config.TaskOverride{"rust/nightly-simd": {Image: "registry.example/rust:pinned"}}
+	if got, _ := TaskImage(&cfg, loader, built); got != "registry.example/rust:pinned" {
+		t.Errorf("TaskImage() with an override = %q, want the override", got)
+	}
+}
+
+func TestBuildTaskImage(t *testing.T) {
+	t.Parallel()
+
+	dir := writeTaskPack(t, map[string]string{
+		"env/Dockerfile":       "FROM nvidia/cuda:12.4.1-devel-ubuntu22.04\nCOPY setup.sh /\n",
+		"env/setup.sh":         "#!/bin/sh\n",
+		"tests/hidden.rs.txt":  "// hidden",
+		"src/lib.rs":           "// stub",
+		"env/patches/ptx.diff": "",
+	})
+	loader := task.NewLoader(embed.FS{}, dir)
+	cfg := config.Default
+	tk := &task.Task{Slug: "nightly-simd", Language: task.Rust, Dockerfile: "env/Dockerfile"}
+
+	b := &fakeBuilder{images: map[string]bool{}}
+	ref, err := BuildTaskImage(context.Background(), b, &cfg, loader, tk)
+	if err != nil {
+		t.Fatalf("BuildTaskImage() error = %v", err)
+	}
+	if want := []string{"Dockerfile " + ref}; !slices.Equal(b.builds, want) {
+		t.Errorf("builds = %q, want %q", b.builds, want)
+	}
+	// Only the Dockerfile's directory is sent, so hidden tests stay out of
+	// the image.
+	if want := []string{"Dockerfile", "patches/", "patches/ptx.diff", "setup.sh"}; !slices.Equal(b.files, want) {
+		t.Errorf("build context = %q, want %q", b.files, want)
+	}
+
+	if _, err := BuildTaskImage(context.Background(), b, &cfg, loader, tk); err != nil {
+		t.Fatalf("BuildTaskImage() error = %v", err)
+	}
+	if len(b.builds) != 1 {
+		t.Errorf("BuildTaskImage() rebuilt an existing image: %q", b.builds)
+	}
+
+	missing := &task.Task{Slug: "nightly-simd", Language: task.Rust, Dockerfile: "Dockerfile.gpu"}
+	if _, err := BuildTaskImage(context.Background(), b, &cfg, loader, missing); err == nil {
+		t.Error("BuildTaskImage() with a missing Dockerfile succeeded, want an error")
+	}
+}
+
+func TestTaskBuildContextIsDeterministic(t *testing.T) {
+	t.Parallel()
+
+	dir := writeTaskPack(t, map[string]string{"Dockerfile": "FROM alpine\n", "a.txt": "a"})
+	loader := task.NewLoader(embed.FS{}, dir)
+	tk := &task.Task{Slug: "nightly-simd", Language: task.Rust, Dockerfile: "Dockerfile"}
+	first, err := taskBuildContext(loader, tk)
+	if err != nil {
+		t.Fatalf("taskBuildContext() error = %v", err)
+	}
+	touched := filepath.Join(dir, "rust", "nightly-simd", "a.txt")
+	if err := os.Chtimes(touched, time.Now(), time.Now().Add(time.Hour)); err != nil {
+		t.Fatal(err)
+	}
+	second, err := taskBuildContext(loader, tk)
+	if err != nil {
+		t.Fatalf("taskBuildContext() error = %v", err)
+	}
+	if !bytes.Equal(first, second) {
+		t.Error("taskBuildContext() changed with a file's modification time")
+	}
+}
+
+func TestBuildDockerfileImage(t *testing.T) {
+	t.Parallel()
+
+	path := filepath.Join(t.TempDir(), "Dockerfile-go")
+	if err := os.WriteFile(path, []byte("FROM golang:1.25\n"), 0o644); err != nil {
+		t.Fatal(err)
+	}
+	b := &fakeBuilder{images: map[string]bool{}}
+	if err := BuildDockerfileImage(context.Background(), b, path, "ghcr.io/lemon07r/sanity-go:latest"); err != nil {
+		t.Fatalf("BuildDockerfileImage() error = %v", err)
+	}
+	if want := []string{"Dockerfile ghcr.io/lemon07r/sanity-go:latest"}; !slices.Equal(b.builds, want) {
+		t.Errorf("builds = %q, want %q", b.builds, want)
+	}
+	if !slices.Equal(b.files, []string{"Dockerfile"}) {
+		t.Errorf("build context = %q, want only the Dockerfile", b.files)
+	}
+
+	if !BuiltTaskImage("sanity-task/rust-nightly-simd:0a1b2c3d4e5f") || BuiltTaskImage("ghcr.io/lemon07r/sanity-go:latest") {
+		t.Error("BuiltTaskImage() misclassified a ref")
+	}
+}
@@ -41,12 +41,23 @@ func validateEmbeddedTaskMetadata(t *testing.T, tt *Task) {
 	if tt.Tier != "core" && tt.Tier != "extended" {
 		t.Fatalf("invalid tier %q", tt.Tier)
 	}
+	// eval --tag selects tasks by these.
+	if len(tt.Tags) == 0 {
+		t.Fatalf("missing tags")
+	}
 	if len(tt.Files.Stub) == 0 {
 		t.Fatalf("missing stub files")
 	}
 	if len(tt.Files.Test) == 0 {
 		t.Fatalf("missing test files")
 	}
+	// Every built-in task ships the same prompt languages, so --prompt-lang
+	// runs cover the whole suite.
+	for _, lang := range []string{"ja", "de", "pt"} {
+		if tt.Translations[lang].Description == "" {
+			t.Fatalf("missing %s translation", lang)
+		}
+	}
 }
 
 func validateEmbeddedTaskFiles(t *testing.T, loader *Loader, tt *Task) {
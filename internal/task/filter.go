@@ -0,0 +1,89 @@
+package task
+
+import (
+	"fmt"
+	"path"
+	"slices"
+	"strings"
+)
+
+// Filter selects tasks by ID glob and tag. An empty filter selects every
+// task.
+type Filter struct {
+	Include []string // Globs such as "rust/*" or "go/errgroup-*"; a task must match one
+	Exclude []string // Globs of tasks to drop, applied after Include and Tags
+	Tags    []string // A task must have at least one of these tags
+}
+
+// ParseFilter builds a Filter from comma-separated include globs, exclude
+// globs, and tags, rejecting malformed globs.
+func ParseFilter(include, exclude, tags string) (Filter, error) {
+	f := Filter{
+		Include: splitList(include),
+		Exclude: splitList(exclude),
+		Tags:    splitList(tags),
+	}
+	for _, pattern := range slices.Concat(f.Include, f.Exclude) {
+		if _, err := path.Match(pattern, ""); err != nil {
+			return Filter{}, fmt.Errorf("invalid task glob %q: %w", pattern, err)
+		}
+	}
+	return f, nil
+}
+
+func splitList(s string) []string {
+	var out []string
+	for _, tok := range strings.Split(s, ",") {
+		if tok = strings.TrimSpace(tok); tok != "" {
+			out = append(out, tok)
+		}
+	}
+	return out
+}
+
+// IsZero reports whether the filter selects every task.
+func (f Filter) IsZero() bool {
+	return len(f.Include) == 0 && len(f.Exclude) == 0 && len(f.Tags) == 0
+}
+
+// Matches reports whether the filter selects t.
+func (f Filter) Matches(t *Task) bool {
+	if len(f.Include) > 0 && !slices.ContainsFunc(f.Include, t.MatchesGlob) {
+		return false
+	}
+	if len(f.Tags) > 0 && !slices.ContainsFunc(f.Tags, t.HasTag) {
+		return false
+	}
+	return !slices.ContainsFunc(f.Exclude, t.MatchesGlob)
+}
+
+// Apply returns the tasks the filter selects, in order.
+func (f Filter) Apply(tasks []*Task) []*Task {
+	if f.IsZero() {
+		return tasks
+	}
+	var selected []*Task
+	for _, t := range tasks {
+		if f.Matches(t) {
+			selected = append(selected, t)
+		}
+	}
+	return selected
+}
+
+// MatchesGlob reports whether the task's "<language>/<slug>" ID matches
+// pattern, using path.Match syntax. A pattern without a slash is matched
+// against the slug alone.
+func (t *Task) MatchesGlob(pattern string) bool {
+	name := t.ID()
+	if !strings.Contains(pattern, "/") {
+		name = t.Slug
+	}
+	ok, _ := path.Match(pattern, name)
+	return ok
+}
+
+// HasTag reports whether the task is tagged with tag, ignoring case.
+func (t *Task) HasTag(tag string) bool {
+	return slices.ContainsFunc(t.Tags, func(have string) bool { return strings.EqualFold(have, tag) })
+}
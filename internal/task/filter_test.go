@@ -0,0 +1,52 @@
+package task
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestFilter(t *testing.T) {
+	t.Parallel()
+
+	tasks := []*Task{
+		{Language: Go, Slug: "bank-account", Tags: []string{"concurrency", "synchronization"}},
+		{Language: Go, Slug: "errgroup-limit", Tags: []string{"Concurrency"}},
+		{Language: Rust, Slug: "macros", Tags: []string{"macros"}},
+		{Language: Rust, Slug: "regex-lite", Tags: []string{"parsing"}},
+	}
+	ids := func(selected []*Task) []string {
+		out := make([]string, 0, len(selected))
+		for _, sel := range selected {
+			out = append(out, sel.ID())
+		}
+		return out
+	}
+
+	tests := []struct {
+		name                   string
+		include, exclude, tags string
+		want                   []string
+	}{
+		{name: "empty", want: []string{"go/bank-account", "go/errgroup-limit", "rust/macros", "rust/regex-lite"}},
+		{name: "language glob", include: "rust/*", want: []string{"rust/macros", "rust/regex-lite"}},
+		{name: "slug glob", include: "go/errgroup-*", want: []string{"go/errgroup-limit"}},
+		{name: "glob without language", include: "*-lite", want: []string{"rust/regex-lite"}},
+		{name: "several globs", include: "go/bank-*, rust/regex-*", want: []string{"go/bank-account", "rust/regex-lite"}},
+		{name: "tag ignores case", tags: "concurrency", want: []string{"go/bank-account", "go/errgroup-limit"}},
+		{name: "exclude", include: "rust/*", exclude: "rust/macros", want: []string{"rust/regex-lite"}},
+		{name: "tag and exclude", tags: "concurrency", exclude: "*/bank-account", want: []string{"go/errgroup-limit"}},
+	}
+	for _, tc := range tests {
+		f, err := ParseFilter(tc.include, tc.exclude, tc.tags)
+		if err != nil {
+			t.Fatalf("%s: ParseFilter() error = %v", tc.name, err)
+		}
+		if got := ids(f.Apply(tasks)); !reflect.DeepEqual(got, tc.want) {
+			t.Errorf("%s: Apply() = %v, want %v", tc.name, got, tc.want)
+		}
+	}
+
+	if _, err := ParseFilter("rust/[", "", ""); err == nil {
+		t.Error("ParseFilter() should reject a malformed glob")
+	}
+}
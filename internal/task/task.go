@@ -9,6 +9,8 @@ import (
 	"os"
 	"path"
 	"path/filepath"
+	"regexp"
+	"slices"
 	"sort"
 	"strings"
 
@@ -46,8 +48,67 @@ type Task struct {
 	Description  string     `json:"description"             toml:"description"`
 	Timeout      int        `json:"timeout,omitempty"       toml:"timeout,omitempty"`
 	AgentTimeout int        `json:"agent_timeout,omitempty" toml:"agent_timeout,omitempty"`
+	Image        string     `json:"image,omitempty"         toml:"image,omitempty"`
+	Dockerfile   string     `json:"dockerfile,omitempty"    toml:"dockerfile,omitempty"`
+	Tags         []string   `json:"tags,omitempty"          toml:"tags,omitempty"`
 	Files        TaskFiles  `json:"files"                   toml:"files"`
 	Validation   Validation `json:"validation"              toml:"validation"`
+	Benchmark    *Benchmark `json:"benchmark,omitempty"     toml:"benchmark,omitempty"`
+	FollowUps    []FollowUp `json:"follow_ups,omitempty"    toml:"follow_up,omitempty"`
+
+	// Translations holds the task's name and description in other languages,
+	// keyed by lowercase language code (e.g. "ja", "pt-br").
+	Translations map[string]Translation `json:"translations,omitempty" toml:"translations,omitempty"`
+}
+
+// OwnImage reports whether the task's manifest names its own image or a
+// Dockerfile to build one, instead of using the image for its language.
+func (t *Task) OwnImage() bool {
+	return t.Image != "" || t.Dockerfile != ""
+}
+
+// FollowUp is a scripted request given to the agent during eval once its
+// solution to the task, and to every earlier follow-up, passes. HiddenTest
+// lists the tests for the new behavior; they are run together with the
+// hidden tests of the task and of earlier follow-ups.
+type FollowUp struct {
+	Prompt     string   `json:"prompt"      toml:"prompt"`
+	HiddenTest []string `json:"hidden_test" toml:"hidden_test"`
+}
+
+// Translation is a task's name and description in another language.
+type Translation struct {
+	Name        string `json:"name,omitempty" toml:"name,omitempty"`
+	Description string `json:"description"    toml:"description"`
+}
+
+// translationKeyPattern matches the lowercase language codes used as
+// translation keys.
+var translationKeyPattern = regexp.MustCompile(`^[a-z]{2,3}(-[a-z0-9]{2,8})*$`)
+
+// IsSourcePromptLang reports whether lang selects the untranslated English
+// task text.
+func IsSourcePromptLang(lang string) bool {
+	return lang == "" || strings.EqualFold(lang, "en")
+}
+
+// Localized returns a copy of the task with its name and description in
+// lang, and whether a translation exists. The source language always has
+// one. A translation without a name keeps the English name.
+func (t *Task) Localized(lang string) (*Task, bool) {
+	if IsSourcePromptLang(lang) {
+		return t, true
+	}
+	tr, ok := t.Translations[strings.ToLower(lang)]
+	if !ok {
+		return t, false
+	}
+	out := *t
+	out.Description = tr.Description
+	if tr.Name != "" {
+		out.Name = tr.Name
+	}
+	return &out, true
 }
 
 // ID returns the canonical task identifier in the form "<language>/<slug>".
@@ -64,9 +125,50 @@ type TaskFiles struct {
 }
 
 // Validation specifies how to validate a task solution.
+// MaxMemoryMB, when set, fails a solution whose validation, compiling the
+// tests included, peaks above that many MiB of memory; MaxBinaryKB fails one
+// whose compiled test binaries exceed that many KiB. TimingTests names tests
+// whose outcome depends on machine load, such as speedup assertions; see
+// TimingFailure.
 type Validation struct {
-	Command string   `json:"command" toml:"command"`
-	Args    []string `json:"args"    toml:"args"`
+	Command     string   `json:"command"                 toml:"command"`
+	Args        []string `json:"args"                    toml:"args"`
+	MaxMemoryMB int      `json:"max_memory_mb,omitempty" toml:"max_memory_mb,omitempty"`
+	MaxBinaryKB int      `json:"max_binary_kb,omitempty" toml:"max_binary_kb,omitempty"`
+	TimingTests []string `json:"timing_tests,omitempty"  toml:"timing_tests,omitempty"`
+}
+
+// TimingFailure reports whether validation output shows one of the task's
+// timing tests failing: a line that names the test and a failure, like Go's
+// "--- FAIL: TestX" or Rust's "test x ... FAILED".
+func (t *Task) TimingFailure(output string) bool {
+	for _, name := range t.Validation.TimingTests {
+		re := regexp.MustCompile(`\b` + regexp.QuoteMeta(name) + `\b`)
+		for line := range strings.Lines(output) {
+			if strings.Contains(strings.ToLower(line), "fail") && re.MatchString(line) {
+				return true
+			}
+		}
+	}
+	return false
+}
+
+// binarySizeScripts print the total size in bytes of a task's compiled test
+// binaries, built the same way validation builds them.
+var binarySizeScripts = map[Language]string{
+	Go:   "go test -c -o /tmp/sanity-size.test . >/dev/null 2>&1 && wc -c < /tmp/sanity-size.test",
+	Rust: `cargo test --no-run --message-format=json 2>/dev/null | grep -o '"executable":"[^"]*"' | cut -d'"' -f4 | xargs cat | wc -c`,
+}
+
+// Benchmark specifies the criterion benchmarks run for a performance task
+// once its solution passes validation. Thresholds map a criterion benchmark
+// ID (e.g. "group/function") to the slowest acceptable mean time in
+// nanoseconds.
+type Benchmark struct {
+	Command    string             `json:"command"              toml:"command"`
+	Args       []string           `json:"args"                 toml:"args"`
+	Timeout    int                `json:"timeout,omitempty"    toml:"timeout,omitempty"`
+	Thresholds map[string]float64 `json:"thresholds,omitempty" toml:"thresholds,omitempty"`
 }
 
 // VisibleFiles returns the files that should be visible to the agent initially.
@@ -93,6 +195,18 @@ func (t *Task) HiddenTestFiles() []string {
 	return t.Files.HiddenTest
 }
 
+// FollowUpTask returns a copy of the task for validating follow-up n, whose
+// hidden tests are those of the task and of follow-ups 0 through n.
+func (t *Task) FollowUpTask(n int) *Task {
+	out := *t
+	hidden := append([]string{}, t.Files.HiddenTest...)
+	for _, f := range t.FollowUps[:n+1] {
+		hidden = append(hidden, f.HiddenTest...)
+	}
+	out.Files.HiddenTest = hidden
+	return &out
+}
+
 // ValidationCommand returns the full command to run for validation.
 func (t *Task) ValidationCommand() []string {
 	cmd := make([]string, 0, 1+len(t.Validation.Args))
@@ -101,6 +215,62 @@ func (t *Task) ValidationCommand() []string {
 	return cmd
 }
 
+// BenchmarkCommand returns the full command to run the task's benchmarks, or
+// nil when the task has none.
+func (t *Task) BenchmarkCommand() []string {
+	if t.Benchmark == nil {
+		return nil
+	}
+	cmd := make([]string, 0, 1+len(t.Benchmark.Args))
+	cmd = append(cmd, t.Benchmark.Command)
+	cmd = append(cmd, t.Benchmark.Args...)
+	return cmd
+}
+
+// BinarySizeCommand returns the command that prints the size of the task's
+// compiled test binaries, or nil for languages where it is not measured.
+func (t *Task) BinarySizeCommand() []string {
+	script, ok := binarySizeScripts[t.Language]
+	if !ok {
+		return nil
+	}
+	return []string{"sh", "-c", script}
+}
+
+// WarmCommand returns a command that compiles the task's dependencies and
+// tests with the same flags as validation, without running any tests, so a
+// build cache it fills leaves validation only the solution to compile. It is
+// nil unless the task validates with "cargo test" or "go test".
+func (t *Task) WarmCommand() []string {
+	cmd := t.ValidationCommand()
+	if len(cmd) < 2 || cmd[1] != "test" {
+		return nil
+	}
+	var extra []string
+	switch {
+	case t.Language == Rust && cmd[0] == "cargo":
+		extra = []string{"--no-run"}
+	case t.Language == Go && cmd[0] == "go":
+		extra = []string{"-run", "^$"}
+	default:
+		return nil
+	}
+	return slices.Concat(cmd[:2], extra, cmd[2:])
+}
+
+// HiddenValidationCommand returns the validation command to run once hidden
+// tests are in the workspace. TypeScript runners are given the hidden test
+// files explicitly; other languages discover them on their own.
+func (t *Task) HiddenValidationCommand() []string {
+	cmd := t.ValidationCommand()
+	if t.Language == TypeScript {
+		for _, filename := range t.HiddenTestFiles() {
+			cmd = append(cmd, StripTxtExtension(filename))
+		}
+	}
+	return cmd
+}
+
 // Validate checks that required task fields are present and valid.
 func (t *Task) Validate() error {
 	if t.Slug == "" {
@@ -142,6 +312,38 @@ func (t *Task) Validate() error {
 	if t.Validation.Command == "" {
 		return errors.New("task validation command is required")
 	}
+	if t.Image != "" && t.Dockerfile != "" {
+		return fmt.Errorf("task %s sets both image and dockerfile", t.Slug)
+	}
+	if t.Dockerfile != "" && !fs.ValidPath(t.Dockerfile) {
+		return fmt.Errorf("task %s dockerfile %q must be a relative path inside the task directory", t.Slug, t.Dockerfile)
+	}
+	if t.Validation.MaxMemoryMB < 0 {
+		return errors.New("task validation max_memory_mb must not be negative")
+	}
+	if t.Validation.MaxBinaryKB < 0 {
+		return errors.New("task validation max_binary_kb must not be negative")
+	}
+	if t.Validation.MaxBinaryKB > 0 && t.BinarySizeCommand() == nil {
+		return fmt.Errorf("task validation max_binary_kb is not supported for %s tasks", t.Language)
+	}
+	if slices.Contains(t.Validation.TimingTests, "") {
+		return errors.New("task validation timing_tests must not contain empty names")
+	}
+	if err := t.Benchmark.validate(); err != nil {
+		return fmt.Errorf("task %s benchmark: %w", t.Slug, err)
+	}
+	if err := t.validateTranslations(); err != nil {
+		return fmt.Errorf("task %s %w", t.Slug, err)
+	}
+	for i, f := range t.FollowUps {
+		if strings.TrimSpace(f.Prompt) == "" {
+			return fmt.Errorf("task %s follow_up %d: prompt is required", t.Slug, i+1)
+		}
+		if len(f.HiddenTest) == 0 {
+			return fmt.Errorf("task %s follow_up %d: hidden_test is required", t.Slug, i+1)
+		}
+	}
 	if len(t.Files.Stub) == 0 {
 		return fmt.Errorf("task %s has no stub files", t.Slug)
 	}
@@ -151,6 +353,36 @@ func (t *Task) Validate() error {
 	return nil
 }
 
+func (t *Task) validateTranslations() error {
+	for lang, tr := range t.Translations {
+		if !translationKeyPattern.MatchString(lang) {
+			return fmt.Errorf("translation %q: language must be a lowercase code such as \"ja\" or \"pt-br\"", lang)
+		}
+		if tr.Description == "" {
+			return fmt.Errorf("translation %q: description is required", lang)
+		}
+	}
+	return nil
+}
+
+func (b *Benchmark) validate() error {
+	if b == nil {
+		return nil
+	}
+	if b.Command == "" {
+		return errors.New("command is required")
+	}
+	if b.Timeout < 0 {
+		return errors.New("timeout must not be negative")
+	}
+	for id, maxNs := range b.Thresholds {
+		if maxNs <= 0 {
+			return fmt.Errorf("threshold for %q must be positive", id)
+		}
+	}
+	return nil
+}
+
 // Loader handles loading tasks from embedded or external sources.
 type Loader struct {
 	embeddedFS  embed.FS
@@ -328,6 +560,14 @@ func (l *Loader) GetTaskDir(task *Task) string {
 	return path.Join(string(task.Language), task.Slug)
 }
 
+// TaskFS returns a task's directory as a filesystem.
+func (l *Loader) TaskFS(task *Task) (fs.FS, error) {
+	if l.externalDir != "" {
+		return os.DirFS(l.GetTaskDir(task)), nil
+	}
+	return fs.Sub(l.embeddedFS, l.GetTaskDir(task))
+}
+
 // ReadTaskFile reads a file from a task's directory.
 func (l *Loader) ReadTaskFile(task *Task, filename string) ([]byte, error) {
 	taskDir := l.GetTaskDir(task)
@@ -1,6 +1,7 @@
 package task
 
 import (
+	"strings"
 	"testing"
 )
 
@@ -254,6 +255,30 @@ func TestTaskHiddenTestFiles(t *testing.T) {
 	}
 }
 
+func TestTaskFollowUpTask(t *testing.T) {
+	t.Parallel()
+
+	task := &Task{
+		Files: TaskFiles{HiddenTest: []string{"hidden_test.go"}},
+		FollowUps: []FollowUp{
+			{Prompt: "Now make it thread-safe.", HiddenTest: []string{"concurrent_test.go"}},
+			{Prompt: "Now add an iterator.", HiddenTest: []string{"iter_test.go"}},
+		},
+	}
+
+	got := strings.Join(task.FollowUpTask(0).HiddenTestFiles(), ",")
+	if got != "hidden_test.go,concurrent_test.go" {
+		t.Fatalf("FollowUpTask(0) hidden tests = %q", got)
+	}
+	got = strings.Join(task.FollowUpTask(1).HiddenTestFiles(), ",")
+	if got != "hidden_test.go,concurrent_test.go,iter_test.go" {
+		t.Fatalf("FollowUpTask(1) hidden tests = %q", got)
+	}
+	if len(task.HiddenTestFiles()) != 1 {
+		t.Fatalf("FollowUpTask modified the task: %v", task.HiddenTestFiles())
+	}
+}
+
 func TestTaskValidationCommand(t *testing.T) {
 	t.Parallel()
 
@@ -276,6 +301,72 @@ func TestTaskValidationCommand(t *testing.T) {
 	}
 }
 
+func TestTaskTimingFailure(t *testing.T) {
+	t.Parallel()
+
+	goTask := &Task{Validation: Validation{TimingTests: []string{"TestIsConcurrent"}}}
+	rustTask := &Task{Validation: Validation{TimingTests: []string{"uses_multiple_threads"}}}
+	tests := []struct {
+		name   string
+		task   *Task
+		output string
+		want   bool
+	}{
+		{"go timing test failed", goTask, "=== RUN   TestIsConcurrent\n--- FAIL: TestIsConcurrent (0.50s)\nFAIL\n", true},
+		{"go timing test passed", goTask, "--- PASS: TestIsConcurrent (0.01s)\n--- FAIL: TestCount (0.00s)\n", false},
+		{"go longer test name", goTask, "--- FAIL: TestIsConcurrentlySafe (0.00s)\n", false},
+		{"rust timing test failed", rustTask, "test hidden::uses_multiple_threads ... FAILED\n", true},
+		{"no timing tests", &Task{}, "--- FAIL: TestIsConcurrent (0.50s)\n", false},
+	}
+	for _, tc := range tests {
+		if got := tc.task.TimingFailure(tc.output); got != tc.want {
+			t.Errorf("%s: TimingFailure() = %v, want %v", tc.name, got, tc.want)
+		}
+	}
+}
+
+func TestTaskHiddenValidationCommand(t *testing.T) {
+	t.Parallel()
+
+	ts := &Task{
+		Language:   TypeScript,
+		Files:      TaskFiles{HiddenTest: []string{"glob.hidden.test.ts.txt"}},
+		Validation: Validation{Command: "bun", Args: []string{"test"}},
+	}
+	got := strings.Join(ts.HiddenValidationCommand(), " ")
+	if got != "bun test glob.hidden.test.ts" {
+		t.Fatalf("HiddenValidationCommand() = %q, want %q", got, "bun test glob.hidden.test.ts")
+	}
+
+	goTask := &Task{
+		Language:   Go,
+		Files:      TaskFiles{HiddenTest: []string{"hidden_test.go"}},
+		Validation: Validation{Command: "go", Args: []string{"test", "./..."}},
+	}
+	if got := strings.Join(goTask.HiddenValidationCommand(), " "); got != "go test ./..." {
+		t.Fatalf("HiddenValidationCommand() = %q, want %q", got, "go test ./...")
+	}
+}
+
+func TestTaskWarmCommand(t *testing.T) {
+	t.Parallel()
+
+	tests := []struct {
+		task *Task
+		want string
+	}{
+		{&Task{Language: Rust, Validation: Validation{Command: "cargo", Args: []string{"test"}}}, "cargo test --no-run"},
+		{&Task{Language: Go, Validation: Validation{Command: "go", Args: []string{"test", "-count=1", "-race", "./..."}}}, "go test -run ^$ -count=1 -race ./..."},
+		{&Task{Language: TypeScript, Validation: Validation{Command: "bun", Args: []string{"test"}}}, ""},
+		{&Task{Language: Go, Validation: Validation{Command: "make", Args: []string{"test"}}}, ""},
+	}
+	for _, tt := range tests {
+		if got := strings.Join(tt.task.WarmCommand(), " "); got != tt.want {
+			t.Errorf("WarmCommand() = %q, want %q", got, tt.want)
+		}
+	}
+}
+
 func TestTaskValidate(t *testing.T) {
 	t.Parallel()
 
@@ -357,6 +448,145 @@ func TestTaskValidate(t *testing.T) {
 			},
 			wantErr: true,
 		},
+		{
+			name: "benchmark without command",
+			task: Task{
+				Slug:     "test",
+				Language: Rust,
+				Files: TaskFiles{
+					Stub: []string{"src/lib.rs"},
+					Test: []string{"tests/lib.rs"},
+				},
+				Validation: Validation{Command: "cargo", Args: []string{"test"}},
+				Benchmark:  &Benchmark{Thresholds: map[string]float64{"push": 1000}},
+			},
+			wantErr: true,
+		},
+		{
+			name: "benchmark with non-positive threshold",
+			task: Task{
+				Slug:     "test",
+				Language: Rust,
+				Files: TaskFiles{
+					Stub: []string{"src/lib.rs"},
+					Test: []string{"tests/lib.rs"},
+				},
+				Validation: Validation{Command: "cargo", Args: []string{"test"}},
+				Benchmark:  &Benchmark{Command: "cargo", Args: []string{"bench"}, Thresholds: map[string]float64{"push": 0}},
+			},
+			wantErr: true,
+		},
+		{
+			name: "binary size limit for uncompiled language",
+			task: Task{
+				Slug:     "test",
+				Language: TypeScript,
+				Files: TaskFiles{
+					Stub: []string{"index.ts"},
+					Test: []string{"index.test.ts"},
+				},
+				Validation: Validation{Command: "bun", Args: []string{"test"}, MaxBinaryKB: 512},
+			},
+			wantErr: true,
+		},
+		{
+			name: "empty timing test name",
+			task: Task{
+				Slug:     "test",
+				Language: Go,
+				Files: TaskFiles{
+					Stub: []string{"main.go"},
+					Test: []string{"main_test.go"},
+				},
+				Validation: Validation{Command: "go", TimingTests: []string{""}},
+			},
+			wantErr: true,
+		},
+		{
+			name: "translation without description",
+			task: Task{
+				Slug:     "test",
+				Language: Go,
+				Files: TaskFiles{
+					Stub: []string{"main.go"},
+					Test: []string{"main_test.go"},
+				},
+				Validation:   Validation{Command: "go"},
+				Translations: map[string]Translation{"ja": {Name: "テスト"}},
+			},
+			wantErr: true,
+		},
+		{
+			name: "follow-up without hidden tests",
+			task: Task{
+				Slug:     "test",
+				Language: Go,
+				Files: TaskFiles{
+					Stub: []string{"main.go"},
+					Test: []string{"main_test.go"},
+				},
+				Validation: Validation{Command: "go"},
+				FollowUps:  []FollowUp{{Prompt: "Now make it thread-safe."}},
+			},
+			wantErr: true,
+		},
+		{
+			name: "translation with uppercase language code",
+			task: Task{
+				Slug:     "test",
+				Language: Go,
+				Files: TaskFiles{
+					Stub: []string{"main.go"},
+					Test: []string{"main_test.go"},
+				},
+				Validation:   Validation{Command: "go"},
+				Translations: map[string]Translation{"pt-BR": {Description: "Implemente a tarefa"}},
+			},
+			wantErr: true,
+		},
+		{
+			name: "dockerfile",
+			task: Task{
+				Slug:     "test",
+				Language: Rust,
+				Files: TaskFiles{
+					Stub: []string{"src/lib.rs"},
+					Test: []string{"tests/lib.rs"},
+				},
+				Validation: Validation{Command: "cargo"},
+				Dockerfile: "env/Dockerfile",
+			},
+			wantErr: false,
+		},
+		{
+			name: "both image and dockerfile",
+			task: Task{
+				Slug:     "test",
+				Language: Rust,
+				Files: TaskFiles{
+					Stub: []string{"src/lib.rs"},
+					Test: []string{"tests/lib.rs"},
+				},
+				Validation: Validation{Command: "cargo"},
+				Image:      "rustlang/rust:nightly",
+				Dockerfile: "env/Dockerfile",
+			},
+			wantErr: true,
+		},
+		{
+			name: "dockerfile outside the task directory",
+			task: Task{
+				Slug:     "test",
+				Language: Rust,
+				Files: TaskFiles{
+					Stub: []string{"src/lib.rs"},
+					Test: []string{"tests/lib.rs"},
+				},
+				Validation: Validation{Command: "cargo"},
+				Dockerfile: "../shared/Dockerfile",
+			},
+			wantErr: true,
+		},
 	}
 
 	for _, tc := range tests {
@@ -372,3 +602,35 @@ func TestTaskValidate(t *testing.T) {
 		})
 	}
 }
+
+func TestTaskLocalized(t *testing.T) {
+	t.Parallel()
+
+	tt := &Task{
+		Slug:        "bank-account",
+		Name:        "Bank Account",
+		Description: "Implement a bank account",
+		Translations: map[string]Translation{
+			"ja": {Name: "銀行口座", Description: "銀行口座を実装してください"},
+			"de": {Description: "Implementiere ein Bankkonto"},
+		},
+	}
+
+	ja, ok := tt.Localized("JA")
+	if !ok || ja.Name != "銀行口座" || ja.Description != "銀行口座を実装してください" {
+		t.Fatalf("Localized(ja) = %q, %q, %v", ja.Name, ja.Description, ok)
+	}
+	if tt.Description != "Implement a bank account" {
+		t.Fatalf("Localized modified the original task: %q", tt.Description)
+	}
+	de, ok := tt.Localized("de")
+	if !ok || de.Name != "Bank Account" || de.Description != "Implementiere ein Bankkonto" {
+		t.Fatalf("Localized(de) = %q, %q, %v", de.Name, de.Description, ok)
+	}
+	if en, ok := tt.Localized("en"); !ok || en != tt {
+		t.Fatal("Localized(en) should return the task itself")
+	}
+	if _, ok := tt.Localized("pt"); ok {
+		t.Fatal("Localized(pt) should report a missing translation")
+	}
+}
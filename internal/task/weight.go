@@ -80,7 +80,12 @@ type Weight struct {
 //	     + novel_problem * 0.2
 //	weight = min(base, 1.5)  // capped at 1.5
 func ComputeWeight(t *Task) Weight {
-	taskID := t.ID()
+	return ComputeWeightForID(t.ID())
+}
+
+// ComputeWeightForID calculates the weight of the task with the given
+// "language/slug" ID, as ComputeWeight does.
+func ComputeWeightForID(taskID string) Weight {
 	diff, ok := taskDifficulties[taskID]
 	if !ok {
 		// Unknown task gets baseline weight
@@ -115,6 +120,7 @@ type ResultStatus string
 const (
 	StatusPass               ResultStatus = "pass"
 	StatusPartialPass        ResultStatus = "partial_pass"
+	StatusFlakyPass          ResultStatus = "flaky_pass" // Passed after rerunning failed timing tests
 	StatusFail               ResultStatus = "fail"
 	StatusIntegrityViolation ResultStatus = "integrity_violation"
 	StatusError              ResultStatus = "error"
@@ -0,0 +1,32 @@
+// Package sanity is the embeddable API of SanityHarness.
+//
+// It exposes the pieces the sanity CLI is built from so other Go programs can
+// run evaluations in-process instead of shelling out and parsing terminal
+// output:
+//
+//   - [TaskRegistry] loads and filters the built-in (or an external) task set.
+//   - [Runner] prepares task workspaces and validates them in the task's
+//     language container. [Runner.Evaluate] runs a caller-supplied [Solver]
+//     in a fresh workspace and then validates and scores the result.
+//   - [Session] and [Attempt] describe a validation run.
+//   - [Scorer] computes task weights, per-task scores, and run summaries using
+//     the same methodology as sanity eval.
+//
+// A minimal evaluation of one task:
+//
+//	cfg, _ := sanity.LoadConfig("")
+//	reg := sanity.NewTaskRegistry("")
+//	t, _ := reg.Resolve("go/bank-account")
+//
+//	r, err := sanity.NewRunner(cfg, reg, nil)
+//	if err != nil {
+//		return err
+//	}
+//	defer r.Close()
+//
+//	res, err := r.Evaluate(ctx, t, func(ctx context.Context, workspace string) error {
+//		return runMyAgent(ctx, t.Description, workspace)
+//	})
+//
+// Docker is required for validation; loading tasks and scoring are pure Go.
+package sanity
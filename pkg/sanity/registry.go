@@ -0,0 +1,93 @@
+package sanity
+
+import (
+	"fmt"
+	"slices"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// Task is a single evaluation task.
+type Task = task.Task
+
+// Language is a supported task language.
+type Language = task.Language
+
+// Supported languages.
+const (
+	Go         = task.Go
+	Rust       = task.Rust
+	TypeScript = task.TypeScript
+	Kotlin     = task.Kotlin
+	Dart       = task.Dart
+	Zig        = task.Zig
+)
+
+// TaskRegistry loads tasks from the embedded task set or an external directory.
+type TaskRegistry struct {
+	loader *task.Loader
+	dir    string
+}
+
+// TaskFilter selects tasks. Empty fields match everything.
+type TaskFilter struct {
+	Languages    []Language
+	Tiers        []string
+	Difficulties []string
+	Tags         []string
+}
+
+// NewTaskRegistry returns a registry for the embedded tasks, or for the tasks
+// under dir when it is non-empty.
+func NewTaskRegistry(dir string) *TaskRegistry {
+	return &TaskRegistry{loader: task.NewLoader(tasks.FS, dir), dir: dir}
+}
+
+// All returns every task.
+func (r *TaskRegistry) All() ([]*Task, error) {
+	return r.loader.LoadAll()
+}
+
+// Resolve finds a task by canonical ID ("go/bank-account") or unambiguous slug.
+func (r *TaskRegistry) Resolve(ref string) (*Task, error) {
+	all, err := r.All()
+	if err != nil {
+		return nil, err
+	}
+	return task.ResolveRef(all, ref)
+}
+
+// Filter returns the tasks matching f. A task matches Tags if it has any of them.
+func (r *TaskRegistry) Filter(f TaskFilter) ([]*Task, error) {
+	all, err := r.All()
+	if err != nil {
+		return nil, err
+	}
+	out := make([]*Task, 0, len(all))
+	for _, t := range all {
+		if len(f.Languages) > 0 && !slices.Contains(f.Languages, t.Language) {
+			continue
+		}
+		if len(f.Tiers) > 0 && !slices.Contains(f.Tiers, t.Tier) {
+			continue
+		}
+		if len(f.Difficulties) > 0 && !slices.Contains(f.Difficulties, t.Difficulty) {
+			continue
+		}
+		if len(f.Tags) > 0 && !slices.ContainsFunc(f.Tags, func(tag string) bool { return slices.Contains(t.Tags, tag) }) {
+			continue
+		}
+		out = append(out, t)
+	}
+	return out, nil
+}
+
+// ReadFile returns the contents of one of t's files, including hidden tests.
+func (r *TaskRegistry) ReadFile(t *Task, name string) ([]byte, error) {
+	data, err := r.loader.ReadTaskFile(t, name)
+	if err != nil {
+		return nil, fmt.Errorf("reading %s for %s: %w", name, t.ID(), err)
+	}
+	return data, nil
+}
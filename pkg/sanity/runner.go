@@ -0,0 +1,158 @@
+package sanity
+
+import (
+	"context"
+	"fmt"
+	"log/slog"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/config"
+	"github.com/lemon07r/sanityharness/internal/result"
+	"github.com/lemon07r/sanityharness/internal/runner"
+	"github.com/lemon07r/sanityharness/internal/task"
+	"github.com/lemon07r/sanityharness/tasks"
+)
+
+// Config is the harness configuration (sanity.toml).
+type Config = config.Config
+
+// Session is the outcome of validating one workspace.
+type Session = result.Session
+
+// Attempt is a single validation attempt within a Session.
+type Attempt = result.Attempt
+
+// LoadConfig loads configuration from path over the defaults. When path is
+// empty, it merges the system, user, and project config files that exist.
+func LoadConfig(path string) (*Config, error) {
+	return config.Load(path)
+}
+
+// DefaultConfig returns a copy of the built-in default configuration.
+func DefaultConfig() *Config {
+	cfg := config.Default
+	return &cfg
+}
+
+// Runner prepares and validates task workspaces in Docker containers.
+type Runner struct {
+	r        *runner.Runner
+	registry *TaskRegistry
+	cfg      *Config
+}
+
+// NewRunner connects to Docker and returns a Runner for the tasks in registry.
+// A nil logger discards log output.
+func NewRunner(cfg *Config, registry *TaskRegistry, logger *slog.Logger) (*Runner, error) {
+	if cfg == nil {
+		cfg = DefaultConfig()
+	}
+	if registry == nil {
+		registry = NewTaskRegistry("")
+	}
+	if logger == nil {
+		logger = slog.New(slog.DiscardHandler)
+	}
+	r, err := runner.NewRunner(cfg, tasks.FS, registry.dir, logger)
+	if err != nil {
+		return nil, err
+	}
+	return &Runner{r: r, registry: registry, cfg: cfg}, nil
+}
+
+// Close releases the Docker client.
+func (r *Runner) Close() error {
+	return r.r.Close()
+}
+
+// InitWorkspace writes t's visible files (stubs, tests, support files) into
+// dir, which must be empty or not exist. Hidden tests are not included.
+func (r *Runner) InitWorkspace(t *Task, dir string) error {
+	return r.r.InitWorkspaceForTask(t, dir)
+}
+
+// ValidateOptions configures a validation run.
+type ValidateOptions struct {
+	// WorkspaceDir holds the solution to validate.
+	WorkspaceDir string
+	// Timeout is the validation timeout in seconds (default: task or config timeout).
+	Timeout int
+	// OutputDir is where session results are saved (default: the config's session dir).
+	OutputDir string
+	// IncludeHiddenTests overlays t's hidden tests onto the workspace first,
+	// as sanity eval does.
+	IncludeHiddenTests bool
+}
+
+// Validate runs t's validation command once against opts.WorkspaceDir.
+func (r *Runner) Validate(ctx context.Context, t *Task, opts ValidateOptions) (*Session, error) {
+	validationCmd := t.ValidationCommand()
+	if opts.IncludeHiddenTests {
+		for _, name := range t.HiddenTestFiles() {
+			data, err := r.registry.ReadFile(t, name)
+			if err != nil {
+				return nil, err
+			}
+			dest := filepath.Join(opts.WorkspaceDir, task.StripTxtExtension(name))
+			if err := os.MkdirAll(filepath.Dir(dest), 0o755); err != nil {
+				return nil, fmt.Errorf("creating directory for %s: %w", name, err)
+			}
+			if err := os.WriteFile(dest, data, 0o644); err != nil {
+				return nil, fmt.Errorf("writing hidden test %s: %w", name, err)
+			}
+		}
+		validationCmd = t.HiddenValidationCommand()
+	}
+	return r.r.Run(ctx, runner.RunOptions{
+		Task:              t,
+		WorkspaceDir:      opts.WorkspaceDir,
+		Timeout:           opts.Timeout,
+		MaxAttempts:       1,
+		OutputDir:         opts.OutputDir,
+		ValidationCommand: validationCmd,
+	})
+}
+
+// Solver produces a solution in workspace, typically by running a coding agent.
+type Solver func(ctx context.Context, workspace string) error
+
+// Evaluate runs solve in a fresh temporary workspace for t, then validates it
+// with hidden tests and scores the outcome. A Solver error is recorded in the
+// result rather than returned; the returned error covers harness failures.
+func (r *Runner) Evaluate(ctx context.Context, t *Task, solve Solver) (*TaskResult, error) {
+	start := time.Now()
+	root, err := os.MkdirTemp("", fmt.Sprintf("sanity-%s-%s-*", t.Language, t.Slug))
+	if err != nil {
+		return nil, fmt.Errorf("creating workspace: %w", err)
+	}
+	defer func() { _ = os.RemoveAll(root) }()
+	workspace := filepath.Join(root, "workspace")
+
+	if err := r.InitWorkspace(t, workspace); err != nil {
+		return nil, err
+	}
+
+	res := &TaskResult{Task: t.ID(), Language: t.Language, Tier: t.Tier, Difficulty: t.Difficulty}
+	if err := solve(ctx, workspace); err != nil {
+		res.Error = err.Error()
+	}
+
+	if res.Error == "" {
+		session, err := r.Validate(ctx, t, ValidateOptions{
+			WorkspaceDir:       workspace,
+			OutputDir:          filepath.Join(root, "sessions"),
+			IncludeHiddenTests: true,
+		})
+		if err != nil {
+			return nil, fmt.Errorf("validating %s: %w", t.ID(), err)
+		}
+		res.Session = session
+		res.Passed = session.Passed()
+	}
+
+	res.Duration = time.Since(start)
+	Scorer{}.Apply(t, res)
+	return res, nil
+}
@@ -0,0 +1,75 @@
+package sanity
+
+import (
+	"math"
+	"testing"
+)
+
+func TestTaskRegistryFilter(t *testing.T) {
+	t.Parallel()
+
+	reg := NewTaskRegistry("")
+	all, err := reg.All()
+	if err != nil {
+		t.Fatalf("All() error = %v", err)
+	}
+
+	goTasks, err := reg.Filter(TaskFilter{Languages: []Language{Go}})
+	if err != nil {
+		t.Fatalf("Filter() error = %v", err)
+	}
+	if len(goTasks) == 0 || len(goTasks) >= len(all) {
+		t.Fatalf("expected Go tasks (%d) to be a non-empty subset of all (%d)", len(goTasks), len(all))
+	}
+	for _, tk := range goTasks {
+		if tk.Language != Go {
+			t.Fatalf("Filter() returned %s", tk.ID())
+		}
+	}
+
+	tk, err := reg.Resolve("go/bank-account")
+	if err != nil {
+		t.Fatalf("Resolve() error = %v", err)
+	}
+	if _, err := reg.ReadFile(tk, tk.Files.Stub[0]); err != nil {
+		t.Fatalf("ReadFile() error = %v", err)
+	}
+}
+
+func TestScorerSummarize(t *testing.T) {
+	t.Parallel()
+
+	reg := NewTaskRegistry("")
+	easy, err := reg.Resolve("go/bank-account")
+	if err != nil {
+		t.Fatalf("Resolve() error = %v", err)
+	}
+	hard, err := reg.Resolve("zig/comptime-json")
+	if err != nil {
+		t.Fatalf("Resolve() error = %v", err)
+	}
+
+	var s Scorer
+	if s.Weight(hard).Base <= s.Weight(easy).Base {
+		t.Fatalf("expected zig/comptime-json to outweigh go/bank-account")
+	}
+
+	results := []TaskResult{{Task: easy.ID(), Passed: true}, {Task: hard.ID()}}
+	s.Apply(easy, &results[0])
+	s.Apply(hard, &results[1])
+	sum := s.Summarize(results)
+
+	if sum.Total != 2 || sum.Passed != 1 || sum.PassRate != 50 {
+		t.Fatalf("unexpected counts: %+v", sum)
+	}
+	wantRate := s.Weight(easy).Base / (s.Weight(easy).Base + s.Weight(hard).Base) * 100
+	if math.Abs(sum.WeightedPassRate-wantRate) > 1e-9 {
+		t.Fatalf("WeightedPassRate = %v, want %v", sum.WeightedPassRate, wantRate)
+	}
+
+	violation := TaskResult{Error: "modified task files"}
+	s.Apply(easy, &violation)
+	if violation.Score >= 0 {
+		t.Fatalf("expected integrity violation penalty, got %v", violation.Score)
+	}
+}
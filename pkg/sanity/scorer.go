@@ -0,0 +1,79 @@
+package sanity
+
+import (
+	"time"
+
+	"github.com/lemon07r/sanityharness/internal/task"
+)
+
+// WeightVersion identifies the scoring methodology.
+const WeightVersion = task.WeightVersion
+
+// Weight is a task's difficulty weight and the factors behind it.
+type Weight = task.Weight
+
+// TaskResult is the scored outcome of one task.
+type TaskResult struct {
+	Task          string        `json:"task"`
+	Language      Language      `json:"language"`
+	Tier          string        `json:"tier,omitempty"`
+	Difficulty    string        `json:"difficulty,omitempty"`
+	Passed        bool          `json:"passed"`
+	AgentTimedOut bool          `json:"agent_timed_out,omitempty"`
+	Error         string        `json:"error,omitempty"`
+	Duration      time.Duration `json:"duration_ns"`
+	Weight        float64       `json:"weight"`
+	Score         float64       `json:"weighted_score"`
+	Session       *Session      `json:"session,omitempty"`
+}
+
+// Summary aggregates TaskResults the way sanity eval's summary does.
+type Summary struct {
+	Total            int     `json:"total"`
+	Passed           int     `json:"passed"`
+	PassRate         float64 `json:"pass_rate"`
+	WeightedScore    float64 `json:"weighted_score"`
+	MaxPossibleScore float64 `json:"max_possible_score"`
+	WeightedPassRate float64 `json:"weighted_pass_rate"`
+}
+
+// Scorer applies the harness's weighting and scoring rules.
+type Scorer struct{}
+
+// Weight returns t's difficulty weight.
+func (Scorer) Weight(t *Task) Weight {
+	return task.ComputeWeight(t)
+}
+
+// Score returns the weighted score for an outcome: the full weight for a pass
+// (including a pass after agent timeout), a penalty for integrity violations,
+// and zero otherwise.
+func (s Scorer) Score(t *Task, passed, agentTimedOut bool, errMsg string) float64 {
+	return task.ScoreResult(passed, agentTimedOut, errMsg, s.Weight(t))
+}
+
+// Apply fills in res.Weight and res.Score for t.
+func (s Scorer) Apply(t *Task, res *TaskResult) {
+	res.Weight = s.Weight(t).Base
+	res.Score = s.Score(t, res.Passed, res.AgentTimedOut, res.Error)
+}
+
+// Summarize aggregates scored results. Rates are percentages.
+func (Scorer) Summarize(results []TaskResult) Summary {
+	var sum Summary
+	for _, r := range results {
+		sum.Total++
+		if r.Passed {
+			sum.Passed++
+		}
+		sum.WeightedScore += r.Score
+		sum.MaxPossibleScore += r.Weight
+	}
+	if sum.Total > 0 {
+		sum.PassRate = float64(sum.Passed) / float64(sum.Total) * 100
+	}
+	if sum.MaxPossibleScore > 0 {
+		sum.WeightedPassRate = sum.WeightedScore / sum.MaxPossibleScore * 100
+	}
+	return sum
+}
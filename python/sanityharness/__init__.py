@@ -0,0 +1,9 @@
+"""Python client for the SanityHarness CLI.
+
+The harness is a Go program; this package runs the ``sanity`` binary and
+decodes its JSON output into plain dataclasses.
+"""
+
+from .client import EvalConfig, EvalSummary, Harness, HarnessError, Task, TaskResult
+
+__all__ = ["EvalConfig", "EvalSummary", "Harness", "HarnessError", "Task", "TaskResult"]
@@ -0,0 +1,263 @@
+"""Subprocess client for the sanity CLI."""
+
+from __future__ import annotations
+
+import json
+import os
+import shutil
+import subprocess
+import tempfile
+from dataclasses import dataclass, field, replace
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Sequence, Union
+
+PathLike = Union[str, "os.PathLike[str]"]
+
+
+class HarnessError(RuntimeError):
+    """Raised when the sanity binary fails or produces unexpected output."""
+
+    def __init__(self, message: str, returncode: Optional[int] = None, stderr: str = ""):
+        super().__init__(message)
+        self.returncode = returncode
+        self.stderr = stderr
+
+
+@dataclass
+class Task:
+    """A benchmark task as reported by ``sanity list --json``."""
+
+    slug: str
+    language: str
+    name: str = ""
+    tier: str = ""
+    difficulty: str = ""
+    description: str = ""
+    tags: List[str] = field(default_factory=list)
+    raw: Dict[str, Any] = field(default_factory=dict, repr=False)
+
+    @property
+    def id(self) -> str:
+        return f"{self.language}/{self.slug}"
+
+    @classmethod
+    def from_json(cls, data: Dict[str, Any]) -> "Task":
+        return cls(
+            slug=data.get("slug", ""),
+            language=data.get("language", ""),
+            name=data.get("name", ""),
+            tier=data.get("tier", ""),
+            difficulty=data.get("difficulty", ""),
+            description=data.get("description", ""),
+            tags=list(data.get("tags") or []),
+            raw=data,
+        )
+
+
+@dataclass
+class TaskResult:
+    """One task's outcome from an eval summary."""
+
+    task: str
+    language: str
+    passed: bool
+    status: str = ""
+    agent_timed_out: bool = False
+    attempts: int = 0
+    duration_seconds: float = 0.0
+    weight: float = 0.0
+    weighted_score: float = 0.0
+    error: str = ""
+    raw: Dict[str, Any] = field(default_factory=dict, repr=False)
+
+    @classmethod
+    def from_json(cls, data: Dict[str, Any]) -> "TaskResult":
+        return cls(
+            task=data.get("task", ""),
+            language=data.get("language", ""),
+            passed=bool(data.get("passed", False)),
+            status=data.get("status", ""),
+            agent_timed_out=bool(data.get("agent_timed_out", False)),
+            attempts=int(data.get("attempts", 0)),
+            duration_seconds=float(data.get("duration_seconds", 0.0)),
+            weight=float(data.get("weight", 0.0)),
+            weighted_score=float(data.get("weighted_score", 0.0)),
+            error=data.get("error", ""),
+            raw=data,
+        )
+
+
+@dataclass
+class EvalSummary:
+    """The decoded ``summary.json`` of an eval run."""
+
+    agent: str
+    model: str
+    timestamp: str
+    passed: int
+    failed: int
+    total: int
+    pass_rate: float
+    weighted_score: float
+    max_possible_score: float
+    weighted_pass_rate: float
+    results: List[TaskResult]
+    output_dir: Path
+    raw: Dict[str, Any] = field(default_factory=dict, repr=False)
+
+    @classmethod
+    def from_json(cls, data: Dict[str, Any], output_dir: Path) -> "EvalSummary":
+        return cls(
+            agent=data.get("agent", ""),
+            model=data.get("model", ""),
+            timestamp=data.get("timestamp", ""),
+            passed=int(data.get("passed", 0)),
+            failed=int(data.get("failed", 0)),
+            total=int(data.get("total", 0)),
+            pass_rate=float(data.get("pass_rate", 0.0)),
+            weighted_score=float(data.get("weighted_score", 0.0)),
+            max_possible_score=float(data.get("max_possible_score", 0.0)),
+            weighted_pass_rate=float(data.get("weighted_pass_rate", 0.0)),
+            results=[TaskResult.from_json(r) for r in data.get("results") or []],
+            output_dir=output_dir,
+            raw=data,
+        )
+
+
+@dataclass
+class EvalConfig:
+    """Options for :meth:`Harness.evaluate`, mirroring ``sanity eval`` flags."""
+
+    agent: str
+    model: str = ""
+    reasoning: str = ""
+    tasks: Sequence[str] = ()
+    lang: str = ""
+    tier: str = ""
+    difficulty: str = ""
+    timeout: int = 0
+    parallel: int = 1
+    output: Optional[PathLike] = None
+    keep_workspaces: bool = False
+    use_mcp_tools: bool = False
+    use_skills: bool = False
+    no_sandbox: bool = False
+    extra_args: Sequence[str] = ()
+
+    def to_args(self) -> List[str]:
+        args = ["eval", "--agent", self.agent, "--no-progress"]
+        for flag, value in (
+            ("--model", self.model),
+            ("--reasoning", self.reasoning),
+            ("--tasks", ",".join(self.tasks)),
+            ("--lang", self.lang),
+            ("--tier", self.tier),
+            ("--difficulty", self.difficulty),
+        ):
+            if value:
+                args += [flag, value]
+        if self.timeout:
+            args += ["--timeout", str(self.timeout)]
+        if self.parallel > 1:
+            args += ["--parallel", str(self.parallel)]
+        if self.output is not None:
+            args += ["--output", os.fspath(self.output)]
+        for flag, enabled in (
+            ("--keep-workspaces", self.keep_workspaces),
+            ("--use-mcp-tools", self.use_mcp_tools),
+            ("--use-skills", self.use_skills),
+            ("--no-sandbox", self.no_sandbox),
+        ):
+            if enabled:
+                args.append(flag)
+        args += list(self.extra_args)
+        return args
+
+
+class Harness:
+    """Runs the ``sanity`` binary.
+
+    ``config`` and ``tasks_dir`` map to the global ``--config`` and
+    ``--tasks-dir`` flags.
+    """
+
+    def __init__(
+        self,
+        binary: str = "sanity",
+        config: Optional[PathLike] = None,
+        tasks_dir: Optional[PathLike] = None,
+        cwd: Optional[PathLike] = None,
+    ):
+        resolved = shutil.which(binary)
+        if resolved is None:
+            raise HarnessError(f"sanity binary not found: {binary}")
+        self.binary = resolved
+        self.config = config
+        self.tasks_dir = tasks_dir
+        self.cwd = cwd
+
+    def _global_args(self) -> List[str]:
+        # Keep task text verbatim; the non-terminal default theme rewrites symbols.
+        args = ["--theme", "unicode", "--no-color"]
+        if self.config is not None:
+            args += ["--config", os.fspath(self.config)]
+        if self.tasks_dir is not None:
+            args += ["--tasks-dir", os.fspath(self.tasks_dir)]
+        return args
+
+    def run(self, *args: str, capture: bool = True) -> subprocess.CompletedProcess:
+        """Runs ``sanity <args>`` and raises HarnessError on failure."""
+        cmd = [self.binary, *self._global_args(), *args]
+        env = dict(os.environ, SANITY_NO_UPDATE_CHECK="1")
+        proc = subprocess.run(cmd, cwd=self.cwd, env=env, capture_output=capture, text=True)
+        if proc.returncode != 0:
+            stderr = proc.stderr or ""
+            raise HarnessError(
+                f"{' '.join(args[:1])} exited with status {proc.returncode}: {stderr.strip()}",
+                returncode=proc.returncode,
+                stderr=stderr,
+            )
+        return proc
+
+    def version(self) -> str:
+        return self.run("version").stdout.strip()
+
+    def list_tasks(
+        self, language: str = "", tier: str = "", difficulty: str = ""
+    ) -> List[Task]:
+        args = ["list", "--json"]
+        if language:
+            args += ["--language", language]
+        if tier:
+            args += ["--tier", tier]
+        if difficulty:
+            args += ["--difficulty", difficulty]
+        out = self.run(*args).stdout
+        try:
+            data = json.loads(out) or []
+        except json.JSONDecodeError as exc:
+            raise HarnessError(f"parsing task list: {exc}") from exc
+        return [Task.from_json(t) for t in data]
+
+    def evaluate(self, config: EvalConfig, capture: bool = True) -> EvalSummary:
+        """Runs ``sanity eval`` and returns its summary.
+
+        When ``config.output`` is unset, results go to a new temporary
+        directory that the caller owns. Set ``capture=False`` to stream the
+        harness's own output to the terminal.
+        """
+        if config.output is None:
+            config = replace(config, output=tempfile.mkdtemp(prefix="sanity-eval-"))
+        self.run(*config.to_args(), capture=capture)
+        return self.load_summary(config.output)
+
+    @staticmethod
+    def load_summary(output_dir: PathLike) -> EvalSummary:
+        """Reads ``summary.json`` from an eval results directory."""
+        path = Path(output_dir) / "summary.json"
+        try:
+            data = json.loads(path.read_text())
+        except (OSError, json.JSONDecodeError) as exc:
+            raise HarnessError(f"reading {path}: {exc}") from exc
+        return EvalSummary.from_json(data, Path(output_dir))
+